@@ -0,0 +1,136 @@
+//! Splits Chromium browsers into one pseudo-app per profile, so work and personal
+//! browsing can be categorized apart. Profile names come from the browser's
+//! `Local State` file.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use limetrace_core::apps::{browser_profile_app, browser_profile_in_title, normalize_app_key};
+
+use crate::monitor::{ActivityKind, ActivitySample};
+
+// Profiles are added and renamed rarely.
+const REFRESH_EVERY: Duration = Duration::from_secs(60);
+
+// App key, then the default user data folder under %LOCALAPPDATA% and ~/.config.
+const USER_DATA_DIRS: [(&str, &str, &str); 5] = [
+    ("chrome", r"Google\Chrome\User Data", "google-chrome"),
+    ("msedge", r"Microsoft\Edge\User Data", "microsoft-edge"),
+    ("brave", r"BraveSoftware\Brave-Browser\User Data", "BraveSoftware/Brave-Browser"),
+    ("vivaldi", r"Vivaldi\User Data", "vivaldi"),
+    ("chromium", r"Chromium\User Data", "chromium"),
+];
+
+struct Profiles {
+    read_at: Instant,
+    // Profile folder (`Default`, `Profile 1`) and the name the user gave it.
+    by_dir: Vec<(String, String)>,
+    names: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct BrowserProfiles {
+    by_data_dir: HashMap<PathBuf, Profiles>,
+}
+
+impl BrowserProfiles {
+    /// Renames a browser sample to its profile's pseudo-app, e.g. `chrome (Work)`.
+    /// Browsers with a single profile, and windows whose profile can't be told,
+    /// are left alone.
+    pub fn split(&mut self, sample: &mut ActivitySample) {
+        let ActivityKind::Active(window) = &mut sample.kind else {
+            return;
+        };
+        let app_key = normalize_app_key(&window.exe_name);
+        let Some(&(_, windows_dir, unix_dir)) = USER_DATA_DIRS.iter().find(|(key, ..)| *key == app_key) else {
+            return;
+        };
+        let args = window.process_args.as_deref().unwrap_or("");
+        let Some(data_dir) = command_line_flag(args, "--user-data-dir")
+            .map(PathBuf::from)
+            .or_else(|| default_user_data_dir(windows_dir, unix_dir))
+        else {
+            return;
+        };
+        let profiles = self.profiles(data_dir);
+        if profiles.names.len() < 2 {
+            return;
+        }
+        let profile = browser_profile_in_title(&window.window_title, &profiles.names)
+            .map(str::to_owned)
+            .or_else(|| {
+                let dir = command_line_flag(args, "--profile-directory")?;
+                profiles.by_dir.iter().find(|(folder, _)| *folder == dir).map(|(_, name)| name.clone())
+            });
+        let Some(profile) = profile else {
+            return;
+        };
+        // The executable's own description would merge the profiles again in
+        // summaries, and so would a shared product identity.
+        window.process_path = format!("<{app_key}:{profile}>");
+        window.exe_name = browser_profile_app(&window.exe_name, &profile);
+        window.identity = None;
+    }
+
+    fn profiles(&mut self, data_dir: PathBuf) -> &Profiles {
+        let stale = self
+            .by_data_dir
+            .get(&data_dir)
+            .is_none_or(|profiles| profiles.read_at.elapsed() >= REFRESH_EVERY);
+        if stale {
+            let by_dir = read_profiles(&data_dir);
+            let names = by_dir.iter().map(|(_, name)| name.clone()).collect();
+            self.by_data_dir.insert(
+                data_dir.clone(),
+                Profiles {
+                    read_at: Instant::now(),
+                    by_dir,
+                    names,
+                },
+            );
+        }
+        &self.by_data_dir[&data_dir]
+    }
+}
+
+fn read_profiles(data_dir: &Path) -> Vec<(String, String)> {
+    let Some(state) = fs::read(data_dir.join("Local State"))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+    else {
+        return Vec::new();
+    };
+    state["profile"]["info_cache"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(dir, info)| Some((dir.clone(), info["name"].as_str()?.trim().to_owned())))
+        .filter(|(_, name)| !name.is_empty())
+        .collect()
+}
+
+fn default_user_data_dir(windows_dir: &str, unix_dir: &str) -> Option<PathBuf> {
+    if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(|local| PathBuf::from(local).join(windows_dir))
+    } else {
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_home.join(unix_dir))
+    }
+}
+
+// `--flag=value`, `--flag="a b"` or `"--flag=a b"` in a joined command line.
+fn command_line_flag(args: &str, flag: &str) -> Option<String> {
+    let start = args.find(&format!("{flag}="))?;
+    let rest = &args[start + flag.len() + 1..];
+    let (rest, quoted) = match rest.strip_prefix('"') {
+        Some(rest) => (rest, true),
+        None => (rest, args[..start].ends_with('"')),
+    };
+    let value = if quoted { rest.split('"').next() } else { rest.split_whitespace().next() }?;
+    (!value.is_empty()).then(|| value.to_owned())
+}
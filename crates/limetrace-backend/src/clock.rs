@@ -0,0 +1,50 @@
+/// The local wall-clock date and time of day, as the OS reports it.
+#[derive(Debug, Clone, Copy)]
+pub struct LocalTime {
+    pub year: u16,
+    pub month: u16,
+    pub day: u16,
+    /// 0 is Sunday.
+    pub weekday: u16,
+    pub secs_since_midnight: i64,
+}
+
+impl LocalTime {
+    pub fn date(&self) -> (u16, u16, u16) {
+        (self.year, self.month, self.day)
+    }
+}
+
+#[cfg(windows)]
+pub fn local_now() -> LocalTime {
+    use windows_sys::Win32::Foundation::SYSTEMTIME;
+    use windows_sys::Win32::System::SystemInformation::GetLocalTime;
+
+    let mut local: SYSTEMTIME = unsafe { std::mem::zeroed() };
+    unsafe { GetLocalTime(&mut local) };
+    LocalTime {
+        year: local.wYear,
+        month: local.wMonth,
+        day: local.wDay,
+        weekday: local.wDayOfWeek,
+        secs_since_midnight: i64::from(local.wHour) * 3600
+            + i64::from(local.wMinute) * 60
+            + i64::from(local.wSecond),
+    }
+}
+
+#[cfg(unix)]
+pub fn local_now() -> LocalTime {
+    let now = unsafe { libc::time(std::ptr::null_mut()) };
+    let mut local: libc::tm = unsafe { std::mem::zeroed() };
+    unsafe { libc::localtime_r(&now, &mut local) };
+    LocalTime {
+        year: (local.tm_year + 1900) as u16,
+        month: (local.tm_mon + 1) as u16,
+        day: local.tm_mday as u16,
+        weekday: local.tm_wday as u16,
+        secs_since_midnight: i64::from(local.tm_hour) * 3600
+            + i64::from(local.tm_min) * 60
+            + i64::from(local.tm_sec),
+    }
+}
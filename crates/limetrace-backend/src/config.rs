@@ -1,47 +1,467 @@
 ﻿use anyhow::{bail, Context, Result};
+use limetrace_core::title_regex::TitleRegex;
 use std::env;
-use std::path::PathBuf;
-use std::time::Duration;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::identity::AppIdentityStrategy;
+use crate::monitor::UrlCapture;
+use crate::restrict::Restriction;
+use crate::rules::{ScheduleWindow, TrackingRules};
+use crate::slack::SlackStatusConfig;
+
+const DEFAULT_SLACK_FOCUS_STATUS: &str = ":headphones: Focusing \u{2014} {app}";
+pub const CATEGORY_CODES: [&str; 3] = ["work", "neutral", "distracting"];
 
 pub struct Config {
     pub db_path: PathBuf,
+    pub config_path: Option<PathBuf>,
+    pub jsonl_path: Option<PathBuf>,
+    pub today_feed_dir: Option<PathBuf>,
+    pub widget_ini_path: Option<PathBuf>,
+    pub daily_summary_at_secs: Option<i64>,
+    pub app_identity: AppIdentityStrategy,
+    pub merge_app_identities: bool,
+    pub print_status: bool,
+    pub dry_run: bool,
+    pub http_port: Option<u16>,
+    pub http_token: Option<String>,
+    pub slack: Option<SlackStatusConfig>,
     pub poll_interval: Duration,
     pub idle_threshold: Duration,
+    pub media_suppresses_idle: bool,
     pub rotate_segment_every: Duration,
+    pub rules: TrackingRules,
+    pub restrictions: Vec<Restriction>,
+    pub capture_command_line: bool,
+    pub browser_urls: UrlCapture,
+    pub split_browser_profiles: bool,
 }
 
+// Keys a config file may set, each named after the flag it stands for.
+const FILE_KEYS: [&str; 23] = [
+    "db",
+    "jsonl",
+    "today-json",
+    "widget-ini",
+    "daily-summary-at",
+    "app-identity",
+    "http-port",
+    "http-token",
+    "slack-token",
+    "slack-focus-status",
+    "slack-category-status",
+    "poll-ms",
+    "idle-secs",
+    "idle-during-media",
+    "rotate-secs",
+    "exclude",
+    "schedule",
+    "redact-title",
+    "redact-all-titles",
+    "restrict",
+    "capture-command-line",
+    "browser-urls",
+    "split-browser-profiles",
+];
+const SWITCH_KEYS: [&str; 4] =
+    ["idle-during-media", "redact-all-titles", "capture-command-line", "split-browser-profiles"];
+const REPEATABLE_FLAGS: [&str; 5] =
+    ["--slack-category-status", "--exclude", "--schedule", "--redact-title", "--restrict"];
+
 impl Config {
+    /// Settings from the config file, then the command line, which wins. Called again
+    /// on every config file change, so it must not have side effects.
     pub fn from_args() -> Result<Self> {
-        let mut db_path = default_db_path();
-        let mut poll_ms: u64 = 1000;
-        let mut idle_secs: u64 = 300;
-        let mut rotate_secs: u64 = 10;
+        let cli_args: Vec<String> = env::args().skip(1).collect();
+        let config_path = config_path_from(&cli_args)?;
+
+        let mut values = ArgValues::new();
+        if let Some(path) = &config_path {
+            for (_, file_args) in load_config_file(path)? {
+                file_args
+                    .and_then(|args| values.apply_all(args))
+                    .with_context(|| format!("invalid config file {}", path.display()))?;
+            }
+            values.from_file_repeatables = REPEATABLE_FLAGS.iter().map(|flag| (*flag).to_owned()).collect();
+        }
+        values.apply_all(cli_args)?;
+        let mut config = values.finish()?;
+        config.config_path = config_path;
+        Ok(config)
+    }
+}
+
+/// Validates the config file, the command line and the rules they add up to without
+/// starting, printing every problem found. Returns false when any is an error.
+pub fn check() -> bool {
+    let cli_args: Vec<String> = env::args().skip(1).collect();
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    match config_path_from(&cli_args) {
+        Err(err) => errors.push(format!("{err:#}")),
+        Ok(Some(path)) if !path.is_file() => errors.push(format!("config file {} does not exist", path.display())),
+        Ok(Some(path)) => {
+            println!("config file: {}", path.display());
+            match load_config_file(&path) {
+                Err(err) => errors.push(format!("{err:#}")),
+                Ok(entries) => {
+                    for (key, args) in entries {
+                        let mut scratch = ArgValues::new();
+                        if let Err(err) = args.and_then(|args| scratch.apply_all(args)) {
+                            errors.push(format!("{key}: {err:#}"));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(None) => println!(
+            "config file: none ({} does not exist, command-line flags only)",
+            default_config_path().map(|path| path.display().to_string()).unwrap_or_default()
+        ),
+    }
+
+    // The combined settings only make sense once every key parses on its own.
+    if errors.is_empty() {
+        match Config::from_args() {
+            Ok(config) => check_rules(&config, &mut errors, &mut warnings),
+            Err(err) => errors.push(format!("{err:#}")),
+        }
+    }
+
+    for error in &errors {
+        println!("error: {error}");
+    }
+    for warning in &warnings {
+        println!("warning: {warning}");
+    }
+    if errors.is_empty() && warnings.is_empty() {
+        println!("config ok");
+    } else {
+        println!("{} error(s), {} warning(s)", errors.len(), warnings.len());
+    }
+    errors.is_empty()
+}
+
+fn check_rules(config: &Config, errors: &mut Vec<String>, warnings: &mut Vec<String>) {
+    let folders = [
+        ("db", config.db_path.parent().map(Path::to_path_buf)),
+        ("jsonl", config.jsonl_path.as_ref().and_then(|path| path.parent()).map(Path::to_path_buf)),
+        ("today-json", config.today_feed_dir.clone()),
+        ("widget-ini", config.widget_ini_path.as_ref().and_then(|path| path.parent()).map(Path::to_path_buf)),
+    ];
+    for (key, folder) in folders {
+        let Some(folder) = folder.filter(|folder| !folder.as_os_str().is_empty()) else {
+            continue;
+        };
+        if folder.is_file() {
+            errors.push(format!("{key}: {} is a file, not a folder", folder.display()));
+        } else if !folder.exists() {
+            warnings.push(format!("{key}: folder {} does not exist yet and will be created", folder.display()));
+        }
+    }
+    for (key, path) in [("jsonl", &config.jsonl_path), ("widget-ini", &config.widget_ini_path)] {
+        if let Some(path) = path.as_ref().filter(|path| path.is_dir()) {
+            errors.push(format!("{key}: {} is a folder; give a file path", path.display()));
+        }
+    }
+
+    let rules = &config.rules;
+    for (index, rule) in rules.exclude.iter().enumerate() {
+        if rules.exclude[..index].iter().any(|earlier| earlier.eq_ignore_ascii_case(rule)) {
+            warnings.push(format!("exclude: \"{rule}\" is listed more than once"));
+        } else if rule.contains(['\\', '/']) {
+            if !Path::new(rule).exists() {
+                warnings.push(format!("exclude: nothing exists at \"{rule}\", so the rule matches no app yet"));
+            }
+        } else if cfg!(windows) && !rule.contains('.') {
+            warnings.push(format!(
+                "exclude: \"{rule}\" is compared with exe names, which end in .exe; did you mean \"{rule}.exe\"?"
+            ));
+        }
+    }
+
+    for (index, window) in rules.schedule.iter().enumerate() {
+        for earlier in &rules.schedule[..index] {
+            let shared_days: Vec<&str> = (0..7)
+                .filter(|day| window.days[*day] && earlier.days[*day])
+                .map(|day| crate::rules::WEEKDAYS[day])
+                .collect();
+            if !shared_days.is_empty() && window.start_secs < earlier.end_secs && earlier.start_secs < window.end_secs {
+                warnings.push(format!(
+                    "schedule: \"{}\" and \"{}\" overlap on {}; merge them into one window",
+                    earlier.source,
+                    window.source,
+                    shared_days.join(", ")
+                ));
+            }
+        }
+    }
+
+    for (pattern, regex) in &rules.redact_titles {
+        if regex.is_match("") {
+            warnings.push(format!(
+                "redact-title: \"{pattern}\" matches every title; use redact-all-titles if that is intended"
+            ));
+        }
+    }
+    if rules.redact_all_titles && !rules.redact_titles.is_empty() {
+        warnings.push("redact-title: patterns have no effect while redact-all-titles is set".to_owned());
+    }
+}
+
+fn config_path_from(cli_args: &[String]) -> Result<Option<PathBuf>> {
+    match cli_args.iter().position(|arg| arg == "--config") {
+        Some(index) => Ok(Some(PathBuf::from(
+            cli_args.get(index + 1).context("missing value for --config")?,
+        ))),
+        None => Ok(default_config_path().filter(|path| path.is_file())),
+    }
+}
+
+/// Notices edits to the config file, including one created after startup.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    checked_at: Instant,
+}
+
+impl ConfigWatcher {
+    const CHECK_EVERY: std::time::Duration = std::time::Duration::from_secs(2);
+
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified_time(&path);
+        Self {
+            path,
+            modified,
+            checked_at: Instant::now(),
+        }
+    }
+
+    pub fn changed(&mut self) -> bool {
+        if self.checked_at.elapsed() < Self::CHECK_EVERY {
+            return false;
+        }
+        self.checked_at = Instant::now();
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        true
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+struct ArgValues {
+    db_path: PathBuf,
+    jsonl_path: Option<PathBuf>,
+    today_feed_dir: Option<PathBuf>,
+    widget_ini_path: Option<PathBuf>,
+    daily_summary_at_secs: Option<i64>,
+    app_identity: AppIdentityStrategy,
+    merge_app_identities: bool,
+    print_status: bool,
+    dry_run: bool,
+    http_port: Option<u16>,
+    http_token: Option<String>,
+    slack_token: Option<String>,
+    slack_focus_status: String,
+    slack_category_statuses: Vec<(String, String)>,
+    poll_ms: u64,
+    idle_secs: u64,
+    idle_during_media: bool,
+    rotate_secs: u64,
+    rules: TrackingRules,
+    restrictions: Vec<Restriction>,
+    capture_command_line: bool,
+    browser_urls: UrlCapture,
+    split_browser_profiles: bool,
+    // A repeatable flag given on the command line replaces the file's list instead of adding to it.
+    from_file_repeatables: Vec<String>,
+}
 
-        let mut args = env::args().skip(1);
+impl ArgValues {
+    fn new() -> Self {
+        Self {
+            db_path: default_db_path(),
+            jsonl_path: None,
+            today_feed_dir: None,
+            widget_ini_path: None,
+            daily_summary_at_secs: None,
+            app_identity: AppIdentityStrategy::Path,
+            merge_app_identities: false,
+            print_status: false,
+            dry_run: false,
+            http_port: None,
+            http_token: env::var("LIMETRACE_HTTP_TOKEN").ok().filter(|token| !token.is_empty()),
+            slack_token: env::var("LIMETRACE_SLACK_TOKEN").ok().filter(|token| !token.is_empty()),
+            slack_focus_status: DEFAULT_SLACK_FOCUS_STATUS.to_owned(),
+            slack_category_statuses: Vec::new(),
+            poll_ms: 1000,
+            idle_secs: 300,
+            idle_during_media: false,
+            rotate_secs: 10,
+            rules: TrackingRules::default(),
+            restrictions: Vec::new(),
+            capture_command_line: false,
+            browser_urls: UrlCapture::Off,
+            split_browser_profiles: false,
+            from_file_repeatables: Vec::new(),
+        }
+    }
+
+    fn apply_all(&mut self, args: Vec<String>) -> Result<()> {
+        let mut args = args.into_iter();
         while let Some(arg) = args.next() {
+            if let Some(index) = self.from_file_repeatables.iter().position(|flag| *flag == arg) {
+                self.from_file_repeatables.swap_remove(index);
+                match arg.as_str() {
+                    "--slack-category-status" => self.slack_category_statuses.clear(),
+                    "--exclude" => self.rules.exclude.clear(),
+                    "--schedule" => self.rules.schedule.clear(),
+                    "--restrict" => self.restrictions.clear(),
+                    _ => self.rules.redact_titles.clear(),
+                }
+            }
             match arg.as_str() {
+                "--config" => {
+                    args.next();
+                }
+                "--check-config" => {}
                 "--db" => {
                     let value = args.next().context("missing value for --db")?;
-                    db_path = PathBuf::from(value);
+                    self.db_path = PathBuf::from(value);
+                }
+                "--jsonl" => {
+                    let value = args.next().context("missing value for --jsonl")?;
+                    self.jsonl_path = Some(PathBuf::from(value));
+                }
+                "--today-json" => {
+                    let value = args.next().context("missing value for --today-json")?;
+                    self.today_feed_dir = Some(PathBuf::from(value));
+                }
+                "--widget-ini" => {
+                    let value = args.next().context("missing value for --widget-ini")?;
+                    self.widget_ini_path = Some(PathBuf::from(value));
+                }
+                "--daily-summary-at" => {
+                    let value = args.next().context("missing value for --daily-summary-at")?;
+                    self.daily_summary_at_secs = Some(
+                        parse_clock_time(&value)
+                            .with_context(|| format!("invalid --daily-summary-at value: {value}"))?,
+                    );
+                }
+                "--app-identity" => {
+                    let value = args.next().context("missing value for --app-identity")?;
+                    self.app_identity = AppIdentityStrategy::from_code(&value)
+                        .with_context(|| format!("invalid --app-identity value: {value}"))?;
+                }
+                "--merge-app-identities" => {
+                    self.merge_app_identities = true;
+                }
+                "--status" => {
+                    self.print_status = true;
+                }
+                "--dry-run" => {
+                    self.dry_run = true;
+                }
+                "--http-port" => {
+                    let value = args.next().context("missing value for --http-port")?;
+                    self.http_port = Some(
+                        value
+                            .parse::<u16>()
+                            .with_context(|| format!("invalid --http-port value: {value}"))?,
+                    );
+                }
+                "--http-token" => {
+                    let value = args.next().context("missing value for --http-token")?;
+                    self.http_token = Some(value);
+                }
+                "--slack-token" => {
+                    let value = args.next().context("missing value for --slack-token")?;
+                    self.slack_token = Some(value);
+                }
+                "--slack-focus-status" => {
+                    self.slack_focus_status = args.next().context("missing value for --slack-focus-status")?;
+                }
+                "--slack-category-status" => {
+                    let value = args.next().context("missing value for --slack-category-status")?;
+                    let (category, status) = value
+                        .split_once('=')
+                        .filter(|(category, _)| CATEGORY_CODES.contains(category))
+                        .with_context(|| {
+                            format!("invalid --slack-category-status value (expected <work|neutral|distracting>=<status>): {value}")
+                        })?;
+                    self.slack_category_statuses.push((category.to_owned(), status.to_owned()));
                 }
                 "--poll-ms" => {
                     let value = args.next().context("missing value for --poll-ms")?;
-                    poll_ms = value
+                    self.poll_ms = value
                         .parse::<u64>()
                         .with_context(|| format!("invalid --poll-ms value: {value}"))?;
                 }
                 "--idle-secs" => {
                     let value = args.next().context("missing value for --idle-secs")?;
-                    idle_secs = value
+                    self.idle_secs = value
                         .parse::<u64>()
                         .with_context(|| format!("invalid --idle-secs value: {value}"))?;
                 }
+                "--idle-during-media" => {
+                    self.idle_during_media = true;
+                }
                 "--rotate-secs" => {
                     let value = args.next().context("missing value for --rotate-secs")?;
-                    rotate_secs = value
+                    self.rotate_secs = value
                         .parse::<u64>()
                         .with_context(|| format!("invalid --rotate-secs value: {value}"))?;
                 }
+                "--exclude" => {
+                    let value = args.next().context("missing value for --exclude")?;
+                    if value.trim().is_empty() {
+                        bail!("--exclude value must not be empty");
+                    }
+                    self.rules.exclude.push(value.trim().to_owned());
+                }
+                "--schedule" => {
+                    let value = args.next().context("missing value for --schedule")?;
+                    let window = ScheduleWindow::parse(&value).with_context(|| {
+                        format!("invalid --schedule value (expected e.g. \"mon-fri 09:00-18:00\"): {value}")
+                    })?;
+                    self.rules.schedule.push(window);
+                }
+                "--redact-title" => {
+                    let value = args.next().context("missing value for --redact-title")?;
+                    let regex = TitleRegex::new(&value)
+                        .with_context(|| format!("invalid --redact-title pattern: {value}"))?;
+                    self.rules.redact_titles.push((value, regex));
+                }
+                "--redact-all-titles" => {
+                    self.rules.redact_all_titles = true;
+                }
+                "--restrict" => {
+                    let value = args.next().context("missing value for --restrict")?;
+                    let restriction = Restriction::parse(&value).with_context(|| {
+                        format!("invalid --restrict value (expected e.g. \"distracting daily 23:00-24:00 minimize\"): {value}")
+                    })?;
+                    self.restrictions.push(restriction);
+                }
+                "--capture-command-line" => {
+                    self.capture_command_line = true;
+                }
+                "--split-browser-profiles" => {
+                    self.split_browser_profiles = true;
+                }
+                "--browser-urls" => {
+                    let value = args.next().context("missing value for --browser-urls")?;
+                    self.browser_urls = UrlCapture::from_code(&value)
+                        .with_context(|| format!("invalid --browser-urls value (expected off, domain or url): {value}"))?;
+                }
                 "--help" | "-h" => {
                     print_help();
                     std::process::exit(0);
@@ -49,6 +469,36 @@ impl Config {
                 _ => bail!("unknown argument: {arg}"),
             }
         }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Config> {
+        let Self {
+            db_path,
+            jsonl_path,
+            today_feed_dir,
+            widget_ini_path,
+            daily_summary_at_secs,
+            app_identity,
+            merge_app_identities,
+            print_status,
+            dry_run,
+            http_port,
+            http_token,
+            slack_token,
+            slack_focus_status,
+            slack_category_statuses,
+            poll_ms,
+            idle_secs,
+            idle_during_media,
+            rotate_secs,
+            rules,
+            restrictions,
+            capture_command_line,
+            browser_urls,
+            split_browser_profiles,
+            from_file_repeatables: _,
+        } = self;
 
         if poll_ms == 0 {
             bail!("--poll-ms must be greater than zero");
@@ -59,39 +509,233 @@ impl Config {
         if rotate_secs == 0 {
             bail!("--rotate-secs must be greater than zero");
         }
+        if http_port.is_some() && http_token.is_none() {
+            bail!("--http-port requires --http-token or LIMETRACE_HTTP_TOKEN");
+        }
+        if merge_app_identities && app_identity == AppIdentityStrategy::Path {
+            bail!("--merge-app-identities requires --app-identity product or hash");
+        }
 
-        Ok(Self {
+        Ok(Config {
             db_path,
+            config_path: None,
+            jsonl_path,
+            today_feed_dir,
+            widget_ini_path,
+            daily_summary_at_secs,
+            app_identity,
+            merge_app_identities,
+            print_status,
+            dry_run,
+            http_port,
+            http_token,
+            slack: slack_token.map(|token| SlackStatusConfig {
+                token,
+                focus_status: slack_focus_status,
+                category_statuses: slack_category_statuses,
+            }),
             poll_interval: Duration::from_millis(poll_ms),
             idle_threshold: Duration::from_secs(idle_secs),
+            media_suppresses_idle: !idle_during_media,
             rotate_segment_every: Duration::from_secs(rotate_secs),
+            rules,
+            restrictions,
+            capture_command_line,
+            browser_urls,
+            split_browser_profiles,
         })
     }
 }
 
+// The file is flat TOML whose keys are flag names; arrays repeat the flag, `true`
+// sets a switch. Relative paths are taken from the file's folder. Each key turns into
+// its flags separately, so `--check-config` can report every bad key at once.
+fn load_config_file(path: &Path) -> Result<Vec<(String, Result<Vec<String>>)>> {
+    const PATH_KEYS: [&str; 4] = ["db", "jsonl", "today-json", "widget-ini"];
+
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read config file {}", path.display()))?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+    let table: toml::value::Table =
+        toml::from_str(text).with_context(|| format!("failed to parse config file {}", path.display()))?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    let key_args = |key: &str, value: &toml::Value| -> Result<Vec<String>> {
+        if !FILE_KEYS.contains(&key) {
+            bail!("unknown key \"{key}\"");
+        }
+        let flag = format!("--{key}");
+        let scalar = |value: &toml::Value| -> Result<String> {
+            let text = match value {
+                toml::Value::String(text) => text.clone(),
+                toml::Value::Integer(number) => number.to_string(),
+                _ => bail!("\"{key}\" must be a string or a number"),
+            };
+            if PATH_KEYS.contains(&key) {
+                return Ok(base.join(text).display().to_string());
+            }
+            Ok(text)
+        };
+        let mut args = Vec::new();
+        match value {
+            toml::Value::Boolean(enabled) => {
+                if !SWITCH_KEYS.contains(&key) {
+                    bail!("\"{key}\" takes a value, not true/false");
+                }
+                if *enabled {
+                    args.push(flag);
+                }
+            }
+            toml::Value::Array(items) => {
+                if !REPEATABLE_FLAGS.contains(&flag.as_str()) {
+                    bail!("\"{key}\" takes a single value, not a list");
+                }
+                for item in items {
+                    args.push(flag.clone());
+                    args.push(scalar(item)?);
+                }
+            }
+            value => {
+                if SWITCH_KEYS.contains(&key) {
+                    bail!("\"{key}\" must be true or false");
+                }
+                args.push(flag);
+                args.push(scalar(value)?);
+            }
+        }
+        Ok(args)
+    };
+    Ok(table
+        .iter()
+        .map(|(key, value)| (key.clone(), key_args(key, value)))
+        .collect())
+}
+
+/// `backend.toml` beside the default database.
+pub fn default_config_path() -> Option<PathBuf> {
+    default_db_path().parent().map(|dir| dir.join("backend.toml"))
+}
+
+pub fn parse_clock_time(value: &str) -> Result<i64> {
+    let (hours, minutes) = value.split_once(':').context("expected HH:MM")?;
+    let hours: i64 = hours.trim().parse().context("invalid hour")?;
+    let minutes: i64 = minutes.trim().parse().context("invalid minute")?;
+    if !(0..24).contains(&hours) || !(0..60).contains(&minutes) {
+        bail!("time out of range");
+    }
+    Ok(hours * 3600 + minutes * 60)
+}
+
 fn default_db_path() -> PathBuf {
     if let Some(local) = env::var_os("LOCALAPPDATA") {
         return PathBuf::from(local)
             .join("LimeTrace")
             .join("tracker.db");
     }
+    #[cfg(target_os = "macos")]
+    if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+            .join("LimeTrace")
+            .join("tracker.db");
+    }
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")));
+    if let Some(data_home) = data_home {
+        return data_home.join("LimeTrace").join("tracker.db");
+    }
     PathBuf::from("data").join("tracker.db")
 }
 
 fn print_help() {
     println!(
         "\
-LimeTrace Backend (Windows)
+LimeTrace Backend (Windows, Linux X11/Wayland, macOS)
 
 Usage:
-  limetrace-backend [--db <path>] [--poll-ms <ms>] [--idle-secs <s>] [--rotate-secs <s>]
+  limetrace-backend [--config <path>] [--check-config] [--dry-run] [--db <path>] [--jsonl <path>] [--poll-ms <ms>] [--idle-secs <s>]
+                    [--idle-during-media] [--rotate-secs <s>] [--daily-summary-at <HH:MM>] [--today-json <dir>]
+                    [--widget-ini <path>]
+                    [--app-identity <path|product|hash>] [--merge-app-identities]
+                    [--http-port <port> --http-token <token>] [--status]
+                    [--slack-token <token>] [--slack-focus-status <status>]
+                    [--slack-category-status <category>=<status>]...
+                    [--exclude <app>]... [--schedule <days> <HH:MM>-<HH:MM>]...
+                    [--redact-title <regex>]... [--redact-all-titles]
+                    [--restrict <category|app> <days> <HH:MM>-<HH:MM> [notify|minimize|close]]...
+                    [--capture-command-line] [--browser-urls <off|domain|url>]
+                    [--split-browser-profiles]
 
 Options:
-  --db           SQLite file path (default: %LOCALAPPDATA%\\LimeTrace\\tracker.db)
-  --poll-ms      Sampling interval in milliseconds (default: 1000)
+  --check-config Validate the config file, flags and rules, print every problem and exit
+                 (exit code 1 on errors)
+  --dry-run      Monitor and print each segment to stdout instead of writing it; no
+                 database, sinks, notifications or control endpoints are touched
+  --config       Read settings from this TOML file (default: backend.toml beside the
+                 default database, when it exists). Keys are the flag names without
+                 dashes, e.g. poll-ms = 1000, exclude = [\"steam.exe\"]; flags given on
+                 the command line win. Changes are picked up while running; --db and
+                 --http-port need a restart
+  --db           SQLite file path (default: %LOCALAPPDATA%\\LimeTrace\\tracker.db,
+                 $XDG_DATA_HOME/LimeTrace/tracker.db on Linux,
+                 ~/Library/Application Support/LimeTrace/tracker.db on macOS)
+  --jsonl        Also append one JSON line per closed segment to this file (default: off)
+  --poll-ms      Sampling interval in milliseconds (default: 1000). On Windows window
+                 switches and title changes are also sampled as they happen
   --idle-secs    Idle threshold in seconds (default: 300)
+  --idle-during-media
+                 Let time without input turn idle even while a video plays, a full-screen
+                 app runs or the foreground app is making sound
   --rotate-secs  Force-segment rotation interval in seconds (default: 10)
+  --daily-summary-at
+                 Show an end-of-day summary notification at this local time (default: off)
+  --today-json   Keep <dir>\\today.json current with today's totals, top apps and the
+                 current activity, for phone widgets or a NAS page (default: off)
+  --widget-ini   Keep a Rainmeter-style INI (UTF-16, section [LimeTrace]) current with
+                 Date, Updated, ActiveSecs/Active, FocusSecs/Focus/FocusActive,
+                 CurrentApp/CurrentIdle/CurrentSecs/Current and TopApp1..5 with
+                 TopApp<n>Secs/TopApp<n>Time (default: off)
+  --app-identity Group executables by full path, ProductName/CompanyName, or file hash
+                 so portable apps keep one history when moved (default: path)
+  --merge-app-identities
+                 Merge recorded apps that share an identity under --app-identity, then exit
+  --http-port    Serve quick actions on 127.0.0.1:<port> (default: off):
+                 POST /pause, POST /resume, POST /focus/start?minutes=<n>,
+                 GET /today-summary
+  --http-token   Bearer token required by --http-port (or set LIMETRACE_HTTP_TOKEN)
+  --status       Print the running backend's status as JSON (uptime, current app, last
+                 write, samples/sec), then exit; fails if no backend is running
+  --slack-token  Slack user token with users.profile:write; enables status sync
+                 (or set LIMETRACE_SLACK_TOKEN)
+  --slack-focus-status
+                 Status while a focus session runs (default: \":headphones: Focusing \u{2014} {{app}}\")
+  --slack-category-status
+                 Status while an app of this category is in front, e.g.
+                 work=\":computer: Working in {{app}}\"; repeatable. Idle clears the status
+  --exclude      Don't record this app: an exe name, or a path prefix when it contains
+                 a slash; repeatable
+  --schedule     Only record inside these weekly windows, e.g. \"mon-fri 09:00-18:00\"
+                 or \"sat,sun 10:00-12:00\"; repeatable (default: always)
+  --redact-title Store \"[redacted]\" instead of window titles matching this regex;
+                 repeatable
+  --redact-all-titles
+                 Store \"[redacted]\" instead of every window title
+  --restrict     Warn when a category (work, neutral, distracting) or app is in front
+                 during this weekly window, e.g. \"distracting daily 23:00-24:00 minimize\";
+                 three warnings a minute apart, then minimize or close it if given.
+                 Every step is logged to the restriction_events table; repeatable
+  --capture-command-line
+                 Also store each process's command line with its segments (the
+                 process_args column), e.g. the folder an editor was opened on. Arguments
+                 can hold file paths, URLs or tokens, so this is off by default
+  --browser-urls Record the active tab's domain or full URL for Chrome, Edge, Firefox
+                 and other Chromium browsers, read from the address bar through UI
+                 Automation (Windows only; default: off)
+  --split-browser-profiles
+                 Record each Chrome, Edge, Brave, Vivaldi or Chromium profile as its
+                 own app, e.g. \"chrome (Work)\", so profiles can be categorized apart.
+                 The profile comes from the window title or --profile-directory
   -h, --help     Print this help"
     );
 }
-
+
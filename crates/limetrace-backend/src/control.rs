@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use subtle::ConstantTimeEq;
+
+use crate::db::Database;
+
+const DEFAULT_FOCUS_MINUTES: i64 = 25;
+const MAX_REQUEST_BODY_BYTES: usize = 4096;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A request the route understood but can't carry out as given; answered with 400.
+#[derive(Debug)]
+struct BadRequest(String);
+
+impl fmt::Display for BadRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BadRequest {}
+
+/// State shared between the sampling loop and the control endpoints.
+pub struct ControlState {
+    pub paused: AtomicBool,
+}
+
+/// Serves the quick-action endpoints on 127.0.0.1 for Stream Deck style buttons.
+/// Every request must carry `Authorization: Bearer <token>`.
+pub fn spawn(port: u16, token: String, db_path: PathBuf, state: Arc<ControlState>) -> Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .with_context(|| format!("failed to bind control port {port}"))?;
+    let db = Database::open(&db_path)?;
+    thread::Builder::new()
+        .name("limetrace-control".to_owned())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        if let Err(err) = handle_connection(stream, &token, &db, &state) {
+                            eprintln!("control request error: {err:#}");
+                        }
+                    }
+                    Err(err) => eprintln!("control accept error: {err}"),
+                }
+            }
+        })
+        .context("failed to start control thread")?;
+    Ok(())
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    token: &str,
+    db: &Database,
+    state: &ControlState,
+) -> Result<()> {
+    stream
+        .set_read_timeout(Some(READ_TIMEOUT))
+        .context("failed to set control read timeout")?;
+    let mut reader = BufReader::new(stream.try_clone().context("failed to clone control stream")?);
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .context("failed to read request line")?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let target = parts.next().unwrap_or_default().to_owned();
+
+    let mut authorized = false;
+    let mut content_length = 0usize;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).context("failed to read header")? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            // Compared in constant time so response timing doesn't reveal a matching prefix.
+            authorized = value
+                .strip_prefix("Bearer ")
+                .is_some_and(|given| bool::from(given.as_bytes().ct_eq(token.as_bytes())));
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().unwrap_or(0);
+        }
+    }
+    // Bodies are ignored, but draining them keeps clients from seeing a reset.
+    let mut body = vec![0u8; content_length.min(MAX_REQUEST_BODY_BYTES)];
+    reader.read_exact(&mut body).context("failed to read request body")?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+    let (status, response) = if !authorized {
+        (401, json!({ "error": "missing or invalid token" }))
+    } else {
+        match route(&method, path, query, db, state) {
+            Ok(Some(response)) => (200, response),
+            Ok(None) => (404, json!({ "error": format!("no route for {method} {path}") })),
+            Err(err) if err.is::<BadRequest>() => (400, json!({ "error": err.to_string() })),
+            Err(err) => (500, json!({ "error": format!("{err:#}") })),
+        }
+    };
+    write_response(stream, status, &response)
+}
+
+fn route(
+    method: &str,
+    path: &str,
+    query: &str,
+    db: &Database,
+    state: &ControlState,
+) -> Result<Option<serde_json::Value>> {
+    let now = crate::unix_seconds_now();
+    let response = match (method, path) {
+        ("POST", "/pause") => {
+            state.paused.store(true, Ordering::SeqCst);
+            json!({ "paused": true })
+        }
+        ("POST", "/resume") => {
+            state.paused.store(false, Ordering::SeqCst);
+            json!({ "paused": false })
+        }
+        ("POST", "/focus/start") => {
+            let minutes = query_param(query, "minutes")
+                .and_then(|value| value.parse::<i64>().ok())
+                .filter(|minutes| *minutes > 0)
+                .unwrap_or(DEFAULT_FOCUS_MINUTES);
+            let minimize = query_param(query, "minimize").is_some_and(|value| value == "1");
+            let ends_ts = minutes
+                .checked_mul(60)
+                .and_then(|secs| now.checked_add(secs))
+                .ok_or_else(|| BadRequest(format!("minutes out of range: {minutes}")))?;
+            let session_id = db.start_focus_session(now, ends_ts, minimize)?;
+            json!({ "session_id": session_id, "ends_ts": ends_ts })
+        }
+        ("GET", "/today-summary") => {
+            let day_start = now - crate::clock::local_now().secs_since_midnight;
+            let totals = db.activity_totals(day_start, now)?;
+            let focus_secs = db.focus_secs(day_start, now)?;
+            json!({
+                "active_secs": totals.active_secs,
+                "focus_secs": focus_secs,
+                "top_app": totals.top_app.as_ref().map(|(app, _)| app),
+                "top_app_secs": totals.top_app.as_ref().map(|(_, secs)| secs),
+                "paused": state.paused.load(Ordering::SeqCst),
+                "focus_active": db.active_focus_session(now)?.is_some(),
+            })
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(response))
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn write_response(mut stream: TcpStream, status: u16, body: &serde_json::Value) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+    .and_then(|_| stream.flush())
+    .context("failed to write control response")
+}
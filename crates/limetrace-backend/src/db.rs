@@ -1,24 +1,32 @@
 use anyhow::{Context, Result};
-use rusqlite::{params, Connection};
+use limetrace_core::recorder::{SegmentInsert, SegmentStore};
+use limetrace_core::schema::ensure_segment_columns;
+use rusqlite::{params, Connection, OptionalExtension};
 use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
 
-#[derive(Debug, Clone)]
-pub struct SegmentInsert {
-    pub start_ts: i64,
-    pub end_ts: i64,
-    pub app_id: Option<i64>,
-    pub title_id: Option<i64>,
-    pub is_idle: bool,
-    pub pid: Option<u32>,
-    pub pid_create_time: Option<u64>,
+use crate::identity::{app_identity, AppIdentityStrategy};
+use crate::monitor::canonicalize_process_path;
+
+pub struct ActivityTotals {
+    pub active_secs: i64,
+    pub top_app: Option<(String, i64)>,
+    pub by_app: Vec<(String, i64)>,
+}
+
+pub struct FocusSession {
+    pub id: i64,
+    pub minimize_distracting: bool,
 }
 
 pub struct Database {
     conn: Connection,
-    app_cache: HashMap<(String, String), i64>,
+    // Keyed by `app_merge_key`.
+    app_cache: HashMap<String, i64>,
+    identity_cache: HashMap<String, i64>,
     title_cache: HashMap<String, i64>,
+    url_cache: HashMap<String, i64>,
 }
 
 impl Database {
@@ -32,7 +40,7 @@ impl Database {
             })?;
         }
 
-        let conn = Connection::open(path)
+        let mut conn = Connection::open(path)
             .with_context(|| format!("failed to open database: {}", path.display()))?;
         conn.busy_timeout(Duration::from_secs(5))
             .context("failed to set busy timeout")?;
@@ -42,76 +50,354 @@ impl Database {
             PRAGMA journal_mode = WAL;
             PRAGMA synchronous = NORMAL;
             PRAGMA temp_store = MEMORY;
-            PRAGMA foreign_keys = ON;
-
-            CREATE TABLE IF NOT EXISTS apps (
-              id INTEGER PRIMARY KEY,
-              exe_name TEXT NOT NULL,
-              process_path TEXT NOT NULL,
-              UNIQUE(exe_name, process_path)
-            );
-
-            CREATE TABLE IF NOT EXISTS titles (
-              id INTEGER PRIMARY KEY,
-              title TEXT NOT NULL UNIQUE
-            );
-
-            CREATE TABLE IF NOT EXISTS segments (
-              id INTEGER PRIMARY KEY,
-              start_ts INTEGER NOT NULL,
-              end_ts INTEGER NOT NULL CHECK (end_ts >= start_ts),
-              app_id INTEGER,
-              title_id INTEGER,
-              is_idle INTEGER NOT NULL DEFAULT 0,
-              pid INTEGER,
-              pid_create_time INTEGER,
-              FOREIGN KEY(app_id) REFERENCES apps(id),
-              FOREIGN KEY(title_id) REFERENCES titles(id)
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_segments_start ON segments(start_ts);
-            CREATE INDEX IF NOT EXISTS idx_segments_app_start ON segments(app_id, start_ts);
-            CREATE INDEX IF NOT EXISTS idx_segments_idle_start ON segments(is_idle, start_ts);",
+            PRAGMA foreign_keys = ON;",
         )
-        .context("failed to initialize schema")?;
+        .context("failed to configure connection")?;
+        apply_migrations(&mut conn)?;
+
+        Ok(Self::with_connection(conn))
+    }
+
+    /// A scratch database with the full schema, for `--dry-run`.
+    pub fn open_in_memory() -> Result<Self> {
+        let mut conn = Connection::open_in_memory().context("failed to open in-memory database")?;
+        conn.execute_batch("PRAGMA foreign_keys = ON;")
+            .context("failed to configure connection")?;
+        apply_migrations(&mut conn)?;
+        Ok(Self::with_connection(conn))
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn into_connection(self) -> Connection {
+        self.conn
+    }
 
-        Ok(Self {
+    fn with_connection(conn: Connection) -> Self {
+        Self {
             conn,
             app_cache: HashMap::new(),
+            identity_cache: HashMap::new(),
             title_cache: HashMap::new(),
-        })
+            url_cache: HashMap::new(),
+        }
+    }
+
+    /// Computes identities for recorded apps whose executables still exist and folds
+    /// rows sharing one into the oldest. Returns the number of rows merged away.
+    pub fn merge_apps_by_identity(&mut self, strategy: AppIdentityStrategy) -> Result<usize> {
+        let tx = self
+            .conn
+            .transaction()
+            .context("failed to start app merge transaction")?;
+        let apps = load_apps(&tx)?;
+
+        let mut groups: HashMap<String, Vec<i64>> = HashMap::new();
+        for (id, _, process_path) in apps {
+            if let Some(identity) = app_identity(strategy, &process_path) {
+                groups.entry(identity).or_default().push(id);
+            }
+        }
+
+        let mut merged = 0;
+        for (identity, ids) in &groups {
+            let Some((keep_id, duplicate_ids)) = ids.split_first() else {
+                continue;
+            };
+            for duplicate_id in duplicate_ids {
+                tx.execute(
+                    "UPDATE app_identities SET app_id = ?1 WHERE app_id = ?2",
+                    params![keep_id, duplicate_id],
+                )
+                .context("failed to repoint app identity")?;
+            }
+            merge_app_rows(&tx, *keep_id, duplicate_ids)?;
+            tx.execute(
+                "INSERT OR REPLACE INTO app_identities (identity, app_id) VALUES (?1, ?2)",
+                params![identity, keep_id],
+            )
+            .context("failed to record app identity")?;
+            merged += duplicate_ids.len();
+        }
+        tx.commit().context("failed to commit app merge")?;
+        self.app_cache.clear();
+        self.identity_cache.clear();
+        Ok(merged)
+    }
+
+    pub fn activity_totals(&self, start_ts: i64, end_ts: i64) -> Result<ActivityTotals> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "\
+                SELECT a.exe_name,
+                       SUM(MIN(s.end_ts, ?2) - MAX(s.start_ts, ?1)) AS secs
+                FROM segments s
+                JOIN apps a ON a.id = s.app_id
+                WHERE s.is_idle = 0
+                  AND s.deleted_ts IS NULL
+                  AND s.end_ts > ?1
+                  AND s.start_ts < ?2
+                GROUP BY a.exe_name
+                ORDER BY secs DESC",
+            )
+            .context("failed to prepare activity totals query")?;
+        let rows = stmt
+            .query_map(params![start_ts, end_ts], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })
+            .context("failed to query activity totals")?;
+
+        let mut totals = ActivityTotals {
+            active_secs: 0,
+            top_app: None,
+            by_app: Vec::new(),
+        };
+        for row in rows {
+            let (exe_name, secs) = row.context("failed to read activity totals row")?;
+            totals.active_secs += secs.max(0);
+            if totals.top_app.is_none() {
+                totals.top_app = Some((exe_name.clone(), secs.max(0)));
+            }
+            totals.by_app.push((exe_name, secs.max(0)));
+        }
+        Ok(totals)
+    }
+
+    pub fn active_focus_session(&self, now_ts: i64) -> Result<Option<FocusSession>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "\
+                SELECT id, minimize_distracting
+                FROM focus_sessions
+                WHERE stopped_ts IS NULL
+                  AND started_ts <= ?1
+                  AND ends_ts > ?1
+                ORDER BY started_ts DESC
+                LIMIT 1",
+            )
+            .context("failed to prepare focus session query")?;
+        let mut rows = stmt
+            .query(params![now_ts])
+            .context("failed to query focus sessions")?;
+        let Some(row) = rows.next().context("failed to read focus session row")? else {
+            return Ok(None);
+        };
+        Ok(Some(FocusSession {
+            id: row.get(0)?,
+            minimize_distracting: row.get::<_, i64>(1)? != 0,
+        }))
     }
 
-    pub fn upsert_app(&mut self, exe_name: &str, process_path: &str) -> Result<i64> {
-        let key = (exe_name.to_owned(), process_path.to_owned());
+    pub fn start_focus_session(&self, started_ts: i64, ends_ts: i64, minimize_distracting: bool) -> Result<i64> {
+        self.conn
+            .execute(
+                "\
+                INSERT INTO focus_sessions (started_ts, ends_ts, minimize_distracting)
+                VALUES (?1, ?2, ?3)",
+                params![started_ts, ends_ts, bool_to_i64(minimize_distracting)],
+            )
+            .context("failed to start focus session")?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Seconds of focus sessions inside the range; running sessions count up to `end_ts`.
+    pub fn focus_secs(&self, start_ts: i64, end_ts: i64) -> Result<i64> {
+        self.conn
+            .query_row(
+                "\
+                SELECT COALESCE(SUM(MAX(0, MIN(COALESCE(stopped_ts, ends_ts), ?2) - MAX(started_ts, ?1))), 0)
+                FROM focus_sessions
+                WHERE started_ts < ?2",
+                params![start_ts, end_ts],
+                |row| row.get(0),
+            )
+            .context("failed to query focus time")
+    }
+
+    pub fn app_categories(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT app_key, category FROM app_categories")
+            .context("failed to prepare app categories query")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .context("failed to query app categories")?;
+        rows.collect::<rusqlite::Result<HashMap<_, _>>>()
+            .context("failed to read app categories")
+    }
+
+    pub fn distracting_app_keys(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT app_key FROM app_categories WHERE category = 'distracting'")
+            .context("failed to prepare app categories query")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .context("failed to query app categories")?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to read app categories")
+    }
+
+    pub fn insert_focus_violation(
+        &self,
+        session_id: i64,
+        ts: i64,
+        app_key: &str,
+        title: &str,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "\
+                INSERT INTO focus_violations (session_id, ts, app_key, title)
+                VALUES (?1, ?2, ?3, ?4)",
+                params![session_id, ts, app_key, title],
+            )
+            .context("failed to insert focus violation")?;
+        Ok(())
+    }
+
+    pub fn insert_restriction_event(
+        &self,
+        ts: i64,
+        rule: &str,
+        app_key: &str,
+        title: &str,
+        action: &str,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "\
+                INSERT INTO restriction_events (ts, rule, app_key, title, action)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![ts, rule, app_key, title, action],
+            )
+            .context("failed to insert restriction event")?;
+        Ok(())
+    }
+
+    pub fn recorded_exe_names(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT exe_name FROM apps")
+            .context("failed to prepare app names query")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .context("failed to query app names")?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to read app names")
+    }
+
+    pub fn insert_notification(
+        &self,
+        ts: i64,
+        kind: &str,
+        app_key: Option<&str>,
+        message: &str,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO notifications (ts, kind, app_key, message) VALUES (?1, ?2, ?3, ?4)",
+                params![ts, kind, app_key, message],
+            )
+            .context("failed to insert notification")?;
+        Ok(())
+    }
+
+    /// Written every poll, paused or not, so the viewer can tell a running but
+    /// idle backend from a stopped one.
+    pub fn write_heartbeat(&self, pid: u32, ts: i64, paused: bool) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO heartbeat (id, pid, ts, paused) VALUES (1, ?1, ?2, ?3)",
+                params![pid, ts, bool_to_i64(paused)],
+            )
+            .context("failed to write heartbeat")?;
+        Ok(())
+    }
+
+    /// A clean exit removes the heartbeat; a stale one means the backend died.
+    pub fn clear_heartbeat(&self) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM heartbeat", [])
+            .context("failed to clear heartbeat")?;
+        Ok(())
+    }
+
+}
+
+impl SegmentStore for Database {
+    fn upsert_app(&mut self, exe_name: &str, process_path: &str) -> Result<i64> {
+        let key = app_merge_key(exe_name, process_path);
         if let Some(id) = self.app_cache.get(&key) {
             return Ok(*id);
         }
 
+        // A row differing only in case is the same app, as the migration merged them;
+        // the key's UNIQUE index keeps a second one out.
+        assign_app_keys(&self.conn)?;
         self.conn
             .execute(
                 "\
-                INSERT INTO apps (exe_name, process_path)
-                VALUES (?1, ?2)
-                ON CONFLICT(exe_name, process_path) DO NOTHING",
-                params![exe_name, process_path],
+                INSERT INTO apps (exe_name, process_path, app_key)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT DO NOTHING",
+                params![exe_name, process_path, key],
             )
             .context("failed to upsert apps row")?;
 
         let app_id = self
+            .conn
+            .query_row("SELECT id FROM apps WHERE app_key = ?1", params![key], |row| {
+                row.get::<_, i64>(0)
+            })
+            .context("failed to read apps.id after upsert")?;
+
+        self.app_cache.insert(key, app_id);
+        Ok(app_id)
+    }
+
+    fn upsert_app_with_identity(
+        &mut self,
+        identity: &str,
+        exe_name: &str,
+        process_path: &str,
+    ) -> Result<i64> {
+        if let Some(id) = self.identity_cache.get(identity) {
+            return Ok(*id);
+        }
+
+        let existing = self
             .conn
             .query_row(
-                "SELECT id FROM apps WHERE exe_name = ?1 AND process_path = ?2",
-                params![exe_name, process_path],
+                "SELECT app_id FROM app_identities WHERE identity = ?1",
+                params![identity],
                 |row| row.get::<_, i64>(0),
             )
-            .context("failed to read apps.id after upsert")?;
+            .optional()
+            .context("failed to read app identity")?;
+        let app_id = match existing {
+            Some(app_id) => app_id,
+            None => {
+                let app_id = self.upsert_app(exe_name, process_path)?;
+                self.conn
+                    .execute(
+                        "INSERT OR IGNORE INTO app_identities (identity, app_id) VALUES (?1, ?2)",
+                        params![identity, app_id],
+                    )
+                    .context("failed to insert app identity")?;
+                app_id
+            }
+        };
 
-        self.app_cache.insert(key, app_id);
+        self.identity_cache.insert(identity.to_owned(), app_id);
         Ok(app_id)
     }
 
-    pub fn upsert_title(&mut self, title: &str) -> Result<i64> {
+    fn upsert_title(&mut self, title: &str) -> Result<i64> {
         if let Some(id) = self.title_cache.get(title) {
             return Ok(*id);
         }
@@ -139,8 +425,33 @@ impl Database {
         Ok(title_id)
     }
 
-    pub fn insert_segment(&mut self, segment: &SegmentInsert) -> Result<()> {
-        if segment.end_ts <= segment.start_ts {
+    fn upsert_url(&mut self, url: &str) -> Result<i64> {
+        if let Some(id) = self.url_cache.get(url) {
+            return Ok(*id);
+        }
+
+        self.conn
+            .execute(
+                "INSERT INTO urls (url) VALUES (?1) ON CONFLICT(url) DO NOTHING",
+                params![url],
+            )
+            .context("failed to upsert urls row")?;
+        let url_id = self
+            .conn
+            .query_row("SELECT id FROM urls WHERE url = ?1", params![url], |row| {
+                row.get::<_, i64>(0)
+            })
+            .context("failed to read urls.id after upsert")?;
+
+        if self.url_cache.len() >= 4096 {
+            self.url_cache.clear();
+        }
+        self.url_cache.insert(url.to_owned(), url_id);
+        Ok(url_id)
+    }
+
+    fn insert_segment(&mut self, segment: &SegmentInsert) -> Result<()> {
+        if segment.end_ms <= segment.start_ms {
             return Ok(());
         }
 
@@ -150,21 +461,33 @@ impl Database {
                 INSERT INTO segments (
                   start_ts,
                   end_ts,
+                  start_ms,
+                  end_ms,
                   app_id,
                   title_id,
                   is_idle,
                   pid,
-                  pid_create_time
+                  pid_create_time,
+                  process_args,
+                  url_id,
+                  is_call,
+                  monitor_name
                 )
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
                 params![
                     segment.start_ts,
                     segment.end_ts,
+                    segment.start_ms,
+                    segment.end_ms,
                     segment.app_id,
                     segment.title_id,
                     bool_to_i64(segment.is_idle),
                     segment.pid.map(i64::from),
                     segment.pid_create_time.map(|v| v as i64),
+                    segment.process_args,
+                    segment.url_id,
+                    bool_to_i64(segment.is_call),
+                    segment.monitor,
                 ],
             )
             .context("failed to insert segment")?;
@@ -172,7 +495,32 @@ impl Database {
         Ok(())
     }
 
-    pub fn truncate_active_segments_from(&mut self, cutoff_ts: i64) -> Result<()> {
+    fn publish_live_segment(
+        &self,
+        start_ts: i64,
+        app_id: Option<i64>,
+        title_id: Option<i64>,
+        is_idle: bool,
+    ) -> Result<()> {
+        self.conn
+            .execute(
+                "\
+                INSERT OR REPLACE INTO live_segment (id, start_ts, app_id, title_id, is_idle)
+                VALUES (1, ?1, ?2, ?3, ?4)",
+                params![start_ts, app_id, title_id, bool_to_i64(is_idle)],
+            )
+            .context("failed to publish live segment")?;
+        Ok(())
+    }
+
+    fn clear_live_segment(&self) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM live_segment", [])
+            .context("failed to clear live segment")?;
+        Ok(())
+    }
+
+    fn truncate_active_segments_from(&mut self, cutoff_ms: i64) -> Result<()> {
         let tx = self
             .conn
             .transaction()
@@ -182,19 +530,21 @@ impl Database {
             "\
             DELETE FROM segments
             WHERE is_idle = 0
-              AND start_ts >= ?1",
-            params![cutoff_ts],
+              AND deleted_ts IS NULL
+              AND COALESCE(start_ms, start_ts * 1000) >= ?1",
+            params![cutoff_ms],
         )
         .context("failed to delete active segments after idle cutoff")?;
 
         tx.execute(
             "\
             UPDATE segments
-            SET end_ts = ?1
+            SET end_ms = ?1, end_ts = ?1 / 1000
             WHERE is_idle = 0
-              AND start_ts < ?1
-              AND end_ts > ?1",
-            params![cutoff_ts],
+              AND deleted_ts IS NULL
+              AND COALESCE(start_ms, start_ts * 1000) < ?1
+              AND COALESCE(end_ms, end_ts * 1000) > ?1",
+            params![cutoff_ms],
         )
         .context("failed to trim active segments at idle cutoff")?;
 
@@ -212,3 +562,337 @@ fn bool_to_i64(v: bool) -> i64 {
         0
     }
 }
+
+enum Migration {
+    Sql(&'static str),
+    // Runs inside the migration's transaction, which derefs to the connection.
+    Native(fn(&Connection) -> Result<()>),
+}
+
+// Each entry moves the schema one `user_version` forward; never edit a shipped
+// entry, append a new one instead.
+const MIGRATIONS: &[Migration] = &[
+    Migration::Sql(
+        "\
+        CREATE TABLE IF NOT EXISTS apps (
+          id INTEGER PRIMARY KEY,
+          exe_name TEXT NOT NULL,
+          process_path TEXT NOT NULL,
+          UNIQUE(exe_name, process_path)
+        );
+
+        CREATE TABLE IF NOT EXISTS titles (
+          id INTEGER PRIMARY KEY,
+          title TEXT NOT NULL UNIQUE
+        );
+
+        CREATE TABLE IF NOT EXISTS segments (
+          id INTEGER PRIMARY KEY,
+          start_ts INTEGER NOT NULL,
+          end_ts INTEGER NOT NULL CHECK (end_ts >= start_ts),
+          app_id INTEGER,
+          title_id INTEGER,
+          is_idle INTEGER NOT NULL DEFAULT 0,
+          pid INTEGER,
+          pid_create_time INTEGER,
+          FOREIGN KEY(app_id) REFERENCES apps(id),
+          FOREIGN KEY(title_id) REFERENCES titles(id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_segments_start ON segments(start_ts);
+        CREATE INDEX IF NOT EXISTS idx_segments_app_start ON segments(app_id, start_ts);
+        CREATE INDEX IF NOT EXISTS idx_segments_idle_start ON segments(is_idle, start_ts);
+
+        CREATE TABLE IF NOT EXISTS app_categories (
+          app_key TEXT PRIMARY KEY,
+          category TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS focus_sessions (
+          id INTEGER PRIMARY KEY,
+          started_ts INTEGER NOT NULL,
+          ends_ts INTEGER NOT NULL,
+          stopped_ts INTEGER,
+          minimize_distracting INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS focus_violations (
+          id INTEGER PRIMARY KEY,
+          session_id INTEGER NOT NULL,
+          ts INTEGER NOT NULL,
+          app_key TEXT NOT NULL,
+          title TEXT,
+          FOREIGN KEY(session_id) REFERENCES focus_sessions(id)
+        );"
+    ),
+    // Range queries filter on `end_ts > start AND start_ts < end`, which an index on
+    // start_ts can only bound from above. Tracking the longest segment lets readers
+    // add `start_ts >= start - max_span_secs` and scan just the overlapping slice.
+    Migration::Sql(
+        "\
+        CREATE TABLE IF NOT EXISTS segment_meta (
+          id INTEGER PRIMARY KEY CHECK (id = 1),
+          max_span_secs INTEGER NOT NULL
+        );
+
+        INSERT OR REPLACE INTO segment_meta (id, max_span_secs)
+        SELECT 1, COALESCE(MAX(end_ts - start_ts), 0) FROM segments;
+
+        CREATE TRIGGER IF NOT EXISTS trg_segments_span_insert
+        AFTER INSERT ON segments
+        BEGIN
+          UPDATE segment_meta
+          SET max_span_secs = NEW.end_ts - NEW.start_ts
+          WHERE id = 1 AND max_span_secs < NEW.end_ts - NEW.start_ts;
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS trg_segments_span_update
+        AFTER UPDATE OF start_ts, end_ts ON segments
+        BEGIN
+          UPDATE segment_meta
+          SET max_span_secs = NEW.end_ts - NEW.start_ts
+          WHERE id = 1 AND max_span_secs < NEW.end_ts - NEW.start_ts;
+        END;"
+    ),
+    // Covers every segments column the viewer's range reload reads, so the scan never
+    // touches the table rows; it also subsumes the plain start_ts index.
+    Migration::Sql(
+        "\
+        CREATE INDEX IF NOT EXISTS idx_segments_range_covering
+          ON segments(start_ts, end_ts, app_id, title_id, is_idle);
+        DROP INDEX IF EXISTS idx_segments_start;"
+    ),
+    // Paths are canonicalized before upsert now (long names, lowercase drive). Fold the
+    // rows that older versions recorded separately for the same executable.
+    Migration::Native(merge_duplicate_apps),
+    Migration::Sql(
+        "\
+        CREATE TABLE IF NOT EXISTS app_identities (
+          identity TEXT PRIMARY KEY,
+          app_id INTEGER NOT NULL,
+          FOREIGN KEY(app_id) REFERENCES apps(id)
+        );",
+    ),
+    // The backend only flushes a segment when it closes or rotates; this row mirrors
+    // the open one so the viewer can draw the current activity.
+    Migration::Sql(
+        "\
+        CREATE TABLE IF NOT EXISTS live_segment (
+          id INTEGER PRIMARY KEY CHECK (id = 1),
+          start_ts INTEGER NOT NULL,
+          app_id INTEGER,
+          title_id INTEGER,
+          is_idle INTEGER NOT NULL DEFAULT 0
+        );",
+    ),
+    // Entries for the viewer's notification center.
+    Migration::Sql(
+        "\
+        CREATE TABLE IF NOT EXISTS notifications (
+          id INTEGER PRIMARY KEY,
+          ts INTEGER NOT NULL,
+          kind TEXT NOT NULL,
+          app_key TEXT,
+          message TEXT NOT NULL,
+          read INTEGER NOT NULL DEFAULT 0
+        );",
+    ),
+    // Segments removed from the viewer are kept until purged from its trash. Readers
+    // filter on deleted_ts, so it joins the covering index.
+    Migration::Native(add_segment_deleted_ts),
+    // Audit log of scheduled restrictions: every warning, minimize and close.
+    Migration::Sql(
+        "\
+        CREATE TABLE IF NOT EXISTS restriction_events (
+          id INTEGER PRIMARY KEY,
+          ts INTEGER NOT NULL,
+          rule TEXT NOT NULL,
+          app_key TEXT NOT NULL,
+          title TEXT NOT NULL,
+          action TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_restriction_events_ts ON restriction_events(ts);",
+    ),
+    // Filled only with --capture-command-line, e.g. the folder an editor was opened on.
+    Migration::Sql("ALTER TABLE segments ADD COLUMN process_args TEXT;"),
+    // Browser URLs (--browser-urls), millisecond boundaries beside the whole seconds
+    // (rows recorded before keep NULL and read as seconds), the call flag and the
+    // monitor. The viewer may have added any of them already.
+    Migration::Native(ensure_segment_columns),
+    // Refreshed by the sampling loop; the viewer reads it for the backend's status.
+    Migration::Sql(
+        "\
+        CREATE TABLE IF NOT EXISTS heartbeat (
+          id INTEGER PRIMARY KEY CHECK (id = 1),
+          pid INTEGER NOT NULL,
+          ts INTEGER NOT NULL,
+          paused INTEGER NOT NULL DEFAULT 0
+        );",
+    ),
+    // Stores each app's merge key so `upsert_app` finds a row with one indexed lookup
+    // instead of canonicalizing every stored path. Rows the viewer or an import added
+    // since the merge above are folded the same way first.
+    Migration::Native(add_app_keys),
+];
+
+fn apply_migrations(conn: &mut Connection) -> Result<()> {
+    let version: usize = conn
+        .pragma_query_value(None, "user_version", |row| row.get::<_, i64>(0))
+        .context("failed to read schema version")?
+        .try_into()
+        .unwrap_or(0);
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn
+            .transaction()
+            .context("failed to start migration transaction")?;
+        match migration {
+            Migration::Sql(sql) => tx.execute_batch(sql).map_err(anyhow::Error::from),
+            Migration::Native(apply) => apply(&tx),
+        }
+        .with_context(|| format!("failed to apply schema migration {}", index + 1))?;
+        tx.pragma_update(None, "user_version", (index + 1) as i64)
+            .context("failed to update schema version")?;
+        tx.commit().context("failed to commit schema migration")?;
+    }
+    Ok(())
+}
+
+// The viewer adds deleted_ts itself when it opens a database before the backend does.
+fn add_segment_deleted_ts(tx: &Connection) -> Result<()> {
+    if !has_column(tx, "segments", "deleted_ts")? {
+        tx.execute_batch("ALTER TABLE segments ADD COLUMN deleted_ts INTEGER;")
+            .context("failed to add segments.deleted_ts")?;
+    }
+    tx.execute_batch(
+        "\
+        DROP INDEX IF EXISTS idx_segments_range_covering;
+        CREATE INDEX idx_segments_range_covering
+          ON segments(start_ts, end_ts, app_id, title_id, is_idle, deleted_ts);",
+    )
+    .context("failed to rebuild the covering range index")
+}
+
+fn add_app_keys(tx: &Connection) -> Result<()> {
+    if !has_column(tx, "apps", "app_key")? {
+        tx.execute_batch("ALTER TABLE apps ADD COLUMN app_key TEXT;")
+            .context("failed to add apps.app_key")?;
+    }
+    tx.execute_batch("CREATE UNIQUE INDEX IF NOT EXISTS idx_apps_app_key ON apps(app_key);")
+        .context("failed to index app keys")?;
+    assign_app_keys(tx)
+}
+
+// Keys the rows written without one (all of them at the migration, later only those
+// the viewer or an import added), folding each into an older row with the same key.
+fn assign_app_keys(conn: &Connection) -> Result<()> {
+    let mut stmt = conn
+        .prepare_cached("SELECT id, exe_name, process_path FROM apps WHERE app_key IS NULL ORDER BY id")
+        .context("failed to prepare unkeyed apps query")?;
+    let unkeyed = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))
+        .context("failed to query unkeyed apps")?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read unkeyed apps")?;
+    for (id, exe_name, process_path) in unkeyed {
+        let key = app_merge_key(&exe_name, &process_path);
+        let keep_id = conn
+            .query_row("SELECT id FROM apps WHERE app_key = ?1", params![key], |row| row.get::<_, i64>(0))
+            .optional()
+            .context("failed to look up app key")?;
+        let Some(keep_id) = keep_id else {
+            conn.execute("UPDATE apps SET app_key = ?1 WHERE id = ?2", params![key, id])
+                .context("failed to store app key")?;
+            continue;
+        };
+        conn.execute(
+            "UPDATE app_identities SET app_id = ?1 WHERE app_id = ?2",
+            params![keep_id, id],
+        )
+        .context("failed to repoint app identity")?;
+        merge_app_rows(conn, keep_id, &[id])?;
+    }
+    Ok(())
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info({table})"))
+        .with_context(|| format!("failed to read the {table} columns"))?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .with_context(|| format!("failed to read the {table} columns"))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(names.iter().any(|name| name == column))
+}
+
+fn load_apps(conn: &Connection) -> Result<Vec<(i64, String, String)>> {
+    let mut stmt = conn
+        .prepare("SELECT id, exe_name, process_path FROM apps ORDER BY id")
+        .context("failed to prepare apps query")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .context("failed to query apps")?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read apps")
+}
+
+fn merge_app_rows(tx: &Connection, keep_id: i64, duplicate_ids: &[i64]) -> Result<()> {
+    for duplicate_id in duplicate_ids {
+        tx.execute(
+            "UPDATE segments SET app_id = ?1 WHERE app_id = ?2",
+            params![keep_id, duplicate_id],
+        )
+        .context("failed to repoint segments to merged app")?;
+        tx.execute("DELETE FROM apps WHERE id = ?1", params![duplicate_id])
+            .context("failed to delete duplicate app")?;
+    }
+    Ok(())
+}
+
+fn canonical_app(exe_name: &str, process_path: &str) -> (String, String) {
+    let canonical_path = canonicalize_process_path(process_path);
+    let canonical_exe = Path::new(&canonical_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| exe_name.to_owned());
+    (canonical_exe, canonical_path)
+}
+
+// Rows with the same key are one app, whatever the case of their exe and path.
+fn app_merge_key(exe_name: &str, process_path: &str) -> String {
+    let (canonical_exe, canonical_path) = canonical_app(exe_name, process_path);
+    format!("{}|{}", canonical_exe.to_lowercase(), canonical_path.to_lowercase())
+}
+
+fn merge_duplicate_apps(tx: &Connection) -> Result<()> {
+    let apps = load_apps(tx)?;
+
+    let mut groups: HashMap<String, Vec<(i64, String, String)>> = HashMap::new();
+    for (id, exe_name, process_path) in apps {
+        let (canonical_exe, canonical_path) = canonical_app(&exe_name, &process_path);
+        groups
+            .entry(app_merge_key(&exe_name, &process_path))
+            .or_default()
+            .push((id, canonical_exe, canonical_path));
+    }
+
+    for rows in groups.values() {
+        let Some((keep_id, exe_name, process_path)) = rows.first() else {
+            continue;
+        };
+        let duplicate_ids: Vec<i64> = rows[1..].iter().map(|(id, _, _)| *id).collect();
+        merge_app_rows(tx, *keep_id, &duplicate_ids)?;
+        tx.execute(
+            "UPDATE apps SET exe_name = ?1, process_path = ?2 WHERE id = ?3",
+            params![exe_name, process_path, keep_id],
+        )
+        .context("failed to store canonical app path")?;
+    }
+    Ok(())
+}
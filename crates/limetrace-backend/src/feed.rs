@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::clock;
+use crate::db::Database;
+use crate::format_short_duration;
+use crate::monitor::{ActivityKind, ActivitySample};
+
+const WRITE_EVERY: Duration = Duration::from_secs(15);
+// App switches show up quickly, but a flurry of them doesn't rewrite the file each poll.
+const MIN_WRITE_INTERVAL: Duration = Duration::from_secs(2);
+const TOP_APPS: usize = 5;
+
+pub enum FeedOutput {
+    /// `<dir>/today.json`.
+    Json(PathBuf),
+    /// A Rainmeter-style INI file (UTF-16LE with BOM, which both `@Include` and
+    /// WebParser with `CodePage=1200` read). Everything sits in a `[LimeTrace]` section:
+    ///
+    /// - `Date`: local date, `YYYY-MM-DD`
+    /// - `Updated`: unix time of the write; a value older than a minute means the
+    ///   backend is paused or stopped
+    /// - `ActiveSecs`, `Active`: active time today, in seconds and as `3h 12m`
+    /// - `FocusSecs`, `Focus`, `FocusActive` (`0`/`1`): focus session time today
+    /// - `CurrentApp`: foreground app, empty while idle
+    /// - `CurrentIdle` (`0`/`1`), `CurrentSecs`, `Current`: time in the current activity
+    /// - `TopApp1`..`TopApp5` with `TopApp<n>Secs` and `TopApp<n>Time`: today's top apps,
+    ///   empty past the last one
+    RainmeterIni(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CurrentActivity {
+    app: Option<String>,
+    idle: bool,
+    since_ts: i64,
+}
+
+/// Keeps small files with today's stats current, for phone widgets, a status page
+/// on a NAS or desktop widgets.
+pub struct TodayFeed {
+    outputs: Vec<FeedOutput>,
+    current: Option<CurrentActivity>,
+    written_at: Option<Instant>,
+    dirty: bool,
+}
+
+impl TodayFeed {
+    pub fn new(outputs: Vec<FeedOutput>) -> Self {
+        Self {
+            outputs,
+            current: None,
+            written_at: None,
+            dirty: true,
+        }
+    }
+
+    pub fn observe(&mut self, db: &Database, sample: &ActivitySample) {
+        let (app, idle) = match &sample.kind {
+            ActivityKind::Idle { .. } => (None, true),
+            ActivityKind::Active(window) => (Some(display_app_name(&window.exe_name)), false),
+        };
+        let changed = self
+            .current
+            .as_ref()
+            .is_none_or(|current| current.app != app || current.idle != idle);
+        if changed {
+            self.current = Some(CurrentActivity {
+                app,
+                idle,
+                since_ts: sample.ts(),
+            });
+            self.dirty = true;
+        }
+        let due = match self.written_at {
+            None => true,
+            Some(written_at) if self.dirty => written_at.elapsed() >= MIN_WRITE_INTERVAL,
+            Some(written_at) => written_at.elapsed() >= WRITE_EVERY,
+        };
+        if !due {
+            return;
+        }
+        self.written_at = Some(Instant::now());
+        self.dirty = false;
+        if let Err(err) = self.write(db, sample.ts()) {
+            eprintln!("today feed error: {err:#}");
+        }
+    }
+
+    fn write(&self, db: &Database, now: i64) -> Result<()> {
+        let local = clock::local_now();
+        let date = format!("{:04}-{:02}-{:02}", local.year, local.month, local.day);
+        let day_start = now - local.secs_since_midnight;
+        let totals = db.activity_totals(day_start, now)?;
+        let focus_secs = db.focus_secs(day_start, now)?;
+        let focus_active = db.active_focus_session(now)?.is_some();
+        let top_apps: Vec<(String, i64)> = totals
+            .by_app
+            .iter()
+            .take(TOP_APPS)
+            .map(|(exe_name, secs)| (display_app_name(exe_name), *secs))
+            .collect();
+        for output in &self.outputs {
+            let (path, contents) = match output {
+                FeedOutput::Json(folder) => {
+                    let feed = json!({
+                        "date": date,
+                        "updated_ts": now,
+                        "active_secs": totals.active_secs,
+                        "focus_secs": focus_secs,
+                        "focus_active": focus_active,
+                        "top_apps": top_apps
+                            .iter()
+                            .map(|(app, secs)| json!({ "app": app, "secs": secs }))
+                            .collect::<Vec<_>>(),
+                        "current": self.current.as_ref().map(|current| json!({
+                            "app": current.app,
+                            "idle": current.idle,
+                            "since_ts": current.since_ts,
+                        })),
+                    });
+                    (folder.join("today.json"), feed.to_string().into_bytes())
+                }
+                FeedOutput::RainmeterIni(path) => {
+                    let mut ini = String::from("[LimeTrace]\r\n");
+                    let mut line = |key: &str, value: &str| {
+                        ini.push_str(&format!("{key}={}\r\n", value.replace(['\r', '\n'], " ")));
+                    };
+                    line("Date", &date);
+                    line("Updated", &now.to_string());
+                    line("ActiveSecs", &totals.active_secs.to_string());
+                    line("Active", &format_short_duration(totals.active_secs));
+                    line("FocusSecs", &focus_secs.to_string());
+                    line("Focus", &format_short_duration(focus_secs));
+                    line("FocusActive", if focus_active { "1" } else { "0" });
+                    let current_secs = self.current.as_ref().map_or(0, |current| now - current.since_ts);
+                    line(
+                        "CurrentApp",
+                        self.current.as_ref().and_then(|current| current.app.as_deref()).unwrap_or(""),
+                    );
+                    line(
+                        "CurrentIdle",
+                        if self.current.as_ref().is_some_and(|current| current.idle) { "1" } else { "0" },
+                    );
+                    line("CurrentSecs", &current_secs.to_string());
+                    line("Current", &format_short_duration(current_secs));
+                    for rank in 0..TOP_APPS {
+                        let (app, secs) = top_apps
+                            .get(rank)
+                            .map_or(("", None), |(app, secs)| (app.as_str(), Some(*secs)));
+                        let n = rank + 1;
+                        line(&format!("TopApp{n}"), app);
+                        line(&format!("TopApp{n}Secs"), &secs.map(|secs| secs.to_string()).unwrap_or_default());
+                        line(&format!("TopApp{n}Time"), &secs.map(format_short_duration).unwrap_or_default());
+                    }
+                    let mut bytes = vec![0xFF, 0xFE];
+                    bytes.extend(ini.encode_utf16().flat_map(u16::to_le_bytes));
+                    (path.clone(), bytes)
+                }
+            };
+            write_replacing(&path, &contents)?;
+        }
+        Ok(())
+    }
+}
+
+// Readers polling the file never see a half-written one.
+fn write_replacing(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder).with_context(|| format!("failed to create {}", folder.display()))?;
+    }
+    let mut partial_path = path.as_os_str().to_owned();
+    partial_path.push(".tmp");
+    let partial_path = PathBuf::from(partial_path);
+    fs::write(&partial_path, contents).with_context(|| format!("failed to write {}", partial_path.display()))?;
+    fs::rename(&partial_path, path).with_context(|| format!("failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+fn display_app_name(exe_name: &str) -> String {
+    exe_name
+        .strip_suffix(".exe")
+        .or_else(|| exe_name.strip_suffix(".EXE"))
+        .unwrap_or(exe_name)
+        .to_owned()
+}
@@ -0,0 +1,68 @@
+use std::collections::{HashMap, HashSet};
+
+use limetrace_core::apps::LOCKED_PROCESS_PATH;
+
+use crate::db::Database;
+use crate::focus::normalize_app_key;
+use crate::monitor::{ActivityKind, ActivitySample};
+use crate::notify::{self, Notification};
+
+// Brief flashes (installers, UAC prompts, splash screens) shouldn't raise an entry.
+const FIRST_USE_MIN_SECS: i64 = 60;
+const MAX_SAMPLE_GAP_SECS: i64 = 10;
+
+/// Raises a notification once an executable that had never been recorded before
+/// has been in the foreground for a minute.
+pub struct FirstUseWatcher {
+    known: HashSet<String>,
+    pending_secs: HashMap<String, i64>,
+    last_sample_ts: Option<i64>,
+}
+
+impl FirstUseWatcher {
+    pub fn new(db: &Database) -> Self {
+        let known = match db.recorded_exe_names() {
+            Ok(names) => names.iter().map(|name| normalize_app_key(name)).collect(),
+            Err(err) => {
+                eprintln!("first-use watcher error: {err:#}");
+                HashSet::new()
+            }
+        };
+        Self {
+            known,
+            pending_secs: HashMap::new(),
+            last_sample_ts: None,
+        }
+    }
+
+    pub fn observe(&mut self, db: &Database, sample: &ActivitySample) {
+        let elapsed = self
+            .last_sample_ts
+            .map_or(0, |last| (sample.ts() - last).clamp(0, MAX_SAMPLE_GAP_SECS));
+        self.last_sample_ts = Some(sample.ts());
+        let ActivityKind::Active(window) = &sample.kind else {
+            return;
+        };
+        let app_key = normalize_app_key(&window.exe_name);
+        if app_key.is_empty() || window.process_path == LOCKED_PROCESS_PATH || self.known.contains(&app_key) {
+            return;
+        }
+        let secs = self.pending_secs.entry(app_key.clone()).or_insert(0);
+        *secs += elapsed;
+        if *secs < FIRST_USE_MIN_SECS {
+            return;
+        }
+        self.pending_secs.remove(&app_key);
+        self.known.insert(app_key.clone());
+
+        let message = format!("New app in use: {}", window.exe_name);
+        if let Err(err) = db.insert_notification(sample.ts(), "new_app", Some(&app_key), &message) {
+            eprintln!("notification insert error: {err:#}");
+        }
+        notify::show(Notification {
+            title: "LimeTrace".to_owned(),
+            body: format!("{message}. Open LimeTrace to categorize, rename or exclude it."),
+            viewer_args: Vec::new(),
+        });
+    }
+}
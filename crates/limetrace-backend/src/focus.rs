@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::db::{Database, FocusSession};
+use crate::monitor::{self, ActivityKind, ActivitySample};
+use crate::notify::{self, Notification};
+
+const REFRESH_EVERY: Duration = Duration::from_secs(5);
+
+/// Watches foreground changes while the viewer has a focus session running.
+pub struct FocusGuard {
+    session: Option<FocusSession>,
+    distracting: HashSet<String>,
+    refreshed_at: Option<Instant>,
+    last_app_key: Option<String>,
+}
+
+impl FocusGuard {
+    pub fn new() -> Self {
+        Self {
+            session: None,
+            distracting: HashSet::new(),
+            refreshed_at: None,
+            last_app_key: None,
+        }
+    }
+
+    pub fn observe(&mut self, db: &Database, sample: &ActivitySample) {
+        self.refresh(db, sample.ts());
+        let ActivityKind::Active(window) = &sample.kind else {
+            self.last_app_key = None;
+            return;
+        };
+        let app_key = normalize_app_key(&window.exe_name);
+        if self.last_app_key.as_deref() == Some(app_key.as_str()) {
+            return;
+        }
+        self.last_app_key = Some(app_key.clone());
+
+        let Some(session) = &self.session else {
+            return;
+        };
+        if !self.distracting.contains(&app_key) {
+            return;
+        }
+        if let Err(err) =
+            db.insert_focus_violation(session.id, sample.ts(), &app_key, &window.window_title)
+        {
+            eprintln!("focus violation error: {err:#}");
+        }
+        if session.minimize_distracting {
+            monitor::minimize_foreground_window();
+        }
+        notify::show(Notification {
+            title: "LimeTrace focus session".to_owned(),
+            body: format!("{} is marked as distracting", window.exe_name),
+            viewer_args: Vec::new(),
+        });
+    }
+
+    fn refresh(&mut self, db: &Database, now_ts: i64) {
+        if self
+            .refreshed_at
+            .is_some_and(|refreshed_at| refreshed_at.elapsed() < REFRESH_EVERY)
+        {
+            return;
+        }
+        self.refreshed_at = Some(Instant::now());
+        match db.active_focus_session(now_ts) {
+            Ok(session) => self.session = session,
+            Err(err) => eprintln!("focus session error: {err:#}"),
+        }
+        if self.session.is_none() {
+            self.distracting.clear();
+            return;
+        }
+        match db.distracting_app_keys() {
+            Ok(keys) => self.distracting = keys.into_iter().collect(),
+            Err(err) => eprintln!("focus categories error: {err:#}"),
+        }
+    }
+}
+
+// Mirrors the viewer's app key so categories set there match here.
+pub fn normalize_app_key(exe_name: &str) -> String {
+    let lower = exe_name.trim().to_ascii_lowercase();
+    lower.strip_suffix(".exe").unwrap_or(&lower).to_owned()
+}
@@ -0,0 +1,114 @@
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io;
+
+#[cfg(windows)]
+use windows_sys::Win32::Storage::FileSystem::{
+    GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
+};
+
+/// How executables map to app rows. `Path` keys on the full path as before; the
+/// other strategies let a portable app keep one identity when its folder moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppIdentityStrategy {
+    Path,
+    Product,
+    Hash,
+}
+
+impl AppIdentityStrategy {
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "path" => Some(Self::Path),
+            "product" => Some(Self::Product),
+            "hash" => Some(Self::Hash),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `None` when the strategy is `Path` or the executable has no usable
+/// identity (no version resource, unreadable file), so callers fall back to the path.
+pub fn app_identity(strategy: AppIdentityStrategy, process_path: &str) -> Option<String> {
+    if process_path.starts_with('<') {
+        return None;
+    }
+    match strategy {
+        AppIdentityStrategy::Path => None,
+        AppIdentityStrategy::Product => product_identity(process_path),
+        AppIdentityStrategy::Hash => file_hash(process_path).map(|hash| format!("sha256:{hash}")),
+    }
+}
+
+// Linux executables carry no version resource; `Product` falls back to the path there.
+#[cfg(not(windows))]
+fn product_identity(_process_path: &str) -> Option<String> {
+    None
+}
+
+#[cfg(windows)]
+fn product_identity(process_path: &str) -> Option<String> {
+    let wide_path = wide(process_path);
+    let mut handle = 0;
+    let size = unsafe { GetFileVersionInfoSizeW(wide_path.as_ptr(), &mut handle) };
+    if size == 0 {
+        return None;
+    }
+    let mut data = vec![0_u8; size as usize];
+    if unsafe { GetFileVersionInfoW(wide_path.as_ptr(), 0, size, data.as_mut_ptr().cast()) } == 0 {
+        return None;
+    }
+
+    let translation = query_value(&data, "\\VarFileInfo\\Translation")?;
+    if translation.len() < 4 {
+        return None;
+    }
+    let language = u16::from_le_bytes([translation[0], translation[1]]);
+    let code_page = u16::from_le_bytes([translation[2], translation[3]]);
+    let prefix = format!("\\StringFileInfo\\{language:04x}{code_page:04x}");
+    let product = query_string(&data, &format!("{prefix}\\ProductName"))?;
+    let company = query_string(&data, &format!("{prefix}\\CompanyName")).unwrap_or_default();
+    Some(format!("product:{}|{}", company.to_lowercase(), product.to_lowercase()))
+}
+
+#[cfg(windows)]
+fn query_raw(data: &[u8], sub_block: &str) -> Option<(*const u8, usize)> {
+    let sub_block = wide(sub_block);
+    let mut value = std::ptr::null_mut();
+    let mut len = 0_u32;
+    let ok = unsafe { VerQueryValueW(data.as_ptr().cast(), sub_block.as_ptr(), &mut value, &mut len) };
+    if ok == 0 || value.is_null() || len == 0 {
+        return None;
+    }
+    Some((value as *const u8, len as usize))
+}
+
+#[cfg(windows)]
+fn query_value<'a>(data: &'a [u8], sub_block: &str) -> Option<&'a [u8]> {
+    let (value, len) = query_raw(data, sub_block)?;
+    let start = (value as usize).checked_sub(data.as_ptr() as usize)?;
+    data.get(start..start + len)
+}
+
+#[cfg(windows)]
+fn query_string(data: &[u8], sub_block: &str) -> Option<String> {
+    // String values report their length in UTF-16 units, including the terminator.
+    let (value, len) = query_raw(data, sub_block)?;
+    let units = unsafe { std::slice::from_raw_parts(value as *const u16, len) };
+    let text = String::from_utf16_lossy(units);
+    let text = text.trim_end_matches('\0').trim();
+    (!text.is_empty()).then(|| text.to_owned())
+}
+
+// SHA-256 of the executable's contents, so two different builds can't end up
+// sharing an identity the way they could under a short non-cryptographic hash.
+fn file_hash(process_path: &str) -> Option<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(process_path).ok()?, &mut hasher).ok()?;
+    Some(hasher.finalize().iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+#[cfg(windows)]
+fn wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(std::iter::once(0)).collect()
+}
@@ -0,0 +1,565 @@
+//! The recording backend. `main.rs` only calls [`run`]; the store is public so
+//! tests can drive the real schema and migrations.
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+compile_error!("LimeTrace Backend supports Windows, Linux and macOS.");
+
+mod browsers;
+mod clock;
+mod config;
+mod control;
+pub mod db;
+mod feed;
+mod first_use;
+mod focus;
+mod identity;
+mod monitor;
+mod notify;
+mod restrict;
+mod rules;
+mod sink;
+mod slack;
+mod status;
+
+use anyhow::{Context, Result};
+use limetrace_core::crash;
+use limetrace_core::recorder::{Recorder, SegmentSink};
+use limetrace_core::update;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+#[cfg(windows)]
+use windows_sys::Win32::Foundation::{CloseHandle, GetLastError, HANDLE};
+#[cfg(windows)]
+use windows_sys::Win32::System::Threading::CreateMutexW;
+
+use crate::browsers::BrowserProfiles;
+use crate::config::{Config, ConfigWatcher};
+use crate::control::ControlState;
+use crate::db::Database;
+use crate::feed::{FeedOutput, TodayFeed};
+use crate::first_use::FirstUseWatcher;
+use crate::focus::FocusGuard;
+use crate::monitor::PlatformMonitor;
+use crate::notify::Notification;
+use crate::restrict::RestrictionGuard;
+use crate::sink::{EchoSink, JsonlSink};
+use crate::slack::SlackStatusSync;
+use crate::status::StatusBoard;
+
+const UPDATE_CHECK_EVERY: Duration = Duration::from_secs(10);
+
+pub fn run() -> Result<()> {
+    if std::env::args().any(|arg| arg == "--check-config") {
+        std::process::exit(if config::check() { 0 } else { 1 });
+    }
+    let mut config = Config::from_args()?;
+    crash::install(crash::logs_dir(&config.db_path), "limetrace-backend", env!("CARGO_PKG_VERSION"));
+    if config.print_status {
+        println!("{}", status::query()?);
+        return Ok(());
+    }
+    if config.merge_app_identities {
+        let mut db = Database::open(&config.db_path)?;
+        let merged = db.merge_apps_by_identity(config.app_identity)?;
+        eprintln!("merged {merged} app row(s) by identity");
+        return Ok(());
+    }
+
+    if config.dry_run {
+        return run_dry(config);
+    }
+
+    let instance_guard = match acquire_single_instance_guard() {
+        Ok(Some(guard)) => guard,
+        Ok(None) => {
+            return Ok(());
+        }
+        Err(err) => {
+            eprintln!("single-instance guard error: {err:#}");
+            return Ok(());
+        }
+    };
+
+    if let Some(parent) = config.db_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create db parent directory: {}", parent.display()))?;
+    }
+
+    let current_exe = std::env::current_exe().ok();
+    let update_dir = update::staging_dir(&config.db_path);
+    if let Some(exe) = &current_exe {
+        update::remove_replaced(exe);
+    }
+
+    let db = Database::open(&config.db_path)?;
+    let mut monitor = PlatformMonitor::new(config.idle_threshold, config.app_identity);
+    monitor.set_media_suppresses_idle(config.media_suppresses_idle);
+    monitor.set_capture_command_line(config.capture_command_line);
+    monitor.set_url_capture(config.browser_urls);
+    let mut recorder = Recorder::new(db, config.rotate_segment_every);
+    recorder.set_max_sample_gap(max_sample_gap(config.poll_interval));
+    if let Some(jsonl_path) = &config.jsonl_path {
+        recorder = recorder.with_sink(Box::new(JsonlSink::open(jsonl_path)?));
+    }
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_signal = Arc::clone(&shutdown);
+    if let Err(err) = ctrlc::set_handler(move || {
+        shutdown_signal.store(true, Ordering::SeqCst);
+    }) {
+        eprintln!("ctrlc handler registration warning: {err}");
+    }
+
+    eprintln!(
+        "LimeTrace Backend started | config={} | db={} | poll={}ms | idle={}s | rotate={}s | jsonl={}",
+        config
+            .config_path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "none".to_owned()),
+        config.db_path.display(),
+        duration_millis(config.poll_interval),
+        config.idle_threshold.as_secs(),
+        config.rotate_segment_every.as_secs(),
+        config
+            .jsonl_path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "off".to_owned())
+    );
+    crash::note("backend started");
+
+    let control_state = Arc::new(ControlState {
+        paused: AtomicBool::new(false),
+    });
+    if let (Some(port), Some(token)) = (config.http_port, config.http_token.clone()) {
+        control::spawn(port, token, config.db_path.clone(), Arc::clone(&control_state))?;
+        eprintln!("control endpoints listening on 127.0.0.1:{port}");
+    }
+    let status_board = Arc::new(StatusBoard::new(config.db_path.clone()));
+    if let Err(err) = status::spawn(Arc::clone(&status_board), Arc::clone(&control_state)) {
+        eprintln!("status endpoint warning: {err:#}");
+    }
+
+    let mut last_summary_day = None;
+    let mut focus_guard = FocusGuard::new();
+    let mut restriction_guard = RestrictionGuard::new();
+    let mut browser_profiles = BrowserProfiles::default();
+    let mut first_use_watcher = FirstUseWatcher::new(recorder.store());
+    let mut slack_sync = config.slack.clone().map(SlackStatusSync::new);
+    let mut today_feed = today_feed_for(&config);
+    let mut config_watcher = config
+        .config_path
+        .clone()
+        .or_else(config::default_config_path)
+        .map(ConfigWatcher::new);
+    let mut was_paused = false;
+    let mut was_excluded = false;
+    let mut heartbeat_failing = false;
+    let mut update_checked_at = Instant::now();
+    let mut updated_to = None;
+    while !shutdown.load(Ordering::Relaxed) {
+        // The viewer stages updates; swapping here keeps the tracking gap to a restart.
+        let update_due = updated_to.is_none() && update_checked_at.elapsed() >= UPDATE_CHECK_EVERY;
+        if let Some(exe) = current_exe.as_deref().filter(|_| update_due) {
+            update_checked_at = Instant::now();
+            let public_key = update::UPDATE_PUBLIC_KEY.filter(|_| update::is_staged_for(&update_dir, exe));
+            if let Some(public_key) = public_key {
+                match update::apply_staged(&update_dir, exe, env!("CARGO_PKG_VERSION"), public_key) {
+                    Ok(Some(version)) => {
+                        eprintln!("updated to {version}; restarting");
+                        crash::note(format!("updated to {version}"));
+                        updated_to = Some(version);
+                        break;
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        eprintln!("update failed, keeping the current version: {err:#}");
+                        crash::note(format!("update failed: {err:#}"));
+                    }
+                }
+            }
+        }
+        if config_watcher.as_mut().is_some_and(ConfigWatcher::changed) {
+            match Config::from_args() {
+                Ok(reloaded) => {
+                    apply_reloaded_config(
+                        &mut config,
+                        reloaded,
+                        &mut monitor,
+                        &mut recorder,
+                        &mut today_feed,
+                        &mut slack_sync,
+                    );
+                    eprintln!("config reloaded");
+                    crash::note("config reloaded");
+                }
+                Err(err) => {
+                    eprintln!("config reload error, keeping previous settings: {err:#}");
+                    crash::note(format!("config reload error: {err:#}"));
+                }
+            }
+        }
+        let paused = control_state.paused.load(Ordering::Relaxed)
+            || !config.rules.in_schedule(&clock::local_now());
+        match recorder.store().write_heartbeat(std::process::id(), unix_seconds_now(), paused) {
+            Ok(()) => heartbeat_failing = false,
+            Err(err) if !heartbeat_failing => {
+                heartbeat_failing = true;
+                eprintln!("heartbeat error: {err:#}");
+            }
+            Err(_) => {}
+        }
+        if paused != was_paused {
+            was_paused = paused;
+            if paused {
+                if let Err(err) = recorder.pause(unix_seconds_now()) {
+                    eprintln!("pause flush error: {err:#}");
+                }
+            } else {
+                recorder.resume(unix_seconds_now());
+            }
+            eprintln!("tracking {}", if paused { "paused" } else { "resumed" });
+            crash::note(if paused { "tracking paused" } else { "tracking resumed" });
+        }
+        if paused {
+            thread::sleep(config.poll_interval);
+            continue;
+        }
+        if let Some(suspended_at) = monitor.take_suspended_at() {
+            if let Err(err) = recorder.suspend(suspended_at, unix_seconds_now()) {
+                eprintln!("suspend flush error: {err:#}");
+            }
+            eprintln!("resumed from sleep");
+            crash::note("resumed from sleep");
+        }
+        let mut captured = monitor.capture();
+        if config.split_browser_profiles {
+            browser_profiles.split(&mut captured);
+        }
+        let sample = config.rules.apply(captured);
+        // Excluded apps leave a gap, the same as a pause.
+        if sample.is_none() != was_excluded {
+            was_excluded = sample.is_none();
+            crash::note(if was_excluded { "excluded app in front" } else { "excluded app left" });
+            if was_excluded {
+                if let Err(err) = recorder.pause(unix_seconds_now()) {
+                    eprintln!("exclusion flush error: {err:#}");
+                }
+            } else {
+                recorder.resume(unix_seconds_now());
+            }
+        }
+        let Some(sample) = sample else {
+            thread::sleep(config.poll_interval);
+            continue;
+        };
+        focus_guard.observe(recorder.store(), &sample);
+        restriction_guard.observe(recorder.store(), &sample, &config.restrictions);
+        first_use_watcher.observe(recorder.store(), &sample);
+        if let Some(slack_sync) = slack_sync.as_mut() {
+            slack_sync.observe(recorder.store(), &sample);
+        }
+        if let Some(today_feed) = today_feed.as_mut() {
+            today_feed.observe(recorder.store(), &sample);
+        }
+        let observed = sample.clone();
+        if let Err(err) = recorder.ingest(sample) {
+            eprintln!("ingest error: {err:#}");
+            crash::note(format!("ingest error: {err:#}"));
+        }
+        status_board.observe(&observed, recorder.last_write_ts());
+        if let Some(summary_at_secs) = config.daily_summary_at_secs {
+            maybe_show_daily_summary(&recorder, summary_at_secs, &mut last_summary_day);
+        }
+        monitor.wait(config.poll_interval);
+    }
+
+    if let Some(slack_sync) = slack_sync.as_mut() {
+        slack_sync.clear();
+    }
+    recorder.flush_and_close(unix_seconds_now())?;
+    if let Err(err) = recorder.store().clear_heartbeat() {
+        eprintln!("heartbeat error: {err:#}");
+    }
+    eprintln!("LimeTrace Backend stopped");
+    if let (Some(_), Some(exe)) = (updated_to, &current_exe) {
+        drop(instance_guard);
+        update::relaunch(exe)?;
+    }
+    Ok(())
+}
+
+// Runs beside a normal backend: no instance guard, and nothing but the sampling and
+// the rules, so exclusions and title redaction can be tried out safely.
+fn run_dry(config: Config) -> Result<()> {
+    let mut monitor = PlatformMonitor::new(config.idle_threshold, config.app_identity);
+    monitor.set_media_suppresses_idle(config.media_suppresses_idle);
+    monitor.set_capture_command_line(config.capture_command_line);
+    monitor.set_url_capture(config.browser_urls);
+    let mut recorder =
+        Recorder::new(Database::open_in_memory()?, config.rotate_segment_every).with_sink(Box::new(EchoSink));
+    recorder.set_max_sample_gap(max_sample_gap(config.poll_interval));
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_signal = Arc::clone(&shutdown);
+    if let Err(err) = ctrlc::set_handler(move || {
+        shutdown_signal.store(true, Ordering::SeqCst);
+    }) {
+        eprintln!("ctrlc handler registration warning: {err}");
+    }
+    eprintln!(
+        "LimeTrace Backend dry run | poll={}ms | idle={}s | rotate={}s | nothing is written; Ctrl+C stops",
+        duration_millis(config.poll_interval),
+        config.idle_threshold.as_secs(),
+        config.rotate_segment_every.as_secs()
+    );
+
+    let mut was_outside_schedule = false;
+    let mut excluded_app: Option<String> = None;
+    let mut browser_profiles = BrowserProfiles::default();
+    while !shutdown.load(Ordering::Relaxed) {
+        let outside_schedule = !config.rules.in_schedule(&clock::local_now());
+        if outside_schedule != was_outside_schedule {
+            was_outside_schedule = outside_schedule;
+            if outside_schedule {
+                recorder.pause(unix_seconds_now())?;
+                println!("outside the schedule; not recording");
+            } else {
+                recorder.resume(unix_seconds_now());
+                println!("schedule window started");
+            }
+        }
+        if outside_schedule {
+            thread::sleep(config.poll_interval);
+            continue;
+        }
+        let mut captured = monitor.capture();
+        if config.split_browser_profiles {
+            browser_profiles.split(&mut captured);
+        }
+        let captured_app = match &captured.kind {
+            monitor::ActivityKind::Active(window) => Some(window.exe_name.clone()),
+            monitor::ActivityKind::Idle { .. } => None,
+        };
+        match config.rules.apply(captured) {
+            Some(sample) => {
+                if excluded_app.take().is_some() {
+                    recorder.resume(unix_seconds_now());
+                }
+                recorder.ingest(sample)?;
+            }
+            None => {
+                if excluded_app != captured_app {
+                    recorder.pause(unix_seconds_now())?;
+                    println!("excluded: {}", captured_app.as_deref().unwrap_or("?"));
+                    excluded_app = captured_app;
+                }
+            }
+        }
+        monitor.wait(config.poll_interval);
+    }
+    recorder.flush_and_close(unix_seconds_now())
+}
+
+fn today_feed_for(config: &Config) -> Option<TodayFeed> {
+    let outputs: Vec<FeedOutput> = config
+        .today_feed_dir
+        .clone()
+        .map(FeedOutput::Json)
+        .into_iter()
+        .chain(config.widget_ini_path.clone().map(FeedOutput::RainmeterIni))
+        .collect();
+    (!outputs.is_empty()).then(|| TodayFeed::new(outputs))
+}
+
+fn apply_reloaded_config(
+    config: &mut Config,
+    mut reloaded: Config,
+    monitor: &mut PlatformMonitor,
+    recorder: &mut Recorder<Database>,
+    today_feed: &mut Option<TodayFeed>,
+    slack_sync: &mut Option<SlackStatusSync>,
+) {
+    // The open database and the control port stay as they were started.
+    if reloaded.db_path != config.db_path {
+        eprintln!("config: db change takes effect after a restart");
+        reloaded.db_path = config.db_path.clone();
+    }
+    if reloaded.http_port != config.http_port || reloaded.http_token != config.http_token {
+        eprintln!("config: http-port and http-token changes take effect after a restart");
+        reloaded.http_port = config.http_port;
+        reloaded.http_token = config.http_token.clone();
+    }
+    monitor.set_idle_threshold(reloaded.idle_threshold);
+    monitor.set_media_suppresses_idle(reloaded.media_suppresses_idle);
+    monitor.set_capture_command_line(reloaded.capture_command_line);
+    monitor.set_url_capture(reloaded.browser_urls);
+    recorder.set_rotate_every(reloaded.rotate_segment_every);
+    recorder.set_max_sample_gap(max_sample_gap(reloaded.poll_interval));
+    if reloaded.jsonl_path != config.jsonl_path {
+        let sink = reloaded.jsonl_path.as_deref().and_then(|path| match JsonlSink::open(path) {
+            Ok(sink) => Some(Box::new(sink) as Box<dyn SegmentSink>),
+            Err(err) => {
+                eprintln!("config: jsonl sink error: {err:#}");
+                None
+            }
+        });
+        recorder.set_sink(sink);
+    }
+    if reloaded.today_feed_dir != config.today_feed_dir
+        || reloaded.widget_ini_path != config.widget_ini_path
+    {
+        *today_feed = today_feed_for(&reloaded);
+    }
+    if reloaded.slack != config.slack {
+        if let Some(slack_sync) = slack_sync.as_mut() {
+            slack_sync.clear();
+        }
+        *slack_sync = reloaded.slack.clone().map(SlackStatusSync::new);
+    }
+    *config = reloaded;
+}
+
+fn maybe_show_daily_summary(
+    recorder: &Recorder<Database>,
+    summary_at_secs: i64,
+    last_summary_day: &mut Option<(u16, u16, u16)>,
+) {
+    // Only fire within an hour of the configured time so a late start doesn't pop stale toasts.
+    const FIRE_WINDOW_SECS: i64 = 3600;
+
+    let local = clock::local_now();
+    let day = local.date();
+    let secs_since_midnight = local.secs_since_midnight;
+    if *last_summary_day == Some(day)
+        || secs_since_midnight < summary_at_secs
+        || secs_since_midnight >= summary_at_secs + FIRE_WINDOW_SECS
+    {
+        return;
+    }
+    *last_summary_day = Some(day);
+
+    let now = unix_seconds_now();
+    let totals = match recorder.store().activity_totals(now - secs_since_midnight, now) {
+        Ok(totals) => totals,
+        Err(err) => {
+            eprintln!("daily summary error: {err:#}");
+            return;
+        }
+    };
+    let mut body = format!("Today: {} active", format_short_duration(totals.active_secs));
+    if let Some((exe_name, secs)) = &totals.top_app {
+        let app_name = exe_name
+            .strip_suffix(".exe")
+            .or_else(|| exe_name.strip_suffix(".EXE"))
+            .unwrap_or(exe_name);
+        body.push_str(&format!(", top app {app_name} ({})", format_short_duration(*secs)));
+    }
+    notify::show(Notification {
+        title: "LimeTrace daily summary".to_owned(),
+        body,
+        viewer_args: vec!["--date".to_owned(), "today".to_owned()],
+    });
+}
+
+fn format_short_duration(secs: i64) -> String {
+    let secs = secs.max(0);
+    let hours = secs / 3600;
+    let minutes = (secs % 3600) / 60;
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+// Well past any scheduling hiccup, so only sleep (or a stopped process) trips it.
+fn max_sample_gap(poll_interval: Duration) -> Duration {
+    (poll_interval * 10).max(Duration::from_secs(30))
+}
+
+fn unix_seconds_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn unix_millis_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+fn duration_millis(duration: Duration) -> u128 {
+    duration.as_millis()
+}
+
+#[cfg(windows)]
+struct InstanceGuard {
+    handle: HANDLE,
+}
+
+#[cfg(windows)]
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe {
+                CloseHandle(self.handle);
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+fn acquire_single_instance_guard() -> Result<Option<InstanceGuard>> {
+    const ERROR_ALREADY_EXISTS_CODE: u32 = 183;
+
+    let name: Vec<u16> = "Local\\LimeTraceBackendSingleton"
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let handle = unsafe { CreateMutexW(std::ptr::null(), 0, name.as_ptr()) };
+    if handle.is_null() {
+        return Err(anyhow::anyhow!("CreateMutexW failed"));
+    }
+
+    let last_error = unsafe { GetLastError() };
+    if last_error == ERROR_ALREADY_EXISTS_CODE {
+        unsafe {
+            CloseHandle(handle);
+        }
+        return Ok(None);
+    }
+
+    Ok(Some(InstanceGuard { handle }))
+}
+
+// The lock is released with the file when the process exits, however it exits.
+#[cfg(unix)]
+struct InstanceGuard {
+    _file: std::fs::File,
+}
+
+#[cfg(unix)]
+fn acquire_single_instance_guard() -> Result<Option<InstanceGuard>> {
+    use std::os::fd::AsRawFd;
+
+    let dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    let path = dir.join("limetrace-backend.lock");
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("failed to open lock file: {}", path.display()))?;
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+        return Ok(None);
+    }
+    Ok(Some(InstanceGuard { _file: file }))
+}
@@ -0,0 +1,48 @@
+//! Which processes are audibly playing sound, or recording, from the audio
+//! sessions on the default output and communications input devices.
+
+use windows::core::{Interface, Result};
+use windows::Win32::Media::Audio::Endpoints::IAudioMeterInformation;
+use windows::Win32::Media::Audio::{
+    eCapture, eCommunications, eMultimedia, eRender, AudioSessionStateActive, EDataFlow, ERole, IAudioSessionControl2,
+    IAudioSessionManager2, IMMDeviceEnumerator, MMDeviceEnumerator,
+};
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_ALL, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED};
+
+/// Processes with an active session whose meter is above zero right now. Browsers
+/// play from a helper process, so callers match by executable rather than pid.
+pub fn audible_pids() -> Vec<u32> {
+    unsafe { sessions(eRender, eMultimedia, true).unwrap_or_default() }
+}
+
+/// Processes with an active session on the microphone used for calls.
+pub fn recording_pids() -> Vec<u32> {
+    unsafe { sessions(eCapture, eCommunications, false).unwrap_or_default() }
+}
+
+unsafe fn sessions(flow: EDataFlow, role: ERole, audible_only: bool) -> Result<Vec<u32>> {
+    // Also fine when the thread already joined the multithreaded apartment.
+    let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    let devices: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_INPROC_SERVER)?;
+    let device = devices.GetDefaultAudioEndpoint(flow, role)?;
+    let manager: IAudioSessionManager2 = device.Activate(CLSCTX_ALL, None)?;
+    let sessions = manager.GetSessionEnumerator()?;
+    let mut pids = Vec::new();
+    for index in 0..sessions.GetCount()? {
+        let Ok(session) = sessions.GetSession(index) else {
+            continue;
+        };
+        if session.GetState().ok() != Some(AudioSessionStateActive) {
+            continue;
+        }
+        let audible = !audible_only
+            || session
+                .cast::<IAudioMeterInformation>()
+                .and_then(|meter| meter.GetPeakValue())
+                .is_ok_and(|peak| peak > 0.0);
+        if let (true, Ok(session)) = (audible, session.cast::<IAudioSessionControl2>()) {
+            pids.extend(session.GetProcessId().ok());
+        }
+    }
+    Ok(pids)
+}
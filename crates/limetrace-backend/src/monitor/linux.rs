@@ -0,0 +1,553 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use x11rb::connection::Connection as _;
+use x11rb::protocol::screensaver::ConnectionExt as _;
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ClientMessageEvent, ConnectionExt as _, EventMask, Window,
+};
+use x11rb::rust_connection::RustConnection;
+
+use super::{
+    is_call_app, locked_sample, ActiveWindow, ActivityKind, ActivitySample, CallDetection, CommandLines, ProcessKey,
+    ProcessMeta, UrlCapture,
+};
+use crate::identity::{app_identity, AppIdentityStrategy};
+
+const RECONNECT_EVERY: Duration = Duration::from_secs(10);
+// WM_CHANGE_STATE's IconicState from ICCCM 4.1.4.
+const ICONIC_STATE: u32 = 3;
+
+struct X11 {
+    conn: RustConnection,
+    root: Window,
+    net_active_window: Atom,
+    net_close_window: Atom,
+    net_wm_pid: Atom,
+    net_wm_name: Atom,
+    utf8_string: Atom,
+    wm_change_state: Atom,
+}
+
+impl X11 {
+    fn connect() -> Option<Self> {
+        env::var_os("DISPLAY")?;
+        let (conn, screen_num) = x11rb::connect(None).ok()?;
+        let root = conn.setup().roots.get(screen_num)?.root;
+        let atom = |name: &[u8]| -> Option<Atom> {
+            Some(conn.intern_atom(false, name).ok()?.reply().ok()?.atom)
+        };
+        Some(Self {
+            net_active_window: atom(b"_NET_ACTIVE_WINDOW")?,
+            net_close_window: atom(b"_NET_CLOSE_WINDOW")?,
+            net_wm_pid: atom(b"_NET_WM_PID")?,
+            net_wm_name: atom(b"_NET_WM_NAME")?,
+            utf8_string: atom(b"UTF8_STRING")?,
+            wm_change_state: atom(b"WM_CHANGE_STATE")?,
+            conn,
+            root,
+        })
+    }
+
+    fn idle_millis(&self) -> Option<u32> {
+        let info = self
+            .conn
+            .screensaver_query_info(self.root)
+            .ok()?
+            .reply()
+            .ok()?;
+        Some(info.ms_since_user_input)
+    }
+
+    fn active_window(&self) -> Option<Window> {
+        let reply = self
+            .conn
+            .get_property(
+                false,
+                self.root,
+                self.net_active_window,
+                AtomEnum::WINDOW,
+                0,
+                1,
+            )
+            .ok()?
+            .reply()
+            .ok()?;
+        let window = reply.value32()?.next();
+        window.filter(|window| *window != 0)
+    }
+
+    fn window_pid(&self, window: Window) -> Option<u32> {
+        let reply = self
+            .conn
+            .get_property(false, window, self.net_wm_pid, AtomEnum::CARDINAL, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?;
+        let pid = reply.value32()?.next();
+        pid.filter(|pid| *pid != 0)
+    }
+
+    fn window_title(&self, window: Window) -> String {
+        let read = |property: Atom, kind: Atom| {
+            self.conn
+                .get_property(false, window, property, kind, 0, 1024)
+                .ok()?
+                .reply()
+                .ok()
+                .map(|reply| reply.value)
+                .filter(|value| !value.is_empty())
+        };
+        read(self.net_wm_name, self.utf8_string)
+            .or_else(|| read(AtomEnum::WM_NAME.into(), AtomEnum::STRING.into()))
+            .map(|value| String::from_utf8_lossy(&value).trim().to_owned())
+            .unwrap_or_default()
+    }
+
+    fn minimize(&self, window: Window) -> Option<()> {
+        let event =
+            ClientMessageEvent::new(32, window, self.wm_change_state, [ICONIC_STATE, 0, 0, 0, 0]);
+        self.conn
+            .send_event(
+                false,
+                self.root,
+                EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+                event,
+            )
+            .ok()?;
+        self.conn.flush().ok()
+    }
+
+    // Source indication 2 (EWMH): the request comes from a pager-like tool, which
+    // window managers honor without a user timestamp.
+    fn close(&self, window: Window) -> Option<()> {
+        let event = ClientMessageEvent::new(32, window, self.net_close_window, [0, 2, 0, 0, 0]);
+        self.conn
+            .send_event(
+                false,
+                self.root,
+                EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+                event,
+            )
+            .ok()?;
+        self.conn.flush().ok()
+    }
+}
+
+/// Samples the foreground window on Linux. X11 sessions use `_NET_ACTIVE_WINDOW`
+/// and the XScreenSaver extension. Wayland offers no generic way to read the
+/// focused window, so there idle time comes from the compositor over D-Bus (GNOME
+/// Mutter, or `org.freedesktop.ScreenSaver` on KDE). The focused window comes from
+/// the GNOME "Window Calls" extension when it is installed, and from XWayland
+/// otherwise, which only sees X11 apps.
+///
+/// A video player or a call keeping the screen awake registers an idle inhibitor;
+/// while one is held, time without input still counts as active in the foreground app
+/// (unless `--idle-during-media` is set).
+pub struct LinuxMonitor {
+    idle_threshold_ms: u32,
+    media_suppresses_idle: bool,
+    identity_strategy: AppIdentityStrategy,
+    process_cache: HashMap<ProcessKey, ProcessMeta>,
+    command_lines: CommandLines,
+    wayland: bool,
+    x11: Option<X11>,
+    dbus: Option<zbus::blocking::Connection>,
+    connect_attempted_at: Option<Instant>,
+    calls: CallDetection,
+}
+
+impl LinuxMonitor {
+    pub fn new(idle_threshold: Duration, identity_strategy: AppIdentityStrategy) -> Self {
+        let threshold_ms_u64 = idle_threshold.as_millis() as u64;
+        let idle_threshold_ms = threshold_ms_u64.min(u32::MAX as u64) as u32;
+        let mut monitor = Self {
+            idle_threshold_ms,
+            media_suppresses_idle: true,
+            identity_strategy,
+            process_cache: HashMap::new(),
+            command_lines: CommandLines::default(),
+            wayland: env::var_os("WAYLAND_DISPLAY").is_some(),
+            x11: None,
+            dbus: None,
+            connect_attempted_at: None,
+            calls: CallDetection::default(),
+        };
+        monitor.connect();
+        if monitor.x11.is_none() && monitor.dbus.is_none() {
+            eprintln!(
+                "monitor warning: no X11 display or session bus; every sample will be UNKNOWN"
+            );
+        }
+        monitor
+    }
+
+    fn connect(&mut self) {
+        if self
+            .connect_attempted_at
+            .is_some_and(|attempted_at| attempted_at.elapsed() < RECONNECT_EVERY)
+        {
+            return;
+        }
+        self.connect_attempted_at = Some(Instant::now());
+        if self.x11.is_none() {
+            self.x11 = X11::connect();
+        }
+        if self.dbus.is_none() {
+            self.dbus = zbus::blocking::Connection::session().ok();
+        }
+    }
+
+    pub fn set_idle_threshold(&mut self, idle_threshold: Duration) {
+        self.idle_threshold_ms = idle_threshold.as_millis().min(u128::from(u32::MAX)) as u32;
+    }
+
+    pub fn set_media_suppresses_idle(&mut self, enabled: bool) {
+        self.media_suppresses_idle = enabled;
+    }
+
+    pub fn set_capture_command_line(&mut self, enabled: bool) {
+        self.command_lines.set_enabled(enabled);
+    }
+
+    /// Browser addresses are only read through Windows UI Automation.
+    pub fn set_url_capture(&mut self, _capture: UrlCapture) {}
+
+    /// No window change events here; the loop just polls.
+    pub fn wait(&mut self, timeout: Duration) {
+        std::thread::sleep(timeout);
+    }
+
+    /// Sleep isn't reported here; the recorder's sample gap check closes the
+    /// segment instead.
+    pub fn take_suspended_at(&mut self) -> Option<i64> {
+        None
+    }
+
+    pub fn capture(&mut self) -> ActivitySample {
+        let ts_ms = crate::unix_millis_now();
+        if self.x11.is_none() || self.dbus.is_none() {
+            self.connect();
+        }
+        if self.dbus.as_ref().is_some_and(screen_locked) {
+            return locked_sample(ts_ms);
+        }
+
+        let in_call = self.calls.get(call_in_progress);
+        if let Some(idle_ms) = self.idle_millis() {
+            let inhibited = self.media_suppresses_idle && (in_call || self.dbus.as_ref().is_some_and(idle_inhibited));
+            if idle_ms >= self.idle_threshold_ms && !inhibited {
+                return ActivitySample {
+                    ts_ms,
+                    kind: ActivityKind::Idle { idle_ms },
+                };
+            }
+        }
+
+        let focused = self
+            .wayland
+            .then(|| self.dbus.as_ref().and_then(gnome_focused_window))
+            .flatten()
+            .or_else(|| {
+                let x11 = self.x11.as_ref()?;
+                let window = x11.active_window()?;
+                Some((x11.window_pid(window), x11.window_title(window)))
+            });
+        let Some((pid, window_title)) = focused else {
+            return unknown_sample(ts_ms, "<foreground-window-missing>", String::new());
+        };
+        let Some(pid) = pid else {
+            return unknown_sample(ts_ms, "<pid-missing>", window_title);
+        };
+
+        let pid_create_time = process_creation_time(pid);
+        let (exe_name, process_path, identity) = self.resolve_process(pid, pid_create_time);
+        let process_args = self.command_lines.get(pid, pid_create_time, process_command_line);
+        ActivitySample {
+            ts_ms,
+            kind: ActivityKind::Active(ActiveWindow {
+                pid,
+                pid_create_time,
+                exe_name,
+                process_path,
+                window_title,
+                identity,
+                process_args,
+                url: None,
+                in_call,
+                monitor: None,
+            }),
+        }
+    }
+
+    fn idle_millis(&mut self) -> Option<u32> {
+        // XScreenSaver under XWayland only sees input sent to X11 clients.
+        if self.wayland {
+            if let Some(idle_ms) = self.dbus.as_ref().and_then(dbus_idle_millis) {
+                return Some(idle_ms);
+            }
+        }
+        let idle_ms = self.x11.as_ref()?.idle_millis();
+        if idle_ms.is_none() {
+            self.x11 = None;
+        }
+        idle_ms
+    }
+
+    fn resolve_process(
+        &mut self,
+        pid: u32,
+        pid_create_time: Option<u64>,
+    ) -> (String, String, Option<String>) {
+        let Some(create_time) = pid_create_time else {
+            return match process_path(pid) {
+                Some(process_path) => (exe_name_from_path(&process_path, pid), process_path, None),
+                None => ("UNKNOWN".to_owned(), format!("<pid-{pid}>"), None),
+            };
+        };
+        let key = ProcessKey {
+            pid,
+            creation_time: create_time,
+        };
+        if let Some(meta) = self.process_cache.get(&key) {
+            return (
+                meta.exe_name.clone(),
+                meta.process_path.clone(),
+                meta.identity.clone(),
+            );
+        }
+        let Some(process_path) = process_path(pid) else {
+            return ("UNKNOWN".to_owned(), format!("<pid-{pid}>"), None);
+        };
+        let exe_name = exe_name_from_path(&process_path, pid);
+        let identity = app_identity(self.identity_strategy, &process_path);
+        if self.process_cache.len() >= 4096 {
+            self.process_cache.clear();
+        }
+        self.process_cache.insert(
+            key,
+            ProcessMeta {
+                exe_name: exe_name.clone(),
+                process_path: process_path.clone(),
+                identity: identity.clone(),
+            },
+        );
+        (exe_name, process_path, identity)
+    }
+}
+
+fn unknown_sample(ts_ms: i64, process_path: &str, window_title: String) -> ActivitySample {
+    ActivitySample {
+        ts_ms,
+        kind: ActivityKind::Active(ActiveWindow {
+            pid: 0,
+            pid_create_time: None,
+            exe_name: "UNKNOWN".to_owned(),
+            process_path: process_path.to_owned(),
+            window_title,
+            identity: None,
+            process_args: None,
+            url: None,
+            in_call: false,
+            monitor: None,
+        }),
+    }
+}
+
+fn dbus_idle_millis(dbus: &zbus::blocking::Connection) -> Option<u32> {
+    let mutter = dbus
+        .call_method(
+            Some("org.gnome.Mutter.IdleMonitor"),
+            "/org/gnome/Mutter/IdleMonitor/Core",
+            Some("org.gnome.Mutter.IdleMonitor"),
+            "GetIdletime",
+            &(),
+        )
+        .ok()
+        .and_then(|reply| reply.body().deserialize::<u64>().ok());
+    if let Some(idle_ms) = mutter {
+        return Some(idle_ms.min(u64::from(u32::MAX)) as u32);
+    }
+    // KWin answers in milliseconds, whatever the freedesktop draft says.
+    dbus.call_method(
+        Some("org.freedesktop.ScreenSaver"),
+        "/org/freedesktop/ScreenSaver",
+        Some("org.freedesktop.ScreenSaver"),
+        "GetSessionIdleTime",
+        &(),
+    )
+    .ok()
+    .and_then(|reply| reply.body().deserialize::<u32>().ok())
+}
+
+// GNOME answers on its own screensaver service, KDE and most others on the
+// freedesktop one.
+// PulseAudio, or PipeWire's pulse server, names the binary behind each recording stream.
+fn call_in_progress() -> bool {
+    let Ok(output) = Command::new("pactl").args(["list", "source-outputs"]).output() else {
+        return false;
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("application.process.binary = "))
+        .any(|binary| is_call_app(binary.trim_matches('"')))
+}
+
+fn screen_locked(dbus: &zbus::blocking::Connection) -> bool {
+    [
+        ("org.gnome.ScreenSaver", "/org/gnome/ScreenSaver"),
+        ("org.freedesktop.ScreenSaver", "/org/freedesktop/ScreenSaver"),
+    ]
+    .into_iter()
+    .find_map(|(service, path)| {
+        dbus.call_method(Some(service), path, Some(service), "GetActive", &())
+            .ok()
+            .and_then(|reply| reply.body().deserialize::<bool>().ok())
+    })
+    .unwrap_or(false)
+}
+
+// Wayland's idle-inhibit protocol can't be queried by other clients, but GNOME and
+// KDE both surface those inhibitors (and `org.freedesktop.ScreenSaver.Inhibit` calls
+// from X11 players) through their session services.
+fn idle_inhibited(dbus: &zbus::blocking::Connection) -> bool {
+    const GSM_INHIBIT_IDLE: u32 = 8;
+    let gnome = dbus
+        .call_method(
+            Some("org.gnome.SessionManager"),
+            "/org/gnome/SessionManager",
+            Some("org.gnome.SessionManager"),
+            "IsInhibited",
+            &(GSM_INHIBIT_IDLE,),
+        )
+        .ok()
+        .and_then(|reply| reply.body().deserialize::<bool>().ok());
+    if let Some(inhibited) = gnome {
+        return inhibited;
+    }
+    dbus.call_method(
+        Some("org.freedesktop.PowerManagement"),
+        "/org/freedesktop/PowerManagement/Inhibit",
+        Some("org.freedesktop.PowerManagement.Inhibit"),
+        "HasInhibit",
+        &(),
+    )
+    .ok()
+    .and_then(|reply| reply.body().deserialize::<bool>().ok())
+    .unwrap_or(false)
+}
+
+// The "Window Calls" GNOME Shell extension lists windows as JSON.
+fn gnome_focused_window(dbus: &zbus::blocking::Connection) -> Option<(Option<u32>, String)> {
+    let call = |method: &str, id: Option<u32>| -> Option<String> {
+        let reply = match id {
+            Some(id) => dbus.call_method(
+                Some("org.gnome.Shell"),
+                "/org/gnome/Shell/Extensions/Windows",
+                Some("org.gnome.Shell.Extensions.Windows"),
+                method,
+                &(id,),
+            ),
+            None => dbus.call_method(
+                Some("org.gnome.Shell"),
+                "/org/gnome/Shell/Extensions/Windows",
+                Some("org.gnome.Shell.Extensions.Windows"),
+                method,
+                &(),
+            ),
+        };
+        reply.ok()?.body().deserialize::<String>().ok()
+    };
+    let windows: serde_json::Value = serde_json::from_str(&call("List", None)?).ok()?;
+    let focused = windows
+        .as_array()?
+        .iter()
+        .find(|window| window.get("focus").and_then(serde_json::Value::as_bool) == Some(true))?;
+    let pid = focused
+        .get("pid")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|pid| u32::try_from(pid).ok())
+        .filter(|pid| *pid != 0);
+    let title = match focused.get("title").and_then(serde_json::Value::as_str) {
+        Some(title) => title.to_owned(),
+        None => focused
+            .get("id")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|id| u32::try_from(id).ok())
+            .and_then(|id| call("GetTitle", Some(id)))
+            .unwrap_or_default(),
+    };
+    Some((pid, title.trim().to_owned()))
+}
+
+pub fn minimize_foreground_window() {
+    // Wayland clients can't minimize other clients' windows.
+    let Some(x11) = X11::connect() else {
+        return;
+    };
+    if let Some(window) = x11.active_window() {
+        x11.minimize(window);
+    }
+}
+
+pub fn close_foreground_window() {
+    // As with minimizing, Wayland leaves other clients' windows alone.
+    let Some(x11) = X11::connect() else {
+        return;
+    };
+    if let Some(window) = x11.active_window() {
+        x11.close(window);
+    }
+}
+
+// Field 22 of /proc/<pid>/stat, in clock ticks since boot; the command name before
+// it may contain spaces, so fields are counted from the closing parenthesis.
+fn process_creation_time(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let (_, rest) = stat.rsplit_once(')')?;
+    rest.split_whitespace().nth(19)?.parse().ok()
+}
+
+fn process_path(pid: u32) -> Option<String> {
+    if let Ok(path) = fs::read_link(format!("/proc/{pid}/exe")) {
+        return Some(canonicalize_process_path(&path.to_string_lossy()));
+    }
+    // Another user's process: the name is still readable, the executable isn't.
+    let comm = fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+    let comm = comm.trim();
+    (!comm.is_empty()).then(|| format!("<proc-{comm}>"))
+}
+
+fn process_command_line(pid: u32) -> Option<String> {
+    let cmdline = fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+    super::join_command_line(cmdline.split(|byte| *byte == 0).filter(|arg| !arg.is_empty()))
+}
+
+/// Resolves symlinks so one executable always maps to the same apps row.
+pub fn canonicalize_process_path(path: &str) -> String {
+    if path.starts_with('<') {
+        return path.to_owned();
+    }
+    // A binary replaced by an update shows up as "<path> (deleted)".
+    let path = path.strip_suffix(" (deleted)").unwrap_or(path);
+    fs::canonicalize(path)
+        .map(|canonical| canonical.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_owned())
+}
+
+fn exe_name_from_path(path: &str, pid: u32) -> String {
+    if let Some(comm) = path
+        .strip_prefix("<proc-")
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        return comm.to_owned();
+    }
+    Path::new(path)
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format!("pid-{pid}"))
+}
@@ -0,0 +1,594 @@
+// Apple's framework constants keep their C names.
+#![allow(non_upper_case_globals)]
+
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::Path;
+use std::time::Duration;
+
+use super::{locked_sample, ActiveWindow, ActivityKind, ActivitySample, CommandLines, ProcessKey, ProcessMeta, UrlCapture};
+use crate::identity::{app_identity, AppIdentityStrategy};
+
+type Id = *mut c_void;
+type Sel = *const c_void;
+type CFTypeRef = *const c_void;
+type CFStringRef = *const c_void;
+type CFIndex = isize;
+
+const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+const K_CF_NUMBER_SINT64_TYPE: CFIndex = 4;
+const K_CG_WINDOW_LIST_ON_SCREEN_ONLY: u32 = 1 << 0;
+const K_CG_WINDOW_LIST_EXCLUDE_DESKTOP: u32 = 1 << 4;
+const K_AX_ERROR_SUCCESS: i32 = 0;
+
+#[link(name = "objc")]
+extern "C" {
+    fn objc_getClass(name: *const c_char) -> Id;
+    fn sel_registerName(name: *const c_char) -> Sel;
+    fn objc_msgSend();
+    fn objc_autoreleasePoolPush() -> *mut c_void;
+    fn objc_autoreleasePoolPop(pool: *mut c_void);
+}
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFBooleanTrue: CFTypeRef;
+    static kCFTypeDictionaryKeyCallBacks: c_void;
+    static kCFTypeDictionaryValueCallBacks: c_void;
+    fn CFRelease(value: CFTypeRef);
+    fn CFArrayGetCount(array: CFTypeRef) -> CFIndex;
+    fn CFArrayGetValueAtIndex(array: CFTypeRef, index: CFIndex) -> CFTypeRef;
+    fn CFDictionaryGetValue(dict: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+    fn CFDictionaryCreate(
+        allocator: CFTypeRef,
+        keys: *const CFTypeRef,
+        values: *const CFTypeRef,
+        count: CFIndex,
+        key_callbacks: *const c_void,
+        value_callbacks: *const c_void,
+    ) -> CFTypeRef;
+    fn CFNumberGetValue(number: CFTypeRef, kind: CFIndex, value: *mut c_void) -> u8;
+    fn CFStringCreateWithCString(
+        allocator: CFTypeRef,
+        value: *const c_char,
+        encoding: u32,
+    ) -> CFStringRef;
+    fn CFStringGetLength(value: CFStringRef) -> CFIndex;
+    fn CFStringGetMaximumSizeForEncoding(length: CFIndex, encoding: u32) -> CFIndex;
+    fn CFStringGetCString(
+        value: CFStringRef,
+        buffer: *mut c_char,
+        size: CFIndex,
+        encoding: u32,
+    ) -> u8;
+    fn CFGetTypeID(value: CFTypeRef) -> usize;
+    fn CFStringGetTypeID() -> usize;
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    static kCGWindowOwnerPID: CFStringRef;
+    static kCGWindowLayer: CFStringRef;
+    static kCGWindowName: CFStringRef;
+    fn CGWindowListCopyWindowInfo(option: u32, relative_to: u32) -> CFTypeRef;
+    fn CGSessionCopyCurrentDictionary() -> CFTypeRef;
+}
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    static kAXTrustedCheckOptionPrompt: CFStringRef;
+    fn AXIsProcessTrustedWithOptions(options: CFTypeRef) -> u8;
+    fn AXUIElementCreateApplication(pid: i32) -> CFTypeRef;
+    fn AXUIElementCopyAttributeValue(
+        element: CFTypeRef,
+        attribute: CFStringRef,
+        value: *mut CFTypeRef,
+    ) -> i32;
+    fn AXUIElementSetAttributeValue(
+        element: CFTypeRef,
+        attribute: CFStringRef,
+        value: CFTypeRef,
+    ) -> i32;
+    fn AXUIElementPerformAction(element: CFTypeRef, action: CFStringRef) -> i32;
+}
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOServiceMatching(name: *const c_char) -> CFTypeRef;
+    fn IOServiceGetMatchingService(main_port: u32, matching: CFTypeRef) -> u32;
+    fn IORegistryEntryCreateCFProperty(
+        entry: u32,
+        key: CFStringRef,
+        allocator: CFTypeRef,
+        options: u32,
+    ) -> CFTypeRef;
+    fn IOObjectRelease(object: u32) -> i32;
+    fn IOPMCopyAssertionsStatus(status: *mut CFTypeRef) -> i32;
+}
+
+/// Samples the frontmost app on macOS: NSWorkspace names the app, CGWindowList
+/// supplies the window title (which needs Screen Recording permission since
+/// 10.15), the Accessibility API fills in titles CGWindowList withholds, and
+/// IOHIDSystem's `HIDIdleTime` gives the idle time.
+pub struct MacMonitor {
+    idle_threshold_ms: u32,
+    media_suppresses_idle: bool,
+    identity_strategy: AppIdentityStrategy,
+    process_cache: HashMap<ProcessKey, ProcessMeta>,
+    command_lines: CommandLines,
+    hid_system: u32,
+}
+
+impl MacMonitor {
+    pub fn new(idle_threshold: Duration, identity_strategy: AppIdentityStrategy) -> Self {
+        let threshold_ms_u64 = idle_threshold.as_millis() as u64;
+        let idle_threshold_ms = threshold_ms_u64.min(u32::MAX as u64) as u32;
+        if !prompt_for_accessibility() {
+            eprintln!(
+                "monitor warning: Accessibility access not granted yet; window titles may be empty \
+                 until LimeTrace is allowed in System Settings > Privacy & Security > Accessibility"
+            );
+        }
+        let hid_system =
+            unsafe { IOServiceGetMatchingService(0, IOServiceMatching(c"IOHIDSystem".as_ptr())) };
+        Self {
+            idle_threshold_ms,
+            media_suppresses_idle: true,
+            identity_strategy,
+            process_cache: HashMap::new(),
+            command_lines: CommandLines::default(),
+            hid_system,
+        }
+    }
+
+    pub fn set_idle_threshold(&mut self, idle_threshold: Duration) {
+        self.idle_threshold_ms = idle_threshold.as_millis().min(u128::from(u32::MAX)) as u32;
+    }
+
+    pub fn set_media_suppresses_idle(&mut self, enabled: bool) {
+        self.media_suppresses_idle = enabled;
+    }
+
+    pub fn set_capture_command_line(&mut self, enabled: bool) {
+        self.command_lines.set_enabled(enabled);
+    }
+
+    /// Browser addresses are only read through Windows UI Automation.
+    pub fn set_url_capture(&mut self, _capture: UrlCapture) {}
+
+    /// No window change events here; the loop just polls.
+    pub fn wait(&mut self, timeout: Duration) {
+        std::thread::sleep(timeout);
+    }
+
+    /// Sleep isn't reported here; the recorder's sample gap check closes the
+    /// segment instead.
+    pub fn take_suspended_at(&mut self) -> Option<i64> {
+        None
+    }
+
+    pub fn capture(&mut self) -> ActivitySample {
+        let ts_ms = crate::unix_millis_now();
+        if screen_locked() {
+            return locked_sample(ts_ms);
+        }
+
+        if let Some(idle_ms) = self.idle_millis() {
+            if idle_ms >= self.idle_threshold_ms && !(self.media_suppresses_idle && display_sleep_prevented()) {
+                return ActivitySample {
+                    ts_ms,
+                    kind: ActivityKind::Idle { idle_ms },
+                };
+            }
+        }
+
+        let Some(pid) = frontmost_pid() else {
+            return ActivitySample {
+                ts_ms,
+                kind: ActivityKind::Active(ActiveWindow {
+                    pid: 0,
+                    pid_create_time: None,
+                    exe_name: "UNKNOWN".to_owned(),
+                    process_path: "<foreground-window-missing>".to_owned(),
+                    window_title: String::new(),
+                    identity: None,
+                    process_args: None,
+                    url: None,
+                    in_call: false,
+                    monitor: None,
+                }),
+            };
+        };
+        let window_title = window_list_title(pid)
+            .filter(|title| !title.is_empty())
+            .or_else(|| accessibility_title(pid))
+            .unwrap_or_default();
+        let pid = pid as u32;
+        let pid_create_time = process_creation_time(pid);
+        let (exe_name, process_path, identity) = self.resolve_process(pid, pid_create_time);
+        let process_args = self.command_lines.get(pid, pid_create_time, process_command_line);
+        ActivitySample {
+            ts_ms,
+            kind: ActivityKind::Active(ActiveWindow {
+                pid,
+                pid_create_time,
+                exe_name,
+                process_path,
+                window_title,
+                identity,
+                process_args,
+                url: None,
+                in_call: false,
+                monitor: None,
+            }),
+        }
+    }
+
+    fn idle_millis(&self) -> Option<u32> {
+        if self.hid_system == 0 {
+            return None;
+        }
+        let key = CfString::new("HIDIdleTime")?;
+        let value =
+            unsafe { IORegistryEntryCreateCFProperty(self.hid_system, key.0, std::ptr::null(), 0) };
+        if value.is_null() {
+            return None;
+        }
+        let idle_ns = cf_number_i64(value);
+        unsafe { CFRelease(value) };
+        Some((idle_ns? / 1_000_000).clamp(0, i64::from(u32::MAX)) as u32)
+    }
+
+    fn resolve_process(
+        &mut self,
+        pid: u32,
+        pid_create_time: Option<u64>,
+    ) -> (String, String, Option<String>) {
+        let Some(create_time) = pid_create_time else {
+            return match process_path(pid) {
+                Some(process_path) => (exe_name_from_path(&process_path, pid), process_path, None),
+                None => ("UNKNOWN".to_owned(), format!("<pid-{pid}>"), None),
+            };
+        };
+        let key = ProcessKey {
+            pid,
+            creation_time: create_time,
+        };
+        if let Some(meta) = self.process_cache.get(&key) {
+            return (
+                meta.exe_name.clone(),
+                meta.process_path.clone(),
+                meta.identity.clone(),
+            );
+        }
+        let Some(process_path) = process_path(pid) else {
+            return ("UNKNOWN".to_owned(), format!("<pid-{pid}>"), None);
+        };
+        let exe_name = exe_name_from_path(&process_path, pid);
+        let identity = app_identity(self.identity_strategy, &process_path);
+        if self.process_cache.len() >= 4096 {
+            self.process_cache.clear();
+        }
+        self.process_cache.insert(
+            key,
+            ProcessMeta {
+                exe_name: exe_name.clone(),
+                process_path: process_path.clone(),
+                identity: identity.clone(),
+            },
+        );
+        (exe_name, process_path, identity)
+    }
+}
+
+impl Drop for MacMonitor {
+    fn drop(&mut self) {
+        if self.hid_system != 0 {
+            unsafe { IOObjectRelease(self.hid_system) };
+        }
+    }
+}
+
+struct CfString(CFStringRef);
+
+impl CfString {
+    fn new(value: &str) -> Option<Self> {
+        let value = CString::new(value).ok()?;
+        let string = unsafe {
+            CFStringCreateWithCString(std::ptr::null(), value.as_ptr(), K_CF_STRING_ENCODING_UTF8)
+        };
+        (!string.is_null()).then_some(Self(string))
+    }
+}
+
+impl Drop for CfString {
+    fn drop(&mut self) {
+        unsafe { CFRelease(self.0) };
+    }
+}
+
+// Shows the system prompt once; macOS remembers the answer per binary.
+fn prompt_for_accessibility() -> bool {
+    unsafe {
+        let keys = [kAXTrustedCheckOptionPrompt];
+        let values = [kCFBooleanTrue];
+        let options = CFDictionaryCreate(
+            std::ptr::null(),
+            keys.as_ptr(),
+            values.as_ptr(),
+            1,
+            &kCFTypeDictionaryKeyCallBacks,
+            &kCFTypeDictionaryValueCallBacks,
+        );
+        let trusted = AXIsProcessTrustedWithOptions(options) != 0;
+        if !options.is_null() {
+            CFRelease(options);
+        }
+        trusted
+    }
+}
+
+fn frontmost_pid() -> Option<i32> {
+    unsafe {
+        let pool = objc_autoreleasePoolPush();
+        let send_id: unsafe extern "C" fn(Id, Sel) -> Id =
+            std::mem::transmute(objc_msgSend as *const c_void);
+        let send_pid: unsafe extern "C" fn(Id, Sel) -> i32 =
+            std::mem::transmute(objc_msgSend as *const c_void);
+        let workspace_class = objc_getClass(c"NSWorkspace".as_ptr());
+        let mut pid = None;
+        if !workspace_class.is_null() {
+            let workspace = send_id(
+                workspace_class,
+                sel_registerName(c"sharedWorkspace".as_ptr()),
+            );
+            if !workspace.is_null() {
+                let app = send_id(
+                    workspace,
+                    sel_registerName(c"frontmostApplication".as_ptr()),
+                );
+                if !app.is_null() {
+                    pid = Some(send_pid(
+                        app,
+                        sel_registerName(c"processIdentifier".as_ptr()),
+                    ))
+                    .filter(|pid| *pid > 0);
+                }
+            }
+        }
+        objc_autoreleasePoolPop(pool);
+        pid
+    }
+}
+
+// CGWindowList is ordered front to back; the first normal-layer window the app
+// owns is its front window.
+fn window_list_title(pid: i32) -> Option<String> {
+    unsafe {
+        let windows = CGWindowListCopyWindowInfo(
+            K_CG_WINDOW_LIST_ON_SCREEN_ONLY | K_CG_WINDOW_LIST_EXCLUDE_DESKTOP,
+            0,
+        );
+        if windows.is_null() {
+            return None;
+        }
+        let mut title = None;
+        for index in 0..CFArrayGetCount(windows) {
+            let window = CFArrayGetValueAtIndex(windows, index);
+            let owner = cf_number_i64(CFDictionaryGetValue(window, kCGWindowOwnerPID));
+            let layer = cf_number_i64(CFDictionaryGetValue(window, kCGWindowLayer));
+            if owner != Some(i64::from(pid)) || layer != Some(0) {
+                continue;
+            }
+            title = cf_string(CFDictionaryGetValue(window, kCGWindowName));
+            break;
+        }
+        CFRelease(windows);
+        title
+    }
+}
+
+fn focused_window_element(pid: i32) -> Option<CFTypeRef> {
+    let attribute = CfString::new("AXFocusedWindow")?;
+    unsafe {
+        let app = AXUIElementCreateApplication(pid);
+        if app.is_null() {
+            return None;
+        }
+        let mut window: CFTypeRef = std::ptr::null();
+        let result = AXUIElementCopyAttributeValue(app, attribute.0, &mut window);
+        CFRelease(app);
+        (result == K_AX_ERROR_SUCCESS && !window.is_null()).then_some(window)
+    }
+}
+
+fn accessibility_title(pid: i32) -> Option<String> {
+    let window = focused_window_element(pid)?;
+    let attribute = CfString::new("AXTitle");
+    unsafe {
+        let mut title: CFTypeRef = std::ptr::null();
+        let result = attribute
+            .as_ref()
+            .map(|attribute| AXUIElementCopyAttributeValue(window, attribute.0, &mut title));
+        CFRelease(window);
+        if result != Some(K_AX_ERROR_SUCCESS) || title.is_null() {
+            return None;
+        }
+        let text = cf_string(title);
+        CFRelease(title);
+        text
+    }
+}
+
+pub fn minimize_foreground_window() {
+    let Some(pid) = frontmost_pid() else {
+        return;
+    };
+    let Some(window) = focused_window_element(pid) else {
+        return;
+    };
+    if let Some(attribute) = CfString::new("AXMinimized") {
+        unsafe { AXUIElementSetAttributeValue(window, attribute.0, kCFBooleanTrue) };
+    }
+    unsafe { CFRelease(window) };
+}
+
+// Presses the window's close button, so the app can still ask to save.
+pub fn close_foreground_window() {
+    let Some(pid) = frontmost_pid() else {
+        return;
+    };
+    let Some(window) = focused_window_element(pid) else {
+        return;
+    };
+    if let (Some(attribute), Some(action)) = (CfString::new("AXCloseButton"), CfString::new("AXPress")) {
+        unsafe {
+            let mut button: CFTypeRef = std::ptr::null();
+            if AXUIElementCopyAttributeValue(window, attribute.0, &mut button) == K_AX_ERROR_SUCCESS
+                && !button.is_null()
+            {
+                AXUIElementPerformAction(button, action.0);
+                CFRelease(button);
+            }
+        }
+    }
+    unsafe { CFRelease(window) };
+}
+
+fn cf_number_i64(value: CFTypeRef) -> Option<i64> {
+    if value.is_null() {
+        return None;
+    }
+    let mut number: i64 = 0;
+    let ok = unsafe {
+        CFNumberGetValue(
+            value,
+            K_CF_NUMBER_SINT64_TYPE,
+            (&mut number as *mut i64).cast(),
+        )
+    };
+    (ok != 0).then_some(number)
+}
+
+// Video players hold this assertion while playing, the same one that keeps the
+// display awake.
+fn display_sleep_prevented() -> bool {
+    let Some(key) = CfString::new("PreventUserIdleDisplaySleep") else {
+        return false;
+    };
+    unsafe {
+        let mut status: CFTypeRef = std::ptr::null();
+        if IOPMCopyAssertionsStatus(&mut status) != 0 || status.is_null() {
+            return false;
+        }
+        let held = cf_number_i64(CFDictionaryGetValue(status, key.0)).is_some_and(|count| count > 0);
+        CFRelease(status);
+        held
+    }
+}
+
+fn screen_locked() -> bool {
+    let Some(key) = CfString::new("CGSSessionScreenIsLocked") else {
+        return false;
+    };
+    unsafe {
+        let session = CGSessionCopyCurrentDictionary();
+        if session.is_null() {
+            return false;
+        }
+        let locked = CFDictionaryGetValue(session, key.0) == kCFBooleanTrue;
+        CFRelease(session);
+        locked
+    }
+}
+
+fn cf_string(value: CFTypeRef) -> Option<String> {
+    if value.is_null() || unsafe { CFGetTypeID(value) != CFStringGetTypeID() } {
+        return None;
+    }
+    unsafe {
+        let size =
+            CFStringGetMaximumSizeForEncoding(CFStringGetLength(value), K_CF_STRING_ENCODING_UTF8)
+                + 1;
+        let mut buffer = vec![0 as c_char; size.max(1) as usize];
+        if CFStringGetCString(value, buffer.as_mut_ptr(), size, K_CF_STRING_ENCODING_UTF8) == 0 {
+            return None;
+        }
+        Some(
+            CStr::from_ptr(buffer.as_ptr())
+                .to_string_lossy()
+                .trim()
+                .to_owned(),
+        )
+    }
+}
+
+fn process_creation_time(pid: u32) -> Option<u64> {
+    let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as i32;
+    let read = unsafe {
+        libc::proc_pidinfo(
+            pid as i32,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            (&mut info as *mut libc::proc_bsdinfo).cast(),
+            size,
+        )
+    };
+    (read == size).then(|| info.pbi_start_tvsec * 1_000_000 + info.pbi_start_tvusec)
+}
+
+fn process_path(pid: u32) -> Option<String> {
+    let mut buffer = vec![0_u8; libc::PROC_PIDPATHINFO_MAXSIZE as usize];
+    let len =
+        unsafe { libc::proc_pidpath(pid as i32, buffer.as_mut_ptr().cast(), buffer.len() as u32) };
+    if len <= 0 {
+        return None;
+    }
+    Some(canonicalize_process_path(&String::from_utf8_lossy(
+        &buffer[..len as usize],
+    )))
+}
+
+// KERN_PROCARGS2 lays out argc, the executable path, NUL padding, then argv.
+fn process_command_line(pid: u32) -> Option<String> {
+    let mut mib = [libc::CTL_KERN, libc::KERN_PROCARGS2, pid as i32];
+    let mut size: libc::size_t = 0;
+    let sized = unsafe {
+        libc::sysctl(mib.as_mut_ptr(), 3, std::ptr::null_mut(), &mut size, std::ptr::null_mut(), 0)
+    };
+    if sized != 0 || size == 0 {
+        return None;
+    }
+    let mut buffer = vec![0_u8; size];
+    let read = unsafe {
+        libc::sysctl(mib.as_mut_ptr(), 3, buffer.as_mut_ptr().cast(), &mut size, std::ptr::null_mut(), 0)
+    };
+    if read != 0 {
+        return None;
+    }
+    buffer.truncate(size);
+    let argc = i32::from_ne_bytes(buffer.get(..4)?.try_into().ok()?);
+    let args = buffer[4..].split(|byte| *byte == 0).filter(|arg| !arg.is_empty()).skip(1);
+    super::join_command_line(args.take(argc.max(0) as usize))
+}
+
+/// Resolves symlinks so one executable always maps to the same apps row.
+pub fn canonicalize_process_path(path: &str) -> String {
+    if path.starts_with('<') {
+        return path.to_owned();
+    }
+    std::fs::canonicalize(path)
+        .map(|canonical| canonical.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_owned())
+}
+
+fn exe_name_from_path(path: &str, pid: u32) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format!("pid-{pid}"))
+}
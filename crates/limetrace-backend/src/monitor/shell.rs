@@ -0,0 +1,34 @@
+//! Shell properties of top-level windows, such as the AppUserModelID the taskbar
+//! groups them by.
+
+use std::ffi::c_void;
+
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Storage::EnhancedStorage::{PKEY_AppUserModel_ID, PKEY_AppUserModel_RelaunchDisplayNameResource};
+use windows::Win32::UI::Shell::PropertiesSystem::{IPropertyStore, SHGetPropertyStoreForWindow, PROPERTYKEY};
+
+#[derive(Clone)]
+pub struct WindowAppId {
+    pub app_id: String,
+    /// The name the taskbar shows for the window, when it is a plain string.
+    pub display_name: Option<String>,
+}
+
+/// The AppUserModelID a window set explicitly; windows that leave it to the
+/// process have none.
+pub fn window_app_id(hwnd: *mut c_void) -> Option<WindowAppId> {
+    unsafe {
+        let store: IPropertyStore = SHGetPropertyStoreForWindow(HWND(hwnd)).ok()?;
+        let app_id = string_property(&store, &PKEY_AppUserModel_ID)?;
+        // `@dll,-id` names point into a resource; those aren't worth loading here.
+        let display_name = string_property(&store, &PKEY_AppUserModel_RelaunchDisplayNameResource)
+            .filter(|name| !name.starts_with('@'));
+        Some(WindowAppId { app_id, display_name })
+    }
+}
+
+unsafe fn string_property(store: &IPropertyStore, key: &PROPERTYKEY) -> Option<String> {
+    let value = store.GetValue(key).ok()?.to_string();
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_owned())
+}
@@ -0,0 +1,69 @@
+//! UI Automation lookups for what plain Win32 calls don't expose, such as the
+//! address bar of a browser window.
+
+use std::ffi::c_void;
+
+use windows::core::VARIANT;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED};
+use windows::Win32::UI::Accessibility::{
+    CUIAutomation, IUIAutomation, IUIAutomationCondition, IUIAutomationValuePattern, TreeScope_Descendants,
+    UIA_ControlTypePropertyId, UIA_DocumentControlTypeId, UIA_EditControlTypeId, UIA_ValuePatternId,
+};
+
+pub struct Automation {
+    automation: IUIAutomation,
+    edit_condition: IUIAutomationCondition,
+    document_condition: IUIAutomationCondition,
+}
+
+impl Automation {
+    pub fn new() -> Option<Self> {
+        unsafe {
+            // Also fine when the thread already joined the multithreaded apartment.
+            let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+            let automation: IUIAutomation = CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER).ok()?;
+            let edit_condition = automation
+                .CreatePropertyCondition(UIA_ControlTypePropertyId, &VARIANT::from(UIA_EditControlTypeId.0))
+                .ok()?;
+            let document_condition = automation
+                .CreatePropertyCondition(UIA_ControlTypePropertyId, &VARIANT::from(UIA_DocumentControlTypeId.0))
+                .ok()?;
+            Some(Self {
+                automation,
+                edit_condition,
+                document_condition,
+            })
+        }
+    }
+
+    /// The text of the window's first edit field, which in Chromium browsers and
+    /// Firefox is the address bar (the toolbar precedes the page in tree order).
+    pub fn address_bar(&self, hwnd: *mut c_void) -> Option<String> {
+        unsafe {
+            let window = self.automation.ElementFromHandle(HWND(hwnd)).ok()?;
+            let edit = window.FindFirst(TreeScope_Descendants, &self.edit_condition).ok()?;
+            let value: IUIAutomationValuePattern = edit.GetCurrentPatternAs(UIA_ValuePatternId).ok()?;
+            let text = value.CurrentValue().ok()?.to_string();
+            let text = text.trim();
+            (!text.is_empty()).then(|| text.to_owned())
+        }
+    }
+
+    /// The window's accessible name, or else its first document's, for windows
+    /// whose Win32 title is empty.
+    pub fn window_name(&self, hwnd: *mut c_void) -> Option<String> {
+        unsafe {
+            let window = self.automation.ElementFromHandle(HWND(hwnd)).ok()?;
+            let name = window.CurrentName().ok().map(|name| name.to_string()).unwrap_or_default();
+            let name = if name.trim().is_empty() {
+                let document = window.FindFirst(TreeScope_Descendants, &self.document_condition).ok()?;
+                document.CurrentName().ok()?.to_string()
+            } else {
+                name
+            };
+            let name = name.trim();
+            (!name.is_empty()).then(|| name.to_owned())
+        }
+    }
+}
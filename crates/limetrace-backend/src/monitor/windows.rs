@@ -0,0 +1,716 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Condvar, Mutex, Once};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use windows_sys::Wdk::System::Threading::{NtQueryInformationProcess, ProcessCommandLineInformation};
+use windows_sys::Win32::Foundation::{
+    CloseHandle, BOOL, FILETIME, HANDLE, HWND, LPARAM, LRESULT, UNICODE_STRING, WPARAM,
+};
+use windows_sys::Win32::Graphics::Gdi::{
+    GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONULL,
+};
+use windows_sys::Win32::Storage::FileSystem::GetLongPathNameW;
+use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows_sys::Win32::System::Power::RegisterSuspendResumeNotification;
+use windows_sys::Win32::System::RemoteDesktop::{WTSRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION};
+use windows_sys::Win32::System::SystemInformation::GetTickCount;
+use windows_sys::Win32::System::Threading::{
+    GetProcessTimes, OpenProcess, QueryFullProcessImageNameW, PROCESS_QUERY_LIMITED_INFORMATION,
+};
+use windows_sys::Win32::UI::Accessibility::{SetWinEventHook, HWINEVENTHOOK};
+use windows_sys::Win32::UI::Shell::{
+    SHQueryUserNotificationState, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_RUNNING_D3D_FULL_SCREEN,
+};
+use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, EnumChildWindows, GetClassNameW, GetForegroundWindow,
+    GetMessageW, GetWindowTextLengthW, GetWindowTextW, GetWindowThreadProcessId, PostMessageW, RegisterClassW,
+    ShowWindow, TranslateMessage, CHILDID_SELF, DEVICE_NOTIFY_WINDOW_HANDLE, EVENT_OBJECT_NAMECHANGE,
+    EVENT_SYSTEM_FOREGROUND, HWND_MESSAGE, MSG, OBJID_WINDOW, PBT_APMSUSPEND, SW_MINIMIZE, WINEVENT_OUTOFCONTEXT,
+    WINEVENT_SKIPOWNPROCESS, WM_CLOSE, WM_POWERBROADCAST, WM_WTSSESSION_CHANGE, WNDCLASSW, WTS_SESSION_LOCK,
+    WTS_SESSION_UNLOCK,
+};
+
+use limetrace_core::apps::{is_browser_app, url_domain};
+
+use super::audio::{audible_pids, recording_pids};
+use super::shell::{window_app_id, WindowAppId};
+use super::uia::Automation;
+use super::{
+    is_call_app, locked_sample, ActiveWindow, ActivityKind, ActivitySample, CallDetection, CommandLines, ProcessKey,
+    ProcessMeta, UrlCapture,
+};
+use crate::identity::{app_identity, AppIdentityStrategy};
+
+const FRAME_HOST_EXE: &str = "ApplicationFrameHost.exe";
+// Chromium browsers and Electron apps draw every top-level window with this class.
+const CHROMIUM_WINDOW_CLASS: &str = "Chrome_WidgetWin_";
+// Launchers many Electron apps run under, which say nothing about the app itself.
+const GENERIC_ELECTRON_EXES: [&str; 2] = ["electron.exe", "update.exe"];
+// Marks the AppUserModelID of an installed web app (PWA) in Chrome and Edge.
+const WEB_APP_ID_MARKER: &str = "_crx_";
+// Address bars are read again when the tab's title changes, or after this long.
+const URL_REFRESH_EVERY: Duration = Duration::from_secs(5);
+// How long an untitled window's UI Automation name is reused.
+const UIA_TITLE_REFRESH_EVERY: Duration = Duration::from_secs(5);
+
+// Set from the session window's WM_WTSSESSION_CHANGE and WM_POWERBROADCAST
+// notifications; zero while no suspend is waiting to be handled.
+static SESSION_LOCKED: AtomicBool = AtomicBool::new(false);
+static SUSPENDED_AT: AtomicI64 = AtomicI64::new(0);
+static WATCH_SESSION: Once = Once::new();
+// Raised by the foreground and title hooks to cut the sampling loop's wait short.
+static WINDOW_CHANGED: (Mutex<bool>, Condvar) = (Mutex::new(false), Condvar::new());
+// Keeps a window whose title ticks many times a second from sampling at that rate.
+const MIN_EVENT_WAIT: Duration = Duration::from_millis(250);
+
+struct UrlLookup {
+    hwnd: isize,
+    title: String,
+    at: Instant,
+    url: Option<String>,
+}
+
+struct TitleLookup {
+    hwnd: isize,
+    at: Instant,
+    title: String,
+}
+
+struct AppIdLookup {
+    hwnd: isize,
+    app: Option<WindowAppId>,
+}
+
+pub struct WindowsMonitor {
+    idle_threshold_ms: u32,
+    media_suppresses_idle: bool,
+    identity_strategy: AppIdentityStrategy,
+    process_cache: HashMap<ProcessKey, ProcessMeta>,
+    command_lines: CommandLines,
+    url_capture: UrlCapture,
+    automation: Option<Automation>,
+    automation_tried: bool,
+    last_url: Option<UrlLookup>,
+    last_uia_title: Option<TitleLookup>,
+    last_app_id: Option<AppIdLookup>,
+    calls: CallDetection,
+}
+
+impl WindowsMonitor {
+    pub fn new(idle_threshold: Duration, identity_strategy: AppIdentityStrategy) -> Self {
+        let threshold_ms_u64 = idle_threshold.as_millis() as u64;
+        let idle_threshold_ms = threshold_ms_u64.min(u32::MAX as u64) as u32;
+        WATCH_SESSION.call_once(watch_session);
+        Self {
+            idle_threshold_ms,
+            media_suppresses_idle: true,
+            identity_strategy,
+            process_cache: HashMap::new(),
+            command_lines: CommandLines::default(),
+            url_capture: UrlCapture::Off,
+            automation: None,
+            automation_tried: false,
+            last_url: None,
+            last_uia_title: None,
+            last_app_id: None,
+            calls: CallDetection::default(),
+        }
+    }
+
+    pub fn set_idle_threshold(&mut self, idle_threshold: Duration) {
+        self.idle_threshold_ms = idle_threshold.as_millis().min(u128::from(u32::MAX)) as u32;
+    }
+
+    pub fn set_media_suppresses_idle(&mut self, enabled: bool) {
+        self.media_suppresses_idle = enabled;
+    }
+
+    pub fn set_capture_command_line(&mut self, enabled: bool) {
+        self.command_lines.set_enabled(enabled);
+    }
+
+    pub fn set_url_capture(&mut self, capture: UrlCapture) {
+        if capture != self.url_capture {
+            self.last_url = None;
+        }
+        self.url_capture = capture;
+        if capture != UrlCapture::Off && self.automation().is_none() {
+            eprintln!("monitor warning: UI Automation is unavailable; browser URLs won't be recorded");
+        }
+    }
+
+    // Created on first use and not retried after a failure.
+    fn automation(&mut self) -> Option<&Automation> {
+        if !self.automation_tried {
+            self.automation_tried = true;
+            self.automation = Automation::new();
+        }
+        self.automation.as_ref()
+    }
+
+    /// Sleeps for `timeout`, or less when another window comes to the front or
+    /// the foreground window's title changes, so switches are sampled as they happen.
+    pub fn wait(&mut self, timeout: Duration) {
+        thread::sleep(MIN_EVENT_WAIT.min(timeout));
+        let (changed, signal) = &WINDOW_CHANGED;
+        let Ok(mut changed) = changed.lock() else {
+            thread::sleep(timeout.saturating_sub(MIN_EVENT_WAIT));
+            return;
+        };
+        let remaining = timeout.saturating_sub(MIN_EVENT_WAIT);
+        if !*changed && !remaining.is_zero() {
+            changed = match signal.wait_timeout_while(changed, remaining, |changed| !*changed) {
+                Ok((changed, _)) => changed,
+                Err(_) => return,
+            };
+        }
+        *changed = false;
+    }
+
+    /// When the machine last went to sleep, once per suspend.
+    pub fn take_suspended_at(&mut self) -> Option<i64> {
+        Some(SUSPENDED_AT.swap(0, Ordering::Relaxed)).filter(|ts| *ts != 0)
+    }
+
+    pub fn capture(&mut self) -> ActivitySample {
+        let ts_ms = crate::unix_millis_now();
+        if SESSION_LOCKED.load(Ordering::Relaxed) {
+            return locked_sample(ts_ms);
+        }
+
+        let in_call = self.calls.get(call_in_progress);
+        if let Some(idle_ms) = idle_millis() {
+            if idle_ms >= self.idle_threshold_ms && !(self.media_suppresses_idle && (in_call || media_playing())) {
+                return ActivitySample {
+                    ts_ms,
+                    kind: ActivityKind::Idle { idle_ms },
+                };
+            }
+        }
+
+        let hwnd = unsafe { GetForegroundWindow() };
+        if hwnd.is_null() {
+            return ActivitySample {
+                ts_ms,
+                kind: ActivityKind::Active(ActiveWindow {
+                    pid: 0,
+                    pid_create_time: None,
+                    exe_name: "UNKNOWN".to_owned(),
+                    process_path: "<foreground-window-missing>".to_owned(),
+                    window_title: String::new(),
+                    identity: None,
+                    process_args: None,
+                    url: None,
+                    in_call: false,
+                    monitor: None,
+                }),
+            };
+        }
+
+        let mut window_title = get_window_title(hwnd);
+        if window_title.is_empty() {
+            window_title = self.uia_title(hwnd);
+        }
+        let pid = window_pid(hwnd).unwrap_or(0);
+        if pid == 0 {
+            return ActivitySample {
+                ts_ms,
+                kind: ActivityKind::Active(ActiveWindow {
+                    pid: 0,
+                    pid_create_time: None,
+                    exe_name: "UNKNOWN".to_owned(),
+                    process_path: "<pid-missing>".to_owned(),
+                    window_title,
+                    identity: None,
+                    process_args: None,
+                    url: None,
+                    in_call: false,
+                    monitor: None,
+                }),
+            };
+        }
+
+        let mut pid = pid;
+        let mut pid_create_time = process_creation_time(pid);
+        let (mut exe_name, mut process_path, mut identity) = self.resolve_process(pid, pid_create_time);
+        if exe_name.eq_ignore_ascii_case(FRAME_HOST_EXE) {
+            if let Some(app_pid) = uwp_app_pid(hwnd, pid) {
+                pid = app_pid;
+                pid_create_time = process_creation_time(pid);
+                (exe_name, process_path, identity) = self.resolve_process(pid, pid_create_time);
+            }
+        }
+        if let Some(app) = self.chromium_app_id(hwnd).cloned() {
+            let browser = is_browser_app(&exe_name);
+            let web_app = browser && app.app_id.contains(WEB_APP_ID_MARKER);
+            let generic = GENERIC_ELECTRON_EXES.iter().any(|generic| exe_name.eq_ignore_ascii_case(generic));
+            if web_app || (!browser && generic) {
+                // A pseudo path keeps the exe's own description ("Google Chrome",
+                // "Electron") from naming the app in summaries.
+                exe_name = app.display_name.clone().unwrap_or_else(|| app_name_from_id(&app.app_id));
+                process_path = format!("<app:{}>", app.app_id);
+            }
+            if (web_app || !browser) && self.identity_strategy != AppIdentityStrategy::Path {
+                identity = Some(format!("aumid:{}", app.app_id));
+            }
+        }
+        let process_args = self.command_lines.get(pid, pid_create_time, process_command_line);
+        let url = if is_browser_app(&exe_name) { self.browser_url(hwnd, &window_title) } else { None };
+        ActivitySample {
+            ts_ms,
+            kind: ActivityKind::Active(ActiveWindow {
+                pid,
+                pid_create_time,
+                exe_name,
+                process_path,
+                window_title,
+                identity,
+                process_args,
+                url,
+                in_call,
+                monitor: window_monitor(hwnd),
+            }),
+        }
+    }
+
+    /// The explicit AppUserModelID of a Chromium or Electron window, looked up once
+    /// per window.
+    fn chromium_app_id(&mut self, hwnd: HWND) -> Option<&WindowAppId> {
+        if self.last_app_id.as_ref().is_none_or(|last| last.hwnd != hwnd as isize) {
+            let app = window_class(hwnd)
+                .starts_with(CHROMIUM_WINDOW_CLASS)
+                .then(|| window_app_id(hwnd))
+                .flatten();
+            self.last_app_id = Some(AppIdLookup {
+                hwnd: hwnd as isize,
+                app,
+            });
+        }
+        self.last_app_id.as_ref()?.app.as_ref()
+    }
+
+    /// Some apps leave the Win32 title empty but name the window (or its document)
+    /// for screen readers. Looked up at most every few seconds per window.
+    fn uia_title(&mut self, hwnd: HWND) -> String {
+        let fresh = self
+            .last_uia_title
+            .as_ref()
+            .is_some_and(|last| last.hwnd == hwnd as isize && last.at.elapsed() < UIA_TITLE_REFRESH_EVERY);
+        if !fresh {
+            let title = self.automation().and_then(|automation| automation.window_name(hwnd)).unwrap_or_default();
+            self.last_uia_title = Some(TitleLookup {
+                hwnd: hwnd as isize,
+                at: Instant::now(),
+                title,
+            });
+        }
+        self.last_uia_title.as_ref().map(|last| last.title.clone()).unwrap_or_default()
+    }
+
+    fn browser_url(&mut self, hwnd: HWND, title: &str) -> Option<String> {
+        let automation = self.automation.as_ref().filter(|_| self.url_capture != UrlCapture::Off)?;
+        let fresh = self.last_url.as_ref().is_some_and(|last| {
+            last.hwnd == hwnd as isize && last.title == title && last.at.elapsed() < URL_REFRESH_EVERY
+        });
+        if !fresh {
+            let url = automation.address_bar(hwnd).and_then(|url| match self.url_capture {
+                UrlCapture::Full => Some(url),
+                _ => url_domain(&url),
+            });
+            self.last_url = Some(UrlLookup {
+                hwnd: hwnd as isize,
+                title: title.to_owned(),
+                at: Instant::now(),
+                url,
+            });
+        }
+        self.last_url.as_ref().and_then(|last| last.url.clone())
+    }
+
+    fn resolve_process(
+        &mut self,
+        pid: u32,
+        pid_create_time: Option<u64>,
+    ) -> (String, String, Option<String>) {
+        if let Some(create_time) = pid_create_time {
+            let key = ProcessKey {
+                pid,
+                creation_time: create_time,
+            };
+            if let Some(meta) = self.process_cache.get(&key) {
+                return (
+                    meta.exe_name.clone(),
+                    meta.process_path.clone(),
+                    meta.identity.clone(),
+                );
+            }
+
+            if let Some(process_path) = process_path(pid) {
+                let exe_name = exe_name_from_path(&process_path, pid);
+                let identity = app_identity(self.identity_strategy, &process_path);
+                if self.process_cache.len() >= 4096 {
+                    self.process_cache.clear();
+                }
+                self.process_cache.insert(
+                    key,
+                    ProcessMeta {
+                        exe_name: exe_name.clone(),
+                        process_path: process_path.clone(),
+                        identity: identity.clone(),
+                    },
+                );
+                return (exe_name, process_path, identity);
+            }
+        }
+
+        // Without a creation time nothing can be cached, so skip the identity lookup
+        // rather than hashing the executable on every sample.
+        if let Some(process_path) = process_path(pid) {
+            let exe_name = exe_name_from_path(&process_path, pid);
+            return (exe_name, process_path, None);
+        }
+        ("UNKNOWN".to_owned(), format!("<pid-{pid}>"), None)
+    }
+}
+
+pub fn minimize_foreground_window() {
+    unsafe {
+        ShowWindow(GetForegroundWindow(), SW_MINIMIZE);
+    }
+}
+
+// WM_CLOSE goes through the app's normal close path, including save prompts.
+pub fn close_foreground_window() {
+    unsafe {
+        PostMessageW(GetForegroundWindow(), WM_CLOSE, 0, 0);
+    }
+}
+
+// A message-only window on its own thread, since session and power notifications
+// arrive as window messages.
+fn watch_session() {
+    let spawn_result = thread::Builder::new().name("limetrace-session".to_owned()).spawn(|| {
+        let class_name: Vec<u16> = "LimeTraceBackendSession".encode_utf16().chain(std::iter::once(0)).collect();
+        let hinstance = unsafe { GetModuleHandleW(std::ptr::null()) };
+        let mut class: WNDCLASSW = unsafe { std::mem::zeroed() };
+        class.lpfnWndProc = Some(session_window_proc);
+        class.hInstance = hinstance;
+        class.lpszClassName = class_name.as_ptr();
+        unsafe { RegisterClassW(&class) };
+        let hwnd = unsafe {
+            CreateWindowExW(
+                0,
+                class_name.as_ptr(),
+                class_name.as_ptr(),
+                0,
+                0,
+                0,
+                0,
+                0,
+                HWND_MESSAGE,
+                std::ptr::null_mut(),
+                hinstance,
+                std::ptr::null(),
+            )
+        };
+        if hwnd.is_null() || unsafe { WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) } == 0 {
+            eprintln!("monitor warning: session lock notifications are unavailable");
+            return;
+        }
+        // Out-of-context hooks call back on this thread while it pumps messages.
+        let flags = WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS;
+        let hooked = [EVENT_SYSTEM_FOREGROUND, EVENT_OBJECT_NAMECHANGE].into_iter().all(|event| unsafe {
+            !SetWinEventHook(event, event, std::ptr::null_mut(), Some(window_event), 0, 0, flags).is_null()
+        });
+        if !hooked {
+            eprintln!("monitor warning: foreground change events are unavailable; polling only");
+        }
+        // Message-only windows don't get power broadcasts unless they ask.
+        if unsafe { RegisterSuspendResumeNotification(hwnd, DEVICE_NOTIFY_WINDOW_HANDLE) } == 0 {
+            eprintln!("monitor warning: suspend notifications are unavailable");
+        }
+        let mut msg: MSG = unsafe { std::mem::zeroed() };
+        while unsafe { GetMessageW(&mut msg, std::ptr::null_mut(), 0, 0) } > 0 {
+            unsafe {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+    });
+    if let Err(err) = spawn_result {
+        eprintln!("session watcher thread error: {err}");
+    }
+}
+
+unsafe extern "system" fn session_window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if msg == WM_WTSSESSION_CHANGE {
+        match wparam as u32 {
+            WTS_SESSION_LOCK => SESSION_LOCKED.store(true, Ordering::Relaxed),
+            WTS_SESSION_UNLOCK => SESSION_LOCKED.store(false, Ordering::Relaxed),
+            _ => {}
+        }
+        return 0;
+    }
+    if msg == WM_POWERBROADCAST && wparam as u32 == PBT_APMSUSPEND {
+        SUSPENDED_AT.store(crate::unix_seconds_now(), Ordering::Relaxed);
+        return 1;
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+unsafe extern "system" fn window_event(
+    _hook: HWINEVENTHOOK,
+    event: u32,
+    hwnd: HWND,
+    id_object: i32,
+    id_child: i32,
+    _event_thread: u32,
+    _event_time: u32,
+) {
+    // Name changes fire for every control; only the foreground window's own title counts.
+    if event == EVENT_OBJECT_NAMECHANGE
+        && (id_object != OBJID_WINDOW || id_child != CHILDID_SELF as i32 || hwnd != GetForegroundWindow())
+    {
+        return;
+    }
+    let (changed, signal) = &WINDOW_CHANGED;
+    if let Ok(mut changed) = changed.lock() {
+        *changed = true;
+        signal.notify_one();
+    }
+}
+
+// Full-screen apps (video, games, presentations) and sound from the foreground
+// app mean someone is watching, input or not.
+fn media_playing() -> bool {
+    let mut state = 0;
+    let full_screen = unsafe { SHQueryUserNotificationState(&mut state) } == 0
+        && matches!(state, QUNS_BUSY | QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE);
+    if full_screen {
+        return true;
+    }
+    let Some(foreground_path) = window_pid(unsafe { GetForegroundWindow() }).and_then(process_path) else {
+        return false;
+    };
+    audible_pids()
+        .into_iter()
+        .filter_map(process_path)
+        .any(|path| path.eq_ignore_ascii_case(&foreground_path))
+}
+
+// The display's device name, e.g. `\\.\DISPLAY2`; it stays put while the setup does.
+fn window_monitor(hwnd: HWND) -> Option<String> {
+    let monitor = unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONULL) };
+    if monitor.is_null() {
+        return None;
+    }
+    let mut info: MONITORINFOEXW = unsafe { std::mem::zeroed() };
+    info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+    if unsafe { GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO) } == 0 {
+        return None;
+    }
+    let len = info.szDevice.iter().position(|c| *c == 0).unwrap_or(info.szDevice.len());
+    (len > 0).then(|| String::from_utf16_lossy(&info.szDevice[..len]))
+}
+
+fn call_in_progress() -> bool {
+    recording_pids()
+        .into_iter()
+        .filter_map(process_path)
+        .any(|path| is_call_app(&path))
+}
+
+fn idle_millis() -> Option<u32> {
+    let mut lii = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    let ok = unsafe { GetLastInputInfo(&mut lii) };
+    if ok == 0 {
+        return None;
+    }
+
+    let now_tick = unsafe { GetTickCount() };
+    Some(now_tick.wrapping_sub(lii.dwTime))
+}
+
+fn window_pid(hwnd: HWND) -> Option<u32> {
+    let mut pid: u32 = 0;
+    unsafe {
+        GetWindowThreadProcessId(hwnd, &mut pid);
+    }
+    if pid == 0 {
+        None
+    } else {
+        Some(pid)
+    }
+}
+
+// UWP windows belong to ApplicationFrameHost; the app process owns a CoreWindow
+// child. Suspended or minimized apps may have none, which leaves the frame host.
+fn uwp_app_pid(frame: HWND, host_pid: u32) -> Option<u32> {
+    struct Search {
+        host_pid: u32,
+        found: Option<u32>,
+    }
+    unsafe extern "system" fn visit(child: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam as *mut Search);
+        match window_pid(child) {
+            Some(pid) if pid != search.host_pid => {
+                search.found = Some(pid);
+                0
+            }
+            _ => 1,
+        }
+    }
+    let mut search = Search { host_pid, found: None };
+    unsafe { EnumChildWindows(frame, Some(visit), &mut search as *mut Search as LPARAM) };
+    search.found
+}
+
+fn get_window_title(hwnd: HWND) -> String {
+    let len = unsafe { GetWindowTextLengthW(hwnd) };
+    if len <= 0 {
+        return String::new();
+    }
+
+    let mut buffer: Vec<u16> = vec![0; len as usize + 1];
+    let copied = unsafe { GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32) };
+    if copied <= 0 {
+        return String::new();
+    }
+    String::from_utf16_lossy(&buffer[..copied as usize]).trim().to_owned()
+}
+
+fn window_class(hwnd: HWND) -> String {
+    let mut buf = [0_u16; 256];
+    let len = unsafe { GetClassNameW(hwnd, buf.as_mut_ptr(), buf.len() as i32) };
+    String::from_utf16_lossy(&buf[..len.max(0) as usize])
+}
+
+// `com.squirrel.slack.slack` -> `slack`, for windows that carry no display name.
+fn app_name_from_id(app_id: &str) -> String {
+    app_id.rsplit(['.', '!']).find(|part| !part.is_empty()).unwrap_or(app_id).to_owned()
+}
+
+fn process_creation_time(pid: u32) -> Option<u64> {
+    with_process_handle(pid, |handle| {
+        let mut creation = zero_filetime();
+        let mut exit = zero_filetime();
+        let mut kernel = zero_filetime();
+        let mut user = zero_filetime();
+
+        let ok = unsafe { GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) };
+        if ok == 0 {
+            return None;
+        }
+        Some(filetime_to_u64(creation))
+    })
+}
+
+fn process_path(pid: u32) -> Option<String> {
+    with_process_handle(pid, |handle| {
+        let mut buffer: Vec<u16> = vec![0; 4096];
+        let mut size: u32 = buffer.len() as u32;
+        let ok = unsafe { QueryFullProcessImageNameW(handle, 0, buffer.as_mut_ptr(), &mut size) };
+        if ok == 0 || size == 0 {
+            return None;
+        }
+        Some(canonicalize_process_path(&String::from_utf16_lossy(
+            &buffer[..size as usize],
+        )))
+    })
+}
+
+// ProcessCommandLineInformation (Windows 8.1+) fills the buffer with a UNICODE_STRING
+// followed by the text it points to; the first call asks for the size.
+fn process_command_line(pid: u32) -> Option<String> {
+    with_process_handle(pid, |handle| {
+        let mut size: u32 = 0;
+        unsafe {
+            NtQueryInformationProcess(handle, ProcessCommandLineInformation, std::ptr::null_mut(), 0, &mut size);
+        }
+        if (size as usize) < std::mem::size_of::<UNICODE_STRING>() {
+            return None;
+        }
+        let mut buffer: Vec<u64> = vec![0; (size as usize).div_ceil(8)];
+        let status = unsafe {
+            NtQueryInformationProcess(
+                handle,
+                ProcessCommandLineInformation,
+                buffer.as_mut_ptr().cast(),
+                size,
+                &mut size,
+            )
+        };
+        if status < 0 {
+            return None;
+        }
+        let line = unsafe { &*buffer.as_ptr().cast::<UNICODE_STRING>() };
+        if line.Buffer.is_null() || line.Length == 0 {
+            return None;
+        }
+        let chars = unsafe { std::slice::from_raw_parts(line.Buffer, usize::from(line.Length) / 2) };
+        Some(String::from_utf16_lossy(chars).trim().to_owned())
+    })
+}
+
+/// Expands 8.3 short names and lowercases the drive letter so one executable
+/// always maps to the same apps row.
+pub fn canonicalize_process_path(path: &str) -> String {
+    let mut canonical = long_path_name(path).unwrap_or_else(|| path.to_owned());
+    let bytes = canonical.as_bytes();
+    if bytes.len() >= 2 && bytes[1] == b':' && bytes[0].is_ascii_uppercase() {
+        canonical.replace_range(..1, &canonical[..1].to_ascii_lowercase());
+    }
+    canonical
+}
+
+fn long_path_name(path: &str) -> Option<String> {
+    // Pseudo paths such as "<pid-missing>" never touch the filesystem.
+    if path.starts_with('<') {
+        return None;
+    }
+    let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buffer: Vec<u16> = vec![0; 4096];
+    let len = unsafe { GetLongPathNameW(wide.as_ptr(), buffer.as_mut_ptr(), buffer.len() as u32) };
+    if len == 0 || len as usize >= buffer.len() {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&buffer[..len as usize]))
+}
+
+fn with_process_handle<T>(pid: u32, f: impl FnOnce(HANDLE) -> Option<T>) -> Option<T> {
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if handle.is_null() {
+        return None;
+    }
+
+    let result = f(handle);
+    unsafe {
+        CloseHandle(handle);
+    }
+    result
+}
+
+fn exe_name_from_path(path: &str, pid: u32) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| format!("pid-{pid}"))
+}
+
+fn zero_filetime() -> FILETIME {
+    FILETIME {
+        dwLowDateTime: 0,
+        dwHighDateTime: 0,
+    }
+}
+
+fn filetime_to_u64(value: FILETIME) -> u64 {
+    ((value.dwHighDateTime as u64) << 32) | (value.dwLowDateTime as u64)
+}
@@ -0,0 +1,219 @@
+#[cfg(windows)]
+use std::cell::Cell;
+use std::path::PathBuf;
+use std::process::Command;
+use std::thread;
+
+#[cfg(windows)]
+use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+#[cfg(windows)]
+use windows_sys::Win32::System::LibraryLoader::GetModuleHandleW;
+#[cfg(windows)]
+use windows_sys::Win32::UI::Shell::{
+    Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_INFO, NIM_ADD, NIM_DELETE,
+    NIN_BALLOONHIDE, NIN_BALLOONTIMEOUT, NIN_BALLOONUSERCLICK, NOTIFYICONDATAW,
+};
+#[cfg(windows)]
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetMessageW, KillTimer,
+    LoadIconW, PostQuitMessage, RegisterClassW, SetTimer, TranslateMessage, HWND_MESSAGE,
+    IDI_APPLICATION, MSG, WM_APP, WM_TIMER, WNDCLASSW,
+};
+
+#[cfg(windows)]
+const TRAY_CALLBACK_MESSAGE: u32 = WM_APP + 1;
+#[cfg(windows)]
+const DISMISS_TIMER_ID: usize = 1;
+#[cfg(windows)]
+const DISMISS_AFTER_MS: u32 = 30_000;
+
+#[cfg(windows)]
+thread_local! {
+    static BALLOON_CLICKED: Cell<bool> = const { Cell::new(false) };
+}
+
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    pub viewer_args: Vec<String>,
+}
+
+/// Shows a tray balloon (a desktop notification on Linux and macOS) on a
+/// short-lived thread; clicking it opens the viewer where the platform reports clicks.
+pub fn show(notification: Notification) {
+    let spawn_result = thread::Builder::new()
+        .name("limetrace-notify".to_owned())
+        .spawn(move || {
+            if run_balloon(&notification.title, &notification.body) {
+                launch_viewer(&notification.viewer_args);
+            }
+        });
+    if let Err(err) = spawn_result {
+        eprintln!("notification thread error: {err}");
+    }
+}
+
+#[cfg(windows)]
+fn run_balloon(title: &str, body: &str) -> bool {
+    let class_name = wide("LimeTraceBackendNotify");
+    let hinstance = unsafe { GetModuleHandleW(std::ptr::null()) };
+
+    let mut class: WNDCLASSW = unsafe { std::mem::zeroed() };
+    class.lpfnWndProc = Some(window_proc);
+    class.hInstance = hinstance;
+    class.lpszClassName = class_name.as_ptr();
+    // Registration fails harmlessly when an earlier notification already registered the class.
+    unsafe { RegisterClassW(&class) };
+
+    let hwnd = unsafe {
+        CreateWindowExW(
+            0,
+            class_name.as_ptr(),
+            class_name.as_ptr(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            std::ptr::null_mut(),
+            hinstance,
+            std::ptr::null(),
+        )
+    };
+    if hwnd.is_null() {
+        eprintln!("notification window creation failed");
+        return false;
+    }
+
+    let mut data: NOTIFYICONDATAW = unsafe { std::mem::zeroed() };
+    data.cbSize = std::mem::size_of::<NOTIFYICONDATAW>() as u32;
+    data.hWnd = hwnd;
+    data.uID = 1;
+    data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP | NIF_INFO;
+    data.uCallbackMessage = TRAY_CALLBACK_MESSAGE;
+    data.hIcon = unsafe {
+        // winres embeds the application icon as resource 1.
+        let icon = LoadIconW(hinstance, 1 as _);
+        if icon.is_null() {
+            LoadIconW(std::ptr::null_mut(), IDI_APPLICATION)
+        } else {
+            icon
+        }
+    };
+    data.dwInfoFlags = NIIF_INFO;
+    copy_wide(&mut data.szTip, "LimeTrace");
+    copy_wide(&mut data.szInfoTitle, title);
+    copy_wide(&mut data.szInfo, body);
+
+    if unsafe { Shell_NotifyIconW(NIM_ADD, &data) } == 0 {
+        eprintln!("notification icon registration failed");
+        unsafe { DestroyWindow(hwnd) };
+        return false;
+    }
+
+    BALLOON_CLICKED.with(|clicked| clicked.set(false));
+    unsafe { SetTimer(hwnd, DISMISS_TIMER_ID, DISMISS_AFTER_MS, None) };
+    let mut msg: MSG = unsafe { std::mem::zeroed() };
+    while unsafe { GetMessageW(&mut msg, std::ptr::null_mut(), 0, 0) } > 0 {
+        unsafe {
+            TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+
+    unsafe {
+        KillTimer(hwnd, DISMISS_TIMER_ID);
+        Shell_NotifyIconW(NIM_DELETE, &data);
+        DestroyWindow(hwnd);
+    }
+    BALLOON_CLICKED.with(Cell::get)
+}
+
+// notify-send only reports clicks with `--action`, which needs libnotify 0.7.9+;
+// older versions still get the notification, just without the click-through.
+#[cfg(target_os = "linux")]
+fn run_balloon(title: &str, body: &str) -> bool {
+    let with_action = Command::new("notify-send")
+        .args(["--app-name=LimeTrace", "--action=default=Open LimeTrace", "--wait", title, body])
+        .output();
+    match with_action {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).trim() == "default",
+        _ => {
+            if let Err(err) = Command::new("notify-send")
+                .args(["--app-name=LimeTrace", title, body])
+                .status()
+            {
+                eprintln!("notification error: failed to run notify-send: {err}");
+            }
+            false
+        }
+    }
+}
+
+// Notification Center doesn't tell a script when a banner is clicked.
+#[cfg(target_os = "macos")]
+fn run_balloon(title: &str, body: &str) -> bool {
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+    let script = format!("display notification {} with title {}", quote(body), quote(title));
+    if let Err(err) = Command::new("osascript").args(["-e", &script]).status() {
+        eprintln!("notification error: failed to run osascript: {err}");
+    }
+    false
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        TRAY_CALLBACK_MESSAGE => {
+            let event = (lparam as u32) & 0xFFFF;
+            if event == NIN_BALLOONUSERCLICK {
+                BALLOON_CLICKED.with(|clicked| clicked.set(true));
+                PostQuitMessage(0);
+            } else if event == NIN_BALLOONTIMEOUT || event == NIN_BALLOONHIDE {
+                PostQuitMessage(0);
+            }
+            0
+        }
+        WM_TIMER if wparam == DISMISS_TIMER_ID => {
+            PostQuitMessage(0);
+            0
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
+
+fn launch_viewer(args: &[String]) {
+    let Some(viewer_exe) = viewer_executable_path() else {
+        eprintln!("viewer executable not found next to backend");
+        return;
+    };
+    if let Err(err) = Command::new(&viewer_exe).args(args).spawn() {
+        eprintln!("failed to launch viewer {}: {err}", viewer_exe.display());
+    }
+}
+
+fn viewer_executable_path() -> Option<PathBuf> {
+    let path = std::env::current_exe()
+        .ok()?
+        .with_file_name(format!("limetrace{}", std::env::consts::EXE_SUFFIX));
+    path.is_file().then_some(path)
+}
+
+#[cfg(windows)]
+fn wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+#[cfg(windows)]
+fn copy_wide(target: &mut [u16], value: &str) {
+    let max_len = target.len().saturating_sub(1);
+    for (slot, unit) in target.iter_mut().zip(value.encode_utf16().take(max_len)) {
+        *slot = unit;
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::clock;
+use crate::config::CATEGORY_CODES;
+use crate::db::Database;
+use crate::focus::normalize_app_key;
+use crate::monitor::{self, ActivityKind, ActivitySample};
+use crate::notify::{self, Notification};
+use crate::rules::ScheduleWindow;
+
+const REFRESH_EVERY: Duration = Duration::from_secs(5);
+const WARNINGS: u32 = 3;
+const WARN_EVERY_SECS: i64 = 60;
+// Notify-only rules keep reminding, at a slower pace, once the warnings are used up.
+const REMIND_EVERY_SECS: i64 = 300;
+// Once enforced, an app brought back is handled again without fighting every sample.
+const ENFORCE_EVERY_SECS: i64 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestrictionAction {
+    Notify,
+    Minimize,
+    Close,
+}
+
+impl RestrictionAction {
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "notify" => Some(Self::Notify),
+            "minimize" => Some(Self::Minimize),
+            "close" => Some(Self::Close),
+            _ => None,
+        }
+    }
+
+    // How the audit log names what happened.
+    fn event(self) -> &'static str {
+        match self {
+            Self::Notify => "warned",
+            Self::Minimize => "minimized",
+            Self::Close => "closed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RestrictionTarget {
+    Category(String),
+    App(String),
+}
+
+/// A weekly window during which a category or app is off limits, e.g.
+/// `distracting daily 23:00-24:00 minimize` or `steam.exe mon-fri 08:00-15:00`.
+#[derive(Debug, Clone)]
+pub struct Restriction {
+    pub source: String,
+    pub target: RestrictionTarget,
+    pub window: ScheduleWindow,
+    pub action: RestrictionAction,
+}
+
+impl Restriction {
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let (target, rest) = value
+            .split_once(' ')
+            .context("expected <category|app> <days> <HH:MM>-<HH:MM> [notify|minimize|close]")?;
+        let rest = rest.trim();
+        let (schedule, action) = rest
+            .rsplit_once(' ')
+            .and_then(|(schedule, code)| Some((schedule, RestrictionAction::from_code(code)?)))
+            .unwrap_or((rest, RestrictionAction::Notify));
+        let target = if CATEGORY_CODES.contains(&target) {
+            RestrictionTarget::Category(target.to_owned())
+        } else {
+            RestrictionTarget::App(normalize_app_key(target))
+        };
+        Ok(Self {
+            source: value.to_owned(),
+            target,
+            window: ScheduleWindow::parse(schedule)?,
+            action,
+        })
+    }
+
+    fn applies_to(&self, app_key: &str, categories: &HashMap<String, String>) -> bool {
+        match &self.target {
+            RestrictionTarget::Category(category) => categories.get(app_key) == Some(category),
+            RestrictionTarget::App(key) => key == app_key,
+        }
+    }
+}
+
+struct Escalation {
+    warnings: u32,
+    enforced: bool,
+    last_action_ts: i64,
+}
+
+/// Warns while a restricted app is in front, three times a minute apart, then
+/// minimizes or closes it if the rule says so. Each step is written to the
+/// `restriction_events` audit log. A rule starts over the next time its window opens.
+pub struct RestrictionGuard {
+    categories: HashMap<String, String>,
+    refreshed_at: Option<Instant>,
+    escalations: HashMap<String, Escalation>,
+}
+
+impl RestrictionGuard {
+    pub fn new() -> Self {
+        Self {
+            categories: HashMap::new(),
+            refreshed_at: None,
+            escalations: HashMap::new(),
+        }
+    }
+
+    pub fn observe(&mut self, db: &Database, sample: &ActivitySample, restrictions: &[Restriction]) {
+        let local = clock::local_now();
+        let active: Vec<&Restriction> = restrictions.iter().filter(|rule| rule.window.contains(&local)).collect();
+        self.escalations
+            .retain(|source, _| active.iter().any(|rule| rule.source == *source));
+        let ActivityKind::Active(window) = &sample.kind else {
+            return;
+        };
+        if active.is_empty() {
+            return;
+        }
+        self.refresh(db);
+        let app_key = normalize_app_key(&window.exe_name);
+        let Some(rule) = active.into_iter().find(|rule| rule.applies_to(&app_key, &self.categories)) else {
+            return;
+        };
+
+        let escalation = self.escalations.entry(rule.source.clone()).or_insert(Escalation {
+            warnings: 0,
+            enforced: false,
+            last_action_ts: i64::MIN,
+        });
+        let since = sample.ts().saturating_sub(escalation.last_action_ts);
+        let event = if escalation.warnings < WARNINGS || rule.action == RestrictionAction::Notify {
+            let wait = if escalation.warnings < WARNINGS { WARN_EVERY_SECS } else { REMIND_EVERY_SECS };
+            if since < wait {
+                return;
+            }
+            escalation.warnings = (escalation.warnings + 1).min(WARNINGS);
+            notify::show(Notification {
+                title: "LimeTrace restriction".to_owned(),
+                body: warning_text(rule, &window.exe_name, escalation.warnings),
+                viewer_args: Vec::new(),
+            });
+            RestrictionAction::Notify.event()
+        } else {
+            let wait = if escalation.enforced { ENFORCE_EVERY_SECS } else { WARN_EVERY_SECS };
+            if since < wait {
+                return;
+            }
+            escalation.enforced = true;
+            if rule.action == RestrictionAction::Close {
+                monitor::close_foreground_window();
+            } else {
+                monitor::minimize_foreground_window();
+            }
+            rule.action.event()
+        };
+        escalation.last_action_ts = sample.ts();
+        if let Err(err) =
+            db.insert_restriction_event(sample.ts(), &rule.source, &app_key, &window.window_title, event)
+        {
+            eprintln!("restriction log error: {err:#}");
+        }
+    }
+
+    fn refresh(&mut self, db: &Database) {
+        if self
+            .refreshed_at
+            .is_some_and(|refreshed_at| refreshed_at.elapsed() < REFRESH_EVERY)
+        {
+            return;
+        }
+        self.refreshed_at = Some(Instant::now());
+        match db.app_categories() {
+            Ok(categories) => self.categories = categories,
+            Err(err) => eprintln!("restriction categories error: {err:#}"),
+        }
+    }
+}
+
+fn warning_text(rule: &Restriction, exe_name: &str, warning: u32) -> String {
+    let until = format!("{:02}:{:02}", rule.window.end_secs / 3600, rule.window.end_secs % 3600 / 60);
+    match (warning, rule.action) {
+        (1, _) => format!("{exe_name} is restricted until {until} ({})", rule.source),
+        (2, _) | (_, RestrictionAction::Notify) => format!("{exe_name} is still restricted until {until}"),
+        (_, RestrictionAction::Minimize) => format!("Last warning: {exe_name} will be minimized in a minute"),
+        (_, RestrictionAction::Close) => format!("Last warning: {exe_name} will be closed in a minute"),
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::{bail, Context, Result};
+use limetrace_core::title_regex::TitleRegex;
+
+use crate::clock::LocalTime;
+use crate::monitor::{ActivityKind, ActivitySample};
+
+const REDACTED_TITLE: &str = "[redacted]";
+pub const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A weekly window during which tracking runs, e.g. `mon-fri 09:00-18:00`.
+#[derive(Debug, Clone)]
+pub struct ScheduleWindow {
+    pub source: String,
+    pub days: [bool; 7],
+    pub start_secs: i64,
+    pub end_secs: i64,
+}
+
+impl ScheduleWindow {
+    pub fn parse(value: &str) -> Result<Self> {
+        let (days, times) = value.trim().split_once(' ').context("expected <days> <HH:MM>-<HH:MM>")?;
+        let (start, end) = times.trim().split_once('-').context("expected <HH:MM>-<HH:MM>")?;
+        let start_secs = crate::config::parse_clock_time(start).with_context(|| format!("invalid start time {start}"))?;
+        let end_secs = if end.trim() == "24:00" {
+            86_400
+        } else {
+            crate::config::parse_clock_time(end).with_context(|| format!("invalid end time {end}"))?
+        };
+        if end_secs <= start_secs {
+            bail!("end time must be after start time; split overnight windows into two entries");
+        }
+        Ok(Self {
+            source: value.trim().to_owned(),
+            days: parse_days(days)?,
+            start_secs,
+            end_secs,
+        })
+    }
+
+    pub fn contains(&self, local: &LocalTime) -> bool {
+        self.days[usize::from(local.weekday) % 7]
+            && (self.start_secs..self.end_secs).contains(&local.secs_since_midnight)
+    }
+}
+
+// `daily`, `mon-fri`, `sat,sun` or `mon,wed-fri`.
+fn parse_days(value: &str) -> Result<[bool; 7]> {
+    let mut days = [false; 7];
+    if matches!(value, "daily" | "*") {
+        return Ok([true; 7]);
+    }
+    let weekday = |name: &str| {
+        WEEKDAYS
+            .iter()
+            .position(|day| name.eq_ignore_ascii_case(day))
+            .with_context(|| format!("unknown day {name} (expected mon, tue, ... sun)"))
+    };
+    for part in value.split(',') {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (from, to) = (weekday(from)?, weekday(to)?);
+                let mut day = from;
+                loop {
+                    days[day] = true;
+                    if day == to {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+            }
+            None => days[weekday(part)?] = true,
+        }
+    }
+    Ok(days)
+}
+
+/// What the backend leaves out of the database: excluded apps, time outside the
+/// schedule, and window titles that match a redaction pattern.
+#[derive(Default)]
+pub struct TrackingRules {
+    pub exclude: Vec<String>,
+    pub schedule: Vec<ScheduleWindow>,
+    pub redact_titles: Vec<(String, TitleRegex)>,
+    pub redact_all_titles: bool,
+}
+
+impl TrackingRules {
+    /// True when no schedule is set or `local` falls inside one of its windows.
+    pub fn in_schedule(&self, local: &LocalTime) -> bool {
+        self.schedule.is_empty() || self.schedule.iter().any(|window| window.contains(local))
+    }
+
+    /// `None` for samples of excluded apps; otherwise the sample with its title
+    /// redacted as configured.
+    pub fn apply(&self, mut sample: ActivitySample) -> Option<ActivitySample> {
+        let ActivityKind::Active(window) = &mut sample.kind else {
+            return Some(sample);
+        };
+        if self.exclude.iter().any(|rule| {
+            // Rules with a path separator are folder or full-path prefixes, the rest exe names.
+            if rule.contains(['\\', '/']) {
+                window.process_path.to_lowercase().starts_with(&rule.to_lowercase())
+            } else {
+                window.exe_name.eq_ignore_ascii_case(rule)
+            }
+        }) {
+            return None;
+        }
+        if !window.window_title.is_empty()
+            && (self.redact_all_titles
+                || self.redact_titles.iter().any(|(_, regex)| regex.is_match(&window.window_title)))
+        {
+            window.window_title = REDACTED_TITLE.to_owned();
+        }
+        Some(sample)
+    }
+}
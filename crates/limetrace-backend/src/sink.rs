@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use limetrace_core::recorder::{SegmentEvent, SegmentSink};
+use serde_json::json;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+pub struct JsonlSink {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl JsonlSink {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).with_context(|| {
+                format!("failed to create JSONL directory: {}", parent.display())
+            })?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open JSONL sink: {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: BufWriter::new(file),
+        })
+    }
+
+    fn write_line(&mut self, value: &serde_json::Value) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, value).context("failed to encode JSONL event")?;
+        self.writer
+            .write_all(b"\n")
+            .and_then(|_| self.writer.flush())
+            .with_context(|| format!("failed to write JSONL sink: {}", self.path.display()))
+    }
+}
+
+impl SegmentSink for JsonlSink {
+    fn write_segment(&mut self, event: &SegmentEvent) -> Result<()> {
+        self.write_line(&json!({
+            "event": "segment",
+            "start_ts": event.start_ts,
+            "end_ts": event.end_ts,
+            "start_ms": event.start_ms,
+            "end_ms": event.end_ms,
+            "is_idle": event.is_idle,
+            "app": event.app_name,
+            "process_path": event.process_path,
+            "title": event.title,
+            "pid": event.pid,
+            "process_args": event.process_args,
+            "url": event.url,
+            "is_call": event.is_call,
+            "monitor": event.monitor,
+        }))
+    }
+
+    // Active segments after the cutoff are rewritten as idle in SQLite; tailing
+    // consumers get this marker so they can drop the same span on their side.
+    fn write_idle_cutoff(&mut self, cutoff_ts: i64) -> Result<()> {
+        self.write_line(&json!({
+            "event": "idle_cutoff",
+            "ts": cutoff_ts,
+        }))
+    }
+}
+
+/// Prints every segment to stdout as it closes, for `--dry-run`.
+pub struct EchoSink;
+
+impl SegmentSink for EchoSink {
+    fn write_segment(&mut self, event: &SegmentEvent) -> Result<()> {
+        let span = format!(
+            "{} - {} {:>7.1}s",
+            clock_label(event.start_ts),
+            clock_label(event.end_ts),
+            (event.end_ms - event.start_ms) as f64 / 1000.0
+        );
+        if event.is_idle {
+            println!("{span}  idle");
+            return Ok(());
+        }
+        println!(
+            "{span}  {}  {:?}  [{}{}]",
+            event.app_name.unwrap_or("?"),
+            event.title.unwrap_or(""),
+            event.process_path.unwrap_or("?"),
+            event.pid.map(|pid| format!(", pid {pid}")).unwrap_or_default()
+        );
+        Ok(())
+    }
+
+    fn write_idle_cutoff(&mut self, cutoff_ts: i64) -> Result<()> {
+        println!("{} idle began; active time after it is dropped", clock_label(cutoff_ts));
+        Ok(())
+    }
+}
+
+// Local `HH:MM:SS`, worked out from the current wall clock since segments are recent.
+fn clock_label(ts: i64) -> String {
+    let now = crate::unix_seconds_now();
+    let secs = (crate::clock::local_now().secs_since_midnight - (now - ts)).rem_euclid(86_400);
+    format!("{:02}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+}
@@ -0,0 +1,186 @@
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::db::Database;
+use crate::focus::normalize_app_key;
+use crate::monitor::{ActivityKind, ActivitySample};
+
+const REFRESH_EVERY: Duration = Duration::from_secs(30);
+// Slack rate-limits profile writes; quick app switches only publish the state they settle on.
+const MIN_UPDATE_INTERVAL: Duration = Duration::from_secs(15);
+const PROFILE_SET_URL: &str = "https://slack.com/api/users.profile.set";
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct SlackStatusConfig {
+    pub token: String,
+    pub focus_status: String,
+    pub category_statuses: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Status {
+    emoji: String,
+    text: String,
+}
+
+/// Mirrors focus sessions and categorized foreground apps into the Slack status.
+pub struct SlackStatusSync {
+    config: SlackStatusConfig,
+    categories: HashMap<String, String>,
+    focus_active: bool,
+    refreshed_at: Option<Instant>,
+    published: Option<Status>,
+    published_at: Option<Instant>,
+}
+
+impl SlackStatusSync {
+    pub fn new(config: SlackStatusConfig) -> Self {
+        Self {
+            config,
+            categories: HashMap::new(),
+            focus_active: false,
+            refreshed_at: None,
+            published: None,
+            published_at: None,
+        }
+    }
+
+    pub fn observe(&mut self, db: &Database, sample: &ActivitySample) {
+        self.refresh(db, sample.ts());
+        let desired = match &sample.kind {
+            ActivityKind::Idle { .. } => None,
+            ActivityKind::Active(window) => {
+                let app_key = normalize_app_key(&window.exe_name);
+                let template = if self.focus_active {
+                    Some(self.config.focus_status.as_str())
+                } else {
+                    self.categories.get(&app_key).and_then(|category| {
+                        self.config
+                            .category_statuses
+                            .iter()
+                            .find(|(configured, _)| configured == category)
+                            .map(|(_, template)| template.as_str())
+                    })
+                };
+                template.map(|template| render_status(template, &app_key))
+            }
+        };
+        if desired == self.published
+            || self
+                .published_at
+                .is_some_and(|published_at| published_at.elapsed() < MIN_UPDATE_INTERVAL)
+        {
+            return;
+        }
+        self.published_at = Some(Instant::now());
+        self.published = desired.clone();
+        let token = self.config.token.clone();
+        let spawn_result = thread::Builder::new()
+            .name("limetrace-slack".to_owned())
+            .spawn(move || publish_status(&token, desired.as_ref()));
+        if let Err(err) = spawn_result {
+            eprintln!("slack status thread error: {err}");
+        }
+    }
+
+    /// Clears a status this process set, so a stopped backend doesn't leave one behind.
+    pub fn clear(&mut self) {
+        if self.published.take().is_some() {
+            publish_status(&self.config.token, None);
+        }
+    }
+
+    fn refresh(&mut self, db: &Database, now_ts: i64) {
+        if self
+            .refreshed_at
+            .is_some_and(|refreshed_at| refreshed_at.elapsed() < REFRESH_EVERY)
+        {
+            return;
+        }
+        self.refreshed_at = Some(Instant::now());
+        match db.active_focus_session(now_ts) {
+            Ok(session) => self.focus_active = session.is_some(),
+            Err(err) => eprintln!("slack focus session error: {err:#}"),
+        }
+        match db.app_categories() {
+            Ok(categories) => self.categories = categories,
+            Err(err) => eprintln!("slack categories error: {err:#}"),
+        }
+    }
+}
+
+// A leading `:emoji:` token becomes the status emoji; `{app}` expands to the app name.
+fn render_status(template: &str, app_name: &str) -> Status {
+    let template = template.trim();
+    let (emoji, text) = match template.strip_prefix(':').and_then(|rest| rest.split_once(':')) {
+        Some((name, rest)) if !name.is_empty() && !name.contains(' ') => {
+            (format!(":{name}:"), rest.trim_start())
+        }
+        _ => (String::new(), template),
+    };
+    Status {
+        emoji,
+        text: text.replace("{app}", app_name),
+    }
+}
+
+// curl ships with Windows 10+, which keeps TLS out of the backend. The token goes
+// through stdin so it never shows up in the process list.
+fn publish_status(token: &str, status: Option<&Status>) {
+    let (emoji, text) = status
+        .map(|status| (status.emoji.as_str(), status.text.as_str()))
+        .unwrap_or(("", ""));
+    let body = json!({
+        "profile": {
+            "status_text": text,
+            "status_emoji": emoji,
+            "status_expiration": 0,
+        }
+    })
+    .to_string();
+    let child = Command::new("curl.exe")
+        .args(["--silent", "--show-error", "--fail", "--max-time", "10"])
+        .args(["--header", "@-"])
+        .args(["--header", "Content-Type: application/json; charset=utf-8"])
+        .args(["--data-binary", &body, PROFILE_SET_URL])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(err) => {
+            eprintln!("slack status error: failed to run curl: {err}");
+            return;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Err(err) = writeln!(stdin, "Authorization: Bearer {token}") {
+            eprintln!("slack status error: {err}");
+        }
+    }
+    match child.wait_with_output() {
+        Ok(output) if output.status.success() => {
+            let response: serde_json::Value =
+                serde_json::from_slice(&output.stdout).unwrap_or_default();
+            if response.get("ok").and_then(serde_json::Value::as_bool) != Some(true) {
+                eprintln!(
+                    "slack status rejected: {}",
+                    response
+                        .get("error")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or("unknown error")
+                );
+            }
+        }
+        Ok(output) => eprintln!(
+            "slack status error: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(err) => eprintln!("slack status error: {err}"),
+    }
+}
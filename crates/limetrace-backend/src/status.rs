@@ -0,0 +1,285 @@
+use anyhow::{bail, Context, Result};
+use serde_json::json;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::control::ControlState;
+use crate::monitor::{ActivityKind, ActivitySample};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+struct CurrentActivity {
+    app: Option<String>,
+    idle: bool,
+    since_ts: i64,
+}
+
+#[derive(Debug)]
+struct Counters {
+    samples: u64,
+    current: Option<CurrentActivity>,
+    last_write_ts: Option<i64>,
+    window_started_at: Instant,
+    window_samples: u64,
+    samples_per_sec: Option<f64>,
+}
+
+/// What `--status` reports, kept current by the sampling loop.
+pub struct StatusBoard {
+    started_at: Instant,
+    started_ts: i64,
+    db_path: PathBuf,
+    counters: Mutex<Counters>,
+}
+
+impl StatusBoard {
+    pub fn new(db_path: PathBuf) -> Self {
+        Self {
+            started_at: Instant::now(),
+            started_ts: crate::unix_seconds_now(),
+            db_path,
+            counters: Mutex::new(Counters {
+                samples: 0,
+                current: None,
+                last_write_ts: None,
+                window_started_at: Instant::now(),
+                window_samples: 0,
+                samples_per_sec: None,
+            }),
+        }
+    }
+
+    pub fn observe(&self, sample: &ActivitySample, last_write_ts: Option<i64>) {
+        let Ok(mut counters) = self.counters.lock() else {
+            return;
+        };
+        let (app, idle) = match &sample.kind {
+            ActivityKind::Idle { .. } => (None, true),
+            ActivityKind::Active(window) => (Some(window.exe_name.clone()), false),
+        };
+        let changed = counters
+            .current
+            .as_ref()
+            .is_none_or(|current| current.app != app || current.idle != idle);
+        if changed {
+            counters.current = Some(CurrentActivity {
+                app,
+                idle,
+                since_ts: sample.ts(),
+            });
+        }
+        counters.samples += 1;
+        counters.window_samples += 1;
+        counters.last_write_ts = last_write_ts;
+        let window_elapsed = counters.window_started_at.elapsed();
+        if window_elapsed >= RATE_WINDOW {
+            counters.samples_per_sec = Some(counters.window_samples as f64 / window_elapsed.as_secs_f64());
+            counters.window_started_at = Instant::now();
+            counters.window_samples = 0;
+        }
+    }
+
+    fn to_json(&self, state: &ControlState) -> serde_json::Value {
+        let uptime = self.started_at.elapsed();
+        let counters = self.counters.lock().ok();
+        let counters = counters.as_deref();
+        // Until a full minute has passed, the lifetime average is the best there is.
+        let samples_per_sec = counters.and_then(|counters| {
+            counters
+                .samples_per_sec
+                .or_else(|| (uptime.as_secs_f64() > 0.0).then(|| counters.samples as f64 / uptime.as_secs_f64()))
+        });
+        json!({
+            "pid": std::process::id(),
+            "version": env!("CARGO_PKG_VERSION"),
+            "db_path": self.db_path.display().to_string(),
+            "started_ts": self.started_ts,
+            "uptime_secs": uptime.as_secs(),
+            "paused": state.paused.load(Ordering::SeqCst),
+            "current": counters.and_then(|counters| counters.current.as_ref()).map(|current| json!({
+                "app": current.app,
+                "idle": current.idle,
+                "since_ts": current.since_ts,
+            })),
+            "last_write_ts": counters.and_then(|counters| counters.last_write_ts),
+            "samples": counters.map_or(0, |counters| counters.samples),
+            "samples_per_sec": samples_per_sec.map(|rate| (rate * 100.0).round() / 100.0),
+        })
+    }
+}
+
+/// Answers `--status` from another process over a per-user named pipe on Windows or a
+/// Unix socket beside the single-instance lock. Each connection gets one JSON object
+/// and is closed.
+pub fn spawn(board: Arc<StatusBoard>, state: Arc<ControlState>) -> Result<()> {
+    let server = platform::Server::bind()?;
+    thread::Builder::new()
+        .name("limetrace-status".to_owned())
+        .spawn(move || server.serve(|| board.to_json(&state).to_string()))
+        .context("failed to start status thread")?;
+    Ok(())
+}
+
+/// Asks the running backend for its status and returns the JSON it sent.
+pub fn query() -> Result<String> {
+    let mut stream = platform::connect()?;
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .context("failed to read status from the running backend")?;
+    if response.trim().is_empty() {
+        bail!("the running backend closed the status connection without answering");
+    }
+    Ok(response)
+}
+
+#[cfg(windows)]
+mod platform {
+    use anyhow::{bail, Result};
+    use std::fs::File;
+    use std::thread;
+    use std::time::Duration;
+    use windows_sys::Win32::Foundation::{
+        CloseHandle, GetLastError, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, INVALID_HANDLE_VALUE,
+    };
+    use windows_sys::Win32::Storage::FileSystem::{FlushFileBuffers, WriteFile, PIPE_ACCESS_OUTBOUND};
+    use windows_sys::Win32::System::Pipes::{
+        ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE,
+        PIPE_WAIT,
+    };
+
+    const CONNECT_ATTEMPTS: u32 = 20;
+
+    fn pipe_name() -> String {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        format!(r"\\.\pipe\LimeTraceBackendStatus-{user}")
+    }
+
+    pub struct Server {
+        name: Vec<u16>,
+    }
+
+    impl Server {
+        pub fn bind() -> Result<Self> {
+            Ok(Self {
+                name: pipe_name().encode_utf16().chain(std::iter::once(0)).collect(),
+            })
+        }
+
+        pub fn serve(self, status: impl Fn() -> String) {
+            loop {
+                let handle = unsafe {
+                    CreateNamedPipeW(
+                        self.name.as_ptr(),
+                        PIPE_ACCESS_OUTBOUND,
+                        PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                        1,
+                        64 * 1024,
+                        0,
+                        0,
+                        std::ptr::null(),
+                    )
+                };
+                if handle == INVALID_HANDLE_VALUE {
+                    eprintln!("status pipe error: CreateNamedPipeW failed ({})", unsafe { GetLastError() });
+                    return;
+                }
+                let connected = unsafe { ConnectNamedPipe(handle, std::ptr::null_mut()) } != 0
+                    || unsafe { GetLastError() } == ERROR_PIPE_CONNECTED;
+                if connected {
+                    let body = status();
+                    let mut written = 0u32;
+                    unsafe {
+                        WriteFile(handle, body.as_ptr(), body.len() as u32, &mut written, std::ptr::null_mut());
+                        FlushFileBuffers(handle);
+                        DisconnectNamedPipe(handle);
+                    }
+                }
+                unsafe {
+                    CloseHandle(handle);
+                }
+            }
+        }
+    }
+
+    // The pipe has a single instance, so a client arriving while the previous one is
+    // being answered sees it busy for a moment.
+    pub fn connect() -> Result<File> {
+        for _ in 0..CONNECT_ATTEMPTS {
+            match File::open(pipe_name()) {
+                Ok(file) => return Ok(file),
+                Err(err) if err.raw_os_error() == Some(ERROR_PIPE_BUSY as i32) => {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                    bail!("no running limetrace-backend found")
+                }
+                Err(err) => bail!("failed to connect to the running backend: {err}"),
+            }
+        }
+        bail!("the running backend did not answer the status request")
+    }
+}
+
+#[cfg(unix)]
+mod platform {
+    use anyhow::{bail, Context, Result};
+    use std::io::{ErrorKind, Write};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::PathBuf;
+
+    fn socket_path() -> PathBuf {
+        std::env::var_os("XDG_RUNTIME_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir)
+            .join("limetrace-backend.sock")
+    }
+
+    pub struct Server {
+        listener: UnixListener,
+    }
+
+    impl Server {
+        // Only called while holding the single-instance lock, so a leftover socket
+        // belongs to a backend that is gone.
+        pub fn bind() -> Result<Self> {
+            let path = socket_path();
+            let _ = std::fs::remove_file(&path);
+            let listener = UnixListener::bind(&path)
+                .with_context(|| format!("failed to bind status socket: {}", path.display()))?;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+                .with_context(|| format!("failed to restrict status socket: {}", path.display()))?;
+            Ok(Self { listener })
+        }
+
+        pub fn serve(self, status: impl Fn() -> String) {
+            for stream in self.listener.incoming() {
+                match stream {
+                    Ok(mut stream) => {
+                        if let Err(err) = stream.write_all(status().as_bytes()) {
+                            eprintln!("status socket error: {err}");
+                        }
+                    }
+                    Err(err) => eprintln!("status accept error: {err}"),
+                }
+            }
+        }
+    }
+
+    pub fn connect() -> Result<UnixStream> {
+        let path = socket_path();
+        match UnixStream::connect(&path) {
+            Ok(stream) => Ok(stream),
+            Err(err) if matches!(err.kind(), ErrorKind::NotFound | ErrorKind::ConnectionRefused) => {
+                bail!("no running limetrace-backend found")
+            }
+            Err(err) => Err(err).with_context(|| format!("failed to connect to {}", path.display())),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! Reload and summary timings on large demo databases, to check redesigns such as
+//! rollups or new indices against. Each database is generated once into Cargo's
+//! target tmp dir and reused by later runs; delete it to regenerate.
+//!
+//!     cargo bench -p limetrace-core --bench reload
+//!     cargo bench -p limetrace-core --bench reload -- 1m/
+//!     cargo bench -p limetrace-core --bench reload -- range_query/
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use limetrace_core::demo;
+use limetrace_core::segments::{for_each_segment_in_range, load_segments_for_range};
+use limetrace_core::summary::{build_summary_rows, SummaryAccumulator};
+use rusqlite::{params, Connection};
+
+// Days of demo data for roughly 1M and 10M segments; the generator writes about
+// 2,100 a day.
+const SIZES: [(&str, u32); 2] = [("1m", 470), ("10m", 4_700)];
+const RANGES: [(&str, i64); 4] = [("day", 1), ("week", 7), ("month", 30), ("year", 365)];
+const SEED: u64 = 1;
+
+// The range query before and after it bounded its `start_ts` scan by the longest
+// recorded span; the unbounded form walks the index from the first row, so the most
+// recent range is its worst case.
+const UNBOUNDED_QUERY: &str = "\
+    SELECT s.start_ts, s.end_ts, s.is_idle, a.exe_name, a.process_path, t.title
+    FROM segments s
+    LEFT JOIN apps a ON a.id = s.app_id
+    LEFT JOIN titles t ON t.id = s.title_id
+    WHERE s.end_ts > ?1
+      AND s.start_ts < ?2
+    ORDER BY s.start_ts ASC";
+const BOUNDED_QUERY: &str = "\
+    SELECT s.start_ts, s.end_ts, s.is_idle, a.exe_name, a.process_path, t.title
+    FROM segments s
+    LEFT JOIN apps a ON a.id = s.app_id
+    LEFT JOIN titles t ON t.id = s.title_id
+    WHERE s.start_ts >= ?3
+      AND s.start_ts < ?2
+      AND s.end_ts > ?1
+    ORDER BY s.start_ts ASC";
+
+fn demo_db(label: &str, days: u32) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("limetrace-bench-{label}.db"));
+    if path.exists() {
+        return path;
+    }
+    // Written under another name first so an interrupted run is not mistaken for a finished one.
+    let partial = path.with_extension("db.partial");
+    let _ = fs::remove_file(&partial);
+    let mut conn = Connection::open(&partial).expect("open bench database");
+    let stats = demo::generate(&mut conn, days, SEED).expect("generate demo data");
+    drop(conn);
+    fs::rename(&partial, &path).expect("move bench database into place");
+    eprintln!("generated {} segments into {}", stats.segments, path.display());
+    path
+}
+
+// A copy of the demo database whose range scan is served by a covering index.
+fn covering_db(label: &str, days: u32) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("limetrace-bench-{label}-covering.db"));
+    if path.exists() {
+        return path;
+    }
+    let partial = path.with_extension("db.partial");
+    fs::copy(demo_db(label, days), &partial).expect("copy bench database");
+    let conn = Connection::open(&partial).expect("open bench database");
+    conn.execute_batch(
+        "\
+        CREATE INDEX idx_segments_range_covering
+          ON segments(start_ts, end_ts, app_id, title_id, is_idle);
+        DROP INDEX idx_segments_start;",
+    )
+    .expect("add covering index");
+    drop(conn);
+    fs::rename(&partial, &path).expect("move bench database into place");
+    path
+}
+
+fn count_rows(conn: &Connection, query: &str, params: impl rusqlite::Params) -> usize {
+    let mut stmt = conn.prepare_cached(query).expect("prepare range query");
+    let mut rows = stmt.query(params).expect("run range query");
+    let mut count = 0;
+    while let Some(row) = rows.next().expect("read range row") {
+        let _: Option<String> = row.get(5).expect("read title");
+        count += 1;
+    }
+    count
+}
+
+fn latest_end_ts(path: &Path) -> i64 {
+    let conn = Connection::open(path).expect("open bench database");
+    conn.query_row("SELECT MAX(end_ts) FROM segments", [], |row| row.get(0))
+        .expect("read latest segment")
+}
+
+fn reload(c: &mut Criterion) {
+    for (label, days) in SIZES {
+        let mut group = c.benchmark_group(label);
+        group.sample_size(10);
+        for (range_label, range_days) in RANGES {
+            // Databases are only generated for sizes the filter selects.
+            group.bench_function(BenchmarkId::new("load_and_summarize", range_label), |b| {
+                let path = demo_db(label, days);
+                let range_end = latest_end_ts(&path);
+                let range_start = range_end - range_days * 86_400;
+                b.iter(|| {
+                    let segments = load_segments_for_range(&path, range_start, range_end).expect("load segments");
+                    build_summary_rows(range_start, range_end, &segments, 0, |_| None)
+                });
+            });
+            group.bench_function(BenchmarkId::new("streamed_summary", range_label), |b| {
+                let path = demo_db(label, days);
+                let range_end = latest_end_ts(&path);
+                let range_start = range_end - range_days * 86_400;
+                let conn = Connection::open(&path).expect("open bench database");
+                b.iter(|| {
+                    let mut summary = SummaryAccumulator::new(range_start, range_end, 0, |_| None);
+                    for_each_segment_in_range(&conn, range_start, range_end, |seg| summary.push(&seg))
+                        .expect("scan segments");
+                    summary.finish()
+                });
+            });
+        }
+        group.finish();
+    }
+}
+
+fn range_query(c: &mut Criterion) {
+    let mut group = c.benchmark_group("range_query");
+    group.sample_size(10);
+    for (label, days) in SIZES {
+        for (range_label, range_days) in RANGES {
+            let id = format!("{label}/{range_label}");
+            let bounds = |path: &Path| {
+                let conn = Connection::open(path).expect("open bench database");
+                let range_end = latest_end_ts(path);
+                let max_span: i64 = conn
+                    .query_row("SELECT MAX(end_ts - start_ts) FROM segments", [], |row| row.get(0))
+                    .expect("read longest segment");
+                (conn, range_end - range_days * 86_400, range_end, max_span)
+            };
+            group.bench_function(BenchmarkId::new("unbounded", &id), |b| {
+                let (conn, range_start, range_end, _) = bounds(&demo_db(label, days));
+                b.iter(|| count_rows(&conn, UNBOUNDED_QUERY, params![range_start, range_end]));
+            });
+            group.bench_function(BenchmarkId::new("bounded", &id), |b| {
+                let (conn, range_start, range_end, max_span) = bounds(&demo_db(label, days));
+                b.iter(|| count_rows(&conn, BOUNDED_QUERY, params![range_start, range_end, range_start - max_span]));
+            });
+            group.bench_function(BenchmarkId::new("bounded_covering", &id), |b| {
+                let (conn, range_start, range_end, max_span) = bounds(&covering_db(label, days));
+                b.iter(|| count_rows(&conn, BOUNDED_QUERY, params![range_start, range_end, range_start - max_span]));
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, reload, range_query);
+criterion_main!(benches);
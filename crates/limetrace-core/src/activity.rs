@@ -0,0 +1,38 @@
+#[derive(Debug, Clone)]
+pub struct ActiveWindow {
+    pub pid: u32,
+    pub pid_create_time: Option<u64>,
+    pub exe_name: String,
+    pub process_path: String,
+    pub window_title: String,
+    pub identity: Option<String>,
+    /// The process command line, only captured when the backend is asked to.
+    pub process_args: Option<String>,
+    /// The browser tab's URL (or just its domain), when the backend captures them.
+    pub url: Option<String>,
+    /// A communications app (Teams, Zoom, Discord...) has the microphone open.
+    pub in_call: bool,
+    /// The display the window is on (`\\.\DISPLAY2`), where the platform reports one.
+    pub monitor: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ActivityKind {
+    Idle { idle_ms: u32 },
+    Active(ActiveWindow),
+}
+
+/// One poll of the foreground window, as the backend's monitors report it.
+#[derive(Debug, Clone)]
+pub struct ActivitySample {
+    /// Unix milliseconds, so quick switches keep their order and length.
+    pub ts_ms: i64,
+    pub kind: ActivityKind,
+}
+
+impl ActivitySample {
+    /// Whole Unix seconds, for everything that doesn't need sub-second precision.
+    pub fn ts(&self) -> i64 {
+        self.ts_ms.div_euclid(1000)
+    }
+}
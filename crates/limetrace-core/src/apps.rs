@@ -0,0 +1,121 @@
+pub fn display_app_name(raw_name: &str, is_idle: bool) -> String {
+    if is_idle {
+        return if raw_name == AWAY_APP { AWAY_APP } else { "IDLE" }.to_owned();
+    }
+
+    let trimmed = raw_name.trim();
+    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("UNKNOWN") {
+        return "UNKNOWN".to_owned();
+    }
+
+    strip_exe_suffix(trimmed).to_owned()
+}
+
+pub fn strip_exe_suffix(name: &str) -> &str {
+    if name.to_ascii_lowercase().ends_with(".exe") && name.len() > 4 {
+        &name[..name.len() - 4]
+    } else {
+        name
+    }
+}
+
+pub fn normalize_app_key(app_name: &str) -> String {
+    strip_exe_suffix(app_name.trim()).to_ascii_lowercase()
+}
+
+pub fn is_system_level_app(app_name: &str, process_path: Option<&str>) -> bool {
+    let app = normalize_app_key(app_name);
+    if app == "explorer" {
+        return false;
+    }
+    const SYSTEM_APPS: [&str; 11] = [
+        "searchhost",
+        "shellexperiencehost",
+        "startmenuexperiencehost",
+        "applicationframehost",
+        "runtimebroker",
+        "textinputhost",
+        "taskhostw",
+        "sihost",
+        "lockapp",
+        "dwm",
+        "ctfmon",
+    ];
+    if SYSTEM_APPS.contains(&app.as_str()) {
+        return true;
+    }
+
+    let Some(path) = process_path else {
+        return false;
+    };
+    let normalized = path.trim().replace('/', "\\").to_ascii_lowercase();
+    normalized.starts_with(r"c:\windows\") || normalized.starts_with(r"\\?\c:\windows\")
+}
+
+const BROWSER_APPS: [&str; 7] = ["chrome", "msedge", "firefox", "brave", "vivaldi", "opera", "chromium"];
+
+pub fn is_browser_app(app_name: &str) -> bool {
+    let key = normalize_app_key(app_name);
+    // Profiles split into their own pseudo-apps, see `browser_profile_app`.
+    let browser = key.split_once(" (").map_or(key.as_str(), |(browser, _)| browser);
+    BROWSER_APPS.contains(&browser)
+}
+
+/// The pseudo-app a browser profile is recorded as, e.g. `chrome (Work)`.
+pub fn browser_profile_app(exe_name: &str, profile: &str) -> String {
+    format!("{} ({profile})", strip_exe_suffix(exe_name.trim()))
+}
+
+/// Which of `profiles` a Chromium window title names. Chrome appends the profile
+/// after its own name and Edge puts it just before, so only whole ` - ` separated
+/// parts after the page title count.
+pub fn browser_profile_in_title<'a>(title: &str, profiles: &'a [String]) -> Option<&'a str> {
+    let title = title.replace('\u{200b}', "");
+    let parts: Vec<&str> = title.split(" - ").map(str::trim).collect();
+    parts.iter().skip(1).rev().find_map(|part| {
+        profiles
+            .iter()
+            .find(|profile| profile.trim().eq_ignore_ascii_case(part))
+            .map(String::as_str)
+    })
+}
+
+/// The lowercased host of a URL, or of `host/path` as address bars show it, without
+/// `www.`. Search text and pages like `about:blank` have none.
+pub fn url_domain(url: &str) -> Option<String> {
+    let url = url.trim();
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+    let host = match host.rsplit_once(':') {
+        Some((name, port)) if !port.is_empty() && port.bytes().all(|byte| byte.is_ascii_digit()) => name,
+        _ => host,
+    };
+    if host.is_empty() || host.contains(|c: char| c == ':' || c.is_whitespace()) {
+        return None;
+    }
+    let host = host.to_ascii_lowercase();
+    Some(host.strip_prefix("www.").map(str::to_owned).unwrap_or(host))
+}
+
+/// The pseudo-app the backend records while the session is locked; like idle
+/// time it is kept out of summaries, and the timeline shows it as part of an
+/// [`AWAY_APP`] block.
+pub const LOCKED_APP: &str = "LOCKED";
+pub const LOCKED_PROCESS_PATH: &str = "<session-locked>";
+/// What a run of idle and locked time is shown as once it contains a lock; see
+/// `timeline::coalesce_away`.
+pub const AWAY_APP: &str = "AWAY";
+
+pub fn is_locked_session(process_path: Option<&str>) -> bool {
+    process_path == Some(LOCKED_PROCESS_PATH)
+}
+
+pub fn should_hide_in_visualization(app_name: &str, is_idle: bool, process_path: Option<&str>) -> bool {
+    is_idle || is_locked_session(process_path) || is_system_level_app(app_name, process_path)
+}
+
+pub fn is_synthetic_import_path(path: &str) -> bool {
+    let path = path.trim();
+    path.starts_with("<import:") && path.ends_with('>')
+}
@@ -0,0 +1,144 @@
+//! One place for how the viewer opens the tracking database, so every connection
+//! waits out the backend's writes the same way and readers can't write by accident.
+//! [`ConnectionPool`] keeps a few open so polling code doesn't reopen the file on
+//! every tick.
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::schema::ensure_tracking_schema;
+
+/// How long a statement waits on the backend's write lock before failing.
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Idle connections a pool keeps per access mode; more are closed when returned.
+const POOL_IDLE_PER_ACCESS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Queries only; any write fails. The schema is left as it is: read-write
+    /// opens and the backend's migrations bring it up to date.
+    ReadOnly,
+    /// Switches the file to WAL (as the backend runs it) and creates the tables
+    /// the viewer writes.
+    ReadWrite,
+}
+
+pub fn open(db_path: &Path, access: Access) -> Result<Connection> {
+    let conn = Connection::open(db_path).with_context(|| format!("failed to open database: {}", db_path.display()))?;
+    conn.busy_timeout(BUSY_TIMEOUT).context("failed to set busy timeout")?;
+    match access {
+        Access::ReadOnly => {
+            conn.pragma_update(None, "query_only", true)
+                .context("failed to make connection read-only")?;
+        }
+        Access::ReadWrite => {
+            conn.pragma_update(None, "journal_mode", "WAL")
+                .context("failed to enable WAL")?;
+            ensure_tracking_schema(&conn)?;
+        }
+    }
+    Ok(conn)
+}
+
+pub fn open_read_only(db_path: &Path) -> Result<Connection> {
+    open(db_path, Access::ReadOnly)
+}
+
+pub fn open_read_write(db_path: &Path) -> Result<Connection> {
+    open(db_path, Access::ReadWrite)
+}
+
+/// Keeps connections to one database open between uses, so periodic work such as
+/// the backend status probe doesn't reopen (and re-check the schema of) the file
+/// each time. Clones share the same connections.
+#[derive(Clone)]
+pub struct ConnectionPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    db_path: PathBuf,
+    idle: Mutex<Vec<(Access, Connection)>>,
+}
+
+impl ConnectionPool {
+    pub fn new(db_path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                db_path: db_path.into(),
+                idle: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    pub fn db_path(&self) -> &Path {
+        &self.inner.db_path
+    }
+
+    pub fn get(&self, access: Access) -> Result<PooledConnection> {
+        let reused = {
+            let mut idle = self.inner.idle.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            idle.iter()
+                .rposition(|(idle_access, _)| *idle_access == access)
+                .map(|index| idle.swap_remove(index).1)
+        };
+        let conn = match reused {
+            Some(conn) => conn,
+            None => open(&self.inner.db_path, access)?,
+        };
+        Ok(PooledConnection {
+            pool: Arc::clone(&self.inner),
+            access,
+            conn: Some(conn),
+        })
+    }
+
+    pub fn read(&self) -> Result<PooledConnection> {
+        self.get(Access::ReadOnly)
+    }
+
+    pub fn write(&self) -> Result<PooledConnection> {
+        self.get(Access::ReadWrite)
+    }
+}
+
+/// A connection checked out of a [`ConnectionPool`]; it goes back when dropped.
+pub struct PooledConnection {
+    pool: Arc<PoolInner>,
+    access: Access,
+    conn: Option<Connection>,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("pooled connection is present until drop")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("pooled connection is present until drop")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let Some(conn) = self.conn.take() else {
+            return;
+        };
+        // A connection left inside a transaction (a panic mid-write) is not reused.
+        if !conn.is_autocommit() {
+            return;
+        }
+        let mut idle = self.pool.idle.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if idle.iter().filter(|(access, _)| *access == self.access).count() < POOL_IDLE_PER_ACCESS {
+            idle.push((self.access, conn));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Crash reports for panics. Release builds run without a console, so a panic would
+//! otherwise vanish; the hook writes the message, a backtrace, the version and the
+//! last few operations to the logs folder beside the database.
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+const TRAIL_LEN: usize = 32;
+const REPORT_PREFIX: &str = "crash-";
+
+static TRAIL: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// The logs folder both binaries share: `logs` beside the database.
+pub fn logs_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .join("logs")
+}
+
+/// Remembers an operation for the next crash report. Only the most recent ones are kept.
+pub fn note(operation: impl Into<String>) {
+    let Ok(mut trail) = TRAIL.lock() else {
+        return;
+    };
+    if trail.len() == TRAIL_LEN {
+        trail.pop_front();
+    }
+    trail.push_back(format!("[{}] {}", Local::now().format("%H:%M:%S"), operation.into()));
+}
+
+/// Installs a panic hook that writes a report into `logs_dir` before the default hook runs.
+pub fn install(logs_dir: PathBuf, binary: &'static str, version: &'static str) {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        match write_report(&logs_dir, binary, version, &info.to_string()) {
+            Ok(path) => eprintln!("crash report written to {}", path.display()),
+            Err(err) => eprintln!("failed to write crash report: {err:#}"),
+        }
+        previous(info);
+    }));
+}
+
+fn write_report(logs_dir: &Path, binary: &str, version: &str, panic: &str) -> Result<PathBuf> {
+    let now = Local::now();
+    let mut report = String::new();
+    let _ = writeln!(report, "{binary} {version} crashed at {}", now.format("%Y-%m-%d %H:%M:%S %:z"));
+    let _ = writeln!(report, "os: {} {}", std::env::consts::OS, std::env::consts::ARCH);
+    let _ = writeln!(report, "thread: {}", std::thread::current().name().unwrap_or("<unnamed>"));
+    let _ = writeln!(report, "\n{panic}\n\nlast operations:");
+    // The panic may have happened while the trail was locked.
+    match TRAIL.try_lock() {
+        Ok(trail) if trail.is_empty() => report.push_str("  (none)\n"),
+        Ok(trail) => trail.iter().for_each(|entry| {
+            let _ = writeln!(report, "  {entry}");
+        }),
+        Err(_) => report.push_str("  (unavailable)\n"),
+    }
+    let _ = writeln!(report, "\nbacktrace:\n{}", Backtrace::force_capture());
+
+    fs::create_dir_all(logs_dir)
+        .with_context(|| format!("failed to create log directory: {}", logs_dir.display()))?;
+    let path = logs_dir.join(format!("{REPORT_PREFIX}{binary}-{}.txt", now.format("%Y%m%d-%H%M%S")));
+    fs::write(&path, report).with_context(|| format!("failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// A report left in the logs folder by an earlier crash.
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub path: PathBuf,
+    pub binary: String,
+    pub written_ts: i64,
+}
+
+/// The newest report written after `since_ts`, if any.
+pub fn latest_report_since(logs_dir: &Path, since_ts: i64) -> Option<CrashReport> {
+    fs::read_dir(logs_dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let binary = name.strip_prefix(REPORT_PREFIX)?.strip_suffix(".txt")?;
+            // Strip the `-YYYYmmdd-HHMMSS` stamp.
+            let binary = binary.rsplitn(3, '-').nth(2)?.to_owned();
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            let written_ts = modified.duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+            Some(CrashReport {
+                path: entry.path(),
+                binary,
+                written_ts,
+            })
+        })
+        .filter(|report| report.written_ts > since_ts)
+        .max_by_key(|report| report.written_ts)
+}
@@ -0,0 +1,324 @@
+//! Synthetic activity for demos, screenshots and performance runs, so none of them
+//! need a real person's history. Days follow a plausible routine: a workday with a
+//! lunch break and meetings away from the desk, the odd evening session, and light
+//! weekends. Output is deterministic for a given seed.
+
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Days, Local, Weekday};
+use rusqlite::{params, Connection, Transaction};
+
+use crate::apps::normalize_app_key;
+use crate::schema::ensure_tracking_schema;
+use crate::time::local_midnight_ts;
+
+// The backend's default rotation, so ranges load the way real databases do.
+const SEGMENT_SECS: i64 = 10;
+const MIN_IDLE_SECS: i64 = 300;
+
+struct DemoApp {
+    exe_name: &'static str,
+    process_path: &'static str,
+    titles: &'static [&'static str],
+    // The viewer's `app_categories` codes.
+    category: &'static str,
+    work_weight: u64,
+    leisure_weight: u64,
+}
+
+const APPS: [DemoApp; 12] = [
+    DemoApp {
+        exe_name: "Code.exe",
+        process_path: r"C:\Users\demo\AppData\Local\Programs\Microsoft VS Code\Code.exe",
+        titles: &[
+            "main.rs - limetrace - Visual Studio Code",
+            "recorder.rs - limetrace - Visual Studio Code",
+            "README.md - limetrace - Visual Studio Code",
+            "Cargo.toml - limetrace - Visual Studio Code",
+        ],
+        category: "work",
+        work_weight: 30,
+        leisure_weight: 3,
+    },
+    DemoApp {
+        exe_name: "chrome.exe",
+        process_path: r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+        titles: &[
+            "Pull requests \u{00B7} demo/limetrace - Google Chrome",
+            "rust - How do I borrow a field mutably? - Stack Overflow - Google Chrome",
+            "Sprint board - Jira - Google Chrome",
+            "YouTube - Google Chrome",
+            "Weather forecast - Google Chrome",
+        ],
+        category: "neutral",
+        work_weight: 18,
+        leisure_weight: 25,
+    },
+    DemoApp {
+        exe_name: "OUTLOOK.EXE",
+        process_path: r"C:\Program Files\Microsoft Office\root\Office16\OUTLOOK.EXE",
+        titles: &["Inbox - demo@example.com - Outlook", "Calendar - demo@example.com - Outlook"],
+        category: "work",
+        work_weight: 10,
+        leisure_weight: 0,
+    },
+    DemoApp {
+        exe_name: "ms-teams.exe",
+        process_path: r"C:\Program Files\WindowsApps\MSTeams_24000.0.0.0_x64__8wekyb3d8bbwe\ms-teams.exe",
+        titles: &["Chat | Microsoft Teams", "Standup | Microsoft Teams", "Calendar | Microsoft Teams"],
+        category: "work",
+        work_weight: 9,
+        leisure_weight: 0,
+    },
+    DemoApp {
+        exe_name: "WINWORD.EXE",
+        process_path: r"C:\Program Files\Microsoft Office\root\Office16\WINWORD.EXE",
+        titles: &["Quarterly report.docx - Word", "Meeting notes.docx - Word"],
+        category: "work",
+        work_weight: 5,
+        leisure_weight: 0,
+    },
+    DemoApp {
+        exe_name: "EXCEL.EXE",
+        process_path: r"C:\Program Files\Microsoft Office\root\Office16\EXCEL.EXE",
+        titles: &["Budget 2026.xlsx - Excel", "Capacity plan.xlsx - Excel"],
+        category: "work",
+        work_weight: 5,
+        leisure_weight: 1,
+    },
+    DemoApp {
+        exe_name: "WindowsTerminal.exe",
+        process_path: r"C:\Program Files\WindowsApps\Microsoft.WindowsTerminal_1.21.0.0_x64__8wekyb3d8bbwe\WindowsTerminal.exe",
+        titles: &["cargo test", "PowerShell", "git log"],
+        category: "work",
+        work_weight: 8,
+        leisure_weight: 1,
+    },
+    DemoApp {
+        exe_name: "Slack.exe",
+        process_path: r"C:\Users\demo\AppData\Local\slack\slack.exe",
+        titles: &["#general - Demo Co - Slack", "#dev - Demo Co - Slack", "Direct messages - Slack"],
+        category: "neutral",
+        work_weight: 7,
+        leisure_weight: 2,
+    },
+    DemoApp {
+        exe_name: "explorer.exe",
+        process_path: r"C:\Windows\explorer.exe",
+        titles: &["Downloads - File Explorer", "Documents - File Explorer"],
+        category: "neutral",
+        work_weight: 3,
+        leisure_weight: 3,
+    },
+    DemoApp {
+        exe_name: "Spotify.exe",
+        process_path: r"C:\Users\demo\AppData\Roaming\Spotify\Spotify.exe",
+        titles: &["Spotify Premium", "Deep Focus - Spotify"],
+        category: "neutral",
+        work_weight: 1,
+        leisure_weight: 6,
+    },
+    DemoApp {
+        exe_name: "Discord.exe",
+        process_path: r"C:\Users\demo\AppData\Local\Discord\app-1.0.9000\Discord.exe",
+        titles: &["#general | Friends - Discord", "Discord"],
+        category: "distracting",
+        work_weight: 2,
+        leisure_weight: 12,
+    },
+    DemoApp {
+        exe_name: "steam.exe",
+        process_path: r"C:\Program Files (x86)\Steam\steam.exe",
+        titles: &["Steam", "Library - Steam"],
+        category: "distracting",
+        work_weight: 0,
+        leisure_weight: 15,
+    },
+];
+
+pub struct DemoStats {
+    pub days: u32,
+    pub segments: i64,
+}
+
+// splitmix64: small, fast and identical on every platform.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn range(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next() % (high - low + 1) as u64) as i64
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+}
+
+/// Fills an empty database with `days` days of activity ending now. Refuses to touch
+/// one that already has segments, so a real history is never mixed with fake data.
+pub fn generate(conn: &mut Connection, days: u32, seed: u64) -> Result<DemoStats> {
+    ensure_tracking_schema(conn)?;
+    let existing: i64 = conn
+        .query_row("SELECT COUNT(*) FROM segments", [], |row| row.get(0))
+        .context("failed to count existing segments")?;
+    if existing > 0 {
+        bail!("the database already has {existing} segments; pass --db with a new file for demo data");
+    }
+
+    let tx = conn.transaction().context("failed to start demo transaction")?;
+    let mut app_ids = Vec::with_capacity(APPS.len());
+    for app in &APPS {
+        tx.execute(
+            "INSERT OR IGNORE INTO apps (exe_name, process_path) VALUES (?1, ?2)",
+            params![app.exe_name, app.process_path],
+        )
+        .context("failed to insert demo app")?;
+        let app_id: i64 = tx
+            .query_row(
+                "SELECT id FROM apps WHERE exe_name = ?1 AND process_path = ?2",
+                params![app.exe_name, app.process_path],
+                |row| row.get(0),
+            )
+            .context("failed to read demo app id")?;
+        let mut title_ids = Vec::with_capacity(app.titles.len());
+        for title in app.titles {
+            tx.execute("INSERT OR IGNORE INTO titles (title) VALUES (?1)", params![title])
+                .context("failed to insert demo title")?;
+            title_ids.push(
+                tx.query_row("SELECT id FROM titles WHERE title = ?1", params![title], |row| row.get::<_, i64>(0))
+                    .context("failed to read demo title id")?,
+            );
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO app_categories (app_key, category) VALUES (?1, ?2)",
+            params![normalize_app_key(app.exe_name), app.category],
+        )
+        .context("failed to insert demo category")?;
+        app_ids.push((app_id, title_ids));
+    }
+
+    let mut rng = Rng(seed);
+    let now = Local::now().timestamp();
+    let today = Local::now().date_naive();
+    let mut writer = DemoWriter {
+        tx: &tx,
+        now,
+        app_ids: &app_ids,
+        segments: 0,
+    };
+    for offset in (0..days).rev() {
+        let Some(date) = today.checked_sub_days(Days::new(u64::from(offset))) else {
+            continue;
+        };
+        let Some(midnight) = local_midnight_ts(date) else {
+            continue;
+        };
+        let at = |hour: i64, minute: i64| midnight + hour * 3600 + minute * 60;
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            if rng.chance(70) {
+                let start = at(rng.range(10, 14), rng.range(0, 59));
+                let end = start + rng.range(60, 180) * 60;
+                writer.session(&mut rng, start, end, false)?;
+            }
+            if rng.chance(50) {
+                let start = at(rng.range(19, 20), rng.range(0, 59));
+                let end = start + rng.range(45, 150) * 60;
+                writer.session(&mut rng, start, end, false)?;
+            }
+            continue;
+        }
+        // A sick day or holiday now and then.
+        if rng.chance(5) {
+            continue;
+        }
+        let arrival = at(8, rng.range(20, 70));
+        let lunch = at(12, rng.range(0, 30));
+        let lunch_end = lunch + rng.range(35, 65) * 60;
+        let leaving = at(17, rng.range(0, 90));
+        writer.session(&mut rng, arrival, lunch, true)?;
+        writer.idle(lunch, lunch_end)?;
+        writer.session(&mut rng, lunch_end, leaving, true)?;
+        if rng.chance(35) {
+            let start = at(20, rng.range(0, 60));
+            let end = start + rng.range(30, 120) * 60;
+            writer.session(&mut rng, start, end, false)?;
+        }
+    }
+    let segments = writer.segments;
+    tx.commit().context("failed to commit demo data")?;
+    Ok(DemoStats { days, segments })
+}
+
+struct DemoWriter<'a> {
+    tx: &'a Transaction<'a>,
+    now: i64,
+    app_ids: &'a [(i64, Vec<i64>)],
+    segments: i64,
+}
+
+impl DemoWriter<'_> {
+    // Stretches of one app, with the occasional meeting or coffee away from the desk.
+    fn session(&mut self, rng: &mut Rng, start: i64, end: i64, work: bool) -> Result<()> {
+        let mut ts = start;
+        while ts < end.min(self.now) {
+            if work && rng.chance(6) {
+                let away = rng.range(MIN_IDLE_SECS / 60, 45) * 60;
+                self.idle(ts, (ts + away).min(end))?;
+                ts += away;
+                continue;
+            }
+            let app_index = pick_app(rng, work);
+            let (app_id, title_ids) = &self.app_ids[app_index];
+            let title_id = title_ids[rng.range(0, title_ids.len() as i64 - 1) as usize];
+            let stretch = if work { rng.range(1, 25) } else { rng.range(3, 40) } * 60 + rng.range(0, 59);
+            let stretch_end = (ts + stretch).min(end).min(self.now);
+            let mut segment_start = ts;
+            while segment_start < stretch_end {
+                let segment_end = (segment_start + SEGMENT_SECS).min(stretch_end);
+                self.insert(segment_start, segment_end, Some(*app_id), Some(title_id), false)?;
+                segment_start = segment_end;
+            }
+            ts = stretch_end;
+        }
+        Ok(())
+    }
+
+    fn idle(&mut self, start: i64, end: i64) -> Result<()> {
+        let end = end.min(self.now);
+        if end - start >= MIN_IDLE_SECS {
+            self.insert(start, end, None, None, true)?;
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, start: i64, end: i64, app_id: Option<i64>, title_id: Option<i64>, idle: bool) -> Result<()> {
+        self.tx
+            .prepare_cached(
+                "INSERT INTO segments (start_ts, end_ts, app_id, title_id, is_idle) VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .and_then(|mut stmt| stmt.execute(params![start, end, app_id, title_id, idle]))
+            .context("failed to insert demo segment")?;
+        self.segments += 1;
+        Ok(())
+    }
+}
+
+fn pick_app(rng: &mut Rng, work: bool) -> usize {
+    let weight = |app: &DemoApp| if work { app.work_weight } else { app.leisure_weight };
+    let total: u64 = APPS.iter().map(weight).sum();
+    let mut pick = rng.next() % total;
+    for (index, app) in APPS.iter().enumerate() {
+        if pick < weight(app) {
+            return index;
+        }
+        pick -= weight(app);
+    }
+    0
+}
@@ -0,0 +1,571 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, LocalResult, NaiveDateTime, TimeZone};
+use csv::{ReaderBuilder, StringRecord};
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportStats {
+    pub total_rows: usize,
+    pub imported_rows: usize,
+    pub skipped_rows: usize,
+}
+
+/// Imports a CSV export (LimeTrace's own or a similar tool's) into `conn`, reading
+/// local-time columns in the system time zone.
+pub fn import_csv_file(conn: &mut Connection, csv_path: &Path) -> Result<ImportStats> {
+    let reader = csv_reader_builder()
+        .from_path(csv_path)
+        .with_context(|| format!("failed to open CSV file: {}", csv_path.display()))?;
+    import_csv_records(conn, reader, &Local, None)
+}
+
+/// Like [`import_csv_file`], with local-time columns read in `tz`.
+pub fn import_csv<R: Read, Tz: TimeZone>(conn: &mut Connection, input: R, tz: &Tz) -> Result<ImportStats> {
+    import_csv_records(conn, csv_reader_builder().from_reader(input), tz, None)
+}
+
+/// Like [`import_csv`], reading each field from the column the user mapped it to
+/// instead of matching header names.
+pub fn import_csv_with_columns<R: Read, Tz: TimeZone>(
+    conn: &mut Connection,
+    input: R,
+    tz: &Tz,
+    columns: &ImportColumns,
+) -> Result<ImportStats> {
+    import_csv_records(conn, csv_reader_builder().from_reader(input), tz, Some(columns))
+}
+
+/// The headers and first rows of a CSV file, with the columns its headers map to.
+#[derive(Debug, Clone)]
+pub struct CsvPreview {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub columns: ImportColumns,
+}
+
+pub fn preview_csv<R: Read>(input: R, max_rows: usize) -> Result<CsvPreview> {
+    let mut reader = csv_reader_builder().from_reader(input);
+    let headers = reader.headers().context("failed to read CSV headers")?.clone();
+    let rows = reader
+        .records()
+        .filter_map(|row| row.ok())
+        .take(max_rows)
+        .map(|row| row.iter().map(ToOwned::to_owned).collect())
+        .collect();
+    Ok(CsvPreview {
+        headers: headers.iter().map(ToOwned::to_owned).collect(),
+        rows,
+        columns: ImportColumns::guess(&headers),
+    })
+}
+
+fn csv_reader_builder() -> ReaderBuilder {
+    let mut builder = ReaderBuilder::new();
+    builder.has_headers(true).flexible(true).trim(csv::Trim::All);
+    builder
+}
+
+fn import_csv_records<R: Read, Tz: TimeZone>(
+    conn: &mut Connection,
+    mut reader: csv::Reader<R>,
+    tz: &Tz,
+    columns: Option<&ImportColumns>,
+) -> Result<ImportStats> {
+    let columns = match columns {
+        Some(columns) => columns.clone(),
+        None => ImportColumns::guess(reader.headers().context("failed to read CSV headers")?),
+    };
+    columns.validate()?;
+
+    let tx = conn.transaction().context("failed to open import transaction")?;
+
+    let mut app_cache: HashMap<(String, String), i64> = HashMap::new();
+    let mut title_cache: HashMap<String, i64> = HashMap::new();
+    let mut stats = ImportStats::default();
+
+    for (row_idx, row_result) in reader.records().enumerate() {
+        stats.total_rows += 1;
+        let row = match row_result {
+            Ok(row) => row,
+            Err(err) => {
+                stats.skipped_rows += 1;
+                eprintln!("CSV row {} parse error: {err}", row_idx + 2);
+                continue;
+            }
+        };
+
+        let parsed = match parse_import_csv_row(&row, &columns, tz) {
+            Some(parsed) => parsed,
+            None => {
+                stats.skipped_rows += 1;
+                continue;
+            }
+        };
+
+        let app_id = upsert_app_in_tx(&tx, &mut app_cache, &parsed.app_name, &parsed.process_path)?;
+        let title_id = if let Some(title) = parsed.title.as_deref() {
+            Some(upsert_title_in_tx(&tx, &mut title_cache, title)?)
+        } else {
+            None
+        };
+
+        tx.execute(
+            "\
+            INSERT INTO segments (
+              start_ts,
+              end_ts,
+              app_id,
+              title_id,
+              is_idle,
+              pid,
+              pid_create_time
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL)",
+            params![
+                parsed.start_ts,
+                parsed.end_ts,
+                app_id,
+                title_id,
+                if parsed.is_idle { 1_i64 } else { 0_i64 },
+            ],
+        )
+        .context("failed to insert imported segment")?;
+        stats.imported_rows += 1;
+    }
+
+    tx.commit().context("failed to commit CSV import transaction")?;
+
+    if stats.total_rows == 0 {
+        bail!("CSV has no data rows");
+    }
+    if stats.imported_rows == 0 {
+        bail!("CSV contains no valid rows");
+    }
+
+    Ok(stats)
+}
+
+/// A field the importer reads, named after the header it's usually found under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportField {
+    Start,
+    End,
+    StartTs,
+    EndTs,
+    Duration,
+    Process,
+    AppName,
+    ProcessPath,
+    Title,
+    IsIdle,
+}
+
+impl ImportField {
+    pub const ALL: [Self; 10] = [
+        Self::Start,
+        Self::End,
+        Self::StartTs,
+        Self::EndTs,
+        Self::Duration,
+        Self::Process,
+        Self::AppName,
+        Self::ProcessPath,
+        Self::Title,
+        Self::IsIdle,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Start => "Start",
+            Self::End => "End",
+            Self::StartTs => "start_ts",
+            Self::EndTs => "end_ts",
+            Self::Duration => "Duration",
+            Self::Process => "Process",
+            Self::AppName => "app_name",
+            Self::ProcessPath => "process_path",
+            Self::Title => "Title",
+            Self::IsIdle => "is_idle",
+        }
+    }
+}
+
+/// Which column (by index) each field is read from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportColumns {
+    title: Option<usize>,
+    start_local: Option<usize>,
+    end_local: Option<usize>,
+    duration: Option<usize>,
+    process: Option<usize>,
+    start_ts: Option<usize>,
+    end_ts: Option<usize>,
+    is_idle: Option<usize>,
+    app_name: Option<usize>,
+    process_path: Option<usize>,
+}
+
+impl ImportColumns {
+    pub fn get(&self, field: ImportField) -> Option<usize> {
+        match field {
+            ImportField::Start => self.start_local,
+            ImportField::End => self.end_local,
+            ImportField::StartTs => self.start_ts,
+            ImportField::EndTs => self.end_ts,
+            ImportField::Duration => self.duration,
+            ImportField::Process => self.process,
+            ImportField::AppName => self.app_name,
+            ImportField::ProcessPath => self.process_path,
+            ImportField::Title => self.title,
+            ImportField::IsIdle => self.is_idle,
+        }
+    }
+
+    pub fn set(&mut self, field: ImportField, column: Option<usize>) {
+        let slot = match field {
+            ImportField::Start => &mut self.start_local,
+            ImportField::End => &mut self.end_local,
+            ImportField::StartTs => &mut self.start_ts,
+            ImportField::EndTs => &mut self.end_ts,
+            ImportField::Duration => &mut self.duration,
+            ImportField::Process => &mut self.process,
+            ImportField::AppName => &mut self.app_name,
+            ImportField::ProcessPath => &mut self.process_path,
+            ImportField::Title => &mut self.title,
+            ImportField::IsIdle => &mut self.is_idle,
+        };
+        *slot = column;
+    }
+
+    fn guess(headers: &StringRecord) -> Self {
+        Self {
+            title: find_csv_header_index(headers, &["title", "name"]),
+            start_local: find_csv_header_index(headers, &["start", "startlocal"]),
+            end_local: find_csv_header_index(headers, &["end", "endlocal"]),
+            duration: find_csv_header_index(headers, &["duration", "durationsecs"]),
+            process: find_csv_header_index(headers, &["process"]),
+            start_ts: find_csv_header_index(headers, &["startts", "start_ts"]),
+            end_ts: find_csv_header_index(headers, &["endts", "end_ts"]),
+            is_idle: find_csv_header_index(headers, &["isidle", "is_idle"]),
+            app_name: find_csv_header_index(headers, &["appname", "app_name"]),
+            process_path: find_csv_header_index(headers, &["processpath", "process_path"]),
+        }
+    }
+
+    /// Fails unless a start/end pair and an app column are mapped.
+    pub fn validate(&self) -> Result<()> {
+        let has_time_columns = (self.start_ts.is_some() && self.end_ts.is_some())
+            || (self.start_local.is_some() && self.end_local.is_some());
+        if !has_time_columns {
+            bail!("CSV missing required time columns. Need Start/End or start_ts/end_ts.");
+        }
+
+        if self.process.is_none() && self.app_name.is_none() {
+            bail!("CSV missing required app column. Need Process or app_name.");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParsedImportRow {
+    start_ts: i64,
+    end_ts: i64,
+    is_idle: bool,
+    app_name: String,
+    process_path: String,
+    title: Option<String>,
+}
+
+fn find_csv_header_index(headers: &StringRecord, aliases: &[&str]) -> Option<usize> {
+    let mut normalized_aliases = HashSet::with_capacity(aliases.len());
+    for alias in aliases {
+        normalized_aliases.insert(normalize_csv_header_key(alias));
+    }
+
+    headers.iter().position(|header| {
+        let normalized = normalize_csv_header_key(header);
+        normalized_aliases.contains(&normalized)
+    })
+}
+
+fn normalize_csv_header_key(value: &str) -> String {
+    let mut normalized = String::with_capacity(value.len());
+    for ch in value.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            normalized.push(ch.to_ascii_lowercase());
+        } else if ch.is_alphanumeric() {
+            normalized.push(ch);
+        }
+    }
+    normalized
+}
+
+fn csv_record_text(record: &StringRecord, idx: Option<usize>) -> Option<&str> {
+    idx.and_then(|i| record.get(i))
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_import_csv_row<Tz: TimeZone>(
+    record: &StringRecord,
+    columns: &ImportColumns,
+    tz: &Tz,
+) -> Option<ParsedImportRow> {
+    let start_ts = csv_record_text(record, columns.start_ts)
+        .and_then(parse_unix_seconds)
+        .or_else(|| {
+            csv_record_text(record, columns.start_local).and_then(|value| parse_local_datetime_to_unix(value, tz))
+        });
+
+    let mut end_ts = csv_record_text(record, columns.end_ts)
+        .and_then(parse_unix_seconds)
+        .or_else(|| {
+            csv_record_text(record, columns.end_local).and_then(|value| parse_local_datetime_to_unix(value, tz))
+        });
+
+    let duration_secs = csv_record_text(record, columns.duration).and_then(parse_duration_to_seconds);
+
+    if end_ts.is_none() {
+        if let (Some(start), Some(duration)) = (start_ts, duration_secs) {
+            end_ts = Some(start.saturating_add(duration));
+        }
+    }
+
+    let start_ts = start_ts?;
+    let mut end_ts = end_ts?;
+    if end_ts <= start_ts {
+        if let Some(duration) = duration_secs {
+            end_ts = start_ts.saturating_add(duration);
+        }
+    }
+    if end_ts <= start_ts {
+        return None;
+    }
+
+    let process_text =
+        csv_record_text(record, columns.process).or_else(|| csv_record_text(record, columns.app_name))?;
+    let app_name_text = csv_record_text(record, columns.app_name).unwrap_or(process_text);
+    let is_idle = csv_record_text(record, columns.is_idle)
+        .and_then(parse_idle_flag)
+        .unwrap_or_else(|| infer_idle_from_text(process_text) || infer_idle_from_text(app_name_text));
+
+    let app_name = if is_idle {
+        "IDLE".to_owned()
+    } else {
+        app_name_text.to_owned()
+    };
+
+    let process_path = csv_record_text(record, columns.process_path)
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| synthetic_import_process_path(process_text));
+
+    let title = csv_record_text(record, columns.title).map(ToOwned::to_owned);
+
+    Some(ParsedImportRow {
+        start_ts,
+        end_ts,
+        is_idle,
+        app_name,
+        process_path,
+        title,
+    })
+}
+
+fn parse_unix_seconds(value: &str) -> Option<i64> {
+    value.trim().parse::<i64>().ok()
+}
+
+fn parse_local_datetime_to_unix<Tz: TimeZone>(value: &str, tz: &Tz) -> Option<i64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    // Values with an explicit offset (ISO 8601) ignore `tz`.
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.timestamp());
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f%z", "%Y-%m-%d %H:%M:%S%.f%z", "%Y-%m-%d %H:%M:%S %z"] {
+        if let Ok(dt) = DateTime::parse_from_str(value, format) {
+            return Some(dt.timestamp());
+        }
+    }
+
+    const FORMATS: [&str; 10] = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %I:%M:%S %p",
+        "%Y/%m/%d %I:%M:%S %p",
+        "%Y/%m/%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y/%m/%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y/%m/%d %H:%M:%S%.f",
+    ];
+
+    for format in FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+            return match tz.from_local_datetime(&naive) {
+                LocalResult::Single(dt) => Some(dt.timestamp()),
+                LocalResult::Ambiguous(a, b) => Some(a.timestamp().min(b.timestamp())),
+                LocalResult::None => None,
+            };
+        }
+    }
+
+    None
+}
+
+fn parse_duration_to_seconds(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(seconds) = value.parse::<i64>() {
+        return (seconds > 0).then_some(seconds);
+    }
+
+    let parts: Vec<&str> = value.split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+
+    let mut values = Vec::with_capacity(parts.len());
+    for part in parts {
+        let parsed = part.trim().parse::<i64>().ok()?;
+        if parsed < 0 {
+            return None;
+        }
+        values.push(parsed);
+    }
+
+    let seconds = if values.len() == 3 {
+        values[0]
+            .saturating_mul(3600)
+            .saturating_add(values[1].saturating_mul(60))
+            .saturating_add(values[2])
+    } else {
+        values[0].saturating_mul(60).saturating_add(values[1])
+    };
+
+    (seconds > 0).then_some(seconds)
+}
+
+fn parse_idle_flag(value: &str) -> Option<bool> {
+    let normalized = value.trim().to_ascii_lowercase();
+    match normalized.as_str() {
+        "1" | "true" | "yes" | "y" => Some(true),
+        "0" | "false" | "no" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+fn infer_idle_from_text(value: &str) -> bool {
+    let normalized = value.trim().to_ascii_lowercase();
+    normalized == "idle" || normalized == "idling" || normalized == "afk" || value.trim() == "\u{7A7A}\u{95F2}"
+}
+
+fn synthetic_import_process_path(process: &str) -> String {
+    let cleaned = process
+        .trim()
+        .chars()
+        .map(|ch| if ch.is_control() { ' ' } else { ch })
+        .collect::<String>();
+    if cleaned.is_empty() {
+        "<import:UNKNOWN>".to_owned()
+    } else {
+        format!("<import:{}>", cleaned)
+    }
+}
+
+pub fn upsert_app_in_tx(
+    tx: &rusqlite::Transaction<'_>,
+    cache: &mut HashMap<(String, String), i64>,
+    exe_name: &str,
+    process_path: &str,
+) -> Result<i64> {
+    let key = (exe_name.to_owned(), process_path.to_owned());
+    if let Some(id) = cache.get(&key) {
+        return Ok(*id);
+    }
+
+    tx.execute(
+        "\
+        INSERT INTO apps (exe_name, process_path)
+        VALUES (?1, ?2)
+        ON CONFLICT(exe_name, process_path) DO NOTHING",
+        params![exe_name, process_path],
+    )
+    .context("failed to upsert imported app")?;
+
+    let app_id = tx
+        .query_row(
+            "SELECT id FROM apps WHERE exe_name = ?1 AND process_path = ?2",
+            params![exe_name, process_path],
+            |row| row.get::<_, i64>(0),
+        )
+        .context("failed to resolve imported app id")?;
+
+    cache.insert(key, app_id);
+    Ok(app_id)
+}
+
+pub fn upsert_url_in_tx(
+    tx: &rusqlite::Transaction<'_>,
+    cache: &mut HashMap<String, i64>,
+    url: &str,
+) -> Result<i64> {
+    if let Some(id) = cache.get(url) {
+        return Ok(*id);
+    }
+
+    tx.execute(
+        "INSERT INTO urls (url) VALUES (?1) ON CONFLICT(url) DO NOTHING",
+        params![url],
+    )
+    .context("failed to upsert imported url")?;
+
+    let url_id = tx
+        .query_row("SELECT id FROM urls WHERE url = ?1", params![url], |row| {
+            row.get::<_, i64>(0)
+        })
+        .context("failed to resolve imported url id")?;
+
+    cache.insert(url.to_owned(), url_id);
+    Ok(url_id)
+}
+
+pub fn upsert_title_in_tx(
+    tx: &rusqlite::Transaction<'_>,
+    cache: &mut HashMap<String, i64>,
+    title: &str,
+) -> Result<i64> {
+    if let Some(id) = cache.get(title) {
+        return Ok(*id);
+    }
+
+    tx.execute(
+        "\
+        INSERT INTO titles (title)
+        VALUES (?1)
+        ON CONFLICT(title) DO NOTHING",
+        params![title],
+    )
+    .context("failed to upsert imported title")?;
+
+    let title_id = tx
+        .query_row("SELECT id FROM titles WHERE title = ?1", params![title], |row| {
+            row.get::<_, i64>(0)
+        })
+        .context("failed to resolve imported title id")?;
+
+    cache.insert(title.to_owned(), title_id);
+    Ok(title_id)
+}
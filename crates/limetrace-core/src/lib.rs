@@ -0,0 +1,16 @@
+//! Storage, query and summary logic shared by the LimeTrace viewer and backend.
+
+pub mod activity;
+pub mod apps;
+pub mod connection;
+pub mod crash;
+pub mod demo;
+pub mod import;
+pub mod recorder;
+pub mod schema;
+pub mod segments;
+pub mod summary;
+pub mod time;
+pub mod timeline;
+pub mod title_regex;
+pub mod update;
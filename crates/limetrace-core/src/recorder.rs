@@ -0,0 +1,397 @@
+use anyhow::Result;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::activity::{ActivityKind, ActivitySample};
+
+#[derive(Debug, Clone)]
+pub struct SegmentInsert {
+    /// Whole seconds, as older readers of the table expect them.
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub app_id: Option<i64>,
+    pub title_id: Option<i64>,
+    pub is_idle: bool,
+    pub pid: Option<u32>,
+    pub pid_create_time: Option<u64>,
+    pub process_args: Option<String>,
+    pub url_id: Option<i64>,
+    pub is_call: bool,
+    pub monitor: Option<String>,
+}
+
+/// Where the recorder keeps apps, titles and closed segments. The backend's
+/// database implements it over its migrated schema.
+pub trait SegmentStore {
+    fn upsert_app(&mut self, exe_name: &str, process_path: &str) -> Result<i64>;
+    /// Resolves an app through its identity so a moved executable reuses the row
+    /// recorded the first time that identity was seen.
+    fn upsert_app_with_identity(&mut self, identity: &str, exe_name: &str, process_path: &str) -> Result<i64>;
+    fn upsert_title(&mut self, title: &str) -> Result<i64>;
+    fn upsert_url(&mut self, url: &str) -> Result<i64>;
+    /// Segments with `end_ms <= start_ms` are dropped.
+    fn insert_segment(&mut self, segment: &SegmentInsert) -> Result<()>;
+    /// Deletes active segments starting at or after `cutoff_ms` and trims those
+    /// running past it, once idle turns out to have begun there.
+    fn truncate_active_segments_from(&mut self, cutoff_ms: i64) -> Result<()>;
+    fn publish_live_segment(
+        &self,
+        start_ts: i64,
+        app_id: Option<i64>,
+        title_id: Option<i64>,
+        is_idle: bool,
+    ) -> Result<()>;
+    fn clear_live_segment(&self) -> Result<()>;
+}
+
+pub struct SegmentEvent<'a> {
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub is_idle: bool,
+    pub app_name: Option<&'a str>,
+    pub process_path: Option<&'a str>,
+    pub title: Option<&'a str>,
+    pub pid: Option<u32>,
+    pub process_args: Option<&'a str>,
+    pub url: Option<&'a str>,
+    pub is_call: bool,
+    pub monitor: Option<&'a str>,
+}
+
+/// Sees each segment once it has reached the store, plus the idle cutoffs that
+/// rewrite already-stored time (the backend's JSONL log and `--dry-run` echo).
+pub trait SegmentSink {
+    fn write_segment(&mut self, event: &SegmentEvent) -> Result<()>;
+    fn write_idle_cutoff(&mut self, cutoff_ts: i64) -> Result<()>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SegmentKey {
+    app_id: Option<i64>,
+    title_id: Option<i64>,
+    is_idle: bool,
+    pid: Option<u32>,
+    pid_create_time: Option<u64>,
+    url_id: Option<i64>,
+    is_call: bool,
+    monitor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct SegmentDetails {
+    app_name: Option<String>,
+    process_path: Option<String>,
+    title: Option<String>,
+    process_args: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct OpenSegment {
+    start_ms: i64,
+    end_ms: i64,
+    key: SegmentKey,
+    details: SegmentDetails,
+}
+
+/// Turns the sample stream into segments: extends the open one while the key
+/// (app, title, process, idle, call, display) stays the same, closes it on a change or after
+/// `rotate_every`, and rewrites active time as idle once idle is detected.
+pub struct Recorder<S> {
+    db: S,
+    current: Option<OpenSegment>,
+    rotate_every_ms: i64,
+    sink: Option<Box<dyn SegmentSink>>,
+    // Idle spans reported after a pause must not reach back into the paused time.
+    paused_until_ms: i64,
+    last_write_ts: Option<i64>,
+    max_sample_gap_ms: i64,
+}
+
+impl<S: SegmentStore> Recorder<S> {
+    pub fn new(db: S, rotate_every: Duration) -> Self {
+        Self {
+            db,
+            current: None,
+            rotate_every_ms: rotate_every.as_millis() as i64,
+            sink: None,
+            paused_until_ms: i64::MIN,
+            last_write_ts: None,
+            max_sample_gap_ms: 0,
+        }
+    }
+
+    pub fn with_sink(mut self, sink: Box<dyn SegmentSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    pub fn set_sink(&mut self, sink: Option<Box<dyn SegmentSink>>) {
+        self.sink = sink;
+    }
+
+    pub fn set_rotate_every(&mut self, rotate_every: Duration) {
+        self.rotate_every_ms = rotate_every.as_millis() as i64;
+    }
+
+    /// A longer silence between samples means the machine was asleep (or the
+    /// process stopped): the open segment ends at its last sample instead of
+    /// stretching across the gap. Zero turns the check off.
+    pub fn set_max_sample_gap(&mut self, max_gap: Duration) {
+        self.max_sample_gap_ms = max_gap.as_millis() as i64;
+    }
+
+    pub fn ingest(&mut self, sample: ActivitySample) -> Result<()> {
+        let sample_ms = sample.ts_ms;
+        let last_sample_ms = self.current.as_ref().map(|current| current.end_ms);
+        if let Some(last_ms) = last_sample_ms.filter(|_| self.max_sample_gap_ms > 0) {
+            if sample_ms.saturating_sub(last_ms) > self.max_sample_gap_ms {
+                self.suspend_ms(last_ms, sample_ms)?;
+            }
+        }
+        let (key, segment_start_ms, trim_active_after_ms) = match &sample.kind {
+            ActivityKind::Idle { idle_ms } => {
+                let idle_start_ms = sample_ms.saturating_sub(i64::from(*idle_ms)).max(self.paused_until_ms);
+                (Self::idle_key(), idle_start_ms, Some(idle_start_ms))
+            }
+            ActivityKind::Active(_) => (self.build_key(&sample)?, sample_ms, None),
+        };
+
+        if let Some(cutoff_ms) = trim_active_after_ms {
+            self.db.truncate_active_segments_from(cutoff_ms)?;
+            let entering_idle = self
+                .current
+                .as_ref()
+                .map(|current| !current.key.is_idle)
+                .unwrap_or(true);
+            if entering_idle {
+                if let Some(sink) = self.sink.as_mut() {
+                    if let Err(err) = sink.write_idle_cutoff(cutoff_ms.div_euclid(1000)) {
+                        eprintln!("segment sink error: {err:#}");
+                    }
+                }
+            }
+        }
+
+        if self.current.as_ref().map(|current| current.key == key).unwrap_or(false) {
+            let should_rotate = {
+                if let Some(current) = self.current.as_mut() {
+                    current.end_ms = sample_ms;
+                    self.rotate_every_ms > 0
+                        && current.end_ms.saturating_sub(current.start_ms) >= self.rotate_every_ms
+                } else {
+                    false
+                }
+            };
+
+            if should_rotate {
+                if let Some(flushed) = self.current.take() {
+                    self.flush_segment(&flushed)?;
+                    self.current = Some(OpenSegment {
+                        start_ms: sample_ms,
+                        end_ms: sample_ms,
+                        key,
+                        details: flushed.details,
+                    });
+                    self.publish_live();
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(previous) = self.current.take() {
+            let mut previous = previous;
+            // If we just detected idle, trim the tail of the in-memory active segment
+            // before flushing it, so the cutoff can become idle.
+            if key.is_idle && !previous.key.is_idle {
+                previous.end_ms = previous.end_ms.min(segment_start_ms);
+            }
+            self.flush_segment(&previous)?;
+        }
+
+        self.current = Some(OpenSegment {
+            start_ms: segment_start_ms,
+            end_ms: sample_ms,
+            key,
+            details: Self::details_for(&sample),
+        });
+        self.publish_live();
+        Ok(())
+    }
+
+    // Best effort: a failed write only delays the viewer's live block.
+    fn publish_live(&self) {
+        let Some(current) = &self.current else {
+            return;
+        };
+        if let Err(err) = self.db.publish_live_segment(
+            current.start_ms.div_euclid(1000),
+            current.key.app_id,
+            current.key.title_id,
+            current.key.is_idle,
+        ) {
+            eprintln!("live segment error: {err:#}");
+        }
+    }
+
+    /// When a closed segment last reached SQLite.
+    pub fn last_write_ts(&self) -> Option<i64> {
+        self.last_write_ts
+    }
+
+    pub fn store(&self) -> &S {
+        &self.db
+    }
+
+    pub fn flush_and_close(&mut self, now_ts: i64) -> Result<()> {
+        if let Some(mut current) = self.current.take() {
+            current.end_ms = current.end_ms.max(now_ts.saturating_mul(1000));
+            self.flush_segment(&current)?;
+        }
+        self.db.clear_live_segment()
+    }
+
+    pub fn pause(&mut self, now_ts: i64) -> Result<()> {
+        self.flush_and_close(now_ts)
+    }
+
+    pub fn resume(&mut self, now_ts: i64) {
+        self.paused_until_ms = now_ts.saturating_mul(1000);
+    }
+
+    /// Closes the open segment where the machine went to sleep and leaves the
+    /// sleep itself as a gap; idle reported after waking doesn't reach back into it.
+    pub fn suspend(&mut self, suspended_at_ts: i64, resumed_at_ts: i64) -> Result<()> {
+        self.suspend_ms(suspended_at_ts.saturating_mul(1000), resumed_at_ts.saturating_mul(1000))
+    }
+
+    fn suspend_ms(&mut self, suspended_at_ms: i64, resumed_at_ms: i64) -> Result<()> {
+        if let Some(mut current) = self.current.take() {
+            current.end_ms = current.end_ms.max(suspended_at_ms.min(resumed_at_ms));
+            self.flush_segment(&current)?;
+        }
+        self.db.clear_live_segment()?;
+        self.paused_until_ms = resumed_at_ms;
+        Ok(())
+    }
+
+    fn build_key(&mut self, sample: &ActivitySample) -> Result<SegmentKey> {
+        match &sample.kind {
+            ActivityKind::Idle { .. } => Ok(Self::idle_key()),
+            ActivityKind::Active(active) => {
+                let app_id = match &active.identity {
+                    Some(identity) => {
+                        self.db
+                            .upsert_app_with_identity(identity, &active.exe_name, &active.process_path)?
+                    }
+                    None => self.db.upsert_app(&active.exe_name, &active.process_path)?,
+                };
+                let title_id = if active.window_title.is_empty() {
+                    None
+                } else {
+                    Some(self.db.upsert_title(&active.window_title)?)
+                };
+
+                let url_id = match active.url.as_deref().filter(|url| !url.is_empty()) {
+                    Some(url) => Some(self.db.upsert_url(url)?),
+                    None => None,
+                };
+
+                Ok(SegmentKey {
+                    app_id: Some(app_id),
+                    title_id,
+                    is_idle: false,
+                    pid: Some(active.pid),
+                    pid_create_time: active.pid_create_time,
+                    url_id,
+                    is_call: active.in_call,
+                    monitor: active.monitor.clone(),
+                })
+            }
+        }
+    }
+
+    fn details_for(sample: &ActivitySample) -> SegmentDetails {
+        match &sample.kind {
+            ActivityKind::Idle { .. } => SegmentDetails::default(),
+            ActivityKind::Active(active) => SegmentDetails {
+                app_name: Some(active.exe_name.clone()),
+                process_path: Some(active.process_path.clone()),
+                title: (!active.window_title.is_empty()).then(|| active.window_title.clone()),
+                process_args: active.process_args.clone(),
+                url: active.url.clone().filter(|url| !url.is_empty()),
+            },
+        }
+    }
+
+    fn idle_key() -> SegmentKey {
+        SegmentKey {
+            app_id: None,
+            title_id: None,
+            is_idle: true,
+            pid: None,
+            pid_create_time: None,
+            url_id: None,
+            is_call: false,
+            monitor: None,
+        }
+    }
+
+    fn flush_segment(&mut self, segment: &OpenSegment) -> Result<()> {
+        // Single-sample segments, and open ones that an idle cutoff trimmed away.
+        if segment.end_ms <= segment.start_ms {
+            return Ok(());
+        }
+        let (start_ts, end_ts) = (segment.start_ms.div_euclid(1000), segment.end_ms.div_euclid(1000));
+        let row = SegmentInsert {
+            start_ts,
+            end_ts,
+            start_ms: segment.start_ms,
+            end_ms: segment.end_ms,
+            app_id: segment.key.app_id,
+            title_id: segment.key.title_id,
+            is_idle: segment.key.is_idle,
+            pid: segment.key.pid,
+            pid_create_time: segment.key.pid_create_time,
+            process_args: segment.details.process_args.clone(),
+            url_id: segment.key.url_id,
+            is_call: segment.key.is_call,
+            monitor: segment.key.monitor.clone(),
+        };
+        self.db.insert_segment(&row)?;
+        self.last_write_ts = Some(unix_seconds_now());
+
+        // Sinks are best-effort; the store stays the source of truth.
+        if let Some(sink) = self.sink.as_mut() {
+            let event = SegmentEvent {
+                start_ts,
+                end_ts,
+                start_ms: segment.start_ms,
+                end_ms: segment.end_ms,
+                is_idle: segment.key.is_idle,
+                app_name: segment.details.app_name.as_deref(),
+                process_path: segment.details.process_path.as_deref(),
+                title: segment.details.title.as_deref(),
+                pid: segment.key.pid,
+                process_args: segment.details.process_args.as_deref(),
+                url: segment.details.url.as_deref(),
+                is_call: segment.key.is_call,
+                monitor: segment.key.monitor.as_deref(),
+            };
+            if let Err(err) = sink.write_segment(&event) {
+                eprintln!("segment sink error: {err:#}");
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unix_seconds_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+
+// Databases the backend hasn't migrated yet (imports, relocated copies) lack the
+// soft-delete column that every reader filters on, the URL link they join, the
+// millisecond boundaries, the call flag and the display.
+pub fn ensure_segment_columns(conn: &Connection) -> Result<()> {
+    if conn.prepare("SELECT deleted_ts FROM segments LIMIT 0").is_err() {
+        conn.execute_batch("ALTER TABLE segments ADD COLUMN deleted_ts INTEGER;")
+            .context("failed to add segments.deleted_ts")?;
+    }
+    if conn.prepare("SELECT url_id FROM segments LIMIT 0").is_err() {
+        conn.execute_batch("ALTER TABLE segments ADD COLUMN url_id INTEGER;")
+            .context("failed to add segments.url_id")?;
+    }
+    if conn.prepare("SELECT start_ms, end_ms FROM segments LIMIT 0").is_err() {
+        conn.execute_batch(
+            "\
+            ALTER TABLE segments ADD COLUMN start_ms INTEGER;
+            ALTER TABLE segments ADD COLUMN end_ms INTEGER;",
+        )
+        .context("failed to add segments.start_ms and end_ms")?;
+    }
+    if conn.prepare("SELECT is_call FROM segments LIMIT 0").is_err() {
+        conn.execute_batch("ALTER TABLE segments ADD COLUMN is_call INTEGER NOT NULL DEFAULT 0;")
+            .context("failed to add segments.is_call")?;
+    }
+    if conn.prepare("SELECT monitor_name FROM segments LIMIT 0").is_err() {
+        conn.execute_batch("ALTER TABLE segments ADD COLUMN monitor_name TEXT;")
+            .context("failed to add segments.monitor_name")?;
+    }
+    conn.execute_batch("CREATE TABLE IF NOT EXISTS urls (id INTEGER PRIMARY KEY, url TEXT NOT NULL UNIQUE);")
+        .context("failed to create urls table")
+}
+
+/// Creates every table the viewer reads or writes, so it also works on databases
+/// the backend never opened (fresh imports, demo data).
+pub fn ensure_tracking_schema(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "\
+        PRAGMA foreign_keys = ON;
+
+        CREATE TABLE IF NOT EXISTS apps (
+          id INTEGER PRIMARY KEY,
+          exe_name TEXT NOT NULL,
+          process_path TEXT NOT NULL,
+          UNIQUE(exe_name, process_path)
+        );
+
+        CREATE TABLE IF NOT EXISTS titles (
+          id INTEGER PRIMARY KEY,
+          title TEXT NOT NULL UNIQUE
+        );
+
+        CREATE TABLE IF NOT EXISTS segments (
+          id INTEGER PRIMARY KEY,
+          start_ts INTEGER NOT NULL,
+          end_ts INTEGER NOT NULL CHECK (end_ts >= start_ts),
+          app_id INTEGER,
+          title_id INTEGER,
+          is_idle INTEGER NOT NULL DEFAULT 0,
+          pid INTEGER,
+          pid_create_time INTEGER,
+          FOREIGN KEY(app_id) REFERENCES apps(id),
+          FOREIGN KEY(title_id) REFERENCES titles(id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_segments_start ON segments(start_ts);
+        CREATE INDEX IF NOT EXISTS idx_segments_app_start ON segments(app_id, start_ts);
+        CREATE INDEX IF NOT EXISTS idx_segments_idle_start ON segments(is_idle, start_ts);
+
+        CREATE TABLE IF NOT EXISTS app_visual_cache (
+          app_key TEXT PRIMARY KEY,
+          process_path TEXT,
+          color_rgba INTEGER NOT NULL,
+          icon_width INTEGER,
+          icon_height INTEGER,
+          icon_rgba BLOB,
+          display_name TEXT,
+          updated_ts INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_app_visual_cache_process_path
+          ON app_visual_cache(process_path);
+
+        CREATE TABLE IF NOT EXISTS app_categories (
+          app_key TEXT PRIMARY KEY,
+          category TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS title_category_rules (
+          id INTEGER PRIMARY KEY,
+          pattern TEXT NOT NULL,
+          category TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS day_notes (
+          date TEXT PRIMARY KEY,
+          note TEXT NOT NULL,
+          updated_ts INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS days_off (
+          date TEXT PRIMARY KEY,
+          kind TEXT NOT NULL,
+          label TEXT NOT NULL DEFAULT ''
+        );
+
+        CREATE TABLE IF NOT EXISTS app_aliases (
+          app_key TEXT PRIMARY KEY,
+          alias TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS excluded_apps (
+          app_key TEXT PRIMARY KEY
+        );
+
+        CREATE TABLE IF NOT EXISTS notifications (
+          id INTEGER PRIMARY KEY,
+          ts INTEGER NOT NULL,
+          kind TEXT NOT NULL,
+          app_key TEXT,
+          message TEXT NOT NULL,
+          read INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS title_tag_rules (
+          id INTEGER PRIMARY KEY,
+          name TEXT NOT NULL,
+          pattern TEXT NOT NULL,
+          scanned_title_id INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS title_tags (
+          title_id INTEGER NOT NULL,
+          rule_id INTEGER NOT NULL,
+          value TEXT NOT NULL,
+          PRIMARY KEY(title_id, rule_id, value)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_title_tags_rule_value ON title_tags(rule_id, value);
+
+        CREATE TABLE IF NOT EXISTS segment_tags (
+          id INTEGER PRIMARY KEY,
+          start_ts INTEGER NOT NULL,
+          end_ts INTEGER NOT NULL,
+          tag TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_segment_tags_start ON segment_tags(start_ts);
+
+        CREATE TABLE IF NOT EXISTS sync_state (
+          key TEXT PRIMARY KEY,
+          value INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS sync_peers (
+          machine TEXT PRIMARY KEY,
+          last_seq INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS sync_segment_origin (
+          segment_id INTEGER PRIMARY KEY,
+          machine TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS focus_sessions (
+          id INTEGER PRIMARY KEY,
+          started_ts INTEGER NOT NULL,
+          ends_ts INTEGER NOT NULL,
+          stopped_ts INTEGER,
+          minimize_distracting INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS focus_violations (
+          id INTEGER PRIMARY KEY,
+          session_id INTEGER NOT NULL,
+          ts INTEGER NOT NULL,
+          app_key TEXT NOT NULL,
+          title TEXT,
+          FOREIGN KEY(session_id) REFERENCES focus_sessions(id)
+        );",
+    )
+    .context("failed to ensure tracking schema")?;
+    ensure_segment_columns(conn)
+}
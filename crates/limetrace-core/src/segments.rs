@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+use crate::connection::open_read_only;
+
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub start_ts: i64,
+    pub end_ts: i64,
+    /// Millisecond boundaries; rows written before they were recorded carry
+    /// their whole seconds here.
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub is_idle: bool,
+    pub app_name: String,
+    pub process_path: Option<String>,
+    pub title: Option<String>,
+    /// The browser tab's URL or domain, when the backend captured one.
+    pub url: Option<String>,
+    /// A communications app had the microphone open, i.e. a call or meeting.
+    pub is_call: bool,
+    /// The display the window was on, where the backend could tell.
+    pub monitor: Option<String>,
+}
+
+pub fn load_segments_for_range(db_path: &Path, range_start: i64, range_end: i64) -> Result<Vec<Segment>> {
+    let conn = open_read_only(db_path)?;
+    query_segments_for_range(&conn, range_start, range_end)
+}
+
+pub fn query_segments_for_range(conn: &Connection, range_start: i64, range_end: i64) -> Result<Vec<Segment>> {
+    let mut result = Vec::new();
+    for_each_segment_in_range(conn, range_start, range_end, |seg| result.push(seg))?;
+    Ok(result)
+}
+
+pub fn count_segments_in_range(conn: &Connection, range_start: i64, range_end: i64) -> Result<i64> {
+    let mut stmt = conn.prepare_cached(
+        "\
+        SELECT COUNT(*)
+        FROM segments
+        WHERE deleted_ts IS NULL
+          AND start_ts >= ?3
+          AND start_ts < ?2
+          AND COALESCE(end_ms, end_ts * 1000) > ?1 * 1000",
+    )?;
+    let earliest_start = earliest_overlapping_start(conn, range_start);
+    let count = stmt.query_row(params![range_start, range_end, earliest_start], |row| row.get(0))?;
+    Ok(count)
+}
+
+pub fn for_each_segment_in_range(
+    conn: &Connection,
+    range_start: i64,
+    range_end: i64,
+    mut visit: impl FnMut(Segment),
+) -> Result<()> {
+    let mut stmt = conn.prepare_cached(
+        "\
+        SELECT
+          s.start_ts,
+          s.end_ts,
+          s.is_idle,
+          a.exe_name,
+          a.process_path,
+          t.title,
+          u.url,
+          COALESCE(s.start_ms, s.start_ts * 1000),
+          COALESCE(s.end_ms, s.end_ts * 1000),
+          s.is_call,
+          s.monitor_name
+        FROM segments s
+        LEFT JOIN apps a ON a.id = s.app_id
+        LEFT JOIN titles t ON t.id = s.title_id
+        LEFT JOIN urls u ON u.id = s.url_id
+        WHERE s.deleted_ts IS NULL
+          AND s.start_ts >= ?3
+          AND s.start_ts < ?2
+          AND COALESCE(s.end_ms, s.end_ts * 1000) > ?1 * 1000
+        ORDER BY s.start_ts ASC, 8 ASC",
+    )?;
+
+    let earliest_start = earliest_overlapping_start(conn, range_start);
+    let mut rows = stmt.query(params![range_start, range_end, earliest_start])?;
+    while let Some(row) = rows.next()? {
+        let is_idle: i64 = row.get(2)?;
+        let app_name: Option<String> = row.get(3)?;
+        let process_path: Option<String> = row.get(4)?;
+        let title: Option<String> = row.get(5)?;
+
+        visit(Segment {
+            start_ts: row.get(0)?,
+            end_ts: row.get(1)?,
+            start_ms: row.get(7)?,
+            end_ms: row.get(8)?,
+            is_idle: is_idle != 0,
+            app_name: app_name.unwrap_or_else(|| "UNKNOWN".to_owned()),
+            process_path,
+            title,
+            url: row.get(6)?,
+            is_call: row.get::<_, i64>(9)? != 0,
+            monitor: row.get(10)?,
+        });
+    }
+    Ok(())
+}
+
+/// Turns the active time between `cutoff_ts` and the idle stretch starting at
+/// `idle_start_ts` into idle: segments inside it are soft-deleted at `deleted_ts`,
+/// one straddling the cutoff ends there, and the idle segment starts there.
+/// Both the second and millisecond boundaries move, since readers prefer the latter.
+pub fn reclassify_as_idle(conn: &mut Connection, cutoff_ts: i64, idle_start_ts: i64, deleted_ts: i64) -> Result<()> {
+    let tx = conn
+        .transaction()
+        .context("failed to start idle reclassification transaction")?;
+    tx.execute(
+        "\
+        UPDATE segments SET deleted_ts = ?3
+        WHERE is_idle = 0 AND deleted_ts IS NULL AND start_ts >= ?1 AND end_ts <= ?2",
+        params![cutoff_ts, idle_start_ts, deleted_ts],
+    )
+    .context("failed to delete pre-idle segments")?;
+    tx.execute(
+        "\
+        UPDATE segments SET end_ts = ?1, end_ms = ?1 * 1000
+        WHERE is_idle = 0
+          AND deleted_ts IS NULL
+          AND COALESCE(start_ms, start_ts * 1000) < ?1 * 1000
+          AND COALESCE(end_ms, end_ts * 1000) > ?1 * 1000",
+        params![cutoff_ts],
+    )
+    .context("failed to trim pre-idle segment")?;
+    tx.execute(
+        "UPDATE segments SET start_ts = ?1, start_ms = ?1 * 1000 WHERE is_idle = 1 AND start_ts = ?2",
+        params![cutoff_ts, idle_start_ts],
+    )
+    .context("failed to extend idle segment")?;
+    tx.commit()
+        .context("failed to commit idle reclassification transaction")
+}
+
+/// The lowest `start_ts` a segment overlapping `range_start` can have, so range
+/// queries can bound their index scan from below.
+pub fn earliest_overlapping_start(conn: &Connection, range_start: i64) -> i64 {
+    match load_max_segment_span(conn) {
+        Some(max_span) => range_start.saturating_sub(max_span),
+        None => i64::MIN,
+    }
+}
+
+// Maintained by the backend's schema migrations; databases it has not upgraded
+// yet fall back to an unbounded scan.
+fn load_max_segment_span(conn: &Connection) -> Option<i64> {
+    conn.prepare_cached("SELECT max_span_secs FROM segment_meta WHERE id = 1")
+        .and_then(|mut stmt| stmt.query_row([], |row| row.get::<_, i64>(0)))
+        .ok()
+}
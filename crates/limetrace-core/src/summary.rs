@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use crate::apps::{
+    display_app_name, is_locked_session, is_synthetic_import_path, should_hide_in_visualization, url_domain,
+};
+use crate::segments::Segment;
+use crate::timeline::MERGE_GAP_TOLERANCE_SECS;
+
+/// Looks up an executable's user-facing name (its version resource or desktop
+/// entry). The viewer passes its platform lookup; tests pass `|_| None`.
+pub type DescribeApp = fn(&str) -> Option<String>;
+
+#[derive(Debug, Clone)]
+pub struct SummaryRow {
+    pub app_name: String,
+    pub display_name: String,
+    pub duration_secs: i64,
+    pub process_path: Option<String>,
+    pub is_idle: bool,
+    pub last_used_ts: i64,
+    pub previous_duration_secs: i64,
+    pub session_count: usize,
+    /// The longest titles and their seconds, longest first; at most
+    /// [`SUMMARY_TOP_TITLES`].
+    pub top_titles: Vec<(String, i64)>,
+    /// Like `top_titles`, by the domain of captured browser URLs.
+    pub top_sites: Vec<(String, i64)>,
+}
+
+impl SummaryRow {
+    pub fn average_session_secs(&self) -> i64 {
+        if self.session_count == 0 {
+            return 0;
+        }
+        self.duration_secs / self.session_count as i64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummarySort {
+    DurationDesc,
+    DurationAsc,
+    Name,
+    LastUsed,
+    ChangeVsPrevious,
+}
+
+impl SummarySort {
+    pub const ALL: [Self; 5] = [
+        Self::DurationDesc,
+        Self::DurationAsc,
+        Self::Name,
+        Self::LastUsed,
+        Self::ChangeVsPrevious,
+    ];
+}
+
+// Gaps up to this long (e.g. a quick alt-tab) continue the same usage session.
+pub const SUMMARY_SESSION_GAP_SECS: i64 = 60;
+pub const SUMMARY_TOP_TITLES: usize = 3;
+
+pub fn build_summary_rows(
+    range_start: i64,
+    range_end: i64,
+    segments: &[Segment],
+    min_segment_secs: i64,
+    describe_app: DescribeApp,
+) -> Vec<SummaryRow> {
+    let mut summary = SummaryAccumulator::new(range_start, range_end, min_segment_secs, describe_app);
+    for seg in segments {
+        summary.push(seg);
+    }
+    summary.finish()
+}
+
+// Folds segments (in start order) into summary rows without keeping them, so large
+// ranges can be summarized while streaming rows out of SQLite. Time is added up in
+// milliseconds and only rounded to seconds in `finish`.
+pub struct SummaryAccumulator {
+    range_start_ms: i64,
+    range_end_ms: i64,
+    // Active segments shorter than this are counted in `fleeting_secs` instead of their app.
+    min_segment_ms: i64,
+    fleeting_ms: i64,
+    call_ms: i64,
+    away_ms: i64,
+    // The idle/locked run being followed: its clipped bounds and whether it holds a lock.
+    away_run: Option<(i64, i64, bool)>,
+    totals: HashMap<String, SummaryRow>,
+    duration_ms_by_key: HashMap<String, i64>,
+    session_end_by_key: HashMap<String, i64>,
+    title_ms_by_key: HashMap<String, HashMap<String, i64>>,
+    site_ms_by_key: HashMap<String, HashMap<String, i64>>,
+    display_name_by_path: HashMap<String, Option<String>>,
+    describe_app: DescribeApp,
+}
+
+impl SummaryAccumulator {
+    pub fn new(range_start: i64, range_end: i64, min_segment_secs: i64, describe_app: DescribeApp) -> Self {
+        Self {
+            range_start_ms: range_start.saturating_mul(1000),
+            range_end_ms: range_end.saturating_mul(1000),
+            min_segment_ms: min_segment_secs.saturating_mul(1000),
+            fleeting_ms: 0,
+            call_ms: 0,
+            away_ms: 0,
+            away_run: None,
+            totals: HashMap::new(),
+            duration_ms_by_key: HashMap::new(),
+            session_end_by_key: HashMap::new(),
+            title_ms_by_key: HashMap::new(),
+            site_ms_by_key: HashMap::new(),
+            display_name_by_path: HashMap::new(),
+            describe_app,
+        }
+    }
+
+    pub fn push(&mut self, seg: &Segment) {
+        self.track_away(seg);
+        if should_hide_summary_app(&seg.app_name, seg.is_idle, seg.process_path.as_deref()) {
+            return;
+        }
+
+        let clipped_start = seg.start_ms.max(self.range_start_ms);
+        let clipped_end = seg.end_ms.min(self.range_end_ms);
+        if clipped_end <= clipped_start {
+            return;
+        }
+
+        let duration = clipped_end - clipped_start;
+        if seg.is_call && !seg.is_idle {
+            self.call_ms += duration;
+        }
+        if !seg.is_idle && seg.end_ms - seg.start_ms < self.min_segment_ms {
+            self.fleeting_ms += duration;
+            return;
+        }
+        let display_name = resolve_summary_display_name(seg, &mut self.display_name_by_path, self.describe_app);
+        let key = normalize_summary_group_key(&display_name);
+
+        let session_end = self.session_end_by_key.entry(key.clone()).or_insert(i64::MIN);
+        let starts_new_session = clipped_start > session_end.saturating_add(SUMMARY_SESSION_GAP_SECS * 1000);
+        *session_end = (*session_end).max(clipped_end);
+        if let Some(title) = seg.title.as_deref().map(str::trim).filter(|title| !title.is_empty()) {
+            *self
+                .title_ms_by_key
+                .entry(key.clone())
+                .or_default()
+                .entry(title.to_owned())
+                .or_default() += duration;
+        }
+        if let Some(site) = seg.url.as_deref().and_then(url_domain) {
+            *self.site_ms_by_key.entry(key.clone()).or_default().entry(site).or_default() += duration;
+        }
+
+        *self.duration_ms_by_key.entry(key.clone()).or_default() += duration;
+        let last_used_ts = ceil_secs(clipped_end);
+        let entry = self.totals.entry(key).or_insert_with(|| SummaryRow {
+            app_name: seg.app_name.clone(),
+            display_name,
+            duration_secs: 0,
+            process_path: seg.process_path.clone(),
+            is_idle: seg.is_idle,
+            last_used_ts,
+            previous_duration_secs: 0,
+            session_count: 0,
+            top_titles: Vec::new(),
+            top_sites: Vec::new(),
+        });
+
+        if starts_new_session {
+            entry.session_count += 1;
+        }
+        entry.last_used_ts = entry.last_used_ts.max(last_used_ts);
+        if should_prefer_process_path(entry.process_path.as_deref(), seg.process_path.as_deref()) {
+            entry.process_path = seg.process_path.clone();
+            entry.app_name = seg.app_name.clone();
+            entry.is_idle = seg.is_idle;
+        }
+    }
+
+    pub fn fleeting_secs(&self) -> i64 {
+        self.fleeting_ms / 1000
+    }
+
+    /// Time spent in calls, whichever app was in front; it also counts towards those apps.
+    pub fn call_secs(&self) -> i64 {
+        self.call_ms / 1000
+    }
+
+    /// Time away from the machine: runs of idle and locked time that include a lock,
+    /// as the timeline draws them after `timeline::coalesce_away`.
+    pub fn away_secs(&self) -> i64 {
+        let pending = match self.away_run {
+            Some((start, end, true)) => end - start,
+            _ => 0,
+        };
+        (self.away_ms + pending) / 1000
+    }
+
+    fn track_away(&mut self, seg: &Segment) {
+        let locked = is_locked_session(seg.process_path.as_deref());
+        if !seg.is_idle && !locked {
+            self.flush_away_run();
+            return;
+        }
+        let start = seg.start_ms.max(self.range_start_ms);
+        let end = seg.end_ms.min(self.range_end_ms);
+        if end <= start {
+            return;
+        }
+        match &mut self.away_run {
+            Some((_, run_end, run_locked)) if start <= run_end.saturating_add(MERGE_GAP_TOLERANCE_SECS * 1000) => {
+                *run_end = (*run_end).max(end);
+                *run_locked |= locked;
+            }
+            _ => {
+                self.flush_away_run();
+                self.away_run = Some((start, end, locked));
+            }
+        }
+    }
+
+    fn flush_away_run(&mut self) {
+        if let Some((start, end, true)) = self.away_run.take() {
+            self.away_ms += end - start;
+        }
+    }
+
+    pub fn finish(mut self) -> Vec<SummaryRow> {
+        let mut rows: Vec<SummaryRow> = self
+            .totals
+            .into_iter()
+            .map(|(key, mut row)| {
+                row.duration_secs = self.duration_ms_by_key.get(&key).copied().unwrap_or(0) / 1000;
+                row.top_titles = longest_first(self.title_ms_by_key.remove(&key));
+                row.top_sites = longest_first(self.site_ms_by_key.remove(&key));
+                row
+            })
+            .collect();
+        sort_summary_rows(&mut rows, SummarySort::DurationDesc);
+        rows
+    }
+}
+
+fn longest_first(ms_by_name: Option<HashMap<String, i64>>) -> Vec<(String, i64)> {
+    let mut names: Vec<(String, i64)> = ms_by_name
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, ms)| (name, ms / 1000))
+        .collect();
+    names.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    names.truncate(SUMMARY_TOP_TITLES);
+    names
+}
+
+fn ceil_secs(ms: i64) -> i64 {
+    ms.div_euclid(1000) + i64::from(ms.rem_euclid(1000) > 0)
+}
+
+pub fn sort_summary_rows(rows: &mut [SummaryRow], sort: SummarySort) {
+    rows.sort_by(|a, b| {
+        let primary = match sort {
+            SummarySort::DurationDesc => b.duration_secs.cmp(&a.duration_secs),
+            SummarySort::DurationAsc => a.duration_secs.cmp(&b.duration_secs),
+            SummarySort::Name => a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()),
+            SummarySort::LastUsed => b.last_used_ts.cmp(&a.last_used_ts),
+            SummarySort::ChangeVsPrevious => summary_change_sort_key(b).total_cmp(&summary_change_sort_key(a)),
+        };
+        primary
+            .then_with(|| b.duration_secs.cmp(&a.duration_secs))
+            .then_with(|| a.display_name.cmp(&b.display_name))
+            .then_with(|| a.app_name.cmp(&b.app_name))
+            .then_with(|| {
+                a.process_path
+                    .as_deref()
+                    .unwrap_or("")
+                    .cmp(b.process_path.as_deref().unwrap_or(""))
+            })
+            .then_with(|| a.is_idle.cmp(&b.is_idle))
+    });
+}
+
+pub fn summary_change_ratio(row: &SummaryRow) -> Option<f64> {
+    if row.previous_duration_secs <= 0 {
+        return None;
+    }
+    let previous = row.previous_duration_secs as f64;
+    Some((row.duration_secs as f64 - previous) / previous)
+}
+
+fn summary_change_sort_key(row: &SummaryRow) -> f64 {
+    // Apps with no time in the previous period count as the largest increase.
+    summary_change_ratio(row).unwrap_or(f64::INFINITY)
+}
+
+pub fn apply_previous_period_durations(rows: &mut [SummaryRow], previous_rows: &[SummaryRow]) {
+    let previous_by_key: HashMap<String, i64> = previous_rows
+        .iter()
+        .map(|row| (normalize_summary_group_key(&row.display_name), row.duration_secs))
+        .collect();
+    for row in rows {
+        row.previous_duration_secs = previous_by_key
+            .get(&normalize_summary_group_key(&row.display_name))
+            .copied()
+            .unwrap_or(0);
+    }
+}
+
+fn resolve_summary_display_name(
+    seg: &Segment,
+    display_name_by_path: &mut HashMap<String, Option<String>>,
+    describe_app: DescribeApp,
+) -> String {
+    if let Some(path) = seg
+        .process_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .filter(|path| !is_synthetic_import_path(path))
+    {
+        let cached = display_name_by_path
+            .entry(path.to_owned())
+            .or_insert_with(|| describe_app(path));
+        if let Some(name) = cached.as_deref() {
+            let trimmed = name.trim();
+            if !trimmed.is_empty() {
+                return trimmed.to_owned();
+            }
+        }
+    }
+
+    display_app_name(&seg.app_name, seg.is_idle)
+}
+
+pub fn normalize_summary_group_key(display_name: &str) -> String {
+    let mut key = String::with_capacity(display_name.len());
+    let mut prev_is_space = false;
+    for ch in display_name.trim().chars() {
+        if ch.is_whitespace() {
+            if !prev_is_space {
+                key.push(' ');
+                prev_is_space = true;
+            }
+            continue;
+        }
+        prev_is_space = false;
+        key.push(ch.to_ascii_lowercase());
+    }
+    key
+}
+
+fn should_prefer_process_path(current: Option<&str>, incoming: Option<&str>) -> bool {
+    let incoming = incoming.map(str::trim).filter(|value| !value.is_empty());
+    let current = current.map(str::trim).filter(|value| !value.is_empty());
+
+    match (current, incoming) {
+        (None, Some(_)) => true,
+        (Some(cur), Some(next)) => is_synthetic_import_path(cur) && !is_synthetic_import_path(next),
+        _ => false,
+    }
+}
+
+pub fn should_hide_summary_app(app_name: &str, is_idle: bool, process_path: Option<&str>) -> bool {
+    should_hide_in_visualization(app_name, is_idle, process_path)
+}
@@ -0,0 +1,32 @@
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone};
+
+pub fn local_midnight_ts(date: NaiveDate) -> Option<i64> {
+    midnight_ts_in(&Local, date)
+}
+
+/// Where `date` starts in `tz`. On the repeated hour that is the earlier midnight;
+/// in zones that skip midnight for DST, the first minute that exists.
+pub fn midnight_ts_in<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> Option<i64> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    if let Some(dt) = tz.from_local_datetime(&midnight).earliest() {
+        return Some(dt.timestamp());
+    }
+    (1..=180)
+        .map(|minutes| midnight + chrono::Duration::minutes(minutes))
+        .find_map(|naive| tz.from_local_datetime(&naive).earliest())
+        .map(|dt| dt.timestamp())
+}
+
+pub fn date_range_bounds(start_date: NaiveDate, end_exclusive_date: NaiveDate) -> Option<(i64, i64)> {
+    date_range_bounds_in(&Local, start_date, end_exclusive_date)
+}
+
+pub fn date_range_bounds_in<Tz: TimeZone>(
+    tz: &Tz,
+    start_date: NaiveDate,
+    end_exclusive_date: NaiveDate,
+) -> Option<(i64, i64)> {
+    let start = midnight_ts_in(tz, start_date)?;
+    let end = midnight_ts_in(tz, end_exclusive_date)?;
+    Some((start, end))
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use crate::apps::{is_locked_session, normalize_app_key, AWAY_APP};
+use crate::segments::Segment;
+
+pub const MIN_TIMELINE_VIEW_SECS: i64 = 5 * 60;
+/// Same-app segments this close together are drawn as one bar.
+pub const MERGE_GAP_TOLERANCE_SECS: i64 = 1;
+
+#[derive(Debug, Clone)]
+pub struct TimelineRenderSegment {
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub is_idle: bool,
+    pub app_name: String,
+    pub process_path: Option<String>,
+    pub title: Option<String>,
+    pub multi_title: bool,
+    // Only filled while a single app is selected, for the per-title ribbons.
+    pub title_runs: Vec<TitleRun>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TitleRun {
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub title: Option<String>,
+}
+
+/// Clips `segments` to the day and merges neighbours of the same app into the bars the
+/// timeline draws. `segments` must be sorted by start time.
+pub fn build_timeline_segments(
+    day_start: i64,
+    day_end: i64,
+    segments: &[Segment],
+    selected_app_keys: Option<&HashSet<String>>,
+    collect_title_runs: bool,
+) -> Vec<TimelineRenderSegment> {
+    let mut merged: Vec<TimelineRenderSegment> = Vec::new();
+
+    for seg in segments {
+        if selected_app_keys.is_some_and(|keys| !keys.contains(&normalize_app_key(&seg.app_name))) {
+            continue;
+        }
+
+        let clipped_start = seg.start_ts.max(day_start);
+        let clipped_end = seg.end_ts.min(day_end);
+        if clipped_end <= clipped_start {
+            continue;
+        }
+
+        if let Some(last) = merged.last_mut() {
+            if last.is_idle == seg.is_idle
+                && last.app_name == seg.app_name
+                && last.process_path == seg.process_path
+                && clipped_start <= last.end_ts.saturating_add(MERGE_GAP_TOLERANCE_SECS)
+            {
+                if clipped_end > last.end_ts {
+                    last.end_ts = clipped_end;
+                }
+                if !same_title(&last.title, &seg.title) {
+                    last.multi_title = true;
+                }
+                if collect_title_runs {
+                    match last.title_runs.last_mut() {
+                        Some(run) if same_title(&run.title, &seg.title) => {
+                            run.end_ts = run.end_ts.max(clipped_end);
+                        }
+                        _ => last.title_runs.push(TitleRun {
+                            start_ts: clipped_start,
+                            end_ts: clipped_end,
+                            title: seg.title.clone(),
+                        }),
+                    }
+                }
+                continue;
+            }
+        }
+
+        merged.push(TimelineRenderSegment {
+            start_ts: clipped_start,
+            end_ts: clipped_end,
+            is_idle: seg.is_idle,
+            app_name: seg.app_name.clone(),
+            process_path: seg.process_path.clone(),
+            title: seg.title.clone(),
+            multi_title: false,
+            title_runs: if collect_title_runs {
+                vec![TitleRun {
+                    start_ts: clipped_start,
+                    end_ts: clipped_end,
+                    title: seg.title.clone(),
+                }]
+            } else {
+                Vec::new()
+            },
+        });
+    }
+
+    merged
+}
+
+/// Folds each run of idle and locked segments that contains a lock into one idle
+/// [`AWAY_APP`] segment, so stepping away reads as a single block rather than idle,
+/// locked and idle again. Idle-only runs and active segments are kept as they are.
+/// `segments` must be sorted by start time.
+pub fn coalesce_away(segments: &[Segment]) -> Vec<Segment> {
+    let mut result: Vec<Segment> = Vec::with_capacity(segments.len());
+    let mut run_start = 0;
+    let mut in_run = false;
+    for seg in segments {
+        let away = seg.is_idle || is_locked_session(seg.process_path.as_deref());
+        let continues_run = away
+            && in_run
+            && result
+                .last()
+                .is_some_and(|last| seg.start_ms <= last.end_ms.saturating_add(MERGE_GAP_TOLERANCE_SECS * 1000));
+        if !continues_run {
+            fold_away_run(&mut result, run_start);
+            run_start = result.len();
+        }
+        in_run = away;
+        result.push(seg.clone());
+    }
+    fold_away_run(&mut result, run_start);
+    result
+}
+
+fn fold_away_run(result: &mut Vec<Segment>, run_start: usize) {
+    let run = &result[run_start..];
+    if !run.iter().any(|seg| is_locked_session(seg.process_path.as_deref())) {
+        return;
+    }
+    let away = Segment {
+        start_ts: run[0].start_ts,
+        end_ts: run.iter().map(|seg| seg.end_ts).max().unwrap_or(run[0].end_ts),
+        start_ms: run[0].start_ms,
+        end_ms: run.iter().map(|seg| seg.end_ms).max().unwrap_or(run[0].end_ms),
+        is_idle: true,
+        app_name: AWAY_APP.to_owned(),
+        process_path: None,
+        title: None,
+        url: None,
+        is_call: false,
+        monitor: None,
+    };
+    result.truncate(run_start);
+    result.push(away);
+}
+
+fn same_title(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (Some(x), Some(y)) => x == y,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+pub fn sanitize_view_range(
+    view_range: Option<(i64, i64)>,
+    day_start: i64,
+    day_end: i64,
+) -> (i64, i64) {
+    let day_span = (day_end - day_start).max(1);
+    if let Some((start, end)) = view_range {
+        let span = (end - start).clamp(MIN_TIMELINE_VIEW_SECS.min(day_span), day_span);
+        return clamp_view_span(start, span, day_start, day_end);
+    }
+    (day_start, day_end)
+}
+
+pub fn clamp_view_span(start: i64, span: i64, day_start: i64, day_end: i64) -> (i64, i64) {
+    let day_span = (day_end - day_start).max(1);
+    let span = span.clamp(MIN_TIMELINE_VIEW_SECS.min(day_span), day_span);
+    let max_start = day_end - span;
+    let clamped_start = start.clamp(day_start, max_start);
+    (clamped_start, clamped_start + span)
+}
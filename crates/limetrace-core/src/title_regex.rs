@@ -0,0 +1,38 @@
+//! Title patterns for tag, filter and redaction rules, in the `regex` crate's
+//! syntax. Matching runs in time linear in the title, so no pattern can stall the
+//! UI or the backend's poll loop.
+
+use anyhow::Result;
+use regex::{Regex, RegexBuilder};
+
+// Patterns come from settings files and the command line; this keeps a huge or
+// deeply nested one from compiling into an oversized program.
+const COMPILED_SIZE_LIMIT: usize = 1 << 20;
+
+pub struct TitleRegex {
+    regex: Regex,
+}
+
+impl TitleRegex {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let regex = RegexBuilder::new(pattern)
+            .size_limit(COMPILED_SIZE_LIMIT)
+            .build()?;
+        Ok(Self { regex })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+
+    /// One value per non-overlapping match: the first capture group when the
+    /// pattern has one and it took part in the match, otherwise the whole match.
+    pub fn tags(&self, text: &str) -> Vec<String> {
+        self.regex
+            .captures_iter(text)
+            .filter_map(|captures| captures.get(1).or_else(|| captures.get(0)))
+            .filter(|value| !value.is_empty())
+            .map(|value| value.as_str().to_owned())
+            .collect()
+    }
+}
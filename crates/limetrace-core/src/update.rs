@@ -0,0 +1,268 @@
+//! Staged self-updates for both binaries. The viewer downloads a release into the
+//! `update` folder beside the database, checked against its `SHA256SUMS` and the
+//! signature over that file and the version together; each binary then swaps
+//! itself in when it finds a staged copy of its own file, after checking the
+//! signature again and that the version is newer than its own: the backend within
+//! a minute, the viewer on its next start.
+
+use anyhow::{anyhow, bail, Context, Result};
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const MANIFEST_FILE: &str = "staged.json";
+const REPLACED_SUFFIX: &str = ".old";
+
+/// Hex ed25519 key release builds are signed with; without it nothing gets staged.
+pub const UPDATE_PUBLIC_KEY: Option<&str> = option_env!("LIMETRACE_UPDATE_PUBLIC_KEY");
+
+/// The folder both binaries share for staged updates: `update` beside the database.
+pub fn staging_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .join("update")
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// What a release signs: its version on a first line, then the raw `SHA256SUMS`,
+/// so the hashes of one release can't be passed off as another version.
+fn signed_message(version: &str, checksums: &str) -> Vec<u8> {
+    format!("limetrace {version}\n{checksums}").into_bytes()
+}
+
+/// Checks the detached signature (hex) over [`signed_message`].
+fn verify_signature(version: &str, checksums: &str, signature_hex: &str, public_key_hex: &str) -> Result<()> {
+    let key: [u8; 32] = decode_hex(public_key_hex)?
+        .try_into()
+        .map_err(|_| anyhow!("update public key must be 32 bytes"))?;
+    let signature: [u8; 64] = decode_hex(signature_hex)?
+        .try_into()
+        .map_err(|_| anyhow!("signature must be 64 bytes"))?;
+    VerifyingKey::from_bytes(&key)
+        .context("invalid update public key")?
+        .verify_strict(&signed_message(version, checksums), &Signature::from_bytes(&signature))
+        .with_context(|| format!("SHA256SUMS signature does not match version {version}"))
+}
+
+/// `<hex>  <file name>` lines as written by `sha256sum`.
+fn parse_checksums(text: &str) -> BTreeMap<String, String> {
+    text.lines()
+        .filter_map(|line| {
+            let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+            let name = name.trim().trim_start_matches('*');
+            Some((name.to_owned(), hash.to_ascii_lowercase()))
+        })
+        .collect()
+}
+
+/// A downloaded release asset and the file name it replaces once installed.
+pub struct Download {
+    pub asset: String,
+    pub install_as: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Checks `bytes` against the line for `asset` in `checksums`.
+fn verify_checksum(checksums: &str, asset: &str, bytes: &[u8]) -> Result<()> {
+    let checksums = parse_checksums(checksums);
+    let expected = checksums
+        .get(asset)
+        .with_context(|| format!("{asset} is not listed in SHA256SUMS"))?;
+    let actual = sha256_hex(bytes);
+    if &actual != expected {
+        bail!("{asset} checksum mismatch: expected {expected}, got {actual}");
+    }
+    Ok(())
+}
+
+/// Writes the downloads into `dir` once the signature over `version` and
+/// `checksums` holds and each asset matches its line. The signed text is kept with
+/// them so [`apply_staged`] can check it again. A later stage replaces whatever is
+/// still waiting from an earlier one.
+pub fn stage(dir: &Path, version: &str, downloads: &[Download], checksums: &str, signature_hex: &str, public_key_hex: &str) -> Result<()> {
+    verify_signature(version, checksums, signature_hex, public_key_hex)?;
+    let mut files = BTreeMap::new();
+    for download in downloads {
+        verify_checksum(checksums, &download.asset, &download.bytes)?;
+        files.insert(download.install_as.clone(), download.asset.clone());
+    }
+    fs::create_dir_all(dir).with_context(|| format!("failed to create update directory: {}", dir.display()))?;
+    for download in downloads {
+        let path = dir.join(&download.install_as);
+        fs::write(&path, &download.bytes).with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    let manifest = Manifest {
+        version: version.to_owned(),
+        files,
+        checksums: checksums.to_owned(),
+        signature: signature_hex.trim().to_owned(),
+    };
+    manifest.write(dir)
+}
+
+/// `staged.json`: the staged version, each file still to swap in with the release
+/// asset it came from, and the signed `SHA256SUMS` they were checked against.
+struct Manifest {
+    version: String,
+    files: BTreeMap<String, String>,
+    checksums: String,
+    signature: String,
+}
+
+impl Manifest {
+    fn read(dir: &Path) -> Option<Self> {
+        let manifest: serde_json::Value = serde_json::from_slice(&fs::read(dir.join(MANIFEST_FILE)).ok()?).ok()?;
+        let text = |key: &str| Some(manifest.get(key)?.as_str()?.to_owned());
+        let files = manifest
+            .get("files")?
+            .as_object()?
+            .iter()
+            .filter_map(|(name, asset)| Some((name.clone(), asset.as_str()?.to_owned())))
+            .collect();
+        Some(Self {
+            version: text("version")?,
+            files,
+            checksums: text("checksums")?,
+            signature: text("signature")?,
+        })
+    }
+
+    /// Writes the manifest, or removes it once no files are left.
+    fn write(&self, dir: &Path) -> Result<()> {
+        let path = dir.join(MANIFEST_FILE);
+        if self.files.is_empty() {
+            return fs::remove_file(&path).with_context(|| format!("failed to remove {}", path.display()));
+        }
+        let manifest = json!({
+            "version": self.version,
+            "files": self.files,
+            "checksums": self.checksums,
+            "signature": self.signature,
+        });
+        fs::write(&path, serde_json::to_vec_pretty(&manifest)?).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// The version waiting in `dir` and the files it still has to swap in, each with
+/// the release asset it came from.
+pub fn staged(dir: &Path) -> Option<(String, BTreeMap<String, String>)> {
+    Manifest::read(dir).map(|manifest| (manifest.version, manifest.files))
+}
+
+/// True when `dir` holds a staged copy of `current_exe`.
+pub fn is_staged_for(dir: &Path, current_exe: &Path) -> bool {
+    let name = current_exe.file_name().and_then(|name| name.to_str());
+    staged(dir).is_some_and(|(_, files)| name.is_some_and(|name| files.contains_key(name)))
+}
+
+/// Replaces `current_exe` with its staged copy, if there is one, once the copy
+/// still matches the signed `SHA256SUMS` and its version is newer than
+/// `current_version`; otherwise the copy is discarded. The running file is renamed
+/// out of the way first, which Windows allows while it executes. Returns the
+/// version swapped in; the caller should relaunch itself.
+pub fn apply_staged(dir: &Path, current_exe: &Path, current_version: &str, public_key_hex: &str) -> Result<Option<String>> {
+    let Some(mut manifest) = Manifest::read(dir) else {
+        return Ok(None);
+    };
+    let name = current_exe
+        .file_name()
+        .and_then(|name| name.to_str())
+        .context("executable has no file name")?
+        .to_owned();
+    let Some(asset) = manifest.files.remove(&name) else {
+        return Ok(None);
+    };
+    let staged_path = dir.join(&name);
+    let version = manifest.version.clone();
+    let checked = verify_signature(&version, &manifest.checksums, &manifest.signature, public_key_hex)
+        .and_then(|()| {
+            if is_newer(&version, current_version) {
+                Ok(())
+            } else {
+                Err(anyhow!("{version} is not newer than the running {current_version}"))
+            }
+        })
+        .and_then(|()| {
+            let bytes = fs::read(&staged_path).with_context(|| format!("failed to read {}", staged_path.display()))?;
+            verify_checksum(&manifest.checksums, &asset, &bytes)?;
+            Ok(bytes)
+        });
+    let bytes = match checked {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            let _ = fs::remove_file(&staged_path);
+            manifest.write(dir)?;
+            return Err(err.context(format!("discarded staged {name}")));
+        }
+    };
+
+    let replaced = replaced_path(current_exe);
+    let _ = fs::remove_file(&replaced);
+    fs::rename(current_exe, &replaced)
+        .with_context(|| format!("failed to move {} aside", current_exe.display()))?;
+    if let Err(err) = fs::write(current_exe, &bytes) {
+        let _ = fs::rename(&replaced, current_exe);
+        return Err(err).with_context(|| format!("failed to write {}", current_exe.display()));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(current_exe, fs::Permissions::from_mode(0o755));
+    }
+    let _ = fs::remove_file(&staged_path);
+    manifest.write(dir)?;
+    Ok(Some(version))
+}
+
+/// Deletes the copy [`apply_staged`] moved aside, once the new binary is running.
+pub fn remove_replaced(current_exe: &Path) {
+    let _ = fs::remove_file(replaced_path(current_exe));
+}
+
+fn replaced_path(current_exe: &Path) -> PathBuf {
+    let mut path = current_exe.as_os_str().to_owned();
+    path.push(REPLACED_SUFFIX);
+    PathBuf::from(path)
+}
+
+/// Starts the (new) executable with this process's arguments.
+pub fn relaunch(current_exe: &Path) -> Result<()> {
+    Command::new(current_exe)
+        .args(env::args_os().skip(1))
+        .spawn()
+        .with_context(|| format!("failed to relaunch {}", current_exe.display()))?;
+    Ok(())
+}
+
+/// Orders dotted versions numerically; a leading `v` is ignored.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['.', '-'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    parts(candidate) > parts(current)
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    let value = value.trim();
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        bail!("invalid hex");
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).context("invalid hex"))
+        .collect()
+}
@@ -0,0 +1,74 @@
+//! Helpers shared by the integration tests: in-memory databases created by the
+//! backend's migrations and the viewer's schema, and rows to fill them with.
+
+#![allow(dead_code)]
+
+use limetrace_backend::db::Database;
+use limetrace_core::apps::{LOCKED_APP, LOCKED_PROCESS_PATH};
+use limetrace_core::schema::ensure_tracking_schema;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// The backend's migrated schema with the viewer's tables on top, as a database
+/// looks once both have opened it.
+pub fn memory_db() -> Connection {
+    let conn = memory_store().into_connection();
+    ensure_tracking_schema(&conn).expect("create viewer schema");
+    conn
+}
+
+/// The backend's own store, so recorder tests run its SQL and migrations.
+pub fn memory_store() -> Database {
+    Database::open_in_memory().expect("open in-memory backend database")
+}
+
+pub fn live_segment(conn: &Connection) -> Option<(i64, bool)> {
+    conn.query_row("SELECT start_ts, is_idle FROM live_segment WHERE id = 1", [], |row| {
+        Ok((row.get(0)?, row.get::<_, i64>(1)? != 0))
+    })
+    .optional()
+    .unwrap()
+}
+
+pub fn insert_segment(conn: &Connection, start_ts: i64, end_ts: i64, app: Option<&str>, title: Option<&str>) -> i64 {
+    let app_id = app.map(|exe| {
+        conn.execute(
+            "INSERT OR IGNORE INTO apps (exe_name, process_path) VALUES (?1, ?2)",
+            params![exe, format!(r"C:\Apps\{exe}")],
+        )
+        .unwrap();
+        conn.query_row("SELECT id FROM apps WHERE exe_name = ?1", params![exe], |row| {
+            row.get::<_, i64>(0)
+        })
+        .unwrap()
+    });
+    let title_id = title.map(|title| {
+        conn.execute("INSERT OR IGNORE INTO titles (title) VALUES (?1)", params![title])
+            .unwrap();
+        conn.query_row("SELECT id FROM titles WHERE title = ?1", params![title], |row| {
+            row.get::<_, i64>(0)
+        })
+        .unwrap()
+    });
+    conn.execute(
+        "INSERT INTO segments (start_ts, end_ts, app_id, title_id, is_idle) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![start_ts, end_ts, app_id, title_id, i64::from(app.is_none())],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
+
+/// A span recorded while the session was locked, under the backend's LOCKED pseudo-app.
+pub fn insert_locked_segment(conn: &Connection, start_ts: i64, end_ts: i64) -> i64 {
+    conn.execute(
+        "INSERT OR IGNORE INTO apps (exe_name, process_path) VALUES (?1, ?2)",
+        params![LOCKED_APP, LOCKED_PROCESS_PATH],
+    )
+    .unwrap();
+    conn.execute(
+        "INSERT INTO segments (start_ts, end_ts, app_id, is_idle)
+         SELECT ?1, ?2, id, 0 FROM apps WHERE process_path = ?3",
+        params![start_ts, end_ts, LOCKED_PROCESS_PATH],
+    )
+    .unwrap();
+    conn.last_insert_rowid()
+}
@@ -0,0 +1,56 @@
+use std::fs;
+
+use limetrace_core::connection::{open_read_only, open_read_write, ConnectionPool};
+use rusqlite::Connection;
+
+#[test]
+fn read_only_connections_refuse_writes_and_writers_use_wal() {
+    let dir = std::env::temp_dir().join(format!("limetrace-connection-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("limetrace.sqlite");
+
+    let writer = open_read_write(&path).unwrap();
+    let mode: String = writer.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+    assert_eq!(mode, "wal");
+    writer
+        .execute("INSERT INTO day_notes (date, note, updated_ts) VALUES ('2024-03-04', 'x', 0)", [])
+        .unwrap();
+
+    let reader = open_read_only(&path).unwrap();
+    let notes: i64 = reader.query_row("SELECT COUNT(*) FROM day_notes", [], |row| row.get(0)).unwrap();
+    assert_eq!(notes, 1);
+    assert!(reader.execute("DELETE FROM day_notes", []).is_err());
+
+    drop((writer, reader));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn pooled_readers_are_reused_and_leave_the_schema_alone() {
+    let dir = std::env::temp_dir().join(format!("limetrace-pool-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("limetrace.sqlite");
+    Connection::open(&path)
+        .unwrap()
+        .execute_batch("CREATE TABLE segments (id INTEGER PRIMARY KEY, start_ts INTEGER, end_ts INTEGER)")
+        .unwrap();
+
+    let pool = ConnectionPool::new(&path);
+    let first = pool.read().unwrap();
+    let columns: i64 = first
+        .query_row("SELECT COUNT(*) FROM pragma_table_info('segments')", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(columns, 3);
+    // A per-connection setting survives only if the same connection comes back.
+    first.pragma_update(None, "cache_size", 1234).unwrap();
+    drop(first);
+    let second = pool.read().unwrap();
+    let cache_size: i64 = second.query_row("PRAGMA cache_size", [], |row| row.get(0)).unwrap();
+    assert_eq!(cache_size, 1234);
+    assert!(second.execute("DELETE FROM segments", []).is_err());
+
+    drop((pool, second));
+    let _ = fs::remove_dir_all(&dir);
+}
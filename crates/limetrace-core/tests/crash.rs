@@ -0,0 +1,33 @@
+use std::fs;
+use std::thread;
+
+use limetrace_core::crash;
+
+#[test]
+fn panic_writes_a_report_with_the_last_operations() {
+    let logs_dir = std::env::temp_dir().join(format!("limetrace-crash-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&logs_dir);
+    crash::install(logs_dir.clone(), "limetrace-test", "9.9.9");
+    crash::note("opened the import window");
+    crash::note("imported 3 rows");
+
+    let result = thread::Builder::new()
+        .name("worker".to_owned())
+        .spawn(|| panic!("segment list out of order"))
+        .unwrap()
+        .join();
+    assert!(result.is_err());
+
+    let report = crash::latest_report_since(&logs_dir, 0).expect("a crash report");
+    assert_eq!(report.binary, "limetrace-test");
+    let text = fs::read_to_string(&report.path).unwrap();
+    assert!(text.starts_with("limetrace-test 9.9.9 crashed at "), "{text}");
+    assert!(text.contains("thread: worker"));
+    assert!(text.contains("segment list out of order"));
+    let operations = text.find("opened the import window").unwrap();
+    assert!(operations < text.find("imported 3 rows").unwrap());
+    assert!(text.contains("backtrace:"));
+
+    assert!(crash::latest_report_since(&logs_dir, report.written_ts).is_none());
+    let _ = fs::remove_dir_all(&logs_dir);
+}
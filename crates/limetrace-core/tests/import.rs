@@ -0,0 +1,177 @@
+mod common;
+
+use chrono::Utc;
+use chrono_tz::Europe::Berlin;
+use limetrace_core::import::{import_csv, import_csv_with_columns, preview_csv, ImportField};
+use limetrace_core::segments::{query_segments_for_range, Segment};
+
+use common::memory_db;
+
+fn all_segments(conn: &rusqlite::Connection) -> Vec<Segment> {
+    query_segments_for_range(conn, i64::MIN / 2, i64::MAX / 2).unwrap()
+}
+
+#[test]
+fn imports_unix_timestamp_rows() {
+    let mut conn = memory_db();
+    let csv = "\
+start_ts,end_ts,is_idle,app_name,process_path,title
+1700000000,1700000600,0,code.exe,C:\\Apps\\code.exe,main.rs
+1700000600,1700000900,1,,,
+1700000900,1700001000,false,firefox.exe,C:\\Apps\\firefox.exe,
+";
+    let stats = import_csv(&mut conn, csv.as_bytes(), &Utc).unwrap();
+    assert_eq!((stats.total_rows, stats.imported_rows, stats.skipped_rows), (3, 2, 1));
+
+    let segments = all_segments(&conn);
+    assert_eq!(segments.len(), 2);
+    assert_eq!(
+        (segments[0].start_ts, segments[0].end_ts, segments[0].is_idle),
+        (1_700_000_000, 1_700_000_600, false)
+    );
+    assert_eq!(segments[0].app_name, "code.exe");
+    assert_eq!(segments[0].process_path.as_deref(), Some(r"C:\Apps\code.exe"));
+    assert_eq!(segments[0].title.as_deref(), Some("main.rs"));
+    assert_eq!(segments[1].app_name, "firefox.exe");
+    assert_eq!(segments[1].title, None);
+}
+
+#[test]
+fn imports_viewer_export_and_infers_idle() {
+    let mut conn = memory_db();
+    let csv = "\
+\"Title\",\"Start\",\"End\",\"Duration\",\"Process\"
+Inbox,2024-03-04 09:00:00,2024-03-04 09:30:00,00:30:00,Outlook
+IDLE,2024-03-04 09:30:00,2024-03-04 09:45:00,00:15:00,idle
+Standup,2024-03-04 09:45:00,,15:00,Teams
+";
+    let stats = import_csv(&mut conn, csv.as_bytes(), &Utc).unwrap();
+    assert_eq!(stats.imported_rows, 3);
+
+    let segments = all_segments(&conn);
+    let base = 1_709_542_800; // 2024-03-04 09:00 UTC
+    assert_eq!((segments[0].start_ts, segments[0].end_ts), (base, base + 1800));
+    assert_eq!(segments[0].process_path.as_deref(), Some("<import:Outlook>"));
+    assert!(segments[1].is_idle);
+    assert_eq!(segments[1].app_name, "IDLE");
+    // A missing end falls back to start + duration.
+    assert_eq!((segments[2].start_ts, segments[2].end_ts), (base + 2700, base + 3600));
+}
+
+#[test]
+fn midnight_crossing_rows_keep_their_full_span() {
+    let mut conn = memory_db();
+    let csv = "\
+Title,Start,End,Duration,Process
+Late night,2024-03-04 23:50:00,2024-03-05 00:20:00,00:30:00,code
+";
+    import_csv(&mut conn, csv.as_bytes(), &Berlin).unwrap();
+
+    let segments = all_segments(&conn);
+    // 23:50 CET is 22:50 UTC.
+    assert_eq!(
+        (segments[0].start_ts, segments[0].end_ts),
+        (1_709_592_600, 1_709_594_400)
+    );
+}
+
+#[test]
+fn local_times_are_read_in_the_given_zone_across_dst() {
+    let mut conn = memory_db();
+    let csv = "\
+Title,Start,End,Duration,Process
+Before,2024-03-31 01:30:00,2024-03-31 03:30:00,01:00:00,code
+Gap,2024-03-31 02:30:00,2024-03-31 03:30:00,01:00:00,code
+Repeated,2024-10-27 02:30:00,2024-10-27 02:45:00,00:15:00,code
+Across fall back,2024-10-27 02:50:00,2024-10-27 02:10:00,00:20:00,code
+";
+    let stats = import_csv(&mut conn, csv.as_bytes(), &Berlin).unwrap();
+    // 02:30 does not exist on the spring-forward day.
+    assert_eq!((stats.imported_rows, stats.skipped_rows), (3, 1));
+
+    let segments = all_segments(&conn);
+    // 01:30 CET to 03:30 CEST is one real hour.
+    assert_eq!(segments[0].end_ts - segments[0].start_ts, 3600);
+    // Times in the repeated hour resolve to its first pass, in summer time.
+    assert_eq!(
+        (segments[1].start_ts, segments[1].end_ts),
+        (1_729_989_000, 1_729_989_900)
+    );
+    // An end that reads earlier than the start is rescued by the duration column.
+    assert_eq!(segments[2].end_ts - segments[2].start_ts, 1200);
+}
+
+#[test]
+fn explicit_offsets_override_the_import_zone() {
+    let mut conn = memory_db();
+    let csv = "\
+Title,Start,End,Process
+Zulu,2024-03-04T09:00:00Z,2024-03-04T09:30:00Z,code
+Offset,2024-03-04 11:00:00+01:00,2024-03-04 11:30:00+0100,code
+Plain,2024-03-04 10:30:00,2024-03-04 11:00:00,code
+";
+    import_csv(&mut conn, csv.as_bytes(), &Berlin).unwrap();
+
+    let segments = all_segments(&conn);
+    let base = 1_709_542_800; // 2024-03-04 09:00 UTC
+    assert_eq!((segments[0].start_ts, segments[0].end_ts), (base, base + 1800));
+    // Without an offset the row is read in Berlin time (UTC+1).
+    assert_eq!((segments[1].start_ts, segments[1].end_ts), (base + 1800, base + 3600));
+    assert_eq!((segments[2].start_ts, segments[2].end_ts), (base + 3600, base + 5400));
+}
+
+#[test]
+fn rejects_files_without_required_columns_or_rows() {
+    let mut conn = memory_db();
+    let err = import_csv(&mut conn, "Title,Process\nx,code\n".as_bytes(), &Utc).unwrap_err();
+    assert!(err.to_string().contains("time columns"), "{err}");
+    let err = import_csv(&mut conn, "start_ts,end_ts,title\n1,2,x\n".as_bytes(), &Utc).unwrap_err();
+    assert!(err.to_string().contains("app column"), "{err}");
+    let err = import_csv(&mut conn, "start_ts,end_ts,app_name\n".as_bytes(), &Utc).unwrap_err();
+    assert!(err.to_string().contains("no data rows"), "{err}");
+    let err = import_csv(&mut conn, "start_ts,end_ts,app_name\n5,5,code\n".as_bytes(), &Utc).unwrap_err();
+    assert!(err.to_string().contains("no valid rows"), "{err}");
+    assert!(all_segments(&conn).is_empty());
+}
+
+#[test]
+fn unknown_headers_import_through_a_manual_mapping() {
+    let mut conn = memory_db();
+    let csv = "Von,Bis,Programm,Fenster\n1700000000,1700000600,code,main.rs\n1700000600,1700000900,firefox,docs\n";
+    assert!(import_csv(&mut conn, csv.as_bytes(), &Utc).is_err());
+
+    let preview = preview_csv(csv.as_bytes(), 1).unwrap();
+    assert_eq!(preview.headers, ["Von", "Bis", "Programm", "Fenster"]);
+    assert_eq!(preview.rows, [["1700000000", "1700000600", "code", "main.rs"]]);
+    let mut columns = preview.columns;
+    assert!(columns.validate().is_err());
+    for (field, column) in [
+        (ImportField::StartTs, 0),
+        (ImportField::EndTs, 1),
+        (ImportField::AppName, 2),
+        (ImportField::Title, 3),
+    ] {
+        columns.set(field, Some(column));
+    }
+    let stats = import_csv_with_columns(&mut conn, csv.as_bytes(), &Utc, &columns).unwrap();
+    assert_eq!(stats.imported_rows, 2);
+
+    let segments = all_segments(&conn);
+    assert_eq!((segments[0].start_ts, segments[0].end_ts), (1_700_000_000, 1_700_000_600));
+    assert_eq!(segments[1].app_name, "firefox");
+    assert_eq!(segments[1].title.as_deref(), Some("docs"));
+}
+
+#[test]
+fn reimporting_reuses_app_and_title_rows() {
+    let mut conn = memory_db();
+    let csv = "start_ts,end_ts,app_name,title\n100,200,code,a\n200,300,code,a\n";
+    import_csv(&mut conn, csv.as_bytes(), &Utc).unwrap();
+    import_csv(&mut conn, csv.as_bytes(), &Utc).unwrap();
+
+    let count = |table: &str| -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| row.get(0))
+            .unwrap()
+    };
+    assert_eq!((count("apps"), count("titles"), count("segments")), (1, 1, 4));
+}
@@ -0,0 +1,64 @@
+use std::fs;
+
+use limetrace_backend::db::Database;
+use limetrace_core::demo;
+use limetrace_core::recorder::SegmentStore;
+use rusqlite::Connection;
+
+#[test]
+fn backend_migrates_a_database_the_viewer_created() {
+    let dir = std::env::temp_dir().join(format!("limetrace-migrations-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("limetrace.sqlite");
+
+    // What `limetrace generate-demo` leaves behind: the viewer's schema, including
+    // the segment columns it adds itself, and no backend migrations yet.
+    let mut viewer = Connection::open(&path).unwrap();
+    let stats = demo::generate(&mut viewer, 2, 1).unwrap();
+    drop(viewer);
+
+    let db = Database::open(&path).unwrap();
+    let conn = db.connection();
+    let segments: i64 = conn.query_row("SELECT COUNT(*) FROM segments", [], |row| row.get(0)).unwrap();
+    assert_eq!(segments, stats.segments);
+    let covering: String = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'idx_segments_range_covering'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert!(covering.contains("deleted_ts"));
+    drop(db);
+
+    // Reopening finds nothing left to migrate.
+    Database::open(&path).unwrap();
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn apps_differing_only_in_case_share_one_row() {
+    let mut db = Database::open_in_memory().unwrap();
+    // As an import writes it, without the backend's key.
+    db.connection()
+        .execute(
+            "INSERT INTO apps (exe_name, process_path) VALUES ('Code.exe', 'C:\\Apps\\Code.exe')",
+            [],
+        )
+        .unwrap();
+    let imported: i64 = db
+        .connection()
+        .query_row("SELECT id FROM apps", [], |row| row.get(0))
+        .unwrap();
+
+    assert_eq!(db.upsert_app("code.exe", r"c:\apps\code.exe").unwrap(), imported);
+    assert_eq!(db.upsert_app("Code.exe", r"C:\Apps\Code.exe").unwrap(), imported);
+    let firefox = db.upsert_app("firefox.exe", r"C:\Apps\firefox.exe").unwrap();
+    assert_ne!(firefox, imported);
+    let apps: i64 = db
+        .connection()
+        .query_row("SELECT COUNT(*) FROM apps", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(apps, 2);
+}
@@ -0,0 +1,214 @@
+//! Property tests for the timeline and summary math: whatever the segments look
+//! like, clipping and merging must not invent or lose time.
+
+use std::collections::{HashMap, HashSet};
+
+use limetrace_core::apps::normalize_app_key;
+use limetrace_core::segments::Segment;
+use limetrace_core::summary::{build_summary_rows, should_hide_summary_app, SummaryAccumulator, SUMMARY_TOP_TITLES};
+use limetrace_core::timeline::{
+    build_timeline_segments, clamp_view_span, sanitize_view_range, MERGE_GAP_TOLERANCE_SECS, MIN_TIMELINE_VIEW_SECS,
+};
+use proptest::prelude::*;
+
+const APPS: [&str; 3] = ["Code.exe", "chrome.exe", "notepad.exe"];
+const TITLES: [&str; 3] = ["main.rs", "Inbox", "notes.txt"];
+
+/// Sorted, non-overlapping segments as the recorder writes them. Gaps of zero
+/// and one second are common so merging gets exercised.
+fn segments() -> impl Strategy<Value = Vec<Segment>> {
+    let piece = (
+        prop_oneof![3 => Just(0i64), 2 => Just(1i64), 1 => 2i64..120],
+        0i64..900,
+        0usize..=APPS.len(),
+        proptest::option::of(0usize..TITLES.len()),
+    );
+    proptest::collection::vec(piece, 0..40).prop_map(|pieces| {
+        let mut cursor = 0;
+        pieces
+            .into_iter()
+            .map(|(gap, len, app, title)| {
+                let start_ts = cursor + gap;
+                cursor = start_ts + len;
+                // Index 0 stands for idle time.
+                let (app_name, process_path) = match app.checked_sub(1) {
+                    Some(index) => (APPS[index].to_owned(), Some(format!(r"C:\Apps\{}", APPS[index]))),
+                    None => ("Idle".to_owned(), None),
+                };
+                Segment {
+                    start_ts,
+                    end_ts: cursor,
+                    start_ms: start_ts * 1000,
+                    end_ms: cursor * 1000,
+                    is_idle: app == 0,
+                    app_name,
+                    process_path,
+                    title: title.filter(|_| app != 0).map(|index| TITLES[index].to_owned()),
+                    url: None,
+                    is_call: false,
+                    monitor: None,
+                }
+            })
+            .collect()
+    })
+}
+
+/// A range that may start before, inside or after the generated segments.
+fn range() -> impl Strategy<Value = (i64, i64)> {
+    (-600i64..20_000, 1i64..30_000).prop_map(|(start, len)| (start, start + len))
+}
+
+fn clipped(seg: &Segment, range_start: i64, range_end: i64) -> Option<(i64, i64)> {
+    let start = seg.start_ts.max(range_start);
+    let end = seg.end_ts.min(range_end);
+    (end > start).then_some((start, end))
+}
+
+proptest! {
+    #[test]
+    fn timeline_bars_are_ordered_and_inside_the_range(
+        segs in segments(),
+        (day_start, day_end) in range(),
+        collect_title_runs in any::<bool>(),
+    ) {
+        let bars = build_timeline_segments(day_start, day_end, &segs, None, collect_title_runs);
+        for bar in &bars {
+            prop_assert!(day_start <= bar.start_ts && bar.start_ts < bar.end_ts && bar.end_ts <= day_end);
+        }
+        for pair in bars.windows(2) {
+            prop_assert!(pair[0].end_ts <= pair[1].start_ts, "overlapping bars {:?}", pair);
+        }
+    }
+
+    #[test]
+    fn timeline_merging_only_bridges_gaps_within_tolerance(
+        segs in segments(),
+        (day_start, day_end) in range(),
+        selected in proptest::option::of(proptest::sample::subsequence(APPS.to_vec(), 0..=APPS.len())),
+    ) {
+        let selected: Option<HashSet<String>> =
+            selected.map(|apps| apps.into_iter().map(normalize_app_key).collect());
+        let bars = build_timeline_segments(day_start, day_end, &segs, selected.as_ref(), false);
+
+        let kept: Vec<(&Segment, (i64, i64))> = segs
+            .iter()
+            .filter(|seg| selected.as_ref().is_none_or(|keys| keys.contains(&normalize_app_key(&seg.app_name))))
+            .filter_map(|seg| clipped(seg, day_start, day_end).map(|span| (seg, span)))
+            .collect();
+        for (seg, (start, end)) in &kept {
+            prop_assert!(
+                bars.iter().any(|bar| bar.app_name == seg.app_name
+                    && bar.is_idle == seg.is_idle
+                    && bar.start_ts <= *start
+                    && *end <= bar.end_ts),
+                "segment {start}-{end} of {} is not covered", seg.app_name
+            );
+        }
+
+        // Merged bars may only grow by the gaps they bridged, each within tolerance.
+        let kept_secs: i64 = kept.iter().map(|(_, (start, end))| end - start).sum();
+        let bar_secs: i64 = bars.iter().map(|bar| bar.end_ts - bar.start_ts).sum();
+        let merges = (kept.len() - bars.len()) as i64;
+        prop_assert!(bar_secs >= kept_secs);
+        prop_assert!(bar_secs - kept_secs <= merges * MERGE_GAP_TOLERANCE_SECS);
+
+        for pair in bars.windows(2) {
+            if pair[0].app_name == pair[1].app_name && pair[0].is_idle == pair[1].is_idle {
+                prop_assert!(
+                    pair[1].start_ts > pair[0].end_ts + MERGE_GAP_TOLERANCE_SECS,
+                    "bars within tolerance were left apart: {:?}", pair
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn title_runs_tile_their_bar(segs in segments(), (day_start, day_end) in range()) {
+        for bar in build_timeline_segments(day_start, day_end, &segs, None, true) {
+            let (first, last) = (bar.title_runs.first().unwrap(), bar.title_runs.last().unwrap());
+            prop_assert_eq!(first.start_ts, bar.start_ts);
+            prop_assert_eq!(last.end_ts, bar.end_ts);
+            prop_assert_eq!(bar.multi_title, bar.title_runs.len() > 1);
+            for run in &bar.title_runs {
+                prop_assert!(run.start_ts < run.end_ts);
+            }
+            for pair in bar.title_runs.windows(2) {
+                prop_assert_ne!(&pair[0].title, &pair[1].title);
+                let gap = pair[1].start_ts - pair[0].end_ts;
+                prop_assert!((0..=MERGE_GAP_TOLERANCE_SECS).contains(&gap), "runs {:?} gap {}", pair, gap);
+            }
+        }
+    }
+
+    #[test]
+    fn summary_conserves_clipped_durations(
+        segs in segments(),
+        (range_start, range_end) in range(),
+        min_segment_secs in 0i64..120,
+    ) {
+        let expected: i64 = segs
+            .iter()
+            .filter(|seg| !should_hide_summary_app(&seg.app_name, seg.is_idle, seg.process_path.as_deref()))
+            .filter_map(|seg| clipped(seg, range_start, range_end))
+            .map(|(start, end)| end - start)
+            .sum();
+
+        let mut summary = SummaryAccumulator::new(range_start, range_end, min_segment_secs, |_| None);
+        for seg in &segs {
+            summary.push(seg);
+        }
+        let fleeting = summary.fleeting_secs();
+        let rows = summary.finish();
+        let row_secs: i64 = rows.iter().map(|row| row.duration_secs).sum();
+        prop_assert_eq!(row_secs + fleeting, expected);
+        if min_segment_secs == 0 {
+            prop_assert_eq!(fleeting, 0);
+        }
+
+        let mut segment_counts: HashMap<&str, usize> = HashMap::new();
+        for seg in &segs {
+            if clipped(seg, range_start, range_end).is_some() {
+                *segment_counts.entry(seg.app_name.as_str()).or_default() += 1;
+            }
+        }
+        for row in &rows {
+            prop_assert!(row.duration_secs > 0);
+            prop_assert!(range_start < row.last_used_ts && row.last_used_ts <= range_end);
+            prop_assert!(row.session_count >= 1);
+            prop_assert!(row.session_count <= segment_counts[row.app_name.as_str()]);
+            prop_assert!(row.top_titles.len() <= SUMMARY_TOP_TITLES);
+            prop_assert!(row.top_titles.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+            prop_assert!(row.top_titles.iter().map(|(_, secs)| secs).sum::<i64>() <= row.duration_secs);
+        }
+    }
+
+    #[test]
+    fn summary_of_split_ranges_adds_up(segs in segments(), (range_start, range_end) in range(), split in 0.0f64..1.0) {
+        let mid = range_start + ((range_end - range_start) as f64 * split) as i64;
+        let total = |start, end| -> i64 {
+            build_summary_rows(start, end, &segs, 0, |_| None).iter().map(|row| row.duration_secs).sum()
+        };
+        prop_assert_eq!(total(range_start, mid) + total(mid, range_end), total(range_start, range_end));
+    }
+
+    #[test]
+    fn clamped_view_stays_inside_the_day(
+        start in -200_000i64..200_000,
+        span in -1_000i64..200_000,
+        (day_start, day_end) in range(),
+    ) {
+        let day_span = day_end - day_start;
+        let (view_start, view_end) = clamp_view_span(start, span, day_start, day_end);
+        prop_assert!(day_start <= view_start && view_end <= day_end);
+        prop_assert_eq!(view_end - view_start, span.clamp(MIN_TIMELINE_VIEW_SECS.min(day_span), day_span));
+
+        let fits = start >= day_start && span >= MIN_TIMELINE_VIEW_SECS && start + span <= day_end;
+        if fits {
+            prop_assert_eq!((view_start, view_end), (start, start + span));
+        }
+
+        let (view_start, view_end) = sanitize_view_range(Some((start, start + span)), day_start, day_end);
+        prop_assert!(day_start <= view_start && view_start < view_end && view_end <= day_end);
+        prop_assert_eq!(sanitize_view_range(None, day_start, day_end), (day_start, day_end));
+    }
+}
@@ -0,0 +1,246 @@
+mod common;
+
+use chrono::NaiveDate;
+use chrono_tz::{America::Santiago, Europe::Berlin};
+use limetrace_core::segments::{count_segments_in_range, query_segments_for_range, reclassify_as_idle};
+use limetrace_core::apps::AWAY_APP;
+use limetrace_core::summary::{build_summary_rows, SummaryAccumulator};
+use limetrace_core::timeline::coalesce_away;
+use limetrace_core::time::{date_range_bounds_in, midnight_ts_in};
+
+use common::{insert_locked_segment, insert_segment, memory_db};
+
+const DAY: i64 = 86_400;
+// 2024-03-04 00:00 UTC, a Monday.
+const MONDAY: i64 = 1_709_510_400;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn no_description(_: &str) -> Option<String> {
+    None
+}
+
+#[test]
+fn range_query_returns_overlapping_segments_in_start_order() {
+    let conn = memory_db();
+    insert_segment(&conn, MONDAY + 600, MONDAY + 900, Some("code.exe"), Some("main.rs"));
+    insert_segment(&conn, MONDAY - 300, MONDAY + 60, Some("firefox.exe"), None);
+    insert_segment(&conn, MONDAY + 60, MONDAY + 600, None, None);
+    insert_segment(&conn, MONDAY - 900, MONDAY - 300, Some("firefox.exe"), None);
+    insert_segment(&conn, MONDAY + DAY, MONDAY + DAY + 60, Some("code.exe"), None);
+
+    let segments = query_segments_for_range(&conn, MONDAY, MONDAY + DAY).unwrap();
+    let spans: Vec<(i64, i64, bool)> = segments
+        .iter()
+        .map(|seg| (seg.start_ts, seg.end_ts, seg.is_idle))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            (MONDAY - 300, MONDAY + 60, false),
+            (MONDAY + 60, MONDAY + 600, true),
+            (MONDAY + 600, MONDAY + 900, false)
+        ]
+    );
+    assert_eq!(segments[0].app_name, "firefox.exe");
+    assert_eq!(segments[1].app_name, "UNKNOWN");
+    assert_eq!(segments[2].title.as_deref(), Some("main.rs"));
+    assert_eq!(count_segments_in_range(&conn, MONDAY, MONDAY + DAY).unwrap(), 3);
+}
+
+#[test]
+fn range_query_skips_deleted_segments() {
+    let conn = memory_db();
+    insert_segment(&conn, MONDAY, MONDAY + 60, Some("code.exe"), None);
+    let deleted = insert_segment(&conn, MONDAY + 60, MONDAY + 120, Some("slack.exe"), None);
+    conn.execute(
+        "UPDATE segments SET deleted_ts = ?1 WHERE id = ?2",
+        [MONDAY + 500, deleted],
+    )
+    .unwrap();
+
+    let segments = query_segments_for_range(&conn, MONDAY, MONDAY + DAY).unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].app_name, "code.exe");
+    assert_eq!(count_segments_in_range(&conn, MONDAY, MONDAY + DAY).unwrap(), 1);
+}
+
+#[test]
+fn reclassified_time_reads_back_as_idle_at_millisecond_precision() {
+    let mut conn = memory_db();
+    let with_ms = |id: i64, start_ms: i64, end_ms: i64| {
+        conn.execute(
+            "UPDATE segments SET start_ms = ?2, end_ms = ?3 WHERE id = ?1",
+            [id, start_ms, end_ms],
+        )
+        .unwrap();
+    };
+    let straddling = insert_segment(&conn, MONDAY, MONDAY + 600, Some("code.exe"), None);
+    with_ms(straddling, MONDAY * 1000, (MONDAY + 600) * 1000 + 700);
+    let inside = insert_segment(&conn, MONDAY + 600, MONDAY + 900, Some("slack.exe"), None);
+    with_ms(inside, (MONDAY + 600) * 1000 + 700, (MONDAY + 900) * 1000);
+    let idle = insert_segment(&conn, MONDAY + 900, MONDAY + 1200, None, None);
+    with_ms(idle, (MONDAY + 900) * 1000, (MONDAY + 1200) * 1000);
+
+    reclassify_as_idle(&mut conn, MONDAY + 300, MONDAY + 900, MONDAY + 1500).unwrap();
+
+    let segments = query_segments_for_range(&conn, MONDAY, MONDAY + DAY).unwrap();
+    let spans: Vec<(i64, i64, bool)> = segments
+        .iter()
+        .map(|seg| (seg.start_ms, seg.end_ms, seg.is_idle))
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            (MONDAY * 1000, (MONDAY + 300) * 1000, false),
+            ((MONDAY + 300) * 1000, (MONDAY + 1200) * 1000, true)
+        ]
+    );
+    assert_eq!((segments[0].end_ts, segments[1].start_ts), (MONDAY + 300, MONDAY + 300));
+    // Only the time before the cutoff is still counted as active.
+    let rows = build_summary_rows(MONDAY, MONDAY + DAY, &segments, 0, no_description);
+    assert_eq!(rows.iter().map(|row| row.duration_secs).collect::<Vec<_>>(), [300]);
+}
+
+#[test]
+fn long_segment_starting_days_earlier_is_found_by_the_bounded_scan() {
+    let conn = memory_db();
+    insert_segment(&conn, MONDAY - 3 * DAY, MONDAY + 3600, None, None);
+    for offset in (0..50).map(|index| index * 60) {
+        insert_segment(
+            &conn,
+            MONDAY - DAY + offset,
+            MONDAY - DAY + offset + 60,
+            Some("code.exe"),
+            None,
+        );
+    }
+
+    let segments = query_segments_for_range(&conn, MONDAY, MONDAY + DAY).unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(
+        (segments[0].start_ts, segments[0].end_ts),
+        (MONDAY - 3 * DAY, MONDAY + 3600)
+    );
+}
+
+#[test]
+fn midnight_crossing_segment_is_split_between_days() {
+    let conn = memory_db();
+    insert_segment(&conn, MONDAY + DAY - 1800, MONDAY + DAY + 600, Some("code.exe"), None);
+
+    let monday = query_segments_for_range(&conn, MONDAY, MONDAY + DAY).unwrap();
+    let tuesday = query_segments_for_range(&conn, MONDAY + DAY, MONDAY + 2 * DAY).unwrap();
+    assert_eq!(monday.len(), 1);
+    assert_eq!(tuesday.len(), 1);
+
+    let monday_rows = build_summary_rows(MONDAY, MONDAY + DAY, &monday, 0, no_description);
+    let tuesday_rows = build_summary_rows(MONDAY + DAY, MONDAY + 2 * DAY, &tuesday, 0, no_description);
+    assert_eq!(monday_rows[0].duration_secs, 1800);
+    assert_eq!(monday_rows[0].last_used_ts, MONDAY + DAY);
+    assert_eq!(tuesday_rows[0].duration_secs, 600);
+    let both_days = build_summary_rows(MONDAY, MONDAY + 2 * DAY, &monday, 0, no_description);
+    assert_eq!(both_days[0].duration_secs, 2400);
+}
+
+#[test]
+fn summary_groups_by_display_name_and_counts_sessions() {
+    let conn = memory_db();
+    insert_segment(&conn, MONDAY, MONDAY + 600, Some("Code.exe"), Some("a.rs"));
+    insert_segment(&conn, MONDAY + 630, MONDAY + 900, Some("code.exe"), Some("b.rs"));
+    insert_segment(&conn, MONDAY + 900, MONDAY + 1200, Some("firefox.exe"), None);
+    insert_segment(&conn, MONDAY + 1200, MONDAY + 1210, Some("slack.exe"), None);
+    insert_segment(&conn, MONDAY + 4000, MONDAY + 4600, Some("code.exe"), None);
+    insert_segment(&conn, MONDAY + 4600, MONDAY + 5000, None, None);
+
+    let segments = query_segments_for_range(&conn, MONDAY, MONDAY + DAY).unwrap();
+    let mut summary = SummaryAccumulator::new(MONDAY, MONDAY + DAY, 30, no_description);
+    for seg in &segments {
+        summary.push(seg);
+    }
+    assert_eq!(summary.fleeting_secs(), 10);
+    let rows = summary.finish();
+
+    let names: Vec<&str> = rows.iter().map(|row| row.display_name.as_str()).collect();
+    // Idle segments are left out of the summary, like system apps.
+    assert_eq!(names, ["Code", "firefox"]);
+    assert_eq!(rows[0].duration_secs, 1470);
+    assert_eq!(rows[0].session_count, 2);
+    assert_eq!(rows[1].duration_secs, 300);
+}
+
+#[test]
+fn idle_and_lock_runs_with_a_lock_become_one_away_block() {
+    let conn = memory_db();
+    insert_segment(&conn, MONDAY, MONDAY + 600, Some("code.exe"), None);
+    insert_segment(&conn, MONDAY + 600, MONDAY + 900, None, None);
+    insert_locked_segment(&conn, MONDAY + 900, MONDAY + 2700);
+    insert_segment(&conn, MONDAY + 2700, MONDAY + 2760, None, None);
+    insert_segment(&conn, MONDAY + 2760, MONDAY + 3000, Some("code.exe"), None);
+    // Idle without a lock stays idle.
+    insert_segment(&conn, MONDAY + 3000, MONDAY + 3300, None, None);
+    insert_segment(&conn, MONDAY + 3300, MONDAY + 3600, Some("code.exe"), None);
+    let segments = query_segments_for_range(&conn, MONDAY, MONDAY + DAY).unwrap();
+
+    let coalesced = coalesce_away(&segments);
+    let spans: Vec<(i64, i64, bool)> = coalesced
+        .iter()
+        .map(|seg| (seg.start_ts - MONDAY, seg.end_ts - MONDAY, seg.is_idle))
+        .collect();
+    assert_eq!(
+        spans,
+        [(0, 600, false), (600, 2760, true), (2760, 3000, false), (3000, 3300, true), (3300, 3600, false)]
+    );
+    assert_eq!(coalesced[1].app_name, AWAY_APP);
+    assert_ne!(coalesced[3].app_name, AWAY_APP);
+
+    let mut summary = SummaryAccumulator::new(MONDAY, MONDAY + DAY, 0, no_description);
+    for seg in &segments {
+        summary.push(seg);
+    }
+    assert_eq!(summary.away_secs(), 2160);
+    let rows = summary.finish();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].duration_secs, 600 + 240 + 300);
+}
+
+#[test]
+fn dst_days_are_23_and_25_hours_long() {
+    let (start, end) = date_range_bounds_in(&Berlin, date(2024, 3, 31), date(2024, 4, 1)).unwrap();
+    assert_eq!(start, 1_711_839_600);
+    assert_eq!(end - start, 23 * 3600);
+    let (start, end) = date_range_bounds_in(&Berlin, date(2024, 10, 27), date(2024, 10, 28)).unwrap();
+    assert_eq!(end - start, 25 * 3600);
+    let (start, end) = date_range_bounds_in(&Berlin, date(2024, 3, 4), date(2024, 3, 5)).unwrap();
+    assert_eq!(end - start, DAY);
+}
+
+#[test]
+fn day_starting_inside_a_dst_gap_starts_at_the_first_valid_minute() {
+    // Santiago skips from 00:00 to 01:00 on 2024-09-08.
+    assert_eq!(midnight_ts_in(&Santiago, date(2024, 9, 8)), Some(1_725_768_000));
+    let (previous_start, previous_end) = date_range_bounds_in(&Santiago, date(2024, 9, 7), date(2024, 9, 8)).unwrap();
+    let (start, end) = date_range_bounds_in(&Santiago, date(2024, 9, 8), date(2024, 9, 9)).unwrap();
+    assert_eq!(previous_end, start);
+    assert_eq!(previous_end - previous_start, DAY);
+    assert_eq!(end - start, 23 * 3600);
+}
+
+#[test]
+fn summary_of_a_dst_day_covers_exactly_the_local_day() {
+    let conn = memory_db();
+    let (start, end) = date_range_bounds_in(&Berlin, date(2024, 10, 27), date(2024, 10, 28)).unwrap();
+    // Half-hour segments from an hour before the day until an hour after it.
+    let mut ts = start - 3600;
+    while ts < end + 3600 {
+        insert_segment(&conn, ts, ts + 1800, Some("code.exe"), None);
+        ts += 1800;
+    }
+
+    let segments = query_segments_for_range(&conn, start, end).unwrap();
+    let rows = build_summary_rows(start, end, &segments, 0, no_description);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].duration_secs, 25 * 3600);
+}
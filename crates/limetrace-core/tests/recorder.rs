@@ -0,0 +1,452 @@
+mod common;
+
+use anyhow::Result;
+use limetrace_backend::db::Database;
+use limetrace_core::activity::{ActiveWindow, ActivityKind, ActivitySample};
+use limetrace_core::apps::{
+    browser_profile_app, browser_profile_in_title, is_browser_app, url_domain, LOCKED_APP, LOCKED_PROCESS_PATH,
+};
+use limetrace_core::recorder::{Recorder, SegmentEvent, SegmentSink};
+use limetrace_core::segments::query_segments_for_range;
+use limetrace_core::summary::{build_summary_rows, SummaryAccumulator};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::{live_segment, memory_store};
+
+const T0: i64 = 1_709_542_800;
+
+fn active(ts: i64, exe: &str, title: &str) -> ActivitySample {
+    ActivitySample {
+        ts_ms: ts * 1000,
+        kind: ActivityKind::Active(ActiveWindow {
+            pid: 100,
+            pid_create_time: Some(1),
+            exe_name: exe.to_owned(),
+            process_path: format!(r"C:\Apps\{exe}"),
+            window_title: title.to_owned(),
+            identity: None,
+            process_args: None,
+            url: None,
+            in_call: false,
+            monitor: None,
+        }),
+    }
+}
+
+fn idle(ts: i64, idle_secs: u32) -> ActivitySample {
+    ActivitySample {
+        ts_ms: ts * 1000,
+        kind: ActivityKind::Idle {
+            idle_ms: idle_secs * 1000,
+        },
+    }
+}
+
+fn recorder(rotate_secs: u64) -> Recorder<Database> {
+    Recorder::new(memory_store(), Duration::from_secs(rotate_secs))
+}
+
+fn stored(recorder: &Recorder<Database>) -> Vec<(i64, i64, bool, String, Option<String>)> {
+    query_segments_for_range(recorder.store().connection(), i64::MIN / 2, i64::MAX / 2)
+        .unwrap()
+        .into_iter()
+        .map(|seg| (seg.start_ts, seg.end_ts, seg.is_idle, seg.app_name, seg.title))
+        .collect()
+}
+
+#[derive(Default, Clone)]
+struct EventLog(Arc<Mutex<Vec<String>>>);
+
+impl SegmentSink for EventLog {
+    fn write_segment(&mut self, event: &SegmentEvent) -> Result<()> {
+        let name = if event.is_idle {
+            "idle"
+        } else {
+            event.app_name.unwrap_or("?")
+        };
+        let args = event.process_args.map(|args| format!(" [{args}]")).unwrap_or_default();
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("{name} {}-{}{args}", event.start_ts - T0, event.end_ts - T0));
+        Ok(())
+    }
+
+    fn write_idle_cutoff(&mut self, cutoff_ts: i64) -> Result<()> {
+        self.0.lock().unwrap().push(format!("cutoff {}", cutoff_ts - T0));
+        Ok(())
+    }
+}
+
+#[test]
+fn same_window_extends_one_segment_until_the_app_changes() {
+    let mut recorder = recorder(0);
+    for offset in 0..5 {
+        recorder.ingest(active(T0 + offset, "code.exe", "main.rs")).unwrap();
+    }
+    assert!(stored(&recorder).is_empty());
+    assert_eq!(live_segment(recorder.store().connection()), Some((T0, false)));
+
+    recorder.ingest(active(T0 + 5, "firefox.exe", "Docs")).unwrap();
+    recorder.ingest(active(T0 + 9, "firefox.exe", "Docs")).unwrap();
+    recorder.flush_and_close(T0 + 10).unwrap();
+
+    let segments = stored(&recorder);
+    assert_eq!(
+        segments,
+        [
+            (T0, T0 + 4, false, "code.exe".to_owned(), Some("main.rs".to_owned())),
+            (
+                T0 + 5,
+                T0 + 10,
+                false,
+                "firefox.exe".to_owned(),
+                Some("Docs".to_owned())
+            ),
+        ]
+    );
+    assert_eq!(live_segment(recorder.store().connection()), None);
+    assert!(recorder.last_write_ts().is_some());
+}
+
+#[test]
+fn title_change_starts_a_new_segment() {
+    let mut recorder = recorder(0);
+    recorder.ingest(active(T0, "code.exe", "a.rs")).unwrap();
+    recorder.ingest(active(T0 + 2, "code.exe", "a.rs")).unwrap();
+    recorder.ingest(active(T0 + 3, "code.exe", "b.rs")).unwrap();
+    recorder.flush_and_close(T0 + 6).unwrap();
+
+    let titles: Vec<Option<String>> = stored(&recorder).into_iter().map(|seg| seg.4).collect();
+    assert_eq!(titles, [Some("a.rs".to_owned()), Some("b.rs".to_owned())]);
+}
+
+#[test]
+fn long_segments_rotate() {
+    let mut recorder = recorder(10);
+    for offset in 0..=25 {
+        recorder.ingest(active(T0 + offset, "code.exe", "main.rs")).unwrap();
+    }
+    recorder.flush_and_close(T0 + 25).unwrap();
+
+    let spans: Vec<(i64, i64)> = stored(&recorder)
+        .into_iter()
+        .map(|seg| (seg.0 - T0, seg.1 - T0))
+        .collect();
+    assert_eq!(spans, [(0, 10), (10, 20), (20, 25)]);
+}
+
+#[test]
+fn detected_idle_reclaims_active_time_back_to_the_last_input() {
+    let log = EventLog::default();
+    let mut recorder = recorder(10).with_sink(Box::new(log.clone()));
+    for offset in 0..=30 {
+        recorder.ingest(active(T0 + offset, "code.exe", "main.rs")).unwrap();
+    }
+    // At +35 the monitor reports no input for 25s, so everything after +10 was idle.
+    recorder.ingest(idle(T0 + 35, 25)).unwrap();
+    recorder.ingest(idle(T0 + 60, 50)).unwrap();
+    recorder.ingest(active(T0 + 61, "code.exe", "main.rs")).unwrap();
+    recorder.flush_and_close(T0 + 62).unwrap();
+
+    let spans: Vec<(i64, i64, bool)> = stored(&recorder)
+        .into_iter()
+        .map(|seg| (seg.0 - T0, seg.1 - T0, seg.2))
+        .collect();
+    assert_eq!(spans, [(0, 10, false), (10, 60, true), (61, 62, false)]);
+    // Sinks saw the rotated segments before the cutoff withdrew them.
+    let events = log.0.lock().unwrap().clone();
+    assert_eq!(
+        events,
+        [
+            "code.exe 0-10",
+            "code.exe 10-20",
+            "code.exe 20-30",
+            "cutoff 10",
+            "idle 10-60",
+            "code.exe 61-62"
+        ]
+    );
+}
+
+#[test]
+fn idle_after_resume_does_not_reach_into_the_paused_time() {
+    let mut recorder = recorder(0);
+    recorder.ingest(active(T0, "code.exe", "main.rs")).unwrap();
+    recorder.ingest(active(T0 + 10, "code.exe", "main.rs")).unwrap();
+    recorder.pause(T0 + 10).unwrap();
+    recorder.resume(T0 + 100);
+    recorder.ingest(idle(T0 + 120, 600)).unwrap();
+    recorder.flush_and_close(T0 + 130).unwrap();
+
+    let spans: Vec<(i64, i64, bool)> = stored(&recorder)
+        .into_iter()
+        .map(|seg| (seg.0 - T0, seg.1 - T0, seg.2))
+        .collect();
+    assert_eq!(spans, [(0, 10, false), (100, 130, true)]);
+}
+
+#[test]
+fn sleep_between_samples_leaves_a_gap_instead_of_app_time() {
+    let mut recorder = recorder(0);
+    recorder.set_max_sample_gap(Duration::from_secs(30));
+    for offset in 0..=10 {
+        recorder.ingest(active(T0 + offset, "code.exe", "main.rs")).unwrap();
+    }
+    // Woken an hour later, with the idle time still counting the sleep.
+    let woke = T0 + 3_600;
+    recorder.ingest(idle(woke, 3_590)).unwrap();
+    recorder.ingest(idle(woke + 5, 3_595)).unwrap();
+    for offset in 6..=10 {
+        recorder.ingest(active(woke + offset, "code.exe", "main.rs")).unwrap();
+    }
+    // An explicit suspend ends the segment where the machine went to sleep.
+    recorder.suspend(woke + 12, woke + 600).unwrap();
+    recorder.flush_and_close(woke + 600).unwrap();
+
+    assert_eq!(
+        stored(&recorder),
+        [
+            (T0, T0 + 10, false, "code.exe".to_owned(), Some("main.rs".to_owned())),
+            (woke, woke + 5, true, "UNKNOWN".to_owned(), None),
+            (woke + 6, woke + 12, false, "code.exe".to_owned(), Some("main.rs".to_owned())),
+        ]
+    );
+}
+
+#[test]
+fn quick_switches_keep_their_milliseconds() {
+    let at = |offset_ms: i64, exe: &str| {
+        let mut sample = active(T0, exe, "");
+        sample.ts_ms = T0 * 1000 + offset_ms;
+        sample
+    };
+    let mut recorder = recorder(0);
+    recorder.ingest(at(0, "code.exe")).unwrap();
+    recorder.ingest(at(300, "code.exe")).unwrap();
+    recorder.ingest(at(400, "firefox.exe")).unwrap();
+    recorder.ingest(at(900, "firefox.exe")).unwrap();
+    recorder.ingest(at(1_200, "code.exe")).unwrap();
+    recorder.ingest(at(2_700, "code.exe")).unwrap();
+    recorder.flush_and_close(T0).unwrap();
+
+    let segments = query_segments_for_range(recorder.store().connection(), T0, T0 + 10).unwrap();
+    let spans: Vec<(i64, i64, &str)> = segments
+        .iter()
+        .map(|seg| (seg.start_ms - T0 * 1000, seg.end_ms - T0 * 1000, seg.app_name.as_str()))
+        .collect();
+    assert_eq!(spans, [(0, 300, "code.exe"), (400, 900, "firefox.exe"), (1_200, 2_700, "code.exe")]);
+    // Older readers still see whole seconds.
+    assert_eq!((segments[1].start_ts, segments[1].end_ts), (T0, T0));
+
+    let rows = build_summary_rows(T0, T0 + 10, &segments, 0, |_| None);
+    let secs: Vec<(&str, i64)> = rows.iter().map(|row| (row.app_name.as_str(), row.duration_secs)).collect();
+    assert_eq!(secs, [("code.exe", 1), ("firefox.exe", 0)]);
+}
+
+#[test]
+fn calls_split_segments_and_are_summed_apart() {
+    let in_call = |ts: i64, exe: &str| {
+        let mut sample = active(ts, exe, "Notes");
+        if let ActivityKind::Active(window) = &mut sample.kind {
+            window.in_call = true;
+        }
+        sample
+    };
+    let mut recorder = recorder(0);
+    for offset in 0..140 {
+        let sample = match offset {
+            10..70 => in_call(T0 + offset, "word.exe"),
+            70..130 => in_call(T0 + offset, "teams.exe"),
+            _ => active(T0 + offset, "word.exe", "Notes"),
+        };
+        recorder.ingest(sample).unwrap();
+    }
+    recorder.flush_and_close(T0 + 140).unwrap();
+
+    let segments = query_segments_for_range(recorder.store().connection(), T0, T0 + 200).unwrap();
+    let calls: Vec<(i64, bool)> = segments.iter().map(|seg| (seg.start_ts - T0, seg.is_call)).collect();
+    assert_eq!(calls, [(0, false), (10, true), (70, true), (130, false)]);
+
+    let mut summary = SummaryAccumulator::new(T0, T0 + 200, 0, |_| None);
+    for seg in &segments {
+        summary.push(seg);
+    }
+    assert_eq!(summary.call_secs(), 59 + 59);
+    let total: i64 = summary.finish().iter().map(|row| row.duration_secs).sum();
+    assert_eq!(total, 9 + 59 + 59 + 10);
+}
+
+#[test]
+fn moving_a_window_to_another_display_starts_a_new_segment() {
+    let on = |ts: i64, display: &str| {
+        let mut sample = active(ts, "code.exe", "main.rs");
+        if let ActivityKind::Active(window) = &mut sample.kind {
+            window.monitor = Some(display.to_owned());
+        }
+        sample
+    };
+    let mut recorder = recorder(0);
+    for offset in 0..6 {
+        let display = if offset < 3 { r"\\.\DISPLAY1" } else { r"\\.\DISPLAY2" };
+        recorder.ingest(on(T0 + offset, display)).unwrap();
+    }
+    recorder.flush_and_close(T0 + 6).unwrap();
+
+    let segments = query_segments_for_range(recorder.store().connection(), T0, T0 + 10).unwrap();
+    let displays: Vec<(i64, Option<&str>)> =
+        segments.iter().map(|seg| (seg.start_ts - T0, seg.monitor.as_deref())).collect();
+    assert_eq!(displays, [(0, Some(r"\\.\DISPLAY1")), (3, Some(r"\\.\DISPLAY2"))]);
+}
+
+#[test]
+fn segments_crossing_midnight_are_stored_whole() {
+    let midnight = T0 - 9 * 3600 + 86_400;
+    let mut recorder = recorder(0);
+    recorder.ingest(active(midnight - 30, "code.exe", "main.rs")).unwrap();
+    recorder.ingest(active(midnight + 30, "code.exe", "main.rs")).unwrap();
+    recorder.flush_and_close(midnight + 30).unwrap();
+
+    let conn = recorder.store().connection();
+    let before = query_segments_for_range(conn, midnight - 86_400, midnight).unwrap();
+    let after = query_segments_for_range(conn, midnight, midnight + 86_400).unwrap();
+    assert_eq!(before.len(), 1);
+    assert_eq!((after[0].start_ts, after[0].end_ts), (midnight - 30, midnight + 30));
+}
+
+#[test]
+fn identity_keeps_a_moved_executable_on_its_first_app_row() {
+    let mut recorder = recorder(0);
+    let mut sample = active(T0, "code.exe", "");
+    if let ActivityKind::Active(window) = &mut sample.kind {
+        window.identity = Some("publisher:code".to_owned());
+    }
+    recorder.ingest(sample.clone()).unwrap();
+    sample.ts_ms = (T0 + 4) * 1000;
+    recorder.ingest(sample.clone()).unwrap();
+    if let ActivityKind::Active(window) = &mut sample.kind {
+        window.process_path = r"D:\Portable\code.exe".to_owned();
+        window.pid = 200;
+    }
+    sample.ts_ms = (T0 + 5) * 1000;
+    recorder.ingest(sample).unwrap();
+    recorder.flush_and_close(T0 + 10).unwrap();
+
+    let conn = recorder.store().connection();
+    let apps: i64 = conn
+        .query_row("SELECT COUNT(*) FROM apps", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(apps, 1);
+    let segments = query_segments_for_range(conn, T0, T0 + 10).unwrap();
+    // A new pid still closes the segment, but both point at the same app.
+    assert_eq!(segments.len(), 2);
+    assert!(segments
+        .iter()
+        .all(|seg| seg.process_path.as_deref() == Some(r"C:\Apps\code.exe")));
+    assert!(segments.iter().all(|seg| seg.title.is_none()));
+}
+
+#[test]
+fn captured_command_lines_follow_every_rotated_segment() {
+    let log = EventLog::default();
+    let mut recorder = recorder(10).with_sink(Box::new(log.clone()));
+    let mut sample = active(T0, "code.exe", "main.rs");
+    if let ActivityKind::Active(window) = &mut sample.kind {
+        window.process_args = Some(r"code.exe D:\src\limetrace".to_owned());
+    }
+    for offset in 0..=15 {
+        sample.ts_ms = (T0 + offset) * 1000;
+        recorder.ingest(sample.clone()).unwrap();
+    }
+    recorder.ingest(active(T0 + 16, "notepad.exe", "notes.txt")).unwrap();
+    recorder.flush_and_close(T0 + 20).unwrap();
+
+    let events = log.0.lock().unwrap().clone();
+    assert_eq!(
+        events,
+        [
+            r"code.exe 0-10 [code.exe D:\src\limetrace]",
+            r"code.exe 10-15 [code.exe D:\src\limetrace]",
+            "notepad.exe 16-20"
+        ]
+    );
+}
+
+#[test]
+fn browser_urls_split_segments_and_summaries_by_site() {
+    let mut recorder = recorder(0);
+    let mut sample = active(T0, "chrome.exe", "Inbox");
+    for offset in 0..10 {
+        let url = match offset {
+            0..=4 => "mail.example.com/inbox",
+            5..=7 => "https://www.example.org/news",
+            _ => "about:blank",
+        };
+        if let ActivityKind::Active(window) = &mut sample.kind {
+            window.url = Some(url.to_owned());
+        }
+        sample.ts_ms = (T0 + offset) * 1000;
+        recorder.ingest(sample.clone()).unwrap();
+    }
+    recorder.flush_and_close(T0 + 10).unwrap();
+
+    let segments = query_segments_for_range(recorder.store().connection(), T0, T0 + 10).unwrap();
+    let urls: Vec<Option<&str>> = segments.iter().map(|seg| seg.url.as_deref()).collect();
+    assert_eq!(urls, [Some("mail.example.com/inbox"), Some("https://www.example.org/news"), Some("about:blank")]);
+    let rows = build_summary_rows(T0, T0 + 10, &segments, 0, |_| None);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].top_sites, [("mail.example.com".to_owned(), 4), ("example.org".to_owned(), 2)]);
+
+    assert_eq!(url_domain("http://user@LOCALHOST:8080/a?b").as_deref(), Some("localhost"));
+    assert_eq!(url_domain("search words"), None);
+}
+
+#[test]
+fn locked_time_ends_the_open_segment_and_stays_out_of_summaries() {
+    let mut recorder = recorder(0);
+    let mut samples: Vec<ActivitySample> = (0..=5).map(|offset| active(T0 + offset, "code.exe", "main.rs")).collect();
+    for offset in 6..=20 {
+        let mut locked = active(T0 + offset, LOCKED_APP, "");
+        if let ActivityKind::Active(window) = &mut locked.kind {
+            window.process_path = LOCKED_PROCESS_PATH.to_owned();
+        }
+        samples.push(locked);
+    }
+    samples.extend((21..=25).map(|offset| active(T0 + offset, "code.exe", "main.rs")));
+    for sample in samples {
+        recorder.ingest(sample).unwrap();
+    }
+    recorder.flush_and_close(T0 + 25).unwrap();
+
+    let segments: Vec<(i64, i64, String)> = stored(&recorder)
+        .into_iter()
+        .map(|(start, end, _, app, _)| (start, end, app))
+        .collect();
+    assert_eq!(
+        segments,
+        [
+            (T0, T0 + 5, "code.exe".to_owned()),
+            (T0 + 6, T0 + 20, LOCKED_APP.to_owned()),
+            (T0 + 21, T0 + 25, "code.exe".to_owned()),
+        ]
+    );
+    let all = query_segments_for_range(recorder.store().connection(), T0, T0 + 25).unwrap();
+    let rows = build_summary_rows(T0, T0 + 25, &all, 0, |_| None);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].duration_secs, 9);
+}
+
+#[test]
+fn browser_profiles_are_read_from_window_titles() {
+    let profiles = ["Personal".to_owned(), "Work".to_owned()];
+    assert_eq!(browser_profile_in_title("Inbox - Google Chrome - Work", &profiles), Some("Work"));
+    assert_eq!(browser_profile_in_title("Inbox - Personal - Microsoft\u{200b} Edge", &profiles), Some("Personal"));
+    // A page that happens to be titled like a profile is not one.
+    assert_eq!(browser_profile_in_title("Work - Google Chrome", &profiles), None);
+    assert_eq!(browser_profile_in_title("Inbox - Google Chrome", &profiles), None);
+
+    let app = browser_profile_app("chrome.exe", "Work");
+    assert_eq!(app, "chrome (Work)");
+    assert!(is_browser_app(&app));
+}
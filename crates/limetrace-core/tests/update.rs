@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::PathBuf;
+
+use ed25519_dalek::{Signer, SigningKey};
+use limetrace_core::update::{self, Download};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("limetrace-update-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+struct SignedRelease {
+    checksums: String,
+    signature: String,
+    public_key: String,
+}
+
+fn sign(version: &str, assets: &[(&str, &[u8])]) -> SignedRelease {
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let checksums: String = assets
+        .iter()
+        .map(|(name, bytes)| format!("{}  {name}\n", update::sha256_hex(bytes)))
+        .collect();
+    let message = format!("limetrace {version}\n{checksums}");
+    SignedRelease {
+        signature: hex(&key.sign(message.as_bytes()).to_bytes()),
+        public_key: hex(key.verifying_key().as_bytes()),
+        checksums,
+    }
+}
+
+fn download(asset: &str, install_as: &str, bytes: &[u8]) -> Download {
+    Download {
+        asset: asset.to_owned(),
+        install_as: install_as.to_owned(),
+        bytes: bytes.to_vec(),
+    }
+}
+
+#[test]
+fn staged_binary_replaces_the_running_one() {
+    let dir = scratch_dir("apply");
+    let install_dir = dir.join("app");
+    let staging = dir.join("update");
+    fs::create_dir_all(&install_dir).unwrap();
+    let exe = install_dir.join("limetrace-backend.exe");
+    fs::write(&exe, b"old backend").unwrap();
+
+    let release = sign("0.2.0", &[
+        ("limetrace-windows-x86_64.exe", b"new viewer"),
+        ("limetrace-backend-windows-x86_64.exe", b"new backend"),
+    ]);
+    let downloads = [
+        download("limetrace-windows-x86_64.exe", "limetrace.exe", b"new viewer"),
+        download("limetrace-backend-windows-x86_64.exe", "limetrace-backend.exe", b"new backend"),
+    ];
+    update::stage(&staging, "0.2.0", &downloads, &release.checksums, &release.signature, &release.public_key)
+        .unwrap();
+    assert!(update::is_staged_for(&staging, &exe));
+
+    assert_eq!(
+        update::apply_staged(&staging, &exe, "0.1.0", &release.public_key).unwrap().as_deref(),
+        Some("0.2.0")
+    );
+    assert_eq!(fs::read(&exe).unwrap(), b"new backend");
+    assert!(!update::is_staged_for(&staging, &exe));
+    // The viewer's copy is still waiting for its next start.
+    let (version, files) = update::staged(&staging).unwrap();
+    assert_eq!(version, "0.2.0");
+    assert_eq!(files.keys().collect::<Vec<_>>(), ["limetrace.exe"]);
+
+    update::remove_replaced(&exe);
+    assert_eq!(fs::read_dir(&install_dir).unwrap().count(), 1);
+    assert_eq!(update::apply_staged(&staging, &exe, "0.2.0", &release.public_key).unwrap(), None);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn unsigned_or_mismatched_downloads_are_not_staged() {
+    let dir = scratch_dir("verify");
+    let release = sign("0.2.0", &[("limetrace-linux-x86_64", b"viewer")]);
+    let good = [download("limetrace-linux-x86_64", "limetrace", b"viewer")];
+
+    // The signature covers the version, so these hashes can't be staged as another.
+    assert!(update::stage(&dir, "0.3.0", &good, &release.checksums, &release.signature, &release.public_key).is_err());
+    let tampered = release.checksums.replace("  ", "  x");
+    assert!(update::stage(&dir, "0.2.0", &good, &tampered, &release.signature, &release.public_key).is_err());
+    let other_key = hex(SigningKey::from_bytes(&[9; 32]).verifying_key().as_bytes());
+    assert!(update::stage(&dir, "0.2.0", &good, &release.checksums, &release.signature, &other_key).is_err());
+    let corrupt = [download("limetrace-linux-x86_64", "limetrace", b"viewer!")];
+    let err = update::stage(&dir, "0.2.0", &corrupt, &release.checksums, &release.signature, &release.public_key)
+        .unwrap_err();
+    assert!(err.to_string().contains("checksum mismatch"), "{err:#}");
+    assert!(update::staged(&dir).is_none());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn staged_copies_are_checked_again_before_they_replace_anything() {
+    let dir = scratch_dir("recheck");
+    let staging = dir.join("update");
+    let exe = dir.join("limetrace");
+    fs::write(&exe, b"old viewer").unwrap();
+    let release = sign("0.2.0", &[("limetrace-linux-x86_64", b"viewer")]);
+    let downloads = [download("limetrace-linux-x86_64", "limetrace", b"viewer")];
+
+    // Swapped on disk after staging.
+    update::stage(&staging, "0.2.0", &downloads, &release.checksums, &release.signature, &release.public_key)
+        .unwrap();
+    fs::write(staging.join("limetrace"), b"not the viewer").unwrap();
+    assert!(update::apply_staged(&staging, &exe, "0.1.0", &release.public_key).is_err());
+    assert!(update::staged(&staging).is_none());
+
+    // Not newer than what is running, e.g. an old stage left behind by a downgrade.
+    update::stage(&staging, "0.2.0", &downloads, &release.checksums, &release.signature, &release.public_key)
+        .unwrap();
+    let err = update::apply_staged(&staging, &exe, "0.2.0", &release.public_key).unwrap_err();
+    assert!(format!("{err:#}").contains("not newer"), "{err:#}");
+    assert!(update::staged(&staging).is_none());
+
+    // Signed by someone else.
+    update::stage(&staging, "0.2.0", &downloads, &release.checksums, &release.signature, &release.public_key)
+        .unwrap();
+    let other_key = hex(SigningKey::from_bytes(&[9; 32]).verifying_key().as_bytes());
+    assert!(update::apply_staged(&staging, &exe, "0.1.0", &other_key).is_err());
+
+    assert_eq!(fs::read(&exe).unwrap(), b"old viewer");
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn versions_compare_numerically() {
+    assert!(update::is_newer("0.10.0", "0.9.3"));
+    assert!(update::is_newer("v1.0.0", "0.99.0"));
+    assert!(!update::is_newer("0.1.0", "0.1.0"));
+    assert!(!update::is_newer("0.1.0", "0.2.0"));
+}
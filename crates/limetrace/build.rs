@@ -1,12 +1,21 @@
-#[cfg(windows)]
 fn main() {
+    detect_bundled_font();
+    #[cfg(windows)]
     if let Err(err) = compile_windows_resources() {
         panic!("failed to compile LimeTrace resources: {err}");
     }
 }
 
-#[cfg(not(windows))]
-fn main() {}
+// The CJK fallback subset is built by scripts/subset-fallback-font.ps1; without it the
+// viewer relies on system fonts alone.
+fn detect_bundled_font() {
+    let font_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("assets").join("fonts");
+    println!("cargo:rustc-check-cfg=cfg(bundled_fallback_font)");
+    println!("cargo:rerun-if-changed={}", font_dir.display());
+    if font_dir.join("NotoSansSC-Subset.otf").is_file() {
+        println!("cargo:rustc-cfg=bundled_fallback_font");
+    }
+}
 
 #[cfg(windows)]
 fn compile_windows_resources() -> Result<(), Box<dyn std::error::Error>> {
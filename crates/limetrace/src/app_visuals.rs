@@ -0,0 +1,389 @@
+//! App icons and display names outside Windows: freedesktop `.desktop` entries and
+//! icon themes on Linux, `.app` bundles on macOS. Only PNG artwork is decoded; apps
+//! that ship SVG-only icons or asset catalogs keep the fallback square.
+
+use eframe::egui;
+
+// Matches the small icon ExtractIconExW hands back on Windows, so rows look the same.
+const ICON_SIZE: u32 = 32;
+
+pub fn load_icon(process_path: &str) -> Option<egui::ColorImage> {
+    if process_path.trim().is_empty() {
+        return None;
+    }
+    platform::load_icon_png(process_path).and_then(|png| decode_png(&png))
+}
+
+pub fn load_display_name(process_path: &str) -> Option<String> {
+    if process_path.trim().is_empty() {
+        return None;
+    }
+    platform::load_display_name(process_path)
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+}
+
+fn decode_png(bytes: &[u8]) -> Option<egui::ColorImage> {
+    let mut image = image::load_from_memory_with_format(bytes, image::ImageFormat::Png).ok()?;
+    if image.width() > ICON_SIZE || image.height() > ICON_SIZE {
+        image = image.thumbnail(ICON_SIZE, ICON_SIZE);
+    }
+    let rgba = image.to_rgba8();
+    Some(egui::ColorImage::from_rgba_unmultiplied(
+        [rgba.width() as usize, rgba.height() as usize],
+        rgba.as_raw(),
+    ))
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use std::collections::HashSet;
+    use std::env;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::OnceLock;
+
+    const ICON_SIZES: [&str; 8] = [
+        "32x32", "48x48", "24x24", "64x64", "128x128", "256x256", "22x22", "16x16",
+    ];
+
+    struct DesktopEntry {
+        file_stem: String,
+        names: Vec<(String, String)>,
+        exec: Option<String>,
+        try_exec: Option<String>,
+        wm_class: Option<String>,
+        icon: Option<String>,
+    }
+
+    pub fn load_icon_png(process_path: &str) -> Option<Vec<u8>> {
+        let icon = find_entry(process_path)?.icon.as_deref()?;
+        resolve_icon(icon).and_then(|path| fs::read(path).ok())
+    }
+
+    pub fn load_display_name(process_path: &str) -> Option<String> {
+        let entry = find_entry(process_path)?;
+        for locale in preferred_locales() {
+            if let Some((_, name)) = entry.names.iter().find(|(key, _)| *key == locale) {
+                return Some(name.clone());
+            }
+        }
+        entry
+            .names
+            .iter()
+            .find(|(key, _)| key.is_empty())
+            .map(|(_, name)| name.clone())
+    }
+
+    // Exact Exec/TryExec paths beat a matching binary name, which beats StartupWMClass or
+    // the file name; the latter two catch apps launched through a wrapper script.
+    fn find_entry(process_path: &str) -> Option<&'static DesktopEntry> {
+        let exe_name = Path::new(process_path).file_name()?.to_str()?;
+        let mut best: Option<(u8, &DesktopEntry)> = None;
+        for entry in desktop_entries() {
+            let commands = [entry.exec.as_deref().and_then(exec_program), entry.try_exec.as_deref()];
+            let score = if commands.iter().flatten().any(|command| *command == process_path) {
+                3
+            } else if commands
+                .iter()
+                .flatten()
+                .any(|command| Path::new(command).file_name().and_then(|name| name.to_str()) == Some(exe_name))
+            {
+                2
+            } else if entry
+                .wm_class
+                .as_deref()
+                .is_some_and(|class| class.eq_ignore_ascii_case(exe_name))
+                || entry.file_stem.eq_ignore_ascii_case(exe_name)
+                || entry
+                    .file_stem
+                    .rsplit('.')
+                    .next()
+                    .is_some_and(|stem| stem.eq_ignore_ascii_case(exe_name))
+            {
+                1
+            } else {
+                continue;
+            };
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, entry));
+            }
+        }
+        best.map(|(_, entry)| entry)
+    }
+
+    fn desktop_entries() -> &'static [DesktopEntry] {
+        static ENTRIES: OnceLock<Vec<DesktopEntry>> = OnceLock::new();
+        ENTRIES.get_or_init(|| {
+            let mut seen = HashSet::new();
+            let mut entries = Vec::new();
+            for dir in data_dirs() {
+                collect_desktop_entries(&dir.join("applications"), 0, &mut seen, &mut entries);
+            }
+            entries
+        })
+    }
+
+    // Earlier directories win, the same precedence the desktop uses for overrides.
+    fn collect_desktop_entries(dir: &Path, depth: usize, seen: &mut HashSet<String>, entries: &mut Vec<DesktopEntry>) {
+        let Ok(read_dir) = fs::read_dir(dir) else {
+            return;
+        };
+        for item in read_dir.flatten() {
+            let path = item.path();
+            if path.is_dir() {
+                if depth < 2 {
+                    collect_desktop_entries(&path, depth + 1, seen, entries);
+                }
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some("desktop") {
+                continue;
+            }
+            let Some(file_stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if !seen.insert(file_stem.to_owned()) {
+                continue;
+            }
+            if let Some(entry) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|text| parse_desktop_entry(file_stem, &text))
+            {
+                entries.push(entry);
+            }
+        }
+    }
+
+    fn parse_desktop_entry(file_stem: &str, text: &str) -> Option<DesktopEntry> {
+        let mut entry = DesktopEntry {
+            file_stem: file_stem.to_owned(),
+            names: Vec::new(),
+            exec: None,
+            try_exec: None,
+            wm_class: None,
+            icon: None,
+        };
+        let mut in_main_group = false;
+        for line in text.lines() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                in_main_group = line == "[Desktop Entry]";
+                continue;
+            }
+            if !in_main_group || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim().to_owned());
+            if let Some(locale) = key.strip_prefix("Name") {
+                if locale.is_empty() {
+                    entry.names.push((String::new(), value));
+                } else if let Some(locale) = locale.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                    entry.names.push((locale.to_owned(), value));
+                }
+                continue;
+            }
+            match key {
+                "Type" if value != "Application" => return None,
+                "Exec" => entry.exec = Some(value),
+                "TryExec" => entry.try_exec = Some(value),
+                "StartupWMClass" => entry.wm_class = Some(value),
+                "Icon" => entry.icon = Some(value),
+                _ => {}
+            }
+        }
+        (!entry.names.is_empty()).then_some(entry)
+    }
+
+    // `env FOO=1 /usr/bin/app %U` and `"/opt/My App/app" %F` both come down to the program.
+    fn exec_program(exec: &str) -> Option<&str> {
+        let mut rest = exec.trim();
+        loop {
+            let (token, tail) = if let Some(quoted) = rest.strip_prefix('"') {
+                let end = quoted.find('"')?;
+                (&quoted[..end], &quoted[end + 1..])
+            } else {
+                rest.split_once(char::is_whitespace).unwrap_or((rest, ""))
+            };
+            if token == "env" || (token.contains('=') && !token.starts_with('/')) {
+                rest = tail.trim_start();
+                if rest.is_empty() {
+                    return None;
+                }
+                continue;
+            }
+            return (!token.is_empty()).then_some(token);
+        }
+    }
+
+    fn resolve_icon(icon: &str) -> Option<PathBuf> {
+        let icon_path = Path::new(icon);
+        if icon_path.is_absolute() {
+            return (icon_path.extension().and_then(|ext| ext.to_str()) == Some("png") && icon_path.is_file())
+                .then(|| icon_path.to_path_buf());
+        }
+        let file_name = if icon.ends_with(".png") {
+            icon.to_owned()
+        } else {
+            format!("{icon}.png")
+        };
+        let mut icon_dirs = Vec::new();
+        if let Some(home) = env::var_os("HOME") {
+            icon_dirs.push(PathBuf::from(home).join(".icons"));
+        }
+        icon_dirs.extend(data_dirs().into_iter().map(|dir| dir.join("icons")));
+        for size in ICON_SIZES {
+            for dir in &icon_dirs {
+                let candidate = dir.join("hicolor").join(size).join("apps").join(&file_name);
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+        let pixmap = PathBuf::from("/usr/share/pixmaps").join(&file_name);
+        pixmap.is_file().then_some(pixmap)
+    }
+
+    fn data_dirs() -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+        match env::var_os("XDG_DATA_HOME").filter(|value| !value.is_empty()) {
+            Some(data_home) => dirs.push(PathBuf::from(data_home)),
+            None => {
+                if let Some(home) = env::var_os("HOME") {
+                    dirs.push(PathBuf::from(home).join(".local/share"));
+                }
+            }
+        }
+        if let Some(home) = env::var_os("HOME") {
+            dirs.push(PathBuf::from(home).join(".local/share/flatpak/exports/share"));
+        }
+        let system_dirs = env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_owned());
+        dirs.extend(system_dirs.split(':').filter(|dir| !dir.is_empty()).map(PathBuf::from));
+        dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+        dirs.push(PathBuf::from("/var/lib/snapd/desktop"));
+        dirs
+    }
+
+    // `zh_CN.UTF-8` looks up `Name[zh_CN]`, then `Name[zh]`.
+    fn preferred_locales() -> Vec<String> {
+        let Some(locale) = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|key| env::var(key).ok())
+            .find(|value| !value.is_empty())
+        else {
+            return Vec::new();
+        };
+        let locale = locale.split(['.', '@']).next().unwrap_or_default().to_owned();
+        let mut locales = vec![locale.clone()];
+        if let Some((language, _)) = locale.split_once('_') {
+            locales.push(language.to_owned());
+        }
+        locales
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    // PNG-carrying icns entries, closest to the target size first.
+    const ICNS_PNG_TYPES: [&[u8; 4]; 10] = [
+        b"ic11", b"icp5", b"ic12", b"icp6", b"ic07", b"ic13", b"ic08", b"ic14", b"ic09", b"ic10",
+    ];
+
+    pub fn load_icon_png(process_path: &str) -> Option<Vec<u8>> {
+        let bundle = app_bundle(process_path)?;
+        let plist = read_info_plist(&bundle)?;
+        let icon_file = plist_string(&plist, "CFBundleIconFile")?;
+        let mut icon_path = bundle.join("Contents/Resources").join(&icon_file);
+        if icon_path.extension().is_none() {
+            icon_path.set_extension("icns");
+        }
+        let icns = fs::read(icon_path).ok()?;
+        icns_png(&icns)
+    }
+
+    pub fn load_display_name(process_path: &str) -> Option<String> {
+        let bundle = app_bundle(process_path)?;
+        read_info_plist(&bundle)
+            .and_then(|plist| {
+                plist_string(&plist, "CFBundleDisplayName").or_else(|| plist_string(&plist, "CFBundleName"))
+            })
+            .or_else(|| bundle.file_stem().and_then(|stem| stem.to_str()).map(str::to_owned))
+    }
+
+    // The innermost bundle is the one that owns the process, so helper apps keep their own name.
+    fn app_bundle(process_path: &str) -> Option<PathBuf> {
+        Path::new(process_path)
+            .ancestors()
+            .find(|dir| dir.extension().and_then(|ext| ext.to_str()) == Some("app"))
+            .map(Path::to_path_buf)
+    }
+
+    fn read_info_plist(bundle: &Path) -> Option<String> {
+        let path = bundle.join("Contents/Info.plist");
+        let bytes = fs::read(&path).ok()?;
+        if !bytes.starts_with(b"bplist") {
+            return String::from_utf8(bytes).ok();
+        }
+        let output = Command::new("plutil")
+            .args(["-convert", "xml1", "-o", "-"])
+            .arg(&path)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        String::from_utf8(output.stdout).ok()
+    }
+
+    // Top-level string values only, which is all the bundle keys above need.
+    fn plist_string(plist: &str, key: &str) -> Option<String> {
+        let marker = format!("<key>{key}</key>");
+        let rest = plist[plist.find(&marker)? + marker.len()..].trim_start();
+        let value = rest.strip_prefix("<string>")?;
+        let value = &value[..value.find("</string>")?];
+        let value = value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&");
+        let value = value.trim().to_owned();
+        (!value.is_empty()).then_some(value)
+    }
+
+    fn icns_png(icns: &[u8]) -> Option<Vec<u8>> {
+        if icns.len() < 8 || &icns[..4] != b"icns" {
+            return None;
+        }
+        let mut entries = Vec::new();
+        let mut offset = 8;
+        while offset + 8 <= icns.len() {
+            let kind = &icns[offset..offset + 4];
+            let len = u32::from_be_bytes(icns[offset + 4..offset + 8].try_into().ok()?) as usize;
+            if len < 8 || offset + len > icns.len() {
+                break;
+            }
+            let data = &icns[offset + 8..offset + len];
+            if data.starts_with(b"\x89PNG") {
+                entries.push((kind, data));
+            }
+            offset += len;
+        }
+        ICNS_PNG_TYPES.iter().find_map(|wanted| {
+            entries
+                .iter()
+                .find(|(kind, _)| *kind == wanted.as_slice())
+                .map(|(_, data)| data.to_vec())
+        })
+    }
+}
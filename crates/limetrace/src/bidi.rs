@@ -0,0 +1,96 @@
+//! Right-to-left text for the UI. egui lays glyphs out left to right and does no
+//! shaping, so Arabic strings are reshaped into presentation forms and reordered
+//! into visual order before they are handed to a label or the painter.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use unicode_bidi::{BidiInfo, Level};
+
+const FIRST_STRONG_ISOLATE: char = '\u{2068}';
+const POP_DIRECTIONAL_ISOLATE: char = '\u{2069}';
+
+pub fn has_rtl(text: &str) -> bool {
+    text.chars().any(|ch| {
+        matches!(ch, '\u{0590}'..='\u{08FF}' | '\u{FB1D}'..='\u{FDFF}' | '\u{FE70}'..='\u{FEFF}')
+    })
+}
+
+/// `text` in visual order for a right-to-left paragraph, as UI strings are in ar-SA.
+pub fn display_rtl(text: &str) -> Cow<'_, str> {
+    display(text, Some(Level::rtl()))
+}
+
+/// `text` in visual order with the direction taken from its first strong character.
+/// Window titles and app names go through this wherever they are shown.
+pub fn display_auto(text: &str) -> Cow<'_, str> {
+    display(text, None)
+}
+
+/// Like [`display_rtl`] for the static translation tables; each string is shaped once.
+pub fn display_static(text: &'static str) -> &'static str {
+    if !has_rtl(text) {
+        return text;
+    }
+    static CACHE: OnceLock<Mutex<HashMap<&'static str, &'static str>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(Default::default).lock().unwrap_or_else(|err| err.into_inner());
+    cache
+        .entry(text)
+        .or_insert_with(|| Box::leak(display_rtl(text).into_owned().into_boxed_str()))
+}
+
+/// Wraps right-to-left `text` in a first-strong isolate so it cannot reorder the
+/// punctuation and numbers around it in exported plain text and Markdown.
+pub fn isolate(text: &str) -> Cow<'_, str> {
+    if has_rtl(text) {
+        Cow::Owned(format!("{FIRST_STRONG_ISOLATE}{text}{POP_DIRECTIONAL_ISOLATE}"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn display(text: &str, base: Option<Level>) -> Cow<'_, str> {
+    if !has_rtl(text) {
+        return Cow::Borrowed(text);
+    }
+    let shaped = ar_reshaper::reshape_line(text);
+    let info = BidiInfo::new(&shaped, base);
+    let mut out = String::with_capacity(shaped.len());
+    for paragraph in &info.paragraphs {
+        let mut line = paragraph.range.clone();
+        let newline = shaped[line.clone()].ends_with('\n');
+        if newline {
+            line.end -= 1;
+        }
+        let (levels, runs) = info.visual_runs(paragraph, line);
+        for run in runs {
+            let run_text = &shaped[run.clone()];
+            if levels[run.start].is_rtl() {
+                out.extend(run_text.chars().rev().map(mirror));
+            } else {
+                out.push_str(run_text);
+            }
+        }
+        if newline {
+            out.push('\n');
+        }
+    }
+    Cow::Owned(out)
+}
+
+fn mirror(ch: char) -> char {
+    match ch {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '\u{00AB}' => '\u{00BB}',
+        '\u{00BB}' => '\u{00AB}',
+        _ => ch,
+    }
+}
@@ -0,0 +1,66 @@
+//! Release notes for the "What's new" window, compiled in from CHANGELOG.md.
+
+use std::cmp::Ordering;
+
+const CHANGELOG: &str = include_str!("../../../CHANGELOG.md");
+
+pub struct Release {
+    pub version: &'static str,
+    pub changes: Vec<String>,
+    pub migrations: Vec<String>,
+}
+
+fn releases() -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    let mut in_migrations = false;
+    for line in CHANGELOG.lines() {
+        if let Some(version) = line.strip_prefix("## ") {
+            releases.push(Release {
+                version: version.trim(),
+                changes: Vec::new(),
+                migrations: Vec::new(),
+            });
+            in_migrations = false;
+            continue;
+        }
+        let Some(release) = releases.last_mut() else {
+            continue;
+        };
+        if let Some(heading) = line.strip_prefix("### ") {
+            in_migrations = heading.trim().eq_ignore_ascii_case("migration");
+            continue;
+        }
+        let entries = if in_migrations { &mut release.migrations } else { &mut release.changes };
+        if let Some(entry) = line.strip_prefix("- ") {
+            entries.push(entry.trim().to_owned());
+        } else if let (Some(last), true) = (entries.last_mut(), line.starts_with("  ")) {
+            last.push(' ');
+            last.push_str(line.trim());
+        }
+    }
+    releases
+}
+
+/// Releases after `previous` up to and including `current`, newest first. Without a
+/// previous version only `current` is returned.
+pub fn releases_since(previous: Option<&str>, current: &str) -> Vec<Release> {
+    releases()
+        .into_iter()
+        .filter(|release| compare_versions(release.version, current) != Ordering::Greater)
+        .filter(|release| match previous {
+            Some(previous) => compare_versions(release.version, previous) == Ordering::Greater,
+            None => release.version == current,
+        })
+        .collect()
+}
+
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['.', '-'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+    parts(a).cmp(&parts(b))
+}
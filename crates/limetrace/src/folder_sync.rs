@@ -0,0 +1,307 @@
+//! Server-less sync between machines through a shared folder (OneDrive, Dropbox,
+//! a NAS share).
+//!
+//! Each machine writes the segments it recorded as numbered batch files under
+//! `<folder>/<machine id>/` and imports the batches every other machine wrote.
+//! A batch is the payload JSON on the first line and its HMAC-SHA256 under the
+//! shared secret on the second, so a file altered or dropped in by someone without
+//! the secret is rejected. Imports skip segments that already exist locally, which
+//! lets both sides converge on the same merged history.
+
+use anyhow::{bail, Context, Result};
+use hmac::{Hmac, Mac};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::json;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use limetrace_core::import::{upsert_app_in_tx, upsert_title_in_tx, upsert_url_in_tx};
+
+const BATCH_MAX_SEGMENTS: usize = 5000;
+const BATCH_EXTENSION: &str = "batch";
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SyncStats {
+    pub exported: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+    pub rejected_batches: usize,
+    pub uploaded: usize,
+    pub downloaded: usize,
+}
+
+pub fn run(conn: &mut Connection, folder: &Path, machine_id: &str, secret: &str) -> Result<SyncStats> {
+    if secret.is_empty() {
+        bail!("sync secret is empty");
+    }
+    if machine_id.is_empty() || machine_id.contains(['/', '\\', '.']) {
+        bail!("invalid sync machine id: {machine_id:?}");
+    }
+    let mut stats = SyncStats::default();
+    export_batches(conn, folder, machine_id, secret, &mut stats)?;
+    import_batches(conn, folder, machine_id, secret, &mut stats)?;
+    Ok(stats)
+}
+
+fn read_state(conn: &Connection, key: &str) -> Result<Option<i64>> {
+    conn.query_row("SELECT value FROM sync_state WHERE key = ?1", params![key], |row| row.get(0))
+        .optional()
+        .with_context(|| format!("failed to read sync state {key}"))
+}
+
+fn export_batches(
+    conn: &mut Connection,
+    folder: &Path,
+    machine_id: &str,
+    secret: &str,
+    stats: &mut SyncStats,
+) -> Result<()> {
+    let machine_dir = folder.join(machine_id);
+    fs::create_dir_all(&machine_dir)
+        .with_context(|| format!("failed to create sync directory: {}", machine_dir.display()))?;
+    loop {
+        let exported_through = read_state(conn, "exported_through_id")?.unwrap_or(0);
+        let seq = read_state(conn, "next_seq")?.unwrap_or(1);
+        let mut last_id = exported_through;
+        let mut segments = Vec::new();
+        {
+            let mut stmt = conn
+                .prepare(
+                    "\
+                    SELECT s.id, s.start_ts, s.end_ts, s.is_idle, a.exe_name, a.process_path, t.title,
+                           s.deleted_ts IS NOT NULL OR o.segment_id IS NOT NULL,
+                           s.start_ms, s.end_ms, u.url, s.is_call, s.monitor_name
+                    FROM segments s
+                    LEFT JOIN apps a ON a.id = s.app_id
+                    LEFT JOIN titles t ON t.id = s.title_id
+                    LEFT JOIN urls u ON u.id = s.url_id
+                    LEFT JOIN sync_segment_origin o ON o.segment_id = s.id
+                    WHERE s.id > ?1
+                    ORDER BY s.id
+                    LIMIT ?2",
+                )
+                .context("failed to prepare sync export query")?;
+            let mut rows = stmt
+                .query(params![exported_through, BATCH_MAX_SEGMENTS as i64])
+                .context("failed to query segments for sync")?;
+            while let Some(row) = rows.next().context("failed to read sync export row")? {
+                last_id = row.get(0)?;
+                // Segments that came from another machine stay with their origin.
+                if row.get::<_, bool>(7)? {
+                    continue;
+                }
+                segments.push(json!({
+                    "start_ts": row.get::<_, i64>(1)?,
+                    "end_ts": row.get::<_, i64>(2)?,
+                    "is_idle": row.get::<_, i64>(3)? != 0,
+                    "app_name": row.get::<_, Option<String>>(4)?,
+                    "process_path": row.get::<_, Option<String>>(5)?,
+                    "title": row.get::<_, Option<String>>(6)?,
+                    "start_ms": row.get::<_, Option<i64>>(8)?,
+                    "end_ms": row.get::<_, Option<i64>>(9)?,
+                    "url": row.get::<_, Option<String>>(10)?,
+                    "is_call": row.get::<_, i64>(11)? != 0,
+                    "monitor": row.get::<_, Option<String>>(12)?,
+                }));
+            }
+        }
+        if last_id == exported_through {
+            return Ok(());
+        }
+        if !segments.is_empty() {
+            let payload = json!({ "machine": machine_id, "seq": seq, "segments": segments }).to_string();
+            let signature = to_hex(&batch_mac(secret, &payload).finalize().into_bytes());
+            let file_name = format!("{seq:08}.{BATCH_EXTENSION}");
+            let partial_path = machine_dir.join(format!("{file_name}.partial"));
+            fs::write(&partial_path, format!("{payload}\n{signature}\n"))
+                .with_context(|| format!("failed to write sync batch: {}", partial_path.display()))?;
+            fs::rename(&partial_path, machine_dir.join(&file_name))
+                .with_context(|| format!("failed to finalize sync batch: {file_name}"))?;
+            stats.exported += segments.len();
+        }
+        let tx = conn.transaction().context("failed to start sync state update")?;
+        tx.execute(
+            "INSERT OR REPLACE INTO sync_state (key, value) VALUES ('exported_through_id', ?1)",
+            params![last_id],
+        )
+        .context("failed to save sync export position")?;
+        let next_seq = if segments.is_empty() { seq } else { seq + 1 };
+        tx.execute(
+            "INSERT OR REPLACE INTO sync_state (key, value) VALUES ('next_seq', ?1)",
+            params![next_seq],
+        )
+        .context("failed to save sync batch sequence")?;
+        tx.commit().context("failed to commit sync state update")?;
+    }
+}
+
+fn import_batches(
+    conn: &mut Connection,
+    folder: &Path,
+    machine_id: &str,
+    secret: &str,
+    stats: &mut SyncStats,
+) -> Result<()> {
+    let entries = fs::read_dir(folder)
+        .with_context(|| format!("failed to list sync folder: {}", folder.display()))?;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let peer = entry.file_name().to_string_lossy().into_owned();
+        if peer == machine_id || !entry.path().is_dir() {
+            continue;
+        }
+        let last_seq: i64 = conn
+            .query_row("SELECT last_seq FROM sync_peers WHERE machine = ?1", params![peer], |row| row.get(0))
+            .optional()
+            .context("failed to read sync peer position")?
+            .unwrap_or(0);
+        let mut batches: Vec<(i64, std::path::PathBuf)> = fs::read_dir(entry.path())
+            .with_context(|| format!("failed to list sync peer folder: {}", entry.path().display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(BATCH_EXTENSION))
+            .filter_map(|path| {
+                let seq = path.file_stem()?.to_str()?.parse::<i64>().ok()?;
+                Some((seq, path))
+            })
+            .filter(|(seq, _)| *seq > last_seq)
+            .collect();
+        batches.sort();
+        for (seq, path) in batches {
+            match import_batch(conn, &path, &peer, seq, secret) {
+                Ok((imported, duplicates)) => {
+                    stats.imported += imported;
+                    stats.duplicates += duplicates;
+                }
+                Err(err) => {
+                    // Later batches wait until this one is fixed, so nothing is skipped silently.
+                    eprintln!("sync batch rejected: {}: {err:#}", path.display());
+                    stats.rejected_batches += 1;
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn import_batch(conn: &mut Connection, path: &Path, peer: &str, seq: i64, secret: &str) -> Result<(usize, usize)> {
+    let content = fs::read_to_string(path).context("failed to read batch")?;
+    let mut lines = content.lines();
+    let payload = lines.next().context("batch is empty")?;
+    let signature = lines.next().context("batch has no signature")?.trim();
+    // `verify_slice` compares in constant time.
+    if from_hex(signature).is_none_or(|signature| batch_mac(secret, payload).verify_slice(&signature).is_err()) {
+        bail!("signature mismatch");
+    }
+    let payload: serde_json::Value = serde_json::from_str(payload).context("batch payload is not JSON")?;
+    if payload.get("machine").and_then(serde_json::Value::as_str) != Some(peer)
+        || payload.get("seq").and_then(serde_json::Value::as_i64) != Some(seq)
+    {
+        bail!("batch header doesn't match its location");
+    }
+    let segments = payload
+        .get("segments")
+        .and_then(serde_json::Value::as_array)
+        .context("batch has no segments")?;
+
+    let tx = conn.transaction().context("failed to start sync import")?;
+    let mut app_cache = HashMap::new();
+    let mut title_cache = HashMap::new();
+    let mut url_cache = HashMap::new();
+    let (mut imported, mut duplicates) = (0, 0);
+    for segment in segments {
+        let (Some(start_ts), Some(end_ts)) = (
+            segment.get("start_ts").and_then(serde_json::Value::as_i64),
+            segment.get("end_ts").and_then(serde_json::Value::as_i64),
+        ) else {
+            continue;
+        };
+        if end_ts <= start_ts {
+            continue;
+        }
+        let flag = |key: &str| segment.get(key).and_then(serde_json::Value::as_bool).unwrap_or(false);
+        let is_idle = flag("is_idle");
+        let text = |key: &str| segment.get(key).and_then(serde_json::Value::as_str);
+        let millis = |key: &str| segment.get(key).and_then(serde_json::Value::as_i64);
+        let app_name = text("app_name").unwrap_or_default();
+        // Deleted rows count too, so a segment removed here doesn't come back.
+        let exists = tx
+            .query_row(
+                "\
+                SELECT 1 FROM segments s
+                LEFT JOIN apps a ON a.id = s.app_id
+                WHERE s.start_ts = ?1 AND s.end_ts = ?2 AND s.is_idle = ?3 AND COALESCE(a.exe_name, '') = ?4
+                LIMIT 1",
+                params![start_ts, end_ts, i64::from(is_idle), app_name],
+                |_| Ok(()),
+            )
+            .optional()
+            .context("failed to check for duplicate segment")?
+            .is_some();
+        if exists {
+            duplicates += 1;
+            continue;
+        }
+        let app_id = if app_name.is_empty() {
+            None
+        } else {
+            Some(upsert_app_in_tx(&tx, &mut app_cache, app_name, text("process_path").unwrap_or_default())?)
+        };
+        let title_id = text("title")
+            .map(|title| upsert_title_in_tx(&tx, &mut title_cache, title))
+            .transpose()?;
+        let url_id = text("url").map(|url| upsert_url_in_tx(&tx, &mut url_cache, url)).transpose()?;
+        tx.execute(
+            "\
+            INSERT INTO segments (start_ts, end_ts, app_id, title_id, is_idle, start_ms, end_ms, url_id, is_call, monitor_name)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                start_ts,
+                end_ts,
+                app_id,
+                title_id,
+                i64::from(is_idle),
+                millis("start_ms"),
+                millis("end_ms"),
+                url_id,
+                i64::from(flag("is_call")),
+                text("monitor"),
+            ],
+        )
+        .context("failed to insert synced segment")?;
+        tx.execute(
+            "INSERT INTO sync_segment_origin (segment_id, machine) VALUES (?1, ?2)",
+            params![tx.last_insert_rowid(), peer],
+        )
+        .context("failed to record synced segment origin")?;
+        imported += 1;
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO sync_peers (machine, last_seq) VALUES (?1, ?2)",
+        params![peer, seq],
+    )
+    .context("failed to save sync peer position")?;
+    tx.commit().context("failed to commit sync import")?;
+    Ok((imported, duplicates))
+}
+
+fn batch_mac(secret: &str, payload: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC takes keys of any length");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+        .collect()
+}
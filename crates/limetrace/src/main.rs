@@ -1,24 +1,59 @@
 ﻿#![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 
-use std::collections::{HashMap, HashSet};
+#[cfg(not(target_os = "windows"))]
+mod app_visuals;
+mod bidi;
+mod changelog;
+mod folder_sync;
+mod remote_storage;
+mod updater;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::env;
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Context, Result};
-use chrono::{Datelike, Days, Local, LocalResult, NaiveDate, NaiveDateTime, TimeZone};
-use csv::{ReaderBuilder, StringRecord};
+use chrono::{Datelike, Days, Local, NaiveDate, TimeZone, Weekday};
 use eframe::egui::{self, Align2, Color32, FontId, Pos2, Rect, Sense, Stroke};
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use rusqlite::{backup::Backup, params, Connection};
 use serde_json::json;
+
+use limetrace_core::apps::{
+    display_app_name, is_locked_session, is_system_level_app, normalize_app_key, should_hide_in_visualization,
+    AWAY_APP,
+};
+use limetrace_core::connection::{self, ConnectionPool, PooledConnection};
+use limetrace_core::crash::{self, CrashReport};
+use limetrace_core::demo;
+use limetrace_core::import::{import_csv_with_columns, preview_csv, CsvPreview, ImportColumns, ImportField, ImportStats};
+use limetrace_core::segments::{
+    count_segments_in_range, earliest_overlapping_start, for_each_segment_in_range, load_segments_for_range, reclassify_as_idle,
+    Segment,
+};
+use limetrace_core::summary::{
+    apply_previous_period_durations, build_summary_rows, should_hide_summary_app, sort_summary_rows,
+    summary_change_ratio, SummaryAccumulator, SummaryRow, SummarySort,
+};
+use limetrace_core::time::{date_range_bounds, local_midnight_ts};
+use limetrace_core::timeline::{
+    build_timeline_segments, clamp_view_span, coalesce_away, sanitize_view_range, TimelineRenderSegment, MIN_TIMELINE_VIEW_SECS,
+};
+use limetrace_core::title_regex::TitleRegex;
+use limetrace_core::update;
 
 #[cfg(target_os = "windows")]
 use std::ffi::c_void;
 #[cfg(target_os = "windows")]
-use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+use windows_sys::Win32::Foundation::{
+    CloseHandle, GetLastError, ERROR_PIPE_CONNECTED, FILETIME, HANDLE, INVALID_HANDLE_VALUE,
+};
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::Graphics::Gdi::{
     CreateCompatibleDC, DeleteDC, DeleteObject, GetDIBits, GetObjectW, BITMAP, BITMAPINFO,
@@ -26,32 +61,123 @@ use windows_sys::Win32::Graphics::Gdi::{
 };
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::Storage::FileSystem::{
-    GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
+    GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, PIPE_ACCESS_INBOUND,
+};
+#[cfg(target_os = "windows")]
+use windows_sys::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
 };
 #[cfg(target_os = "windows")]
-use windows_sys::Win32::System::Threading::OpenMutexW;
+use windows_sys::Win32::System::Threading::{
+    CreateMutexW, GetProcessTimes, OpenMutexW, OpenProcess, QueryFullProcessImageNameW, TerminateProcess,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
+};
 #[cfg(target_os = "windows")]
 use windows_sys::Win32::UI::Shell::ExtractIconExW;
 #[cfg(target_os = "windows")]
-use windows_sys::Win32::UI::WindowsAndMessaging::{DestroyIcon, GetIconInfo, HICON, ICONINFO};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    AllowSetForegroundWindow, DestroyIcon, GetIconInfo, ASFW_ANY, HICON, ICONINFO,
+};
 
-#[derive(Debug, Clone)]
-struct Segment {
-    start_ts: i64,
-    end_ts: i64,
-    is_idle: bool,
-    app_name: String,
-    process_path: Option<String>,
-    title: Option<String>,
+#[derive(Clone, Copy)]
+struct SummaryRowLayout {
+    name_rect: Rect,
+    bar_rect: Rect,
+    session_columns: Option<(Rect, Rect)>,
+    duration_rect: Rect,
+    rtl: bool,
 }
 
-#[derive(Debug, Clone)]
-struct SummaryRow {
-    app_name: String,
-    display_name: String,
-    duration_secs: i64,
-    process_path: Option<String>,
-    is_idle: bool,
+impl SummaryRowLayout {
+    fn compute(content_rect: Rect, show_session_columns: bool, rtl: bool) -> Self {
+        let gap = 8.0;
+        let row_width = content_rect.width().max(1.0);
+        let duration_width = 88.0;
+        let session_count_width = 56.0;
+        let mut fixed_right = duration_width + gap * 2.0;
+        if show_session_columns {
+            fixed_right += session_count_width + duration_width + gap * 2.0;
+        }
+        let available_left = (row_width - fixed_right).max(40.0);
+        let mut name_width = (available_left * 0.50).clamp(90.0, 620.0);
+        let mut bar_width = (available_left - name_width).max(52.0);
+        if name_width + bar_width > available_left {
+            bar_width = (available_left - name_width).max(40.0);
+            name_width = (available_left - bar_width).max(70.0);
+        }
+
+        let mut x = content_rect.left();
+        let y = content_rect.top();
+        let h = content_rect.height();
+        let name_rect = Rect::from_min_size(Pos2::new(x, y), egui::vec2(name_width, h));
+        x += name_width + gap;
+        let bar_rect = Rect::from_min_size(Pos2::new(x, y), egui::vec2(bar_width, h));
+        x += bar_width + gap;
+        let session_columns = if show_session_columns {
+            let count_rect =
+                Rect::from_min_size(Pos2::new(x, y), egui::vec2(session_count_width, h));
+            x += session_count_width + gap;
+            let average_rect = Rect::from_min_size(Pos2::new(x, y), egui::vec2(duration_width, h));
+            x += duration_width + gap;
+            Some((count_rect, average_rect))
+        } else {
+            None
+        };
+        let duration_rect = Rect::from_min_size(Pos2::new(x, y), egui::vec2(duration_width, h));
+
+        let mut layout = Self {
+            name_rect,
+            bar_rect,
+            session_columns,
+            duration_rect,
+            rtl,
+        };
+        if rtl {
+            let mirror = |rect: Rect| {
+                let axis = content_rect.left() + content_rect.right();
+                Rect::from_x_y_ranges((axis - rect.right())..=(axis - rect.left()), rect.y_range())
+            };
+            layout.name_rect = mirror(layout.name_rect);
+            layout.bar_rect = mirror(layout.bar_rect);
+            layout.session_columns = layout.session_columns.map(|(count, average)| (mirror(count), mirror(average)));
+            layout.duration_rect = mirror(layout.duration_rect);
+        }
+        layout
+    }
+
+    /// Anchor for text that starts at the reading edge of `rect`.
+    fn leading(&self, rect: Rect, inset: f32) -> (Pos2, Align2) {
+        if self.rtl {
+            (Pos2::new(rect.right() - inset, rect.center().y), Align2::RIGHT_CENTER)
+        } else {
+            (Pos2::new(rect.left() + inset, rect.center().y), Align2::LEFT_CENTER)
+        }
+    }
+
+    /// Anchor for numbers, which line up on the far edge of their column.
+    fn trailing(&self, rect: Rect) -> (Pos2, Align2) {
+        if self.rtl {
+            (rect.left_center(), Align2::LEFT_CENTER)
+        } else {
+            (rect.right_center(), Align2::RIGHT_CENTER)
+        }
+    }
+
+    /// The first `width` points of `rect` in reading order, e.g. a bar's filled part.
+    fn leading_part(&self, rect: Rect, width: f32) -> Rect {
+        if self.rtl {
+            Rect::from_min_max(Pos2::new(rect.right() - width, rect.top()), rect.max)
+        } else {
+            Rect::from_min_max(rect.min, Pos2::new(rect.left() + width, rect.bottom()))
+        }
+    }
+}
+
+fn summary_row_content_rect(rect: Rect) -> Rect {
+    let mut content_rect = rect.shrink2(egui::vec2(6.0, 3.0));
+    content_rect.max.x = (content_rect.max.x - SCROLLBAR_SAFE_GUTTER)
+        .max(content_rect.min.x + 1.0);
+    content_rect
 }
 
 #[derive(Debug, Clone)]
@@ -62,79 +188,19 @@ struct ExportSegmentRow {
     app_name: String,
     process_path: Option<String>,
     title: Option<String>,
-}
-
-#[derive(Debug, Clone, Default)]
-struct ImportStats {
-    total_rows: usize,
-    imported_rows: usize,
-    skipped_rows: usize,
-}
-
-#[derive(Debug, Clone)]
-struct ImportCsvColumns {
-    title: Option<usize>,
-    start_local: Option<usize>,
-    end_local: Option<usize>,
-    duration: Option<usize>,
-    process: Option<usize>,
-    start_ts: Option<usize>,
-    end_ts: Option<usize>,
-    is_idle: Option<usize>,
-    app_name: Option<usize>,
-    process_path: Option<usize>,
-}
-
-impl ImportCsvColumns {
-    fn from_headers(headers: &StringRecord) -> Result<Self> {
-        let columns = Self {
-            title: find_csv_header_index(headers, &["title", "name"]),
-            start_local: find_csv_header_index(headers, &["start", "startlocal"]),
-            end_local: find_csv_header_index(headers, &["end", "endlocal"]),
-            duration: find_csv_header_index(headers, &["duration", "durationsecs"]),
-            process: find_csv_header_index(headers, &["process"]),
-            start_ts: find_csv_header_index(headers, &["startts", "start_ts"]),
-            end_ts: find_csv_header_index(headers, &["endts", "end_ts"]),
-            is_idle: find_csv_header_index(headers, &["isidle", "is_idle"]),
-            app_name: find_csv_header_index(headers, &["appname", "app_name"]),
-            process_path: find_csv_header_index(headers, &["processpath", "process_path"]),
-        };
-
-        let has_time_columns = (columns.start_ts.is_some() && columns.end_ts.is_some())
-            || (columns.start_local.is_some() && columns.end_local.is_some());
-        if !has_time_columns {
-            bail!(
-                "CSV missing required time columns. Need Start/End or start_ts/end_ts."
-            );
-        }
-
-        if columns.process.is_none() && columns.app_name.is_none() {
-            bail!("CSV missing required app column. Need Process or app_name.");
-        }
-
-        Ok(columns)
-    }
+    category: Option<AppCategory>,
+    // The configured git repository an editor title mentions.
+    project: Option<String>,
+    url: Option<String>,
+    is_call: bool,
 }
 
 #[derive(Debug, Clone)]
-struct ParsedImportRow {
-    start_ts: i64,
-    end_ts: i64,
-    is_idle: bool,
-    app_name: String,
-    process_path: String,
-    title: Option<String>,
-}
-
-#[derive(Debug, Clone)]
-struct TimelineRenderSegment {
-    start_ts: i64,
-    end_ts: i64,
-    is_idle: bool,
-    app_name: String,
-    process_path: Option<String>,
-    title: Option<String>,
-    multi_title: bool,
+struct ExportDaySummary {
+    date: NaiveDate,
+    active_secs: i64,
+    apps: Vec<(String, i64)>,
+    note: Option<String>,
 }
 
 enum IconState {
@@ -164,12 +230,29 @@ struct ReloadRequest {
     db_path: PathBuf,
     range_start: i64,
     range_end: i64,
+    timeline_needed: bool,
+    categories: Option<CategorySnapshot>,
+    min_segment_secs: i64,
+    // Merged spans of the tag being filtered on; segments are clipped to them.
+    tag_spans: Option<Vec<(i64, i64)>>,
+}
+
+#[derive(Clone)]
+struct CategorySnapshot {
+    app_categories: HashMap<String, AppCategory>,
+    title_rules: Vec<TitleCategoryRule>,
 }
 
 struct ReloadPayload {
     segments: Vec<Segment>,
     summary_rows: Vec<SummaryRow>,
     summary_total_secs: i64,
+    fleeting_secs: i64,
+    call_secs: i64,
+    away_secs: i64,
+    // Set when the range was too large to materialize; `segments` is then empty.
+    aggregated: bool,
+    category_totals: Option<Vec<(Option<AppCategory>, i64)>>,
 }
 
 struct ReloadResult {
@@ -177,6 +260,173 @@ struct ReloadResult {
     payload: Result<ReloadPayload, String>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RolloverKind {
+    Day,
+    Week,
+    Month,
+}
+
+impl RolloverKind {
+    fn ui_label(self, language: UiLanguage) -> &'static str {
+        match language {
+            UiLanguage::ZhCn => match self {
+                Self::Day => "\u{6628}\u{65E5}\u{603B}\u{7ED3}",
+                Self::Week => "\u{4E0A}\u{5468}\u{603B}\u{7ED3}",
+                Self::Month => "\u{4E0A}\u{6708}\u{603B}\u{7ED3}",
+            },
+            UiLanguage::EnUs => match self {
+                Self::Day => "Yesterday's summary",
+                Self::Week => "Last week's summary",
+                Self::Month => "Last month's summary",
+            },
+            UiLanguage::ArSa => match self {
+                Self::Day => bidi::display_static("\u{645}\u{644}\u{62E}\u{635} \u{627}\u{644}\u{623}\u{645}\u{633}"),
+                Self::Week => bidi::display_static("\u{645}\u{644}\u{62E}\u{635} \u{627}\u{644}\u{623}\u{633}\u{628}\u{648}\u{639} \u{627}\u{644}\u{645}\u{627}\u{636}\u{64A}"),
+                Self::Month => bidi::display_static("\u{645}\u{644}\u{62E}\u{635} \u{627}\u{644}\u{634}\u{647}\u{631} \u{627}\u{644}\u{645}\u{627}\u{636}\u{64A}"),
+            },
+        }
+    }
+}
+
+struct RolloverSummary {
+    kind: RolloverKind,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    total_secs: i64,
+    previous_total_secs: Option<i64>,
+    top_app: Option<SummaryRow>,
+    // Average workday start and end, as seconds after local midnight.
+    workday_average: Option<(i64, i64)>,
+}
+
+struct OvertimeReport {
+    month: NaiveDate,
+    rows: Vec<OvertimeRow>,
+}
+
+struct OvertimeRow {
+    date: NaiveDate,
+    worked_secs: i64,
+    target_secs: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DayOffKind {
+    Holiday,
+    Vacation,
+}
+
+impl DayOffKind {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Holiday => "holiday",
+            Self::Vacation => "vacation",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        [Self::Holiday, Self::Vacation].into_iter().find(|kind| kind.code() == code)
+    }
+
+    fn label_key(self) -> &'static str {
+        match self {
+            Self::Holiday => "day_off_holiday",
+            Self::Vacation => "day_off_vacation",
+        }
+    }
+
+    fn shade(self) -> Color32 {
+        match self {
+            Self::Holiday => Color32::from_rgba_unmultiplied(230, 150, 60, 70),
+            Self::Vacation => Color32::from_rgba_unmultiplied(60, 170, 160, 70),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct DayOff {
+    kind: DayOffKind,
+    label: String,
+}
+
+const HOLIDAY_PRESETS: [&str; 3] = ["US", "DE", "GB"];
+
+const DEFAULT_CONTRACT_HOURS: [f64; 7] = [8.0, 8.0, 8.0, 8.0, 8.0, 0.0, 0.0];
+
+#[derive(Debug, Clone, Copy)]
+struct WorkdaySpan {
+    date: NaiveDate,
+    start_ts: i64,
+    end_ts: i64,
+}
+
+struct CompareView {
+    selected_date: NaiveDate,
+    range_preset: Option<RangePreset>,
+    reload_request_tx: mpsc::Sender<ReloadRequest>,
+    reload_result_rx: mpsc::Receiver<ReloadResult>,
+    next_request_id: u64,
+    pending_request_id: Option<u64>,
+    summary_rows: Vec<SummaryRow>,
+    summary_total_secs: i64,
+    min_segment_secs: i64,
+    error: Option<String>,
+}
+
+impl CompareView {
+    fn active_range_bounds(&self) -> Option<(i64, i64)> {
+        if let Some(preset) = self.range_preset {
+            return range_bounds_for_preset(self.selected_date, preset);
+        }
+        let end_exclusive = self.selected_date.checked_add_days(Days::new(1))?;
+        date_range_bounds(self.selected_date, end_exclusive)
+    }
+
+    fn reload(&mut self, db_path: &Path) {
+        let Some((range_start, range_end)) = self.active_range_bounds() else {
+            self.error = Some("failed to resolve active range".to_owned());
+            return;
+        };
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+        self.pending_request_id = Some(self.next_request_id);
+        if self
+            .reload_request_tx
+            .send(ReloadRequest {
+                request_id: self.next_request_id,
+                db_path: db_path.to_path_buf(),
+                range_start,
+                range_end,
+                timeline_needed: false,
+                categories: None,
+                min_segment_secs: self.min_segment_secs,
+                tag_spans: None,
+            })
+            .is_err()
+        {
+            self.pending_request_id = None;
+            self.error = Some("reload worker unavailable".to_owned());
+        }
+    }
+
+    fn drain_results(&mut self) {
+        while let Ok(result) = self.reload_result_rx.try_recv() {
+            if Some(result.request_id) != self.pending_request_id {
+                continue;
+            }
+            self.pending_request_id = None;
+            match result.payload {
+                Ok(payload) => {
+                    self.summary_rows = payload.summary_rows;
+                    self.summary_total_secs = payload.summary_total_secs;
+                    self.error = None;
+                }
+                Err(err) => self.error = Some(err),
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 enum BackendStatusWorkerRequest {
     ProbeNow,
@@ -194,6 +444,33 @@ enum RangePreset {
 }
 
 impl RangePreset {
+    const ALL: [Self; 7] = [
+        Self::All,
+        Self::Day7,
+        Self::Day30,
+        Self::ThisWeek,
+        Self::ThisMonth,
+        Self::ThisQuarter,
+        Self::YearToDate,
+    ];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Day7 => "7d",
+            Self::Day30 => "30d",
+            Self::ThisWeek => "this-week",
+            Self::ThisMonth => "this-month",
+            Self::ThisQuarter => "this-quarter",
+            Self::YearToDate => "ytd",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim().to_ascii_lowercase();
+        Self::ALL.into_iter().find(|preset| preset.code() == code)
+    }
+
     fn short_label(self) -> &'static str {
         match self {
             Self::All => "ALL",
@@ -226,8 +503,62 @@ impl RangePreset {
                 Self::ThisQuarter => "This Quarter",
                 Self::YearToDate => "YTD",
             },
+            UiLanguage::ArSa => match self {
+                Self::All => bidi::display_static("\u{627}\u{644}\u{643}\u{644}"),
+                Self::Day7 => bidi::display_static("\u{622}\u{62E}\u{631} 7 \u{623}\u{64A}\u{627}\u{645}"),
+                Self::Day30 => bidi::display_static("\u{622}\u{62E}\u{631} 30 \u{64A}\u{648}\u{645}\u{64B}\u{627}"),
+                Self::ThisWeek => bidi::display_static("\u{647}\u{630}\u{627} \u{627}\u{644}\u{623}\u{633}\u{628}\u{648}\u{639}"),
+                Self::ThisMonth => bidi::display_static("\u{647}\u{630}\u{627} \u{627}\u{644}\u{634}\u{647}\u{631}"),
+                Self::ThisQuarter => bidi::display_static("\u{647}\u{630}\u{627} \u{627}\u{644}\u{631}\u{628}\u{639}"),
+                Self::YearToDate => bidi::display_static("\u{645}\u{646}\u{630} \u{628}\u{62F}\u{627}\u{64A}\u{629} \u{627}\u{644}\u{633}\u{646}\u{629}"),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InitialView {
+    Date(NaiveDate),
+    Preset(RangePreset),
+    Custom(NaiveDate, NaiveDate),
+}
+
+impl InitialView {
+    // Absolute, so a running viewer reads it the same as the launch that parsed it.
+    fn cli_value(self) -> String {
+        match self {
+            Self::Date(date) => date.format("%Y-%m-%d").to_string(),
+            Self::Preset(preset) => preset.code().to_owned(),
+            Self::Custom(start, end) => format!("{}..{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d")),
         }
     }
+
+    #[cfg(target_os = "windows")]
+    fn parse_cli_value(value: &str) -> Option<Self> {
+        parse_cli_date(value)
+            .map(Self::Date)
+            .or_else(|_| parse_cli_range(value))
+            .ok()
+    }
+}
+
+struct LaunchOptions {
+    db_path: PathBuf,
+    initial_view: Option<InitialView>,
+    safe_mode: bool,
+    query_mode: bool,
+    generate_demo: bool,
+    demo_days: u32,
+    demo_seed: u64,
+}
+
+// Safe mode skips settings, the icon cache and custom fonts, so a corrupt file among
+// them cannot keep the viewer from opening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SafeMode {
+    Off,
+    Requested,
+    AfterStartupCrashes,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -240,6 +571,7 @@ enum CustomRangeFocus {
 enum UiLanguage {
     ZhCn,
     EnUs,
+    ArSa,
 }
 
 impl UiLanguage {
@@ -247,6 +579,7 @@ impl UiLanguage {
         match self {
             Self::ZhCn => "zh-CN",
             Self::EnUs => "en-US",
+            Self::ArSa => "ar-SA",
         }
     }
 
@@ -254,6 +587,7 @@ impl UiLanguage {
         match self {
             Self::ZhCn => "\u{4E2D}\u{6587}",
             Self::EnUs => "EN",
+            Self::ArSa => bidi::display_static("\u{639}\u{631}\u{628}\u{64A}"),
         }
     }
 
@@ -262,75 +596,652 @@ impl UiLanguage {
         match normalized.as_str() {
             "zh" | "zh-cn" | "zh_hans" | "zh-hans" => Some(Self::ZhCn),
             "en" | "en-us" => Some(Self::EnUs),
+            "ar" | "ar-sa" => Some(Self::ArSa),
             _ => None,
         }
+    }
+
+    fn is_rtl(self) -> bool {
+        self == Self::ArSa
+    }
+
+    /// Toolbar rows start on the reading edge.
+    fn row_layout(self) -> egui::Layout {
+        if self.is_rtl() {
+            egui::Layout::right_to_left(egui::Align::Center)
+        } else {
+            egui::Layout::left_to_right(egui::Align::Center)
+        }
+    }
+
+    /// The settings group packed against the far edge of a toolbar row.
+    fn trailing_row_layout(self) -> egui::Layout {
+        if self.is_rtl() {
+            egui::Layout::left_to_right(egui::Align::Center)
+        } else {
+            egui::Layout::right_to_left(egui::Align::Center)
+        }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum ExportFormat {
-    Csv,
-    Json,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum BackendHealth {
-    Running,
-    Stopped,
+enum ThemeChoice {
+    Light,
+    Dark,
+    System,
 }
-
-#[derive(Debug, Clone)]
-struct BackendStatus {
-    health: BackendHealth,
-    last_write_ts: Option<i64>,
-    checked_ts: i64,
-    detail: Option<String>,
-}
-
-impl BackendStatus {
-    fn short_label_lang(&self, language: UiLanguage) -> &'static str {
-        match (language, self.health) {
-            (UiLanguage::ZhCn, BackendHealth::Running) => "\u{670D}\u{52A1}\u{8FD0}\u{884C}\u{4E2D}",
-            (UiLanguage::ZhCn, BackendHealth::Stopped) => "\u{670D}\u{52A1}\u{672A}\u{8FD0}\u{884C}",
-            (UiLanguage::EnUs, BackendHealth::Running) => "Tracking Active",
-            (UiLanguage::EnUs, BackendHealth::Stopped) => "Service Not Running",
+
+impl ThemeChoice {
+    const ALL: [Self; 3] = [Self::Light, Self::Dark, Self::System];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::System => "system",
         }
     }
 
-    fn color(&self) -> Color32 {
-        match self.health {
-            BackendHealth::Running => Color32::from_rgb(28, 136, 64),
-            BackendHealth::Stopped => Color32::from_rgb(190, 56, 56),
+    fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|theme| theme.code() == code)
+    }
+
+    fn preference(self) -> egui::ThemePreference {
+        match self {
+            Self::Light => egui::ThemePreference::Light,
+            Self::Dark => egui::ThemePreference::Dark,
+            Self::System => egui::ThemePreference::System,
+        }
+    }
+
+    fn ui_label(self, language: UiLanguage) -> &'static str {
+        match language {
+            UiLanguage::ZhCn => match self {
+                Self::Light => "\u{6D45}\u{8272}",
+                Self::Dark => "\u{6DF1}\u{8272}",
+                Self::System => "\u{8DDF}\u{968F}\u{7CFB}\u{7EDF}",
+            },
+            UiLanguage::EnUs => match self {
+                Self::Light => "Light",
+                Self::Dark => "Dark",
+                Self::System => "System",
+            },
+            UiLanguage::ArSa => match self {
+                Self::Light => bidi::display_static("\u{641}\u{627}\u{62A}\u{62D}"),
+                Self::Dark => bidi::display_static("\u{62F}\u{627}\u{643}\u{646}"),
+                Self::System => bidi::display_static("\u{62D}\u{633}\u{628} \u{627}\u{644}\u{646}\u{638}\u{627}\u{645}"),
+            },
         }
     }
 }
-
-struct TimelineApp {
-    db_path: PathBuf,
-    selected_date: NaiveDate,
-    calendar_month: NaiveDate,
-    range_preset: Option<RangePreset>,
-    custom_range: Option<(NaiveDate, NaiveDate)>,
-    custom_range_focus: CustomRangeFocus,
-    custom_start_input: String,
-    custom_end_input: String,
-    summary_limit: Option<usize>,
-    summary_limit_custom_input: String,
-    timeline_view_range: Option<(i64, i64)>,
-    segments: Vec<Segment>,
-    summary_rows: Vec<SummaryRow>,
-    summary_total_secs: i64,
-    selected_app_keys: HashSet<String>,
-    icon_cache: HashMap<String, IconState>,
-    icon_color_cache: HashMap<String, Color32>,
-    cached_app_visuals: HashMap<String, CachedAppVisual>,
-    process_display_name_cache: HashMap<String, String>,
-    app_color_cache: HashMap<String, Color32>,
-    icon_request_tx: mpsc::Sender<String>,
-    icon_result_rx: mpsc::Receiver<IconLoadResult>,
-    reload_request_tx: mpsc::Sender<ReloadRequest>,
-    reload_result_rx: mpsc::Receiver<ReloadResult>,
-    backend_status_request_tx: mpsc::Sender<BackendStatusWorkerRequest>,
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DurationFormat {
+    HoursMinutesSeconds,
+    DecimalHours,
+    HoursMinutes,
+    Clock,
+}
+
+impl DurationFormat {
+    const ALL: [Self; 4] = [
+        Self::HoursMinutesSeconds,
+        Self::DecimalHours,
+        Self::HoursMinutes,
+        Self::Clock,
+    ];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::HoursMinutesSeconds => "hms",
+            Self::DecimalHours => "decimal",
+            Self::HoursMinutes => "hm",
+            Self::Clock => "clock",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.code() == code)
+    }
+
+    fn example_label(self) -> &'static str {
+        match self {
+            Self::HoursMinutesSeconds => "07:31:05",
+            Self::DecimalHours => "7.52 h",
+            Self::HoursMinutes => "7h 31m",
+            Self::Clock => "07:31",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClockFormat {
+    H24,
+    H12,
+}
+
+impl ClockFormat {
+    fn code(self) -> &'static str {
+        match self {
+            Self::H24 => "24h",
+            Self::H12 => "12h",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "24h" => Some(Self::H24),
+            "12h" => Some(Self::H12),
+            _ => None,
+        }
+    }
+
+    fn time_pattern(self) -> &'static str {
+        match self {
+            Self::H24 => "%H:%M:%S",
+            Self::H12 => "%I:%M:%S %p",
+        }
+    }
+
+    fn minute_pattern(self) -> &'static str {
+        match self {
+            Self::H24 => "%H:%M",
+            Self::H12 => "%I:%M %p",
+        }
+    }
+
+    fn hour_pattern(self) -> &'static str {
+        match self {
+            Self::H24 => "%H",
+            Self::H12 => "%-I%p",
+        }
+    }
+
+    fn ui_label(self, language: UiLanguage) -> &'static str {
+        match (language, self) {
+            (UiLanguage::ZhCn, Self::H24) => "24 \u{5C0F}\u{65F6}\u{5236}",
+            (UiLanguage::ZhCn, Self::H12) => "12 \u{5C0F}\u{65F6}\u{5236}",
+            (UiLanguage::EnUs, Self::H24) => "24-hour",
+            (UiLanguage::EnUs, Self::H12) => "12-hour (AM/PM)",
+            (UiLanguage::ArSa, Self::H24) => bidi::display_static("24 \u{633}\u{627}\u{639}\u{629}"),
+            (UiLanguage::ArSa, Self::H12) => bidi::display_static("12 \u{633}\u{627}\u{639}\u{629} (\u{635}/\u{645})"),
+        }
+    }
+}
+
+fn summary_sort_label(sort: SummarySort, language: UiLanguage) -> &'static str {
+    match language {
+        UiLanguage::ZhCn => match sort {
+            SummarySort::DurationDesc => "\u{65F6}\u{957F}\u{FF08}\u{9AD8}\u{5230}\u{4F4E}\u{FF09}",
+            SummarySort::DurationAsc => "\u{65F6}\u{957F}\u{FF08}\u{4F4E}\u{5230}\u{9AD8}\u{FF09}",
+            SummarySort::Name => "\u{540D}\u{79F0}\u{FF08}A-Z\u{FF09}",
+            SummarySort::LastUsed => "\u{6700}\u{8FD1}\u{4F7F}\u{7528}",
+            SummarySort::ChangeVsPrevious => "\u{8F83}\u{4E0A}\u{671F}\u{53D8}\u{5316}",
+        },
+        UiLanguage::EnUs => match sort {
+            SummarySort::DurationDesc => "Duration (High-Low)",
+            SummarySort::DurationAsc => "Duration (Low-High)",
+            SummarySort::Name => "Name (A-Z)",
+            SummarySort::LastUsed => "Last Used",
+            SummarySort::ChangeVsPrevious => "Change vs Previous",
+        },
+        UiLanguage::ArSa => match sort {
+            SummarySort::DurationDesc => bidi::display_static("\u{627}\u{644}\u{645}\u{62F}\u{629} (\u{645}\u{646} \u{627}\u{644}\u{623}\u{639}\u{644}\u{649})"),
+            SummarySort::DurationAsc => bidi::display_static("\u{627}\u{644}\u{645}\u{62F}\u{629} (\u{645}\u{646} \u{627}\u{644}\u{623}\u{62F}\u{646}\u{649})"),
+            SummarySort::Name => bidi::display_static("\u{627}\u{644}\u{627}\u{633}\u{645} (A-Z)"),
+            SummarySort::LastUsed => bidi::display_static("\u{622}\u{62E}\u{631} \u{627}\u{633}\u{62A}\u{62E}\u{62F}\u{627}\u{645}"),
+            SummarySort::ChangeVsPrevious => bidi::display_static("\u{627}\u{644}\u{62A}\u{63A}\u{64A}\u{631} \u{639}\u{646} \u{627}\u{644}\u{641}\u{62A}\u{631}\u{629} \u{627}\u{644}\u{633}\u{627}\u{628}\u{642}\u{629}"),
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+    Html,
+}
+
+/// What the per-day tables of the Markdown and HTML reports add up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportGroupBy {
+    App,
+    Category,
+    Project,
+}
+
+impl ExportGroupBy {
+    const ALL: [Self; 3] = [Self::App, Self::Category, Self::Project];
+
+    fn label_key(self) -> &'static str {
+        match self {
+            Self::App => "app",
+            Self::Category => "category",
+            Self::Project => "project",
+        }
+    }
+
+    // Reports are written in English whatever the UI language.
+    fn column_header(self) -> &'static str {
+        match self {
+            Self::App => "App",
+            Self::Category => "Category",
+            Self::Project => "Project",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum AppCategory {
+    Work,
+    Neutral,
+    Distracting,
+}
+
+impl AppCategory {
+    const ALL: [Self; 3] = [Self::Work, Self::Neutral, Self::Distracting];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::Work => "work",
+            Self::Neutral => "neutral",
+            Self::Distracting => "distracting",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|category| category.code() == code)
+    }
+
+    fn color(self) -> Color32 {
+        match self {
+            Self::Work => Color32::from_rgb(92, 168, 96),
+            Self::Neutral => Color32::from_rgb(110, 140, 190),
+            Self::Distracting => Color32::from_rgb(214, 96, 80),
+        }
+    }
+
+    fn ui_label(self, language: UiLanguage) -> &'static str {
+        match language {
+            UiLanguage::ZhCn => match self {
+                Self::Work => "\u{5DE5}\u{4F5C}",
+                Self::Neutral => "\u{4E2D}\u{6027}",
+                Self::Distracting => "\u{5206}\u{5FC3}",
+            },
+            UiLanguage::EnUs => match self {
+                Self::Work => "Work",
+                Self::Neutral => "Neutral",
+                Self::Distracting => "Distracting",
+            },
+            UiLanguage::ArSa => match self {
+                Self::Work => bidi::display_static("\u{639}\u{645}\u{644}"),
+                Self::Neutral => bidi::display_static("\u{645}\u{62D}\u{627}\u{64A}\u{62F}"),
+                Self::Distracting => bidi::display_static("\u{645}\u{634}\u{62A}\u{62A}"),
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+struct TitleCategoryRule {
+    id: i64,
+    pattern: String,
+    category: AppCategory,
+}
+
+#[derive(Clone)]
+struct TitleTagRule {
+    id: i64,
+    name: String,
+    pattern: String,
+}
+
+const DASHBOARD_TOP_APPS_MAX_HEIGHT: f32 = 320.0;
+const MAX_NOISE_THRESHOLD_SECS: i64 = 600;
+const EXPORT_REPORT_APPS_PER_DAY: usize = 10;
+const AFK_CONFIRM_MIN_IDLE_SECS: i64 = 2 * 3600;
+// Returns noticed later than this are left alone; the user has moved on by then.
+const AFK_PROMPT_WINDOW_SECS: i64 = 30 * 60;
+const AFK_DEFAULT_RECLASSIFY_MINUTES: i64 = 10;
+const AFK_MAX_RECLASSIFY_MINUTES: i64 = 60;
+
+struct AfkPrompt {
+    idle_start: i64,
+    idle_end: i64,
+    last_app: String,
+    max_minutes: i64,
+    reclassify_minutes: i64,
+}
+const CATEGORY_DONUT_SIZE: f32 = 132.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DashboardWidget {
+    HourlyPattern,
+    Timeline,
+    Categories,
+    CategoryDonut,
+    Workday,
+    TopApps,
+}
+
+impl DashboardWidget {
+    const ALL: [Self; 6] = [
+        Self::HourlyPattern,
+        Self::Timeline,
+        Self::Categories,
+        Self::CategoryDonut,
+        Self::Workday,
+        Self::TopApps,
+    ];
+
+    fn code(self) -> &'static str {
+        match self {
+            Self::HourlyPattern => "hourly_pattern",
+            Self::Timeline => "timeline",
+            Self::Categories => "categories",
+            Self::CategoryDonut => "category_donut",
+            Self::Workday => "workday",
+            Self::TopApps => "top_apps",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|widget| widget.code() == code)
+    }
+
+    fn label_key(self) -> &'static str {
+        match self {
+            Self::HourlyPattern => "hourly_pattern",
+            Self::Timeline => "timeline",
+            Self::Categories => "category_summary",
+            Self::CategoryDonut => "category_donut",
+            Self::Workday => "workday",
+            Self::TopApps => "top_apps",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DashboardPanel {
+    widget: DashboardWidget,
+    visible: bool,
+}
+
+#[derive(Debug, Clone)]
+struct GitCommitMarker {
+    repo: String,
+    ts: i64,
+    summary: String,
+}
+
+struct GitCommitCache {
+    range: (i64, i64),
+    commits: Vec<GitCommitMarker>,
+}
+
+struct GitRepoReportRow {
+    repo: String,
+    coding_secs: i64,
+    commit_count: usize,
+}
+
+// Editors whose window titles usually carry the open folder or workspace name.
+const EDITOR_APP_KEYS: &[&str] = &[
+    "code",
+    "code - insiders",
+    "cursor",
+    "devenv",
+    "idea64",
+    "pycharm64",
+    "rider64",
+    "clion64",
+    "webstorm64",
+    "goland64",
+    "rustrover64",
+    "sublime_text",
+    "notepad++",
+    "zed",
+    "nvim-qt",
+];
+
+#[derive(Debug, Clone)]
+struct EventEntry {
+    id: i64,
+    ts: i64,
+    kind: String,
+    app_key: Option<String>,
+    message: String,
+    read: bool,
+}
+
+const EVENT_LIST_LIMIT: usize = 50;
+const ANOMALY_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const ANOMALY_BASELINE_DAYS: u64 = 28;
+const ANOMALY_MIN_BASELINE_DAYS: usize = 7;
+const ANOMALY_MIN_SECS: i64 = 30 * 60;
+const ANOMALY_RATIO: f64 = 2.0;
+
+const FORECAST_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const FORECAST_HISTORY_DAYS: u64 = 28;
+const FORECAST_MIN_HISTORY_DAYS: usize = 5;
+// Below this share of a typical day the pace is too noisy to extrapolate from.
+const FORECAST_MIN_DAY_SHARE: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy)]
+enum UsageAnomaly {
+    AboveNormal { category: AppCategory, ratio: f64 },
+    Missing { category: AppCategory },
+}
+
+impl UsageAnomaly {
+    // Stored in the notification's app_key column so each flag is raised once per day.
+    fn subject(self) -> String {
+        match self {
+            Self::AboveNormal { category, .. } => format!("category:{}:above", category.code()),
+            Self::Missing { category } => format!("category:{}:missing", category.code()),
+        }
+    }
+}
+
+struct TitleTagReport {
+    range: (i64, i64),
+    rows: Vec<(String, String, i64)>,
+    segment_tag_rows: Vec<(String, i64)>,
+}
+
+struct TrashEntry {
+    id: i64,
+    start_ts: i64,
+    end_ts: i64,
+    deleted_ts: i64,
+    is_idle: bool,
+    app_name: String,
+    title: Option<String>,
+}
+
+const TRASH_LIST_LIMIT: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BulkCriteria {
+    app_key: String,
+    title_pattern: String,
+    range: (i64, i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BulkAction {
+    Tag,
+    Category,
+    Delete,
+}
+
+struct BulkPreview {
+    criteria: BulkCriteria,
+    // (segment id, start_ts, end_ts) clipped to the range.
+    matches: Vec<(i64, i64, i64)>,
+}
+
+#[derive(Debug, Clone)]
+struct SegmentTag {
+    id: i64,
+    start_ts: i64,
+    end_ts: i64,
+    tag: String,
+}
+
+struct FocusSession {
+    id: i64,
+    started_ts: i64,
+    ends_ts: i64,
+}
+
+struct FocusReport {
+    started_ts: i64,
+    ended_ts: i64,
+    violations: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Clone)]
+struct JiraWorklogDraft {
+    issue_key: String,
+    started_ts: i64,
+    duration_secs: i64,
+    comment: String,
+    selected: bool,
+}
+
+type JiraSubmitResult = Vec<(String, Result<(), String>)>;
+
+const JIRA_MIN_WORKLOG_SECS: i64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportCollisionPolicy {
+    Overwrite,
+    AutoIncrement,
+}
+
+impl ExportCollisionPolicy {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Overwrite => "overwrite",
+            Self::AutoIncrement => "increment",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "overwrite" => Some(Self::Overwrite),
+            "increment" => Some(Self::AutoIncrement),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackendHealth {
+    Running,
+    Stopped,
+}
+
+#[derive(Debug, Clone)]
+struct BackendStatus {
+    health: BackendHealth,
+    last_write_ts: Option<i64>,
+    // From the backend's heartbeat row; older backends don't write one.
+    heartbeat_ts: Option<i64>,
+    paused: bool,
+    checked_ts: i64,
+    detail: Option<String>,
+    stop_reason: Option<BackendStopReason>,
+    live_segment: Option<TimelineRenderSegment>,
+}
+
+// Why a stopped backend isn't tracking, as far as the viewer can tell.
+#[derive(Debug, Clone)]
+enum BackendStopReason {
+    BinaryMissing(PathBuf),
+    // Holds the instance mutex but its heartbeat went stale. The pid and
+    // timestamp of that last heartbeat, when there is one.
+    Unresponsive { heartbeat: Option<(u32, i64)> },
+    DatabaseLocked,
+    Crashed(CrashReport),
+}
+
+impl BackendStopReason {
+    fn message_key(&self) -> &'static str {
+        match self {
+            Self::BinaryMissing(_) => "stopped_binary_missing",
+            Self::Unresponsive { .. } => "stopped_unresponsive",
+            Self::DatabaseLocked => "stopped_database_locked",
+            Self::Crashed(_) => "stopped_crashed",
+        }
+    }
+}
+
+impl BackendStatus {
+    fn short_label_lang(&self, language: UiLanguage) -> &'static str {
+        match (language, self.health) {
+            (UiLanguage::ZhCn, BackendHealth::Running) => "\u{670D}\u{52A1}\u{8FD0}\u{884C}\u{4E2D}",
+            (UiLanguage::ZhCn, BackendHealth::Stopped) => "\u{670D}\u{52A1}\u{672A}\u{8FD0}\u{884C}",
+            (UiLanguage::EnUs, BackendHealth::Running) => "Tracking Active",
+            (UiLanguage::EnUs, BackendHealth::Stopped) => "Service Not Running",
+            (UiLanguage::ArSa, BackendHealth::Running) => bidi::display_static("\u{627}\u{644}\u{62A}\u{62A}\u{628}\u{639} \u{646}\u{634}\u{637}"),
+            (UiLanguage::ArSa, BackendHealth::Stopped) => bidi::display_static("\u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{644}\u{627} \u{62A}\u{639}\u{645}\u{644}"),
+        }
+    }
+
+    fn color(&self) -> Color32 {
+        match self.health {
+            BackendHealth::Running => Color32::from_rgb(28, 136, 64),
+            BackendHealth::Stopped => Color32::from_rgb(190, 56, 56),
+        }
+    }
+}
+
+struct TimelineApp {
+    db_path: PathBuf,
+    connections: ConnectionPool,
+    selected_date: NaiveDate,
+    calendar_month: NaiveDate,
+    range_preset: Option<RangePreset>,
+    custom_range: Option<(NaiveDate, NaiveDate)>,
+    custom_range_focus: CustomRangeFocus,
+    custom_start_input: String,
+    custom_end_input: String,
+    jump_date_input: String,
+    summary_limit: Option<usize>,
+    summary_filter_input: String,
+    pinned_app_keys: HashSet<String>,
+    summary_other_expanded: bool,
+    summary_limit_custom_input: String,
+    summary_sort: SummarySort,
+    show_session_columns: bool,
+    show_title_split: bool,
+    timeline_view_range: Option<(i64, i64)>,
+    timeline_follow_now: bool,
+    segments: Vec<Segment>,
+    segments_aggregated: bool,
+    summary_rows: Vec<SummaryRow>,
+    summary_total_secs: i64,
+    summary_fleeting_secs: i64,
+    summary_call_secs: i64,
+    summary_away_secs: i64,
+    noise_threshold_secs: i64,
+    afk_confirm_enabled: bool,
+    afk_handled_idle_start: i64,
+    afk_prompt: Option<AfkPrompt>,
+    selected_app_keys: HashSet<String>,
+    icon_cache: HashMap<String, IconState>,
+    icon_color_cache: HashMap<String, Color32>,
+    cached_app_visuals: HashMap<String, CachedAppVisual>,
+    process_display_name_cache: HashMap<String, String>,
+    app_color_cache: HashMap<String, Color32>,
+    icon_request_tx: mpsc::Sender<String>,
+    icon_result_rx: mpsc::Receiver<IconLoadResult>,
+    reload_request_tx: mpsc::Sender<ReloadRequest>,
+    reload_result_rx: mpsc::Receiver<ReloadResult>,
+    backend_status_request_tx: mpsc::Sender<BackendStatusWorkerRequest>,
     backend_status_result_rx: mpsc::Receiver<BackendStatus>,
     next_reload_request_id: u64,
     pending_reload_request_id: Option<u64>,
@@ -339,14 +1250,164 @@ struct TimelineApp {
     save_dir_override: Option<PathBuf>,
     save_dir_input: String,
     ui_language: UiLanguage,
+    theme_choice: ThemeChoice,
+    duration_format: DurationFormat,
+    clock_format: ClockFormat,
+    window_size: Option<[f32; 2]>,
+    font_path: Option<PathBuf>,
+    font_path_input: String,
     settings_path: PathBuf,
+    last_saved_view_state: Option<serde_json::Value>,
+    view_state_checked_at: Instant,
     export_format: ExportFormat,
+    export_filename_template: String,
+    export_collision_policy: ExportCollisionPolicy,
+    export_exclude_idle: bool,
+    export_gzip: bool,
+    export_split_by_month: bool,
+    export_exclude_system: bool,
+    export_group_by: ExportGroupBy,
+    // Empty exports every category; `None` stands for uncategorized apps.
+    export_categories: HashSet<Option<AppCategory>>,
+    export_only_selected_apps: bool,
     import_file_input: String,
+    // Set when the file's headers didn't map to the required fields.
+    import_preview: Option<(PathBuf, CsvPreview)>,
+    import_timezone_input: String,
     show_import_window: bool,
     show_export_window: bool,
     show_backup_window: bool,
     last_auto_refresh: Instant,
     backend_status: BackendStatus,
+    compare_view: Option<CompareView>,
+    last_seen_date: NaiveDate,
+    rollover_tx: mpsc::Sender<Result<RolloverSummary, String>>,
+    rollover_rx: mpsc::Receiver<Result<RolloverSummary, String>>,
+    rollover_cards: Vec<RolloverSummary>,
+    sync_folder_provider: Option<&'static str>,
+    crash_report: Option<CrashReport>,
+    safe_mode: SafeMode,
+    // Removed once the viewer has run for a while, marking the startup as finished.
+    startup_marker: Option<(PathBuf, Instant)>,
+    // Requests from later launches, which hand off to this viewer and exit.
+    viewer_handoff_rx: Option<mpsc::Receiver<Option<InitialView>>>,
+    app_categories: HashMap<String, AppCategory>,
+    title_category_rules: Vec<TitleCategoryRule>,
+    category_totals: Option<Vec<(Option<AppCategory>, i64)>>,
+    show_category_rules_window: bool,
+    show_jira_window: bool,
+    events: Vec<EventEntry>,
+    anomaly_checked_at: Option<Instant>,
+    forecast_profile: Option<(NaiveDate, Option<[f64; 24]>)>,
+    forecast_refreshed_at: Option<Instant>,
+    forecast_secs: Option<i64>,
+    app_aliases: HashMap<String, String>,
+    excluded_app_keys: HashSet<String>,
+    event_alias_target: Option<String>,
+    event_alias_input: String,
+    git_repositories: Vec<PathBuf>,
+    show_git_markers: bool,
+    dashboard_layout: Vec<DashboardPanel>,
+    // `Some(None)` keeps only uncategorized apps.
+    category_filter: Option<Option<AppCategory>>,
+    show_git_window: bool,
+    new_git_repo_input: String,
+    git_commit_cache: Option<GitCommitCache>,
+    workday_cache: Option<((i64, i64), Vec<WorkdaySpan>)>,
+    show_overtime_window: bool,
+    // Indexed from Monday.
+    contract_hours: [f64; 7],
+    overtime_month: NaiveDate,
+    overtime_report: Option<OvertimeReport>,
+    days_off: HashMap<NaiveDate, DayOff>,
+    day_notes: HashMap<NaiveDate, String>,
+    day_note_draft: Option<(NaiveDate, String)>,
+    show_day_note_panel: bool,
+    day_off_date_input: String,
+    day_off_kind: DayOffKind,
+    day_off_label_input: String,
+    holiday_preset: String,
+    git_report: Option<((i64, i64), Vec<GitRepoReportRow>)>,
+    title_tag_rules: Vec<TitleTagRule>,
+    segment_tags: Vec<SegmentTag>,
+    tag_filter: Option<String>,
+    timeline_selection: Option<(i64, i64)>,
+    timeline_focus: Option<(i64, i64)>,
+    tag_target: Option<(i64, i64)>,
+    tag_input: String,
+    show_bulk_window: bool,
+    show_trash_window: bool,
+    show_changelog_window: bool,
+    changelog: Vec<changelog::Release>,
+    trash_entries: Option<Vec<TrashEntry>>,
+    bulk_app_input: String,
+    bulk_title_pattern: String,
+    bulk_action: BulkAction,
+    bulk_tag_input: String,
+    bulk_category: AppCategory,
+    bulk_preview: Option<BulkPreview>,
+    title_tag_report: Option<TitleTagReport>,
+    show_title_tags_window: bool,
+    new_tag_rule_name: String,
+    new_tag_rule_pattern: String,
+    jira_base_url: String,
+    jira_email: String,
+    jira_token: String,
+    jira_drafts: Vec<JiraWorklogDraft>,
+    jira_submit_rx: Option<mpsc::Receiver<JiraSubmitResult>>,
+    new_rule_pattern: String,
+    new_rule_category: AppCategory,
+    focus_session: Option<FocusSession>,
+    focus_report: Option<FocusReport>,
+    focus_minutes: u32,
+    focus_minimize: bool,
+    relocate_target_input: String,
+    snapshot_enabled: bool,
+    snapshot_dir_input: String,
+    snapshot_interval_hours: u64,
+    last_snapshot_ts: i64,
+    snapshot_in_progress: bool,
+    snapshot_checked_at: Instant,
+    snapshot_tx: mpsc::Sender<Result<PathBuf, String>>,
+    snapshot_rx: mpsc::Receiver<Result<PathBuf, String>>,
+    team_export_enabled: bool,
+    team_export_path_input: String,
+    team_export_days: u32,
+    last_team_export_ts: i64,
+    team_export_in_progress: bool,
+    team_export_checked_at: Instant,
+    team_export_tx: mpsc::Sender<Result<PathBuf, String>>,
+    team_export_rx: mpsc::Receiver<Result<PathBuf, String>>,
+    update_channel: Option<updater::UpdateChannel>,
+    last_update_check_ts: i64,
+    update_checked_at: Instant,
+    update_in_progress: bool,
+    update_status: Option<String>,
+    update_tx: mpsc::Sender<Result<Option<String>, String>>,
+    update_rx: mpsc::Receiver<Result<Option<String>, String>>,
+    folder_sync_enabled: bool,
+    folder_sync_path_input: String,
+    folder_sync_secret: String,
+    folder_sync_machine_id: String,
+    folder_sync_in_progress: bool,
+    folder_sync_checked_at: Option<Instant>,
+    folder_sync_status: Option<String>,
+    folder_sync_tx: mpsc::Sender<Result<folder_sync::SyncStats, String>>,
+    folder_sync_rx: mpsc::Receiver<Result<folder_sync::SyncStats, String>>,
+    remote_kind: Option<remote_storage::RemoteKind>,
+    remote_endpoint_input: String,
+    remote_region_input: String,
+    remote_user_input: String,
+    remote_secret: String,
+    remote_passphrase: String,
+    remote_snapshots: bool,
+    remote_upload_tx: mpsc::Sender<Result<String, String>>,
+    remote_upload_rx: mpsc::Receiver<Result<String, String>>,
+    auto_restart_backend: bool,
+    auto_restart_after_secs: u64,
+    backend_stopped_since: Option<Instant>,
+    backend_restart_attempts: u32,
+    next_backend_restart_at: Option<Instant>,
     error: Option<String>,
     info: Option<String>,
     info_expires_at: Option<Instant>,
@@ -356,12 +1417,44 @@ struct TimelineApp {
 }
 
 const SCROLLBAR_SAFE_GUTTER: f32 = 16.0;
-const MIN_TIMELINE_VIEW_SECS: i64 = 5 * 60;
+const STARTUP_MARKER_FILE: &str = "viewer-starting";
+const STARTUP_CRASH_LIMIT: u32 = 2;
+const STARTUP_GRACE: Duration = Duration::from_secs(15);
 const AUTO_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
 const INFO_MESSAGE_TTL: Duration = Duration::from_secs(4);
+const VIEW_STATE_SAVE_INTERVAL: Duration = Duration::from_secs(2);
+const DEFAULT_EXPORT_FILENAME_TEMPLATE: &str = "export_{date}_{time}_{range}";
+const DEFAULT_SNAPSHOT_INTERVAL_HOURS: u64 = 24;
+const SNAPSHOT_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const SNAPSHOT_RETAIN_COUNT: usize = 7;
+const TEAM_EXPORT_INTERVAL_SECS: i64 = 3600;
+const DEFAULT_TEAM_EXPORT_DAYS: u32 = 30;
+const FOLDER_SYNC_INTERVAL_SECS: u64 = 600;
+const UPDATE_CHECK_INTERVAL_SECS: i64 = 86_400;
+const DEFAULT_WINDOW_SIZE: [f32; 2] = [1280.0, 720.0];
+const MIN_WINDOW_SIZE: [f32; 2] = [980.0, 640.0];
 const BACKEND_STATUS_POLL_INTERVAL: Duration = Duration::from_secs(1);
 const BACKEND_HEARTBEAT_GRACE_SECS: i64 = 180;
+// The backend writes its heartbeat every poll, so a short silence already means trouble.
+const BACKEND_HEARTBEAT_STALE_SECS: i64 = 30;
+const BACKEND_EXIT_WAIT: Duration = Duration::from_secs(2);
+const DEFAULT_AUTO_RESTART_AFTER_SECS: u64 = 60;
+const MIN_AUTO_RESTART_AFTER_SECS: u64 = 10;
+const MAX_AUTO_RESTART_AFTER_SECS: u64 = 3600;
+const BACKEND_RESTART_BASE_BACKOFF: Duration = Duration::from_secs(15);
+const BACKEND_RESTART_MAX_BACKOFF: Duration = Duration::from_secs(10 * 60);
+// Ranges with more segments than this are summarized while streaming instead of
+// being loaded into memory, unless the day timeline needs them.
+const AGGREGATE_ONLY_SEGMENT_THRESHOLD: i64 = 200_000;
+#[cfg(target_os = "windows")]
 const TRACKER_DAEMON_MUTEX_NAME: &str = "Local\\LimeTraceBackendSingleton";
+#[cfg(target_os = "windows")]
+const VIEWER_MUTEX_NAME: &str = "Local\\LimeTraceViewerSingleton";
+#[cfg(target_os = "windows")]
+const VIEWER_HANDOFF_WAIT: Duration = Duration::from_secs(3);
+#[cfg(target_os = "windows")]
+const VIEWER_HANDOFF_MAX_BYTES: u64 = 4096;
+const VIEWER_WINDOW_TITLE: &str = "LimeTrace";
 const APP_ICON_PNG: &[u8] = include_bytes!("../../../LimeTrace.png");
 // Fixed timeline sizing. At 1280x720 startup, one-hour cell is close to golden ratio.
 const TIMELINE_HEADER_HEIGHT: f32 = 24.0;
@@ -369,22 +1462,59 @@ const TIMELINE_CHART_HEIGHT: f32 = 86.0;
 const TIMELINE_FOOTER_HEIGHT: f32 = 28.0;
 const TIMELINE_TOTAL_HEIGHT: f32 =
     TIMELINE_HEADER_HEIGHT + TIMELINE_CHART_HEIGHT + TIMELINE_FOOTER_HEIGHT;
+const TIMELINE_DENSITY_HEIGHT: f32 = 20.0;
+// Segments at least this wide show their app name inside the bar.
+const TIMELINE_LABEL_MIN_WIDTH: f32 = 80.0;
 
 impl TimelineApp {
-    fn new(db_path: PathBuf) -> Self {
+    fn new(db_path: PathBuf, initial_view: Option<InitialView>, safe_mode: SafeMode, startup_marker: PathBuf) -> Self {
         let today = Local::now().date_naive();
         let (icon_request_tx, icon_result_rx) = spawn_icon_loader();
         let (reload_request_tx, reload_result_rx) = spawn_reload_worker();
+        let connections = ConnectionPool::new(&db_path);
         let (backend_status_request_tx, backend_status_result_rx) =
-            spawn_backend_status_worker(db_path.clone());
+            spawn_backend_status_worker(connections.clone());
+        let (rollover_tx, rollover_rx) = mpsc::channel();
+        let (snapshot_tx, snapshot_rx) = mpsc::channel();
+        let (team_export_tx, team_export_rx) = mpsc::channel();
+        let (folder_sync_tx, folder_sync_rx) = mpsc::channel();
+        let (remote_upload_tx, remote_upload_rx) = mpsc::channel();
+        let (update_tx, update_rx) = mpsc::channel();
+        let sync_folder_provider = detect_sync_folder(&db_path);
         let default_save_dir = db_path
             .parent()
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from("."));
-        let settings_path = default_save_dir.join("limetrace-settings.json");
-        let ui_language = load_ui_language(&settings_path).unwrap_or_else(default_ui_language);
+        let settings_path = settings_path_for_db(&db_path);
+        let settings = match safe_mode {
+            SafeMode::Off => load_settings(&settings_path),
+            _ => serde_json::Map::new(),
+        };
+        let crash_report = crash::latest_report_since(
+            &crash::logs_dir(&db_path),
+            settings.get("crash_report_seen_ts").and_then(serde_json::Value::as_i64).unwrap_or(0),
+        );
+        let font_path = settings
+            .get("font_path")
+            .and_then(serde_json::Value::as_str)
+            .map(PathBuf::from);
+        // Chinese text needs the custom fonts safe mode leaves out.
+        let ui_language = match safe_mode {
+            SafeMode::Off => load_ui_language(&settings).unwrap_or_else(default_ui_language),
+            _ => UiLanguage::EnUs,
+        };
+        let auto_restart_backend = settings
+            .get("auto_restart_backend")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let auto_restart_after_secs = settings
+            .get("auto_restart_after_secs")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(DEFAULT_AUTO_RESTART_AFTER_SECS)
+            .clamp(MIN_AUTO_RESTART_AFTER_SECS, MAX_AUTO_RESTART_AFTER_SECS);
         let mut app = Self {
             db_path,
+            connections,
             selected_date: today,
             calendar_month: month_start(today),
             range_preset: None,
@@ -392,12 +1522,49 @@ impl TimelineApp {
             custom_range_focus: CustomRangeFocus::From,
             custom_start_input: today.format("%Y-%m-%d").to_string(),
             custom_end_input: today.format("%Y-%m-%d").to_string(),
+            jump_date_input: String::new(),
             summary_limit: None,
+            summary_filter_input: String::new(),
+            summary_other_expanded: false,
+            pinned_app_keys: settings
+                .get("pinned_apps")
+                .and_then(serde_json::Value::as_array)
+                .map(|apps| {
+                    apps.iter()
+                        .filter_map(|app| app.as_str().map(str::to_owned))
+                        .collect()
+                })
+                .unwrap_or_default(),
             summary_limit_custom_input: "10".to_owned(),
+            summary_sort: SummarySort::DurationDesc,
+            show_session_columns: false,
+            show_title_split: false,
             timeline_view_range: None,
+            timeline_follow_now: settings
+                .get("timeline_follow_now")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
             segments: Vec::new(),
+            segments_aggregated: false,
             summary_rows: Vec::new(),
             summary_total_secs: 0,
+            summary_fleeting_secs: 0,
+            summary_call_secs: 0,
+            summary_away_secs: 0,
+            noise_threshold_secs: settings
+                .get("noise_threshold_secs")
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0)
+                .clamp(0, MAX_NOISE_THRESHOLD_SECS),
+            afk_confirm_enabled: settings
+                .get("afk_confirm_enabled")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+            afk_handled_idle_start: settings
+                .get("afk_handled_idle_start")
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0),
+            afk_prompt: None,
             selected_app_keys: HashSet::new(),
             icon_cache: HashMap::new(),
             icon_color_cache: HashMap::new(),
@@ -417,9 +1584,45 @@ impl TimelineApp {
             save_dir_override: None,
             save_dir_input: default_save_dir.display().to_string(),
             ui_language,
+            theme_choice: ThemeChoice::Light,
+            duration_format: settings
+                .get("duration_format")
+                .and_then(serde_json::Value::as_str)
+                .and_then(DurationFormat::from_code)
+                .unwrap_or(DurationFormat::HoursMinutesSeconds),
+            clock_format: settings
+                .get("clock_format")
+                .and_then(serde_json::Value::as_str)
+                .and_then(ClockFormat::from_code)
+                .unwrap_or(ClockFormat::H24),
+            window_size: None,
+            font_path: font_path.clone(),
+            font_path_input: font_path.map(|path| path.display().to_string()).unwrap_or_default(),
             settings_path,
+            last_saved_view_state: None,
+            view_state_checked_at: Instant::now(),
             export_format: ExportFormat::Csv,
+            export_filename_template: settings
+                .get("export_filename_template")
+                .and_then(serde_json::Value::as_str)
+                .filter(|template| !template.trim().is_empty())
+                .unwrap_or(DEFAULT_EXPORT_FILENAME_TEMPLATE)
+                .to_owned(),
+            export_collision_policy: settings
+                .get("export_collision_policy")
+                .and_then(serde_json::Value::as_str)
+                .and_then(ExportCollisionPolicy::from_code)
+                .unwrap_or(ExportCollisionPolicy::AutoIncrement),
+            export_exclude_idle: false,
+            export_gzip: false,
+            export_split_by_month: false,
+            export_exclude_system: false,
+            export_group_by: ExportGroupBy::App,
+            export_categories: HashSet::new(),
+            export_only_selected_apps: false,
             import_file_input: String::new(),
+            import_preview: None,
+            import_timezone_input: String::new(),
             show_import_window: false,
             show_export_window: false,
             show_backup_window: false,
@@ -427,9 +1630,229 @@ impl TimelineApp {
             backend_status: BackendStatus {
                 health: BackendHealth::Stopped,
                 last_write_ts: None,
+                heartbeat_ts: None,
+                paused: false,
                 checked_ts: unix_seconds_now(),
                 detail: None,
+                stop_reason: None,
+                live_segment: None,
             },
+            compare_view: None,
+            last_seen_date: today,
+            rollover_tx,
+            rollover_rx,
+            rollover_cards: Vec::new(),
+            sync_folder_provider,
+            crash_report,
+            safe_mode,
+            startup_marker: Some((startup_marker, Instant::now() + STARTUP_GRACE)),
+            viewer_handoff_rx: None,
+            app_categories: HashMap::new(),
+            title_category_rules: Vec::new(),
+            category_totals: None,
+            show_category_rules_window: false,
+            show_jira_window: false,
+            events: Vec::new(),
+            anomaly_checked_at: None,
+            forecast_profile: None,
+            forecast_refreshed_at: None,
+            forecast_secs: None,
+            app_aliases: HashMap::new(),
+            excluded_app_keys: HashSet::new(),
+            event_alias_target: None,
+            event_alias_input: String::new(),
+            git_repositories: settings
+                .get("git_repositories")
+                .and_then(serde_json::Value::as_array)
+                .map(|paths| {
+                    paths
+                        .iter()
+                        .filter_map(|path| path.as_str().map(PathBuf::from))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            show_git_markers: settings
+                .get("show_git_markers")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(true),
+            dashboard_layout: parse_dashboard_layout(settings.get("dashboard_layout")),
+            category_filter: None,
+            show_git_window: false,
+            new_git_repo_input: String::new(),
+            git_commit_cache: None,
+            workday_cache: None,
+            show_overtime_window: false,
+            contract_hours: parse_contract_hours(settings.get("contract_hours")),
+            overtime_month: Local::now().date_naive().with_day(1).unwrap_or_else(|| Local::now().date_naive()),
+            overtime_report: None,
+            days_off: HashMap::new(),
+            day_notes: HashMap::new(),
+            day_note_draft: None,
+            show_day_note_panel: settings
+                .get("show_day_note_panel")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+            day_off_date_input: String::new(),
+            day_off_kind: DayOffKind::Vacation,
+            day_off_label_input: String::new(),
+            holiday_preset: settings
+                .get("holiday_preset")
+                .and_then(serde_json::Value::as_str)
+                .filter(|code| HOLIDAY_PRESETS.contains(code))
+                .unwrap_or(HOLIDAY_PRESETS[0])
+                .to_owned(),
+            git_report: None,
+            title_tag_rules: Vec::new(),
+            segment_tags: Vec::new(),
+            tag_filter: None,
+            timeline_selection: None,
+            timeline_focus: None,
+            tag_target: None,
+            tag_input: String::new(),
+            show_bulk_window: false,
+            show_trash_window: false,
+            show_changelog_window: false,
+            changelog: Vec::new(),
+            trash_entries: None,
+            bulk_app_input: String::new(),
+            bulk_title_pattern: String::new(),
+            bulk_action: BulkAction::Tag,
+            bulk_tag_input: String::new(),
+            bulk_category: AppCategory::Work,
+            bulk_preview: None,
+            title_tag_report: None,
+            show_title_tags_window: false,
+            new_tag_rule_name: String::new(),
+            new_tag_rule_pattern: String::new(),
+            jira_base_url: settings
+                .get("jira_base_url")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            jira_email: settings
+                .get("jira_email")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            // The API token is never written to settings.json.
+            jira_token: env::var("LIMETRACE_JIRA_TOKEN").unwrap_or_default(),
+            jira_drafts: Vec::new(),
+            jira_submit_rx: None,
+            new_rule_pattern: String::new(),
+            new_rule_category: AppCategory::Work,
+            focus_session: None,
+            focus_report: None,
+            focus_minutes: 25,
+            focus_minimize: false,
+            relocate_target_input: default_db_path().display().to_string(),
+            snapshot_enabled: settings
+                .get("snapshot_enabled")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+            snapshot_dir_input: settings
+                .get("snapshot_dir")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            snapshot_interval_hours: settings
+                .get("snapshot_interval_hours")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL_HOURS)
+                .clamp(1, 24 * 7),
+            last_snapshot_ts: settings
+                .get("last_snapshot_ts")
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0),
+            snapshot_in_progress: false,
+            snapshot_checked_at: Instant::now(),
+            snapshot_tx,
+            snapshot_rx,
+            team_export_enabled: settings
+                .get("team_export_enabled")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+            team_export_path_input: settings
+                .get("team_export_path")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            team_export_days: settings
+                .get("team_export_days")
+                .and_then(serde_json::Value::as_u64)
+                .map_or(DEFAULT_TEAM_EXPORT_DAYS, |days| days.clamp(1, 366) as u32),
+            last_team_export_ts: settings
+                .get("last_team_export_ts")
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0),
+            team_export_in_progress: false,
+            team_export_checked_at: Instant::now(),
+            team_export_tx,
+            team_export_rx,
+            update_channel: settings
+                .get("update_channel")
+                .and_then(serde_json::Value::as_str)
+                .and_then(updater::UpdateChannel::from_code),
+            last_update_check_ts: settings
+                .get("last_update_check_ts")
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0),
+            update_checked_at: Instant::now(),
+            update_in_progress: false,
+            update_status: None,
+            update_tx,
+            update_rx,
+            folder_sync_enabled: settings
+                .get("folder_sync_enabled")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+            folder_sync_path_input: settings
+                .get("folder_sync_path")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            folder_sync_secret: env::var("LIMETRACE_SYNC_SECRET").unwrap_or_default(),
+            folder_sync_machine_id: settings
+                .get("folder_sync_machine_id")
+                .and_then(serde_json::Value::as_str)
+                .filter(|id| !id.is_empty())
+                .map_or_else(new_sync_machine_id, str::to_owned),
+            folder_sync_in_progress: false,
+            folder_sync_checked_at: None,
+            folder_sync_status: None,
+            folder_sync_tx,
+            folder_sync_rx,
+            remote_kind: settings
+                .get("remote_kind")
+                .and_then(serde_json::Value::as_str)
+                .and_then(remote_storage::RemoteKind::from_code),
+            remote_endpoint_input: settings
+                .get("remote_endpoint")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            remote_region_input: settings
+                .get("remote_region")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            remote_user_input: settings
+                .get("remote_user")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            remote_secret: env::var("LIMETRACE_REMOTE_SECRET").unwrap_or_default(),
+            remote_passphrase: env::var("LIMETRACE_REMOTE_PASSPHRASE").unwrap_or_default(),
+            remote_snapshots: settings
+                .get("remote_snapshots")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+            remote_upload_tx,
+            remote_upload_rx,
+            auto_restart_backend,
+            auto_restart_after_secs,
+            backend_stopped_since: None,
+            backend_restart_attempts: 0,
+            next_backend_restart_at: None,
             error: None,
             info: None,
             info_expires_at: None,
@@ -437,18 +1860,62 @@ impl TimelineApp {
             timeline_cache_range: None,
             timeline_cache_dirty: true,
         };
+        app.restore_view_state(&settings);
+        app.last_saved_view_state = Some(app.view_state_snapshot());
+        if safe_mode == SafeMode::Off {
+            app.check_version_change(&settings);
+        }
+        if let Some(view) = initial_view {
+            app.apply_initial_view(view);
+        }
         app.load_cached_app_visuals();
         app.reload();
         app.refresh_backend_status();
         app
     }
-
-    fn set_info_message(&mut self, message: impl Into<String>) {
-        self.error = None;
-        self.info = Some(message.into());
-        self.info_expires_at = Some(Instant::now() + INFO_MESSAGE_TTL);
-    }
-
+
+    // Updates install silently, so the first run of a new version shows its notes.
+    // A fresh install (no settings yet) only records the version.
+    fn check_version_change(&mut self, settings: &serde_json::Map<String, serde_json::Value>) {
+        let current = env!("CARGO_PKG_VERSION");
+        let last_seen = settings.get("last_seen_version").and_then(serde_json::Value::as_str);
+        if last_seen == Some(current) {
+            return;
+        }
+        if last_seen.is_some() || !settings.is_empty() {
+            self.changelog = changelog::releases_since(last_seen, current);
+            self.show_changelog_window = !self.changelog.is_empty();
+        }
+        if let Err(err) = persist_settings_values(&self.settings_path, &[("last_seen_version", json!(current))]) {
+            self.log_event(&format!("version setting save failed: {err:#}"));
+        }
+    }
+
+    fn draw_changelog_window_content(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+            for release in &self.changelog {
+                ui.heading(release.version);
+                for change in &release.changes {
+                    ui.label(format!("\u{2022} {change}"));
+                }
+                if !release.migrations.is_empty() {
+                    ui.add_space(4.0);
+                    ui.strong(self.t("migration_notes"));
+                    for migration in &release.migrations {
+                        ui.label(format!("\u{2022} {migration}"));
+                    }
+                }
+                ui.add_space(8.0);
+            }
+        });
+    }
+
+    fn set_info_message(&mut self, message: impl Into<String>) {
+        self.error = None;
+        self.info = Some(message.into());
+        self.info_expires_at = Some(Instant::now() + INFO_MESSAGE_TTL);
+    }
+
     fn clear_info_message(&mut self) {
         self.info = None;
         self.info_expires_at = None;
@@ -458,52 +1925,2415 @@ impl TimelineApp {
         tr(self.ui_language, key)
     }
 
-    fn set_ui_language(&mut self, language: UiLanguage) {
-        if self.ui_language == language {
-            return;
+    fn set_ui_language(&mut self, language: UiLanguage) {
+        if self.ui_language == language {
+            return;
+        }
+        self.ui_language = language;
+        if let Err(err) = persist_ui_language(&self.settings_path, self.ui_language) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save language setting: {err:#}"));
+        }
+    }
+
+    fn seed_app_color_cache_from_cached_visuals(&mut self) {
+        for (app_key, visual) in &self.cached_app_visuals {
+            self.app_color_cache.insert(app_key.clone(), visual.color);
+            if let Some(path) = visual.process_path.as_deref() {
+                self.icon_color_cache
+                    .entry(path.to_owned())
+                    .or_insert(visual.color);
+                if let Some(name) = visual
+                    .display_name
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                {
+                    self.process_display_name_cache
+                        .entry(path.to_owned())
+                        .or_insert(name.to_owned());
+                }
+            }
+        }
+    }
+
+    fn load_cached_app_visuals(&mut self) {
+        let Ok(conn) = self.open_tracking_db() else {
+            return;
+        };
+        if self.safe_mode == SafeMode::Off {
+            if let Ok(cached) = load_cached_app_visuals_from_db(&conn) {
+                self.cached_app_visuals = cached;
+                self.seed_app_color_cache_from_cached_visuals();
+            }
+        }
+        if let Ok(categories) = load_app_categories_from_db(&conn) {
+            self.app_categories = categories;
+        }
+        if let Ok(rules) = load_title_category_rules_from_db(&conn) {
+            self.title_category_rules = rules;
+        }
+        if let Ok(rules) = load_title_tag_rules_from_db(&conn) {
+            self.title_tag_rules = rules;
+        }
+        if let Ok(tags) = load_segment_tags_from_db(&conn) {
+            self.segment_tags = tags;
+        }
+        if let Ok(aliases) = load_app_aliases_from_db(&conn) {
+            self.app_aliases = aliases;
+        }
+        if let Ok(excluded) = load_excluded_apps_from_db(&conn) {
+            self.excluded_app_keys = excluded;
+        }
+        if let Ok(days_off) = load_days_off_from_db(&conn) {
+            self.days_off = days_off;
+        }
+        if let Ok(notes) = load_day_notes_from_db(&conn) {
+            self.day_notes = notes;
+        }
+        if let Ok(events) = load_events_from_db(&conn) {
+            self.events = events;
+        }
+        if let Ok(session) = load_active_focus_session(&conn, unix_seconds_now()) {
+            self.focus_session = session;
+        }
+    }
+
+    fn open_tracking_db(&self) -> Result<PooledConnection> {
+        self.connections.write()
+    }
+
+    fn set_app_category(&mut self, app_key: &str, category: Option<AppCategory>) {
+        let result = self.open_tracking_db().and_then(|conn| {
+            match category {
+                Some(category) => conn.execute(
+                    "INSERT OR REPLACE INTO app_categories (app_key, category) VALUES (?1, ?2)",
+                    params![app_key, category.code()],
+                ),
+                None => conn.execute("DELETE FROM app_categories WHERE app_key = ?1", params![app_key]),
+            }
+            .context("failed to save app category")
+        });
+        match result {
+            Ok(_) => {
+                match category {
+                    Some(category) => self.app_categories.insert(app_key.to_owned(), category),
+                    None => self.app_categories.remove(app_key),
+                };
+                self.invalidate_category_totals();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn add_title_category_rule(&mut self) {
+        let pattern = self.new_rule_pattern.trim().to_owned();
+        if pattern.is_empty() {
+            self.clear_info_message();
+            self.error = Some(self.t("rule_pattern_empty").to_owned());
+            return;
+        }
+        let category = self.new_rule_category;
+        let result = self.open_tracking_db().and_then(|conn| {
+            conn.execute(
+                "INSERT INTO title_category_rules (pattern, category) VALUES (?1, ?2)",
+                params![pattern, category.code()],
+            )
+            .context("failed to save title rule")?;
+            Ok(conn.last_insert_rowid())
+        });
+        match result {
+            Ok(id) => {
+                self.title_category_rules.push(TitleCategoryRule {
+                    id,
+                    pattern,
+                    category,
+                });
+                self.new_rule_pattern.clear();
+                self.invalidate_category_totals();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn delete_title_category_rule(&mut self, id: i64) {
+        let result = self.open_tracking_db().and_then(|conn| {
+            conn.execute("DELETE FROM title_category_rules WHERE id = ?1", params![id])
+                .context("failed to delete title rule")
+        });
+        match result {
+            Ok(_) => {
+                self.title_category_rules.retain(|rule| rule.id != id);
+                self.invalidate_category_totals();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn invalidate_category_totals(&mut self) {
+        self.category_totals = None;
+        // Aggregated reloads have no segments to recount, so the worker recomputes them.
+        if self.segments_aggregated {
+            self.reload();
+        }
+    }
+
+    fn ensure_category_totals(&mut self) -> &[(Option<AppCategory>, i64)] {
+        if self.category_totals.is_none() && !self.segments_aggregated {
+            let totals = match self.active_range_bounds() {
+                Some((range_start, range_end)) => compute_category_totals(
+                    &self.segments,
+                    range_start,
+                    range_end,
+                    &self.app_categories,
+                    &self.title_category_rules,
+                ),
+                None => Vec::new(),
+            };
+            self.category_totals = Some(totals);
+        }
+        self.category_totals.as_deref().unwrap_or_default()
+    }
+
+    fn draw_category_summary(&mut self, ui: &mut egui::Ui) {
+        if self.app_categories.is_empty() && self.title_category_rules.is_empty() {
+            return;
+        }
+        let totals = self.ensure_category_totals().to_vec();
+        let duration_of = |wanted: Option<AppCategory>| {
+            totals
+                .iter()
+                .find(|(category, _)| *category == wanted)
+                .map_or(0, |(_, secs)| *secs)
+        };
+        let work_secs = duration_of(Some(AppCategory::Work));
+        let distracting_secs = duration_of(Some(AppCategory::Distracting));
+        ui.horizontal_wrapped(|ui| {
+            for (category, secs) in &totals {
+                let label = match category {
+                    Some(category) => category.ui_label(self.ui_language),
+                    None => self.t("uncategorized"),
+                };
+                ui.label(format!("{label} {}", format_duration(*secs, self.duration_format)));
+                ui.separator();
+            }
+            if work_secs + distracting_secs > 0 {
+                let score = work_secs as f64 * 100.0 / (work_secs + distracting_secs) as f64;
+                ui.strong(format!("{} {score:.0}%", self.t("productivity")));
+            }
+            if ui.small_button(self.t("title_rules_menu")).clicked() {
+                self.show_category_rules_window = true;
+            }
+            if ui.small_button(self.t("title_tags_menu")).clicked() {
+                self.show_title_tags_window = true;
+            }
+        });
+        ui.add_space(4.0);
+    }
+
+    fn refresh_overtime_report(&mut self) {
+        let month = self.overtime_month;
+        let result = (|| -> Result<OvertimeReport> {
+            let next_month = add_months(month, 1).context("month out of range")?;
+            let (range_start, range_end) =
+                date_range_bounds(month, next_month).context("failed to resolve month bounds")?;
+            let segments = load_segments_for_range(&self.db_path, range_start, range_end)?;
+            let today = Local::now().date_naive();
+            let mut rows = Vec::new();
+            let mut date = month;
+            while date < next_month && date <= today {
+                let next_day = date.checked_add_days(Days::new(1)).context("date out of range")?;
+                let (day_start, day_end) =
+                    date_range_bounds(date, next_day).context("failed to resolve day bounds")?;
+                let worked_secs = build_summary_rows(
+                    day_start,
+                    day_end,
+                    &segments,
+                    self.noise_threshold_secs,
+                    load_app_file_description,
+                )
+                .iter()
+                .filter(|row| !row.is_idle)
+                .map(|row| row.duration_secs.max(0))
+                .sum();
+                let target_hours = if self.days_off.contains_key(&date) {
+                    0.0
+                } else {
+                    self.contract_hours[date.weekday().num_days_from_monday() as usize]
+                };
+                rows.push(OvertimeRow {
+                    date,
+                    worked_secs,
+                    target_secs: (target_hours * 3600.0).round() as i64,
+                });
+                date = next_day;
+            }
+            Ok(OvertimeReport { month, rows })
+        })();
+        match result {
+            Ok(report) => self.overtime_report = Some(report),
+            Err(err) => {
+                // An empty report keeps the window from retrying every frame.
+                self.overtime_report = Some(OvertimeReport { month, rows: Vec::new() });
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn export_overtime_report_csv(&self, report: &OvertimeReport) -> Result<PathBuf> {
+        let report_dir = self.output_root_dir().join("reports");
+        fs::create_dir_all(&report_dir)
+            .with_context(|| format!("failed to create report directory: {}", report_dir.display()))?;
+        let output_path = report_dir.join(format!("overtime_{}.csv", report.month.format("%Y-%m")));
+        let file = File::create(&output_path)
+            .with_context(|| format!("failed to create report file: {}", output_path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "\"Date\",\"Weekday\",\"Worked (h)\",\"Target (h)\",\"Delta (h)\",\"Balance (h)\"")
+            .context("failed to write CSV header")?;
+        let hours = |secs: i64| format!("{:.2}", secs as f64 / 3600.0);
+        let mut balance = 0;
+        for row in &report.rows {
+            let delta = row.worked_secs - row.target_secs;
+            balance += delta;
+            writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                csv_escape(&row.date.format("%Y-%m-%d").to_string()),
+                csv_escape(&row.date.format("%a").to_string()),
+                hours(row.worked_secs),
+                hours(row.target_secs),
+                hours(delta),
+                hours(balance),
+            )
+            .context("failed to write CSV row")?;
+        }
+        writer.flush().context("failed to flush CSV writer")?;
+        Ok(output_path)
+    }
+
+    fn draw_overtime_window_content(&mut self, ui: &mut egui::Ui) {
+        ui.label(self.t("overtime_contract_hours"));
+        let mut hours = self.contract_hours;
+        let mut hours_changed = false;
+        ui.horizontal(|ui| {
+            for (name, value) in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].into_iter().zip(hours.iter_mut()) {
+                ui.label(name);
+                hours_changed |= ui
+                    .add(egui::DragValue::new(value).range(0.0..=24.0).speed(0.25).max_decimals(2))
+                    .changed();
+            }
+        });
+        if hours_changed {
+            self.contract_hours = hours;
+            if let Err(err) = persist_settings_values(&self.settings_path, &[("contract_hours", json!(hours))]) {
+                self.clear_info_message();
+                self.error = Some(format!("failed to save contract hours: {err:#}"));
+            }
+            self.overtime_report = None;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.small_button("<").clicked() {
+                if let Some(month) = add_months(self.overtime_month, -1) {
+                    self.overtime_month = month;
+                    self.overtime_report = None;
+                }
+            }
+            ui.strong(self.overtime_month.format("%Y-%m").to_string());
+            if ui.small_button(">").clicked() {
+                if let Some(month) = add_months(self.overtime_month, 1) {
+                    self.overtime_month = month;
+                    self.overtime_report = None;
+                }
+            }
+            if ui.button(self.t("refresh")).clicked() {
+                self.refresh_overtime_report();
+            }
+            let exportable = self.overtime_report.as_ref().is_some_and(|report| !report.rows.is_empty());
+            if ui.add_enabled(exportable, egui::Button::new(self.t("export"))).clicked() {
+                if let Some(report) = &self.overtime_report {
+                    match self.export_overtime_report_csv(report) {
+                        Ok(path) => self.set_info_message(format!("{}: {}", self.t("overtime_exported"), path.display())),
+                        Err(err) => {
+                            self.clear_info_message();
+                            self.error = Some(format!("{err:#}"));
+                        }
+                    }
+                }
+            }
+        });
+        egui::CollapsingHeader::new(self.t("days_off"))
+            .id_salt("days_off_section")
+            .show(ui, |ui| self.draw_days_off_editor(ui));
+        if self.overtime_report.is_none() {
+            self.refresh_overtime_report();
+        }
+        let Some(report) = &self.overtime_report else {
+            return;
+        };
+
+        let signed = |secs: i64| {
+            let sign = if secs < 0 { "-" } else { "+" };
+            format!("{sign}{}", format_duration(secs.abs(), self.duration_format))
+        };
+        let balance: i64 = report.rows.iter().map(|row| row.worked_secs - row.target_secs).sum();
+        ui.strong(format!("{}: {}", self.t("overtime_month_balance"), signed(balance)));
+        ui.separator();
+        egui::ScrollArea::vertical()
+            .id_salt("overtime_scroll")
+            .max_height(320.0)
+            .show(ui, |ui| {
+                egui::Grid::new("overtime_grid").striped(true).show(ui, |ui| {
+                    ui.strong(self.t("date"));
+                    ui.strong(self.t("overtime_worked"));
+                    ui.strong(self.t("overtime_target"));
+                    ui.strong(self.t("overtime_delta"));
+                    ui.strong(self.t("overtime_balance"));
+                    ui.end_row();
+                    let mut running = 0;
+                    for row in &report.rows {
+                        let delta = row.worked_secs - row.target_secs;
+                        running += delta;
+                        match self.days_off.get(&row.date) {
+                            Some(day_off) => ui.label(
+                                egui::RichText::new(row.date.format("%Y-%m-%d %a").to_string())
+                                    .background_color(day_off.kind.shade()),
+                            ),
+                            None => ui.label(row.date.format("%Y-%m-%d %a").to_string()),
+                        }
+                        .on_hover_text(self.days_off.get(&row.date).map_or(String::new(), |day_off| {
+                            format!("{}: {}", self.t(day_off.kind.label_key()), day_off.label)
+                        }));
+                        ui.label(format_duration(row.worked_secs, self.duration_format));
+                        ui.label(format_duration(row.target_secs, self.duration_format));
+                        ui.label(signed(delta));
+                        ui.label(signed(running));
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+
+    fn save_day_note(&mut self, date: NaiveDate, note: &str) {
+        let note = note.trim();
+        if self.day_notes.get(&date).map_or(note.is_empty(), |saved| saved == note) {
+            return;
+        }
+        let date_key = date.format("%Y-%m-%d").to_string();
+        let result = self.open_tracking_db().and_then(|conn| {
+            if note.is_empty() {
+                conn.execute("DELETE FROM day_notes WHERE date = ?1", params![date_key])
+            } else {
+                conn.execute(
+                    "INSERT OR REPLACE INTO day_notes (date, note, updated_ts) VALUES (?1, ?2, ?3)",
+                    params![date_key, note, unix_seconds_now()],
+                )
+            }
+            .context("failed to save day note")
+        });
+        match result {
+            Ok(_) => {
+                if note.is_empty() {
+                    self.day_notes.remove(&date);
+                } else {
+                    self.day_notes.insert(date, note.to_owned());
+                }
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn draw_day_note_panel(&mut self, ui: &mut egui::Ui) {
+        let date = self.selected_date;
+        // A draft left over from another day is saved before the panel switches to this one.
+        if let Some((draft_date, draft)) = self.day_note_draft.take() {
+            if draft_date == date {
+                self.day_note_draft = Some((draft_date, draft));
+            } else {
+                self.save_day_note(draft_date, &draft);
+            }
+        }
+        let (_, mut draft) = self
+            .day_note_draft
+            .take()
+            .unwrap_or_else(|| (date, self.day_notes.get(&date).cloned().unwrap_or_default()));
+
+        ui.strong(format!("{} {}", self.t("day_note"), date.format("%Y-%m-%d %a")));
+        if let Some(day_off) = self.days_off.get(&date) {
+            ui.label(
+                egui::RichText::new(format!("{} {}", self.t(day_off.kind.label_key()), day_off.label))
+                    .background_color(day_off.kind.shade()),
+            );
+        }
+        let hint = self.t("day_note_hint");
+        let response = ui.add(
+            egui::TextEdit::multiline(&mut draft)
+                .hint_text(hint)
+                .desired_width(f32::INFINITY)
+                .desired_rows(8),
+        );
+        if response.lost_focus() {
+            self.save_day_note(date, &draft);
+        }
+        self.day_note_draft = Some((date, draft));
+    }
+
+    fn set_day_off(&mut self, date: NaiveDate, day_off: Option<DayOff>) {
+        let date_key = date.format("%Y-%m-%d").to_string();
+        let result = self.open_tracking_db().and_then(|conn| {
+            match &day_off {
+                Some(day_off) => conn.execute(
+                    "INSERT OR REPLACE INTO days_off (date, kind, label) VALUES (?1, ?2, ?3)",
+                    params![date_key, day_off.kind.code(), day_off.label],
+                ),
+                None => conn.execute("DELETE FROM days_off WHERE date = ?1", params![date_key]),
+            }
+            .context("failed to save day off")
+        });
+        match result {
+            Ok(_) => {
+                match day_off {
+                    Some(day_off) => self.days_off.insert(date, day_off),
+                    None => self.days_off.remove(&date),
+                };
+                self.overtime_report = None;
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn add_holiday_preset(&mut self, year: i32) {
+        let preset = self.holiday_preset.clone();
+        if let Err(err) = persist_settings_values(&self.settings_path, &[("holiday_preset", json!(preset))]) {
+            self.log_event(&format!("holiday preset save failed: {err:#}"));
+        }
+        for (date, label) in holiday_preset_dates(&preset, year) {
+            // Manual entries win over the preset.
+            if !self.days_off.contains_key(&date) {
+                self.set_day_off(
+                    date,
+                    Some(DayOff {
+                        kind: DayOffKind::Holiday,
+                        label: label.to_owned(),
+                    }),
+                );
+            }
+        }
+    }
+
+    fn draw_days_off_editor(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.day_off_date_input)
+                    .hint_text("YYYY-MM-DD")
+                    .desired_width(90.0),
+            );
+            egui::ComboBox::from_id_salt("day_off_kind")
+                .selected_text(self.t(self.day_off_kind.label_key()))
+                .show_ui(ui, |ui| {
+                    for kind in [DayOffKind::Vacation, DayOffKind::Holiday] {
+                        let label = self.t(kind.label_key());
+                        ui.selectable_value(&mut self.day_off_kind, kind, label);
+                    }
+                });
+            let label_hint = self.t("day_off_label");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.day_off_label_input)
+                    .hint_text(label_hint)
+                    .desired_width(120.0),
+            );
+            if ui.button(self.t("add")).clicked() {
+                match NaiveDate::parse_from_str(self.day_off_date_input.trim(), "%Y-%m-%d") {
+                    Ok(date) => {
+                        let day_off = DayOff {
+                            kind: self.day_off_kind,
+                            label: self.day_off_label_input.trim().to_owned(),
+                        };
+                        self.set_day_off(date, Some(day_off));
+                        self.day_off_date_input.clear();
+                        self.day_off_label_input.clear();
+                    }
+                    Err(err) => {
+                        self.clear_info_message();
+                        self.error = Some(format!("invalid date: {err}"));
+                    }
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(self.t("day_off_preset"));
+            egui::ComboBox::from_id_salt("holiday_preset")
+                .selected_text(self.holiday_preset.clone())
+                .show_ui(ui, |ui| {
+                    for code in HOLIDAY_PRESETS {
+                        ui.selectable_value(&mut self.holiday_preset, code.to_owned(), code);
+                    }
+                });
+            let year = self.overtime_month.year();
+            if ui.button(format!("{} {year}", self.t("add"))).clicked() {
+                self.add_holiday_preset(year);
+            }
+        });
+
+        let month = self.overtime_month;
+        let mut entries: Vec<(NaiveDate, DayOff)> = self
+            .days_off
+            .iter()
+            .filter(|(date, _)| date.year() == month.year() && date.month() == month.month())
+            .map(|(date, day_off)| (*date, day_off.clone()))
+            .collect();
+        entries.sort_by_key(|(date, _)| *date);
+        let mut removed = None;
+        for (date, day_off) in &entries {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(date.format("%Y-%m-%d").to_string())
+                        .background_color(day_off.kind.shade()),
+                );
+                ui.label(self.t(day_off.kind.label_key()));
+                ui.label(egui::RichText::new(&day_off.label).weak());
+                if ui.small_button("x").clicked() {
+                    removed = Some(*date);
+                }
+            });
+        }
+        if let Some(date) = removed {
+            self.set_day_off(date, None);
+        }
+    }
+
+    fn draw_workday_widget(&mut self, ui: &mut egui::Ui, range_start: i64, range_end: i64) {
+        if self
+            .workday_cache
+            .as_ref()
+            .is_none_or(|(range, _)| *range != (range_start, range_end))
+        {
+            let spans = match self.active_range_segments(range_start, range_end) {
+                Ok(segments) => compute_workday_spans(&segments, range_start, range_end),
+                Err(err) => {
+                    self.log_event(&format!("workday load failed: {err:#}"));
+                    Vec::new()
+                }
+            };
+            self.workday_cache = Some(((range_start, range_end), spans));
+        }
+        let Some((_, spans)) = &self.workday_cache else {
+            return;
+        };
+        if spans.is_empty() {
+            return;
+        }
+        draw_section_header(ui, self.t("workday"));
+        ui.add_space(4.0);
+        if let Some((avg_start, avg_end)) = average_workday_bounds(spans).filter(|_| spans.len() > 1) {
+            ui.label(format!(
+                "{} {}, {} {}",
+                self.t("workday_avg_start"),
+                format_time_of_day(avg_start, self.clock_format),
+                self.t("workday_avg_end"),
+                format_time_of_day(avg_end, self.clock_format)
+            ));
+        }
+        egui::ScrollArea::vertical()
+            .id_salt("workday_scroll")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                egui::Grid::new("workday_grid").striped(true).show(ui, |ui| {
+                    ui.strong(self.t("date"));
+                    ui.strong(self.t("workday_start"));
+                    ui.strong(self.t("workday_end"));
+                    ui.strong(self.t("workday_span"));
+                    ui.end_row();
+                    for span in spans.iter().rev() {
+                        ui.label(span.date.format("%Y-%m-%d").to_string());
+                        ui.label(format_hms(span.start_ts, self.clock_format));
+                        ui.label(format_hms(span.end_ts, self.clock_format));
+                        ui.label(format_duration(span.end_ts - span.start_ts, self.duration_format));
+                        ui.end_row();
+                    }
+                });
+            });
+        ui.add_space(8.0);
+    }
+
+    fn set_category_filter(&mut self, category_filter: Option<Option<AppCategory>>) {
+        self.category_filter = category_filter;
+        self.retain_selection_within_summary_limit();
+        self.invalidate_timeline_cache();
+    }
+
+    // Slices follow the title-rule aware totals; the filter itself goes by each app's category.
+    fn draw_category_donut(&mut self, ui: &mut egui::Ui) {
+        if self.app_categories.is_empty() && self.title_category_rules.is_empty() {
+            return;
+        }
+        let totals = self.ensure_category_totals().to_vec();
+        let total_secs: i64 = totals.iter().map(|(_, secs)| (*secs).max(0)).sum();
+        if total_secs <= 0 {
+            return;
+        }
+        let label_of = |category: Option<AppCategory>| match category {
+            Some(category) => category.ui_label(self.ui_language),
+            None => self.t("uncategorized"),
+        };
+        let mut clicked = None;
+        ui.horizontal(|ui| {
+            let (rect, response) =
+                ui.allocate_exact_size(egui::vec2(CATEGORY_DONUT_SIZE, CATEGORY_DONUT_SIZE), Sense::click());
+            let center = rect.center();
+            let outer = rect.width() / 2.0 - 4.0;
+            let inner = outer * 0.58;
+            let fractions: Vec<f32> = totals
+                .iter()
+                .map(|(_, secs)| (*secs).max(0) as f32 / total_secs as f32)
+                .collect();
+            let hovered = response.hover_pos().and_then(|pos| {
+                let offset = pos - center;
+                if offset.length() < inner || offset.length() > outer + 4.0 {
+                    return None;
+                }
+                let turn = (offset.y.atan2(offset.x) + std::f32::consts::FRAC_PI_2)
+                    .rem_euclid(std::f32::consts::TAU)
+                    / std::f32::consts::TAU;
+                let mut acc = 0.0;
+                fractions.iter().position(|fraction| {
+                    acc += fraction;
+                    turn < acc
+                })
+            });
+
+            let painter = ui.painter_at(rect);
+            let mut start = 0.0;
+            for (idx, ((category, _), fraction)) in totals.iter().zip(&fractions).enumerate() {
+                let mut color = category.map_or(Color32::from_gray(150), AppCategory::color);
+                if self.category_filter.is_some_and(|wanted| wanted != *category) {
+                    color = color.gamma_multiply(0.35);
+                }
+                let slice_outer = if hovered == Some(idx) { outer + 4.0 } else { outer };
+                paint_donut_slice(&painter, center, inner, slice_outer, start, *fraction, color);
+                start += fraction;
+            }
+            let center_text = match self.category_filter {
+                Some(category) => label_of(category).to_owned(),
+                None => format_duration(total_secs, self.duration_format),
+            };
+            painter.text(
+                center,
+                Align2::CENTER_CENTER,
+                center_text,
+                FontId::proportional(13.0),
+                ui.visuals().text_color(),
+            );
+
+            if let Some(idx) = hovered {
+                let (category, secs) = totals[idx];
+                if response.clicked() {
+                    clicked = Some(category);
+                }
+                response.on_hover_text_at_pointer(format!(
+                    "{}\n{} ({:.0}%)\n{}",
+                    label_of(category),
+                    format_duration(secs, self.duration_format),
+                    fractions[idx] * 100.0,
+                    self.t("category_donut_hint"),
+                ));
+            }
+
+            ui.vertical(|ui| {
+                for ((category, secs), fraction) in totals.iter().zip(&fractions) {
+                    ui.horizontal(|ui| {
+                        let (swatch, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), Sense::hover());
+                        ui.painter().rect_filled(
+                            swatch,
+                            2.0,
+                            category.map_or(Color32::from_gray(150), AppCategory::color),
+                        );
+                        let text = format!(
+                            "{} {} ({:.0}%)",
+                            label_of(*category),
+                            format_duration(*secs, self.duration_format),
+                            fraction * 100.0
+                        );
+                        if ui
+                            .selectable_label(self.category_filter == Some(*category), text)
+                            .clicked()
+                        {
+                            clicked = Some(*category);
+                        }
+                    });
+                }
+            });
+        });
+        ui.add_space(6.0);
+        if let Some(category) = clicked {
+            let next = (self.category_filter != Some(category)).then_some(category);
+            self.set_category_filter(next);
+        }
+    }
+
+    // Large ranges only keep aggregates in memory, so their segments are read back on demand.
+    fn active_range_segments(&self, range_start: i64, range_end: i64) -> Result<std::borrow::Cow<'_, [Segment]>> {
+        if self.segments_aggregated {
+            load_segments_for_range(&self.db_path, range_start, range_end).map(std::borrow::Cow::Owned)
+        } else {
+            Ok(std::borrow::Cow::Borrowed(self.segments.as_slice()))
+        }
+    }
+
+    fn scan_jira_worklogs(&mut self) {
+        let Some((range_start, range_end)) = self.active_range_bounds() else {
+            return;
+        };
+        let drafts = self
+            .active_range_segments(range_start, range_end)
+            .map(|segments| build_jira_worklog_drafts(&segments, range_start, range_end));
+        self.jira_drafts = match drafts {
+            Ok(drafts) => drafts,
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+                return;
+            }
+        };
+        if self.jira_drafts.is_empty() {
+            self.set_info_message(self.t("jira_no_keys"));
+        }
+    }
+
+    fn submit_jira_worklogs(&mut self) {
+        let base_url = self.jira_base_url.trim().trim_end_matches('/').to_owned();
+        let email = self.jira_email.trim().to_owned();
+        let token = self.jira_token.trim().to_owned();
+        if base_url.is_empty() || email.is_empty() || token.is_empty() {
+            self.clear_info_message();
+            self.error = Some(self.t("jira_missing_credentials").to_owned());
+            return;
+        }
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[("jira_base_url", json!(base_url)), ("jira_email", json!(email))],
+        ) {
+            self.log_event(&format!("jira settings save failed: {err:#}"));
+        }
+        let drafts: Vec<JiraWorklogDraft> =
+            self.jira_drafts.iter().filter(|draft| draft.selected).cloned().collect();
+        if drafts.is_empty() {
+            return;
+        }
+        let (result_tx, result_rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let results: JiraSubmitResult = drafts
+                .iter()
+                .map(|draft| {
+                    let result = submit_jira_worklog(&base_url, &email, &token, draft)
+                        .map_err(|err| format!("{err:#}"));
+                    (draft.issue_key.clone(), result)
+                })
+                .collect();
+            let _ = result_tx.send(results);
+        });
+        self.jira_submit_rx = Some(result_rx);
+    }
+
+    fn drain_jira_results(&mut self) {
+        let Some(rx) = &self.jira_submit_rx else {
+            return;
+        };
+        let Ok(results) = rx.try_recv() else {
+            return;
+        };
+        self.jira_submit_rx = None;
+        let mut failures = Vec::new();
+        let mut submitted = 0;
+        for (issue_key, result) in results {
+            match result {
+                Ok(()) => {
+                    submitted += 1;
+                    // Keep failed rows so they can be retried without rescanning.
+                    if let Some(draft) = self
+                        .jira_drafts
+                        .iter_mut()
+                        .find(|draft| draft.selected && draft.issue_key == issue_key)
+                    {
+                        draft.selected = false;
+                    }
+                }
+                Err(err) => failures.push(format!("{issue_key}: {err}")),
+            }
+        }
+        self.log_event(&format!("jira worklogs submitted: {submitted}, failed: {}", failures.len()));
+        if failures.is_empty() {
+            self.set_info_message(format!("{} ({submitted})", self.t("jira_submitted")));
+        } else {
+            self.clear_info_message();
+            self.error = Some(failures.join("; "));
+        }
+    }
+
+    fn draw_jira_window_content(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new(self.t("jira_hint")).weak());
+        ui.add_space(4.0);
+        egui::Grid::new("jira_credentials_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label(self.t("jira_base_url"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.jira_base_url)
+                        .hint_text("https://example.atlassian.net")
+                        .desired_width(260.0),
+                );
+                ui.end_row();
+                ui.label(self.t("jira_email"));
+                ui.add(egui::TextEdit::singleline(&mut self.jira_email).desired_width(260.0));
+                ui.end_row();
+                ui.label(self.t("jira_token"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.jira_token)
+                        .password(true)
+                        .desired_width(260.0),
+                );
+                ui.end_row();
+            });
+        ui.separator();
+        if ui.button(self.t("jira_scan")).clicked() {
+            self.scan_jira_worklogs();
+        }
+        let duration_format = self.duration_format;
+        egui::ScrollArea::vertical()
+            .id_salt("jira_drafts_scroll")
+            .max_height(220.0)
+            .show(ui, |ui| {
+                for draft in &mut self.jira_drafts {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut draft.selected, "");
+                        ui.monospace(&draft.issue_key);
+                        let started = Local
+                            .timestamp_opt(draft.started_ts, 0)
+                            .single()
+                            .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_default();
+                        ui.label(started);
+                        ui.label(format_duration(draft.duration_secs, duration_format));
+                        ui.add(egui::TextEdit::singleline(&mut draft.comment).desired_width(200.0));
+                    });
+                }
+            });
+        ui.separator();
+        let selected = self.jira_drafts.iter().filter(|draft| draft.selected).count();
+        if self.jira_submit_rx.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(self.t("jira_submitting"));
+            });
+        } else if ui
+            .add_enabled(selected > 0, egui::Button::new(format!("{} ({selected})", self.t("jira_submit"))))
+            .clicked()
+        {
+            self.submit_jira_worklogs();
+        }
+    }
+
+    fn draw_category_rules_window_content(&mut self, ui: &mut egui::Ui) {
+        ui.label(egui::RichText::new(self.t("title_rules_hint")).weak());
+        ui.add_space(4.0);
+        let mut delete_id = None;
+        egui::ScrollArea::vertical()
+            .id_salt("title_rules_scroll")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                for rule in &self.title_category_rules {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{} \"{}\" \u{2192} {}",
+                            self.t("title_contains"),
+                            rule.pattern,
+                            rule.category.ui_label(self.ui_language)
+                        ));
+                        if ui.small_button(self.t("delete")).clicked() {
+                            delete_id = Some(rule.id);
+                        }
+                    });
+                }
+            });
+        if let Some(id) = delete_id {
+            self.delete_title_category_rule(id);
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(self.t("title_contains"));
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_rule_pattern).desired_width(140.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            egui::ComboBox::from_id_salt("new_rule_category")
+                .selected_text(self.new_rule_category.ui_label(self.ui_language))
+                .show_ui(ui, |ui| {
+                    for category in AppCategory::ALL {
+                        ui.selectable_value(
+                            &mut self.new_rule_category,
+                            category,
+                            category.ui_label(self.ui_language),
+                        );
+                    }
+                });
+            if ui.button(self.t("add")).clicked() || submitted {
+                self.add_title_category_rule();
+            }
+        });
+    }
+
+    fn ensure_git_commits(&mut self, range_start: i64, range_end: i64) {
+        if self
+            .git_commit_cache
+            .as_ref()
+            .is_some_and(|cache| cache.range == (range_start, range_end))
+        {
+            return;
+        }
+        let mut commits = Vec::new();
+        for repo in &self.git_repositories {
+            match read_reflog_commits(repo, range_start, range_end) {
+                Ok(repo_commits) => commits.extend(repo_commits),
+                Err(err) => self.log_event(&format!("git reflog read failed: {err:#}")),
+            }
+        }
+        commits.sort_by_key(|commit| commit.ts);
+        self.git_commit_cache = Some(GitCommitCache {
+            range: (range_start, range_end),
+            commits,
+        });
+    }
+
+    fn set_git_repositories(&mut self, repositories: Vec<PathBuf>) {
+        self.git_repositories = repositories;
+        self.git_commit_cache = None;
+        self.git_report = None;
+        let paths: Vec<String> = self
+            .git_repositories
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        if let Err(err) = persist_settings_values(&self.settings_path, &[("git_repositories", json!(paths))]) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save git repositories: {err:#}"));
+        }
+    }
+
+    fn refresh_git_report(&mut self) {
+        let Some(range) = self.active_range_bounds() else {
+            return;
+        };
+        self.ensure_git_commits(range.0, range.1);
+        let commits = self
+            .git_commit_cache
+            .as_ref()
+            .map(|cache| cache.commits.as_slice())
+            .unwrap_or_default();
+        let rows = self.active_range_segments(range.0, range.1).map(|segments| {
+            build_git_repo_report(&self.git_repositories, &segments, commits, range.0, range.1)
+        });
+        match rows {
+            Ok(rows) => self.git_report = Some((range, rows)),
+            Err(err) => {
+                self.git_report = Some((range, Vec::new()));
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn draw_git_window_content(&mut self, ui: &mut egui::Ui) {
+        if self.git_report.as_ref().map(|(range, _)| *range) != self.active_range_bounds() {
+            self.refresh_git_report();
+        }
+        ui.label(egui::RichText::new(self.t("git_hint")).weak());
+        ui.add_space(4.0);
+        let markers_label = self.t("git_show_markers");
+        if ui.checkbox(&mut self.show_git_markers, markers_label).changed() {
+            if let Err(err) = persist_settings_values(
+                &self.settings_path,
+                &[("show_git_markers", json!(self.show_git_markers))],
+            ) {
+                self.clear_info_message();
+                self.error = Some(format!("failed to save git setting: {err:#}"));
+            }
+        }
+        let mut remove_index = None;
+        for (index, repo) in self.git_repositories.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.monospace(repo.display().to_string());
+                if ui.small_button(self.t("delete")).clicked() {
+                    remove_index = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove_index {
+            let mut repositories = self.git_repositories.clone();
+            repositories.remove(index);
+            self.set_git_repositories(repositories);
+        }
+        ui.horizontal(|ui| {
+            let hint = self.t("git_repo_path");
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_git_repo_input)
+                    .hint_text(hint)
+                    .desired_width(300.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if (ui.button(self.t("add")).clicked() || submitted) && !self.new_git_repo_input.trim().is_empty() {
+                let path = PathBuf::from(self.new_git_repo_input.trim());
+                if reflog_path_for_repo(&path).is_none() {
+                    self.clear_info_message();
+                    self.error = Some(format!("{}: {}", self.t("git_repo_missing"), path.display()));
+                } else if !self.git_repositories.contains(&path) {
+                    let mut repositories = self.git_repositories.clone();
+                    repositories.push(path);
+                    self.set_git_repositories(repositories);
+                    self.new_git_repo_input.clear();
+                }
+            }
+        });
+        ui.separator();
+        let Some((_, rows)) = &self.git_report else {
+            return;
+        };
+        egui::Grid::new("git_report_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong(self.t("git_repository"));
+                ui.strong(self.t("git_coding_time"));
+                ui.strong(self.t("git_commits"));
+                ui.end_row();
+                for row in rows {
+                    ui.label(&row.repo);
+                    ui.label(format_duration(row.coding_secs, self.duration_format));
+                    ui.label(row.commit_count.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn add_title_tag_rule(&mut self) {
+        let name = self.new_tag_rule_name.trim().to_owned();
+        let pattern = self.new_tag_rule_pattern.trim().to_owned();
+        if name.is_empty() || pattern.is_empty() {
+            self.clear_info_message();
+            self.error = Some(self.t("tag_rule_empty").to_owned());
+            return;
+        }
+        if let Err(err) = TitleRegex::new(&pattern) {
+            self.clear_info_message();
+            self.error = Some(format!("invalid pattern: {err:#}"));
+            return;
+        }
+        let result = self.open_tracking_db().and_then(|conn| {
+            conn.execute(
+                "INSERT INTO title_tag_rules (name, pattern) VALUES (?1, ?2)",
+                params![name, pattern],
+            )
+            .context("failed to save tag rule")?;
+            Ok(conn.last_insert_rowid())
+        });
+        match result {
+            Ok(id) => {
+                self.title_tag_rules.push(TitleTagRule { id, name, pattern });
+                self.new_tag_rule_name.clear();
+                self.new_tag_rule_pattern.clear();
+                self.title_tag_report = None;
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn delete_title_tag_rule(&mut self, id: i64) {
+        let result = self.open_tracking_db().and_then(|mut conn| {
+            let tx = conn.transaction().context("failed to start tag rule delete")?;
+            tx.execute("DELETE FROM title_tags WHERE rule_id = ?1", params![id])
+                .context("failed to delete title tags")?;
+            tx.execute("DELETE FROM title_tag_rules WHERE id = ?1", params![id])
+                .context("failed to delete tag rule")?;
+            tx.commit().context("failed to commit tag rule delete")
+        });
+        match result {
+            Ok(()) => {
+                self.title_tag_rules.retain(|rule| rule.id != id);
+                self.title_tag_report = None;
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn refresh_title_tag_report(&mut self) {
+        let Some(range) = self.active_range_bounds() else {
+            return;
+        };
+        let result = self.open_tracking_db().and_then(|mut conn| {
+            scan_title_tags(&mut conn, &self.title_tag_rules)?;
+            let rows = load_title_tag_totals(&conn, range.0, range.1)?;
+            // Loaded unclipped, so an active tag filter doesn't skew the other tags.
+            let segments = load_segments_for_range(&self.db_path, range.0, range.1)?;
+            Ok((rows, segment_tag_totals(&segments, &self.segment_tags, range.0, range.1)))
+        });
+        match result {
+            Ok((rows, segment_tag_rows)) => {
+                self.title_tag_report = Some(TitleTagReport {
+                    range,
+                    rows,
+                    segment_tag_rows,
+                })
+            }
+            Err(err) => {
+                // Keep the failed range so the window doesn't retry every frame.
+                self.title_tag_report = Some(TitleTagReport {
+                    range,
+                    rows: Vec::new(),
+                    segment_tag_rows: Vec::new(),
+                });
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn draw_title_tags_window_content(&mut self, ui: &mut egui::Ui) {
+        if self.title_tag_report.as_ref().map(|report| report.range) != self.active_range_bounds() {
+            self.refresh_title_tag_report();
+        }
+        ui.label(egui::RichText::new(self.t("title_tags_hint")).weak());
+        ui.add_space(4.0);
+        let mut delete_id = None;
+        for rule in &self.title_tag_rules {
+            ui.horizontal(|ui| {
+                ui.strong(&rule.name);
+                ui.monospace(&rule.pattern);
+                if ui.small_button(self.t("delete")).clicked() {
+                    delete_id = Some(rule.id);
+                }
+            });
+        }
+        if let Some(id) = delete_id {
+            self.delete_title_tag_rule(id);
+        }
+        ui.horizontal(|ui| {
+            let name_hint = self.t("tag_rule_name");
+            let pattern_hint = self.t("tag_rule_pattern");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_tag_rule_name)
+                    .hint_text(name_hint)
+                    .desired_width(90.0),
+            );
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.new_tag_rule_pattern)
+                    .hint_text(pattern_hint)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_width(180.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button(self.t("add")).clicked() || submitted {
+                self.add_title_tag_rule();
+            }
+        });
+        ui.separator();
+        if ui.small_button(self.t("refresh")).clicked() {
+            self.refresh_title_tag_report();
+        }
+        let Some(report) = &self.title_tag_report else {
+            return;
+        };
+        let mut next_filter = None;
+        ui.strong(self.t("segment_tags"));
+        ui.label(egui::RichText::new(self.t("segment_tags_hint")).weak());
+        if report.segment_tag_rows.is_empty() {
+            ui.label(egui::RichText::new(self.t("segment_tags_empty")).weak());
+        }
+        egui::Grid::new("segment_tags_report_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for (tag, secs) in &report.segment_tag_rows {
+                    let selected = self.tag_filter.as_ref() == Some(tag);
+                    if ui
+                        .selectable_label(selected, tag)
+                        .on_hover_text(self.t("tag_filter"))
+                        .clicked()
+                    {
+                        next_filter = Some((!selected).then(|| tag.clone()));
+                    }
+                    ui.label(format_duration(*secs, self.duration_format));
+                    ui.end_row();
+                }
+            });
+        ui.separator();
+        if let Some(tag_filter) = next_filter {
+            self.set_tag_filter(tag_filter);
+            return;
+        }
+        if report.rows.is_empty() {
+            ui.label(egui::RichText::new(self.t("title_tags_empty")).weak());
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .id_salt("title_tags_report_scroll")
+            .max_height(220.0)
+            .show(ui, |ui| {
+                egui::Grid::new("title_tags_report_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (rule_name, value, secs) in &report.rows {
+                            ui.label(rule_name);
+                            ui.monospace(value);
+                            ui.label(format_duration(*secs, self.duration_format));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn merged_tag_spans(&self, tag: &str) -> Vec<(i64, i64)> {
+        merge_spans(
+            self.segment_tags
+                .iter()
+                .filter(|segment_tag| segment_tag.tag == tag)
+                .map(|segment_tag| (segment_tag.start_ts, segment_tag.end_ts))
+                .collect(),
+        )
+    }
+
+    fn set_tag_filter(&mut self, tag_filter: Option<String>) {
+        if self.tag_filter == tag_filter {
+            return;
+        }
+        self.tag_filter = tag_filter;
+        self.reload();
+    }
+
+    fn add_segment_tag(&mut self, start_ts: i64, end_ts: i64) {
+        let tag = self.tag_input.trim().to_owned();
+        if tag.is_empty() || end_ts <= start_ts {
+            return;
+        }
+        let result = self.open_tracking_db().and_then(|conn| {
+            conn.execute(
+                "INSERT INTO segment_tags (start_ts, end_ts, tag) VALUES (?1, ?2, ?3)",
+                params![start_ts, end_ts, tag],
+            )
+            .context("failed to save segment tag")?;
+            Ok(conn.last_insert_rowid())
+        });
+        match result {
+            Ok(id) => {
+                self.segment_tags.push(SegmentTag {
+                    id,
+                    start_ts,
+                    end_ts,
+                    tag,
+                });
+                self.tag_input.clear();
+                self.after_segment_tags_changed();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn delete_segment_tag(&mut self, id: i64) {
+        let result = self.open_tracking_db().and_then(|conn| {
+            conn.execute("DELETE FROM segment_tags WHERE id = ?1", params![id])
+                .context("failed to delete segment tag")
+        });
+        match result {
+            Ok(_) => {
+                self.segment_tags.retain(|segment_tag| segment_tag.id != id);
+                self.after_segment_tags_changed();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn after_segment_tags_changed(&mut self) {
+        self.title_tag_report = None;
+        if self.tag_filter.is_some() {
+            self.reload();
+        }
+    }
+
+    fn draw_tag_target_window_content(&mut self, ui: &mut egui::Ui) {
+        let Some((start_ts, end_ts)) = self.tag_target else {
+            return;
+        };
+        ui.label(format!(
+            "{} - {} ({})",
+            format_hms(start_ts, self.clock_format),
+            format_hms(end_ts, self.clock_format),
+            format_duration(end_ts - start_ts, self.duration_format)
+        ));
+        let mut delete_id = None;
+        for segment_tag in self
+            .segment_tags
+            .iter()
+            .filter(|segment_tag| segment_tag.start_ts < end_ts && segment_tag.end_ts > start_ts)
+        {
+            ui.horizontal(|ui| {
+                ui.strong(&segment_tag.tag);
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} - {}",
+                        format_hms(segment_tag.start_ts, self.clock_format),
+                        format_hms(segment_tag.end_ts, self.clock_format)
+                    ))
+                    .weak(),
+                );
+                if ui.small_button("x").clicked() {
+                    delete_id = Some(segment_tag.id);
+                }
+            });
+        }
+        if let Some(id) = delete_id {
+            self.delete_segment_tag(id);
+        }
+        ui.horizontal(|ui| {
+            let hint = self.t("tag_name");
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.tag_input)
+                    .hint_text(hint)
+                    .desired_width(160.0),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button(self.t("add")).clicked() || submitted {
+                self.add_segment_tag(start_ts, end_ts);
+            }
+        });
+        let known: BTreeSet<String> = self.segment_tags.iter().map(|segment_tag| segment_tag.tag.clone()).collect();
+        if !known.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for tag in known {
+                    if ui.small_button(&tag).clicked() {
+                        self.tag_input = tag;
+                        self.add_segment_tag(start_ts, end_ts);
+                    }
+                }
+            });
+        }
+    }
+
+    fn bulk_criteria(&self) -> Option<BulkCriteria> {
+        Some(BulkCriteria {
+            app_key: normalize_app_key(self.bulk_app_input.trim()),
+            title_pattern: self.bulk_title_pattern.trim().to_owned(),
+            range: self.active_range_bounds()?,
+        })
+    }
+
+    fn preview_bulk_edit(&mut self) {
+        let Some(criteria) = self.bulk_criteria() else {
+            return;
+        };
+        let result = self
+            .open_tracking_db()
+            .and_then(|conn| find_bulk_matches(&conn, &criteria));
+        match result {
+            Ok(matches) => self.bulk_preview = Some(BulkPreview { criteria, matches }),
+            Err(err) => {
+                self.bulk_preview = None;
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn apply_bulk_edit(&mut self) {
+        let Some(preview) = self.bulk_preview.take() else {
+            return;
+        };
+        let count = preview.matches.len();
+        let result = match self.bulk_action {
+            BulkAction::Category => {
+                self.set_app_category(&preview.criteria.app_key, Some(self.bulk_category));
+                Ok(())
+            }
+            BulkAction::Tag => {
+                let tag = self.bulk_tag_input.trim().to_owned();
+                let spans = merge_spans(preview.matches.iter().map(|(_, start, end)| (*start, *end)).collect());
+                self.open_tracking_db().and_then(|mut conn| {
+                    let tx = conn.transaction().context("failed to start bulk tag transaction")?;
+                    {
+                        let mut insert = tx
+                            .prepare("INSERT INTO segment_tags (start_ts, end_ts, tag) VALUES (?1, ?2, ?3)")
+                            .context("failed to prepare bulk tag insert")?;
+                        for (start_ts, end_ts) in &spans {
+                            insert
+                                .execute(params![start_ts, end_ts, tag])
+                                .context("failed to insert segment tag")?;
+                        }
+                    }
+                    tx.commit().context("failed to commit bulk tag transaction")?;
+                    self.segment_tags = load_segment_tags_from_db(&conn)?;
+                    Ok(())
+                })
+            }
+            BulkAction::Delete => self.open_tracking_db().and_then(|mut conn| {
+                let tx = conn.transaction().context("failed to start bulk delete transaction")?;
+                {
+                    let deleted_ts = unix_seconds_now();
+                    let mut delete = tx
+                        .prepare("UPDATE segments SET deleted_ts = ?2 WHERE id = ?1")
+                        .context("failed to prepare bulk delete")?;
+                    for (id, _, _) in &preview.matches {
+                        delete.execute(params![id, deleted_ts]).context("failed to delete segment")?;
+                    }
+                }
+                tx.commit().context("failed to commit bulk delete transaction")
+            }),
+        };
+        match result {
+            Ok(()) => {
+                self.log_event(&format!("bulk {:?} applied to {count} segments", self.bulk_action));
+                self.set_info_message(self.t("bulk_done").replace("{count}", &count.to_string()));
+                self.title_tag_report = None;
+                self.reload();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn draw_bulk_window_content(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("bulk_criteria_grid").num_columns(2).show(ui, |ui| {
+            ui.label(timeline_tip_text(self.ui_language, "app"));
+            let app_hint = self.t("bulk_app_hint");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.bulk_app_input)
+                    .hint_text(app_hint)
+                    .desired_width(220.0),
+            );
+            ui.end_row();
+            ui.label(timeline_tip_text(self.ui_language, "title"));
+            let title_hint = self.t("bulk_title_hint");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.bulk_title_pattern)
+                    .hint_text(title_hint)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_width(220.0),
+            );
+            ui.end_row();
+            ui.label(self.t("range"));
+            if let Some((start, end)) = self.active_range_dates() {
+                ui.label(format!("{} ~ {}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d")));
+            }
+            ui.end_row();
+        });
+        ui.horizontal(|ui| {
+            let tag_label = self.t("bulk_tag");
+            let category_label = self.t("category");
+            let delete_label = self.t("bulk_delete");
+            ui.selectable_value(&mut self.bulk_action, BulkAction::Tag, tag_label);
+            let category_allowed = self.bulk_title_pattern.trim().is_empty() && !self.bulk_app_input.trim().is_empty();
+            let category_hint = self.t("bulk_category_hint");
+            ui.add_enabled_ui(category_allowed, |ui| {
+                ui.selectable_value(&mut self.bulk_action, BulkAction::Category, category_label)
+                    .on_hover_text(category_hint)
+                    .on_disabled_hover_text(category_hint);
+            });
+            if !category_allowed && self.bulk_action == BulkAction::Category {
+                self.bulk_action = BulkAction::Tag;
+            }
+            ui.selectable_value(&mut self.bulk_action, BulkAction::Delete, delete_label);
+        });
+        match self.bulk_action {
+            BulkAction::Tag => {
+                let hint = self.t("tag_name");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.bulk_tag_input)
+                        .hint_text(hint)
+                        .desired_width(160.0),
+                );
+            }
+            BulkAction::Category => {
+                ui.horizontal(|ui| {
+                    for category in AppCategory::ALL {
+                        ui.selectable_value(&mut self.bulk_category, category, category.ui_label(self.ui_language));
+                    }
+                });
+            }
+            BulkAction::Delete => {}
+        }
+        ui.separator();
+        let criteria = self.bulk_criteria();
+        ui.horizontal(|ui| {
+            if ui.button(self.t("bulk_preview")).clicked() {
+                if let Err(err) = Some(self.bulk_title_pattern.trim())
+                    .filter(|pattern| !pattern.is_empty())
+                    .map_or(Ok(()), |pattern| TitleRegex::new(pattern).map(|_| ()))
+                {
+                    self.clear_info_message();
+                    self.error = Some(format!("invalid pattern: {err:#}"));
+                } else {
+                    self.preview_bulk_edit();
+                }
+            }
+            let fresh = self
+                .bulk_preview
+                .as_ref()
+                .is_some_and(|preview| Some(&preview.criteria) == criteria.as_ref() && !preview.matches.is_empty());
+            let ready = fresh && (self.bulk_action != BulkAction::Tag || !self.bulk_tag_input.trim().is_empty());
+            if ui.add_enabled(ready, egui::Button::new(self.t("bulk_apply"))).clicked() {
+                self.apply_bulk_edit();
+            }
+        });
+        if let Some(preview) = &self.bulk_preview {
+            if Some(&preview.criteria) == criteria.as_ref() {
+                let secs = preview.matches.iter().map(|(_, start, end)| end - start).sum();
+                ui.label(
+                    self.t("bulk_matches")
+                        .replace("{count}", &preview.matches.len().to_string())
+                        .replace("{time}", &format_duration(secs, self.duration_format)),
+                );
+            } else {
+                ui.label(egui::RichText::new(self.t("bulk_stale")).weak());
+            }
+        }
+    }
+
+    fn refresh_trash_entries(&mut self) {
+        match self.open_tracking_db().and_then(|conn| load_trash_entries(&conn)) {
+            Ok(entries) => self.trash_entries = Some(entries),
+            Err(err) => {
+                self.trash_entries = Some(Vec::new());
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    /// `None` applies the action to every segment in the trash.
+    fn run_trash_action(&mut self, restore: bool, id: Option<i64>) {
+        let sql = match (restore, id.is_some()) {
+            (true, true) => "UPDATE segments SET deleted_ts = NULL WHERE id = ?1",
+            (true, false) => "UPDATE segments SET deleted_ts = NULL WHERE deleted_ts IS NOT NULL",
+            (false, true) => "DELETE FROM segments WHERE id = ?1 AND deleted_ts IS NOT NULL",
+            (false, false) => "DELETE FROM segments WHERE deleted_ts IS NOT NULL",
+        };
+        let result = self.open_tracking_db().and_then(|conn| {
+            match id {
+                Some(id) => conn.execute(sql, params![id]),
+                None => conn.execute(sql, []),
+            }
+            .context("failed to update trash")
+        });
+        match result {
+            Ok(count) => {
+                let action = if restore { "restored" } else { "purged" };
+                self.log_event(&format!("trash: {action} {count} segments"));
+                self.trash_entries = None;
+                if restore {
+                    self.reload();
+                }
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn draw_trash_window_content(&mut self, ui: &mut egui::Ui) {
+        if self.trash_entries.is_none() {
+            self.refresh_trash_entries();
+        }
+        let Some(entries) = &self.trash_entries else {
+            return;
+        };
+        let has_entries = !entries.is_empty();
+        let mut action = None;
+        let mut refresh = false;
+        ui.horizontal(|ui| {
+            if ui.button(self.t("refresh")).clicked() {
+                refresh = true;
+            }
+            if ui.add_enabled(has_entries, egui::Button::new(self.t("trash_restore_all"))).clicked() {
+                action = Some((true, None));
+            }
+            if ui.add_enabled(has_entries, egui::Button::new(self.t("trash_empty"))).clicked() {
+                action = Some((false, None));
+            }
+        });
+        ui.separator();
+        if !has_entries {
+            ui.label(egui::RichText::new(self.t("trash_none")).weak());
+        }
+        egui::ScrollArea::vertical()
+            .id_salt("trash_scroll")
+            .max_height(340.0)
+            .show(ui, |ui| {
+                egui::Grid::new("trash_grid").num_columns(5).striped(true).show(ui, |ui| {
+                    for entry in entries {
+                        ui.label(format_local_datetime(entry.start_ts, self.clock_format));
+                        ui.label(format_duration(entry.end_ts - entry.start_ts, self.duration_format));
+                        ui.label(display_app_name(&entry.app_name, entry.is_idle))
+                            .on_hover_text(entry.title.as_deref().unwrap_or("-"));
+                        ui.label(egui::RichText::new(format_local_datetime(entry.deleted_ts, self.clock_format)).weak())
+                            .on_hover_text(self.t("trash_deleted_at"));
+                        ui.horizontal(|ui| {
+                            if ui.small_button(self.t("trash_restore")).clicked() {
+                                action = Some((true, Some(entry.id)));
+                            }
+                            if ui.small_button(self.t("trash_purge")).clicked() {
+                                action = Some((false, Some(entry.id)));
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+        if let Some((restore, id)) = action {
+            self.run_trash_action(restore, id);
+        } else if refresh {
+            self.trash_entries = None;
+        }
+    }
+
+    fn start_focus_session(&mut self) {
+        let started_ts = unix_seconds_now();
+        let ends_ts = started_ts + i64::from(self.focus_minutes) * 60;
+        let minimize = self.focus_minimize;
+        let result = self.open_tracking_db().and_then(|conn| {
+            conn.execute(
+                "INSERT INTO focus_sessions (started_ts, ends_ts, minimize_distracting) VALUES (?1, ?2, ?3)",
+                params![started_ts, ends_ts, i64::from(minimize)],
+            )
+            .context("failed to start focus session")?;
+            Ok(conn.last_insert_rowid())
+        });
+        match result {
+            Ok(id) => {
+                self.focus_report = None;
+                self.focus_session = Some(FocusSession {
+                    id,
+                    started_ts,
+                    ends_ts,
+                });
+                self.log_event(&format!("focus session {id} started for {} min", self.focus_minutes));
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn finish_focus_session(&mut self, stopped_early: bool) {
+        let Some(session) = self.focus_session.take() else {
+            return;
+        };
+        let ended_ts = if stopped_early {
+            unix_seconds_now().min(session.ends_ts)
+        } else {
+            session.ends_ts
+        };
+        let result = self.open_tracking_db().and_then(|conn| {
+            conn.execute(
+                "UPDATE focus_sessions SET stopped_ts = ?1 WHERE id = ?2",
+                params![ended_ts, session.id],
+            )
+            .context("failed to stop focus session")?;
+            load_focus_violation_counts(&conn, session.id)
+        });
+        match result {
+            Ok(violations) => {
+                self.log_event(&format!(
+                    "focus session {} ended with {} violation(s)",
+                    session.id,
+                    violations.iter().map(|(_, count)| count).sum::<i64>()
+                ));
+                self.focus_report = Some(FocusReport {
+                    started_ts: session.started_ts,
+                    ended_ts,
+                    violations,
+                });
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn run_focus_session_timer(&mut self, ctx: &egui::Context) {
+        let Some(session) = &self.focus_session else {
+            return;
+        };
+        if unix_seconds_now() >= session.ends_ts {
+            self.finish_focus_session(false);
+        } else {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+
+    fn refresh_events(&mut self) {
+        match self.open_tracking_db().and_then(|conn| load_events_from_db(&conn)) {
+            Ok(events) => self.events = events,
+            Err(err) => self.log_event(&format!("notification load failed: {err:#}")),
+        }
+    }
+
+    fn run_anomaly_check(&mut self) {
+        if self
+            .anomaly_checked_at
+            .is_some_and(|checked_at| checked_at.elapsed() < ANOMALY_CHECK_INTERVAL)
+        {
+            return;
+        }
+        self.anomaly_checked_at = Some(Instant::now());
+        let now = unix_seconds_now();
+        let today = Local::now().date_naive();
+        let Some(day_start) = local_midnight_ts(today) else {
+            return;
+        };
+        let result = self.open_tracking_db().and_then(|conn| {
+            let anomalies = detect_usage_anomalies(
+                &conn,
+                today,
+                now,
+                &self.app_categories,
+                &self.title_category_rules,
+                &self.days_off,
+            )?;
+            let mut inserted = 0;
+            for anomaly in anomalies {
+                let subject = anomaly.subject();
+                let already_flagged: bool = conn
+                    .query_row(
+                        "SELECT EXISTS(SELECT 1 FROM notifications WHERE kind = 'anomaly' AND app_key = ?1 AND ts >= ?2)",
+                        params![subject, day_start],
+                        |row| row.get(0),
+                    )
+                    .context("failed to query anomaly notifications")?;
+                if already_flagged {
+                    continue;
+                }
+                conn.execute(
+                    "INSERT INTO notifications (ts, kind, app_key, message) VALUES (?1, 'anomaly', ?2, ?3)",
+                    params![now, subject, self.anomaly_message(anomaly, now)],
+                )
+                .context("failed to insert anomaly notification")?;
+                inserted += 1;
+            }
+            Ok(inserted)
+        });
+        match result {
+            Ok(0) => {}
+            Ok(_) => self.refresh_events(),
+            Err(err) => self.log_event(&format!("anomaly check failed: {err:#}")),
+        }
+    }
+
+    fn refresh_forecast(&mut self) {
+        if self
+            .forecast_refreshed_at
+            .is_some_and(|refreshed_at| refreshed_at.elapsed() < FORECAST_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.forecast_refreshed_at = Some(Instant::now());
+        let now = unix_seconds_now();
+        let today = Local::now().date_naive();
+        let Some(day_start) = local_midnight_ts(today) else {
+            return;
+        };
+        let cached_profile = self
+            .forecast_profile
+            .filter(|(day, _)| *day == today)
+            .map(|(_, profile)| profile);
+        let result = self.open_tracking_db().and_then(|conn| {
+            let profile = match cached_profile {
+                Some(profile) => profile,
+                None => load_hourly_activity_profile(&conn, today, &self.days_off)?,
+            };
+            let mut active_secs = 0;
+            for_each_segment_in_range(&conn, day_start, now, |seg| {
+                if !seg.is_idle {
+                    active_secs += (seg.end_ts.min(now) - seg.start_ts.max(day_start)).max(0);
+                }
+            })?;
+            Ok((profile, active_secs))
+        });
+        match result {
+            Ok((profile, active_secs)) => {
+                self.forecast_profile = Some((today, profile));
+                self.forecast_secs = profile
+                    .and_then(|profile| project_day_total(active_secs, &profile, now - day_start));
+            }
+            Err(err) => {
+                self.forecast_secs = None;
+                self.log_event(&format!("forecast failed: {err:#}"));
+            }
+        }
+    }
+
+    fn anomaly_message(&self, anomaly: UsageAnomaly, now: i64) -> String {
+        match anomaly {
+            UsageAnomaly::AboveNormal { category, ratio } => self
+                .t("anomaly_above_normal")
+                .replace("{category}", category.ui_label(self.ui_language))
+                .replace("{ratio}", &format!("{ratio:.1}")),
+            UsageAnomaly::Missing { category } => self
+                .t("anomaly_missing")
+                .replace("{category}", category.ui_label(self.ui_language))
+                .replace("{time}", &format_hms(now, self.clock_format)),
+        }
+    }
+
+    fn run_events_update(&mut self, sql: &str, id: Option<i64>) {
+        let result = self.open_tracking_db().and_then(|conn| {
+            match id {
+                Some(id) => conn.execute(sql, params![id]),
+                None => conn.execute(sql, []),
+            }
+            .context("failed to update notifications")
+        });
+        if let Err(err) = result {
+            self.clear_info_message();
+            self.error = Some(format!("{err:#}"));
+        }
+        self.refresh_events();
+    }
+
+    fn set_app_alias(&mut self, app_key: &str, alias: &str) {
+        let alias = alias.trim();
+        let result = self.open_tracking_db().and_then(|conn| {
+            if alias.is_empty() {
+                conn.execute("DELETE FROM app_aliases WHERE app_key = ?1", params![app_key])
+            } else {
+                conn.execute(
+                    "INSERT OR REPLACE INTO app_aliases (app_key, alias) VALUES (?1, ?2)",
+                    params![app_key, alias],
+                )
+            }
+            .context("failed to save app alias")
+        });
+        match result {
+            Ok(_) => {
+                if alias.is_empty() {
+                    self.app_aliases.remove(app_key);
+                } else {
+                    self.app_aliases.insert(app_key.to_owned(), alias.to_owned());
+                }
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    // Excluded apps keep being recorded; they are only left out of the summary and timeline.
+    fn set_app_excluded(&mut self, app_key: &str, excluded: bool) {
+        let result = self.open_tracking_db().and_then(|conn| {
+            if excluded {
+                conn.execute(
+                    "INSERT OR IGNORE INTO excluded_apps (app_key) VALUES (?1)",
+                    params![app_key],
+                )
+            } else {
+                conn.execute("DELETE FROM excluded_apps WHERE app_key = ?1", params![app_key])
+            }
+            .context("failed to save excluded app")
+        });
+        match result {
+            Ok(_) => {
+                if excluded {
+                    self.excluded_app_keys.insert(app_key.to_owned());
+                    self.selected_app_keys.remove(app_key);
+                } else {
+                    self.excluded_app_keys.remove(app_key);
+                }
+                self.invalidate_timeline_cache();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn event_message(&self, event: &EventEntry) -> String {
+        match (event.kind.as_str(), event.app_key.as_deref()) {
+            ("new_app", Some(app_key)) => format!("{}: {app_key}", self.t("new_app_seen")),
+            _ => event.message.clone(),
+        }
+    }
+
+    fn draw_events_button(&mut self, ui: &mut egui::Ui) {
+        let unread = self.events.iter().filter(|event| !event.read).count();
+        let label = if unread > 0 {
+            format!("{} ({unread})", self.t("events"))
+        } else {
+            self.t("events").to_owned()
+        };
+        let button = ui.selectable_label(unread > 0, label);
+        let popup_id = ui.make_persistent_id("events_popup");
+        if button.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+            if unread > 0 {
+                self.run_events_update("UPDATE notifications SET read = 1 WHERE read = 0", None);
+            }
+        }
+        egui::popup::popup_below_widget(
+            ui,
+            popup_id,
+            &button,
+            egui::popup::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| self.draw_events_popup(ui),
+        );
+    }
+
+    fn draw_events_popup(&mut self, ui: &mut egui::Ui) {
+        ui.set_min_width(340.0);
+        if self.events.is_empty() {
+            ui.label(egui::RichText::new(self.t("no_events")).weak());
         }
-        self.ui_language = language;
-        if let Err(err) = persist_ui_language(&self.settings_path, self.ui_language) {
-            self.clear_info_message();
-            self.error = Some(format!("failed to save language setting: {err:#}"));
+        let mut dismiss_id = None;
+        let mut category_change = None;
+        let mut alias_change = None;
+        let mut exclude_key = None;
+        let events = self.events.clone();
+        egui::ScrollArea::vertical()
+            .id_salt("events_scroll")
+            .max_height(320.0)
+            .show(ui, |ui| {
+                for event in &events {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(format_hms(event.ts, self.clock_format)).weak());
+                        ui.label(self.event_message(event));
+                    });
+                    ui.horizontal(|ui| {
+                        if let (Some(app_key), "new_app") = (event.app_key.as_deref(), event.kind.as_str()) {
+                            let current = self.app_categories.get(app_key).copied();
+                            for category in AppCategory::ALL {
+                                if ui
+                                    .selectable_label(current == Some(category), category.ui_label(self.ui_language))
+                                    .clicked()
+                                {
+                                    category_change = Some((app_key.to_owned(), category));
+                                }
+                            }
+                            if self.event_alias_target.as_deref() == Some(app_key) {
+                                let response = ui.add(
+                                    egui::TextEdit::singleline(&mut self.event_alias_input).desired_width(100.0),
+                                );
+                                if ui.small_button(self.t("set_alias")).clicked()
+                                    || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+                                {
+                                    alias_change = Some((app_key.to_owned(), self.event_alias_input.clone()));
+                                }
+                            } else if ui.small_button(self.t("alias")).clicked() {
+                                self.event_alias_target = Some(app_key.to_owned());
+                                self.event_alias_input = self.app_aliases.get(app_key).cloned().unwrap_or_default();
+                            }
+                            if !self.excluded_app_keys.contains(app_key) && ui.small_button(self.t("exclude")).clicked() {
+                                exclude_key = Some(app_key.to_owned());
+                            }
+                        }
+                        if ui.small_button(self.t("dismiss")).clicked() {
+                            dismiss_id = Some(event.id);
+                        }
+                    });
+                    ui.separator();
+                }
+            });
+        if let Some((app_key, category)) = category_change {
+            self.set_app_category(&app_key, Some(category));
+        }
+        if let Some((app_key, alias)) = alias_change {
+            self.set_app_alias(&app_key, &alias);
+            self.event_alias_target = None;
+        }
+        if let Some(app_key) = exclude_key {
+            self.set_app_excluded(&app_key, true);
+        }
+        if let Some(id) = dismiss_id {
+            self.run_events_update("DELETE FROM notifications WHERE id = ?1", Some(id));
+        }
+        if !self.events.is_empty() && ui.small_button(self.t("clear_all")).clicked() {
+            self.run_events_update("DELETE FROM notifications", None);
+        }
+
+        if !self.excluded_app_keys.is_empty() {
+            ui.separator();
+            ui.label(egui::RichText::new(self.t("excluded_apps")).strong());
+            let mut restore_key = None;
+            let mut excluded: Vec<&String> = self.excluded_app_keys.iter().collect();
+            excluded.sort();
+            for app_key in excluded {
+                ui.horizontal(|ui| {
+                    ui.label(app_key);
+                    if ui.small_button(self.t("restore")).clicked() {
+                        restore_key = Some(app_key.clone());
+                    }
+                });
+            }
+            if let Some(app_key) = restore_key {
+                self.set_app_excluded(&app_key, false);
+            }
         }
     }
 
-    fn seed_app_color_cache_from_cached_visuals(&mut self) {
-        for (app_key, visual) in &self.cached_app_visuals {
-            self.app_color_cache.insert(app_key.clone(), visual.color);
-            if let Some(path) = visual.process_path.as_deref() {
-                self.icon_color_cache
-                    .entry(path.to_owned())
-                    .or_insert(visual.color);
-                if let Some(name) = visual
-                    .display_name
-                    .as_deref()
-                    .map(str::trim)
-                    .filter(|value| !value.is_empty())
-                {
-                    self.process_display_name_cache
-                        .entry(path.to_owned())
-                        .or_insert(name.to_owned());
+    fn draw_timeline_heading(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(self.t("timeline"));
+            ui.add_space(12.0);
+            let follow_label = self.t("follow_now");
+            let follow_hint = self.t("follow_now_hint");
+            if ui
+                .checkbox(&mut self.timeline_follow_now, follow_label)
+                .on_hover_text(follow_hint)
+                .changed()
+            {
+                if let Err(err) = persist_settings_values(
+                    &self.settings_path,
+                    &[("timeline_follow_now", json!(self.timeline_follow_now))],
+                ) {
+                    self.clear_info_message();
+                    self.error = Some(format!("failed to save follow setting: {err:#}"));
                 }
             }
+        });
+        ui.add_space(8.0);
+    }
+
+    fn draw_hourly_pattern_widget(&mut self, ui: &mut egui::Ui, range_start: i64, range_end: i64) {
+        let timeline_segments = self.ensure_timeline_cache(range_start, range_end);
+        draw_hourly_density_ruler(
+            ui,
+            range_start,
+            range_end,
+            timeline_segments.as_slice(),
+            &mut self.timeline_view_range,
+            self.duration_format,
+            self.clock_format,
+        );
+    }
+
+    fn draw_timeline_widget(
+        &mut self,
+        ui: &mut egui::Ui,
+        range_start: i64,
+        range_end: i64,
+        now_ts: Option<i64>,
+    ) {
+        let timeline_segments = self.ensure_timeline_cache(range_start, range_end);
+        if self.show_git_markers && !self.git_repositories.is_empty() {
+            self.ensure_git_commits(range_start, range_end);
         }
+        let commit_markers = self
+            .git_commit_cache
+            .as_ref()
+            .filter(|_| self.show_git_markers)
+            .map(|cache| cache.commits.as_slice())
+            .unwrap_or_default();
+        let live_segment = self.backend_status.live_segment.clone().filter(|live| {
+            live.end_ts > range_start
+                && live.start_ts < range_end
+                && self
+                    .effective_timeline_filter_keys()
+                    .is_none_or(|keys| keys.contains(&normalize_app_key(&live.app_name)))
+        });
+        let tag_request = draw_timeline(
+            ui,
+            range_start,
+            range_end,
+            timeline_segments.as_slice(),
+            &mut self.timeline_view_range,
+            &self.icon_color_cache,
+            &self.app_color_cache,
+            self.summary_rows.as_slice(),
+            &self.process_display_name_cache,
+            self.ui_language,
+            self.duration_format,
+            self.clock_format,
+            live_segment.as_ref(),
+            now_ts,
+            commit_markers,
+            &self.segment_tags,
+            &mut self.timeline_selection,
+            &mut self.timeline_focus,
+        );
+        if let Some(target) = tag_request {
+            self.tag_input.clear();
+            self.tag_target = Some(target);
+        }
+        ui.add_space(8.0);
     }
 
-    fn load_cached_app_visuals(&mut self) {
-        let conn = match Connection::open(&self.db_path) {
-            Ok(conn) => conn,
-            Err(_) => return,
+    // Only the last visible widget may take the remaining height; earlier placement
+    // caps the list so the widgets below it stay on screen.
+    fn draw_top_apps_widget(&mut self, ctx: &egui::Context, ui: &mut egui::Ui, fill_remaining: bool) {
+        draw_section_header(ui, self.t("top_apps"));
+        ui.add_space(6.0);
+        self.draw_summary_filter_input(ui);
+        if let Some(tag) = self.tag_filter.clone() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}: {tag}", self.t("tag_filter")));
+                if ui.small_button("x").clicked() {
+                    self.set_tag_filter(None);
+                }
+            });
+        }
+        if self.summary_fleeting_secs > 0 {
+            let fleeting_hint = self.t("noise_threshold_hint");
+            ui.label(
+                egui::RichText::new(format!(
+                    "{}: {}",
+                    self.t("fleeting_switches"),
+                    format_duration(self.summary_fleeting_secs, self.duration_format)
+                ))
+                .weak(),
+            )
+            .on_hover_text(fleeting_hint);
+        }
+        if self.summary_call_secs > 0 {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{}: {}",
+                    self.t("in_calls"),
+                    format_duration(self.summary_call_secs, self.duration_format)
+                ))
+                .weak(),
+            )
+            .on_hover_text(self.t("in_calls_hint"));
+        }
+        if self.summary_away_secs > 0 {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{}: {}",
+                    self.t("away"),
+                    format_duration(self.summary_away_secs, self.duration_format)
+                ))
+                .weak(),
+            )
+            .on_hover_text(self.t("away_hint"));
+        }
+        ui.add_space(4.0);
+        let max_height = if fill_remaining {
+            ui.available_height().max(0.0)
+        } else {
+            DASHBOARD_TOP_APPS_MAX_HEIGHT.min(ui.available_height().max(0.0))
         };
-        if conn.busy_timeout(Duration::from_millis(500)).is_err() {
-            return;
+        egui::ScrollArea::vertical()
+            .id_salt("top_apps_scroll")
+            .auto_shrink([false, !fill_remaining])
+            .max_height(max_height)
+            .show(ui, |ui| {
+                self.draw_summary_rows(ctx, ui);
+            });
+        if !fill_remaining {
+            ui.add_space(8.0);
+        }
+    }
+
+    fn set_dashboard_layout(&mut self, layout: Vec<DashboardPanel>) {
+        self.dashboard_layout = layout;
+        let value: Vec<_> = self
+            .dashboard_layout
+            .iter()
+            .map(|panel| json!({ "widget": panel.widget.code(), "visible": panel.visible }))
+            .collect();
+        if let Err(err) = persist_settings_values(&self.settings_path, &[("dashboard_layout", json!(value))]) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save dashboard layout: {err:#}"));
+        }
+    }
+
+    fn draw_dashboard_button(&mut self, ui: &mut egui::Ui) {
+        let button = ui.button(self.t("layout"));
+        let popup_id = ui.make_persistent_id("dashboard_popup");
+        if button.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+        }
+        egui::popup::popup_below_widget(
+            ui,
+            popup_id,
+            &button,
+            egui::popup::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| self.draw_dashboard_popup(ui),
+        );
+    }
+
+    fn draw_dashboard_popup(&mut self, ui: &mut egui::Ui) {
+        ui.set_min_width(200.0);
+        ui.label(egui::RichText::new(self.t("layout_drag_hint")).weak());
+        let mut layout = self.dashboard_layout.clone();
+        let mut changed = false;
+        let mut moved: Option<(usize, usize)> = None;
+        for (idx, panel) in layout.iter_mut().enumerate() {
+            let label = self.t(panel.widget.label_key());
+            let row = ui
+                .horizontal(|ui| {
+                    ui.dnd_drag_source(egui::Id::new(("dashboard_widget", idx)), idx, |ui| {
+                        ui.label("\u{2261}");
+                    });
+                    changed |= ui.checkbox(&mut panel.visible, label).changed();
+                })
+                .response;
+            if let Some(dragged) = row.dnd_release_payload::<usize>() {
+                moved = Some((*dragged, idx));
+            } else if row.dnd_hover_payload::<usize>().is_some() {
+                ui.painter().hline(
+                    row.rect.x_range(),
+                    row.rect.top(),
+                    ui.visuals().selection.stroke,
+                );
+            }
+        }
+        if let Some((from, to)) = moved.filter(|(from, to)| from != to) {
+            let panel = layout.remove(from);
+            layout.insert(to, panel);
+            changed = true;
+        }
+        if ui.small_button(self.t("layout_reset")).clicked() {
+            layout = default_dashboard_layout();
+            changed = true;
+        }
+        if changed {
+            self.set_dashboard_layout(layout);
+        }
+    }
+
+    fn draw_focus_button(&mut self, ui: &mut egui::Ui) {
+        let label = match &self.focus_session {
+            Some(session) => {
+                let remaining = (session.ends_ts - unix_seconds_now()).max(0);
+                format!(
+                    "{} {:02}:{:02}",
+                    self.t("focus"),
+                    remaining / 60,
+                    remaining % 60
+                )
+            }
+            None => self.t("focus").to_owned(),
+        };
+        let button = ui.selectable_label(self.focus_session.is_some(), label);
+        let popup_id = ui.make_persistent_id("focus_popup");
+        if button.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(popup_id));
+        }
+        egui::popup::popup_below_widget(
+            ui,
+            popup_id,
+            &button,
+            egui::popup::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| self.draw_focus_popup(ui),
+        );
+    }
+
+    fn draw_focus_popup(&mut self, ui: &mut egui::Ui) {
+        ui.set_min_width(240.0);
+        if self.focus_session.is_some() {
+            if ui.button(self.t("stop_focus")).clicked() {
+                self.finish_focus_session(true);
+            }
+        } else {
+            ui.horizontal(|ui| {
+                ui.label(self.t("focus_minutes"));
+                ui.add(egui::DragValue::new(&mut self.focus_minutes).range(5..=240));
+            });
+            let minimize_label = self.t("focus_minimize");
+            ui.checkbox(&mut self.focus_minimize, minimize_label);
+            let has_distracting = self
+                .app_categories
+                .values()
+                .any(|category| *category == AppCategory::Distracting);
+            if !has_distracting {
+                ui.label(egui::RichText::new(self.t("focus_no_distracting")).weak());
+            }
+            if ui.button(self.t("start_focus")).clicked() {
+                self.start_focus_session();
+            }
         }
-        if ensure_tracking_schema(&conn).is_err() {
+
+        let Some(report) = &self.focus_report else {
+            return;
+        };
+        ui.separator();
+        ui.strong(format!(
+            "{}: {} - {}",
+            self.t("last_focus"),
+            format_hms(report.started_ts, self.clock_format),
+            format_hms(report.ended_ts, self.clock_format)
+        ));
+        if report.violations.is_empty() {
+            ui.label(self.t("focus_clean"));
             return;
         }
-        if let Ok(cached) = load_cached_app_visuals_from_db(&conn) {
-            self.cached_app_visuals = cached;
-            self.seed_app_color_cache_from_cached_visuals();
+        let total: i64 = report.violations.iter().map(|(_, count)| count).sum();
+        ui.label(format!("{}: {total}", self.t("focus_breaks")));
+        for (app_key, count) in &report.violations {
+            ui.label(format!("{app_key}: {count}"));
+        }
+    }
+
+    fn draw_summary_row_menu(&mut self, ui: &mut egui::Ui, app_key: &str) {
+        let pin_label = if self.pinned_app_keys.contains(app_key) {
+            self.t("unpin_app")
+        } else {
+            self.t("pin_app")
+        };
+        if ui.button(pin_label).clicked() {
+            self.toggle_app_pin(app_key);
+            ui.close_menu();
+        }
+        ui.separator();
+        ui.label(egui::RichText::new(self.t("category")).weak());
+        let current = self.app_categories.get(app_key).copied();
+        for category in AppCategory::ALL {
+            if ui
+                .radio(current == Some(category), category.ui_label(self.ui_language))
+                .clicked()
+            {
+                self.set_app_category(app_key, Some(category));
+                ui.close_menu();
+            }
+        }
+        if ui.radio(current.is_none(), self.t("uncategorized")).clicked() {
+            self.set_app_category(app_key, None);
+            ui.close_menu();
+        }
+        ui.separator();
+        if ui.button(self.t("title_rules_menu")).clicked() {
+            self.show_category_rules_window = true;
+            ui.close_menu();
         }
     }
 
@@ -577,8 +4407,9 @@ impl TimelineApp {
             let rebuilt = build_timeline_segments(
                 range_start,
                 range_end,
-                &self.segments,
-                &timeline_filter_keys,
+                &coalesce_away(&self.segments),
+                timeline_filter_keys.as_ref(),
+                self.selected_app_keys.len() == 1,
             );
             self.timeline_segments_cache = Arc::new(rebuilt);
             self.timeline_cache_range = Some(active_range);
@@ -587,10 +4418,66 @@ impl TimelineApp {
         Arc::clone(&self.timeline_segments_cache)
     }
 
-    fn visible_summary_count(&self) -> usize {
-        match self.summary_limit {
-            Some(limit) => limit.min(self.summary_rows.len()),
-            None => self.summary_rows.len(),
+    // Pinned rows come first and always show; the Items limit is filled with the
+    // remaining rows in sort order.
+    fn visible_summary_indices(&self) -> Vec<usize> {
+        self.partition_summary_rows().0
+    }
+
+    /// Splits the rows matching the filter into those shown and those hidden by the
+    /// Items limit, both in display order.
+    fn partition_summary_rows(&self) -> (Vec<usize>, Vec<usize>) {
+        let filter = SummaryFilter::parse(&self.summary_filter_input);
+        let (mut visible, unpinned): (Vec<usize>, Vec<usize>) = self
+            .summary_rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                let app_key = normalize_app_key(&row.app_name);
+                filter.matches(row)
+                    && !self.excluded_app_keys.contains(&app_key)
+                    && self
+                        .category_filter
+                        .is_none_or(|wanted| self.app_categories.get(&app_key).copied() == wanted)
+            })
+            .map(|(idx, _)| idx)
+            .partition(|idx| {
+                self.pinned_app_keys
+                    .contains(&normalize_app_key(&self.summary_rows[*idx].app_name))
+            });
+        let remaining = match self.summary_limit {
+            Some(limit) => limit.saturating_sub(visible.len()),
+            None => unpinned.len(),
+        };
+        let mut unpinned = unpinned.into_iter();
+        visible.extend(unpinned.by_ref().take(remaining));
+        (visible, unpinned.collect())
+    }
+
+    fn toggle_app_pin(&mut self, app_key: &str) {
+        if !self.pinned_app_keys.remove(app_key) {
+            self.pinned_app_keys.insert(app_key.to_owned());
+        }
+        let mut pinned: Vec<&String> = self.pinned_app_keys.iter().collect();
+        pinned.sort();
+        if let Err(err) = persist_settings_values(&self.settings_path, &[("pinned_apps", json!(pinned))]) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save pinned apps: {err:#}"));
+        }
+        self.retain_selection_within_summary_limit();
+        self.invalidate_timeline_cache();
+    }
+
+    fn draw_summary_filter_input(&mut self, ui: &mut egui::Ui) {
+        let hint = self.t("summary_filter_hint");
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.summary_filter_input)
+                .hint_text(hint)
+                .desired_width(220.0),
+        );
+        if response.changed() {
+            self.retain_selection_within_summary_limit();
+            self.invalidate_timeline_cache();
         }
     }
 
@@ -598,6 +4485,7 @@ impl TimelineApp {
         match self.ui_language {
             UiLanguage::ZhCn => "\u{5168}\u{90E8}",
             UiLanguage::EnUs => "All",
+            UiLanguage::ArSa => bidi::display_static("\u{627}\u{644}\u{643}\u{644}"),
         }
     }
 
@@ -606,42 +4494,56 @@ impl TimelineApp {
             return;
         }
 
-        let show_count = self.visible_summary_count();
-        if show_count < self.summary_rows.len() {
-            let allowed_keys: HashSet<String> = self
-                .summary_rows
+        self.retain_selection_within_summary_limit();
+        self.invalidate_timeline_cache();
+    }
+
+    fn retain_selection_within_summary_limit(&mut self) {
+        let visible = self.visible_summary_indices();
+        if visible.len() < self.summary_rows.len() {
+            let allowed_keys: HashSet<String> = visible
                 .iter()
-                .take(show_count)
-                .map(|row| normalize_app_key(&row.app_name))
+                .map(|idx| normalize_app_key(&self.summary_rows[*idx].app_name))
                 .collect();
             self.selected_app_keys
                 .retain(|selected| allowed_keys.contains(selected));
         }
-        self.invalidate_timeline_cache();
     }
 
-    fn effective_timeline_filter_keys(&self) -> HashSet<String> {
-        let mut limit_keys: HashSet<String> = HashSet::new();
-        let show_count = self.visible_summary_count();
-        let limit_is_active = show_count < self.summary_rows.len();
-        if limit_is_active {
-            for row in self.summary_rows.iter().take(show_count) {
-                limit_keys.insert(normalize_app_key(&row.app_name));
-            }
+    fn set_summary_sort(&mut self, sort: SummarySort) {
+        if self.summary_sort == sort {
+            return;
         }
+        self.summary_sort = sort;
+        sort_summary_rows(&mut self.summary_rows, sort);
+        // Re-sorting changes which rows fall inside the Items limit.
+        self.retain_selection_within_summary_limit();
+        self.invalidate_timeline_cache();
+    }
+
+    /// `None` shows every app; an empty set (a filter matching nothing) shows none.
+    fn effective_timeline_filter_keys(&self) -> Option<HashSet<String>> {
+        let visible = self.visible_summary_indices();
+        let limit_is_active = visible.len() < self.summary_rows.len();
+        let limit_keys: HashSet<String> = visible
+            .iter()
+            .map(|idx| normalize_app_key(&self.summary_rows[*idx].app_name))
+            .collect();
 
         if self.selected_app_keys.is_empty() {
-            return limit_keys;
+            return limit_is_active.then_some(limit_keys);
         }
         if !limit_is_active {
-            return self.selected_app_keys.clone();
+            return Some(self.selected_app_keys.clone());
         }
 
-        self.selected_app_keys
-            .iter()
-            .filter(|key| limit_keys.contains(*key))
-            .cloned()
-            .collect()
+        Some(
+            self.selected_app_keys
+                .iter()
+                .filter(|key| limit_keys.contains(*key))
+                .cloned()
+                .collect(),
+        )
     }
 
     fn drain_reload_results(&mut self) {
@@ -656,8 +4558,14 @@ impl TimelineApp {
             match result.payload {
                 Ok(payload) => {
                     self.segments = payload.segments;
+                    self.segments_aggregated = payload.aggregated;
                     self.summary_rows = payload.summary_rows;
+                    sort_summary_rows(&mut self.summary_rows, self.summary_sort);
                     self.summary_total_secs = payload.summary_total_secs;
+                    self.summary_fleeting_secs = payload.fleeting_secs;
+                    self.summary_call_secs = payload.call_secs;
+                    self.summary_away_secs = payload.away_secs;
+                    self.check_afk_return();
                     let valid_keys: HashSet<String> = self
                         .summary_rows
                         .iter()
@@ -669,6 +4577,7 @@ impl TimelineApp {
                     self.seed_app_color_cache_from_cached_visuals();
                     self.pending_icon_refresh = true;
                     self.error = None;
+                    self.category_totals = payload.category_totals;
                     self.invalidate_timeline_cache();
                 }
                 Err(err) => {
@@ -678,31 +4587,332 @@ impl TimelineApp {
         }
     }
 
-    fn reload(&mut self) {
-        self.last_auto_refresh = Instant::now();
-        let Some((range_start, range_end)) = self.active_range_bounds() else {
-            self.error = Some("failed to resolve active range".to_owned());
+    fn reload(&mut self) {
+        self.last_auto_refresh = Instant::now();
+        self.refresh_events();
+        self.git_commit_cache = None;
+        // Aggregated ranges read their segments back from disk, so only small ranges refresh on every reload.
+        if !self.segments_aggregated {
+            self.workday_cache = None;
+        }
+        self.git_report = None;
+        let Some((range_start, range_end)) = self.active_range_bounds() else {
+            self.error = Some("failed to resolve active range".to_owned());
+            return;
+        };
+
+        crash::note(format!("reload {range_start}-{range_end}"));
+        self.next_reload_request_id = self.next_reload_request_id.wrapping_add(1);
+        let request_id = self.next_reload_request_id;
+        self.pending_reload_request_id = Some(request_id);
+        self.is_reloading = true;
+
+        if self
+            .reload_request_tx
+            .send(ReloadRequest {
+                request_id,
+                db_path: self.db_path.clone(),
+                range_start,
+                range_end,
+                timeline_needed: self.range_preset.is_none() && self.custom_range.is_none(),
+                categories: Some(CategorySnapshot {
+                    app_categories: self.app_categories.clone(),
+                    title_rules: self.title_category_rules.clone(),
+                }),
+                min_segment_secs: self.noise_threshold_secs,
+                tag_spans: self.tag_filter.as_ref().map(|tag| self.merged_tag_spans(tag)),
+            })
+            .is_err()
+        {
+            self.pending_reload_request_id = None;
+            self.is_reloading = false;
+            self.error = Some("reload worker unavailable".to_owned());
+        }
+    }
+
+    fn restore_view_state(&mut self, settings: &serde_json::Map<String, serde_json::Value>) {
+        let parse_date = |value: &serde_json::Value| {
+            NaiveDate::parse_from_str(value.as_str()?, "%Y-%m-%d").ok()
+        };
+        if let Some(theme) = settings
+            .get("theme")
+            .and_then(serde_json::Value::as_str)
+            .and_then(ThemeChoice::from_code)
+        {
+            self.theme_choice = theme;
+        }
+        if let Some(limit) = settings.get("summary_limit").and_then(serde_json::Value::as_u64) {
+            self.summary_limit = Some(limit.max(1) as usize);
+            self.summary_limit_custom_input = limit.max(1).to_string();
+        }
+        if let Some(apps) = settings.get("selected_apps").and_then(serde_json::Value::as_array) {
+            self.selected_app_keys = apps
+                .iter()
+                .filter_map(|app| app.as_str().map(str::to_owned))
+                .collect();
+        }
+        if let Some(range) = settings.get("custom_range").and_then(serde_json::Value::as_array) {
+            if let [start, end] = range.as_slice() {
+                if let (Some(start), Some(end)) = (parse_date(start), parse_date(end)) {
+                    if start <= end {
+                        self.apply_initial_view(InitialView::Custom(start, end));
+                        return;
+                    }
+                }
+            }
+        }
+        if let Some(preset) = settings
+            .get("range_preset")
+            .and_then(serde_json::Value::as_str)
+            .and_then(RangePreset::from_code)
+        {
+            self.apply_initial_view(InitialView::Preset(preset));
+        }
+    }
+
+    fn view_state_snapshot(&self) -> serde_json::Value {
+        let mut selected_apps: Vec<&String> = self.selected_app_keys.iter().collect();
+        selected_apps.sort();
+        json!({
+            "range_preset": self.range_preset.map(RangePreset::code),
+            "custom_range": self.custom_range.map(|(start, end)| {
+                [start.format("%Y-%m-%d").to_string(), end.format("%Y-%m-%d").to_string()]
+            }),
+            "summary_limit": self.summary_limit,
+            "selected_apps": selected_apps,
+            "theme": self.theme_choice.code(),
+            "window_size": self.window_size,
+        })
+    }
+
+    fn persist_view_state_if_changed(&mut self, force: bool) {
+        // The defaults safe mode opened with would overwrite the saved view.
+        if self.safe_mode != SafeMode::Off {
+            return;
+        }
+        if !force && self.view_state_checked_at.elapsed() < VIEW_STATE_SAVE_INTERVAL {
+            return;
+        }
+        self.view_state_checked_at = Instant::now();
+        let snapshot = self.view_state_snapshot();
+        if self.last_saved_view_state.as_ref() == Some(&snapshot) {
+            return;
+        }
+        let serde_json::Value::Object(values) = &snapshot else {
+            return;
+        };
+        let values: Vec<(&str, serde_json::Value)> = values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.clone()))
+            .collect();
+        if let Err(err) = persist_settings_values(&self.settings_path, &values) {
+            eprintln!("failed to save view state: {err:#}");
+        }
+        self.last_saved_view_state = Some(snapshot);
+    }
+
+    fn check_afk_return(&mut self) {
+        if !self.afk_confirm_enabled || self.afk_prompt.is_some() || self.segments_aggregated {
+            return;
+        }
+        let now = unix_seconds_now();
+        let Some(idle) = self
+            .segments
+            .iter()
+            .rev()
+            .find(|seg| seg.is_idle && seg.end_ts - seg.start_ts >= AFK_CONFIRM_MIN_IDLE_SECS)
+        else {
+            return;
+        };
+        if idle.start_ts <= self.afk_handled_idle_start || now - idle.end_ts > AFK_PROMPT_WINDOW_SECS {
+            return;
+        }
+        let window_start = idle.start_ts - AFK_MAX_RECLASSIFY_MINUTES * 60;
+        let before: Vec<&Segment> = self
+            .segments
+            .iter()
+            .filter(|seg| !seg.is_idle && seg.end_ts <= idle.start_ts && seg.end_ts > window_start)
+            .collect();
+        let Some(last) = before.iter().max_by_key(|seg| seg.end_ts) else {
+            return;
+        };
+        let earliest = before
+            .iter()
+            .map(|seg| seg.start_ts)
+            .min()
+            .unwrap_or(idle.start_ts)
+            .max(window_start);
+        let max_minutes = ((idle.start_ts - earliest + 59) / 60).max(1);
+        self.afk_prompt = Some(AfkPrompt {
+            idle_start: idle.start_ts,
+            idle_end: idle.end_ts,
+            last_app: display_app_name(&last.app_name, false),
+            max_minutes,
+            reclassify_minutes: AFK_DEFAULT_RECLASSIFY_MINUTES.min(max_minutes),
+        });
+    }
+
+    fn resolve_afk_prompt(&mut self, reclassify: bool) {
+        let Some(prompt) = self.afk_prompt.take() else {
+            return;
+        };
+        self.afk_handled_idle_start = prompt.idle_start;
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[("afk_handled_idle_start", json!(prompt.idle_start))],
+        ) {
+            self.log_event(&format!("afk state save failed: {err:#}"));
+        }
+        if !reclassify {
+            return;
+        }
+        let cutoff_ts = prompt.idle_start - prompt.reclassify_minutes * 60;
+        let result = self
+            .open_tracking_db()
+            .and_then(|mut conn| reclassify_as_idle(&mut conn, cutoff_ts, prompt.idle_start, unix_seconds_now()));
+        match result {
+            Ok(()) => {
+                self.log_event(&format!(
+                    "reclassified {} min before idle at {} as idle",
+                    prompt.reclassify_minutes,
+                    format_hms(prompt.idle_start, self.clock_format)
+                ));
+                self.reload();
+            }
+            Err(err) => {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
+            }
+        }
+    }
+
+    fn draw_afk_window_content(&mut self, ui: &mut egui::Ui) {
+        let Some(prompt) = &self.afk_prompt else {
+            return;
+        };
+        ui.label(format!(
+            "{} {} ({} - {})",
+            self.t("afk_away_for"),
+            format_duration(prompt.idle_end - prompt.idle_start, self.duration_format),
+            format_hms(prompt.idle_start, self.clock_format),
+            format_hms(prompt.idle_end, self.clock_format)
+        ));
+        ui.label(format!("{}: {}", self.t("afk_last_activity"), prompt.last_app));
+        ui.add_space(6.0);
+        let max_minutes = prompt.max_minutes;
+        let mut minutes = prompt.reclassify_minutes;
+        ui.horizontal(|ui| {
+            ui.label(self.t("afk_reclassify_minutes"));
+            ui.add(egui::DragValue::new(&mut minutes).range(1..=max_minutes).suffix(" min"));
+        });
+        if let Some(prompt) = &mut self.afk_prompt {
+            prompt.reclassify_minutes = minutes;
+        }
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            if ui.button(self.t("afk_keep")).clicked() {
+                self.resolve_afk_prompt(false);
+            }
+            if ui.button(self.t("afk_mark_idle")).clicked() {
+                self.resolve_afk_prompt(true);
+            }
+        });
+    }
+
+    fn set_noise_threshold(&mut self, threshold_secs: i64) {
+        self.noise_threshold_secs = threshold_secs.clamp(0, MAX_NOISE_THRESHOLD_SECS);
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[("noise_threshold_secs", json!(self.noise_threshold_secs))],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save noise threshold: {err:#}"));
+        }
+        self.reload();
+    }
+
+    fn set_duration_format(&mut self, format: DurationFormat) {
+        if self.duration_format == format {
+            return;
+        }
+        self.duration_format = format;
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[("duration_format", json!(format.code()))],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save duration format: {err:#}"));
+        }
+    }
+
+    fn set_clock_format(&mut self, format: ClockFormat) {
+        if self.clock_format == format {
             return;
-        };
+        }
+        self.clock_format = format;
+        self.invalidate_timeline_cache();
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[("clock_format", json!(format.code()))],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save clock format: {err:#}"));
+        }
+    }
 
-        self.next_reload_request_id = self.next_reload_request_id.wrapping_add(1);
-        let request_id = self.next_reload_request_id;
-        self.pending_reload_request_id = Some(request_id);
-        self.is_reloading = true;
+    fn set_theme_choice(&mut self, ctx: &egui::Context, theme: ThemeChoice) {
+        if self.theme_choice != theme {
+            self.theme_choice = theme;
+            ctx.set_theme(theme.preference());
+            self.persist_view_state_if_changed(true);
+        }
+    }
 
-        if self
-            .reload_request_tx
-            .send(ReloadRequest {
-                request_id,
-                db_path: self.db_path.clone(),
-                range_start,
-                range_end,
-            })
-            .is_err()
-        {
-            self.pending_reload_request_id = None;
-            self.is_reloading = false;
-            self.error = Some("reload worker unavailable".to_owned());
+    fn apply_initial_view(&mut self, view: InitialView) {
+        match view {
+            InitialView::Date(date) => {
+                self.selected_date = date;
+                self.calendar_month = month_start(date);
+                self.range_preset = None;
+                self.custom_range = None;
+                self.reset_range_inputs_for_selected_date();
+            }
+            InitialView::Preset(preset) => {
+                self.range_preset = Some(preset);
+                self.custom_range = None;
+            }
+            InitialView::Custom(start, end) => {
+                self.selected_date = end;
+                self.range_preset = None;
+                self.calendar_month = month_start(start);
+                self.custom_range = Some((start, end));
+                self.custom_start_input = start.format("%Y-%m-%d").to_string();
+                self.custom_end_input = end.format("%Y-%m-%d").to_string();
+            }
+        }
+    }
+
+    fn draw_jump_to_date_input(&mut self, ui: &mut egui::Ui) {
+        let today = Local::now().date_naive();
+        let trimmed = self.jump_date_input.trim();
+        let parsed = parse_date_input(trimmed, today);
+        let is_invalid = !trimmed.is_empty() && parsed.is_none();
+        let mut edit = egui::TextEdit::singleline(&mut self.jump_date_input)
+            .hint_text(tr(self.ui_language, "jump_to_date"))
+            .desired_width(110.0);
+        if is_invalid {
+            edit = edit.text_color(Color32::from_rgb(190, 56, 56));
+        }
+        let response = ui.add(edit).on_hover_text(if is_invalid {
+            tr(self.ui_language, "unrecognized_date")
+        } else {
+            tr(self.ui_language, "jump_to_date_hint")
+        });
+        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            if let Some(date) = parsed {
+                self.jump_date_input.clear();
+                self.set_selected_date(date);
+            }
         }
     }
 
@@ -857,11 +5067,37 @@ impl TimelineApp {
                     } else if date == today {
                         button = button
                             .frame(true)
-                            .fill(Color32::TRANSPARENT)
+                            .fill(self.days_off.get(&date).map_or(Color32::TRANSPARENT, |day_off| day_off.kind.shade()))
                             .stroke(Stroke::new(1.0, Color32::from_rgb(80, 130, 210)));
+                    } else if let Some(day_off) = self.days_off.get(&date) {
+                        button = button.frame(true).fill(day_off.kind.shade()).stroke(Stroke::NONE);
                     }
 
-                    let response = ui.add(button);
+                    let mut response = ui.add(button);
+                    if let Some(day_off) = self.days_off.get(&date) {
+                        let kind_label = self.t(day_off.kind.label_key());
+                        response = if day_off.label.is_empty() {
+                            response.on_hover_text(kind_label)
+                        } else {
+                            response.on_hover_text(format!("{kind_label}: {}", day_off.label))
+                        };
+                    }
+                    response.context_menu(|ui| {
+                        for kind in [DayOffKind::Vacation, DayOffKind::Holiday] {
+                            let key = match kind {
+                                DayOffKind::Vacation => "day_off_mark_vacation",
+                                DayOffKind::Holiday => "day_off_mark_holiday",
+                            };
+                            if ui.button(self.t(key)).clicked() {
+                                self.set_day_off(date, Some(DayOff { kind, label: String::new() }));
+                                ui.close_menu();
+                            }
+                        }
+                        if self.days_off.contains_key(&date) && ui.button(self.t("day_off_clear")).clicked() {
+                            self.set_day_off(date, None);
+                            ui.close_menu();
+                        }
+                    });
                     if response.hovered() && date != selected_date {
                         let hover_rect = response.rect.shrink2(egui::vec2(1.0, 1.0));
                         ui.painter().rect_filled(
@@ -982,13 +5218,144 @@ impl TimelineApp {
         }
 
         let total_secs = self.summary_total_secs;
-        let show_count = self.visible_summary_count();
+        let (visible, hidden) = self.partition_summary_rows();
+        if visible.is_empty() && hidden.is_empty() {
+            ui.label(self.t("no_data"));
+            return;
+        }
 
-        for row_idx in 0..show_count {
+        if self.show_session_columns {
+            self.draw_summary_column_header(ui);
+        }
+        for row_idx in visible {
             if let Some(row) = self.summary_rows.get(row_idx).cloned() {
                 self.draw_summary_row(ctx, ui, row_idx, &row, total_secs);
             }
         }
+        if hidden.is_empty() {
+            return;
+        }
+
+        let other_secs = hidden
+            .iter()
+            .map(|idx| self.summary_rows[*idx].duration_secs.max(0))
+            .sum();
+        if self.draw_other_summary_row(ui, hidden.len(), other_secs, total_secs) {
+            self.summary_other_expanded = !self.summary_other_expanded;
+        }
+        if self.summary_other_expanded {
+            for row_idx in hidden {
+                if let Some(row) = self.summary_rows.get(row_idx).cloned() {
+                    self.draw_summary_row(ctx, ui, row_idx, &row, total_secs);
+                }
+            }
+        }
+    }
+
+    // Stands in for the rows the Items limit hides so the shown percentages still add
+    // up to the range total; clicking it lists those rows underneath.
+    fn draw_other_summary_row(
+        &self,
+        ui: &mut egui::Ui,
+        hidden_count: usize,
+        other_secs: i64,
+        total_secs: i64,
+    ) -> bool {
+        let dark_mode = ui.visuals().dark_mode;
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 24.0), Sense::click());
+        if response.hovered() {
+            let hover_fill = if dark_mode {
+                Color32::from_rgb(48, 48, 48)
+            } else {
+                Color32::from_rgb(230, 230, 230)
+            };
+            ui.painter().rect_filled(rect, 4.0, hover_fill);
+        }
+
+        let ratio = if total_secs > 0 {
+            (other_secs as f32 / total_secs as f32).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let layout =
+            SummaryRowLayout::compute(summary_row_content_rect(rect), self.show_session_columns, self.ui_language.is_rtl());
+        let painter = ui.painter();
+        let text_color = ui.visuals().weak_text_color();
+        let chevron = if self.summary_other_expanded {
+            "\u{25BE}"
+        } else {
+            "\u{25B8}"
+        };
+        let label = match self.ui_language {
+            UiLanguage::ZhCn => format!("{chevron} \u{5176}\u{4ED6}\u{FF08}{hidden_count} \u{4E2A}\u{5E94}\u{7528}\u{FF09}"),
+            UiLanguage::EnUs => format!("{chevron} Other ({hidden_count} apps)"),
+            UiLanguage::ArSa => bidi::display_rtl(&format!("{chevron} \u{623}\u{62E}\u{631}\u{649} ({hidden_count} \u{62A}\u{637}\u{628}\u{64A}\u{642})")).into_owned(),
+        };
+        let (label_pos, label_align) = layout.leading(layout.name_rect, 4.0);
+        painter.with_clip_rect(layout.name_rect).text(
+            label_pos,
+            label_align,
+            label,
+            FontId::proportional(14.0),
+            text_color,
+        );
+
+        let bar_shape = Rect::from_center_size(
+            layout.bar_rect.center(),
+            egui::vec2(layout.bar_rect.width(), 18.0),
+        );
+        let (bar_bg, fill_color) = if dark_mode {
+            (Color32::from_rgb(68, 68, 68), Color32::from_rgb(110, 110, 110))
+        } else {
+            (Color32::from_rgb(240, 240, 240), Color32::from_rgb(176, 176, 176))
+        };
+        painter.rect_filled(bar_shape, 9.0, bar_bg);
+        if ratio > 0.0 {
+            let fill_w = (bar_shape.width() * ratio).clamp(0.0, bar_shape.width());
+            let fill_rect = layout.leading_part(bar_shape, fill_w);
+            painter.rect_filled(fill_rect, 9.0, fill_color);
+        }
+        painter.text(
+            bar_shape.center(),
+            Align2::CENTER_CENTER,
+            format!("{:>5.1}%", ratio * 100.0),
+            FontId::monospace(13.0),
+            text_color,
+        );
+        let (duration_pos, duration_align) = layout.trailing(layout.duration_rect);
+        painter.with_clip_rect(layout.duration_rect).text(
+            duration_pos,
+            duration_align,
+            format_duration(other_secs, self.duration_format),
+            FontId::monospace(13.0),
+            text_color,
+        );
+        response.clicked()
+    }
+
+    fn draw_summary_column_header(&self, ui: &mut egui::Ui) {
+        let (rect, _) =
+            ui.allocate_exact_size(egui::vec2(ui.available_width(), 16.0), Sense::hover());
+        let layout = SummaryRowLayout::compute(summary_row_content_rect(rect), true, self.ui_language.is_rtl());
+        let painter = ui.painter();
+        let color = ui.visuals().weak_text_color();
+        let font = FontId::proportional(11.0);
+        let mut columns = vec![(layout.duration_rect, self.t("duration"))];
+        if let Some((count_rect, average_rect)) = layout.session_columns {
+            columns.push((count_rect, self.t("sessions")));
+            columns.push((average_rect, self.t("avg_session")));
+        }
+        for (column_rect, label) in columns {
+            let (pos, align) = layout.trailing(column_rect);
+            painter.with_clip_rect(column_rect).text(
+                pos,
+                align,
+                label,
+                font.clone(),
+                color,
+            );
+        }
     }
 
     fn draw_summary_row(
@@ -1056,39 +5423,29 @@ impl TimelineApp {
             0.0
         };
         let percent_text = format!("{:>5.1}%", ratio * 100.0);
-        let duration_text = format_duration(row.duration_secs);
-
-        let mut content_rect = rect.shrink2(egui::vec2(6.0, 3.0));
-        content_rect.max.x = (content_rect.max.x - SCROLLBAR_SAFE_GUTTER)
-            .max(content_rect.min.x + 1.0);
-        let gap = 8.0;
-        let row_width = content_rect.width().max(1.0);
-        let duration_width = 88.0;
-        let fixed_right = duration_width + gap * 2.0;
-        let available_left = (row_width - fixed_right).max(40.0);
-        let mut name_width = (available_left * 0.50).clamp(90.0, 620.0);
-        let mut bar_width = (available_left - name_width).max(52.0);
-        if name_width + bar_width > available_left {
-            bar_width = (available_left - name_width).max(40.0);
-            name_width = (available_left - bar_width).max(70.0);
-        }
-
-        let mut x = content_rect.left();
-        let y = content_rect.top();
-        let h = content_rect.height();
-        let name_rect = Rect::from_min_size(Pos2::new(x, y), egui::vec2(name_width, h));
-        x += name_width + gap;
-        let bar_rect = Rect::from_min_size(Pos2::new(x, y), egui::vec2(bar_width, h));
-        x += bar_width + gap;
-        let duration_rect = Rect::from_min_size(Pos2::new(x, y), egui::vec2(duration_width, h));
+        let duration_text = format_duration(row.duration_secs, self.duration_format);
+
+        let layout =
+            SummaryRowLayout::compute(summary_row_content_rect(rect), self.show_session_columns, self.ui_language.is_rtl());
+        let SummaryRowLayout {
+            name_rect,
+            bar_rect,
+            session_columns,
+            duration_rect,
+            ..
+        } = layout;
 
         let painter = ui.painter();
         let text_color = ui.visuals().text_color();
+        if self.pinned_app_keys.contains(&normalize_app_key(&row.app_name)) {
+            painter.rect_filled(
+                layout.leading_part(rect, 3.0),
+                1.5,
+                ui.visuals().selection.bg_fill,
+            );
+        }
 
-        let icon_rect = Rect::from_center_size(
-            Pos2::new(name_rect.left() + 10.0, name_rect.center().y),
-            egui::vec2(16.0, 16.0),
-        );
+        let icon_rect = Rect::from_center_size(layout.leading(name_rect, 10.0).0, egui::vec2(16.0, 16.0));
         let row_color =
             self.display_color_for(row.is_idle, &row.app_name, row.process_path.as_deref());
         if let Some(texture_id) = self.icon_texture_id(ctx, row) {
@@ -1102,10 +5459,11 @@ impl TimelineApp {
             draw_fallback_icon(painter, icon_rect, row_color);
         }
 
-        let name_text_rect = Rect::from_min_max(
-            Pos2::new(icon_rect.right() + 4.0, name_rect.top()),
-            Pos2::new(name_rect.right(), name_rect.bottom()),
-        );
+        let name_text_rect = if layout.rtl {
+            Rect::from_min_max(name_rect.min, Pos2::new(icon_rect.left() - 4.0, name_rect.bottom()))
+        } else {
+            Rect::from_min_max(Pos2::new(icon_rect.right() + 4.0, name_rect.top()), name_rect.max)
+        };
         let name_font = ui
             .style()
             .text_styles
@@ -1113,12 +5471,13 @@ impl TimelineApp {
             .cloned()
             .unwrap_or_else(|| FontId::proportional(18.0));
         let display_name = self.display_name_for_summary_row(row);
+        let (name_pos, name_align) = layout.leading(name_text_rect, 0.0);
         painter
             .with_clip_rect(name_text_rect)
             .text(
-                Pos2::new(name_text_rect.left(), name_text_rect.center().y),
-                Align2::LEFT_CENTER,
-                display_name,
+                name_pos,
+                name_align,
+                bidi::display_auto(display_name),
                 name_font,
                 text_color,
             );
@@ -1136,11 +5495,34 @@ impl TimelineApp {
         let fill_color = row_color;
         if ratio > 0.0 {
             let fill_w = (bar_shape.width() * ratio).clamp(0.0, bar_shape.width());
-            let fill_rect = Rect::from_min_max(
-                bar_shape.min,
-                Pos2::new(bar_shape.left() + fill_w, bar_shape.bottom()),
-            );
-            painter.rect_filled(fill_rect, 9.0, fill_color);
+            let fill_rect = layout.leading_part(bar_shape, fill_w);
+            // Browsers with captured URLs split by site rather than by tab title.
+            let parts = if row.top_sites.is_empty() { &row.top_titles } else { &row.top_sites };
+            if self.show_title_split && !parts.is_empty() && row.duration_secs > 0 {
+                // The top titles stack from the leading edge; the lightest rest is "other".
+                painter.rect_filled(fill_rect, 9.0, mix_toward_white(fill_color, 0.6));
+                let mut offset = 0.0;
+                for (idx, (_, secs)) in parts.iter().enumerate() {
+                    let width = fill_w * (*secs as f32 / row.duration_secs as f32).clamp(0.0, 1.0);
+                    let end = (offset + width).min(fill_w);
+                    let leading = if offset <= 0.0 { 9.0 } else { 0.0 };
+                    let trailing = if end >= fill_w - 0.5 { 9.0 } else { 0.0 };
+                    let (left, right) = if layout.rtl { (trailing, leading) } else { (leading, trailing) };
+                    let slice = if layout.rtl {
+                        Rect::from_x_y_ranges((bar_shape.right() - end)..=(bar_shape.right() - offset), bar_shape.y_range())
+                    } else {
+                        Rect::from_x_y_ranges((bar_shape.left() + offset)..=(bar_shape.left() + end), bar_shape.y_range())
+                    };
+                    painter.rect_filled(
+                        slice,
+                        egui::Rounding { nw: left, sw: left, ne: right, se: right },
+                        mix_toward_white(fill_color, idx as f32 * 0.2),
+                    );
+                    offset = end;
+                }
+            } else {
+                painter.rect_filled(fill_rect, 9.0, fill_color);
+            }
         }
 
         let percent_color = if ratio >= 0.50 {
@@ -1158,15 +5540,83 @@ impl TimelineApp {
             percent_color,
         );
 
+        if let Some((count_rect, average_rect)) = session_columns {
+            let (count_pos, count_align) = layout.trailing(count_rect);
+            painter.with_clip_rect(count_rect).text(
+                count_pos,
+                count_align,
+                row.session_count.to_string(),
+                FontId::monospace(13.0),
+                text_color,
+            );
+            let (average_pos, average_align) = layout.trailing(average_rect);
+            painter.with_clip_rect(average_rect).text(
+                average_pos,
+                average_align,
+                format_duration(row.average_session_secs(), self.duration_format),
+                FontId::monospace(13.0),
+                text_color,
+            );
+        }
+
+        let (duration_pos, duration_align) = layout.trailing(duration_rect);
         painter
             .with_clip_rect(duration_rect)
             .text(
-                Pos2::new(duration_rect.right(), duration_rect.center().y),
-                Align2::RIGHT_CENTER,
+                duration_pos,
+                duration_align,
                 &duration_text,
                 FontId::monospace(13.0),
                 text_color,
             );
+
+        let has_previous_period = self
+            .active_range_bounds()
+            .and_then(|(start, end)| previous_period_bounds(start, end))
+            .is_some();
+        if !row.is_idle {
+            let category_key = normalize_app_key(&row.app_name);
+            response.context_menu(|ui| self.draw_summary_row_menu(ui, &category_key));
+        }
+        let category = self
+            .app_categories
+            .get(&normalize_app_key(&row.app_name))
+            .copied();
+        response.on_hover_ui(|ui| {
+            ui.label(tip_line(
+                self.ui_language,
+                self.t("last_used"),
+                &format_local_datetime(row.last_used_ts, self.clock_format),
+            ));
+            if let Some(category) = category {
+                ui.label(tip_line(self.ui_language, self.t("category"), category.ui_label(self.ui_language)));
+            }
+            if self.show_title_split {
+                for (title, secs) in row.top_sites.iter().chain(&row.top_titles) {
+                    ui.label(tip_line(
+                        self.ui_language,
+                        &bidi::display_auto(title),
+                        &format_duration(*secs, self.duration_format),
+                    ));
+                }
+            }
+            if !has_previous_period {
+                return;
+            }
+            let change_text = match summary_change_ratio(row) {
+                Some(ratio) => format!("{:+.1}%", ratio * 100.0),
+                None => self.t("new_in_period").to_owned(),
+            };
+            ui.label(tip_line(
+                self.ui_language,
+                self.t("previous_period"),
+                &format!(
+                    "{} ({})",
+                    format_duration(row.previous_duration_secs, self.duration_format),
+                    change_text
+                ),
+            ));
+        });
     }
 
     fn icon_texture_id(&mut self, ctx: &egui::Context, row: &SummaryRow) -> Option<egui::TextureId> {
@@ -1202,9 +5652,7 @@ impl TimelineApp {
             };
         }
 
-        let Some(cached) = self.cached_app_visuals.get(app_key) else {
-            return None;
-        };
+        let cached = self.cached_app_visuals.get(app_key)?;
         let Some([width, height]) = cached.icon_size else {
             self.icon_cache.insert(cache_key, IconState::Missing);
             return None;
@@ -1229,7 +5677,7 @@ impl TimelineApp {
     }
 
     fn ensure_icon_cached(&mut self, process_path: &str) {
-        if process_path.is_empty() {
+        if process_path.is_empty() || self.safe_mode != SafeMode::Off {
             return;
         }
         if !matches!(
@@ -1252,17 +5700,25 @@ impl TimelineApp {
         let mut unique_paths: HashSet<String> = HashSet::new();
         let mut app_to_path: HashMap<String, String> = HashMap::new();
 
-        for seg in &self.segments {
-            if seg.is_idle {
+        let segment_apps = self
+            .segments
+            .iter()
+            .map(|seg| (seg.is_idle, seg.process_path.as_deref(), seg.app_name.as_str()));
+        let row_apps = self
+            .summary_rows
+            .iter()
+            .map(|row| (row.is_idle, row.process_path.as_deref(), row.app_name.as_str()));
+        for (is_idle, process_path, app_name) in segment_apps.chain(row_apps) {
+            if is_idle {
                 continue;
             }
-            let Some(path) = seg.process_path.as_deref() else {
+            let Some(path) = process_path else {
                 continue;
             };
             if path.is_empty() {
                 continue;
             }
-            let app_key = normalize_app_key(&seg.app_name);
+            let app_key = normalize_app_key(app_name);
             let path_owned = path.to_owned();
             unique_paths.insert(path_owned.clone());
             app_to_path.entry(app_key).or_insert(path_owned);
@@ -1281,11 +5737,7 @@ impl TimelineApp {
 
     fn drain_icon_results(&mut self, ctx: &egui::Context) {
         let mut has_update = false;
-        let cache_conn = Connection::open(&self.db_path).ok();
-        if let Some(conn) = cache_conn.as_ref() {
-            let _ = conn.busy_timeout(Duration::from_millis(500));
-            let _ = ensure_tracking_schema(conn);
-        }
+        let cache_conn = connection::open_read_write(&self.db_path).ok();
 
         while let Ok(result) = self.icon_result_rx.try_recv() {
             let IconLoadResult {
@@ -1334,6 +5786,9 @@ impl TimelineApp {
     }
 
     fn display_name_for_summary_row<'a>(&'a self, row: &'a SummaryRow) -> &'a str {
+        if let Some(alias) = self.app_aliases.get(&normalize_app_key(&row.app_name)) {
+            return alias;
+        }
         if let Some(path) = row.process_path.as_deref() {
             if let Some(display_name) = self.process_display_name_cache.get(path) {
                 let trimmed = display_name.trim();
@@ -1432,6 +5887,333 @@ impl TimelineApp {
         }
     }
 
+    fn check_day_rollover(&mut self) {
+        let today = Local::now().date_naive();
+        if today <= self.last_seen_date {
+            return;
+        }
+        let previous_date = self.last_seen_date;
+        self.last_seen_date = today;
+
+        let is_single_day_mode = self.range_preset.is_none() && self.custom_range.is_none();
+        if is_single_day_mode && self.selected_date == previous_date {
+            self.set_selected_date(today);
+        } else if self.range_preset.is_some() && self.selected_date == previous_date {
+            self.selected_date = today;
+            self.apply_range_change();
+        }
+
+        let Some(yesterday) = today.checked_sub_days(Days::new(1)) else {
+            return;
+        };
+        let mut periods = vec![(RolloverKind::Day, yesterday, yesterday)];
+        if let Some((week_start, week_end)) =
+            range_dates_for_preset(previous_date, RangePreset::ThisWeek)
+        {
+            if week_end < today {
+                periods.push((RolloverKind::Week, week_start, week_end));
+            }
+        }
+        if let Some((month_first, month_last)) =
+            range_dates_for_preset(previous_date, RangePreset::ThisMonth)
+        {
+            if month_last < today {
+                periods.push((RolloverKind::Month, month_first, month_last));
+            }
+        }
+
+        let db_path = self.db_path.clone();
+        let result_tx = self.rollover_tx.clone();
+        let min_segment_secs = self.noise_threshold_secs;
+        std::thread::spawn(move || {
+            for (kind, start_date, end_date) in periods {
+                let result = load_rollover_summary(&db_path, kind, start_date, end_date, min_segment_secs)
+                    .map_err(|err| format!("failed to summarize completed period: {err:#}"));
+                if result_tx.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn drain_viewer_handoffs(&mut self, ctx: &egui::Context) {
+        let Some(handoff_rx) = &self.viewer_handoff_rx else {
+            return;
+        };
+        let mut raise = false;
+        let mut view = None;
+        while let Ok(requested) = handoff_rx.try_recv() {
+            raise = true;
+            view = requested.or(view);
+        }
+        if !raise {
+            return;
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+        if let Some(view) = view {
+            self.apply_initial_view(view);
+            self.reload();
+        }
+    }
+
+    fn drain_rollover_results(&mut self) {
+        while let Ok(result) = self.rollover_rx.try_recv() {
+            match result {
+                Ok(summary) => {
+                    self.rollover_cards.retain(|card| card.kind != summary.kind);
+                    self.rollover_cards.push(summary);
+                }
+                Err(err) => eprintln!("{err}"),
+            }
+        }
+    }
+
+    fn draw_rollover_cards(&mut self, ctx: &egui::Context) {
+        if self.rollover_cards.is_empty() {
+            return;
+        }
+        let mut dismissed = Vec::new();
+        let mut view_request = None;
+        egui::Window::new(self.t("rollover_title"))
+            .id(egui::Id::new("rollover_cards"))
+            .anchor(Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0))
+            .collapsible(false)
+            .resizable(false)
+            .title_bar(false)
+            .show(ctx, |ui| {
+                ui.set_width(280.0);
+                for (index, card) in self.rollover_cards.iter().enumerate() {
+                    if index > 0 {
+                        ui.separator();
+                    }
+                    ui.horizontal(|ui| {
+                        ui.strong(card.kind.ui_label(self.ui_language));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.small_button("x").on_hover_text(self.t("dismiss")).clicked() {
+                                dismissed.push(index);
+                            }
+                        });
+                    });
+                    let period_text = if card.start_date == card.end_date {
+                        card.start_date.format("%Y-%m-%d").to_string()
+                    } else {
+                        format!(
+                            "{} ~ {}",
+                            card.start_date.format("%Y-%m-%d"),
+                            card.end_date.format("%Y-%m-%d")
+                        )
+                    };
+                    ui.label(period_text);
+                    ui.label(format!(
+                        "{}: {}",
+                        self.t("total"),
+                        format_duration(card.total_secs, self.duration_format)
+                    ));
+                    if let Some(top_app) = &card.top_app {
+                        ui.label(format!(
+                            "{}: {} ({})",
+                            self.t("top_app"),
+                            self.display_name_for_summary_row(top_app),
+                            format_duration(top_app.duration_secs, self.duration_format)
+                        ));
+                    }
+                    if let Some(previous_total) = card.previous_total_secs.filter(|secs| *secs > 0) {
+                        let ratio = (card.total_secs - previous_total) as f64 / previous_total as f64;
+                        ui.label(format!(
+                            "{}: {} ({:+.1}%)",
+                            self.t("previous_period"),
+                            format_duration(previous_total, self.duration_format),
+                            ratio * 100.0
+                        ));
+                    }
+                    if let Some((avg_start, avg_end)) = card.workday_average {
+                        ui.label(format!(
+                            "{} {}, {} {}",
+                            self.t("workday_avg_start"),
+                            format_time_of_day(avg_start, self.clock_format),
+                            self.t("workday_avg_end"),
+                            format_time_of_day(avg_end, self.clock_format)
+                        ));
+                    }
+                    if ui.button(self.t("view")).clicked() {
+                        view_request = Some((card.kind, card.start_date, card.end_date));
+                        dismissed.push(index);
+                    }
+                }
+            });
+
+        if let Some((kind, start_date, end_date)) = view_request {
+            if kind == RolloverKind::Day {
+                self.set_selected_date(start_date);
+            } else {
+                self.set_custom_range(start_date, end_date);
+            }
+        }
+        dismissed.sort_unstable();
+        dismissed.dedup();
+        for index in dismissed.into_iter().rev() {
+            self.rollover_cards.remove(index);
+        }
+    }
+
+    fn open_compare_view(&mut self) {
+        if self.compare_view.is_some() {
+            return;
+        }
+        let (reload_request_tx, reload_result_rx) = spawn_reload_worker();
+        let mut view = CompareView {
+            selected_date: self.selected_date,
+            range_preset: self.range_preset,
+            reload_request_tx,
+            reload_result_rx,
+            next_request_id: 0,
+            pending_request_id: None,
+            summary_rows: Vec::new(),
+            summary_total_secs: 0,
+            min_segment_secs: self.noise_threshold_secs,
+            error: None,
+        };
+        view.reload(&self.db_path);
+        self.compare_view = Some(view);
+    }
+
+    fn show_compare_viewport(&mut self, ctx: &egui::Context) {
+        if self.compare_view.is_none() {
+            return;
+        }
+        let title = format!("LimeTrace - {}", self.t("compare"));
+        let builder = egui::ViewportBuilder::default()
+            .with_title(title.clone())
+            .with_inner_size([560.0, 640.0])
+            .with_min_inner_size([420.0, 360.0]);
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("limetrace_compare_viewport"),
+            builder,
+            |ctx, class| {
+                let mut open = true;
+                if class == egui::ViewportClass::Embedded {
+                    egui::Window::new(title.as_str())
+                        .open(&mut open)
+                        .default_size([520.0, 560.0])
+                        .show(ctx, |ui| self.draw_compare_contents(ui));
+                } else {
+                    egui::CentralPanel::default().show(ctx, |ui| self.draw_compare_contents(ui));
+                    if ctx.input(|i| i.viewport().close_requested()) {
+                        open = false;
+                    }
+                }
+                if !open {
+                    self.compare_view = None;
+                }
+            },
+        );
+    }
+
+    fn draw_compare_contents(&mut self, ui: &mut egui::Ui) {
+        let language = self.ui_language;
+        let Some(mut view) = self.compare_view.take() else {
+            return;
+        };
+        view.drain_results();
+        if view.pending_request_id.is_some() {
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+        }
+
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(tr(language, "range"));
+            let selected_text = view
+                .range_preset
+                .map(|preset| preset.ui_label(language))
+                .unwrap_or_else(|| tr(language, "single_day"));
+            egui::ComboBox::from_id_salt("compare_range")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    changed |= ui
+                        .selectable_value(&mut view.range_preset, None, tr(language, "single_day"))
+                        .changed();
+                    for preset in RangePreset::ALL {
+                        changed |= ui
+                            .selectable_value(
+                                &mut view.range_preset,
+                                Some(preset),
+                                preset.ui_label(language),
+                            )
+                            .changed();
+                    }
+                });
+            if view.range_preset != Some(RangePreset::All) {
+                let step = |date: NaiveDate, forward: bool| {
+                    if forward {
+                        date.checked_add_days(Days::new(1))
+                    } else {
+                        date.checked_sub_days(Days::new(1))
+                    }
+                };
+                if ui.button("<").clicked() {
+                    if let Some(date) = step(view.selected_date, false) {
+                        view.selected_date = date;
+                        changed = true;
+                    }
+                }
+                ui.label(view.selected_date.format("%Y-%m-%d").to_string());
+                if ui.button(">").clicked() {
+                    if let Some(date) = step(view.selected_date, true) {
+                        view.selected_date = date;
+                        changed = true;
+                    }
+                }
+            }
+            if ui.button(tr(language, "refresh")).clicked() {
+                changed = true;
+            }
+        });
+        if changed {
+            view.reload(&self.db_path);
+        }
+
+        ui.separator();
+        if let Some(err) = &view.error {
+            ui.colored_label(Color32::from_rgb(180, 30, 30), err);
+        }
+        ui.label(format!(
+            "{}: {}",
+            tr(language, "total"),
+            format_duration(view.summary_total_secs, self.duration_format)
+        ));
+        let max_secs = view
+            .summary_rows
+            .iter()
+            .map(|row| row.duration_secs)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            for row in &view.summary_rows {
+                let color =
+                    self.display_color_for(row.is_idle, &row.app_name, row.process_path.as_deref());
+                let fraction = row.duration_secs.max(0) as f32 / max_secs as f32;
+                ui.horizontal(|ui| {
+                    let (swatch_rect, _) =
+                        ui.allocate_exact_size(egui::vec2(10.0, 10.0), Sense::hover());
+                    ui.painter().rect_filled(swatch_rect, 2.0, color);
+                    ui.add_sized(
+                        [160.0, 18.0],
+                        egui::Label::new(self.display_name_for_summary_row(row)).truncate(),
+                    );
+                    ui.add(
+                        egui::ProgressBar::new(fraction)
+                            .fill(color)
+                            .desired_width(ui.available_width() - 8.0)
+                            .text(format_duration(row.duration_secs, self.duration_format)),
+                    );
+                });
+            }
+        });
+        self.compare_view = Some(view);
+    }
+
     fn display_color_for(
         &self,
         is_idle: bool,
@@ -1456,8 +6238,12 @@ impl TimelineApp {
             self.backend_status = BackendStatus {
                 health: BackendHealth::Stopped,
                 last_write_ts: None,
+                heartbeat_ts: None,
+                paused: false,
                 checked_ts: unix_seconds_now(),
                 detail: None,
+                stop_reason: None,
+                live_segment: None,
             };
         }
     }
@@ -1472,6 +6258,115 @@ impl TimelineApp {
         }
     }
 
+    fn run_backend_watchdog(&mut self) {
+        if self.backend_status.health == BackendHealth::Running {
+            if self.backend_restart_attempts > 0 {
+                self.log_event("backend is running again; watchdog backoff reset");
+            }
+            self.backend_stopped_since = None;
+            self.backend_restart_attempts = 0;
+            self.next_backend_restart_at = None;
+            return;
+        }
+
+        let now = Instant::now();
+        let stopped_since = *self.backend_stopped_since.get_or_insert(now);
+        if !self.auto_restart_backend {
+            return;
+        }
+        if now.duration_since(stopped_since) < Duration::from_secs(self.auto_restart_after_secs) {
+            return;
+        }
+        if self.next_backend_restart_at.is_some_and(|at| now < at) {
+            return;
+        }
+
+        self.backend_restart_attempts = self.backend_restart_attempts.saturating_add(1);
+        let backoff = backend_restart_backoff(self.backend_restart_attempts);
+        self.next_backend_restart_at = Some(now + backoff);
+        let attempt = self.backend_restart_attempts;
+        match launch_backend_process(&self.db_path) {
+            Ok(pid) => {
+                self.log_event(&format!(
+                    "watchdog restarted backend (attempt {attempt}, pid {pid}, next retry in {}s)",
+                    backoff.as_secs()
+                ));
+                let message = match self.ui_language {
+                    UiLanguage::ZhCn => format!(
+                        "\u{540E}\u{53F0}\u{670D}\u{52A1}\u{5DF2}\u{81EA}\u{52A8}\u{91CD}\u{542F}\u{FF08}\u{7B2C} {attempt} \u{6B21}\u{FF09}"
+                    ),
+                    UiLanguage::EnUs => format!("Backend restarted automatically (attempt {attempt})"),
+                    UiLanguage::ArSa => bidi::display_rtl(&format!("\u{623}\u{64F}\u{639}\u{64A}\u{62F} \u{62A}\u{634}\u{63A}\u{64A}\u{644} \u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{62A}\u{644}\u{642}\u{627}\u{626}\u{64A}\u{64B}\u{627} (\u{627}\u{644}\u{645}\u{62D}\u{627}\u{648}\u{644}\u{629} {attempt})")).into_owned(),
+                };
+                self.set_info_message(message);
+                self.refresh_backend_status();
+            }
+            Err(err) => {
+                self.log_event(&format!(
+                    "watchdog failed to restart backend (attempt {attempt}, next retry in {}s): {err:#}",
+                    backoff.as_secs()
+                ));
+                self.clear_info_message();
+                self.error = Some(format!("backend restart failed: {err:#}"));
+            }
+        }
+    }
+
+    fn start_backend_now(&mut self) {
+        match launch_backend_process(&self.db_path) {
+            Ok(pid) => {
+                self.log_event(&format!("backend started manually (pid {pid})"));
+                self.refresh_backend_status();
+            }
+            Err(err) => {
+                self.log_event(&format!("failed to start backend manually: {err:#}"));
+                self.clear_info_message();
+                self.error = Some(format!("backend start failed: {err:#}"));
+            }
+        }
+    }
+
+    // Without a pid from the heartbeat there is nothing to end; the start below
+    // then gives way to the running instance.
+    fn restart_unresponsive_backend(&mut self, heartbeat: Option<(u32, i64)>) {
+        if let Some((pid, heartbeat_ts)) = heartbeat {
+            match end_stuck_backend(pid, heartbeat_ts) {
+                Ok(()) => self.log_event(&format!("ended unresponsive backend (pid {pid})")),
+                Err(err) => {
+                    self.log_event(&format!("failed to end unresponsive backend: {err:#}"));
+                    self.clear_info_message();
+                    self.error = Some(format!("backend restart failed: {err:#}"));
+                    return;
+                }
+            }
+            // The instance mutex goes away with the process.
+            let deadline = Instant::now() + BACKEND_EXIT_WAIT;
+            while is_tracker_daemon_running() && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        }
+        self.start_backend_now();
+    }
+
+    fn persist_auto_restart_settings(&mut self) {
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[
+                ("auto_restart_backend", json!(self.auto_restart_backend)),
+                ("auto_restart_after_secs", json!(self.auto_restart_after_secs)),
+            ],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save auto-restart setting: {err:#}"));
+        }
+    }
+
+    fn log_event(&self, message: &str) {
+        eprintln!("{message}");
+        crash::note(message);
+        let _ = append_viewer_log(&self.data_root_dir(), message);
+    }
+
     fn data_root_dir(&self) -> PathBuf {
         self.db_path
             .parent()
@@ -1486,17 +6381,54 @@ impl TimelineApp {
     }
 
     fn export_output_path(&self, extension: &str) -> Result<PathBuf> {
+        self.export_part_path(extension, None)
+    }
+
+    /// `part` (e.g. a month) is appended to the file name; `extension` may be
+    /// compound, like `csv.gz`.
+    fn export_part_path(&self, extension: &str, part: Option<&str>) -> Result<PathBuf> {
         let export_dir = self.output_root_dir().join("exports");
         fs::create_dir_all(&export_dir)
             .with_context(|| format!("failed to create export directory: {}", export_dir.display()))?;
 
-        let filename = format!(
-            "export_{}_{}.{}",
-            Local::now().format("%Y%m%d_%H%M%S"),
-            self.current_range_tag(),
-            extension
-        );
-        Ok(export_dir.join(filename))
+        let now = Local::now();
+        let stem = expand_export_filename_template(
+            &self.export_filename_template,
+            &[
+                ("date", now.format("%Y%m%d").to_string()),
+                ("time", now.format("%H%M%S").to_string()),
+                ("range", self.current_range_tag()),
+                ("format", extension.split('.').next().unwrap_or(extension).to_owned()),
+                // Profiles are not implemented yet; keep the placeholder stable for templates.
+                ("profile", "default".to_owned()),
+            ],
+        )?;
+        let stem = match part {
+            Some(part) => format!("{stem}_{part}"),
+            None => stem,
+        };
+        let mut output_path = export_dir.join(format!("{stem}.{extension}"));
+        if self.export_collision_policy == ExportCollisionPolicy::AutoIncrement {
+            let mut counter = 1_u32;
+            while output_path.exists() {
+                output_path = export_dir.join(format!("{stem}_{counter}.{extension}"));
+                counter += 1;
+            }
+        }
+        Ok(output_path)
+    }
+
+    fn persist_export_filename_settings(&mut self) {
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[
+                ("export_filename_template", json!(self.export_filename_template)),
+                ("export_collision_policy", json!(self.export_collision_policy.code())),
+            ],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save export filename settings: {err:#}"));
+        }
     }
 
     fn parse_import_file_path(&self) -> Result<PathBuf> {
@@ -1515,258 +6447,905 @@ impl TimelineApp {
         Ok(csv_path)
     }
 
-    fn import_csv_file(&self, csv_path: &PathBuf) -> Result<ImportStats> {
-        let mut conn = Connection::open(&self.db_path)
-            .with_context(|| format!("failed to open database: {}", self.db_path.display()))?;
-        conn.busy_timeout(Duration::from_secs(5))
-            .context("failed to set busy timeout")?;
-        ensure_tracking_schema(&conn)?;
-
-        let mut reader = ReaderBuilder::new()
-            .has_headers(true)
-            .flexible(true)
-            .trim(csv::Trim::All)
-            .from_path(csv_path)
+    fn import_csv_file(&self, csv_path: &Path, columns: &ImportColumns) -> Result<ImportStats> {
+        let mut conn = connection::open_read_write(&self.db_path)?;
+        let timezone = parse_import_timezone(&self.import_timezone_input)?;
+        let file = fs::File::open(csv_path)
             .with_context(|| format!("failed to open CSV file: {}", csv_path.display()))?;
-
-        let headers = reader
-            .headers()
-            .with_context(|| format!("failed to read CSV headers: {}", csv_path.display()))?
-            .clone();
-        let columns = ImportCsvColumns::from_headers(&headers)?;
-
-        let tx = conn
-            .transaction()
-            .context("failed to open import transaction")?;
-
-        let mut app_cache: HashMap<(String, String), i64> = HashMap::new();
-        let mut title_cache: HashMap<String, i64> = HashMap::new();
-        let mut stats = ImportStats::default();
-
-        for (row_idx, row_result) in reader.records().enumerate() {
-            stats.total_rows += 1;
-            let row = match row_result {
-                Ok(row) => row,
-                Err(err) => {
-                    stats.skipped_rows += 1;
-                    eprintln!("CSV row {} parse error: {err}", row_idx + 2);
-                    continue;
-                }
-            };
-
-            let parsed = match parse_import_csv_row(&row, &columns) {
-                Some(parsed) => parsed,
-                None => {
-                    stats.skipped_rows += 1;
-                    continue;
-                }
-            };
-
-            let app_id = upsert_app_in_tx(
-                &tx,
-                &mut app_cache,
-                &parsed.app_name,
-                &parsed.process_path,
-            )?;
-            let title_id = if let Some(title) = parsed.title.as_deref() {
-                Some(upsert_title_in_tx(&tx, &mut title_cache, title)?)
-            } else {
-                None
-            };
-
-            tx.execute(
-                "\
-                INSERT INTO segments (
-                  start_ts,
-                  end_ts,
-                  app_id,
-                  title_id,
-                  is_idle,
-                  pid,
-                  pid_create_time
-                )
-                VALUES (?1, ?2, ?3, ?4, ?5, NULL, NULL)",
-                params![
-                    parsed.start_ts,
-                    parsed.end_ts,
-                    app_id,
-                    title_id,
-                    if parsed.is_idle { 1_i64 } else { 0_i64 },
-                ],
-            )
-            .context("failed to insert imported segment")?;
-            stats.imported_rows += 1;
-        }
-
-        tx.commit()
-            .context("failed to commit CSV import transaction")?;
-
-        if stats.total_rows == 0 {
-            bail!("CSV has no data rows");
-        }
-        if stats.imported_rows == 0 {
-            bail!("CSV contains no valid rows");
+        match timezone {
+            Some(timezone) => import_csv_with_columns(&mut conn, file, &timezone, columns),
+            None => import_csv_with_columns(&mut conn, file, &Local, columns),
         }
-
-        Ok(stats)
     }
 
-    fn apply_custom_save_dir(&mut self) -> Result<PathBuf> {
-        let trimmed = self.save_dir_input.trim();
-        if trimmed.is_empty() {
-            return Err(anyhow!("save path cannot be empty"));
+    fn apply_custom_save_dir(&mut self) -> Result<PathBuf> {
+        let trimmed = self.save_dir_input.trim();
+        if trimmed.is_empty() {
+            return Err(anyhow!("save path cannot be empty"));
+        }
+        let dir = PathBuf::from(trimmed);
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create save directory: {}", dir.display()))?;
+        self.save_dir_override = Some(dir.clone());
+        self.save_dir_input = dir.display().to_string();
+        Ok(dir)
+    }
+
+    fn apply_custom_save_dir_or_report_error(&mut self) -> bool {
+        if let Err(err) = self.apply_custom_save_dir() {
+            self.clear_info_message();
+            self.error = Some(format!("save path invalid: {err:#}"));
+            return false;
+        }
+        true
+    }
+
+    fn current_range_tag(&self) -> String {
+        if let Some((start, end)) = self.custom_range {
+            return format!(
+                "custom_{}_{}",
+                start.format("%Y%m%d"),
+                end.format("%Y%m%d")
+            );
+        }
+        if let Some(preset) = self.range_preset {
+            return preset
+                .short_label()
+                .to_ascii_lowercase()
+                .replace(' ', "_");
+        }
+        "single_day".to_owned()
+    }
+
+    fn collect_export_rows_for_active_range(&self) -> Vec<ExportSegmentRow> {
+        let Some((range_start, range_end)) = self.active_range_bounds() else {
+            return Vec::new();
+        };
+
+        let loaded_segments;
+        let segments = if self.segments_aggregated {
+            loaded_segments = match load_segments_for_range(&self.db_path, range_start, range_end) {
+                Ok(segments) => segments,
+                Err(err) => {
+                    self.log_event(&format!("export segment load failed: {err:#}"));
+                    Vec::new()
+                }
+            };
+            &loaded_segments
+        } else {
+            &self.segments
+        };
+
+        let repo_names: Vec<String> = self.git_repositories.iter().map(|repo| git_repo_name(repo)).collect();
+        let repo_names_lower: Vec<String> = repo_names.iter().map(|name| name.to_lowercase()).collect();
+        let mut rows = Vec::new();
+        for seg in segments {
+            let start = seg.start_ts.max(range_start);
+            let end = seg.end_ts.min(range_end);
+            if end <= start {
+                continue;
+            }
+            if self.export_exclude_idle && seg.is_idle {
+                continue;
+            }
+            if !seg.is_idle && seg.end_ts - seg.start_ts < self.noise_threshold_secs {
+                continue;
+            }
+            if self.export_exclude_system
+                && !seg.is_idle
+                && is_system_level_app(&seg.app_name, seg.process_path.as_deref())
+            {
+                continue;
+            }
+            if self.export_only_selected_apps
+                && !self.selected_app_keys.is_empty()
+                && !self
+                    .selected_app_keys
+                    .contains(&normalize_app_key(&seg.app_name))
+            {
+                continue;
+            }
+            let category = if seg.is_idle {
+                None
+            } else {
+                category_for_segment(&self.app_categories, &self.title_category_rules, &seg.app_name, seg.title.as_deref())
+            };
+            if !seg.is_idle && !self.export_categories.is_empty() && !self.export_categories.contains(&category) {
+                continue;
+            }
+            rows.push(ExportSegmentRow {
+                start_ts: start,
+                end_ts: end,
+                is_idle: seg.is_idle,
+                app_name: seg.app_name.clone(),
+                process_path: seg.process_path.clone(),
+                title: seg.title.clone(),
+                category,
+                project: editor_segment_repo(&repo_names_lower, seg)
+                    .map(|index| repo_names[index].clone()),
+                url: seg.url.clone(),
+                is_call: seg.is_call,
+            });
+        }
+        rows
+    }
+
+    fn export_current_range_csv(&self) -> Result<Vec<PathBuf>> {
+        let mut process_name_lookup_cache: HashMap<String, String> = HashMap::new();
+        self.export_rows_in_parts("csv", |writer, rows| {
+            writeln!(
+                writer,
+                "\"Title\",\"Start\",\"End\",\"Duration\",\"Process\",\"Category\",\"Project\",\"URL\",\"Call\""
+            )
+                .context("failed to write CSV header")?;
+            for row in rows {
+                let start_text = format_local_datetime(row.start_ts, self.clock_format);
+                let end_text = format_local_datetime(row.end_ts, self.clock_format);
+                let duration_text =
+                    format_duration(row.end_ts.saturating_sub(row.start_ts), self.duration_format);
+                let title_text = row
+                    .title
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(ToOwned::to_owned)
+                    .unwrap_or_else(|| display_app_name(&row.app_name, row.is_idle));
+                let process_text = resolve_export_process_name(
+                    row.is_idle,
+                    &row.app_name,
+                    row.process_path.as_deref(),
+                    &self.process_display_name_cache,
+                    &mut process_name_lookup_cache,
+                );
+
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{},{},{},{}",
+                    csv_escape(&title_text),
+                    csv_escape(&start_text),
+                    csv_escape(&end_text),
+                    csv_escape(&duration_text),
+                    csv_escape(&process_text),
+                    csv_escape(row.category.map_or("", AppCategory::code)),
+                    csv_escape(row.project.as_deref().unwrap_or("")),
+                    csv_escape(row.url.as_deref().unwrap_or("")),
+                    csv_escape(if row.is_call { "true" } else { "false" }),
+                )
+                .context("failed to write CSV row")?;
+            }
+            Ok(())
+        })
+    }
+
+    fn export_current_range_json(&self) -> Result<Vec<PathBuf>> {
+        self.export_rows_in_parts("json", |writer, rows| {
+            let items: Vec<serde_json::Value> = rows
+                .iter()
+                .map(|row| {
+                    let duration_secs = row.end_ts.saturating_sub(row.start_ts);
+                    json!({
+                        "start_ts": row.start_ts,
+                        "end_ts": row.end_ts,
+                        "start_local": format_local_datetime(row.start_ts, self.clock_format),
+                        "end_local": format_local_datetime(row.end_ts, self.clock_format),
+                        "duration_secs": duration_secs,
+                        "is_idle": row.is_idle,
+                        "app_name": row.app_name,
+                        "process_path": row.process_path,
+                        "title": row.title,
+                        "category": row.category.map(AppCategory::code),
+                        "project": row.project,
+                        "url": row.url,
+                        "is_call": row.is_call
+                    })
+                })
+                .collect();
+            serde_json::to_writer_pretty(writer, &items).context("failed to write JSON export")
+        })
+    }
+
+    // Writes one file, or one per month (by segment start) when splitting, gzipped if
+    // asked to.
+    fn export_rows_in_parts(
+        &self,
+        extension: &str,
+        mut write_rows: impl FnMut(&mut dyn Write, &[ExportSegmentRow]) -> Result<()>,
+    ) -> Result<Vec<PathBuf>> {
+        let rows = self.collect_export_rows_for_active_range();
+        let extension = if self.export_gzip {
+            format!("{extension}.gz")
+        } else {
+            extension.to_owned()
+        };
+        let mut parts: Vec<(Option<String>, &[ExportSegmentRow])> = Vec::new();
+        if self.export_split_by_month {
+            let month_of = |row: &ExportSegmentRow| {
+                Local
+                    .timestamp_opt(row.start_ts, 0)
+                    .single()
+                    .map(|dt| dt.format("%Y-%m").to_string())
+            };
+            let mut rest = rows.as_slice();
+            while let Some(first) = rest.first() {
+                let month = month_of(first);
+                let len = rest.iter().position(|row| month_of(row) != month).unwrap_or(rest.len());
+                parts.push((month, &rest[..len]));
+                rest = &rest[len..];
+            }
+        }
+        if parts.is_empty() {
+            parts.push((None, &rows));
+        }
+
+        let mut paths = Vec::with_capacity(parts.len());
+        for (month, part_rows) in parts {
+            let output_path = self.export_part_path(&extension, month.as_deref())?;
+            let file = File::create(&output_path)
+                .with_context(|| format!("failed to create export file: {}", output_path.display()))?;
+            let mut writer = BufWriter::new(file);
+            if self.export_gzip {
+                let mut encoder = GzEncoder::new(&mut writer, Compression::default());
+                write_rows(&mut encoder, part_rows)?;
+                encoder.finish().context("failed to finish gzip stream")?;
+            } else {
+                write_rows(&mut writer, part_rows)?;
+            }
+            writer.flush().context("failed to flush export file")?;
+            paths.push(output_path);
+        }
+        Ok(paths)
+    }
+
+    fn collect_export_day_summaries(&self) -> Vec<ExportDaySummary> {
+        let mut days: BTreeMap<NaiveDate, HashMap<String, (String, i64)>> = BTreeMap::new();
+        for row in self.collect_export_rows_for_active_range() {
+            if row.is_idle {
+                continue;
+            }
+            let Some(date) = Local.timestamp_opt(row.start_ts, 0).single().map(|dt| dt.date_naive()) else {
+                continue;
+            };
+            let (key, label) = match self.export_group_by {
+                ExportGroupBy::App => (normalize_app_key(&row.app_name), display_app_name(&row.app_name, false)),
+                ExportGroupBy::Category => {
+                    let label = row.category.map_or("Uncategorized", |category| category.ui_label(UiLanguage::EnUs));
+                    (row.category.map_or("", AppCategory::code).to_owned(), label.to_owned())
+                }
+                ExportGroupBy::Project => match &row.project {
+                    Some(project) => (project.to_lowercase(), project.clone()),
+                    None => (String::new(), "No project".to_owned()),
+                },
+            };
+            let entry = days.entry(date).or_default().entry(key).or_insert_with(|| (label, 0));
+            entry.1 += row.end_ts - row.start_ts;
+        }
+        if let Some((range_start, range_end)) = self.active_range_bounds() {
+            for date in self.day_notes.keys() {
+                if local_midnight_ts(*date).is_some_and(|ts| ts >= range_start && ts < range_end) {
+                    days.entry(*date).or_default();
+                }
+            }
+        }
+        days.into_iter()
+            .map(|(date, apps)| {
+                let mut apps: Vec<(String, i64)> = apps.into_values().collect();
+                apps.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                let active_secs = apps.iter().map(|(_, secs)| secs).sum();
+                apps.truncate(EXPORT_REPORT_APPS_PER_DAY);
+                ExportDaySummary {
+                    date,
+                    active_secs,
+                    apps,
+                    note: self.day_notes.get(&date).cloned(),
+                }
+            })
+            .collect()
+    }
+
+    fn export_current_range_markdown(&self) -> Result<PathBuf> {
+        let output_path = self.export_output_path("md")?;
+        let file = File::create(&output_path)
+            .with_context(|| format!("failed to create export file: {}", output_path.display()))?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "# LimeTrace report: {}", self.current_range_tag()).context("failed to write Markdown export")?;
+        for day in self.collect_export_day_summaries() {
+            writeln!(
+                writer,
+                "\n## {} ({})\n",
+                day.date.format("%Y-%m-%d %a"),
+                format_duration(day.active_secs, self.duration_format)
+            )
+            .context("failed to write Markdown export")?;
+            if let Some(note) = &day.note {
+                for line in note.lines() {
+                    writeln!(writer, "> {}", bidi::isolate(line)).context("failed to write Markdown export")?;
+                }
+                writeln!(writer).context("failed to write Markdown export")?;
+            }
+            if day.apps.is_empty() {
+                continue;
+            }
+            writeln!(writer, "| {} | Duration |\n| --- | ---: |", self.export_group_by.column_header())
+                .context("failed to write Markdown export")?;
+            for (app, secs) in &day.apps {
+                writeln!(
+                    writer,
+                    "| {} | {} |",
+                    bidi::isolate(&app.replace('|', "\\|")),
+                    format_duration(*secs, self.duration_format)
+                )
+                .context("failed to write Markdown export")?;
+            }
+        }
+        writer.flush().context("failed to flush Markdown writer")?;
+        Ok(output_path)
+    }
+
+    fn export_current_range_html(&self) -> Result<PathBuf> {
+        let output_path = self.export_output_path("html")?;
+        let file = File::create(&output_path)
+            .with_context(|| format!("failed to create export file: {}", output_path.display()))?;
+        let mut writer = BufWriter::new(file);
+        let title = format!("LimeTrace report: {}", self.current_range_tag());
+        writeln!(
+            writer,
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0}</title></head><body>\n<h1 dir=\"auto\">{0}</h1>",
+            html_escape(&title)
+        )
+        .context("failed to write HTML export")?;
+        for day in self.collect_export_day_summaries() {
+            writeln!(
+                writer,
+                "<h2>{} ({})</h2>",
+                day.date.format("%Y-%m-%d %a"),
+                format_duration(day.active_secs, self.duration_format)
+            )
+            .context("failed to write HTML export")?;
+            if let Some(note) = &day.note {
+                writeln!(writer, "<blockquote dir=\"auto\">{}</blockquote>", html_escape(note).replace('\n', "<br>"))
+                    .context("failed to write HTML export")?;
+            }
+            if day.apps.is_empty() {
+                continue;
+            }
+            writeln!(
+                writer,
+                "<table><tr><th>{}</th><th>Duration</th></tr>",
+                self.export_group_by.column_header()
+            )
+            .context("failed to write HTML export")?;
+            for (app, secs) in &day.apps {
+                writeln!(
+                    writer,
+                    "<tr><td dir=\"auto\">{}</td><td>{}</td></tr>",
+                    html_escape(app),
+                    format_duration(*secs, self.duration_format)
+                )
+                .context("failed to write HTML export")?;
+            }
+            writeln!(writer, "</table>").context("failed to write HTML export")?;
+        }
+        writeln!(writer, "</body></html>").context("failed to write HTML export")?;
+        writer.flush().context("failed to flush HTML writer")?;
+        Ok(output_path)
+    }
+
+    fn backup_database(&self) -> Result<PathBuf> {
+        let backup_dir = self.output_root_dir().join("backups");
+        fs::create_dir_all(&backup_dir)
+            .with_context(|| format!("failed to create backup directory: {}", backup_dir.display()))?;
+
+        let filename = format!("tracker_{}.db", Local::now().format("%Y%m%d_%H%M%S"));
+        let output_path = backup_dir.join(filename);
+        backup_database_to(&self.db_path, &output_path)?;
+        Ok(output_path)
+    }
+
+    fn relocate_live_database(&mut self) -> Result<PathBuf> {
+        let target = PathBuf::from(self.relocate_target_input.trim());
+        if target.as_os_str().is_empty() {
+            bail!("target path cannot be empty");
+        }
+        // A running backend would keep writing to the old file.
+        if is_tracker_daemon_running() || self.backend_status.health == BackendHealth::Running {
+            bail!("stop the backend before relocating the database");
+        }
+        if target.exists() {
+            bail!("target already exists: {}", target.display());
+        }
+        if let Some(provider) = detect_sync_folder(&target) {
+            bail!("target is inside a {provider} folder: {}", target.display());
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+        }
+        backup_database_to(&self.db_path, &target)?;
+        // Startup looks for the database through the settings beside the default
+        // one; the settings themselves move along with the database.
+        persist_settings_values(
+            &settings_path_for_db(&default_db_path()),
+            &[("db_path", json!(target.display().to_string()))],
+        )?;
+        let target_settings = settings_path_for_db(&target);
+        if self.settings_path.is_file() && !target_settings.exists() {
+            fs::copy(&self.settings_path, &target_settings)
+                .with_context(|| format!("failed to copy settings to {}", target_settings.display()))?;
+        }
+
+        self.log_event(&format!(
+            "relocated live database from {} to {}",
+            self.db_path.display(),
+            target.display()
+        ));
+        self.db_path = target.clone();
+        self.connections = ConnectionPool::new(&target);
+        self.settings_path = target_settings;
+        self.sync_folder_provider = None;
+        let (backend_status_request_tx, backend_status_result_rx) =
+            spawn_backend_status_worker(self.connections.clone());
+        self.backend_status_request_tx = backend_status_request_tx;
+        self.backend_status_result_rx = backend_status_result_rx;
+        self.start_backend_now();
+        self.reload();
+        Ok(target)
+    }
+
+    fn persist_snapshot_settings(&mut self) {
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[
+                ("snapshot_enabled", json!(self.snapshot_enabled)),
+                ("snapshot_dir", json!(self.snapshot_dir_input.trim())),
+                ("snapshot_interval_hours", json!(self.snapshot_interval_hours)),
+                ("last_snapshot_ts", json!(self.last_snapshot_ts)),
+            ],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save snapshot settings: {err:#}"));
+        }
+    }
+
+    fn run_scheduled_snapshot(&mut self) {
+        while let Ok(result) = self.snapshot_rx.try_recv() {
+            self.snapshot_in_progress = false;
+            match result {
+                Ok(path) => {
+                    self.last_snapshot_ts = unix_seconds_now();
+                    self.persist_snapshot_settings();
+                    self.log_event(&format!("scheduled snapshot saved: {}", path.display()));
+                    self.upload_snapshot_to_remote(path);
+                }
+                Err(err) => self.log_event(&format!("scheduled snapshot failed: {err}")),
+            }
         }
-        let dir = PathBuf::from(trimmed);
-        fs::create_dir_all(&dir)
-            .with_context(|| format!("failed to create save directory: {}", dir.display()))?;
-        self.save_dir_override = Some(dir.clone());
-        self.save_dir_input = dir.display().to_string();
-        Ok(dir)
+        while let Ok(result) = self.remote_upload_rx.try_recv() {
+            match result {
+                Ok(name) => self.log_event(&format!("snapshot uploaded: {name}")),
+                Err(err) => self.log_event(&format!("snapshot upload failed: {err}")),
+            }
+        }
+
+        if !self.snapshot_enabled
+            || self.snapshot_in_progress
+            || self.snapshot_checked_at.elapsed() < SNAPSHOT_CHECK_INTERVAL
+        {
+            return;
+        }
+        self.snapshot_checked_at = Instant::now();
+        let snapshot_dir = PathBuf::from(self.snapshot_dir_input.trim());
+        if snapshot_dir.as_os_str().is_empty() {
+            return;
+        }
+        let interval_secs = self.snapshot_interval_hours.saturating_mul(3600) as i64;
+        if unix_seconds_now() - self.last_snapshot_ts < interval_secs {
+            return;
+        }
+
+        self.snapshot_in_progress = true;
+        let db_path = self.db_path.clone();
+        let result_tx = self.snapshot_tx.clone();
+        std::thread::spawn(move || {
+            let result = write_database_snapshot(&db_path, &snapshot_dir)
+                .map_err(|err| format!("{err:#}"));
+            let _ = result_tx.send(result);
+        });
     }
 
-    fn apply_custom_save_dir_or_report_error(&mut self) -> bool {
-        if let Err(err) = self.apply_custom_save_dir() {
+    fn persist_team_export_settings(&mut self) {
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[
+                ("team_export_enabled", json!(self.team_export_enabled)),
+                ("team_export_path", json!(self.team_export_path_input.trim())),
+                ("team_export_days", json!(self.team_export_days)),
+                ("last_team_export_ts", json!(self.last_team_export_ts)),
+            ],
+        ) {
             self.clear_info_message();
-            self.error = Some(format!("save path invalid: {err:#}"));
-            return false;
+            self.error = Some(format!("failed to save team export settings: {err:#}"));
         }
-        true
     }
 
-    fn current_range_tag(&self) -> String {
-        if let Some((start, end)) = self.custom_range {
-            return format!(
-                "custom_{}_{}",
-                start.format("%Y%m%d"),
-                end.format("%Y%m%d")
-            );
+    fn start_team_export(&mut self) {
+        let output_path = PathBuf::from(self.team_export_path_input.trim());
+        if output_path.as_os_str().is_empty() || self.team_export_in_progress {
+            return;
         }
-        if let Some(preset) = self.range_preset {
-            return preset
-                .short_label()
-                .to_ascii_lowercase()
-                .replace(' ', "_");
+        self.team_export_in_progress = true;
+        let db_path = self.db_path.clone();
+        let categories = CategorySnapshot {
+            app_categories: self.app_categories.clone(),
+            title_rules: self.title_category_rules.clone(),
+        };
+        let days = self.team_export_days;
+        let result_tx = self.team_export_tx.clone();
+        std::thread::spawn(move || {
+            let result = write_team_export(&db_path, &output_path, &categories, days)
+                .map(|()| output_path)
+                .map_err(|err| format!("{err:#}"));
+            let _ = result_tx.send(result);
+        });
+    }
+
+    fn run_scheduled_team_export(&mut self) {
+        while let Ok(result) = self.team_export_rx.try_recv() {
+            self.team_export_in_progress = false;
+            match result {
+                Ok(path) => {
+                    self.last_team_export_ts = unix_seconds_now();
+                    self.persist_team_export_settings();
+                    self.log_event(&format!("team export saved: {}", path.display()));
+                }
+                Err(err) => self.log_event(&format!("team export failed: {err}")),
+            }
+        }
+        if !self.team_export_enabled || self.team_export_checked_at.elapsed() < SNAPSHOT_CHECK_INTERVAL {
+            return;
+        }
+        self.team_export_checked_at = Instant::now();
+        if unix_seconds_now() - self.last_team_export_ts >= TEAM_EXPORT_INTERVAL_SECS {
+            self.start_team_export();
         }
-        "single_day".to_owned()
     }
 
-    fn collect_export_rows_for_active_range(&self) -> Vec<ExportSegmentRow> {
-        let Some((range_start, range_end)) = self.active_range_bounds() else {
-            return Vec::new();
+    fn start_update_check(&mut self) {
+        let Some(channel) = self.update_channel.filter(|_| !self.update_in_progress) else {
+            return;
         };
+        self.update_in_progress = true;
+        self.update_status = Some(self.t("update_checking").to_owned());
+        let staging_dir = update::staging_dir(&self.db_path);
+        let result_tx = self.update_tx.clone();
+        std::thread::spawn(move || {
+            let result = updater::check_and_stage(channel, &staging_dir).map_err(|err| format!("{err:#}"));
+            let _ = result_tx.send(result);
+        });
+    }
 
-        let mut rows = Vec::new();
-        for seg in &self.segments {
-            let start = seg.start_ts.max(range_start);
-            let end = seg.end_ts.min(range_end);
-            if end <= start {
-                continue;
+    fn run_scheduled_update_check(&mut self) {
+        while let Ok(result) = self.update_rx.try_recv() {
+            self.update_in_progress = false;
+            self.last_update_check_ts = unix_seconds_now();
+            if let Err(err) =
+                persist_settings_values(&self.settings_path, &[("last_update_check_ts", json!(self.last_update_check_ts))])
+            {
+                self.log_event(&format!("update setting save failed: {err:#}"));
+            }
+            match result {
+                Ok(Some(version)) => {
+                    self.log_event(&format!("update {version} staged"));
+                    let message = self.t("update_staged").replace("{version}", &version);
+                    self.update_status = Some(message.clone());
+                    self.set_info_message(message);
+                }
+                Ok(None) => self.update_status = Some(self.t("update_up_to_date").to_owned()),
+                Err(err) => {
+                    self.log_event(&format!("update check failed: {err}"));
+                    self.update_status = Some(err);
+                }
             }
-            rows.push(ExportSegmentRow {
-                start_ts: start,
-                end_ts: end,
-                is_idle: seg.is_idle,
-                app_name: seg.app_name.clone(),
-                process_path: seg.process_path.clone(),
-                title: seg.title.clone(),
-            });
         }
-        rows
+        if self.update_channel.is_none() || self.update_checked_at.elapsed() < SNAPSHOT_CHECK_INTERVAL {
+            return;
+        }
+        self.update_checked_at = Instant::now();
+        if unix_seconds_now() - self.last_update_check_ts >= UPDATE_CHECK_INTERVAL_SECS {
+            self.start_update_check();
+        }
     }
 
-    fn export_current_range_csv(&self) -> Result<PathBuf> {
-        let output_path = self.export_output_path("csv")?;
-
-        let file = File::create(&output_path)
-            .with_context(|| format!("failed to create export file: {}", output_path.display()))?;
-        let mut writer = BufWriter::new(file);
-        writeln!(writer, "\"Title\",\"Start\",\"End\",\"Duration\",\"Process\"")
-            .context("failed to write CSV header")?;
-
-        let mut process_name_lookup_cache: HashMap<String, String> = HashMap::new();
-
-        for row in self.collect_export_rows_for_active_range() {
-            let start_text = format_local_datetime(row.start_ts);
-            let end_text = format_local_datetime(row.end_ts);
-            let duration_text = format_duration(row.end_ts.saturating_sub(row.start_ts));
-            let title_text = row
-                .title
-                .as_deref()
-                .map(str::trim)
-                .filter(|value| !value.is_empty())
-                .map(ToOwned::to_owned)
-                .unwrap_or_else(|| display_app_name(&row.app_name, row.is_idle));
-            let process_text = resolve_export_process_name(
-                row.is_idle,
-                &row.app_name,
-                row.process_path.as_deref(),
-                &self.process_display_name_cache,
-                &mut process_name_lookup_cache,
-            );
-
-            writeln!(
-                writer,
-                "{},{},{},{},{}",
-                csv_escape(&title_text),
-                csv_escape(&start_text),
-                csv_escape(&end_text),
-                csv_escape(&duration_text),
-                csv_escape(&process_text),
-            )
-            .context("failed to write CSV row")?;
-        }
-        writer.flush().context("failed to flush CSV writer")?;
-        Ok(output_path)
+    fn set_update_channel(&mut self, channel: Option<updater::UpdateChannel>) {
+        if channel == self.update_channel {
+            return;
+        }
+        self.update_channel = channel;
+        let code = channel.map_or("off", updater::UpdateChannel::code);
+        if let Err(err) = persist_settings_values(&self.settings_path, &[("update_channel", json!(code))]) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save update settings: {err:#}"));
+        }
     }
 
-    fn export_current_range_json(&self) -> Result<PathBuf> {
-        let output_path = self.export_output_path("json")?;
+    fn draw_team_export_settings(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.t("team_export"));
+        ui.label(egui::RichText::new(self.t("team_export_hint")).weak());
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            let enabled_label = self.t("enabled");
+            changed |= ui.checkbox(&mut self.team_export_enabled, enabled_label).changed();
+            ui.label(self.t("team_export_days"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut self.team_export_days).range(1..=366))
+                .changed();
+        });
+        ui.horizontal(|ui| {
+            let hint = self.t("team_export_path");
+            let response = ui.add_sized(
+                [(ui.available_width() - 90.0).max(140.0), 22.0],
+                egui::TextEdit::singleline(&mut self.team_export_path_input).hint_text(hint),
+            );
+            changed |= response.lost_focus();
+            let can_export = !self.team_export_in_progress && !self.team_export_path_input.trim().is_empty();
+            if ui.add_enabled(can_export, egui::Button::new(self.t("team_export_now"))).clicked() {
+                self.start_team_export();
+            }
+        });
+        if changed {
+            self.persist_team_export_settings();
+        }
+    }
 
-        let rows = self.collect_export_rows_for_active_range();
-        let items: Vec<serde_json::Value> = rows
-            .into_iter()
-            .map(|row| {
-                let duration_secs = row.end_ts.saturating_sub(row.start_ts);
-                json!({
-                    "start_ts": row.start_ts,
-                    "end_ts": row.end_ts,
-                    "start_local": format_local_datetime(row.start_ts),
-                    "end_local": format_local_datetime(row.end_ts),
-                    "duration_secs": duration_secs,
-                    "is_idle": row.is_idle,
-                    "app_name": row.app_name,
-                    "process_path": row.process_path,
-                    "title": row.title
-                })
-            })
-            .collect();
+    fn persist_folder_sync_settings(&mut self) {
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[
+                ("folder_sync_enabled", json!(self.folder_sync_enabled)),
+                ("folder_sync_path", json!(self.folder_sync_path_input.trim())),
+                ("folder_sync_machine_id", json!(self.folder_sync_machine_id)),
+            ],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save folder sync settings: {err:#}"));
+        }
+    }
 
-        let file = File::create(&output_path)
-            .with_context(|| format!("failed to create export file: {}", output_path.display()))?;
-        let writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(writer, &items).context("failed to write JSON export")?;
-        Ok(output_path)
+    fn start_folder_sync(&mut self) {
+        let folder = PathBuf::from(self.folder_sync_path_input.trim());
+        if folder.as_os_str().is_empty() || self.folder_sync_secret.is_empty() || self.folder_sync_in_progress {
+            return;
+        }
+        self.folder_sync_in_progress = true;
+        self.folder_sync_checked_at = Some(Instant::now());
+        let db_path = self.db_path.clone();
+        let machine_id = self.folder_sync_machine_id.clone();
+        let secret = self.folder_sync_secret.clone();
+        let remote = self.remote_config();
+        let result_tx = self.folder_sync_tx.clone();
+        std::thread::spawn(move || {
+            let result = (|| {
+                // The local folder stays the exchange point; the remote mirrors it.
+                let remote = match &remote {
+                    Some(config) => {
+                        let store = remote_storage::open(config)?;
+                        let cipher = remote_storage::Cipher::new(&config.passphrase);
+                        let names = store.list().context("failed to list remote storage")?;
+                        Some((store, cipher, names))
+                    }
+                    None => None,
+                };
+                let downloaded = match &remote {
+                    Some((store, cipher, names)) => {
+                        remote_storage::pull_batches(store.as_ref(), cipher, names, &folder, &machine_id)?
+                    }
+                    None => 0,
+                };
+                let mut conn = connection::open_read_write(&db_path)?;
+                let mut stats = folder_sync::run(&mut conn, &folder, &machine_id, &secret)?;
+                stats.downloaded = downloaded;
+                if let Some((store, cipher, names)) = &remote {
+                    stats.uploaded =
+                        remote_storage::push_batches(store.as_ref(), cipher, names, &folder, &machine_id)?;
+                }
+                Ok(stats)
+            })()
+            .map_err(|err: anyhow::Error| format!("{err:#}"));
+            let _ = result_tx.send(result);
+        });
     }
 
-    fn backup_database(&self) -> Result<PathBuf> {
-        let backup_dir = self.output_root_dir().join("backups");
-        fs::create_dir_all(&backup_dir)
-            .with_context(|| format!("failed to create backup directory: {}", backup_dir.display()))?;
+    fn run_scheduled_folder_sync(&mut self) {
+        while let Ok(result) = self.folder_sync_rx.try_recv() {
+            self.folder_sync_in_progress = false;
+            match result {
+                Ok(stats) => {
+                    let mut summary = format!(
+                        "{} {} \u{00B7} {} {} \u{00B7} {} {} \u{00B7} {} {}",
+                        self.t("folder_sync_exported"),
+                        stats.exported,
+                        self.t("folder_sync_imported"),
+                        stats.imported,
+                        self.t("folder_sync_duplicates"),
+                        stats.duplicates,
+                        self.t("folder_sync_rejected"),
+                        stats.rejected_batches
+                    );
+                    if self.remote_kind.is_some() {
+                        summary.push_str(&format!(
+                            " \u{00B7} {} {} \u{00B7} {} {}",
+                            self.t("remote_uploaded"),
+                            stats.uploaded,
+                            self.t("remote_downloaded"),
+                            stats.downloaded
+                        ));
+                    }
+                    self.log_event(&format!("folder sync: {summary}"));
+                    self.folder_sync_status = Some(summary);
+                    if stats.imported > 0 {
+                        self.reload();
+                    }
+                }
+                Err(err) => {
+                    self.log_event(&format!("folder sync failed: {err}"));
+                    self.folder_sync_status = Some(err);
+                }
+            }
+        }
+        if !self.folder_sync_enabled
+            || self.folder_sync_checked_at.is_some_and(|checked_at| {
+                checked_at.elapsed() < Duration::from_secs(FOLDER_SYNC_INTERVAL_SECS)
+            })
+        {
+            return;
+        }
+        self.start_folder_sync();
+    }
 
-        let filename = format!("tracker_{}.db", Local::now().format("%Y%m%d_%H%M%S"));
-        let output_path = backup_dir.join(filename);
+    fn remote_config(&self) -> Option<remote_storage::RemoteConfig> {
+        let kind = self.remote_kind?;
+        let endpoint = self.remote_endpoint_input.trim();
+        if endpoint.is_empty() || self.remote_passphrase.is_empty() {
+            return None;
+        }
+        Some(remote_storage::RemoteConfig {
+            kind,
+            endpoint: endpoint.to_owned(),
+            region: self.remote_region_input.trim().to_owned(),
+            user: self.remote_user_input.trim().to_owned(),
+            secret: self.remote_secret.clone(),
+            passphrase: self.remote_passphrase.clone(),
+        })
+    }
+
+    fn persist_remote_storage_settings(&mut self) {
+        if let Err(err) = persist_settings_values(
+            &self.settings_path,
+            &[
+                ("remote_kind", json!(self.remote_kind.map(remote_storage::RemoteKind::code))),
+                ("remote_endpoint", json!(self.remote_endpoint_input.trim())),
+                ("remote_region", json!(self.remote_region_input.trim())),
+                ("remote_user", json!(self.remote_user_input.trim())),
+                ("remote_snapshots", json!(self.remote_snapshots)),
+            ],
+        ) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save remote storage settings: {err:#}"));
+        }
+    }
 
-        let source = Connection::open(&self.db_path)
-            .with_context(|| format!("failed to open source database: {}", self.db_path.display()))?;
-        let mut destination = Connection::open(&output_path)
-            .with_context(|| format!("failed to create backup file: {}", output_path.display()))?;
+    fn upload_snapshot_to_remote(&mut self, snapshot_path: PathBuf) {
+        if !self.remote_snapshots {
+            return;
+        }
+        let Some(config) = self.remote_config() else {
+            return;
+        };
+        let result_tx = self.remote_upload_tx.clone();
+        std::thread::spawn(move || {
+            let result = remote_storage::push_snapshot(&config, &snapshot_path).map_err(|err| format!("{err:#}"));
+            let _ = result_tx.send(result);
+        });
+    }
 
-        let backup = Backup::new(&source, &mut destination).context("failed to initialize SQLite backup")?;
-        backup
-            .run_to_completion(128, Duration::from_millis(20), None)
-            .context("failed to complete SQLite backup")?;
+    fn draw_remote_storage_settings(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.t("remote_storage"));
+        ui.label(egui::RichText::new(self.t("remote_storage_hint")).weak());
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            let off_label = self.t("remote_off");
+            changed |= ui.selectable_value(&mut self.remote_kind, None, off_label).changed();
+            for kind in [remote_storage::RemoteKind::S3, remote_storage::RemoteKind::WebDav] {
+                changed |= ui.selectable_value(&mut self.remote_kind, Some(kind), kind.label()).changed();
+            }
+        });
+        if self.remote_kind.is_none() {
+            if changed {
+                self.persist_remote_storage_settings();
+            }
+            return;
+        }
+        egui::Grid::new("remote_storage_grid").num_columns(2).show(ui, |ui| {
+            ui.label(self.t("remote_endpoint"));
+            changed |= ui
+                .add(egui::TextEdit::singleline(&mut self.remote_endpoint_input).desired_width(260.0))
+                .lost_focus();
+            ui.end_row();
+            if self.remote_kind == Some(remote_storage::RemoteKind::S3) {
+                ui.label(self.t("remote_region"));
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut self.remote_region_input)
+                            .hint_text("us-east-1")
+                            .desired_width(260.0),
+                    )
+                    .lost_focus();
+                ui.end_row();
+            }
+            ui.label(self.t("remote_user"));
+            changed |= ui
+                .add(egui::TextEdit::singleline(&mut self.remote_user_input).desired_width(260.0))
+                .lost_focus();
+            ui.end_row();
+            ui.label(self.t("remote_secret"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.remote_secret)
+                    .password(true)
+                    .desired_width(260.0),
+            );
+            ui.end_row();
+            ui.label(self.t("remote_passphrase"));
+            ui.add(
+                egui::TextEdit::singleline(&mut self.remote_passphrase)
+                    .password(true)
+                    .desired_width(260.0),
+            );
+            ui.end_row();
+        });
+        let snapshots_label = self.t("remote_snapshots");
+        changed |= ui.checkbox(&mut self.remote_snapshots, snapshots_label).changed();
+        if changed {
+            self.persist_remote_storage_settings();
+        }
+    }
 
-        Ok(output_path)
+    fn draw_folder_sync_settings(&mut self, ui: &mut egui::Ui) {
+        ui.strong(self.t("folder_sync"));
+        ui.label(egui::RichText::new(self.t("folder_sync_hint")).weak());
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            let enabled_label = self.t("enabled");
+            changed |= ui.checkbox(&mut self.folder_sync_enabled, enabled_label).changed();
+            ui.label(egui::RichText::new(&self.folder_sync_machine_id).weak().monospace());
+        });
+        ui.horizontal(|ui| {
+            let hint = self.t("folder_sync_path");
+            let response = ui.add_sized(
+                [(ui.available_width() - 8.0).max(140.0), 22.0],
+                egui::TextEdit::singleline(&mut self.folder_sync_path_input).hint_text(hint),
+            );
+            changed |= response.lost_focus();
+        });
+        ui.horizontal(|ui| {
+            let hint = self.t("folder_sync_secret");
+            ui.add_sized(
+                [(ui.available_width() - 90.0).max(140.0), 22.0],
+                egui::TextEdit::singleline(&mut self.folder_sync_secret)
+                    .password(true)
+                    .hint_text(hint),
+            );
+            let can_sync = !self.folder_sync_in_progress
+                && !self.folder_sync_secret.is_empty()
+                && !self.folder_sync_path_input.trim().is_empty();
+            if ui.add_enabled(can_sync, egui::Button::new(self.t("folder_sync_now"))).clicked() {
+                self.start_folder_sync();
+            }
+        });
+        if let Some(status) = &self.folder_sync_status {
+            ui.label(egui::RichText::new(status).weak());
+        }
+        if changed {
+            self.persist_folder_sync_settings();
+        }
     }
 
     fn draw_export_window_content(&mut self, ui: &mut egui::Ui) {
@@ -1775,11 +7354,89 @@ impl TimelineApp {
             ui.label(format!("{}:", self.t("format")));
             ui.selectable_value(&mut self.export_format, ExportFormat::Csv, "CSV");
             ui.selectable_value(&mut self.export_format, ExportFormat::Json, "JSON");
+            ui.selectable_value(&mut self.export_format, ExportFormat::Markdown, "Markdown");
+            ui.selectable_value(&mut self.export_format, ExportFormat::Html, "HTML");
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", self.t("filename_template")));
+            let response = ui
+                .add(
+                    egui::TextEdit::singleline(&mut self.export_filename_template)
+                        .desired_width(200.0),
+                )
+                .on_hover_text(format!(
+                    "{}: {{date}} {{time}} {{range}} {{format}} {{profile}}",
+                    self.t("placeholders")
+                ));
+            if response.lost_focus() {
+                self.persist_export_filename_settings();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", self.t("existing_file")));
+            let previous_policy = self.export_collision_policy;
+            let auto_increment_label = self.t("auto_increment");
+            let overwrite_label = self.t("overwrite");
+            ui.selectable_value(
+                &mut self.export_collision_policy,
+                ExportCollisionPolicy::AutoIncrement,
+                auto_increment_label,
+            );
+            ui.selectable_value(
+                &mut self.export_collision_policy,
+                ExportCollisionPolicy::Overwrite,
+                overwrite_label,
+            );
+            if self.export_collision_policy != previous_policy {
+                self.persist_export_filename_settings();
+            }
+        });
+        let exclude_idle_label = self.t("export_exclude_idle");
+        ui.checkbox(&mut self.export_exclude_idle, exclude_idle_label);
+        let raw_rows = matches!(self.export_format, ExportFormat::Csv | ExportFormat::Json);
+        ui.add_enabled_ui(raw_rows, |ui| {
+            ui.horizontal(|ui| {
+                let gzip_label = self.t("export_gzip");
+                ui.checkbox(&mut self.export_gzip, gzip_label);
+                let split_label = self.t("export_split_by_month");
+                ui.checkbox(&mut self.export_split_by_month, split_label);
+            });
+        });
+        ui.add_enabled_ui(!raw_rows, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", self.t("export_group_by")));
+                for group_by in ExportGroupBy::ALL {
+                    let label = self.t(group_by.label_key());
+                    ui.selectable_value(&mut self.export_group_by, group_by, label);
+                }
+            });
+        });
+        let exclude_system_label = self.t("export_exclude_system");
+        ui.checkbox(&mut self.export_exclude_system, exclude_system_label);
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("{}:", self.t("export_only_categories")));
+            let choices = AppCategory::ALL.into_iter().map(Some).chain(std::iter::once(None));
+            for category in choices {
+                let label = match category {
+                    Some(category) => category.ui_label(self.ui_language),
+                    None => self.t("uncategorized"),
+                };
+                let selected = self.export_categories.contains(&category);
+                if ui.selectable_label(selected, label).clicked() && !self.export_categories.remove(&category) {
+                    self.export_categories.insert(category);
+                }
+            }
         });
+        let only_selected_label = self.t("export_only_selected");
+        ui.add_enabled(
+            !self.selected_app_keys.is_empty(),
+            egui::Checkbox::new(&mut self.export_only_selected_apps, only_selected_label),
+        );
         if self.range_preset == Some(RangePreset::All) {
             let data_line = match self.ui_language {
                 UiLanguage::ZhCn => "\u{6570}\u{636E}: \u{5168}\u{90E8}\u{65E5}\u{671F}".to_owned(),
                 UiLanguage::EnUs => "Data: all dates".to_owned(),
+                UiLanguage::ArSa => bidi::display_static("\u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A}: \u{643}\u{644} \u{627}\u{644}\u{62A}\u{648}\u{627}\u{631}\u{64A}\u{62E}").to_owned(),
             };
             ui.label(data_line);
         } else if let Some((start, end)) = self.active_range_dates() {
@@ -1790,43 +7447,235 @@ impl TimelineApp {
                     start.format("%Y-%m-%d"),
                     end.format("%Y-%m-%d")
                 ),
+                UiLanguage::ArSa => bidi::display_rtl(&format!(
+                    "\u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A}: {} ~ {} (\u{627}\u{644}\u{646}\u{637}\u{627}\u{642} \u{627}\u{644}\u{62D}\u{627}\u{644}\u{64A})",
+                    start.format("%Y-%m-%d"),
+                    end.format("%Y-%m-%d")
+                ))
+                .into_owned(),
             };
             ui.label(data_line);
         }
 
-        if self.draw_save_path_action_row(ui, "export") {
-            if !self.apply_custom_save_dir_or_report_error() {
+        if self.draw_save_path_action_row(ui, "export") {
+            if !self.apply_custom_save_dir_or_report_error() {
+                return;
+            }
+            let save_result = match self.export_format {
+                ExportFormat::Csv => self
+                    .export_current_range_csv()
+                    .map(|paths| ("CSV", describe_export_paths(&paths)))
+                    .map_err(|err| format!("CSV export failed: {err:#}")),
+                ExportFormat::Json => self
+                    .export_current_range_json()
+                    .map(|paths| ("JSON", describe_export_paths(&paths)))
+                    .map_err(|err| format!("JSON export failed: {err:#}")),
+                ExportFormat::Markdown => self
+                    .export_current_range_markdown()
+                    .map(|path| ("Markdown", path.display().to_string()))
+                    .map_err(|err| format!("Markdown export failed: {err:#}")),
+                ExportFormat::Html => self
+                    .export_current_range_html()
+                    .map(|path| ("HTML", path.display().to_string()))
+                    .map_err(|err| format!("HTML export failed: {err:#}")),
+            };
+
+            match save_result {
+                Ok((kind, path)) => {
+                    self.set_info_message(format!("{kind} saved: {path}"));
+                    eprintln!("{kind} export saved: {path}");
+                }
+                Err(err) => {
+                    self.clear_info_message();
+                    self.error = Some(err);
+                }
+            }
+        }
+    }
+
+    fn draw_sync_folder_warning(&mut self, ui: &mut egui::Ui) {
+        let Some(provider) = self.sync_folder_provider else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            let warning = match self.ui_language {
+                UiLanguage::ZhCn => format!(
+                    "\u{6570}\u{636E}\u{5E93}\u{4F4D}\u{4E8E} {provider} \u{540C}\u{6B65}\u{6587}\u{4EF6}\u{5939}\u{4E2D}\u{FF0C}\u{540C}\u{6B65}\u{5BA2}\u{6237}\u{7AEF}\u{53EF}\u{80FD}\u{9501}\u{5B9A}\u{6216}\u{635F}\u{574F} WAL \u{6587}\u{4EF6}\u{3002}"
+                ),
+                UiLanguage::EnUs => format!(
+                    "The database is inside a {provider} folder; sync clients can lock or corrupt its WAL file."
+                ),
+                UiLanguage::ArSa => bidi::display_rtl(&format!(
+                    "\u{642}\u{627}\u{639}\u{62F}\u{629} \u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A} \u{62F}\u{627}\u{62E}\u{644} \u{645}\u{62C}\u{644}\u{62F} {provider}\u{61B} \u{642}\u{62F} \u{62A}\u{642}\u{641}\u{644} \u{628}\u{631}\u{627}\u{645}\u{62C} \u{627}\u{644}\u{645}\u{632}\u{627}\u{645}\u{646}\u{629} \u{645}\u{644}\u{641} WAL \u{623}\u{648} \u{62A}\u{62A}\u{644}\u{641}\u{647}."
+                ))
+                .into_owned(),
+            };
+            ui.colored_label(Color32::from_rgb(176, 112, 0), warning);
+            if ui.small_button(self.t("relocate_database")).clicked() {
+                self.show_backup_window = true;
+            }
+        });
+    }
+
+    fn apply_font(&mut self, ctx: &egui::Context) {
+        if let Err(err) = configure_fonts(ctx, self.font_path.as_deref()) {
+            self.log_event(&format!("custom font skipped: {err:#}"));
+            self.error = Some(format!("{err:#}"));
+            let _ = configure_fonts(ctx, None);
+        }
+    }
+
+    fn set_font_path(&mut self, ctx: &egui::Context, path: Option<PathBuf>) {
+        if let Some(path) = &path {
+            if let Err(err) = load_font_file(path) {
+                self.clear_info_message();
+                self.error = Some(format!("{err:#}"));
                 return;
             }
-            let save_result = match self.export_format {
-                ExportFormat::Csv => self
-                    .export_current_range_csv()
-                    .map(|path| ("CSV", path))
-                    .map_err(|err| format!("CSV export failed: {err:#}")),
-                ExportFormat::Json => self
-                    .export_current_range_json()
-                    .map(|path| ("JSON", path))
-                    .map_err(|err| format!("JSON export failed: {err:#}")),
-            };
+        }
+        self.font_path = path;
+        self.apply_font(ctx);
+        let value = self
+            .font_path
+            .as_ref()
+            .map_or(serde_json::Value::Null, |path| json!(path.display().to_string()));
+        if let Err(err) = persist_settings_values(&self.settings_path, &[("font_path", value)]) {
+            self.clear_info_message();
+            self.error = Some(format!("failed to save font setting: {err:#}"));
+        }
+    }
 
-            match save_result {
-                Ok((kind, path)) => {
-                    self.set_info_message(format!("{kind} saved: {}", path.display()));
-                    eprintln!("{kind} export saved: {}", path.display());
-                }
-                Err(err) => {
-                    self.clear_info_message();
-                    self.error = Some(err);
+    fn draw_safe_mode_banner(&self, ui: &mut egui::Ui) {
+        let reason = match self.safe_mode {
+            SafeMode::Off => return,
+            SafeMode::Requested => "Safe mode",
+            SafeMode::AfterStartupCrashes => "LimeTrace did not finish starting twice in a row, so it opened in safe mode",
+        };
+        ui.colored_label(
+            Color32::from_rgb(176, 112, 0),
+            format!("{reason}: settings, the icon cache and custom fonts are skipped. Restart LimeTrace to use them again."),
+        );
+    }
+
+    fn draw_crash_report_banner(&mut self, ui: &mut egui::Ui) {
+        let Some(report) = self.crash_report.clone() else {
+            return;
+        };
+        let name = if report.binary.ends_with("backend") { "LimeTrace Backend" } else { "LimeTrace" };
+        let written = Local
+            .timestamp_opt(report.written_ts, 0)
+            .single()
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let mut dismiss = false;
+        ui.horizontal(|ui| {
+            let warning = match self.ui_language {
+                UiLanguage::ZhCn => format!(
+                    "{name} \u{4E8E} {written} \u{610F}\u{5916}\u{9000}\u{51FA}\u{FF0C}\u{5DF2}\u{4FDD}\u{5B58}\u{5D29}\u{6E83}\u{62A5}\u{544A}\u{3002}"
+                ),
+                UiLanguage::EnUs => format!("{name} exited unexpectedly at {written}; a crash report was saved."),
+                UiLanguage::ArSa => bidi::display_rtl(&format!(
+                    "\u{623}\u{64F}\u{63A}\u{644}\u{642} {name} \u{628}\u{634}\u{643}\u{644} \u{63A}\u{64A}\u{631} \u{645}\u{62A}\u{648}\u{642}\u{639} \u{641}\u{64A} {written}\u{61B} \u{62D}\u{64F}\u{641}\u{638} \u{62A}\u{642}\u{631}\u{64A}\u{631} \u{639}\u{637}\u{644}."
+                ))
+                .into_owned(),
+            };
+            ui.colored_label(Color32::from_rgb(176, 112, 0), warning);
+            if ui.small_button(self.t("open_crash_report")).clicked() {
+                if let Err(err) = open_in_default_app(&report.path) {
+                    self.error = Some(format!("failed to open crash report: {err:#}"));
                 }
+                dismiss = true;
+            }
+            if ui.small_button("x").on_hover_text("Dismiss").clicked() {
+                dismiss = true;
+            }
+        });
+        if dismiss {
+            self.crash_report = None;
+            if let Err(err) =
+                persist_settings_values(&self.settings_path, &[("crash_report_seen_ts", json!(report.written_ts))])
+            {
+                self.log_event(&format!("crash report setting save failed: {err:#}"));
             }
         }
     }
 
     fn draw_backup_window_content(&mut self, ui: &mut egui::Ui) {
         ui.set_min_width(320.0);
+        if self.sync_folder_provider.is_some() {
+            self.draw_sync_folder_warning(ui);
+            let mut clicked = false;
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", self.t("relocate_to")));
+                let path_width = (ui.available_width() - 64.0).max(140.0);
+                ui.add_sized(
+                    [path_width, 22.0],
+                    egui::TextEdit::singleline(&mut self.relocate_target_input),
+                );
+                clicked = ui.button(self.t("relocate")).clicked();
+            });
+            if clicked {
+                match self.relocate_live_database() {
+                    Ok(path) => {
+                        let message = match self.ui_language {
+                            UiLanguage::ZhCn => format!(
+                                "\u{6570}\u{636E}\u{5E93}\u{5DF2}\u{79FB}\u{81F3} {}\u{FF0C}\u{540E}\u{53F0}\u{670D}\u{52A1}\u{5DF2}\u{5728}\u{65B0}\u{4F4D}\u{7F6E}\u{542F}\u{52A8}\u{3002}",
+                                path.display()
+                            ),
+                            UiLanguage::EnUs => format!(
+                                "Database moved to {}; the backend now records there.",
+                                path.display()
+                            ),
+                            UiLanguage::ArSa => bidi::display_rtl(&format!(
+                                "\u{646}\u{64F}\u{642}\u{644}\u{62A} \u{642}\u{627}\u{639}\u{62F}\u{629} \u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A} \u{625}\u{644}\u{649} {}\u{60C} \u{648}\u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{62A}\u{633}\u{62C}\u{644} \u{647}\u{646}\u{627}\u{643} \u{627}\u{644}\u{622}\u{646}.",
+                                path.display()
+                            ))
+                            .into_owned(),
+                        };
+                        self.set_info_message(message);
+                    }
+                    Err(err) => {
+                        self.clear_info_message();
+                        self.error = Some(format!("database relocation failed: {err:#}"));
+                    }
+                }
+            }
+            ui.separator();
+        }
+
+        ui.strong(self.t("scheduled_snapshots"));
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            let enabled_label = self.t("enabled");
+            changed |= ui.checkbox(&mut self.snapshot_enabled, enabled_label).changed();
+            ui.label(self.t("interval_hours"));
+            changed |= ui
+                .add(egui::DragValue::new(&mut self.snapshot_interval_hours).range(1..=24 * 7))
+                .changed();
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", self.t("snapshot_dir")));
+            let response = ui.add_sized(
+                [(ui.available_width() - 8.0).max(140.0), 22.0],
+                egui::TextEdit::singleline(&mut self.snapshot_dir_input),
+            );
+            changed |= response.lost_focus();
+        });
+        if changed {
+            self.persist_snapshot_settings();
+        }
+        ui.separator();
+        self.draw_team_export_settings(ui);
+        ui.separator();
+        self.draw_folder_sync_settings(ui);
+        ui.separator();
+        self.draw_remote_storage_settings(ui);
+        ui.separator();
+
         ui.label(match self.ui_language {
             UiLanguage::ZhCn => "\u{6570}\u{636E}: \u{5168}\u{91CF}\u{6570}\u{636E}\u{5E93}\u{FF08}\u{5168}\u{90E8}\u{65E5}\u{671F}\u{FF09}".to_owned(),
             UiLanguage::EnUs => "Data: full database (all dates)".to_owned(),
+            UiLanguage::ArSa => bidi::display_static("\u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A}: \u{642}\u{627}\u{639}\u{62F}\u{629} \u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A} \u{643}\u{627}\u{645}\u{644}\u{629} (\u{643}\u{644} \u{627}\u{644}\u{62A}\u{648}\u{627}\u{631}\u{64A}\u{62E})").to_owned(),
         });
         if self.draw_save_path_action_row(ui, "backup") {
             if !self.apply_custom_save_dir_or_report_error() {
@@ -1851,6 +7700,7 @@ impl TimelineApp {
         ui.label(match self.ui_language {
             UiLanguage::ZhCn => "\u{6570}\u{636E}: CSV \u{6587}\u{4EF6}",
             UiLanguage::EnUs => "Data: CSV file",
+            UiLanguage::ArSa => bidi::display_static("\u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A}: \u{645}\u{644}\u{641} CSV"),
         });
 
         let mut clicked = false;
@@ -1859,6 +7709,7 @@ impl TimelineApp {
             let hint = match self.ui_language {
                 UiLanguage::ZhCn => "CSV \u{6587}\u{4EF6}\u{8DEF}\u{5F84}",
                 UiLanguage::EnUs => "CSV file path",
+                UiLanguage::ArSa => bidi::display_static("\u{645}\u{633}\u{627}\u{631} \u{645}\u{644}\u{641} CSV"),
             };
             let path_width = (ui.available_width() - 64.0).max(140.0);
             ui.add_sized(
@@ -1867,21 +7718,99 @@ impl TimelineApp {
             );
             clicked = ui.button(self.t("import")).clicked();
         });
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", self.t("time_zone")));
+            let invalid = parse_import_timezone(&self.import_timezone_input).is_err();
+            let mut edit = egui::TextEdit::singleline(&mut self.import_timezone_input)
+                .hint_text(tr(self.ui_language, "time_zone_hint"))
+                .desired_width(200.0);
+            if invalid {
+                edit = edit.text_color(Color32::from_rgb(180, 30, 30));
+            }
+            ui.add(edit)
+                .on_hover_text(tr(self.ui_language, "time_zone_offsets"));
+        });
+        self.draw_import_mapping(ui);
 
         if !clicked {
             return;
         }
 
-        let csv_path = match self.parse_import_file_path() {
-            Ok(path) => path,
+        let preview = self.parse_import_file_path().and_then(|path| {
+            let file = fs::File::open(&path)
+                .with_context(|| format!("failed to open CSV file: {}", path.display()))?;
+            Ok((path, preview_csv(file, 5)?))
+        });
+        match preview {
+            Ok((path, preview)) if preview.columns.validate().is_ok() => {
+                self.import_preview = None;
+                self.run_csv_import(&path, &preview.columns);
+            }
+            Ok(preview) => self.import_preview = Some(preview),
             Err(err) => {
                 self.clear_info_message();
                 self.error = Some(format!("CSV import failed: {err:#}"));
-                return;
             }
+        }
+    }
+
+    fn draw_import_mapping(&mut self, ui: &mut egui::Ui) {
+        let Some((csv_path, preview)) = &mut self.import_preview else {
+            return;
         };
+        ui.separator();
+        ui.label(tr(self.ui_language, "map_columns"));
+        egui::Grid::new("import_mapping").num_columns(2).show(ui, |ui| {
+            for field in ImportField::ALL {
+                ui.label(field.label());
+                let current = preview.columns.get(field);
+                let selected_text = current
+                    .and_then(|column| preview.headers.get(column))
+                    .map_or("-", String::as_str);
+                egui::ComboBox::from_id_salt(("import_field", field.label()))
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        let mut selected = current;
+                        ui.selectable_value(&mut selected, None, "-");
+                        for (column, header) in preview.headers.iter().enumerate() {
+                            ui.selectable_value(&mut selected, Some(column), header);
+                        }
+                        preview.columns.set(field, selected);
+                    });
+                ui.end_row();
+            }
+        });
 
-        match self.import_csv_file(&csv_path) {
+        ui.add_space(4.0);
+        ui.label(egui::RichText::new(tr(self.ui_language, "csv_preview")).strong());
+        egui::ScrollArea::horizontal().id_salt("import_preview_rows").show(ui, |ui| {
+            egui::Grid::new("import_preview_grid").striped(true).show(ui, |ui| {
+                for header in &preview.headers {
+                    ui.strong(header);
+                }
+                ui.end_row();
+                for row in &preview.rows {
+                    for value in row {
+                        ui.label(value);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+        let validation = preview.columns.validate();
+        if let Err(err) = &validation {
+            ui.colored_label(Color32::from_rgb(180, 30, 30), err.to_string());
+        }
+        if ui.add_enabled(validation.is_ok(), egui::Button::new(tr(self.ui_language, "import"))).clicked() {
+            let (csv_path, columns) = (csv_path.clone(), preview.columns.clone());
+            self.import_preview = None;
+            self.run_csv_import(&csv_path, &columns);
+        }
+    }
+
+    fn run_csv_import(&mut self, csv_path: &Path, columns: &ImportColumns) {
+        match self.import_csv_file(csv_path, columns) {
             Ok(stats) => {
                 let message = match self.ui_language {
                     UiLanguage::ZhCn => format!(
@@ -1892,6 +7821,11 @@ impl TimelineApp {
                         "Import completed: {} rows imported, {} rows skipped",
                         stats.imported_rows, stats.skipped_rows
                     ),
+                    UiLanguage::ArSa => bidi::display_rtl(&format!(
+                        "\u{627}\u{643}\u{62A}\u{645}\u{644} \u{627}\u{644}\u{627}\u{633}\u{62A}\u{64A}\u{631}\u{627}\u{62F}: \u{627}\u{633}\u{62A}\u{64F}\u{648}\u{631}\u{62F} {} \u{635}\u{641}\u{60C} \u{648}\u{62A}\u{64F}\u{62E}\u{637}\u{64A} {} \u{635}\u{641}",
+                        stats.imported_rows, stats.skipped_rows
+                    ))
+                    .into_owned(),
                 };
                 self.set_info_message(message);
                 self.reload();
@@ -1910,7 +7844,7 @@ impl TimelineApp {
         }
     }
 
-    fn draw_backend_status_indicator(&self, ui: &mut egui::Ui) {
+    fn draw_backend_status_indicator(&mut self, ui: &mut egui::Ui) {
         let text = match (self.ui_language, self.backend_status.health) {
             (UiLanguage::ZhCn, BackendHealth::Running) => "\u{670D}\u{52A1}\u{8FD0}\u{884C}\u{4E2D}".to_owned(),
             (UiLanguage::EnUs, BackendHealth::Running) => "Tracking Active".to_owned(),
@@ -1918,8 +7852,23 @@ impl TimelineApp {
                 "\u{670D}\u{52A1}\u{672A}\u{8FD0}\u{884C}".to_owned()
             }
             (UiLanguage::EnUs, BackendHealth::Stopped) => "Service Not Running".to_owned(),
+            (UiLanguage::ArSa, _) => self.backend_status.short_label_lang(UiLanguage::ArSa).to_owned(),
         };
-        let response = ui.label(egui::RichText::new(text).strong().color(self.backend_status.color()));
+        let response = ui.add(
+            egui::Label::new(egui::RichText::new(text).strong().color(self.backend_status.color()))
+                .sense(Sense::click()),
+        );
+        let watchdog_popup_id = ui.make_persistent_id("backend_watchdog_popup");
+        if response.clicked() {
+            ui.memory_mut(|mem| mem.toggle_popup(watchdog_popup_id));
+        }
+        egui::popup::popup_below_widget(
+            ui,
+            watchdog_popup_id,
+            &response,
+            egui::popup::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| self.draw_backend_watchdog_popup(ui),
+        );
         let status = &self.backend_status;
         response.on_hover_ui(|ui| {
             ui.label(format!(
@@ -1927,17 +7876,123 @@ impl TimelineApp {
                 self.t("status"),
                 status.short_label_lang(self.ui_language)
             ));
-            ui.label(format!("{}: {}", self.t("checked"), format_hms(status.checked_ts)));
+            ui.label(format!(
+                "{}: {}",
+                self.t("checked"),
+                format_hms(status.checked_ts, self.clock_format)
+            ));
             if let Some(last_write_ts) = status.last_write_ts {
-                ui.label(format!("{}: {}", self.t("last_write"), format_hms(last_write_ts)));
+                ui.label(format!(
+                    "{}: {}",
+                    self.t("last_write"),
+                    format_hms(last_write_ts, self.clock_format)
+                ));
             } else {
                 ui.label(format!("{}: --", self.t("last_write")));
             }
-            if let Some(detail) = &status.detail {
+            if let Some(heartbeat_ts) = status.heartbeat_ts {
+                ui.label(format!(
+                    "{}: {}",
+                    self.t("last_heartbeat"),
+                    format_hms(heartbeat_ts, self.clock_format)
+                ));
+            }
+            if status.health == BackendHealth::Running {
+                if status.paused {
+                    ui.label(self.t("backend_paused"));
+                } else if status.live_segment.as_ref().is_some_and(|live| live.is_idle) {
+                    ui.label(self.t("backend_idle"));
+                }
+            }
+            if status.health == BackendHealth::Stopped {
                 ui.separator();
+                let key = status.stop_reason.as_ref().map_or("stopped_not_running", BackendStopReason::message_key);
+                ui.label(self.t(key));
+            }
+            if let Some(detail) = &status.detail {
                 ui.label(detail);
             }
+            if self.backend_restart_attempts > 0 {
+                ui.label(format!(
+                    "{}: {}",
+                    self.t("restart_attempts"),
+                    self.backend_restart_attempts
+                ));
+            }
+        });
+    }
+
+    fn draw_backend_watchdog_popup(&mut self, ui: &mut egui::Ui) {
+        ui.set_min_width(220.0);
+        let mut changed = false;
+        let auto_restart_label = self.t("auto_restart");
+        changed |= ui
+            .checkbox(&mut self.auto_restart_backend, auto_restart_label)
+            .changed();
+        ui.horizontal(|ui| {
+            ui.label(self.t("restart_after"));
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut self.auto_restart_after_secs)
+                        .range(MIN_AUTO_RESTART_AFTER_SECS..=MAX_AUTO_RESTART_AFTER_SECS)
+                        .suffix(" s"),
+                )
+                .changed();
         });
+        if changed {
+            self.backend_restart_attempts = 0;
+            self.next_backend_restart_at = None;
+            self.persist_auto_restart_settings();
+        }
+        ui.separator();
+        if self.backend_status.health == BackendHealth::Running {
+            return;
+        }
+        let reason = self.backend_status.stop_reason.clone();
+        ui.label(self.t(reason.as_ref().map_or("stopped_not_running", BackendStopReason::message_key)));
+        match reason {
+            Some(BackendStopReason::BinaryMissing(backend_exe)) => {
+                if ui.button(self.t("open_install_folder")).clicked() {
+                    if let Err(err) = open_in_default_app(backend_exe.parent().unwrap_or(Path::new("."))) {
+                        self.error = Some(format!("failed to open install folder: {err:#}"));
+                    }
+                    Self::close_active_popup(ui);
+                }
+            }
+            Some(BackendStopReason::Unresponsive { heartbeat }) => {
+                if let Some((pid, _)) = heartbeat {
+                    ui.label(format!("pid {pid}"));
+                }
+                if ui.button(self.t("restart_backend")).clicked() {
+                    self.restart_unresponsive_backend(heartbeat);
+                    Self::close_active_popup(ui);
+                }
+            }
+            Some(BackendStopReason::DatabaseLocked) => {
+                if ui.button(self.t("check_again")).clicked() {
+                    self.refresh_backend_status();
+                    Self::close_active_popup(ui);
+                }
+            }
+            Some(BackendStopReason::Crashed(report)) => {
+                if ui.button(self.t("open_crash_report")).clicked() {
+                    if let Err(err) = open_in_default_app(&report.path) {
+                        self.error = Some(format!("failed to open crash report: {err:#}"));
+                    }
+                    Self::close_active_popup(ui);
+                }
+                if ui.button(self.t("start_backend")).clicked() {
+                    self.start_backend_now();
+                    Self::close_active_popup(ui);
+                }
+            }
+            None => {
+                if ui.button(self.t("start_backend")).clicked() {
+                    self.start_backend_now();
+                    Self::close_active_popup(ui);
+                }
+            }
+        }
     }
 }
 
@@ -1954,6 +8009,29 @@ impl eframe::App for TimelineApp {
         ctx.request_repaint_after(BACKEND_STATUS_POLL_INTERVAL);
         self.drain_reload_results();
         self.drain_backend_status_results();
+        self.run_backend_watchdog();
+        self.check_day_rollover();
+        self.run_scheduled_team_export();
+        self.run_scheduled_update_check();
+        self.run_scheduled_folder_sync();
+        self.run_scheduled_snapshot();
+        self.run_focus_session_timer(ctx);
+        self.run_anomaly_check();
+        self.refresh_forecast();
+        self.drain_jira_results();
+        self.drain_rollover_results();
+        self.drain_viewer_handoffs(ctx);
+        if let Some(rect) = ctx.input(|i| i.viewport().inner_rect) {
+            self.window_size = Some([rect.width().round(), rect.height().round()]);
+        }
+        let close_requested = ctx.input(|i| i.viewport().close_requested());
+        self.persist_view_state_if_changed(close_requested);
+        if let Some((marker, finished_at)) = &self.startup_marker {
+            if close_requested || Instant::now() >= *finished_at {
+                let _ = fs::remove_file(marker);
+                self.startup_marker = None;
+            }
+        }
         if self.last_auto_refresh.elapsed() >= AUTO_REFRESH_INTERVAL
             && self.pending_reload_request_id.is_none()
         {
@@ -1968,7 +8046,8 @@ impl eframe::App for TimelineApp {
         let active_range = self.active_range_bounds();
 
         egui::TopBottomPanel::top("controls").show(ctx, |ui| {
-            ui.horizontal(|ui| {
+            let row_size = egui::vec2(ui.available_width(), ui.spacing().interact_size.y);
+            ui.allocate_ui_with_layout(row_size, self.ui_language.row_layout(), |ui| {
                 let is_single_day_mode = self.range_preset.is_none() && self.custom_range.is_none();
                 let is_all_range_mode = self.range_preset == Some(RangePreset::All);
                 ui.label(self.t("range"));
@@ -2019,8 +8098,24 @@ impl eframe::App for TimelineApp {
                         egui::popup::PopupCloseBehavior::CloseOnClickOutside,
                         |ui| self.draw_date_picker(ui),
                     );
+                    let note_label = if self.day_notes.contains_key(&self.selected_date) {
+                        format!("{} *", self.t("day_note"))
+                    } else {
+                        self.t("day_note").to_owned()
+                    };
+                    if ui.selectable_label(self.show_day_note_panel, note_label).clicked() {
+                        self.show_day_note_panel = !self.show_day_note_panel;
+                        if let Err(err) = persist_settings_values(
+                            &self.settings_path,
+                            &[("show_day_note_panel", json!(self.show_day_note_panel))],
+                        ) {
+                            self.log_event(&format!("day note panel setting save failed: {err:#}"));
+                        }
+                    }
                 }
 
+                self.draw_jump_to_date_input(ui);
+
                 if !is_single_day_mode && !is_all_range_mode {
                     let (range_start_date, range_end_date) = self
                         .active_range_dates()
@@ -2099,6 +8194,7 @@ impl eframe::App for TimelineApp {
                         let hint = match self.ui_language {
                             UiLanguage::ZhCn => "\u{6570}\u{91CF}",
                             UiLanguage::EnUs => "count",
+                            UiLanguage::ArSa => bidi::display_static("\u{627}\u{644}\u{639}\u{62F}\u{62F}"),
                         };
                         let response = ui.add_sized(
                             [52.0, 22.0],
@@ -2116,14 +8212,46 @@ impl eframe::App for TimelineApp {
                         }
                     });
                 self.apply_summary_limit_change(previous_summary_limit);
+                ui.label(self.t("sort"));
+                let mut selected_sort = self.summary_sort;
+                egui::ComboBox::from_id_salt("summary_sort")
+                    .selected_text(summary_sort_label(selected_sort, self.ui_language))
+                    .show_ui(ui, |ui| {
+                        for sort in SummarySort::ALL {
+                            ui.selectable_value(
+                                &mut selected_sort,
+                                sort,
+                                summary_sort_label(sort, self.ui_language),
+                            );
+                        }
+                    });
+                self.set_summary_sort(selected_sort);
+                let sessions_label = self.t("sessions");
+                ui.checkbox(&mut self.show_session_columns, sessions_label);
+                let title_split_label = self.t("title_split");
+                ui.checkbox(&mut self.show_title_split, title_split_label);
                 ui.separator();
                 if ui.button(self.t("refresh")).clicked() {
                     self.reload();
                     self.refresh_backend_status();
                 }
+                self.draw_events_button(ui);
+                self.draw_dashboard_button(ui);
+                self.draw_focus_button(ui);
+                if ui
+                    .selectable_label(self.compare_view.is_some(), self.t("compare"))
+                    .on_hover_text(self.t("compare_hint"))
+                    .clicked()
+                {
+                    if self.compare_view.is_some() {
+                        self.compare_view = None;
+                    } else {
+                        self.open_compare_view();
+                    }
+                }
                 ui.separator();
 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.with_layout(self.ui_language.trailing_row_layout(), |ui| {
                     let mut selected_language = self.ui_language;
                     egui::ComboBox::from_id_salt("ui_language")
                         .selected_text(selected_language.compact_label())
@@ -2139,17 +8267,140 @@ impl eframe::App for TimelineApp {
                                 UiLanguage::EnUs,
                                 UiLanguage::EnUs.compact_label(),
                             );
+                            ui.selectable_value(
+                                &mut selected_language,
+                                UiLanguage::ArSa,
+                                UiLanguage::ArSa.compact_label(),
+                            );
                         });
                     if selected_language != self.ui_language {
                         self.set_ui_language(selected_language);
                     }
                     ui.label("\u{1F310}");
                     ui.separator();
+                    let mut selected_theme = self.theme_choice;
+                    egui::ComboBox::from_id_salt("ui_theme")
+                        .selected_text(selected_theme.ui_label(self.ui_language))
+                        .width(80.0)
+                        .show_ui(ui, |ui| {
+                            for theme in ThemeChoice::ALL {
+                                ui.selectable_value(
+                                    &mut selected_theme,
+                                    theme,
+                                    theme.ui_label(self.ui_language),
+                                );
+                            }
+                        });
+                    if selected_theme != self.theme_choice {
+                        self.set_theme_choice(ui.ctx(), selected_theme);
+                    }
+                    ui.label(self.t("theme"));
+                    ui.separator();
+                    let mut selected_duration_format = self.duration_format;
+                    let mut selected_clock_format = self.clock_format;
+                    ui.menu_button(self.t("display"), |ui| {
+                        ui.label(self.t("duration_format"));
+                        for format in DurationFormat::ALL {
+                            if ui
+                                .radio_value(&mut selected_duration_format, format, format.example_label())
+                                .clicked()
+                            {
+                                ui.close_menu();
+                            }
+                        }
+                        ui.separator();
+                        ui.label(self.t("noise_threshold"));
+                        let mut threshold = self.noise_threshold_secs;
+                        let noise_hint = self.t("noise_threshold_hint");
+                        if ui
+                            .add(egui::DragValue::new(&mut threshold).range(0..=MAX_NOISE_THRESHOLD_SECS).suffix(" s"))
+                            .on_hover_text(noise_hint)
+                            .changed()
+                        {
+                            self.set_noise_threshold(threshold);
+                        }
+                        ui.separator();
+                        let afk_label = self.t("afk_confirm");
+                        if ui.checkbox(&mut self.afk_confirm_enabled, afk_label).changed() {
+                            if let Err(err) = persist_settings_values(
+                                &self.settings_path,
+                                &[("afk_confirm_enabled", json!(self.afk_confirm_enabled))],
+                            ) {
+                                self.clear_info_message();
+                                self.error = Some(format!("failed to save afk setting: {err:#}"));
+                            }
+                        }
+                        ui.separator();
+                        ui.label(self.t("clock"));
+                        for format in [ClockFormat::H24, ClockFormat::H12] {
+                            if ui
+                                .radio_value(
+                                    &mut selected_clock_format,
+                                    format,
+                                    format.ui_label(self.ui_language),
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                            }
+                        }
+                        ui.separator();
+                        ui.label(self.t("font"));
+                        ui.horizontal(|ui| {
+                            let font_hint = self.t("font_hint");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.font_path_input)
+                                    .desired_width(220.0)
+                                    .hint_text(font_hint),
+                            );
+                            if ui.button(self.t("font_apply")).clicked() {
+                                let input = self.font_path_input.trim();
+                                let path = (!input.is_empty()).then(|| PathBuf::from(input));
+                                self.set_font_path(ui.ctx(), path);
+                            }
+                        });
+                        ui.separator();
+                        ui.label(self.t("updates"));
+                        ui.horizontal(|ui| {
+                            let mut selected_channel = self.update_channel;
+                            let channel_label = |app: &Self, channel: Option<updater::UpdateChannel>| match channel {
+                                None => app.t("remote_off"),
+                                Some(updater::UpdateChannel::Stable) => app.t("update_stable"),
+                                Some(updater::UpdateChannel::Prerelease) => app.t("update_prerelease"),
+                            };
+                            egui::ComboBox::from_id_salt("update_channel")
+                                .selected_text(channel_label(self, selected_channel))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut selected_channel, None, channel_label(self, None));
+                                    for channel in updater::UpdateChannel::ALL {
+                                        ui.selectable_value(
+                                            &mut selected_channel,
+                                            Some(channel),
+                                            channel_label(self, Some(channel)),
+                                        );
+                                    }
+                                });
+                            self.set_update_channel(selected_channel);
+                            let can_check = self.update_channel.is_some() && !self.update_in_progress;
+                            if ui.add_enabled(can_check, egui::Button::new(self.t("update_check_now"))).clicked() {
+                                self.start_update_check();
+                            }
+                        });
+                        if let Some(status) = &self.update_status {
+                            ui.label(egui::RichText::new(status).weak());
+                        }
+                    });
+                    self.set_duration_format(selected_duration_format);
+                    self.set_clock_format(selected_clock_format);
+                    ui.separator();
                     self.draw_backend_status_indicator(ui);
                 });
             });
             ui.add_space(1.0);
 
+            self.draw_sync_folder_warning(ui);
+            self.draw_crash_report_banner(ui);
+            self.draw_safe_mode_banner(ui);
             if let Some(err) = &self.error {
                 ui.colored_label(Color32::from_rgb(180, 30, 30), err);
             } else if let Some(info) = self.info.clone() {
@@ -2171,12 +8422,20 @@ impl eframe::App for TimelineApp {
             let total_x = (content_rect.right() - SCROLLBAR_SAFE_GUTTER).max(content_rect.left());
             let left_max_x = (total_x - 130.0).max(content_rect.left() + 120.0);
             let actions_left = (content_rect.left() - 6.0).max(rect.left() + 1.0);
+            // Right-to-left languages get the bar mirrored: actions on the right, total and help on the left.
+            let rtl = self.ui_language.is_rtl();
+            let mirror_x = |x: f32| if rtl { rect.left() + rect.right() - x } else { x };
+            let (actions_min_x, actions_max_x) = if rtl {
+                (mirror_x(left_max_x), mirror_x(actions_left))
+            } else {
+                (actions_left, left_max_x)
+            };
             let actions_rect = Rect::from_min_max(
-                Pos2::new(actions_left, content_rect.top()),
-                Pos2::new(left_max_x, content_rect.bottom()),
+                Pos2::new(actions_min_x, content_rect.top()),
+                Pos2::new(actions_max_x, content_rect.bottom()),
             );
             ui.allocate_new_ui(egui::UiBuilder::new().max_rect(actions_rect), |ui| {
-                ui.with_layout(egui::Layout::left_to_right(egui::Align::Center), |ui| {
+                ui.with_layout(self.ui_language.row_layout(), |ui| {
                     ui.spacing_mut().item_spacing.x = 4.0;
                     let import_button = ui.button(self.t("import"));
                     if import_button.clicked() {
@@ -2190,24 +8449,50 @@ impl eframe::App for TimelineApp {
                     if backup_button.clicked() {
                         self.show_backup_window = !self.show_backup_window;
                     }
+                    if ui.button(self.t("jira")).clicked() {
+                        self.show_jira_window = !self.show_jira_window;
+                    }
+                    if ui.button(self.t("git")).clicked() {
+                        self.show_git_window = !self.show_git_window;
+                    }
+                    if ui.button(self.t("overtime")).clicked() {
+                        self.show_overtime_window = !self.show_overtime_window;
+                    }
+                    if ui.button(self.t("bulk_edit")).clicked() {
+                        self.show_bulk_window = !self.show_bulk_window;
+                    }
+                    if ui.button(self.t("trash")).clicked() {
+                        self.show_trash_window = !self.show_trash_window;
+                        self.trash_entries = None;
+                    }
+                    if let Some(forecast_secs) = self.forecast_secs {
+                        let forecast = self
+                            .t("forecast_on_pace")
+                            .replace("{duration}", &format_duration(forecast_secs, self.duration_format));
+                        ui.label(egui::RichText::new(forecast).weak());
+                    }
                 });
             });
 
             let painter = ui.painter();
             painter.text(
-                Pos2::new(total_x, content_rect.center().y),
-                Align2::RIGHT_CENTER,
-                format!("{}: {}", self.t("total"), format_duration(self.summary_total_secs)),
+                Pos2::new(mirror_x(total_x), content_rect.center().y),
+                if rtl { Align2::LEFT_CENTER } else { Align2::RIGHT_CENTER },
+                format!(
+                    "{}: {}",
+                    self.t("total"),
+                    format_duration(self.summary_total_secs, self.duration_format)
+                ),
                 FontId::monospace(13.0),
                 text_color,
             );
 
             let help_rect = Rect::from_center_size(
-                Pos2::new(content_rect.right() - 5.0, content_rect.center().y),
+                Pos2::new(mirror_x(content_rect.right() - 5.0), content_rect.center().y),
                 egui::vec2(SCROLLBAR_SAFE_GUTTER + 6.0, content_rect.height()),
             );
             ui.allocate_new_ui(egui::UiBuilder::new().max_rect(help_rect), |ui| {
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.with_layout(self.ui_language.trailing_row_layout(), |ui| {
                     ui.spacing_mut().button_padding = egui::vec2(2.0, 0.0);
                     let help_button = ui.button("?");
                     let help_popup_id = ui.make_persistent_id("help_popup");
@@ -2220,12 +8505,36 @@ impl eframe::App for TimelineApp {
                         &help_button,
                         egui::AboveOrBelow::Above,
                         egui::popup::PopupCloseBehavior::CloseOnClickOutside,
-                        |ui| draw_help_menu_content(ui, self.ui_language),
+                        |ui| {
+                            draw_help_menu_content(ui, self.ui_language);
+                            ui.separator();
+                            if ui.button(self.t("whats_new")).clicked() {
+                                if self.changelog.is_empty() {
+                                    self.changelog = changelog::releases_since(None, env!("CARGO_PKG_VERSION"));
+                                }
+                                self.show_changelog_window = true;
+                                ui.memory_mut(|mem| mem.close_popup());
+                            }
+                        },
                     );
                 });
             });
         });
 
+        if self.show_changelog_window {
+            let mut open = self.show_changelog_window;
+            let changelog_title = self.t("whats_new");
+            self.show_centered_window(
+                ctx,
+                "changelog_window",
+                changelog_title,
+                &mut open,
+                egui::vec2(460.0, 320.0),
+                |app, ui| app.draw_changelog_window_content(ui),
+            );
+            self.show_changelog_window = open;
+        }
+
         if self.show_import_window {
             let mut open = self.show_import_window;
             let import_title = self.t("import");
@@ -2262,12 +8571,151 @@ impl eframe::App for TimelineApp {
                 "backup_window",
                 backup_title,
                 &mut open,
-                egui::vec2(380.0, 170.0),
+                egui::vec2(420.0, 260.0),
                 |app, ui| app.draw_backup_window_content(ui),
             );
             self.show_backup_window = open;
         }
 
+        if self.show_category_rules_window {
+            let mut open = self.show_category_rules_window;
+            let rules_title = self.t("title_rules");
+            self.show_centered_window(
+                ctx,
+                "category_rules_window",
+                rules_title,
+                &mut open,
+                egui::vec2(420.0, 260.0),
+                |app, ui| app.draw_category_rules_window_content(ui),
+            );
+            self.show_category_rules_window = open;
+        }
+
+        if self.show_title_tags_window {
+            let mut open = self.show_title_tags_window;
+            let tags_title = self.t("title_tags");
+            self.show_centered_window(
+                ctx,
+                "title_tags_window",
+                tags_title,
+                &mut open,
+                egui::vec2(460.0, 380.0),
+                |app, ui| app.draw_title_tags_window_content(ui),
+            );
+            self.show_title_tags_window = open;
+        }
+
+        if self.show_git_window {
+            let mut open = self.show_git_window;
+            let git_title = self.t("git_title");
+            self.show_centered_window(
+                ctx,
+                "git_window",
+                git_title,
+                &mut open,
+                egui::vec2(480.0, 360.0),
+                |app, ui| app.draw_git_window_content(ui),
+            );
+            self.show_git_window = open;
+        }
+
+        if self.show_overtime_window {
+            let mut open = self.show_overtime_window;
+            let overtime_title = self.t("overtime");
+            self.show_centered_window(
+                ctx,
+                "overtime_window",
+                overtime_title,
+                &mut open,
+                egui::vec2(520.0, 460.0),
+                |app, ui| app.draw_overtime_window_content(ui),
+            );
+            self.show_overtime_window = open;
+        }
+
+        if self.show_jira_window {
+            let mut open = self.show_jira_window;
+            let jira_title = self.t("jira");
+            self.show_centered_window(
+                ctx,
+                "jira_window",
+                jira_title,
+                &mut open,
+                egui::vec2(560.0, 420.0),
+                |app, ui| app.draw_jira_window_content(ui),
+            );
+            self.show_jira_window = open;
+        }
+
+        if self.show_bulk_window {
+            let mut open = self.show_bulk_window;
+            let bulk_title = self.t("bulk_edit");
+            self.show_centered_window(
+                ctx,
+                "bulk_window",
+                bulk_title,
+                &mut open,
+                egui::vec2(420.0, 260.0),
+                |app, ui| app.draw_bulk_window_content(ui),
+            );
+            self.show_bulk_window = open;
+        }
+
+        if self.show_trash_window {
+            let mut open = self.show_trash_window;
+            let trash_title = self.t("trash");
+            self.show_centered_window(
+                ctx,
+                "trash_window",
+                trash_title,
+                &mut open,
+                egui::vec2(560.0, 420.0),
+                |app, ui| app.draw_trash_window_content(ui),
+            );
+            self.show_trash_window = open;
+        }
+
+        if self.tag_target.is_some() {
+            let mut open = true;
+            let tag_title = self.t("tag_range");
+            self.show_centered_window(
+                ctx,
+                "tag_target_window",
+                tag_title,
+                &mut open,
+                egui::vec2(340.0, 200.0),
+                |app, ui| app.draw_tag_target_window_content(ui),
+            );
+            if !open {
+                self.tag_target = None;
+            }
+        }
+
+        if self.afk_prompt.is_some() {
+            let mut open = true;
+            let afk_title = self.t("afk_welcome_back");
+            self.show_centered_window(
+                ctx,
+                "afk_window",
+                afk_title,
+                &mut open,
+                egui::vec2(380.0, 160.0),
+                |app, ui| app.draw_afk_window_content(ui),
+            );
+            if !open {
+                self.resolve_afk_prompt(false);
+            }
+        }
+
+        self.show_compare_viewport(ctx);
+        self.draw_rollover_cards(ctx);
+        if self.show_day_note_panel && self.range_preset.is_none() && self.custom_range.is_none() {
+            egui::SidePanel::right("day_note_panel")
+                .resizable(true)
+                .default_width(240.0)
+                .show(ctx, |ui| self.draw_day_note_panel(ui));
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             let Some((range_start, range_end)) = active_range else {
                 ui.colored_label(
@@ -2277,40 +8725,59 @@ impl eframe::App for TimelineApp {
                 return;
             };
 
-            if self.range_preset.is_none() && self.custom_range.is_none() {
-                ui.heading(self.t("timeline"));
-                ui.add_space(8.0);
-                let timeline_segments = self.ensure_timeline_cache(range_start, range_end);
-                let view_range = &mut self.timeline_view_range;
-                let icon_colors = &self.icon_color_cache;
-                let app_colors = &self.app_color_cache;
-                draw_timeline(
-                    ui,
-                    range_start,
-                    range_end,
-                    timeline_segments.as_slice(),
-                    view_range,
-                    icon_colors,
-                    app_colors,
-                    self.summary_rows.as_slice(),
-                    &self.process_display_name_cache,
-                    self.ui_language,
-                );
-                ui.add_space(8.0);
-            } else {
+            let single_day = self.range_preset.is_none() && self.custom_range.is_none();
+            if !single_day {
                 self.timeline_view_range = None;
             }
+            let now_ts = Some(unix_seconds_now())
+                .filter(|now| single_day && *now >= range_start && *now < range_end);
+            if let (true, Some(now), Some((view_start, view_end))) =
+                (self.timeline_follow_now, now_ts, self.timeline_view_range)
+            {
+                // Keep a little headroom ahead of now, like a transport cursor.
+                let span = view_end - view_start;
+                self.timeline_view_range =
+                    Some(clamp_view_span(now + span / 10 - span, span, range_start, range_end));
+            }
 
-            draw_section_header(ui, self.t("top_apps"));
-            ui.add_space(6.0);
+            let layout = self.dashboard_layout.clone();
+            let last_visible = layout.iter().rposition(|panel| panel.visible);
+            let mut timeline_heading_drawn = false;
+            for (idx, panel) in layout.iter().enumerate() {
+                if !panel.visible {
+                    continue;
+                }
+                match panel.widget {
+                    DashboardWidget::Timeline | DashboardWidget::HourlyPattern if !single_day => {}
+                    DashboardWidget::Timeline | DashboardWidget::HourlyPattern => {
+                        if !timeline_heading_drawn {
+                            self.draw_timeline_heading(ui);
+                            timeline_heading_drawn = true;
+                        }
+                        if panel.widget == DashboardWidget::Timeline {
+                            self.draw_timeline_widget(ui, range_start, range_end, now_ts);
+                        } else {
+                            self.draw_hourly_pattern_widget(ui, range_start, range_end);
+                        }
+                    }
+                    DashboardWidget::TopApps => {
+                        self.draw_top_apps_widget(ctx, ui, Some(idx) == last_visible)
+                    }
+                    DashboardWidget::Categories => {
+                        self.draw_category_summary(ui);
+                        ui.add_space(4.0);
+                    }
+                    DashboardWidget::CategoryDonut => self.draw_category_donut(ui),
+                    DashboardWidget::Workday => self.draw_workday_widget(ui, range_start, range_end),
+                }
+            }
 
-            egui::ScrollArea::vertical()
-                .id_salt("top_apps_scroll")
-                .auto_shrink([false, false])
-                .max_height(ui.available_height().max(0.0))
-                .show(ui, |ui| {
-                    self.draw_summary_rows(ctx, ui);
-                });
+            // Panning away from now hands the viewport back to the user.
+            if let (Some(now), Some((view_start, view_end))) = (now_ts, self.timeline_view_range) {
+                if now < view_start || now > view_end {
+                    self.timeline_follow_now = false;
+                }
+            }
         });
     }
 }
@@ -2336,33 +8803,89 @@ fn spawn_icon_loader() -> (mpsc::Sender<String>, mpsc::Receiver<IconLoadResult>)
     (request_tx, result_rx)
 }
 
+fn load_reload_payload(conn: &Connection, request: &ReloadRequest) -> Result<ReloadPayload> {
+    let (range_start, range_end) = (request.range_start, request.range_end);
+    let aggregated = !request.timeline_needed
+        && count_segments_in_range(conn, range_start, range_end)? > AGGREGATE_ONLY_SEGMENT_THRESHOLD;
+
+    let mut segments = Vec::new();
+    let mut summary =
+        SummaryAccumulator::new(range_start, range_end, request.min_segment_secs, load_app_file_description);
+    let mut category_totals = HashMap::new();
+    let tag_spans = request.tag_spans.as_deref();
+    for_each_segment_in_range(conn, range_start, range_end, |seg| {
+        for seg in clip_segment_to_spans(seg, tag_spans) {
+            summary.push(&seg);
+            if !aggregated {
+                segments.push(seg);
+            } else if let Some(categories) = &request.categories {
+                add_category_duration(&mut category_totals, categories, &seg, range_start, range_end);
+            }
+        }
+    })?;
+    let fleeting_secs = summary.fleeting_secs();
+    let call_secs = summary.call_secs();
+    let away_secs = summary.away_secs();
+    let mut summary_rows = summary.finish();
+
+    if let Some((previous_start, previous_end)) = previous_period_bounds(range_start, range_end) {
+        let mut previous =
+            SummaryAccumulator::new(previous_start, previous_end, request.min_segment_secs, load_app_file_description);
+        let push_previous = |seg: Segment| {
+            for seg in clip_segment_to_spans(seg, tag_spans) {
+                previous.push(&seg);
+            }
+        };
+        if for_each_segment_in_range(conn, previous_start, previous_end, push_previous).is_ok() {
+            apply_previous_period_durations(&mut summary_rows, &previous.finish());
+        }
+    }
+    let summary_total_secs = summary_rows
+        .iter()
+        .map(|row| row.duration_secs.max(0))
+        .sum();
+    Ok(ReloadPayload {
+        segments,
+        summary_rows,
+        summary_total_secs,
+        fleeting_secs,
+        call_secs,
+        away_secs,
+        aggregated,
+        category_totals: (aggregated && request.categories.is_some())
+            .then(|| sorted_category_totals(category_totals)),
+    })
+}
+
 fn spawn_reload_worker() -> (mpsc::Sender<ReloadRequest>, mpsc::Receiver<ReloadResult>) {
     let (request_tx, request_rx) = mpsc::channel::<ReloadRequest>();
     let (result_tx, result_rx) = mpsc::channel::<ReloadResult>();
 
     std::thread::spawn(move || {
+        let mut connection: Option<(PathBuf, Connection)> = None;
         while let Ok(mut request) = request_rx.recv() {
             while let Ok(next_request) = request_rx.try_recv() {
                 request = next_request;
             }
 
-            let payload: std::result::Result<ReloadPayload, String> =
-                match load_segments_for_range(&request.db_path, request.range_start, request.range_end) {
-                    Ok(segments) => {
-                        let summary_rows =
-                            build_summary_rows(request.range_start, request.range_end, &segments);
-                        let summary_total_secs = summary_rows
-                            .iter()
-                            .map(|row| row.duration_secs.max(0))
-                            .sum();
-                        Ok(ReloadPayload {
-                            segments,
-                            summary_rows,
-                            summary_total_secs,
+            let conn = match reload_worker_connection(&mut connection, &request.db_path) {
+                Ok(conn) => conn,
+                Err(err) => {
+                    let payload = Err(format!("failed to load segments: {err:#}"));
+                    if result_tx
+                        .send(ReloadResult {
+                            request_id: request.request_id,
+                            payload,
                         })
+                        .is_err()
+                    {
+                        break;
                     }
-                    Err(err) => Err(format!("failed to load segments: {err:#}")),
-                };
+                    continue;
+                }
+            };
+            let payload = load_reload_payload(conn, &request)
+                .map_err(|err| format!("failed to load segments: {err:#}"));
 
             if result_tx
                 .send(ReloadResult {
@@ -2380,7 +8903,7 @@ fn spawn_reload_worker() -> (mpsc::Sender<ReloadRequest>, mpsc::Receiver<ReloadR
 }
 
 fn spawn_backend_status_worker(
-    db_path: PathBuf,
+    connections: ConnectionPool,
 ) -> (
     mpsc::Sender<BackendStatusWorkerRequest>,
     mpsc::Receiver<BackendStatus>,
@@ -2389,25 +8912,25 @@ fn spawn_backend_status_worker(
     let (result_tx, result_rx) = mpsc::channel::<BackendStatus>();
 
     std::thread::spawn(move || {
-        loop {
-            match request_rx.recv_timeout(BACKEND_STATUS_POLL_INTERVAL) {
-                Ok(BackendStatusWorkerRequest::ProbeNow)
-                | Err(mpsc::RecvTimeoutError::Timeout) => {
-                    while request_rx.try_recv().is_ok() {}
-                    let status = match probe_backend_status(&db_path) {
-                        Ok(status) => status,
-                        Err(_err) => BackendStatus {
-                            health: BackendHealth::Stopped,
-                            last_write_ts: None,
-                            checked_ts: unix_seconds_now(),
-                            detail: None,
-                        },
-                    };
-                    if result_tx.send(status).is_err() {
-                        break;
-                    }
-                }
-                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        while let Ok(BackendStatusWorkerRequest::ProbeNow) | Err(mpsc::RecvTimeoutError::Timeout) =
+            request_rx.recv_timeout(BACKEND_STATUS_POLL_INTERVAL)
+        {
+            while request_rx.try_recv().is_ok() {}
+            let status = match probe_backend_status(&connections) {
+                Ok(status) => status,
+                Err(err) => BackendStatus {
+                    health: BackendHealth::Stopped,
+                    last_write_ts: None,
+                    heartbeat_ts: None,
+                    paused: false,
+                    checked_ts: unix_seconds_now(),
+                    detail: Some(format!("{err:#}")),
+                    stop_reason: is_database_locked(&err).then_some(BackendStopReason::DatabaseLocked),
+                    live_segment: None,
+                },
+            };
+            if result_tx.send(status).is_err() {
+                break;
             }
         }
     });
@@ -2415,6 +8938,287 @@ fn spawn_backend_status_worker(
     (request_tx, result_rx)
 }
 
+// `start` and `fraction` are in turns, clockwise from twelve o'clock.
+fn paint_donut_slice(
+    painter: &egui::Painter,
+    center: Pos2,
+    inner: f32,
+    outer: f32,
+    start: f32,
+    fraction: f32,
+    color: Color32,
+) {
+    if fraction <= 0.0 {
+        return;
+    }
+    let steps = ((fraction * 96.0).ceil() as u32).max(1);
+    let point_at = |turn: f32, radius: f32| {
+        let angle = turn * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+        center + egui::vec2(angle.cos(), angle.sin()) * radius
+    };
+    let mut mesh = egui::Mesh::default();
+    for step in 0..=steps {
+        let turn = start + fraction * step as f32 / steps as f32;
+        mesh.colored_vertex(point_at(turn, outer), color);
+        mesh.colored_vertex(point_at(turn, inner), color);
+    }
+    for step in 0..steps {
+        let base = step * 2;
+        mesh.add_triangle(base, base + 1, base + 2);
+        mesh.add_triangle(base + 1, base + 3, base + 2);
+    }
+    painter.add(egui::Shape::mesh(mesh));
+}
+
+// A local day runs from the unlock before its first activity to the lock after its
+// last, taken from the backend's LOCKED segments; days without a lock on either side
+// fall back to the first or last activity.
+fn compute_workday_spans(segments: &[Segment], range_start: i64, range_end: i64) -> Vec<WorkdaySpan> {
+    let local_date = |ts: i64| Local.timestamp_opt(ts, 0).single().map(|dt| dt.date_naive());
+    let mut spans: Vec<WorkdaySpan> = Vec::new();
+    for seg in segments {
+        if seg.is_idle
+            || is_locked_session(seg.process_path.as_deref())
+            || is_system_level_app(&seg.app_name, seg.process_path.as_deref())
+        {
+            continue;
+        }
+        let start = seg.start_ts.max(range_start);
+        let end = seg.end_ts.min(range_end);
+        if end <= start {
+            continue;
+        }
+        let Some(date) = local_date(start) else {
+            continue;
+        };
+        match spans.iter_mut().find(|span| span.date == date) {
+            Some(span) => {
+                span.start_ts = span.start_ts.min(start);
+                span.end_ts = span.end_ts.max(end);
+            }
+            None => spans.push(WorkdaySpan {
+                date,
+                start_ts: start,
+                end_ts: end,
+            }),
+        }
+    }
+
+    let locks = segments
+        .iter()
+        .filter(|seg| is_locked_session(seg.process_path.as_deref()));
+    for span in &mut spans {
+        let on_day = |ts: &i64| local_date(*ts) == Some(span.date);
+        let unlock = locks
+            .clone()
+            .map(|lock| lock.end_ts.max(range_start))
+            .filter(|ts| *ts <= span.start_ts && on_day(ts))
+            .max();
+        let lock = locks
+            .clone()
+            .map(|lock| lock.start_ts.min(range_end))
+            .filter(|ts| *ts >= span.end_ts && on_day(ts))
+            .min();
+        span.start_ts = unlock.unwrap_or(span.start_ts);
+        span.end_ts = lock.unwrap_or(span.end_ts);
+    }
+    spans.sort_by_key(|span| span.date);
+    spans
+}
+
+fn load_day_notes_from_db(conn: &Connection) -> Result<HashMap<NaiveDate, String>> {
+    let mut stmt = conn
+        .prepare("SELECT date, note FROM day_notes")
+        .context("failed to prepare day notes query")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .context("failed to query day notes")?;
+    let mut notes = HashMap::new();
+    for row in rows {
+        let (date, note) = row.context("failed to read day note")?;
+        if let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+            notes.insert(date, note);
+        }
+    }
+    Ok(notes)
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn load_days_off_from_db(conn: &Connection) -> Result<HashMap<NaiveDate, DayOff>> {
+    let mut stmt = conn
+        .prepare("SELECT date, kind, label FROM days_off")
+        .context("failed to prepare days off query")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })
+        .context("failed to query days off")?;
+    let mut days_off = HashMap::new();
+    for row in rows {
+        let (date, kind, label) = row.context("failed to read day off")?;
+        if let (Ok(date), Some(kind)) = (NaiveDate::parse_from_str(&date, "%Y-%m-%d"), DayOffKind::from_code(&kind)) {
+            days_off.insert(date, DayOff { kind, label });
+        }
+    }
+    Ok(days_off)
+}
+
+// National holidays only; regional ones and substitute days are left to manual entries.
+fn holiday_preset_dates(preset: &str, year: i32) -> Vec<(NaiveDate, &'static str)> {
+    let fixed = |month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day);
+    let easter = easter_sunday(year);
+    let from_easter = |offset_days: i64| easter.map(|date| date + chrono::Duration::days(offset_days));
+    let holidays: Vec<(Option<NaiveDate>, &'static str)> = match preset {
+        "US" => vec![
+            (fixed(1, 1), "New Year's Day"),
+            (nth_weekday_of_month(year, 1, Weekday::Mon, 3), "Martin Luther King Jr. Day"),
+            (nth_weekday_of_month(year, 2, Weekday::Mon, 3), "Presidents' Day"),
+            (last_weekday_of_month(year, 5, Weekday::Mon), "Memorial Day"),
+            (fixed(6, 19), "Juneteenth"),
+            (fixed(7, 4), "Independence Day"),
+            (nth_weekday_of_month(year, 9, Weekday::Mon, 1), "Labor Day"),
+            (nth_weekday_of_month(year, 10, Weekday::Mon, 2), "Columbus Day"),
+            (fixed(11, 11), "Veterans Day"),
+            (nth_weekday_of_month(year, 11, Weekday::Thu, 4), "Thanksgiving"),
+            (fixed(12, 25), "Christmas Day"),
+        ],
+        "DE" => vec![
+            (fixed(1, 1), "Neujahr"),
+            (from_easter(-2), "Karfreitag"),
+            (from_easter(1), "Ostermontag"),
+            (fixed(5, 1), "Tag der Arbeit"),
+            (from_easter(39), "Christi Himmelfahrt"),
+            (from_easter(50), "Pfingstmontag"),
+            (fixed(10, 3), "Tag der Deutschen Einheit"),
+            (fixed(12, 25), "1. Weihnachtstag"),
+            (fixed(12, 26), "2. Weihnachtstag"),
+        ],
+        "GB" => vec![
+            (fixed(1, 1), "New Year's Day"),
+            (from_easter(-2), "Good Friday"),
+            (from_easter(1), "Easter Monday"),
+            (nth_weekday_of_month(year, 5, Weekday::Mon, 1), "Early May bank holiday"),
+            (last_weekday_of_month(year, 5, Weekday::Mon), "Spring bank holiday"),
+            (last_weekday_of_month(year, 8, Weekday::Mon), "Summer bank holiday"),
+            (fixed(12, 25), "Christmas Day"),
+            (fixed(12, 26), "Boxing Day"),
+        ],
+        _ => Vec::new(),
+    };
+    holidays
+        .into_iter()
+        .filter_map(|(date, label)| date.map(|date| (date, label)))
+        .collect()
+}
+
+// Anonymous Gregorian algorithm.
+fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+fn nth_weekday_of_month(year: i32, month: u32, weekday: Weekday, n: u8) -> Option<NaiveDate> {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n)
+}
+
+fn last_weekday_of_month(year: i32, month: u32, weekday: Weekday) -> Option<NaiveDate> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let last = add_months(first, 1)?.checked_sub_days(Days::new(1))?;
+    let back = (last.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+    last.checked_sub_days(Days::new(u64::from(back)))
+}
+
+fn parse_contract_hours(value: Option<&serde_json::Value>) -> [f64; 7] {
+    let mut hours = DEFAULT_CONTRACT_HOURS;
+    if let Some(values) = value.and_then(serde_json::Value::as_array) {
+        for (slot, value) in hours.iter_mut().zip(values) {
+            if let Some(value) = value.as_f64() {
+                *slot = value.clamp(0.0, 24.0);
+            }
+        }
+    }
+    hours
+}
+
+fn average_workday_bounds(spans: &[WorkdaySpan]) -> Option<(i64, i64)> {
+    let offsets: Vec<(i64, i64)> = spans
+        .iter()
+        .filter_map(|span| {
+            let midnight = local_midnight_ts(span.date)?;
+            Some((span.start_ts - midnight, span.end_ts - midnight))
+        })
+        .collect();
+    if offsets.is_empty() {
+        return None;
+    }
+    let count = offsets.len() as i64;
+    Some((
+        offsets.iter().map(|(start, _)| start).sum::<i64>() / count,
+        offsets.iter().map(|(_, end)| end).sum::<i64>() / count,
+    ))
+}
+
+fn format_time_of_day(secs: i64, clock_format: ClockFormat) -> String {
+    chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs.rem_euclid(24 * 3600) as u32, 0)
+        .map(|time| time.format(clock_format.minute_pattern()).to_string())
+        .unwrap_or_else(|| "--:--".to_owned())
+}
+
+fn default_dashboard_layout() -> Vec<DashboardPanel> {
+    DashboardWidget::ALL
+        .into_iter()
+        .map(|widget| DashboardPanel { widget, visible: true })
+        .collect()
+}
+
+// Unknown entries are dropped and widgets missing from older settings are appended.
+fn parse_dashboard_layout(value: Option<&serde_json::Value>) -> Vec<DashboardPanel> {
+    let mut layout: Vec<DashboardPanel> = Vec::new();
+    for entry in value.and_then(serde_json::Value::as_array).into_iter().flatten() {
+        let Some(widget) = entry
+            .get("widget")
+            .and_then(serde_json::Value::as_str)
+            .and_then(DashboardWidget::from_code)
+        else {
+            continue;
+        };
+        if layout.iter().any(|panel| panel.widget == widget) {
+            continue;
+        }
+        let visible = entry
+            .get("visible")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+        layout.push(DashboardPanel { widget, visible });
+    }
+    for panel in default_dashboard_layout() {
+        if !layout.iter().any(|existing| existing.widget == panel.widget) {
+            layout.push(panel);
+        }
+    }
+    layout
+}
+
 fn draw_section_header(ui: &mut egui::Ui, title: &str) {
     ui.horizontal(|ui| {
         ui.heading(title);
@@ -2449,123 +9253,132 @@ fn draw_help_menu_content(ui: &mut egui::Ui, language: UiLanguage) {
     ui.label(tr(language, "help.reset"));
 }
 
-fn build_summary_rows(range_start: i64, range_end: i64, segments: &[Segment]) -> Vec<SummaryRow> {
-    if range_end <= range_start {
-        return Vec::new();
-    }
-
-    let mut totals: HashMap<String, SummaryRow> = HashMap::new();
-    let mut display_name_by_path: HashMap<String, Option<String>> = HashMap::new();
-    for seg in segments {
-        if should_hide_summary_app(&seg.app_name, seg.is_idle, seg.process_path.as_deref()) {
-            continue;
-        }
+/// Space- or comma-separated terms; `*`/`?` make a term a glob, a leading `!`
+/// excludes matches. Rows must match any include term and no exclude term.
+struct SummaryFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
 
-        let clipped_start = seg.start_ts.max(range_start);
-        let clipped_end = seg.end_ts.min(range_end);
-        if clipped_end <= clipped_start {
-            continue;
+impl SummaryFilter {
+    fn parse(input: &str) -> Self {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        for term in input
+            .split(|ch: char| ch.is_whitespace() || ch == ',')
+            .filter(|term| !term.is_empty())
+        {
+            match term.strip_prefix('!') {
+                Some(negated) if !negated.is_empty() => exclude.push(negated.to_lowercase()),
+                Some(_) => {}
+                None => include.push(term.to_lowercase()),
+            }
         }
-
-        let duration = clipped_end - clipped_start;
-        let display_name = resolve_summary_display_name(seg, &mut display_name_by_path);
-        let key = normalize_summary_group_key(&display_name);
-
-        let entry = totals.entry(key).or_insert_with(|| SummaryRow {
-            app_name: seg.app_name.clone(),
-            display_name,
-            duration_secs: 0,
-            process_path: seg.process_path.clone(),
-            is_idle: seg.is_idle,
-        });
-
-        entry.duration_secs += duration;
-        if should_prefer_process_path(entry.process_path.as_deref(), seg.process_path.as_deref()) {
-            entry.process_path = seg.process_path.clone();
-            entry.app_name = seg.app_name.clone();
-            entry.is_idle = seg.is_idle;
-        }
-    }
-
-    let mut rows: Vec<SummaryRow> = totals.into_values().collect();
-    rows.sort_by(|a, b| {
-        b.duration_secs
-            .cmp(&a.duration_secs)
-            .then_with(|| a.display_name.cmp(&b.display_name))
-            .then_with(|| a.app_name.cmp(&b.app_name))
-            .then_with(|| {
-                a.process_path
-                    .as_deref()
-                    .unwrap_or("")
-                    .cmp(b.process_path.as_deref().unwrap_or(""))
-            })
-            .then_with(|| a.is_idle.cmp(&b.is_idle))
-    });
-    rows
-}
-
-fn resolve_summary_display_name(
-    seg: &Segment,
-    display_name_by_path: &mut HashMap<String, Option<String>>,
-) -> String {
-    if let Some(path) = seg
-        .process_path
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .filter(|path| !is_synthetic_import_path(path))
-    {
-        let cached = display_name_by_path
-            .entry(path.to_owned())
-            .or_insert_with(|| load_app_file_description(path));
-        if let Some(name) = cached.as_deref() {
-            let trimmed = name.trim();
-            if !trimmed.is_empty() {
-                return trimmed.to_owned();
-            }
+        Self { include, exclude }
+    }
+
+    fn matches(&self, row: &SummaryRow) -> bool {
+        let mut names = vec![row.display_name.to_lowercase(), row.app_name.to_lowercase()];
+        if row.is_idle {
+            names.push("idle".to_owned());
+        }
+        let term_matches = |term: &String| names.iter().any(|name| filter_term_matches(term, name));
+        (self.include.is_empty() || self.include.iter().any(term_matches))
+            && !self.exclude.iter().any(term_matches)
+    }
+}
+
+fn filter_term_matches(term: &str, name: &str) -> bool {
+    if term.contains(['*', '?']) {
+        glob_matches(term.as_bytes(), name.as_bytes())
+    } else {
+        name.contains(term)
+    }
+}
+
+fn glob_matches(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == b'*')
+}
+
+fn load_rollover_summary(
+    db_path: &Path,
+    kind: RolloverKind,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    min_segment_secs: i64,
+) -> Result<RolloverSummary> {
+    let end_exclusive = end_date
+        .checked_add_days(Days::new(1))
+        .context("date out of range")?;
+    let (range_start, range_end) =
+        date_range_bounds(start_date, end_exclusive).context("failed to resolve period bounds")?;
+    let segments = load_segments_for_range(db_path, range_start, range_end)?;
+    let rows = build_summary_rows(range_start, range_end, &segments, min_segment_secs, load_app_file_description);
+    let total_secs = rows.iter().map(|row| row.duration_secs.max(0)).sum();
+    let top_app = rows
+        .iter()
+        .find(|row| {
+            !should_hide_in_visualization(&row.app_name, row.is_idle, row.process_path.as_deref())
+        })
+        .cloned();
+
+    let previous_total_secs = match previous_period_bounds(range_start, range_end) {
+        Some((previous_start, previous_end)) => {
+            let previous_segments = load_segments_for_range(db_path, previous_start, previous_end)?;
+            let previous_rows = build_summary_rows(
+                previous_start,
+                previous_end,
+                &previous_segments,
+                min_segment_secs,
+                load_app_file_description,
+            );
+            Some(previous_rows.iter().map(|row| row.duration_secs.max(0)).sum())
         }
-    }
-
-    display_app_name(&seg.app_name, seg.is_idle)
-}
+        None => None,
+    };
 
-fn normalize_summary_group_key(display_name: &str) -> String {
-    let mut key = String::with_capacity(display_name.len());
-    let mut prev_is_space = false;
-    for ch in display_name.trim().chars() {
-        if ch.is_whitespace() {
-            if !prev_is_space {
-                key.push(' ');
-                prev_is_space = true;
-            }
-            continue;
-        }
-        prev_is_space = false;
-        key.push(ch.to_ascii_lowercase());
-    }
-    key
+    let workday_average = (kind != RolloverKind::Day)
+        .then(|| average_workday_bounds(&compute_workday_spans(&segments, range_start, range_end)))
+        .flatten();
+
+    Ok(RolloverSummary {
+        kind,
+        start_date,
+        end_date,
+        total_secs,
+        previous_total_secs,
+        top_app,
+        workday_average,
+    })
 }
 
-fn should_prefer_process_path(current: Option<&str>, incoming: Option<&str>) -> bool {
-    let incoming = incoming
-        .map(str::trim)
-        .filter(|value| !value.is_empty());
-    let current = current
-        .map(str::trim)
-        .filter(|value| !value.is_empty());
-
-    match (current, incoming) {
-        (None, Some(_)) => true,
-        (Some(cur), Some(next)) => is_synthetic_import_path(cur) && !is_synthetic_import_path(next),
-        _ => false,
+fn previous_period_bounds(range_start: i64, range_end: i64) -> Option<(i64, i64)> {
+    // The All preset starts at the epoch, so there is nothing before it to compare.
+    if range_start <= 0 || range_end <= range_start {
+        return None;
     }
-}
-
-fn is_synthetic_import_path(path: &str) -> bool {
-    let path = path.trim();
-    path.starts_with("<import:") && path.ends_with('>')
+    let span = range_end - range_start;
+    Some((range_start.saturating_sub(span), range_start))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_timeline(
     ui: &mut egui::Ui,
     range_start: i64,
@@ -2577,10 +9390,18 @@ fn draw_timeline(
     summary_rows: &[SummaryRow],
     process_display_name_cache: &HashMap<String, String>,
     language: UiLanguage,
-) {
+    duration_format: DurationFormat,
+    clock_format: ClockFormat,
+    live_segment: Option<&TimelineRenderSegment>,
+    now_ts: Option<i64>,
+    commit_markers: &[GitCommitMarker],
+    segment_tags: &[SegmentTag],
+    selection: &mut Option<(i64, i64)>,
+    focus: &mut Option<(i64, i64)>,
+) -> Option<(i64, i64)> {
     if range_end <= range_start {
         ui.colored_label(Color32::from_rgb(180, 30, 30), "unable to resolve active range");
-        return;
+        return None;
     }
     let dark_mode = ui.visuals().dark_mode;
     let panel_bg = if dark_mode {
@@ -2645,6 +9466,36 @@ fn draw_timeline(
         Sense::click_and_drag(),
     );
 
+    // Shift-drag selects a span to tag instead of panning.
+    let shift_held = ui.input(|i| i.modifiers.shift);
+    if let Some(pos) = chart_hover.interact_pointer_pos().filter(|_| shift_held) {
+        let secs_per_px = (view_end - view_start) as f32 / chart_rect.width().max(1.0);
+        let ts = snap_to_segment_edge(
+            timeline_ts_at(pos.x, chart_rect, view_start, view_end),
+            timeline_segments,
+            (secs_per_px * 6.0) as i64,
+        );
+        if chart_hover.drag_started() {
+            *selection = Some((ts, ts));
+        } else if chart_hover.dragged() {
+            if let Some((anchor, _)) = *selection {
+                *selection = Some((anchor, ts));
+            }
+        }
+    }
+    if chart_hover.clicked() && !shift_held {
+        *selection = None;
+        chart_hover.request_focus();
+        *focus = find_hovered_timeline_segment(
+            chart_hover.interact_pointer_pos(),
+            chart_rect,
+            view_start,
+            view_end,
+            timeline_segments,
+        )
+        .map(|seg| (seg.start_ts, seg.end_ts));
+    }
+
     let mut view_changed = false;
     if chart_hover.double_clicked() {
         view_start = range_start;
@@ -2675,7 +9526,7 @@ fn draw_timeline(
         }
     }
 
-    if chart_hover.dragged() {
+    if chart_hover.dragged() && !shift_held {
         let delta_x = ui.input(|i| i.pointer.delta().x);
         if delta_x.abs() > f32::EPSILON {
             let span = (view_end - view_start).max(1);
@@ -2690,6 +9541,51 @@ fn draw_timeline(
         }
     }
 
+    // Arrows or Tab/Shift-Tab step through segments, Enter opens the focused one and
+    // Escape hands keyboard focus back.
+    let mut open_focused = false;
+    if chart_hover.has_focus() {
+        ui.memory_mut(|memory| {
+            memory.set_focus_lock_filter(
+                chart_hover.id,
+                egui::EventFilter {
+                    tab: true,
+                    horizontal_arrows: true,
+                    escape: true,
+                    ..Default::default()
+                },
+            )
+        });
+        let (back, forward, enter, escape) = ui.input_mut(|input| {
+            let back = input.consume_key(egui::Modifiers::SHIFT, egui::Key::Tab)
+                || input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowLeft);
+            let forward = input.consume_key(egui::Modifiers::NONE, egui::Key::Tab)
+                || input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowRight);
+            (
+                back,
+                forward,
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if back || forward {
+            if let Some(seg) = step_timeline_focus(timeline_segments, *focus, forward, view_start, view_end) {
+                *focus = Some((seg.start_ts, seg.end_ts));
+                if seg.start_ts < view_start || seg.end_ts > view_end {
+                    let span = (view_end - view_start).max(1);
+                    let center = seg.start_ts + (seg.end_ts - seg.start_ts) / 2;
+                    (view_start, view_end) = clamp_view_span(center - span / 2, span, range_start, range_end);
+                    view_changed = true;
+                }
+            }
+        }
+        open_focused = enter && focus.is_some();
+        if escape {
+            *focus = None;
+            ui.memory_mut(|memory| memory.surrender_focus(chart_hover.id));
+        }
+    }
+
     if view_changed {
         if view_start <= range_start && view_end >= range_end {
             *view_range = None;
@@ -2707,14 +9603,14 @@ fn draw_timeline(
         (range_end - range_start) == 24 * 3600 && view_start == range_start && view_end == range_end;
 
     let left_edge_label = if is_full_day_view {
-        "00".to_owned()
+        format_midnight_label(clock_format, false)
     } else {
-        format_tick_label(view_start, range_end - range_start)
+        format_tick_label(view_start, range_end - range_start, clock_format)
     };
     let right_edge_label = if is_full_day_view {
-        "24".to_owned()
+        format_midnight_label(clock_format, true)
     } else {
-        format_tick_label(view_end, range_end - range_start)
+        format_tick_label(view_end, range_end - range_start, clock_format)
     };
 
     painter.text(
@@ -2749,7 +9645,7 @@ fn draw_timeline(
             if is_major {
                 // Endpoint labels are drawn separately on left/right edges.
                 if tick > view_start && tick < view_end {
-                    let label = format_tick_label(tick, range_end - range_start);
+                    let label = format_tick_label(tick, range_end - range_start, clock_format);
                     painter.text(
                         Pos2::new(x, label_y),
                         Align2::CENTER_TOP,
@@ -2764,7 +9660,7 @@ fn draw_timeline(
     }
 
     for seg in visible_segments {
-        if should_hide_in_visualization(&seg.app_name, seg.is_idle, seg.process_path.as_deref()) {
+        if !is_drawn_on_timeline(seg) {
             continue;
         }
         let seg_start = seg.start_ts.max(view_start);
@@ -2782,23 +9678,192 @@ fn draw_timeline(
             Pos2::new((x1).max(x0 + 1.0), chart_rect.bottom()),
         );
 
-        let color = display_color_from_maps(
-            icon_colors,
-            app_colors,
-            seg.is_idle,
-            &seg.app_name,
-            seg.process_path.as_deref(),
-        );
+        let color = if seg.is_idle {
+            Color32::from_gray(if dark_mode { 78 } else { 206 })
+        } else {
+            display_color_from_maps(icon_colors, app_colors, seg.is_idle, &seg.app_name, seg.process_path.as_deref())
+        };
         painter.rect_filled(seg_rect, 2.0, color);
+        if seg.multi_title {
+            let shade = if dark_mode { Color32::BLACK } else { Color32::WHITE };
+            for (idx, run) in seg.title_runs.iter().enumerate() {
+                let run_start = run.start_ts.max(seg_start);
+                let run_end = run.end_ts.min(seg_end);
+                if idx % 2 == 0 || run_end <= run_start {
+                    continue;
+                }
+                let rx0 = chart_rect.left() + ((run_start - view_start) as f32 / view_span) * chart_rect.width();
+                let rx1 = chart_rect.left() + ((run_end - view_start) as f32 / view_span) * chart_rect.width();
+                painter.rect_filled(
+                    Rect::from_min_max(
+                        Pos2::new(rx0, chart_rect.top()),
+                        Pos2::new(rx1.max(rx0 + 1.0), chart_rect.bottom()),
+                    ),
+                    0.0,
+                    shade.gamma_multiply(0.22),
+                );
+            }
+        }
+        if seg_rect.width() >= TIMELINE_LABEL_MIN_WIDTH {
+            let label = resolve_timeline_app_label(seg, summary_rows, process_display_name_cache);
+            let text_color = timeline_label_color(color);
+            let mut job = egui::text::LayoutJob::simple_singleline(
+                bidi::display_auto(&label).into_owned(),
+                FontId::proportional(12.0),
+                text_color,
+            );
+            job.wrap = egui::text::TextWrapping::truncate_at_width(seg_rect.width() - 8.0);
+            let galley = ui.fonts(|fonts| fonts.layout_job(job));
+            let pos = Pos2::new(seg_rect.left() + 4.0, seg_rect.center().y - galley.size().y / 2.0);
+            painter.with_clip_rect(seg_rect).galley(pos, galley, text_color);
+        }
+    }
+    if let Some(seg) = focus.and_then(|(start, _)| visible_segments.iter().find(|seg| seg.start_ts == start)) {
+        let x0 = chart_rect.left() + ((seg.start_ts.max(view_start) - view_start) as f32 / view_span) * chart_rect.width();
+        let x1 = chart_rect.left() + ((seg.end_ts.min(view_end) - view_start) as f32 / view_span) * chart_rect.width();
+        let stroke = if chart_hover.has_focus() {
+            Stroke::new(2.0, ui.visuals().selection.stroke.color)
+        } else {
+            Stroke::new(1.5, ui.visuals().strong_text_color())
+        };
+        painter.rect_stroke(
+            Rect::from_min_max(
+                Pos2::new(x0, chart_rect.top() + 1.0),
+                Pos2::new(x1.max(x0 + 2.0), chart_rect.bottom() - 1.0),
+            ),
+            2.0,
+            stroke,
+        );
+    }
+
+    let tag_color = Color32::from_rgb(150, 110, 220);
+    for segment_tag in segment_tags {
+        let tag_start = segment_tag.start_ts.max(view_start);
+        let tag_end = segment_tag.end_ts.min(view_end);
+        if tag_end <= tag_start {
+            continue;
+        }
+        let x0 = chart_rect.left() + ((tag_start - view_start) as f32 / view_span) * chart_rect.width();
+        let x1 = chart_rect.left() + ((tag_end - view_start) as f32 / view_span) * chart_rect.width();
+        painter.rect_filled(
+            Rect::from_min_max(
+                Pos2::new(x0, chart_rect.top()),
+                Pos2::new(x1.max(x0 + 1.0), chart_rect.top() + 3.0),
+            ),
+            0.0,
+            tag_color,
+        );
+    }
+    if let Some((sel_start, sel_end)) = selection.map(|(a, b)| (a.min(b), a.max(b))) {
+        let sel_start = sel_start.max(view_start);
+        let sel_end = sel_end.min(view_end);
+        if sel_end > sel_start {
+            let x0 = chart_rect.left() + ((sel_start - view_start) as f32 / view_span) * chart_rect.width();
+            let x1 = chart_rect.left() + ((sel_end - view_start) as f32 / view_span) * chart_rect.width();
+            let sel_rect = Rect::from_min_max(
+                Pos2::new(x0, chart_rect.top()),
+                Pos2::new(x1.max(x0 + 1.0), chart_rect.bottom()),
+            );
+            painter.rect_filled(sel_rect, 0.0, Color32::from_rgba_unmultiplied(80, 130, 210, 50));
+            painter.rect_stroke(sel_rect, 0.0, Stroke::new(1.0, Color32::from_rgb(80, 130, 210)));
+        }
+    }
+
+    if let Some(live) = live_segment.filter(|live| {
+        !should_hide_in_visualization(&live.app_name, live.is_idle, live.process_path.as_deref())
+    }) {
+        let seg_start = live.start_ts.max(view_start);
+        let seg_end = live.end_ts.min(view_end);
+        if seg_end > seg_start {
+            let x0 = chart_rect.left() + ((seg_start - view_start) as f32 / view_span) * chart_rect.width();
+            let x1 = chart_rect.left() + ((seg_end - view_start) as f32 / view_span) * chart_rect.width();
+            let color = display_color_from_maps(
+                icon_colors,
+                app_colors,
+                live.is_idle,
+                &live.app_name,
+                live.process_path.as_deref(),
+            );
+            let pulse = (ui.input(|i| i.time) * 3.0).sin() as f32 * 0.5 + 0.5;
+            let live_rect = Rect::from_min_max(
+                Pos2::new(x0, chart_rect.top()),
+                Pos2::new(x1.max(x0 + 2.0), chart_rect.bottom()),
+            );
+            painter.rect_filled(live_rect, 2.0, color.gamma_multiply(0.45 + 0.45 * pulse));
+            painter.rect_stroke(live_rect, 2.0, Stroke::new(1.0, color));
+            ui.ctx().request_repaint_after(Duration::from_millis(50));
+        }
+    }
+
+    if let Some(now) = now_ts.filter(|now| *now >= view_start && *now <= view_end) {
+        let x = chart_rect.left() + ((now - view_start) as f32 / view_span) * chart_rect.width();
+        let now_color = Color32::from_rgb(220, 70, 60);
+        painter.line_segment(
+            [Pos2::new(x, chart_rect.top() - 3.0), Pos2::new(x, chart_rect.bottom())],
+            Stroke::new(1.5, now_color),
+        );
+        painter.circle_filled(Pos2::new(x, chart_rect.top() - 3.0), 2.5, now_color);
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+    }
+
+    let marker_color = if dark_mode {
+        Color32::from_rgb(240, 240, 240)
+    } else {
+        Color32::from_rgb(40, 40, 40)
+    };
+    let mut hovered_commits: Vec<&GitCommitMarker> = Vec::new();
+    for commit in commit_markers {
+        if commit.ts < view_start || commit.ts > view_end {
+            continue;
+        }
+        let x = chart_rect.left() + ((commit.ts - view_start) as f32 / view_span) * chart_rect.width();
+        let tip = Pos2::new(x, chart_rect.bottom() - 7.0);
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                tip,
+                Pos2::new(x + 4.0, chart_rect.bottom() - 1.0),
+                Pos2::new(x - 4.0, chart_rect.bottom() - 1.0),
+            ],
+            marker_color,
+            Stroke::new(1.0, chart_bg),
+        ));
+        if chart_hover
+            .hover_pos()
+            .is_some_and(|pos| (pos.x - x).abs() <= 4.0 && pos.y >= chart_rect.bottom() - 12.0)
+        {
+            hovered_commits.push(commit);
+        }
     }
 
-    if let Some(seg) = find_hovered_timeline_segment(
+    if !hovered_commits.is_empty() {
+        egui::show_tooltip_at_pointer(
+            ui.ctx(),
+            ui.layer_id(),
+            ui.id().with("timeline_commit_tooltip"),
+            |ui| {
+                for commit in &hovered_commits {
+                    ui.label(
+                        egui::RichText::new(format!("{} {}", format_hms(commit.ts, clock_format), commit.repo))
+                            .strong(),
+                    );
+                    ui.label(&commit.summary);
+                }
+            },
+        );
+    } else if let Some(seg) = find_hovered_timeline_segment(
         chart_hover.hover_pos(),
         chart_rect,
         view_start,
         view_end,
         visible_segments,
     ) {
+        let hovered_run = chart_hover.hover_pos().and_then(|pos| {
+            let ratio = ((pos.x - chart_rect.left()) / chart_rect.width().max(1.0)).clamp(0.0, 1.0);
+            let hover_ts = view_start + (ratio * view_span) as i64;
+            seg.title_runs
+                .iter()
+                .find(|run| run.start_ts <= hover_ts && hover_ts < run.end_ts)
+        });
         let duration = seg.end_ts.saturating_sub(seg.start_ts);
         let app_label = resolve_timeline_app_label(seg, summary_rows, process_display_name_cache);
         egui::show_tooltip_at_pointer(
@@ -2812,42 +9877,220 @@ fn draw_timeline(
                     Color32::BLACK
                 };
                 ui.label(
-                    egui::RichText::new(format!(
-                        "{}: {}",
+                    egui::RichText::new(tip_line(
+                        language,
                         timeline_tip_text(language, "app"),
-                        app_label
+                        &bidi::display_auto(&app_label),
                     ))
                     .strong()
                     .color(app_label_color),
                 );
-                if seg.multi_title {
-                    ui.label(format!(
-                        "{}: {}",
+                if let Some(run) = hovered_run.filter(|_| seg.multi_title) {
+                    ui.label(tip_line(
+                        language,
+                        timeline_tip_text(language, "title"),
+                        &bidi::display_auto(run.title.as_deref().unwrap_or("-")),
+                    ));
+                    ui.label(tip_line(
+                        language,
+                        timeline_tip_text(language, "duration"),
+                        &format!(
+                            "{} ({} - {})",
+                            format_duration(run.end_ts - run.start_ts, duration_format),
+                            format_hms(run.start_ts, clock_format),
+                            format_hms(run.end_ts, clock_format)
+                        ),
+                    ));
+                    ui.separator();
+                } else if seg.multi_title {
+                    ui.label(tip_line(
+                        language,
                         timeline_tip_text(language, "title"),
-                        timeline_tip_text(language, "multi_title")
+                        timeline_tip_text(language, "multi_title"),
                     ));
                 } else if let Some(title) = &seg.title {
-                    ui.label(format!(
-                        "{}: {}",
-                        timeline_tip_text(language, "title"),
-                        title
-                    ));
+                    ui.label(tip_line(language, timeline_tip_text(language, "title"), &bidi::display_auto(title)));
                 }
-                ui.label(format!(
-                    "{}: {}",
+                ui.label(tip_line(
+                    language,
                     timeline_tip_text(language, "duration"),
-                    format_duration(duration)
+                    &format_duration(duration, duration_format),
                 ));
-                ui.label(format!(
-                    "{}: {} - {}",
+                ui.label(tip_line(
+                    language,
                     timeline_tip_text(language, "range"),
-                    format_hms(seg.start_ts),
-                    format_hms(seg.end_ts)
+                    &format!("{} - {}", format_hms(seg.start_ts, clock_format), format_hms(seg.end_ts, clock_format)),
                 ));
+                let tags: BTreeSet<&str> = segment_tags
+                    .iter()
+                    .filter(|segment_tag| segment_tag.start_ts < seg.end_ts && segment_tag.end_ts > seg.start_ts)
+                    .map(|segment_tag| segment_tag.tag.as_str())
+                    .collect();
+                if !tags.is_empty() {
+                    ui.label(tip_line(
+                        language,
+                        timeline_tip_text(language, "tags"),
+                        &bidi::display_auto(&tags.into_iter().collect::<Vec<_>>().join(", ")),
+                    ));
+                }
             },
         );
     }
 
+    if open_focused {
+        return *focus;
+    }
+    // Right-click tags the selection when it's under the pointer, else the hovered block.
+    if !chart_hover.secondary_clicked() {
+        return None;
+    }
+    let pos = chart_hover.interact_pointer_pos()?;
+    let ts = timeline_ts_at(pos.x, chart_rect, view_start, view_end);
+    if let Some((sel_start, sel_end)) = selection.map(|(a, b)| (a.min(b), a.max(b))) {
+        if sel_end > sel_start && ts >= sel_start && ts < sel_end {
+            return Some((sel_start, sel_end));
+        }
+    }
+    find_hovered_timeline_segment(Some(pos), chart_rect, view_start, view_end, visible_segments)
+        .map(|seg| (seg.start_ts, seg.end_ts))
+}
+
+fn step_timeline_focus(
+    segments: &[TimelineRenderSegment],
+    focus: Option<(i64, i64)>,
+    forward: bool,
+    view_start: i64,
+    view_end: i64,
+) -> Option<&TimelineRenderSegment> {
+    let shown = |seg: &&TimelineRenderSegment| is_drawn_on_timeline(seg);
+    match (focus, forward) {
+        (Some((start, _)), true) => segments.iter().filter(shown).find(|seg| seg.start_ts > start),
+        (Some((start, _)), false) => segments.iter().rev().filter(shown).find(|seg| seg.start_ts < start),
+        (None, true) => segments.iter().filter(shown).find(|seg| seg.end_ts > view_start),
+        (None, false) => segments.iter().rev().filter(shown).find(|seg| seg.start_ts < view_end),
+    }
+}
+
+// Selection edges snap to a segment boundary within `tolerance_secs`.
+fn snap_to_segment_edge(ts: i64, segments: &[TimelineRenderSegment], tolerance_secs: i64) -> i64 {
+    let idx = segments.partition_point(|seg| seg.end_ts < ts);
+    segments[idx.saturating_sub(1)..(idx + 1).min(segments.len())]
+        .iter()
+        .flat_map(|seg| [seg.start_ts, seg.end_ts])
+        .filter(|edge| (edge - ts).abs() <= tolerance_secs)
+        .min_by_key(|edge| (edge - ts).abs())
+        .unwrap_or(ts)
+}
+
+fn timeline_ts_at(x: f32, chart_rect: Rect, view_start: i64, view_end: i64) -> i64 {
+    let ratio = ((x - chart_rect.left()) / chart_rect.width().max(1.0)).clamp(0.0, 1.0);
+    view_start + (ratio * (view_end - view_start) as f32) as i64
+}
+
+// One bar per hour of the day scaled to its active minutes, so busy hours stay
+// visible while the timeline below is zoomed in. Clicking a bar moves the view there.
+fn draw_hourly_density_ruler(
+    ui: &mut egui::Ui,
+    range_start: i64,
+    range_end: i64,
+    timeline_segments: &[TimelineRenderSegment],
+    view_range: &mut Option<(i64, i64)>,
+    duration_format: DurationFormat,
+    clock_format: ClockFormat,
+) {
+    const HOUR_SECS: i64 = 3600;
+    if range_end <= range_start {
+        return;
+    }
+    let hour_count = ((range_end - range_start + HOUR_SECS - 1) / HOUR_SECS) as usize;
+    let mut active_secs = vec![0_i64; hour_count];
+    for seg in timeline_segments {
+        if seg.is_idle
+            || should_hide_in_visualization(&seg.app_name, seg.is_idle, seg.process_path.as_deref())
+        {
+            continue;
+        }
+        let mut start = seg.start_ts.max(range_start);
+        let end = seg.end_ts.min(range_end);
+        while start < end {
+            let hour = ((start - range_start) / HOUR_SECS) as usize;
+            let hour_end = (range_start + (hour as i64 + 1) * HOUR_SECS).min(end);
+            if let Some(slot) = active_secs.get_mut(hour) {
+                *slot += hour_end - start;
+            }
+            start = hour_end;
+        }
+    }
+
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width().max(120.0), TIMELINE_DENSITY_HEIGHT),
+        Sense::click(),
+    );
+    let strip = Rect::from_min_max(
+        Pos2::new(rect.left() + 4.0, rect.top()),
+        Pos2::new((rect.right() - 4.0).max(rect.left() + 60.0), rect.bottom() - 2.0),
+    );
+    let painter = ui.painter_at(rect);
+    let dark_mode = ui.visuals().dark_mode;
+    let (bar_color, view_fill) = if dark_mode {
+        (Color32::from_rgb(120, 160, 110), Color32::from_rgba_unmultiplied(255, 255, 255, 18))
+    } else {
+        (Color32::from_rgb(104, 150, 92), Color32::from_rgba_unmultiplied(0, 0, 0, 14))
+    };
+
+    let span = (range_end - range_start) as f32;
+    let x_for = |ts: i64| strip.left() + ((ts - range_start) as f32 / span) * strip.width();
+    let (view_start, view_end) = sanitize_view_range(*view_range, range_start, range_end);
+    if view_range.is_some() {
+        painter.rect_filled(
+            Rect::from_min_max(
+                Pos2::new(x_for(view_start), strip.top()),
+                Pos2::new(x_for(view_end), strip.bottom()),
+            ),
+            2.0,
+            view_fill,
+        );
+    }
+    for (hour, secs) in active_secs.iter().enumerate() {
+        if *secs <= 0 {
+            continue;
+        }
+        let hour_start = range_start + hour as i64 * HOUR_SECS;
+        let hour_end = (hour_start + HOUR_SECS).min(range_end);
+        let ratio = (*secs as f32 / (hour_end - hour_start).max(1) as f32).clamp(0.0, 1.0);
+        let height = (strip.height() * ratio).max(1.0);
+        painter.rect_filled(
+            Rect::from_min_max(
+                Pos2::new(x_for(hour_start) + 1.0, strip.bottom() - height),
+                Pos2::new((x_for(hour_end) - 1.0).max(x_for(hour_start) + 2.0), strip.bottom()),
+            ),
+            1.0,
+            bar_color,
+        );
+    }
+
+    let Some(pointer) = response.hover_pos() else {
+        return;
+    };
+    let hour = (((pointer.x - strip.left()) / strip.width().max(1.0)).clamp(0.0, 0.9999)
+        * hour_count as f32) as usize;
+    let hour_start = range_start + hour as i64 * HOUR_SECS;
+    let active = active_secs.get(hour).copied().unwrap_or(0);
+    response.clone().on_hover_text(format!(
+        "{} \u{00B7} {}",
+        format_tick_label(hour_start, range_end - range_start, clock_format),
+        format_duration(active, duration_format)
+    ));
+    if response.clicked() {
+        let current_span = view_range.map_or(HOUR_SECS, |(start, end)| end - start);
+        let center = hour_start + HOUR_SECS / 2;
+        *view_range = Some(clamp_view_span(
+            center - current_span / 2,
+            current_span,
+            range_start,
+            range_end,
+        ));
+    }
 }
 
 fn visible_timeline_segment_bounds(
@@ -2863,13 +10106,13 @@ fn visible_timeline_segment_bounds(
     (start.min(end), end)
 }
 
-fn find_hovered_timeline_segment<'a>(
+fn find_hovered_timeline_segment(
     hover_pos: Option<Pos2>,
     chart_rect: Rect,
     day_start: i64,
     day_end: i64,
-    segments: &'a [TimelineRenderSegment],
-) -> Option<&'a TimelineRenderSegment> {
+    segments: &[TimelineRenderSegment],
+) -> Option<&TimelineRenderSegment> {
     let hover_pos = hover_pos?;
     if !chart_rect.contains(hover_pos) {
         return None;
@@ -2886,102 +10129,22 @@ fn find_hovered_timeline_segment<'a>(
     if hover_ts < seg.start_ts || hover_ts >= seg.end_ts {
         return None;
     }
-    if should_hide_in_visualization(&seg.app_name, seg.is_idle, seg.process_path.as_deref()) {
+    if !is_drawn_on_timeline(seg) {
         return None;
     }
     Some(seg)
 }
 
-fn build_timeline_segments(
-    day_start: i64,
-    day_end: i64,
-    segments: &[Segment],
-    selected_app_keys: &HashSet<String>,
-) -> Vec<TimelineRenderSegment> {
-    const MERGE_GAP_TOLERANCE_SECS: i64 = 1;
-
-    let mut merged: Vec<TimelineRenderSegment> = Vec::new();
-
-    for seg in segments {
-        if !selected_app_keys.is_empty()
-            && !selected_app_keys.contains(&normalize_app_key(&seg.app_name))
-        {
-            continue;
-        }
-
-        let clipped_start = seg.start_ts.max(day_start);
-        let clipped_end = seg.end_ts.min(day_end);
-        if clipped_end <= clipped_start {
-            continue;
-        }
-
-        if let Some(last) = merged.last_mut() {
-            if last.is_idle == seg.is_idle
-                && last.app_name == seg.app_name
-                && last.process_path == seg.process_path
-                && clipped_start <= last.end_ts.saturating_add(MERGE_GAP_TOLERANCE_SECS)
-            {
-                if clipped_end > last.end_ts {
-                    last.end_ts = clipped_end;
-                }
-                if !same_title(&last.title, &seg.title) {
-                    last.multi_title = true;
-                }
-                continue;
-            }
-        }
-
-        merged.push(TimelineRenderSegment {
-            start_ts: clipped_start,
-            end_ts: clipped_end,
-            is_idle: seg.is_idle,
-            app_name: seg.app_name.clone(),
-            process_path: seg.process_path.clone(),
-            title: seg.title.clone(),
-            multi_title: false,
-        });
-    }
-
-    merged
-}
-
-fn same_title(a: &Option<String>, b: &Option<String>) -> bool {
-    match (a, b) {
-        (Some(x), Some(y)) => x == y,
-        (None, None) => true,
-        _ => false,
-    }
+// Away blocks are idle time too, but they are drawn so a long absence shows on the timeline.
+fn is_drawn_on_timeline(seg: &TimelineRenderSegment) -> bool {
+    (seg.is_idle && seg.app_name == AWAY_APP)
+        || !should_hide_in_visualization(&seg.app_name, seg.is_idle, seg.process_path.as_deref())
 }
 
 fn draw_fallback_icon(painter: &egui::Painter, rect: Rect, fill: Color32) {
     painter.rect_filled(rect, 3.0, fill);
 }
 
-fn display_app_name(raw_name: &str, is_idle: bool) -> String {
-    if is_idle {
-        return "IDLE".to_owned();
-    }
-
-    let trimmed = raw_name.trim();
-    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("UNKNOWN") {
-        return "UNKNOWN".to_owned();
-    }
-
-    strip_exe_suffix(trimmed).to_owned()
-}
-
-fn strip_exe_suffix(name: &str) -> &str {
-    if name.to_ascii_lowercase().ends_with(".exe") && name.len() > 4 {
-        &name[..name.len() - 4]
-    } else {
-        name
-    }
-}
-
-fn normalize_app_key(app_name: &str) -> String {
-    strip_exe_suffix(app_name.trim()).to_ascii_lowercase()
-}
-
 fn display_color_from_maps(
     icon_colors: &HashMap<String, Color32>,
     app_colors: &HashMap<String, Color32>,
@@ -3002,60 +10165,6 @@ fn display_color_from_maps(
     color_for_app(false, app_name)
 }
 
-fn is_system_level_app(app_name: &str, process_path: Option<&str>) -> bool {
-    let app = normalize_app_key(app_name);
-    if app == "explorer" {
-        return false;
-    }
-    const SYSTEM_APPS: [&str; 11] = [
-        "searchhost",
-        "shellexperiencehost",
-        "startmenuexperiencehost",
-        "applicationframehost",
-        "runtimebroker",
-        "textinputhost",
-        "taskhostw",
-        "sihost",
-        "lockapp",
-        "dwm",
-        "ctfmon",
-    ];
-    if SYSTEM_APPS.contains(&app.as_str()) {
-        return true;
-    }
-
-    let Some(path) = process_path else {
-        return false;
-    };
-    let normalized = path.trim().replace('/', "\\").to_ascii_lowercase();
-    normalized.starts_with(r"c:\windows\") || normalized.starts_with(r"\\?\c:\windows\")
-}
-
-fn should_hide_in_visualization(app_name: &str, is_idle: bool, process_path: Option<&str>) -> bool {
-    is_idle || is_system_level_app(app_name, process_path)
-}
-
-fn sanitize_view_range(
-    view_range: Option<(i64, i64)>,
-    day_start: i64,
-    day_end: i64,
-) -> (i64, i64) {
-    let day_span = (day_end - day_start).max(1);
-    if let Some((start, end)) = view_range {
-        let span = (end - start).clamp(MIN_TIMELINE_VIEW_SECS, day_span);
-        return clamp_view_span(start, span, day_start, day_end);
-    }
-    (day_start, day_end)
-}
-
-fn clamp_view_span(start: i64, span: i64, day_start: i64, day_end: i64) -> (i64, i64) {
-    let day_span = (day_end - day_start).max(1);
-    let span = span.clamp(MIN_TIMELINE_VIEW_SECS, day_span);
-    let max_start = day_end - span;
-    let clamped_start = start.clamp(day_start, max_start);
-    (clamped_start, clamped_start + span)
-}
-
 fn dominant_color_from_icon(image: &egui::ColorImage) -> Option<Color32> {
     let mut bins: HashMap<u16, (u32, u64, u64, u64)> = HashMap::new();
 
@@ -3101,10 +10210,6 @@ fn dominant_color_from_icon(image: &egui::ColorImage) -> Option<Color32> {
     Some(Color32::from_rgb(r, g, b))
 }
 
-fn should_hide_summary_app(app_name: &str, is_idle: bool, process_path: Option<&str>) -> bool {
-    should_hide_in_visualization(app_name, is_idle, process_path)
-}
-
 fn month_start(date: NaiveDate) -> NaiveDate {
     NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap_or(date)
 }
@@ -3132,12 +10237,6 @@ fn quarter_start(date: NaiveDate) -> Option<NaiveDate> {
     NaiveDate::from_ymd_opt(date.year(), quarter_month, 1)
 }
 
-fn date_range_bounds(start_date: NaiveDate, end_exclusive_date: NaiveDate) -> Option<(i64, i64)> {
-    let start = local_midnight_ts(start_date)?;
-    let end = local_midnight_ts(end_exclusive_date)?;
-    Some((start, end))
-}
-
 fn rolling_range_bounds(anchor_date: NaiveDate, days: u64) -> Option<(i64, i64)> {
     if days == 0 {
         return None;
@@ -3242,11 +10341,19 @@ fn is_major_tick(_ts: i64, _step_secs: i64) -> bool {
     true
 }
 
-fn format_tick_label(ts: i64, range_span: i64) -> String {
+fn format_midnight_label(clock_format: ClockFormat, end_of_day: bool) -> String {
+    match (clock_format, end_of_day) {
+        (ClockFormat::H24, false) => "00".to_owned(),
+        (ClockFormat::H24, true) => "24".to_owned(),
+        (ClockFormat::H12, _) => "12AM".to_owned(),
+    }
+}
+
+fn format_tick_label(ts: i64, range_span: i64, clock_format: ClockFormat) -> String {
     if let Some(dt) = Local.timestamp_opt(ts, 0).single() {
         let day_span = 24 * 3600;
         if range_span <= 2 * day_span {
-            return dt.format("%H").to_string();
+            return dt.format(clock_format.hour_pattern()).to_string();
         }
         if range_span <= 120 * day_span {
             return dt.format("%m-%d").to_string();
@@ -3256,39 +10363,294 @@ fn format_tick_label(ts: i64, range_span: i64) -> String {
     "--".to_owned()
 }
 
-fn probe_backend_status(db_path: &PathBuf) -> Result<BackendStatus> {
+fn probe_backend_status(connections: &ConnectionPool) -> Result<BackendStatus> {
     let checked_ts = unix_seconds_now();
     let daemon_running = is_tracker_daemon_running();
-    let last_write_ts = load_latest_segment_end_ts(db_path)?;
-    let heartbeat_recent = last_write_ts
-        .map(|ts| checked_ts.saturating_sub(ts) <= BACKEND_HEARTBEAT_GRACE_SECS)
-        .unwrap_or(false);
+    let conn = connections.read()?;
+    let last_write_ts = load_latest_segment_end_ts(&conn)?;
+    let heartbeat = load_backend_heartbeat(&conn);
+    let alive = match heartbeat {
+        Some((_, heartbeat_ts, _)) => checked_ts.saturating_sub(heartbeat_ts) <= BACKEND_HEARTBEAT_STALE_SECS,
+        // Without a heartbeat only a recent segment shows life, which long idle
+        // stretches can't provide.
+        None => last_write_ts.is_none_or(|ts| checked_ts.saturating_sub(ts) <= BACKEND_HEARTBEAT_GRACE_SECS),
+    };
 
-    let (health, detail) = if daemon_running && (heartbeat_recent || last_write_ts.is_none()) {
-        (BackendHealth::Running, None)
+    let health = if daemon_running && alive {
+        BackendHealth::Running
+    } else {
+        BackendHealth::Stopped
+    };
+    let stop_reason = if health == BackendHealth::Running {
+        None
+    } else if daemon_running {
+        Some(BackendStopReason::Unresponsive {
+            heartbeat: heartbeat.and_then(|(pid, ts, _)| Some((u32::try_from(pid).ok()?, ts))),
+        })
+    } else if let Some(backend_exe) = backend_executable_path().ok().filter(|exe| !exe.is_file()) {
+        Some(BackendStopReason::BinaryMissing(backend_exe))
     } else {
-        (BackendHealth::Stopped, None)
+        // A clean exit clears the heartbeat, so a report written after the last
+        // sign of life is what ended it.
+        heartbeat
+            .map(|(_, ts, _)| ts)
+            .or(last_write_ts)
+            .and_then(|since_ts| crash::latest_report_since(&crash::logs_dir(connections.db_path()), since_ts))
+            .filter(|report| report.binary.ends_with("backend"))
+            .map(BackendStopReason::Crashed)
     };
 
+    let live_segment = if health == BackendHealth::Running {
+        load_live_segment(&conn, checked_ts)
+    } else {
+        None
+    };
+
     Ok(BackendStatus {
         health,
         last_write_ts,
+        heartbeat_ts: heartbeat.map(|(_, ts, _)| ts),
+        paused: heartbeat.is_some_and(|(_, _, paused)| paused),
         checked_ts,
-        detail,
+        detail: None,
+        stop_reason,
+        live_segment,
     })
 }
 
-fn load_latest_segment_end_ts(db_path: &PathBuf) -> Result<Option<i64>> {
-    let conn = Connection::open(db_path)
-        .with_context(|| format!("failed to open database: {}", db_path.display()))?;
-    let latest_end_ts = conn
-        .query_row("SELECT MAX(end_ts) FROM segments", [], |row| {
-            row.get::<_, Option<i64>>(0)
+// The backend's open segment, extended to `now_ts`. Older backends have no
+// live_segment table, which reads as no live activity.
+fn load_live_segment(conn: &Connection, now_ts: i64) -> Option<TimelineRenderSegment> {
+    conn.query_row(
+        "\
+        SELECT l.start_ts, l.is_idle, a.exe_name, a.process_path, t.title
+        FROM live_segment l
+        LEFT JOIN apps a ON a.id = l.app_id
+        LEFT JOIN titles t ON t.id = l.title_id
+        WHERE l.id = 1",
+        [],
+        |row| {
+            let start_ts: i64 = row.get(0)?;
+            let is_idle = row.get::<_, i64>(1)? != 0;
+            let app_name: Option<String> = row.get(2)?;
+            Ok(TimelineRenderSegment {
+                start_ts,
+                end_ts: now_ts.max(start_ts),
+                is_idle,
+                app_name: if is_idle {
+                    "IDLE".to_owned()
+                } else {
+                    app_name.unwrap_or_else(|| "UNKNOWN".to_owned())
+                },
+                process_path: row.get(3)?,
+                title: row.get(4)?,
+                multi_title: false,
+                title_runs: Vec::new(),
+            })
+        },
+    )
+    .ok()
+}
+
+// (pid, timestamp, paused) of the backend's last poll. Gone after a clean exit,
+// and before the backend created the table.
+fn load_backend_heartbeat(conn: &Connection) -> Option<(i64, i64, bool)> {
+    conn.prepare_cached("SELECT pid, ts, paused FROM heartbeat WHERE id = 1")
+        .ok()?
+        .query_row([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get::<_, i64>(2)? != 0))
+        })
+        .ok()
+}
+
+fn is_database_locked(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause.downcast_ref::<rusqlite::Error>().is_some_and(|err| {
+            matches!(
+                err.sqlite_error_code(),
+                Some(rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+            )
         })
+    })
+}
+
+fn load_latest_segment_end_ts(conn: &Connection) -> Result<Option<i64>> {
+    let latest_end_ts = conn
+        .prepare_cached("SELECT MAX(end_ts) FROM segments WHERE deleted_ts IS NULL")
+        .and_then(|mut stmt| stmt.query_row([], |row| row.get::<_, Option<i64>>(0)))
         .context("failed to query latest segment timestamp")?;
     Ok(latest_end_ts)
 }
 
+fn backend_restart_backoff(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    BACKEND_RESTART_BASE_BACKOFF
+        .saturating_mul(1_u32 << exponent)
+        .min(BACKEND_RESTART_MAX_BACKOFF)
+}
+
+fn backend_executable_path() -> Result<PathBuf> {
+    let viewer_exe = env::current_exe().context("failed to resolve viewer executable path")?;
+    let file_name = if cfg!(target_os = "windows") {
+        "limetrace-backend.exe"
+    } else {
+        "limetrace-backend"
+    };
+    Ok(viewer_exe.with_file_name(file_name))
+}
+
+// The heartbeat is stale by the time this runs, so its pid may since have been
+// reused: only a limetrace-backend already running at that heartbeat is ended.
+fn ensure_is_stuck_backend(pid: u32, image: &Path, started_ts: Option<i64>, heartbeat_ts: i64) -> Result<()> {
+    let is_backend = image
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| stem.eq_ignore_ascii_case("limetrace-backend"));
+    if !is_backend || started_ts.is_none_or(|started_ts| started_ts > heartbeat_ts) {
+        bail!(
+            "pid {pid} ({}) is not the backend that stopped responding; end the stuck backend yourself",
+            image.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn end_stuck_backend(pid: u32, heartbeat_ts: i64) -> Result<()> {
+    const FILETIME_UNIX_EPOCH_SECS: i64 = 11_644_473_600;
+    let handle = unsafe { OpenProcess(PROCESS_TERMINATE | PROCESS_QUERY_LIMITED_INFORMATION, 0, pid) };
+    if handle.is_null() {
+        bail!("failed to open pid {pid}; end the stuck backend yourself");
+    }
+    let result = (|| {
+        let mut buffer: Vec<u16> = vec![0; 4096];
+        let mut size = buffer.len() as u32;
+        let image = if unsafe { QueryFullProcessImageNameW(handle, 0, buffer.as_mut_ptr(), &mut size) } != 0 {
+            PathBuf::from(String::from_utf16_lossy(&buffer[..size as usize]))
+        } else {
+            PathBuf::new()
+        };
+        let mut times = [FILETIME {
+            dwLowDateTime: 0,
+            dwHighDateTime: 0,
+        }; 4];
+        let [creation, exit, kernel, user] = &mut times;
+        let started_ts = (unsafe { GetProcessTimes(handle, creation, exit, kernel, user) } != 0).then(|| {
+            let ticks = (u64::from(creation.dwHighDateTime) << 32) | u64::from(creation.dwLowDateTime);
+            (ticks / 10_000_000) as i64 - FILETIME_UNIX_EPOCH_SECS
+        });
+        ensure_is_stuck_backend(pid, &image, started_ts, heartbeat_ts)?;
+        if unsafe { TerminateProcess(handle, 1) } == 0 {
+            bail!("failed to end pid {pid}");
+        }
+        Ok(())
+    })();
+    unsafe {
+        CloseHandle(handle);
+    }
+    result
+}
+
+#[cfg(not(target_os = "windows"))]
+fn end_stuck_backend(pid: u32, heartbeat_ts: i64) -> Result<()> {
+    let proc_dir = PathBuf::from(format!("/proc/{pid}"));
+    let image = fs::read_link(proc_dir.join("exe")).unwrap_or_default();
+    ensure_is_stuck_backend(pid, &image, process_start_ts(pid), heartbeat_ts)?;
+    let pid = libc::pid_t::try_from(pid).with_context(|| format!("invalid pid {pid}"))?;
+    if unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+        return Err(std::io::Error::last_os_error()).with_context(|| format!("failed to end pid {pid}"));
+    }
+    Ok(())
+}
+
+/// When `pid` started, from the starttime field of `/proc/<pid>/stat` (clock ticks
+/// since boot) and the boot time in `/proc/stat`.
+#[cfg(not(target_os = "windows"))]
+fn process_start_ts(pid: u32) -> Option<i64> {
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name (field 2) may hold spaces and parentheses; fields after it
+    // start at 3, so starttime (22) is the 20th.
+    let (_, fields) = stat.rsplit_once(')')?;
+    let start_ticks: i64 = fields.split_whitespace().nth(19)?.parse().ok()?;
+    let boot_ts: i64 = fs::read_to_string("/proc/stat")
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse()
+        .ok()?;
+    let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    (ticks_per_sec > 0).then(|| boot_ts + start_ticks / ticks_per_sec as i64)
+}
+
+fn launch_backend_process(db_path: &PathBuf) -> Result<u32> {
+    let backend_exe = backend_executable_path()?;
+    if !backend_exe.is_file() {
+        bail!("backend executable not found: {}", backend_exe.display());
+    }
+
+    let mut command = Command::new(&backend_exe);
+    command
+        .arg("--db")
+        .arg(db_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let child = command
+        .spawn()
+        .with_context(|| format!("failed to launch backend: {}", backend_exe.display()))?;
+    Ok(child.id())
+}
+
+// Left in the logs folder while the viewer starts; finding it means earlier startups
+// never got as far as removing it.
+fn begin_startup_attempt(marker: &Path) -> bool {
+    let attempts: u32 = fs::read_to_string(marker)
+        .ok()
+        .and_then(|content| content.trim().parse().ok())
+        .unwrap_or(0);
+    if let Some(parent) = marker.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(marker, (attempts + 1).to_string());
+    attempts >= STARTUP_CRASH_LIMIT
+}
+
+fn open_in_default_app(path: &Path) -> Result<()> {
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let program = "xdg-open";
+    Command::new(program)
+        .arg(path)
+        .spawn()
+        .with_context(|| format!("failed to run {program}"))?;
+    Ok(())
+}
+
+fn append_viewer_log(data_root: &Path, message: &str) -> Result<()> {
+    let log_dir = data_root.join("logs");
+    fs::create_dir_all(&log_dir)
+        .with_context(|| format!("failed to create log directory: {}", log_dir.display()))?;
+    let log_path = log_dir.join("limetrace.log");
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("failed to open log file: {}", log_path.display()))?;
+    writeln!(file, "[{}] {message}", Local::now().format("%Y-%m-%d %H:%M:%S"))
+        .context("failed to write log entry")?;
+    Ok(())
+}
+
 #[cfg(target_os = "windows")]
 fn is_tracker_daemon_running() -> bool {
     const MUTEX_SYNCHRONIZE_ACCESS: u32 = 0x0010_0000;
@@ -3312,77 +10674,433 @@ fn is_tracker_daemon_running() -> bool {
     false
 }
 
-fn load_segments_for_range(db_path: &PathBuf, range_start: i64, range_end: i64) -> Result<Vec<Segment>> {
-    let conn = Connection::open(db_path)
-        .with_context(|| format!("failed to open database: {}", db_path.display()))?;
+#[cfg(target_os = "windows")]
+struct ViewerInstanceGuard {
+    handle: HANDLE,
+}
 
-    let mut stmt = conn.prepare(
-        "\
-        SELECT
-          s.start_ts,
-          s.end_ts,
-          s.is_idle,
-          a.exe_name,
-          a.process_path,
-          t.title
-        FROM segments s
-        LEFT JOIN apps a ON a.id = s.app_id
-        LEFT JOIN titles t ON t.id = s.title_id
-        WHERE s.end_ts > ?1
-          AND s.start_ts < ?2
-        ORDER BY s.start_ts ASC",
-    )?;
-
-    let mut rows = stmt.query(params![range_start, range_end])?;
-    let mut result = Vec::new();
-    while let Some(row) = rows.next()? {
-        let is_idle: i64 = row.get(2)?;
-        let app_name: Option<String> = row.get(3)?;
-        let process_path: Option<String> = row.get(4)?;
-        let title: Option<String> = row.get(5)?;
-
-        result.push(Segment {
-            start_ts: row.get(0)?,
-            end_ts: row.get(1)?,
-            is_idle: is_idle != 0,
-            app_name: app_name.unwrap_or_else(|| "UNKNOWN".to_owned()),
-            process_path,
-            title,
+#[cfg(target_os = "windows")]
+impl Drop for ViewerInstanceGuard {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe {
+                CloseHandle(self.handle);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+struct ViewerInstanceGuard;
+
+#[cfg(target_os = "windows")]
+fn acquire_viewer_instance_guard() -> Result<Option<ViewerInstanceGuard>> {
+    const ERROR_ALREADY_EXISTS_CODE: u32 = 183;
+
+    let name: Vec<u16> = VIEWER_MUTEX_NAME
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let handle = unsafe { CreateMutexW(std::ptr::null(), 0, name.as_ptr()) };
+    if handle.is_null() {
+        bail!("CreateMutexW failed");
+    }
+
+    let last_error = unsafe { GetLastError() };
+    if last_error == ERROR_ALREADY_EXISTS_CODE {
+        unsafe {
+            CloseHandle(handle);
+        }
+        return Ok(None);
+    }
+
+    Ok(Some(ViewerInstanceGuard { handle }))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn acquire_viewer_instance_guard() -> Result<Option<ViewerInstanceGuard>> {
+    Ok(Some(ViewerInstanceGuard))
+}
+
+// Per user, since pipe names are not scoped to the session like the mutex.
+#[cfg(target_os = "windows")]
+fn viewer_pipe_path() -> String {
+    format!(r"\\.\pipe\LimeTraceViewer-{}", env::var("USERNAME").unwrap_or_default())
+}
+
+// The first viewer listens for later launches on a named pipe; each message is
+// one JSON object, read once the sender closes its end.
+#[cfg(target_os = "windows")]
+fn spawn_viewer_handoff_listener() -> mpsc::Receiver<Option<InitialView>> {
+    use std::io::Read;
+    use std::os::windows::io::FromRawHandle;
+
+    let (handoff_tx, handoff_rx) = mpsc::channel();
+    let name: Vec<u16> = viewer_pipe_path().encode_utf16().chain(std::iter::once(0)).collect();
+    std::thread::spawn(move || loop {
+        let handle = unsafe {
+            CreateNamedPipeW(
+                name.as_ptr(),
+                PIPE_ACCESS_INBOUND,
+                PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                PIPE_UNLIMITED_INSTANCES,
+                0,
+                VIEWER_HANDOFF_MAX_BYTES as u32,
+                0,
+                std::ptr::null(),
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            eprintln!("viewer hand-off pipe error: {}", std::io::Error::last_os_error());
+            return;
+        }
+        let connected = unsafe { ConnectNamedPipe(handle, std::ptr::null_mut()) } != 0
+            || unsafe { GetLastError() } == ERROR_PIPE_CONNECTED;
+        let pipe = unsafe { File::from_raw_handle(handle as _) };
+        if !connected {
+            continue;
+        }
+        let mut message = String::new();
+        if pipe.take(VIEWER_HANDOFF_MAX_BYTES).read_to_string(&mut message).is_err() {
+            continue;
+        }
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(&message) else {
+            continue;
+        };
+        let view = message
+            .get("view")
+            .and_then(serde_json::Value::as_str)
+            .and_then(InitialView::parse_cli_value);
+        if handoff_tx.send(view).is_err() {
+            return;
+        }
+    });
+    handoff_rx
+}
+
+#[cfg(not(target_os = "windows"))]
+fn spawn_viewer_handoff_listener() -> mpsc::Receiver<Option<InitialView>> {
+    mpsc::channel().1
+}
+
+#[cfg(target_os = "windows")]
+fn hand_off_to_running_viewer(message: &serde_json::Value) -> Result<()> {
+    // The running viewer may still be starting, or between two connections.
+    let deadline = Instant::now() + VIEWER_HANDOFF_WAIT;
+    let mut pipe = loop {
+        match fs::OpenOptions::new().write(true).open(viewer_pipe_path()) {
+            Ok(pipe) => break pipe,
+            Err(err) if Instant::now() >= deadline => {
+                return Err(err).context("the running viewer is not listening");
+            }
+            Err(_) => std::thread::sleep(Duration::from_millis(100)),
+        }
+    };
+    // Windows only lets the process the user just started take the foreground.
+    unsafe {
+        AllowSetForegroundWindow(ASFW_ANY);
+    }
+    pipe.write_all(message.to_string().as_bytes())
+        .context("failed to message the running viewer")
+}
+
+#[cfg(not(target_os = "windows"))]
+fn hand_off_to_running_viewer(_message: &serde_json::Value) -> Result<()> {
+    bail!("handing off to a running viewer is only supported on Windows")
+}
+
+// Keeps the worker's connection open between reloads so the cached range statement
+// is reused; a relocated database gets a fresh connection.
+fn reload_worker_connection<'a>(
+    connection: &'a mut Option<(PathBuf, Connection)>,
+    db_path: &Path,
+) -> Result<&'a Connection> {
+    if connection
+        .as_ref()
+        .is_none_or(|(open_path, _)| open_path != db_path)
+    {
+        let conn = connection::open_read_only(db_path)?;
+        *connection = Some((db_path.to_path_buf(), conn));
+    }
+    let (_, conn) = connection.as_ref().context("reload connection missing")?;
+    Ok(conn)
+}
+
+/// Jira-style keys (`PROJ-123`) in a title, in order of appearance.
+fn extract_issue_keys(title: &str) -> Vec<String> {
+    let bytes = title.as_bytes();
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let at_boundary = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+        if !at_boundary || !bytes[i].is_ascii_uppercase() {
+            i += 1;
+            continue;
+        }
+        let mut j = i + 1;
+        while j < bytes.len() && (bytes[j].is_ascii_uppercase() || bytes[j].is_ascii_digit()) {
+            j += 1;
+        }
+        if j < bytes.len() && bytes[j] == b'-' {
+            let digits_start = j + 1;
+            let mut k = digits_start;
+            while k < bytes.len() && bytes[k].is_ascii_digit() {
+                k += 1;
+            }
+            let ends_at_boundary = k == bytes.len() || !bytes[k].is_ascii_alphanumeric();
+            if k > digits_start && j - i >= 2 && ends_at_boundary {
+                keys.push(title[i..k].to_owned());
+                i = k;
+                continue;
+            }
+        }
+        i = j;
+    }
+    keys
+}
+
+// One draft per issue key and local day; a title with several keys books to the first.
+fn build_jira_worklog_drafts(segments: &[Segment], range_start: i64, range_end: i64) -> Vec<JiraWorklogDraft> {
+    let mut drafts: HashMap<(String, NaiveDate), JiraWorklogDraft> = HashMap::new();
+    for seg in segments {
+        if seg.is_idle {
+            continue;
+        }
+        let Some(issue_key) = seg.title.as_deref().and_then(|title| extract_issue_keys(title).into_iter().next())
+        else {
+            continue;
+        };
+        let start = seg.start_ts.max(range_start);
+        let end = seg.end_ts.min(range_end);
+        if end <= start {
+            continue;
+        }
+        let Some(day) = Local.timestamp_opt(start, 0).single().map(|dt| dt.date_naive()) else {
+            continue;
+        };
+        let draft = drafts
+            .entry((issue_key.clone(), day))
+            .or_insert_with(|| JiraWorklogDraft {
+                issue_key,
+                started_ts: start,
+                duration_secs: 0,
+                comment: seg.title.clone().unwrap_or_default(),
+                selected: true,
+            });
+        draft.started_ts = draft.started_ts.min(start);
+        draft.duration_secs += end - start;
+    }
+    let mut drafts: Vec<JiraWorklogDraft> = drafts
+        .into_values()
+        .filter(|draft| draft.duration_secs >= JIRA_MIN_WORKLOG_SECS)
+        .collect();
+    drafts.sort_by(|a, b| a.started_ts.cmp(&b.started_ts).then_with(|| a.issue_key.cmp(&b.issue_key)));
+    drafts
+}
+
+// Credentials go to curl through a stdin config file so they stay out of the process list.
+fn submit_jira_worklog(base_url: &str, email: &str, token: &str, draft: &JiraWorklogDraft) -> Result<()> {
+    let started = Local
+        .timestamp_opt(draft.started_ts, 0)
+        .single()
+        .context("invalid worklog start time")?
+        .format("%Y-%m-%dT%H:%M:%S%.3f%z")
+        .to_string();
+    // Jira books whole minutes.
+    let body = json!({
+        "started": started,
+        "timeSpentSeconds": draft.duration_secs / 60 * 60,
+        "comment": draft.comment,
+    })
+    .to_string();
+    let url = format!("{base_url}/rest/api/2/issue/{}/worklog", draft.issue_key);
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail-with-body", "--max-time", "20", "--config", "-"])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--data-binary", &body, &url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run curl")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        let credentials = format!("{email}:{token}").replace('\\', "\\\\").replace('"', "\\\"");
+        writeln!(stdin, "user = \"{credentials}\"").context("failed to pass credentials to curl")?;
+    }
+    let output = child.wait_with_output().context("failed to wait for curl")?;
+    if !output.status.success() {
+        let detail = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("{} {}", stderr.trim(), detail.trim());
+    }
+    Ok(())
+}
+
+fn reflog_path_for_repo(repo: &Path) -> Option<PathBuf> {
+    let dot_git = repo.join(".git");
+    let git_dir = if dot_git.is_dir() {
+        dot_git
+    } else if dot_git.is_file() {
+        // Worktrees and submodules point at their real git dir from a `.git` file.
+        let contents = fs::read_to_string(&dot_git).ok()?;
+        let target = PathBuf::from(contents.trim().strip_prefix("gitdir:")?.trim());
+        if target.is_absolute() {
+            target
+        } else {
+            repo.join(target)
+        }
+    } else {
+        repo.to_path_buf()
+    };
+    let reflog = git_dir.join("logs").join("HEAD");
+    reflog.is_file().then_some(reflog)
+}
+
+fn git_repo_name(repo: &Path) -> String {
+    repo.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| repo.display().to_string())
+}
+
+// HEAD reflog lines look like `<old> <new> <name> <<email>> <unix-ts> <tz>\t<message>`;
+// only entries written by `git commit` become markers.
+fn read_reflog_commits(repo: &Path, range_start: i64, range_end: i64) -> Result<Vec<GitCommitMarker>> {
+    let reflog = reflog_path_for_repo(repo)
+        .with_context(|| format!("no HEAD reflog under {}", repo.display()))?;
+    let contents = fs::read_to_string(&reflog)
+        .with_context(|| format!("failed to read reflog: {}", reflog.display()))?;
+    let repo_name = git_repo_name(repo);
+    let mut commits = Vec::new();
+    for line in contents.lines() {
+        let Some((header, message)) = line.split_once('\t') else {
+            continue;
+        };
+        let Some(summary) = message.strip_prefix("commit").and_then(|rest| rest.split_once(": ")) else {
+            continue;
+        };
+        let Some(ts) = header.rsplit(' ').nth(1).and_then(|ts| ts.parse::<i64>().ok()) else {
+            continue;
+        };
+        if ts < range_start || ts >= range_end {
+            continue;
+        }
+        commits.push(GitCommitMarker {
+            repo: repo_name.clone(),
+            ts,
+            summary: summary.1.to_owned(),
         });
     }
-    Ok(result)
+    Ok(commits)
+}
+
+/// Index into `names_lower` (lowercased repository folder names) of the repository
+/// an editor segment's title mentions.
+fn editor_segment_repo(names_lower: &[String], seg: &Segment) -> Option<usize> {
+    if seg.is_idle || !EDITOR_APP_KEYS.contains(&normalize_app_key(&seg.app_name).as_str()) {
+        return None;
+    }
+    let title_lower = seg.title.as_deref()?.to_lowercase();
+    names_lower.iter().position(|name| title_mentions_name(&title_lower, name))
+}
+
+fn title_mentions_name(title_lower: &str, name_lower: &str) -> bool {
+    let is_name_char = |ch: char| ch.is_alphanumeric() || ch == '-' || ch == '_' || ch == '.';
+    title_lower.match_indices(name_lower).any(|(index, _)| {
+        let before = title_lower[..index].chars().next_back();
+        let after = title_lower[index + name_lower.len()..].chars().next();
+        !before.is_some_and(is_name_char) && !after.is_some_and(is_name_char)
+    })
 }
 
-fn local_midnight_ts(date: NaiveDate) -> Option<i64> {
-    match Local.with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0) {
-        LocalResult::Single(dt) => Some(dt.timestamp()),
-        LocalResult::Ambiguous(a, b) => Some(a.timestamp().min(b.timestamp())),
-        LocalResult::None => None,
+fn build_git_repo_report(
+    repositories: &[PathBuf],
+    segments: &[Segment],
+    commits: &[GitCommitMarker],
+    range_start: i64,
+    range_end: i64,
+) -> Vec<GitRepoReportRow> {
+    let mut rows: Vec<GitRepoReportRow> = repositories
+        .iter()
+        .map(|repo| {
+            let repo = git_repo_name(repo);
+            let commit_count = commits.iter().filter(|commit| commit.repo == repo).count();
+            GitRepoReportRow {
+                repo,
+                coding_secs: 0,
+                commit_count,
+            }
+        })
+        .collect();
+    let names: Vec<String> = rows.iter().map(|row| row.repo.to_lowercase()).collect();
+    for seg in segments {
+        let duration = seg.end_ts.min(range_end) - seg.start_ts.max(range_start);
+        if duration <= 0 {
+            continue;
+        }
+        if let Some(index) = editor_segment_repo(&names, seg) {
+            rows[index].coding_secs += duration;
+        }
     }
+    rows.sort_by_key(|row| std::cmp::Reverse(row.coding_secs));
+    rows
 }
 
-fn format_duration(seconds: i64) -> String {
+fn format_duration(seconds: i64, format: DurationFormat) -> String {
     let secs = seconds.max(0);
     let h = secs / 3600;
     let m = (secs % 3600) / 60;
     let s = secs % 60;
-    format!("{h:02}:{m:02}:{s:02}")
+    match format {
+        DurationFormat::HoursMinutesSeconds => format!("{h:02}:{m:02}:{s:02}"),
+        DurationFormat::DecimalHours => format!("{:.2} h", secs as f64 / 3600.0),
+        DurationFormat::HoursMinutes if h > 0 => format!("{h}h {m}m"),
+        DurationFormat::HoursMinutes if m > 0 => format!("{m}m"),
+        DurationFormat::HoursMinutes => format!("{s}s"),
+        DurationFormat::Clock => format!("{h:02}:{m:02}"),
+    }
 }
 
-fn format_hms(unix_ts: i64) -> String {
+fn format_hms(unix_ts: i64, clock_format: ClockFormat) -> String {
     if let Some(dt) = Local.timestamp_opt(unix_ts, 0).single() {
-        return dt.format("%H:%M:%S").to_string();
+        return dt.format(clock_format.time_pattern()).to_string();
     }
     "--:--:--".to_owned()
 }
 
-fn format_local_datetime(unix_ts: i64) -> String {
+fn format_local_datetime(unix_ts: i64, clock_format: ClockFormat) -> String {
     if let Some(dt) = Local.timestamp_opt(unix_ts, 0).single() {
-        return dt.format("%Y-%m-%d %H:%M:%S").to_string();
+        return dt
+            .format(&format!("%Y-%m-%d {}", clock_format.time_pattern()))
+            .to_string();
     }
     "--".to_owned()
 }
 
+fn expand_export_filename_template(template: &str, values: &[(&str, String)]) -> Result<String> {
+    let mut expanded = template.trim().to_owned();
+    for (name, value) in values {
+        expanded = expanded.replace(&format!("{{{name}}}"), value);
+    }
+    if let Some(start) = expanded.find('{') {
+        let rest = &expanded[start..];
+        let placeholder = rest.find('}').map(|end| &rest[..=end]).unwrap_or(rest);
+        bail!("unknown filename placeholder: {placeholder}");
+    }
+    let sanitized: String = expanded
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    let sanitized = sanitized.trim_end_matches(['.', ' ']).to_owned();
+    if sanitized.is_empty() {
+        bail!("export filename template produced an empty name");
+    }
+    Ok(sanitized)
+}
+
 fn csv_escape(value: &str) -> String {
     if value.contains(',') || value.contains('"') || value.contains('\n') || value.contains('\r') {
         format!("\"{}\"", value.replace('"', "\"\""))
@@ -3391,56 +11109,19 @@ fn csv_escape(value: &str) -> String {
     }
 }
 
-fn ensure_tracking_schema(conn: &Connection) -> Result<()> {
-    conn.execute_batch(
-        "\
-        PRAGMA foreign_keys = ON;
-
-        CREATE TABLE IF NOT EXISTS apps (
-          id INTEGER PRIMARY KEY,
-          exe_name TEXT NOT NULL,
-          process_path TEXT NOT NULL,
-          UNIQUE(exe_name, process_path)
-        );
-
-        CREATE TABLE IF NOT EXISTS titles (
-          id INTEGER PRIMARY KEY,
-          title TEXT NOT NULL UNIQUE
-        );
-
-        CREATE TABLE IF NOT EXISTS segments (
-          id INTEGER PRIMARY KEY,
-          start_ts INTEGER NOT NULL,
-          end_ts INTEGER NOT NULL CHECK (end_ts >= start_ts),
-          app_id INTEGER,
-          title_id INTEGER,
-          is_idle INTEGER NOT NULL DEFAULT 0,
-          pid INTEGER,
-          pid_create_time INTEGER,
-          FOREIGN KEY(app_id) REFERENCES apps(id),
-          FOREIGN KEY(title_id) REFERENCES titles(id)
-        );
-
-        CREATE INDEX IF NOT EXISTS idx_segments_start ON segments(start_ts);
-        CREATE INDEX IF NOT EXISTS idx_segments_app_start ON segments(app_id, start_ts);
-        CREATE INDEX IF NOT EXISTS idx_segments_idle_start ON segments(is_idle, start_ts);
-
-        CREATE TABLE IF NOT EXISTS app_visual_cache (
-          app_key TEXT PRIMARY KEY,
-          process_path TEXT,
-          color_rgba INTEGER NOT NULL,
-          icon_width INTEGER,
-          icon_height INTEGER,
-          icon_rgba BLOB,
-          display_name TEXT,
-          updated_ts INTEGER NOT NULL
-        );
-
-        CREATE INDEX IF NOT EXISTS idx_app_visual_cache_process_path
-          ON app_visual_cache(process_path);",
-    )
-    .context("failed to ensure tracking schema")?;
-    Ok(())
+// Names the machine's batch folder; the random suffix keeps two PCs with the same
+// hostname apart.
+fn new_sync_machine_id() -> String {
+    let host = env::var("COMPUTERNAME")
+        .or_else(|_| env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "machine".to_owned());
+    let host: String = host
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '-' || *ch == '_')
+        .take(32)
+        .collect();
+    let nanos = Local::now().timestamp_subsec_nanos();
+    format!("{}-{:08x}", if host.is_empty() { "machine" } else { &host }, nanos ^ std::process::id())
 }
 
 fn encode_cached_icon_image(image: &egui::ColorImage) -> ([usize; 2], Vec<u8>) {
@@ -3477,350 +11158,732 @@ fn decode_color32_rgba(value: i64) -> Color32 {
     Color32::from_rgba_unmultiplied(r, g, b, a)
 }
 
-fn load_cached_app_visuals_from_db(conn: &Connection) -> Result<HashMap<String, CachedAppVisual>> {
-    let mut stmt = conn.prepare(
-        "\
-        SELECT
-          app_key,
-          process_path,
-          color_rgba,
-          icon_width,
-          icon_height,
-          icon_rgba,
-          display_name
-        FROM app_visual_cache",
-    )?;
-
-    let mut rows = stmt.query([])?;
-    let mut result = HashMap::new();
-    while let Some(row) = rows.next()? {
-        let app_key: String = row.get(0)?;
-        let process_path: Option<String> = row.get(1)?;
-        let color_rgba: i64 = row.get(2)?;
-        let icon_width: Option<i64> = row.get(3)?;
-        let icon_height: Option<i64> = row.get(4)?;
-        let icon_rgba: Option<Vec<u8>> = row.get(5)?;
-        let display_name: Option<String> = row.get(6)?;
-
-        let icon_size = match (icon_width, icon_height, icon_rgba.as_ref()) {
-            (Some(w), Some(h), Some(buf))
-                if w > 0
-                    && h > 0
-                    && (w as usize)
-                        .checked_mul(h as usize)
-                        .and_then(|pixels| pixels.checked_mul(4))
-                        .is_some_and(|expected| expected == buf.len()) =>
-            {
-                Some([w as usize, h as usize])
-            }
-            _ => None,
-        };
-
-        result.insert(
-            app_key,
-            CachedAppVisual {
-                process_path,
-                color: decode_color32_rgba(color_rgba),
-                icon_size,
-                icon_rgba,
-                display_name,
-            },
-        );
+fn load_app_categories_from_db(conn: &Connection) -> Result<HashMap<String, AppCategory>> {
+    let mut stmt = conn
+        .prepare("SELECT app_key, category FROM app_categories")
+        .context("failed to prepare app categories query")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .context("failed to query app categories")?;
+    let mut categories = HashMap::new();
+    for row in rows {
+        let (app_key, code) = row.context("failed to read app category row")?;
+        if let Some(category) = AppCategory::from_code(&code) {
+            categories.insert(app_key, category);
+        }
     }
+    Ok(categories)
+}
 
-    Ok(result)
+fn load_app_aliases_from_db(conn: &Connection) -> Result<HashMap<String, String>> {
+    let mut stmt = conn
+        .prepare("SELECT app_key, alias FROM app_aliases")
+        .context("failed to prepare app aliases query")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .context("failed to query app aliases")?;
+    rows.collect::<rusqlite::Result<HashMap<_, _>>>()
+        .context("failed to read app aliases")
 }
 
-fn upsert_cached_app_visual(conn: &Connection, app_key: &str, visual: &CachedAppVisual) -> Result<()> {
-    let (icon_width, icon_height, icon_rgba) = match (visual.icon_size, visual.icon_rgba.as_ref()) {
-        (Some([w, h]), Some(buf)) => (Some(w as i64), Some(h as i64), Some(buf.as_slice())),
-        _ => (None, None, None),
-    };
+fn load_excluded_apps_from_db(conn: &Connection) -> Result<HashSet<String>> {
+    let mut stmt = conn
+        .prepare("SELECT app_key FROM excluded_apps")
+        .context("failed to prepare excluded apps query")?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .context("failed to query excluded apps")?;
+    rows.collect::<rusqlite::Result<HashSet<_>>>()
+        .context("failed to read excluded apps")
+}
 
-    conn.execute(
-        "\
-        INSERT INTO app_visual_cache (
-          app_key,
-          process_path,
-          color_rgba,
-          icon_width,
-          icon_height,
-          icon_rgba,
-          display_name,
-          updated_ts
+fn load_events_from_db(conn: &Connection) -> Result<Vec<EventEntry>> {
+    let mut stmt = conn
+        .prepare(
+            "\
+            SELECT id, ts, kind, app_key, message, read
+            FROM notifications
+            ORDER BY ts DESC, id DESC
+            LIMIT ?1",
         )
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-        ON CONFLICT(app_key) DO UPDATE SET
-          process_path = excluded.process_path,
-          color_rgba = excluded.color_rgba,
-          icon_width = excluded.icon_width,
-          icon_height = excluded.icon_height,
-          icon_rgba = excluded.icon_rgba,
-          display_name = excluded.display_name,
-          updated_ts = excluded.updated_ts",
-        params![
-            app_key,
-            visual.process_path.as_deref(),
-            encode_color32_rgba(visual.color),
-            icon_width,
-            icon_height,
-            icon_rgba,
-            visual.display_name.as_deref(),
-            unix_seconds_now(),
-        ],
-    )
-    .context("failed to upsert app visual cache")?;
-    Ok(())
+        .context("failed to prepare notifications query")?;
+    let rows = stmt
+        .query_map(params![EVENT_LIST_LIMIT as i64], |row| {
+            Ok(EventEntry {
+                id: row.get(0)?,
+                ts: row.get(1)?,
+                kind: row.get(2)?,
+                app_key: row.get(3)?,
+                message: row.get(4)?,
+                read: row.get::<_, i64>(5)? != 0,
+            })
+        })
+        .context("failed to query notifications")?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read notifications")
+}
+
+fn load_title_tag_rules_from_db(conn: &Connection) -> Result<Vec<TitleTagRule>> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, pattern FROM title_tag_rules ORDER BY id")
+        .context("failed to prepare tag rules query")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(TitleTagRule {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                pattern: row.get(2)?,
+            })
+        })
+        .context("failed to query tag rules")?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read tag rules")
 }
 
-fn find_csv_header_index(headers: &StringRecord, aliases: &[&str]) -> Option<usize> {
-    let mut normalized_aliases = HashSet::with_capacity(aliases.len());
-    for alias in aliases {
-        normalized_aliases.insert(normalize_csv_header_key(alias));
+// Titles are append-only, so each rule only has to look at titles added since its last scan.
+fn scan_title_tags(conn: &mut Connection, rules: &[TitleTagRule]) -> Result<()> {
+    let tx = conn.transaction().context("failed to start title tag scan")?;
+    for rule in rules {
+        let Ok(regex) = TitleRegex::new(&rule.pattern) else {
+            continue;
+        };
+        let scanned_through: i64 = tx
+            .query_row(
+                "SELECT scanned_title_id FROM title_tag_rules WHERE id = ?1",
+                params![rule.id],
+                |row| row.get(0),
+            )
+            .context("failed to read tag rule scan position")?;
+        let mut last_title_id = scanned_through;
+        {
+            let mut select = tx
+                .prepare("SELECT id, title FROM titles WHERE id > ?1 ORDER BY id")
+                .context("failed to prepare title scan")?;
+            let mut insert = tx
+                .prepare("INSERT OR IGNORE INTO title_tags (title_id, rule_id, value) VALUES (?1, ?2, ?3)")
+                .context("failed to prepare title tag insert")?;
+            let mut rows = select.query(params![scanned_through]).context("failed to scan titles")?;
+            while let Some(row) = rows.next().context("failed to read title row")? {
+                let title_id: i64 = row.get(0)?;
+                let title: String = row.get(1)?;
+                for value in regex.tags(&title) {
+                    insert
+                        .execute(params![title_id, rule.id, value])
+                        .context("failed to insert title tag")?;
+                }
+                last_title_id = title_id;
+            }
+        }
+        tx.execute(
+            "UPDATE title_tag_rules SET scanned_title_id = ?1 WHERE id = ?2",
+            params![last_title_id, rule.id],
+        )
+        .context("failed to save tag rule scan position")?;
     }
+    tx.commit().context("failed to commit title tag scan")
+}
 
-    headers.iter().position(|header| {
-        let normalized = normalize_csv_header_key(header);
-        normalized_aliases.contains(&normalized)
-    })
+fn load_title_tag_totals(conn: &Connection, range_start: i64, range_end: i64) -> Result<Vec<(String, String, i64)>> {
+    let mut stmt = conn
+        .prepare(
+            "\
+            SELECT r.name, tt.value, SUM(MIN(s.end_ts, ?2) - MAX(s.start_ts, ?1)) AS secs
+            FROM segments s
+            JOIN title_tags tt ON tt.title_id = s.title_id
+            JOIN title_tag_rules r ON r.id = tt.rule_id
+            WHERE s.is_idle = 0
+              AND s.deleted_ts IS NULL
+              AND s.start_ts >= ?3
+              AND s.start_ts < ?2
+              AND s.end_ts > ?1
+            GROUP BY r.id, tt.value
+            ORDER BY secs DESC",
+        )
+        .context("failed to prepare title tag totals query")?;
+    let earliest_start = earliest_overlapping_start(conn, range_start);
+    let rows = stmt
+        .query_map(params![range_start, range_end, earliest_start], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .context("failed to query title tag totals")?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read title tag totals")
 }
 
-fn normalize_csv_header_key(value: &str) -> String {
-    let mut normalized = String::with_capacity(value.len());
-    for ch in value.trim().chars() {
-        if ch.is_ascii_alphanumeric() {
-            normalized.push(ch.to_ascii_lowercase());
-        } else if ch.is_alphanumeric() {
-            normalized.push(ch);
+// Matches active segments overlapping the range; an empty app key or pattern matches any.
+fn find_bulk_matches(conn: &Connection, criteria: &BulkCriteria) -> Result<Vec<(i64, i64, i64)>> {
+    let regex = Some(criteria.title_pattern.as_str())
+        .filter(|pattern| !pattern.is_empty())
+        .map(TitleRegex::new)
+        .transpose()?;
+    let (range_start, range_end) = criteria.range;
+    let mut stmt = conn
+        .prepare(
+            "\
+            SELECT s.id, s.start_ts, s.end_ts, a.exe_name, t.title
+            FROM segments s
+            LEFT JOIN apps a ON a.id = s.app_id
+            LEFT JOIN titles t ON t.id = s.title_id
+            WHERE s.is_idle = 0
+              AND s.deleted_ts IS NULL
+              AND s.start_ts >= ?3
+              AND s.start_ts < ?2
+              AND s.end_ts > ?1
+            ORDER BY s.start_ts",
+        )
+        .context("failed to prepare bulk match query")?;
+    let earliest_start = earliest_overlapping_start(conn, range_start);
+    let mut rows = stmt
+        .query(params![range_start, range_end, earliest_start])
+        .context("failed to query bulk matches")?;
+    let mut matches = Vec::new();
+    while let Some(row) = rows.next().context("failed to read bulk match row")? {
+        let app_name: Option<String> = row.get(3)?;
+        let title: Option<String> = row.get(4)?;
+        if !criteria.app_key.is_empty() && normalize_app_key(app_name.as_deref().unwrap_or_default()) != criteria.app_key {
+            continue;
+        }
+        if let Some(regex) = &regex {
+            if !title.as_deref().is_some_and(|title| regex.is_match(title)) {
+                continue;
+            }
         }
+        let start_ts: i64 = row.get(1)?;
+        let end_ts: i64 = row.get(2)?;
+        matches.push((row.get(0)?, start_ts.max(range_start), end_ts.min(range_end)));
     }
-    normalized
+    Ok(matches)
 }
 
-fn csv_record_text<'a>(record: &'a StringRecord, idx: Option<usize>) -> Option<&'a str> {
-    idx.and_then(|i| record.get(i))
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
+fn load_trash_entries(conn: &Connection) -> Result<Vec<TrashEntry>> {
+    let mut stmt = conn
+        .prepare(
+            "\
+            SELECT s.id, s.start_ts, s.end_ts, s.deleted_ts, s.is_idle, a.exe_name, t.title
+            FROM segments s
+            LEFT JOIN apps a ON a.id = s.app_id
+            LEFT JOIN titles t ON t.id = s.title_id
+            WHERE s.deleted_ts IS NOT NULL
+            ORDER BY s.deleted_ts DESC, s.start_ts
+            LIMIT ?1",
+        )
+        .context("failed to prepare trash query")?;
+    let rows = stmt
+        .query_map(params![TRASH_LIST_LIMIT as i64], |row| {
+            Ok(TrashEntry {
+                id: row.get(0)?,
+                start_ts: row.get(1)?,
+                end_ts: row.get(2)?,
+                deleted_ts: row.get(3)?,
+                is_idle: row.get::<_, i64>(4)? != 0,
+                app_name: row.get::<_, Option<String>>(5)?.unwrap_or_else(|| "UNKNOWN".to_owned()),
+                title: row.get(6)?,
+            })
+        })
+        .context("failed to query trash")?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read trash")
 }
 
-fn parse_import_csv_row(record: &StringRecord, columns: &ImportCsvColumns) -> Option<ParsedImportRow> {
-    let start_ts = csv_record_text(record, columns.start_ts)
-        .and_then(parse_unix_seconds)
-        .or_else(|| csv_record_text(record, columns.start_local).and_then(parse_local_datetime_to_unix));
+fn load_segment_tags_from_db(conn: &Connection) -> Result<Vec<SegmentTag>> {
+    let mut stmt = conn
+        .prepare("SELECT id, start_ts, end_ts, tag FROM segment_tags ORDER BY start_ts")
+        .context("failed to prepare segment tags query")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(SegmentTag {
+                id: row.get(0)?,
+                start_ts: row.get(1)?,
+                end_ts: row.get(2)?,
+                tag: row.get(3)?,
+            })
+        })
+        .context("failed to query segment tags")?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read segment tags")
+}
 
-    let mut end_ts = csv_record_text(record, columns.end_ts)
-        .and_then(parse_unix_seconds)
-        .or_else(|| csv_record_text(record, columns.end_local).and_then(parse_local_datetime_to_unix));
+fn merge_spans(mut spans: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    spans.retain(|(start, end)| end > start);
+    spans.sort_unstable();
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
 
-    let duration_secs = csv_record_text(record, columns.duration).and_then(parse_duration_to_seconds);
+// `spans` must be merged (sorted and disjoint).
+fn clip_segment_to_spans(seg: Segment, spans: Option<&[(i64, i64)]>) -> Vec<Segment> {
+    let Some(spans) = spans else {
+        return vec![seg];
+    };
+    let first = spans.partition_point(|(_, end)| end * 1000 <= seg.start_ms);
+    spans[first..]
+        .iter()
+        .take_while(|(start, _)| start * 1000 < seg.end_ms)
+        .map(|(start, end)| {
+            let (start_ms, end_ms) = (seg.start_ms.max(start * 1000), seg.end_ms.min(end * 1000));
+            Segment {
+                start_ts: start_ms.div_euclid(1000),
+                end_ts: end_ms.div_euclid(1000),
+                start_ms,
+                end_ms,
+                ..seg.clone()
+            }
+        })
+        .collect()
+}
 
-    if end_ts.is_none() {
-        if let (Some(start), Some(duration)) = (start_ts, duration_secs) {
-            end_ts = Some(start.saturating_add(duration));
+fn segment_tag_totals(segments: &[Segment], tags: &[SegmentTag], range_start: i64, range_end: i64) -> Vec<(String, i64)> {
+    let mut spans_by_tag: BTreeMap<&str, Vec<(i64, i64)>> = BTreeMap::new();
+    for segment_tag in tags {
+        let start = segment_tag.start_ts.max(range_start);
+        let end = segment_tag.end_ts.min(range_end);
+        if end > start {
+            spans_by_tag.entry(segment_tag.tag.as_str()).or_default().push((start, end));
         }
     }
+    let mut totals: Vec<(String, i64)> = spans_by_tag
+        .into_iter()
+        .map(|(tag, spans)| {
+            let spans = merge_spans(spans);
+            let secs = segments
+                .iter()
+                .filter(|seg| !should_hide_summary_app(&seg.app_name, seg.is_idle, seg.process_path.as_deref()) && !seg.is_idle)
+                .flat_map(|seg| clip_segment_to_spans(seg.clone(), Some(&spans)))
+                .map(|seg| seg.end_ts - seg.start_ts)
+                .sum();
+            (tag.to_owned(), secs)
+        })
+        .collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    totals
+}
 
-    let start_ts = start_ts?;
-    let mut end_ts = end_ts?;
-    if end_ts <= start_ts {
-        if let Some(duration) = duration_secs {
-            end_ts = start_ts.saturating_add(duration);
+fn load_title_category_rules_from_db(conn: &Connection) -> Result<Vec<TitleCategoryRule>> {
+    let mut stmt = conn
+        .prepare("SELECT id, pattern, category FROM title_category_rules ORDER BY id")
+        .context("failed to prepare title rules query")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .context("failed to query title rules")?;
+    let mut rules = Vec::new();
+    for row in rows {
+        let (id, pattern, code) = row.context("failed to read title rule row")?;
+        if let Some(category) = AppCategory::from_code(&code) {
+            rules.push(TitleCategoryRule {
+                id,
+                pattern,
+                category,
+            });
         }
     }
-    if end_ts <= start_ts {
-        return None;
-    }
-
-    let process_text = csv_record_text(record, columns.process)
-        .or_else(|| csv_record_text(record, columns.app_name))?;
-    let app_name_text = csv_record_text(record, columns.app_name).unwrap_or(process_text);
-    let is_idle = csv_record_text(record, columns.is_idle)
-        .and_then(parse_idle_flag)
-        .unwrap_or_else(|| infer_idle_from_text(process_text) || infer_idle_from_text(app_name_text));
-
-    let app_name = if is_idle {
-        "IDLE".to_owned()
-    } else {
-        app_name_text.to_owned()
-    };
-
-    let process_path = csv_record_text(record, columns.process_path)
-        .map(ToOwned::to_owned)
-        .unwrap_or_else(|| synthetic_import_process_path(process_text));
+    Ok(rules)
+}
 
-    let title = csv_record_text(record, columns.title).map(ToOwned::to_owned);
+// Title rules win over the app's own category so a browser can count as work
+// on some pages; the first matching rule applies.
+fn category_for_segment(
+    app_categories: &HashMap<String, AppCategory>,
+    title_rules: &[TitleCategoryRule],
+    app_name: &str,
+    title: Option<&str>,
+) -> Option<AppCategory> {
+    if let Some(title) = title {
+        let title = title.to_lowercase();
+        if let Some(rule) = title_rules
+            .iter()
+            .find(|rule| title.contains(&rule.pattern.to_lowercase()))
+        {
+            return Some(rule.category);
+        }
+    }
+    app_categories.get(&normalize_app_key(app_name)).copied()
+}
 
-    Some(ParsedImportRow {
-        start_ts,
-        end_ts,
-        is_idle,
-        app_name,
-        process_path,
-        title,
-    })
+fn compute_category_totals(
+    segments: &[Segment],
+    range_start: i64,
+    range_end: i64,
+    app_categories: &HashMap<String, AppCategory>,
+    title_rules: &[TitleCategoryRule],
+) -> Vec<(Option<AppCategory>, i64)> {
+    let mut totals: HashMap<Option<AppCategory>, i64> = HashMap::new();
+    for seg in segments {
+        add_category_segment(&mut totals, app_categories, title_rules, seg, range_start, range_end);
+    }
+    sorted_category_totals(totals)
 }
 
-fn parse_unix_seconds(value: &str) -> Option<i64> {
-    value.trim().parse::<i64>().ok()
+fn add_category_duration(
+    totals: &mut HashMap<Option<AppCategory>, i64>,
+    categories: &CategorySnapshot,
+    seg: &Segment,
+    range_start: i64,
+    range_end: i64,
+) {
+    add_category_segment(
+        totals,
+        &categories.app_categories,
+        &categories.title_rules,
+        seg,
+        range_start,
+        range_end,
+    );
 }
 
-fn parse_local_datetime_to_unix(value: &str) -> Option<i64> {
-    let value = value.trim();
-    if value.is_empty() {
-        return None;
+fn add_category_segment(
+    totals: &mut HashMap<Option<AppCategory>, i64>,
+    app_categories: &HashMap<String, AppCategory>,
+    title_rules: &[TitleCategoryRule],
+    seg: &Segment,
+    range_start: i64,
+    range_end: i64,
+) {
+    if seg.is_idle {
+        return;
+    }
+    let start = seg.start_ts.max(range_start);
+    let end = seg.end_ts.min(range_end);
+    if end <= start {
+        return;
     }
+    let category =
+        category_for_segment(app_categories, title_rules, &seg.app_name, seg.title.as_deref());
+    *totals.entry(category).or_default() += end - start;
+}
 
-    const FORMATS: [&str; 8] = [
-        "%Y-%m-%d %H:%M:%S",
-        "%Y/%m/%d %H:%M:%S",
-        "%Y-%m-%d %H:%M",
-        "%Y/%m/%d %H:%M",
-        "%Y-%m-%dT%H:%M:%S",
-        "%Y-%m-%dT%H:%M",
-        "%Y-%m-%d %H:%M:%S%.f",
-        "%Y/%m/%d %H:%M:%S%.f",
-    ];
+// Compares today so far with the same stretch of each of the last four weeks' days;
+// days without any recorded activity stay out of the baseline.
+fn detect_usage_anomalies(
+    conn: &Connection,
+    today: NaiveDate,
+    now: i64,
+    app_categories: &HashMap<String, AppCategory>,
+    title_rules: &[TitleCategoryRule],
+    days_off: &HashMap<NaiveDate, DayOff>,
+) -> Result<Vec<UsageAnomaly>> {
+    let Some(day_start) = local_midnight_ts(today) else {
+        return Ok(Vec::new());
+    };
+    let elapsed = now - day_start;
+    let window_totals = |start: i64| -> Result<HashMap<Option<AppCategory>, i64>> {
+        let mut totals = HashMap::new();
+        for_each_segment_in_range(conn, start, start + elapsed, |seg| {
+            add_category_segment(&mut totals, app_categories, title_rules, &seg, start, start + elapsed);
+        })?;
+        Ok(totals)
+    };
 
-    for format in FORMATS {
-        if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
-            return match Local.from_local_datetime(&naive) {
-                LocalResult::Single(dt) => Some(dt.timestamp()),
-                LocalResult::Ambiguous(a, b) => Some(a.timestamp().min(b.timestamp())),
-                LocalResult::None => None,
-            };
+    let today_totals = window_totals(day_start)?;
+    let mut baseline: HashMap<AppCategory, (i64, usize)> = HashMap::new();
+    let mut baseline_days = 0;
+    for offset in 1..=ANOMALY_BASELINE_DAYS {
+        let Some(start) = today
+            .checked_sub_days(Days::new(offset))
+            .filter(|date| !days_off.contains_key(date))
+            .and_then(local_midnight_ts)
+        else {
+            continue;
+        };
+        let totals = window_totals(start)?;
+        if totals.is_empty() {
+            continue;
+        }
+        baseline_days += 1;
+        for (category, secs) in totals {
+            if let Some(category) = category {
+                let entry = baseline.entry(category).or_default();
+                entry.0 += secs;
+                entry.1 += 1;
+            }
         }
     }
+    if baseline_days < ANOMALY_MIN_BASELINE_DAYS {
+        return Ok(Vec::new());
+    }
 
-    None
+    let mut anomalies = Vec::new();
+    for category in AppCategory::ALL {
+        let (baseline_secs, days_present) = baseline.get(&category).copied().unwrap_or_default();
+        let average = baseline_secs as f64 / baseline_days as f64;
+        let today_secs = today_totals.get(&Some(category)).copied().unwrap_or(0);
+        if today_secs >= ANOMALY_MIN_SECS && average > 0.0 && today_secs as f64 >= average * ANOMALY_RATIO {
+            anomalies.push(UsageAnomaly::AboveNormal {
+                category,
+                ratio: today_secs as f64 / average,
+            });
+        } else if today_secs == 0
+            && !days_off.contains_key(&today)
+            && average >= ANOMALY_MIN_SECS as f64
+            && days_present * 4 >= baseline_days * 3
+        {
+            anomalies.push(UsageAnomaly::Missing { category });
+        }
+    }
+    Ok(anomalies)
 }
 
-fn parse_duration_to_seconds(value: &str) -> Option<i64> {
-    let value = value.trim();
-    if value.is_empty() {
-        return None;
+// Average active seconds per local clock hour over recent days that have any activity.
+fn load_hourly_activity_profile(
+    conn: &Connection,
+    today: NaiveDate,
+    days_off: &HashMap<NaiveDate, DayOff>,
+) -> Result<Option<[f64; 24]>> {
+    let mut totals = [0i64; 24];
+    let mut active_days = 0;
+    for offset in 1..=FORECAST_HISTORY_DAYS {
+        let Some(day_start) = today
+            .checked_sub_days(Days::new(offset))
+            .filter(|date| !days_off.contains_key(date))
+            .and_then(local_midnight_ts)
+        else {
+            continue;
+        };
+        let day_end = day_start + 24 * 3600;
+        let mut day_has_activity = false;
+        for_each_segment_in_range(conn, day_start, day_end, |seg| {
+            if seg.is_idle {
+                return;
+            }
+            let mut start = seg.start_ts.max(day_start);
+            let end = seg.end_ts.min(day_end);
+            while start < end {
+                let hour = ((start - day_start) / 3600) as usize;
+                let hour_end = (day_start + (hour as i64 + 1) * 3600).min(end);
+                totals[hour] += hour_end - start;
+                day_has_activity = true;
+                start = hour_end;
+            }
+        })?;
+        if day_has_activity {
+            active_days += 1;
+        }
     }
-
-    if let Ok(seconds) = value.parse::<i64>() {
-        return (seconds > 0).then_some(seconds);
+    if active_days < FORECAST_MIN_HISTORY_DAYS {
+        return Ok(None);
     }
+    Ok(Some(totals.map(|secs| secs as f64 / active_days as f64)))
+}
 
-    let parts: Vec<&str> = value.split(':').collect();
-    if !(2..=3).contains(&parts.len()) {
+// Scales today's time by how much of a typical day has passed; early in the day,
+// when that share is small, the typical remainder is added instead.
+fn project_day_total(active_secs: i64, profile: &[f64; 24], elapsed_secs: i64) -> Option<i64> {
+    let typical_total: f64 = profile.iter().sum();
+    if typical_total <= 0.0 {
         return None;
     }
+    let hour = (elapsed_secs / 3600).clamp(0, 23) as usize;
+    let hour_fraction = ((elapsed_secs - hour as i64 * 3600) as f64 / 3600.0).clamp(0.0, 1.0);
+    let typical_done = profile[..hour].iter().sum::<f64>() + profile[hour] * hour_fraction;
+    let done_share = typical_done / typical_total;
+    let projected = if done_share >= FORECAST_MIN_DAY_SHARE {
+        active_secs as f64 / done_share
+    } else {
+        active_secs as f64 + (typical_total - typical_done)
+    };
+    Some((projected.round() as i64).max(active_secs))
+}
 
-    let mut values = Vec::with_capacity(parts.len());
-    for part in parts {
-        let parsed = part.trim().parse::<i64>().ok()?;
-        if parsed < 0 {
-            return None;
-        }
-        values.push(parsed);
-    }
+fn sorted_category_totals(
+    totals: HashMap<Option<AppCategory>, i64>,
+) -> Vec<(Option<AppCategory>, i64)> {
+    let mut totals: Vec<_> = totals.into_iter().collect();
+    totals.sort_by_key(|(_, secs)| std::cmp::Reverse(*secs));
+    totals
+}
 
-    let seconds = if values.len() == 3 {
-        values[0]
-            .saturating_mul(3600)
-            .saturating_add(values[1].saturating_mul(60))
-            .saturating_add(values[2])
-    } else {
-        values[0].saturating_mul(60).saturating_add(values[1])
+fn load_active_focus_session(conn: &Connection, now_ts: i64) -> Result<Option<FocusSession>> {
+    let mut stmt = conn
+        .prepare(
+            "\
+            SELECT id, started_ts, ends_ts
+            FROM focus_sessions
+            WHERE stopped_ts IS NULL AND ends_ts > ?1
+            ORDER BY started_ts DESC
+            LIMIT 1",
+        )
+        .context("failed to prepare focus session query")?;
+    let mut rows = stmt
+        .query(params![now_ts])
+        .context("failed to query focus sessions")?;
+    let Some(row) = rows.next().context("failed to read focus session row")? else {
+        return Ok(None);
     };
+    Ok(Some(FocusSession {
+        id: row.get(0)?,
+        started_ts: row.get(1)?,
+        ends_ts: row.get(2)?,
+    }))
+}
 
-    (seconds > 0).then_some(seconds)
+fn load_focus_violation_counts(conn: &Connection, session_id: i64) -> Result<Vec<(String, i64)>> {
+    let mut stmt = conn
+        .prepare(
+            "\
+            SELECT app_key, COUNT(*) AS violations
+            FROM focus_violations
+            WHERE session_id = ?1
+            GROUP BY app_key
+            ORDER BY violations DESC, app_key",
+        )
+        .context("failed to prepare focus violations query")?;
+    let rows = stmt
+        .query_map(params![session_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })
+        .context("failed to query focus violations")?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .context("failed to read focus violations")
 }
 
-fn parse_idle_flag(value: &str) -> Option<bool> {
-    let normalized = value.trim().to_ascii_lowercase();
-    match normalized.as_str() {
-        "1" | "true" | "yes" | "y" => Some(true),
-        "0" | "false" | "no" | "n" => Some(false),
-        _ => None,
+fn load_cached_app_visuals_from_db(conn: &Connection) -> Result<HashMap<String, CachedAppVisual>> {
+    let mut stmt = conn.prepare(
+        "\
+        SELECT
+          app_key,
+          process_path,
+          color_rgba,
+          icon_width,
+          icon_height,
+          icon_rgba,
+          display_name
+        FROM app_visual_cache",
+    )?;
+
+    let mut rows = stmt.query([])?;
+    let mut result = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let app_key: String = row.get(0)?;
+        let process_path: Option<String> = row.get(1)?;
+        let color_rgba: i64 = row.get(2)?;
+        let icon_width: Option<i64> = row.get(3)?;
+        let icon_height: Option<i64> = row.get(4)?;
+        let icon_rgba: Option<Vec<u8>> = row.get(5)?;
+        let display_name: Option<String> = row.get(6)?;
+
+        let icon_size = match (icon_width, icon_height, icon_rgba.as_ref()) {
+            (Some(w), Some(h), Some(buf))
+                if w > 0
+                    && h > 0
+                    && (w as usize)
+                        .checked_mul(h as usize)
+                        .and_then(|pixels| pixels.checked_mul(4))
+                        .is_some_and(|expected| expected == buf.len()) =>
+            {
+                Some([w as usize, h as usize])
+            }
+            _ => None,
+        };
+
+        result.insert(
+            app_key,
+            CachedAppVisual {
+                process_path,
+                color: decode_color32_rgba(color_rgba),
+                icon_size,
+                icon_rgba,
+                display_name,
+            },
+        );
     }
-}
-
-fn infer_idle_from_text(value: &str) -> bool {
-    let normalized = value.trim().to_ascii_lowercase();
-    normalized == "idle" || normalized == "idling" || normalized == "afk" || value.trim() == "\u{7A7A}\u{95F2}"
-}
 
-fn synthetic_import_process_path(process: &str) -> String {
-    let cleaned = process
-        .trim()
-        .chars()
-        .map(|ch| if ch.is_control() { ' ' } else { ch })
-        .collect::<String>();
-    if cleaned.is_empty() {
-        "<import:UNKNOWN>".to_owned()
-    } else {
-        format!("<import:{}>", cleaned)
-    }
+    Ok(result)
 }
 
-fn upsert_app_in_tx(
-    tx: &rusqlite::Transaction<'_>,
-    cache: &mut HashMap<(String, String), i64>,
-    exe_name: &str,
-    process_path: &str,
-) -> Result<i64> {
-    let key = (exe_name.to_owned(), process_path.to_owned());
-    if let Some(id) = cache.get(&key) {
-        return Ok(*id);
-    }
+fn upsert_cached_app_visual(conn: &Connection, app_key: &str, visual: &CachedAppVisual) -> Result<()> {
+    let (icon_width, icon_height, icon_rgba) = match (visual.icon_size, visual.icon_rgba.as_ref()) {
+        (Some([w, h]), Some(buf)) => (Some(w as i64), Some(h as i64), Some(buf.as_slice())),
+        _ => (None, None, None),
+    };
 
-    tx.execute(
+    conn.execute(
         "\
-        INSERT INTO apps (exe_name, process_path)
-        VALUES (?1, ?2)
-        ON CONFLICT(exe_name, process_path) DO NOTHING",
-        params![exe_name, process_path],
-    )
-    .context("failed to upsert imported app")?;
-
-    let app_id = tx
-        .query_row(
-            "SELECT id FROM apps WHERE exe_name = ?1 AND process_path = ?2",
-            params![exe_name, process_path],
-            |row| row.get::<_, i64>(0),
+        INSERT INTO app_visual_cache (
+          app_key,
+          process_path,
+          color_rgba,
+          icon_width,
+          icon_height,
+          icon_rgba,
+          display_name,
+          updated_ts
         )
-        .context("failed to resolve imported app id")?;
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+        ON CONFLICT(app_key) DO UPDATE SET
+          process_path = excluded.process_path,
+          color_rgba = excluded.color_rgba,
+          icon_width = excluded.icon_width,
+          icon_height = excluded.icon_height,
+          icon_rgba = excluded.icon_rgba,
+          display_name = excluded.display_name,
+          updated_ts = excluded.updated_ts",
+        params![
+            app_key,
+            visual.process_path.as_deref(),
+            encode_color32_rgba(visual.color),
+            icon_width,
+            icon_height,
+            icon_rgba,
+            visual.display_name.as_deref(),
+            unix_seconds_now(),
+        ],
+    )
+    .context("failed to upsert app visual cache")?;
+    Ok(())
+}
 
-    cache.insert(key, app_id);
-    Ok(app_id)
+fn unix_seconds_now() -> i64 {
+    Local::now().timestamp()
 }
 
-fn upsert_title_in_tx(
-    tx: &rusqlite::Transaction<'_>,
-    cache: &mut HashMap<String, i64>,
-    title: &str,
-) -> Result<i64> {
-    if let Some(id) = cache.get(title) {
-        return Ok(*id);
+fn describe_export_paths(paths: &[PathBuf]) -> String {
+    match paths {
+        [path] => path.display().to_string(),
+        [first, ..] => format!(
+            "{} files in {}",
+            paths.len(),
+            first.parent().unwrap_or(first).display()
+        ),
+        [] => String::new(),
     }
+}
 
-    tx.execute(
-        "\
-        INSERT INTO titles (title)
-        VALUES (?1)
-        ON CONFLICT(title) DO NOTHING",
-        params![title],
-    )
-    .context("failed to upsert imported title")?;
-
-    let title_id = tx
-        .query_row(
-            "SELECT id FROM titles WHERE title = ?1",
-            params![title],
-            |row| row.get::<_, i64>(0),
-        )
-        .context("failed to resolve imported title id")?;
+// Empty or "Local" reads local-time columns in the system zone.
+fn parse_import_timezone(input: &str) -> Result<Option<chrono_tz::Tz>> {
+    let input = input.trim();
+    if input.is_empty() || input.eq_ignore_ascii_case("local") {
+        return Ok(None);
+    }
+    input
+        .parse::<chrono_tz::Tz>()
+        .map(Some)
+        .map_err(|_| anyhow!("unknown time zone: {input}"))
+}
 
-    cache.insert(title.to_owned(), title_id);
-    Ok(title_id)
+fn mix_toward_white(color: Color32, amount: f32) -> Color32 {
+    let mix = |channel: u8| (channel as f32 + (255.0 - channel as f32) * amount.clamp(0.0, 1.0)).round() as u8;
+    Color32::from_rgb(mix(color.r()), mix(color.g()), mix(color.b()))
 }
 
-fn unix_seconds_now() -> i64 {
-    Local::now().timestamp()
+// Dark text on light bars and light text on dark ones.
+fn timeline_label_color(fill: Color32) -> Color32 {
+    let luma = 0.299 * fill.r() as f32 + 0.587 * fill.g() as f32 + 0.114 * fill.b() as f32;
+    if luma > 150.0 {
+        Color32::from_rgb(24, 24, 24)
+    } else {
+        Color32::from_rgb(245, 245, 245)
+    }
 }
 
 fn resolve_timeline_app_label(
@@ -3895,6 +11958,7 @@ fn timeline_tip_text(language: UiLanguage, key: &'static str) -> &'static str {
             "duration" => "\u{65F6}\u{957F}",
             "range" => "\u{533A}\u{95F4}",
             "multi_title" => "\u{591A}\u{4E2A}\u{6807}\u{9898}",
+            "tags" => "\u{6807}\u{7B7E}",
             _ => key,
         },
         UiLanguage::EnUs => match key {
@@ -3903,8 +11967,28 @@ fn timeline_tip_text(language: UiLanguage, key: &'static str) -> &'static str {
             "duration" => "Duration",
             "range" => "Range",
             "multi_title" => "(multiple titles)",
+            "tags" => "Tags",
             _ => key,
         },
+        UiLanguage::ArSa => bidi::display_static(match key {
+            "app" => "\u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642}",
+            "title" => "\u{627}\u{644}\u{639}\u{646}\u{648}\u{627}\u{646}",
+            "duration" => "\u{627}\u{644}\u{645}\u{62F}\u{629}",
+            "range" => "\u{627}\u{644}\u{641}\u{62A}\u{631}\u{629}",
+            "multi_title" => "(\u{639}\u{646}\u{627}\u{648}\u{64A}\u{646} \u{645}\u{62A}\u{639}\u{62F}\u{62F}\u{629})",
+            "tags" => "\u{627}\u{644}\u{648}\u{633}\u{648}\u{645}",
+            _ => timeline_tip_text(UiLanguage::EnUs, key),
+        }),
+    }
+}
+
+// `label: value` with both parts already in display order; right-to-left languages
+// put the label on the right.
+fn tip_line(language: UiLanguage, label: &str, value: &str) -> String {
+    if language.is_rtl() {
+        format!("{value} :{label}")
+    } else {
+        format!("{label}: {value}")
     }
 }
 
@@ -3912,6 +11996,7 @@ fn items_caption(language: UiLanguage) -> &'static str {
     match language {
         UiLanguage::ZhCn => "\u{6570}\u{91CF}",
         UiLanguage::EnUs => "Show",
+        UiLanguage::ArSa => bidi::display_static("\u{639}\u{631}\u{636}"),
     }
 }
 
@@ -3919,30 +12004,67 @@ fn default_ui_language() -> UiLanguage {
     let locale = env::var("LANG").unwrap_or_default().to_ascii_lowercase();
     if locale.starts_with("zh") {
         UiLanguage::ZhCn
+    } else if locale.starts_with("ar") {
+        UiLanguage::ArSa
     } else {
         UiLanguage::EnUs
     }
 }
 
-fn load_ui_language(settings_path: &PathBuf) -> Option<UiLanguage> {
-    let content = fs::read_to_string(settings_path).ok()?;
-    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
-    let language_code = value.get("language")?.as_str()?;
-    UiLanguage::from_code(language_code)
+fn load_settings(settings_path: &PathBuf) -> serde_json::Map<String, serde_json::Value> {
+    let Ok(content) = fs::read_to_string(settings_path) else {
+        return serde_json::Map::new();
+    };
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    }
 }
 
-fn persist_ui_language(settings_path: &PathBuf, language: UiLanguage) -> Result<()> {
+fn persist_settings_values(
+    settings_path: &PathBuf,
+    values: &[(&str, serde_json::Value)],
+) -> Result<()> {
     if let Some(parent) = settings_path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("failed to create settings directory: {}", parent.display()))?;
     }
-    let payload = json!({
-        "language": language.code()
-    });
-    let text = serde_json::to_string_pretty(&payload).context("failed to serialize UI settings")?;
+    // Merge into the existing file so unrelated settings survive each write.
+    let mut payload = load_settings(settings_path);
+    for (key, value) in values {
+        payload.insert((*key).to_owned(), value.clone());
+    }
+    let text = serde_json::to_string_pretty(&serde_json::Value::Object(payload))
+        .context("failed to serialize UI settings")?;
     fs::write(settings_path, text)
         .with_context(|| format!("failed to write UI settings: {}", settings_path.display()))?;
-    Ok(())
+    Ok(())
+}
+
+fn settings_path_for_db(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("limetrace-settings.json")
+}
+
+fn load_window_size(settings: &serde_json::Map<String, serde_json::Value>) -> Option<[f32; 2]> {
+    let size = settings.get("window_size")?.as_array()?;
+    let [width, height] = size.as_slice() else {
+        return None;
+    };
+    let width = width.as_f64()? as f32;
+    let height = height.as_f64()? as f32;
+    Some([width.max(MIN_WINDOW_SIZE[0]), height.max(MIN_WINDOW_SIZE[1])])
+}
+
+fn load_ui_language(settings: &serde_json::Map<String, serde_json::Value>) -> Option<UiLanguage> {
+    let language_code = settings.get("language")?.as_str()?;
+    UiLanguage::from_code(language_code)
+}
+
+fn persist_ui_language(settings_path: &PathBuf, language: UiLanguage) -> Result<()> {
+    persist_settings_values(settings_path, &[("language", json!(language.code()))])
 }
 
 fn tr(language: UiLanguage, key: &'static str) -> &'static str {
@@ -3951,27 +12073,258 @@ fn tr(language: UiLanguage, key: &'static str) -> &'static str {
             "lang" => "\u{8BED}\u{8A00}",
             "range" => "\u{8303}\u{56F4}",
             "date" => "\u{65E5}\u{671F}",
+            "jump_to_date" => "\u{8DF3}\u{8F6C}\u{5230}\u{65E5}\u{671F}",
+            "jump_to_date_hint" => "\u{652F}\u{6301}\u{FF1A}2024-5-3\u{3001}5/3\u{3001}\u{4ECA}\u{5929}\u{3001}\u{6628}\u{5929}\u{3001}last monday",
+            "unrecognized_date" => "\u{65E0}\u{6CD5}\u{8BC6}\u{522B}\u{7684}\u{65E5}\u{671F}",
             "from" => "\u{5F00}\u{59CB}",
             "to" => "\u{7ED3}\u{675F}",
             "items" => "\u{6761}\u{76EE}",
+            "sort" => "\u{6392}\u{5E8F}",
+            "sessions" => "\u{4F7F}\u{7528}\u{6B21}\u{6570}",
+            "title_split" => "\u{6309}\u{6807}\u{9898}\u{62C6}\u{5206}",
+            "avg_session" => "\u{5E73}\u{5747}\u{65F6}\u{957F}",
+            "duration" => "\u{65F6}\u{957F}",
             "refresh" => "\u{5237}\u{65B0}",
+            "theme" => "\u{4E3B}\u{9898}",
+            "category" => "\u{5206}\u{7C7B}",
+            "remote_storage" => "\u{8FDC}\u{7A0B}\u{5B58}\u{50A8}",
+            "remote_storage_hint" => "\u{540C}\u{6B65}\u{6279}\u{6B21}\u{548C}\u{5FEB}\u{7167}\u{5728}\u{4E0A}\u{4F20}\u{524D}\u{4E8E}\u{672C}\u{673A}\u{52A0}\u{5BC6}\u{FF1B}\u{53E3}\u{4EE4}\u{548C}\u{5BC6}\u{94A5}\u{4E0D}\u{4F1A}\u{4FDD}\u{5B58}",
+            "remote_off" => "\u{5173}\u{95ED}",
+            "updates" => "\u{81EA}\u{52A8}\u{66F4}\u{65B0}",
+            "update_stable" => "\u{7A33}\u{5B9A}\u{7248}",
+            "update_prerelease" => "\u{9884}\u{89C8}\u{7248}",
+            "update_check_now" => "\u{7ACB}\u{5373}\u{68C0}\u{67E5}",
+            "update_up_to_date" => "\u{5DF2}\u{662F}\u{6700}\u{65B0}\u{7248}\u{672C}",
+            "update_checking" => "\u{6B63}\u{5728}\u{68C0}\u{67E5}\u{2026}",
+            "update_staged" => "LimeTrace {version} \u{5DF2}\u{4E0B}\u{8F7D}\u{FF1A}\u{540E}\u{53F0}\u{670D}\u{52A1}\u{5C06}\u{5728}\u{4E00}\u{5206}\u{949F}\u{5185}\u{5207}\u{6362}\u{5230}\u{65B0}\u{7248}\u{672C}\u{FF0C}\u{754C}\u{9762}\u{5728}\u{4E0B}\u{6B21}\u{542F}\u{52A8}\u{65F6}\u{66F4}\u{65B0}\u{3002}",
+            "remote_endpoint" => "\u{7AEF}\u{70B9} URL",
+            "remote_region" => "\u{533A}\u{57DF}",
+            "remote_user" => "\u{7528}\u{6237}\u{540D} / Access Key",
+            "remote_secret" => "\u{5BC6}\u{7801} / Secret Key",
+            "remote_passphrase" => "\u{52A0}\u{5BC6}\u{53E3}\u{4EE4}",
+            "remote_snapshots" => "\u{540C}\u{65F6}\u{4E0A}\u{4F20}\u{5B9A}\u{65F6}\u{5FEB}\u{7167}",
+            "remote_uploaded" => "\u{4E0A}\u{4F20}",
+            "remote_downloaded" => "\u{4E0B}\u{8F7D}",
+            "folder_sync" => "\u{673A}\u{5668}\u{95F4}\u{540C}\u{6B65}",
+            "folder_sync_hint" => "\u{6BCF}\u{53F0}\u{673A}\u{5668}\u{628A}\u{81EA}\u{5DF1}\u{7684}\u{8BB0}\u{5F55}\u{5199}\u{5165}\u{5171}\u{4EAB}\u{6587}\u{4EF6}\u{5939}\u{5E76}\u{5BFC}\u{5165}\u{5176}\u{4ED6}\u{673A}\u{5668}\u{7684}\u{8BB0}\u{5F55}\u{FF1B}\u{6240}\u{6709}\u{673A}\u{5668}\u{9700}\u{4F7F}\u{7528}\u{76F8}\u{540C}\u{5BC6}\u{94A5}",
+            "folder_sync_path" => "\u{540C}\u{6B65}\u{6587}\u{4EF6}\u{5939}",
+            "folder_sync_secret" => "\u{5171}\u{4EAB}\u{5BC6}\u{94A5}",
+            "folder_sync_now" => "\u{7ACB}\u{5373}\u{540C}\u{6B65}",
+            "folder_sync_exported" => "\u{5BFC}\u{51FA}",
+            "folder_sync_imported" => "\u{5BFC}\u{5165}",
+            "folder_sync_duplicates" => "\u{91CD}\u{590D}",
+            "folder_sync_rejected" => "\u{88AB}\u{62D2}\u{6279}\u{6B21}",
+            "team_export" => "\u{56E2}\u{961F}\u{770B}\u{677F}\u{5BFC}\u{51FA}",
+            "team_export_days" => "\u{5BFC}\u{51FA}\u{5929}\u{6570}",
+            "team_export_path" => "\u{8F93}\u{51FA}\u{6587}\u{4EF6}\u{FF08}.json \u{6216} .db\u{FF09}",
+            "team_export_now" => "\u{7ACB}\u{5373}\u{5BFC}\u{51FA}",
+            "team_export_hint" => "\u{53EA}\u{5305}\u{542B}\u{6BCF}\u{65E5}\u{5206}\u{7C7B}\u{6C47}\u{603B}\u{FF0C}\u{4E0D}\u{542B}\u{6807}\u{9898}\u{548C}\u{5E94}\u{7528}",
+            "trash" => "\u{56DE}\u{6536}\u{7AD9}",
+            "trash_restore" => "\u{6062}\u{590D}",
+            "trash_restore_all" => "\u{5168}\u{90E8}\u{6062}\u{590D}",
+            "trash_purge" => "\u{5F7B}\u{5E95}\u{5220}\u{9664}",
+            "trash_empty" => "\u{6E05}\u{7A7A}\u{56DE}\u{6536}\u{7AD9}",
+            "trash_none" => "\u{56DE}\u{6536}\u{7AD9}\u{4E3A}\u{7A7A}",
+            "trash_deleted_at" => "\u{5220}\u{9664}\u{65F6}\u{95F4}",
+            "bulk_edit" => "\u{6279}\u{91CF}\u{7F16}\u{8F91}",
+            "bulk_app_hint" => "\u{5E94}\u{7528}\u{FF08}\u{7559}\u{7A7A}\u{8868}\u{793A}\u{5168}\u{90E8}\u{FF09}",
+            "bulk_title_hint" => "\u{6807}\u{9898}\u{6B63}\u{5219}\u{FF08}\u{53EF}\u{9009}\u{FF09}",
+            "bulk_preview" => "\u{9884}\u{89C8}",
+            "map_columns" => "\u{8868}\u{5934}\u{65E0}\u{6CD5}\u{8BC6}\u{522B}\u{FF0C}\u{8BF7}\u{4E3A}\u{6BCF}\u{4E2A}\u{5B57}\u{6BB5}\u{9009}\u{62E9}\u{5BF9}\u{5E94}\u{7684}\u{5217}",
+            "csv_preview" => "\u{524D}\u{51E0}\u{884C}\u{9884}\u{89C8}",
+            "time_zone" => "\u{65F6}\u{533A}",
+            "time_zone_hint" => "\u{672C}\u{5730}\u{3001}UTC \u{6216}\u{4F8B}\u{5982} Asia/Shanghai",
+            "time_zone_offsets" => "\u{5E26}\u{504F}\u{79FB}\u{91CF}\u{7684}\u{65F6}\u{95F4}\u{FF08}\u{5982} 2024-03-04T09:00:00+08:00\u{FF09}\u{4E0D}\u{53D7}\u{6B64}\u{8BBE}\u{7F6E}\u{5F71}\u{54CD}",
+            "bulk_apply" => "\u{6267}\u{884C}",
+            "bulk_matches" => "{count} \u{4E2A}\u{7247}\u{6BB5}\u{FF0C}\u{5171} {time}",
+            "bulk_tag" => "\u{6DFB}\u{52A0}\u{6807}\u{7B7E}",
+            "bulk_category_hint" => "\u{5206}\u{7C7B}\u{6309}\u{5E94}\u{7528}\u{751F}\u{6548}\u{FF0C}\u{586B}\u{5199}\u{6807}\u{9898}\u{6B63}\u{5219}\u{65F6}\u{4E0D}\u{53EF}\u{7528}\u{FF0C}\u{4E5F}\u{4E0D}\u{53D7}\u{65F6}\u{95F4}\u{8303}\u{56F4}\u{9650}\u{5236}",
+            "bulk_done" => "\u{5DF2}\u{5904}\u{7406} {count} \u{4E2A}\u{7247}\u{6BB5}",
+            "bulk_stale" => "\u{6761}\u{4EF6}\u{5DF2}\u{53D8}\u{5316}\u{FF0C}\u{8BF7}\u{91CD}\u{65B0}\u{9884}\u{89C8}",
+            "bulk_delete" => "\u{5220}\u{9664}\u{7247}\u{6BB5}",
+            "segment_tags" => "\u{7247}\u{6BB5}\u{6807}\u{7B7E}",
+            "segment_tags_hint" => "Shift+\u{62D6}\u{52A8}\u{65F6}\u{95F4}\u{8F74}\u{53EF}\u{9009}\u{62E9}\u{65F6}\u{95F4}\u{6BB5}\u{FF0C}\u{53F3}\u{952E}\u{4E3A}\u{7247}\u{6BB5}\u{6216}\u{9009}\u{533A}\u{6DFB}\u{52A0}\u{6807}\u{7B7E}",
+            "segment_tags_empty" => "\u{8BE5}\u{8303}\u{56F4}\u{5185}\u{8FD8}\u{6CA1}\u{6709}\u{7247}\u{6BB5}\u{6807}\u{7B7E}",
+            "tag_filter" => "\u{6807}\u{7B7E}\u{7B5B}\u{9009}",
+            "tag_name" => "\u{6807}\u{7B7E}\u{540D}",
+            "tag_range" => "\u{4E3A}\u{65F6}\u{95F4}\u{6BB5}\u{6DFB}\u{52A0}\u{6807}\u{7B7E}",
+            "day_note" => "\u{5F53}\u{65E5}\u{5907}\u{6CE8}",
+            "day_note_hint" => "\u{4E3A}\u{8FD9}\u{4E00}\u{5929}\u{5199}\u{70B9}\u{80CC}\u{666F}\u{FF0C}\u{4F8B}\u{5982}\u{75C5}\u{5047}\u{3001}\u{53D1}\u{5E03}\u{65E5}",
+            "days_off" => "\u{4F11}\u{606F}\u{65E5}",
+            "day_off_holiday" => "\u{8282}\u{5047}\u{65E5}",
+            "day_off_vacation" => "\u{4F11}\u{5047}",
+            "day_off_label" => "\u{5907}\u{6CE8}",
+            "day_off_preset" => "\u{6309}\u{56FD}\u{5BB6}\u{6DFB}\u{52A0}\u{8282}\u{5047}\u{65E5}",
+            "day_off_mark_vacation" => "\u{6807}\u{8BB0}\u{4E3A}\u{4F11}\u{5047}",
+            "day_off_mark_holiday" => "\u{6807}\u{8BB0}\u{4E3A}\u{8282}\u{5047}\u{65E5}",
+            "day_off_clear" => "\u{6E05}\u{9664}\u{4F11}\u{606F}\u{65E5}",
+            "overtime" => "\u{52A0}\u{73ED}",
+            "overtime_contract_hours" => "\u{5408}\u{540C}\u{5DE5}\u{65F6}\u{FF08}\u{5C0F}\u{65F6}\u{FF09}",
+            "overtime_exported" => "\u{52A0}\u{73ED}\u{62A5}\u{544A}\u{5DF2}\u{5BFC}\u{51FA}",
+            "overtime_month_balance" => "\u{672C}\u{6708}\u{7ED3}\u{4F59}",
+            "overtime_worked" => "\u{5DF2}\u{5DE5}\u{4F5C}",
+            "overtime_target" => "\u{76EE}\u{6807}",
+            "overtime_delta" => "\u{5DEE}\u{989D}",
+            "overtime_balance" => "\u{7D2F}\u{8BA1}",
+            "workday" => "\u{5DE5}\u{4F5C}\u{65F6}\u{6BB5}",
+            "workday_avg_start" => "\u{5E73}\u{5747}\u{4E0A}\u{73ED}",
+            "workday_avg_end" => "\u{5E73}\u{5747}\u{4E0B}\u{73ED}",
+            "workday_start" => "\u{4E0A}\u{73ED}",
+            "workday_end" => "\u{4E0B}\u{73ED}",
+            "workday_span" => "\u{8DE8}\u{5EA6}",
+            "afk_confirm" => "\u{957F}\u{65F6}\u{95F4}\u{79BB}\u{5F00}\u{540E}\u{8BE2}\u{95EE}",
+            "afk_welcome_back" => "\u{6B22}\u{8FCE}\u{56DE}\u{6765}",
+            "afk_away_for" => "\u{4F60}\u{79BB}\u{5F00}\u{4E86}",
+            "afk_last_activity" => "\u{79BB}\u{5F00}\u{524D}\u{7684}\u{6700}\u{540E}\u{6D3B}\u{52A8}",
+            "afk_reclassify_minutes" => "\u{5C06}\u{79BB}\u{5F00}\u{524D}\u{7684}\u{5206}\u{949F}\u{6570}\u{6539}\u{4E3A}\u{7A7A}\u{95F2}",
+            "afk_keep" => "\u{4FDD}\u{7559}",
+            "afk_mark_idle" => "\u{6807}\u{8BB0}\u{4E3A}\u{7A7A}\u{95F2}",
+            "noise_threshold" => "\u{5FFD}\u{7565}\u{77ED}\u{4E8E}\u{6B64}\u{65F6}\u{957F}\u{7684}\u{7247}\u{6BB5}\u{FF08}\u{79D2}\u{FF09}",
+            "noise_threshold_hint" => "\u{77ED}\u{4E8E}\u{8BE5}\u{65F6}\u{957F}\u{7684}\u{975E}\u{7A7A}\u{95F2}\u{7247}\u{6BB5}\u{4E0D}\u{8BA1}\u{5165}\u{6C47}\u{603B}\u{548C}\u{5BFC}\u{51FA}",
+            "fleeting_switches" => "\u{77ED}\u{6682}\u{5207}\u{6362}",
+            "away" => "\u{79BB}\u{5F00}",
+            "away_hint" => "\u{7A7A}\u{95F2}\u{548C}\u{9501}\u{5C4F}\u{5408}\u{5E76}\u{540E}\u{7684}\u{79BB}\u{5F00}\u{65F6}\u{95F4}\u{FF0C}\u{4E0D}\u{8BA1}\u{5165}\u{4EFB}\u{4F55}\u{5E94}\u{7528}",
+            "in_calls" => "\u{901A}\u{8BDD}\u{4E2D}",
+            "in_calls_hint" => "\u{901A}\u{8BAF}\u{5E94}\u{7528}\u{5360}\u{7528}\u{9EA6}\u{514B}\u{98CE}\u{7684}\u{65F6}\u{95F4}\u{FF0C}\u{540C}\u{65F6}\u{8BA1}\u{5165}\u{5F53}\u{65F6}\u{5728}\u{524D}\u{53F0}\u{7684}\u{5E94}\u{7528}",
+            "category_donut" => "\u{5206}\u{7C7B}\u{73AF}\u{5F62}\u{56FE}",
+            "category_donut_hint" => "\u{70B9}\u{51FB}\u{6247}\u{533A}\u{6309}\u{5206}\u{7C7B}\u{7B5B}\u{9009}",
+            "layout" => "\u{5E03}\u{5C40}",
+            "layout_reset" => "\u{6062}\u{590D}\u{9ED8}\u{8BA4}",
+            "layout_drag_hint" => "\u{62D6}\u{52A8}\u{4EE5}\u{8C03}\u{6574}\u{987A}\u{5E8F}",
+            "hourly_pattern" => "\u{6BCF}\u{5C0F}\u{65F6}\u{5206}\u{5E03}",
+            "category_summary" => "\u{5206}\u{7C7B}\u{6C47}\u{603B}",
+            "forecast_on_pace" => "\u{6309}\u{5F53}\u{524D}\u{8282}\u{594F}\u{9884}\u{8BA1}\u{6D3B}\u{8DC3} {duration}",
+            "anomaly_above_normal" => "{category}\u{65F6}\u{957F}\u{4E3A}\u{5E73}\u{65F6}\u{7684} {ratio} \u{500D}",
+            "anomaly_missing" => "\u{622A}\u{81F3} {time} \u{5C1A}\u{65E0}{category}\u{8BB0}\u{5F55}",
+            "events" => "\u{901A}\u{77E5}",
+            "no_events" => "\u{6682}\u{65E0}\u{901A}\u{77E5}",
+            "new_app_seen" => "\u{65B0}\u{5E94}\u{7528}",
+            "alias" => "\u{522B}\u{540D}",
+            "set_alias" => "\u{8BBE}\u{7F6E}\u{522B}\u{540D}",
+            "exclude" => "\u{6392}\u{9664}",
+            "excluded_apps" => "\u{5DF2}\u{6392}\u{9664}\u{7684}\u{5E94}\u{7528}",
+            "restore" => "\u{6062}\u{590D}",
+            "clear_all" => "\u{5168}\u{90E8}\u{6E05}\u{9664}",
+            "git" => "Git",
+            "git_title" => "Git \u{6D3B}\u{52A8}",
+            "git_hint" => "\u{63D0}\u{4EA4}\u{6765}\u{81EA}\u{5404}\u{4ED3}\u{5E93}\u{7684} HEAD reflog\u{FF1B}\u{7F16}\u{7801}\u{65F6}\u{95F4}\u{7EDF}\u{8BA1}\u{6807}\u{9898}\u{4E2D}\u{5305}\u{542B}\u{4ED3}\u{5E93}\u{6587}\u{4EF6}\u{5939}\u{540D}\u{7684}\u{7F16}\u{8F91}\u{5668}\u{7A97}\u{53E3}\u{3002}",
+            "git_show_markers" => "\u{5728}\u{65F6}\u{95F4}\u{8F74}\u{4E0A}\u{663E}\u{793A}\u{63D0}\u{4EA4}",
+            "git_repo_path" => "\u{4ED3}\u{5E93}\u{6587}\u{4EF6}\u{5939}",
+            "git_repo_missing" => "\u{672A}\u{627E}\u{5230} HEAD reflog",
+            "git_repository" => "\u{4ED3}\u{5E93}",
+            "git_coding_time" => "\u{7F16}\u{7801}\u{65F6}\u{95F4}",
+            "git_commits" => "\u{63D0}\u{4EA4}",
+            "title_tags" => "\u{6807}\u{9898}\u{6807}\u{7B7E}",
+            "title_tags_menu" => "\u{6807}\u{9898}\u{6807}\u{7B7E}\u{2026}",
+            "title_tags_hint" => "\u{4ECE}\u{7A97}\u{53E3}\u{6807}\u{9898}\u{4E2D}\u{7528}\u{6B63}\u{5219}\u{63D0}\u{53D6}\u{6807}\u{7B7E}\u{FF08}\u{6709}\u{6355}\u{83B7}\u{7EC4}\u{65F6}\u{53D6}\u{7B2C}\u{4E00}\u{7EC4}\u{FF09}\u{FF0C}\u{6309}\u{6807}\u{7B7E}\u{6C47}\u{603B}\u{65F6}\u{957F}\u{3002}",
+            "tag_rule_name" => "\u{540D}\u{79F0}",
+            "tag_rule_pattern" => "\u{6B63}\u{5219}\u{8868}\u{8FBE}\u{5F0F}",
+            "tag_rule_empty" => "\u{89C4}\u{5219}\u{540D}\u{79F0}\u{548C}\u{8868}\u{8FBE}\u{5F0F}\u{4E0D}\u{80FD}\u{4E3A}\u{7A7A}",
+            "title_tags_empty" => "\u{6B64}\u{8303}\u{56F4}\u{5185}\u{6CA1}\u{6709}\u{5E26}\u{6807}\u{7B7E}\u{7684}\u{65F6}\u{95F4}",
+            "jira" => "Jira \u{5DE5}\u{65F6}",
+            "jira_hint" => "\u{6309}\u{95EE}\u{9898}\u{7F16}\u{53F7}\u{FF08}\u{5982} PROJ-123\u{FF09}\u{548C}\u{65E5}\u{671F}\u{6C47}\u{603B}\u{6807}\u{9898}\u{4E2D}\u{7684}\u{65F6}\u{957F}\u{FF0C}\u{63D0}\u{4EA4}\u{524D}\u{8BF7}\u{68C0}\u{67E5}\u{3002}",
+            "jira_base_url" => "Jira \u{5730}\u{5740}",
+            "jira_email" => "\u{90AE}\u{7BB1}",
+            "jira_token" => "API \u{4EE4}\u{724C}",
+            "jira_scan" => "\u{67E5}\u{627E}\u{95EE}\u{9898}\u{7F16}\u{53F7}",
+            "jira_submit" => "\u{63D0}\u{4EA4}\u{6240}\u{9009}",
+            "jira_submitting" => "\u{63D0}\u{4EA4}\u{4E2D}\u{2026}",
+            "jira_no_keys" => "\u{6B64}\u{8303}\u{56F4}\u{5185}\u{7684}\u{7A97}\u{53E3}\u{6807}\u{9898}\u{4E2D}\u{6CA1}\u{6709}\u{627E}\u{5230}\u{95EE}\u{9898}\u{7F16}\u{53F7}",
+            "jira_submitted" => "\u{5DF2}\u{63D0}\u{4EA4}\u{5DE5}\u{65F6}\u{8BB0}\u{5F55}",
+            "jira_missing_credentials" => "\u{8BF7}\u{586B}\u{5199} Jira \u{5730}\u{5740}\u{3001}\u{90AE}\u{7BB1}\u{548C} API \u{4EE4}\u{724C}",
+            "pin_app" => "\u{7F6E}\u{9876}",
+            "unpin_app" => "\u{53D6}\u{6D88}\u{7F6E}\u{9876}",
+            "summary_filter_hint" => "\u{7B5B}\u{9009}\u{FF1A}*chrome*  !idle",
+            "title_rules" => "\u{6807}\u{9898}\u{89C4}\u{5219}",
+            "title_rules_menu" => "\u{6807}\u{9898}\u{89C4}\u{5219}\u{2026}",
+            "title_rules_hint" => "\u{6807}\u{9898}\u{5305}\u{542B}\u{5173}\u{952E}\u{5B57}\u{65F6}\u{8986}\u{76D6}\u{5E94}\u{7528}\u{5206}\u{7C7B}\u{FF0C}\u{6309}\u{987A}\u{5E8F}\u{5339}\u{914D}",
+            "title_contains" => "\u{6807}\u{9898}\u{5305}\u{542B}",
+            "rule_pattern_empty" => "\u{89C4}\u{5219}\u{4E0D}\u{80FD}\u{4E3A}\u{7A7A}",
+            "productivity" => "\u{6548}\u{7387}",
+            "add" => "\u{6DFB}\u{52A0}",
+            "delete" => "\u{5220}\u{9664}",
+            "uncategorized" => "\u{672A}\u{5206}\u{7C7B}",
+            "focus" => "\u{4E13}\u{6CE8}",
+            "start_focus" => "\u{5F00}\u{59CB}\u{4E13}\u{6CE8}",
+            "stop_focus" => "\u{7ED3}\u{675F}\u{4E13}\u{6CE8}",
+            "focus_minutes" => "\u{65F6}\u{957F}\u{FF08}\u{5206}\u{949F}\u{FF09}",
+            "focus_minimize" => "\u{6700}\u{5C0F}\u{5316}\u{5206}\u{5FC3}\u{7A97}\u{53E3}",
+            "focus_no_distracting" => "\u{53F3}\u{952E}\u{5E94}\u{7528}\u{5217}\u{8868}\u{4E2D}\u{7684}\u{6761}\u{76EE}\u{53EF}\u{5C06}\u{5176}\u{6807}\u{8BB0}\u{4E3A}\u{5206}\u{5FC3}",
+            "last_focus" => "\u{4E0A}\u{6B21}\u{4E13}\u{6CE8}",
+            "focus_breaks" => "\u{4E2D}\u{65AD}\u{6B21}\u{6570}",
+            "focus_clean" => "\u{4E13}\u{6CE8}\u{671F}\u{95F4}\u{6CA1}\u{6709}\u{6253}\u{5F00}\u{5206}\u{5FC3}\u{5E94}\u{7528}",
+            "rollover_title" => "\u{5468}\u{671F}\u{603B}\u{7ED3}",
+            "top_app" => "\u{6700}\u{5E38}\u{7528}",
+            "view" => "\u{67E5}\u{770B}",
+            "dismiss" => "\u{5173}\u{95ED}",
+            "display" => "\u{663E}\u{793A}",
+            "clock" => "\u{65F6}\u{949F}",
+            "font" => "\u{5B57}\u{4F53}",
+            "font_hint" => "\u{5B57}\u{4F53}\u{6587}\u{4EF6}\u{FF08}.ttf\u{3001}.otf \u{6216} .ttc\u{FF09}\u{FF0C}\u{7559}\u{7A7A}\u{4F7F}\u{7528}\u{9ED8}\u{8BA4}\u{5B57}\u{4F53}",
+            "font_apply" => "\u{5E94}\u{7528}",
+            "duration_format" => "\u{65F6}\u{957F}\u{683C}\u{5F0F}",
+            "compare" => "\u{5BF9}\u{6BD4}",
+            "compare_hint" => "\u{5728}\u{65B0}\u{7A97}\u{53E3}\u{4E2D}\u{67E5}\u{770B}\u{53E6}\u{4E00}\u{4E2A}\u{65E5}\u{671F}\u{8303}\u{56F4}",
             "today" => "\u{4ECA}\u{5929}",
             "single_day" => "\u{5355}\u{65E5}",
             "custom" => "\u{81EA}\u{5B9A}\u{4E49}",
             "rolling" => "\u{6EDA}\u{52A8}",
             "calendar" => "\u{65E5}\u{5386}",
             "timeline" => "\u{65F6}\u{95F4}\u{8F74}",
+            "follow_now" => "\u{8DDF}\u{968F}\u{5F53}\u{524D}\u{65F6}\u{95F4}",
+            "follow_now_hint" => "\u{7F29}\u{653E}\u{65F6}\u{4FDD}\u{6301}\u{89C6}\u{56FE}\u{8DDF}\u{968F}\u{5F53}\u{524D}\u{65F6}\u{95F4}",
             "top_apps" => "\u{5E94}\u{7528}\u{6392}\u{884C}",
             "total" => "\u{603B}\u{8BA1}",
             "import" => "\u{5BFC}\u{5165}",
             "export" => "\u{5BFC}\u{51FA}",
             "backup" => "\u{5907}\u{4EFD}",
             "format" => "\u{683C}\u{5F0F}",
+            "relocate_database" => "\u{8FC1}\u{79FB}\u{6570}\u{636E}\u{5E93}\u{2026}",
+            "open_crash_report" => "\u{6253}\u{5F00}\u{62A5}\u{544A}",
+            "whats_new" => "\u{66F4}\u{65B0}\u{5185}\u{5BB9}",
+            "migration_notes" => "\u{8FC1}\u{79FB}\u{8BF4}\u{660E}",
+            "relocate_to" => "\u{8FC1}\u{79FB}\u{5230}",
+            "relocate" => "\u{8FC1}\u{79FB}",
+            "scheduled_snapshots" => "\u{5B9A}\u{65F6}\u{5FEB}\u{7167}",
+            "snapshot_dir" => "\u{5FEB}\u{7167}\u{76EE}\u{5F55}",
+            "interval_hours" => "\u{95F4}\u{9694}\u{FF08}\u{5C0F}\u{65F6}\u{FF09}",
+            "enabled" => "\u{542F}\u{7528}",
+            "filename_template" => "\u{6587}\u{4EF6}\u{540D}\u{6A21}\u{677F}",
+            "placeholders" => "\u{53EF}\u{7528}\u{5360}\u{4F4D}\u{7B26}",
+            "existing_file" => "\u{540C}\u{540D}\u{6587}\u{4EF6}",
+            "auto_increment" => "\u{81EA}\u{52A8}\u{7F16}\u{53F7}",
+            "overwrite" => "\u{8986}\u{76D6}",
+            "export_exclude_idle" => "\u{6392}\u{9664}\u{7A7A}\u{95F2}",
+            "export_gzip" => "gzip \u{538B}\u{7F29}",
+            "export_split_by_month" => "\u{6309}\u{6708}\u{62C6}\u{5206}\u{6587}\u{4EF6}",
+            "export_group_by" => "\u{5206}\u{7EC4}\u{4F9D}\u{636E}",
+            "project" => "\u{9879}\u{76EE}",
+            "export_only_categories" => "\u{4EC5}\u{9650}\u{5206}\u{7C7B}",
+            "export_exclude_system" => "\u{6392}\u{9664}\u{7CFB}\u{7EDF}\u{5E94}\u{7528}",
+            "export_only_selected" => "\u{4EC5}\u{5BFC}\u{51FA}\u{5DF2}\u{9009}\u{5E94}\u{7528}",
             "path" => "\u{8DEF}\u{5F84}",
             "no_data" => "\u{5F53}\u{524D}\u{8303}\u{56F4}\u{6CA1}\u{6709}\u{5E94}\u{7528}\u{6570}\u{636E}\u{3002}",
             "status" => "\u{72B6}\u{6001}",
             "checked" => "\u{68C0}\u{67E5}\u{65F6}\u{95F4}",
             "last_write" => "\u{6700}\u{8FD1}\u{5199}\u{5165}",
+            "last_heartbeat" => "\u{6700}\u{8FD1}\u{5FC3}\u{8DF3}",
+            "backend_paused" => "\u{8FD0}\u{884C}\u{4E2D}\u{FF0C}\u{8BB0}\u{5F55}\u{5DF2}\u{6682}\u{505C}",
+            "backend_idle" => "\u{8FD0}\u{884C}\u{4E2D}\u{FF0C}\u{5F53}\u{524D}\u{7A7A}\u{95F2}",
+            "last_used" => "\u{6700}\u{8FD1}\u{4F7F}\u{7528}",
+            "auto_restart" => "\u{670D}\u{52A1}\u{505C}\u{6B62}\u{65F6}\u{81EA}\u{52A8}\u{91CD}\u{542F}",
+            "restart_after" => "\u{505C}\u{6B62}\u{8D85}\u{8FC7}",
+            "restart_attempts" => "\u{91CD}\u{542F}\u{6B21}\u{6570}",
+            "stopped_not_running" => "\u{540E}\u{53F0}\u{670D}\u{52A1}\u{672A}\u{8FD0}\u{884C}\u{3002}",
+            "stopped_binary_missing" => "\u{672A}\u{5728}\u{67E5}\u{770B}\u{5668}\u{65C1}\u{627E}\u{5230}\u{540E}\u{53F0}\u{7A0B}\u{5E8F}\u{3002}",
+            "stopped_unresponsive" => "\u{540E}\u{53F0}\u{670D}\u{52A1}\u{4ECD}\u{5728}\u{8FD0}\u{884C}\u{FF0C}\u{4F46}\u{5DF2}\u{505C}\u{6B62}\u{54CD}\u{5E94}\u{3002}",
+            "stopped_database_locked" => "\u{6570}\u{636E}\u{5E93}\u{88AB}\u{5176}\u{4ED6}\u{8FDB}\u{7A0B}\u{9501}\u{5B9A}\u{3002}",
+            "stopped_crashed" => "\u{540E}\u{53F0}\u{670D}\u{52A1}\u{4E0A}\u{6B21}\u{5D29}\u{6E83}\u{9000}\u{51FA}\u{FF0C}\u{5DF2}\u{4FDD}\u{5B58}\u{5D29}\u{6E83}\u{62A5}\u{544A}\u{3002}",
+            "open_install_folder" => "\u{6253}\u{5F00}\u{5B89}\u{88C5}\u{6587}\u{4EF6}\u{5939}",
+            "restart_backend" => "\u{91CD}\u{542F}\u{540E}\u{53F0}\u{670D}\u{52A1}",
+            "check_again" => "\u{91CD}\u{65B0}\u{68C0}\u{67E5}",
+            "start_backend" => "\u{7ACB}\u{5373}\u{542F}\u{52A8}\u{670D}\u{52A1}",
+            "previous_period" => "\u{4E0A}\u{671F}",
+            "new_in_period" => "\u{672C}\u{671F}\u{65B0}\u{589E}",
             "backend" => "LimeTrace Backend",
             "running" => "\u{8FD0}\u{884C}\u{4E2D}",
             "stopped" => "\u{672A}\u{8FD0}\u{884C}",
@@ -3986,27 +12339,258 @@ fn tr(language: UiLanguage, key: &'static str) -> &'static str {
             "lang" => "Lang",
             "range" => "Range",
             "date" => "Date",
+            "jump_to_date" => "Jump to date",
+            "jump_to_date_hint" => "Accepts 2024-5-3, 5/3, today, yesterday, last monday",
+            "unrecognized_date" => "Unrecognized date",
             "from" => "From",
             "to" => "To",
             "items" => "Items",
+            "sort" => "Sort",
+            "sessions" => "Sessions",
+            "title_split" => "Title split",
+            "avg_session" => "Avg Session",
+            "duration" => "Duration",
             "refresh" => "Refresh",
+            "theme" => "Theme",
+            "category" => "Category",
+            "remote_storage" => "Remote storage",
+            "remote_storage_hint" => "Sync batches and snapshots are encrypted on this machine before upload. The secret and passphrase aren't saved (LIMETRACE_REMOTE_SECRET, LIMETRACE_REMOTE_PASSPHRASE).",
+            "remote_off" => "Off",
+            "updates" => "Updates",
+            "update_stable" => "Stable",
+            "update_prerelease" => "Pre-release",
+            "update_check_now" => "Check now",
+            "update_up_to_date" => "Up to date",
+            "update_checking" => "Checking\u{2026}",
+            "update_staged" => "LimeTrace {version} is downloaded: tracking switches to it within a minute, the viewer on its next start.",
+            "remote_endpoint" => "Endpoint URL",
+            "remote_region" => "Region",
+            "remote_user" => "User / access key",
+            "remote_secret" => "Password / secret key",
+            "remote_passphrase" => "Encryption passphrase",
+            "remote_snapshots" => "Also upload scheduled snapshots",
+            "remote_uploaded" => "Uploaded",
+            "remote_downloaded" => "Downloaded",
+            "folder_sync" => "Sync between machines",
+            "folder_sync_hint" => "Each machine writes its segments to the shared folder and imports the others'. Use the same secret everywhere (LIMETRACE_SYNC_SECRET); it isn't saved.",
+            "folder_sync_path" => "Shared folder",
+            "folder_sync_secret" => "Shared secret",
+            "folder_sync_now" => "Sync now",
+            "folder_sync_exported" => "Exported",
+            "folder_sync_imported" => "Imported",
+            "folder_sync_duplicates" => "Duplicates",
+            "folder_sync_rejected" => "Rejected batches",
+            "team_export" => "Team dashboard export",
+            "team_export_days" => "Days",
+            "team_export_path" => "Output file (.json or .db)",
+            "team_export_now" => "Export now",
+            "team_export_hint" => "Hourly, daily category totals only; no titles or app names",
+            "trash" => "Trash",
+            "trash_restore" => "Restore",
+            "trash_restore_all" => "Restore all",
+            "trash_purge" => "Purge",
+            "trash_empty" => "Empty trash",
+            "trash_none" => "Trash is empty",
+            "trash_deleted_at" => "Deleted at",
+            "bulk_edit" => "Bulk edit",
+            "bulk_app_hint" => "App (empty = any)",
+            "bulk_title_hint" => "Title regex (optional)",
+            "bulk_preview" => "Dry run",
+            "map_columns" => "The headers weren't recognized; pick the column for each field",
+            "csv_preview" => "First rows",
+            "time_zone" => "Time zone",
+            "time_zone_hint" => "Local, UTC or e.g. Europe/Berlin",
+            "time_zone_offsets" => "Times with an offset (e.g. 2024-03-04T09:00:00+01:00) ignore this setting",
+            "bulk_apply" => "Apply",
+            "bulk_matches" => "{count} segments, {time} total",
+            "bulk_tag" => "Add tag",
+            "bulk_category_hint" => "Categories apply per app: needs an app, no title pattern, and ignores the range",
+            "bulk_done" => "Updated {count} segments",
+            "bulk_stale" => "Criteria changed; run the dry run again",
+            "bulk_delete" => "Delete segments",
+            "segment_tags" => "Segment tags",
+            "segment_tags_hint" => "Shift-drag the timeline to select a span; right-click a block or selection to tag it",
+            "segment_tags_empty" => "No segment tags in this range",
+            "tag_filter" => "Tag filter",
+            "tag_name" => "Tag",
+            "tag_range" => "Tag time span",
+            "day_note" => "Day note",
+            "day_note_hint" => "Context for this day, e.g. sick day or release day",
+            "days_off" => "Holidays & vacation",
+            "day_off_holiday" => "Holiday",
+            "day_off_vacation" => "Vacation",
+            "day_off_label" => "Note",
+            "day_off_preset" => "Country holidays",
+            "day_off_mark_vacation" => "Mark as vacation",
+            "day_off_mark_holiday" => "Mark as holiday",
+            "day_off_clear" => "Clear day off",
+            "overtime" => "Overtime",
+            "overtime_contract_hours" => "Contract hours per weekday",
+            "overtime_exported" => "Overtime report exported",
+            "overtime_month_balance" => "Month balance",
+            "overtime_worked" => "Worked",
+            "overtime_target" => "Target",
+            "overtime_delta" => "Delta",
+            "overtime_balance" => "Balance",
+            "workday" => "Workday",
+            "workday_avg_start" => "Avg start",
+            "workday_avg_end" => "avg end",
+            "workday_start" => "Start",
+            "workday_end" => "End",
+            "workday_span" => "Span",
+            "afk_confirm" => "Ask after long idle",
+            "afk_welcome_back" => "Welcome back",
+            "afk_away_for" => "You were away for",
+            "afk_last_activity" => "Last activity before that",
+            "afk_reclassify_minutes" => "Minutes before leaving to mark idle",
+            "afk_keep" => "Keep",
+            "afk_mark_idle" => "Mark as idle",
+            "noise_threshold" => "Ignore stints shorter than",
+            "noise_threshold_hint" => "Active segments shorter than this are left out of app totals and exports",
+            "fleeting_switches" => "Fleeting switches",
+            "away" => "Away",
+            "away_hint" => "Idle and locked time around a session lock, shown as one block; it is not counted towards any app",
+            "in_calls" => "In calls",
+            "in_calls_hint" => "Time a communications app had the microphone open; it also counts towards the app in front",
+            "category_donut" => "Category Donut",
+            "category_donut_hint" => "Click a slice to filter by category",
+            "layout" => "Layout",
+            "layout_reset" => "Reset to default",
+            "layout_drag_hint" => "Drag to reorder",
+            "hourly_pattern" => "Hourly Pattern",
+            "category_summary" => "Categories",
+            "forecast_on_pace" => "On pace for {duration} active",
+            "anomaly_above_normal" => "{category} {ratio}\u{D7} above normal",
+            "anomaly_missing" => "No {category} time recorded by {time}",
+            "events" => "Notifications",
+            "no_events" => "No notifications",
+            "new_app_seen" => "New app in use",
+            "alias" => "Alias",
+            "set_alias" => "Set alias",
+            "exclude" => "Exclude",
+            "excluded_apps" => "Excluded apps",
+            "restore" => "Restore",
+            "clear_all" => "Clear all",
+            "git" => "Git",
+            "git_title" => "Git Activity",
+            "git_hint" => "Commits come from each repository's HEAD reflog. Coding time counts editor windows whose title mentions the repository folder name.",
+            "git_show_markers" => "Show commits on timeline",
+            "git_repo_path" => "Repository folder",
+            "git_repo_missing" => "No HEAD reflog found",
+            "git_repository" => "Repository",
+            "git_coding_time" => "Coding time",
+            "git_commits" => "Commits",
+            "title_tags" => "Title Tags",
+            "title_tags_menu" => "Title tags\u{2026}",
+            "title_tags_hint" => "Rules pull tags out of window titles with a regex (the first capture group if there is one) and total time per tag.",
+            "tag_rule_name" => "Name",
+            "tag_rule_pattern" => "Regex",
+            "tag_rule_empty" => "Rule name and pattern cannot be empty",
+            "title_tags_empty" => "No tagged time in this range",
+            "jira" => "Jira Worklogs",
+            "jira_hint" => "Time is grouped per issue key (like PROJ-123 in a window title) and day. Review before submitting.",
+            "jira_base_url" => "Jira URL",
+            "jira_email" => "Email",
+            "jira_token" => "API token",
+            "jira_scan" => "Find issue keys",
+            "jira_submit" => "Submit selected",
+            "jira_submitting" => "Submitting\u{2026}",
+            "jira_no_keys" => "No issue keys found in window titles for this range",
+            "jira_submitted" => "Submitted worklogs",
+            "jira_missing_credentials" => "Fill in the Jira URL, email and API token",
+            "pin_app" => "Pin to top",
+            "unpin_app" => "Unpin",
+            "summary_filter_hint" => "Filter: *chrome*  !idle",
+            "title_rules" => "Title rules",
+            "title_rules_menu" => "Title rules\u{2026}",
+            "title_rules_hint" => "Titles containing the text override the app's category; first match wins",
+            "title_contains" => "Title contains",
+            "rule_pattern_empty" => "Rule text cannot be empty",
+            "productivity" => "Productivity",
+            "add" => "Add",
+            "delete" => "Delete",
+            "uncategorized" => "Uncategorized",
+            "focus" => "Focus",
+            "start_focus" => "Start focus session",
+            "stop_focus" => "Stop focus session",
+            "focus_minutes" => "Duration (minutes)",
+            "focus_minimize" => "Minimize distracting windows",
+            "focus_no_distracting" => "Right-click an app in the list to mark it as distracting",
+            "last_focus" => "Last session",
+            "focus_breaks" => "Focus breaks",
+            "focus_clean" => "No distracting apps during the session",
+            "rollover_title" => "Period summary",
+            "top_app" => "Top app",
+            "view" => "View",
+            "dismiss" => "Dismiss",
+            "display" => "Display",
+            "clock" => "Clock",
+            "font" => "Font",
+            "font_hint" => "A .ttf, .otf or .ttc file; empty for the default",
+            "font_apply" => "Apply",
+            "duration_format" => "Duration format",
+            "compare" => "Compare",
+            "compare_hint" => "Open another date range in a separate window",
             "today" => "Today",
             "single_day" => "Single Day",
             "custom" => "Custom",
             "rolling" => "Rolling",
             "calendar" => "Calendar",
             "timeline" => "Timeline",
+            "follow_now" => "Follow now",
+            "follow_now_hint" => "Keep a zoomed timeline scrolling with the current time",
             "top_apps" => "Top Apps",
             "total" => "Total",
             "import" => "Import",
             "export" => "Export",
             "backup" => "Backup",
             "format" => "Format",
+            "relocate_database" => "Relocate database\u{2026}",
+            "open_crash_report" => "Open report",
+            "whats_new" => "What's new",
+            "migration_notes" => "Migration",
+            "relocate_to" => "Move to",
+            "relocate" => "Relocate",
+            "scheduled_snapshots" => "Scheduled snapshots",
+            "snapshot_dir" => "Snapshot folder",
+            "interval_hours" => "Every (hours)",
+            "enabled" => "Enabled",
+            "filename_template" => "File name",
+            "placeholders" => "Placeholders",
+            "existing_file" => "If file exists",
+            "auto_increment" => "Auto-increment",
+            "overwrite" => "Overwrite",
+            "export_exclude_idle" => "Exclude idle",
+            "export_gzip" => "Gzip",
+            "export_split_by_month" => "One file per month",
+            "export_group_by" => "Group by",
+            "project" => "Project",
+            "export_only_categories" => "Only categories",
+            "export_exclude_system" => "Exclude system apps",
+            "export_only_selected" => "Only selected apps",
             "path" => "Path",
             "no_data" => "No app data for the selected range.",
             "status" => "Status",
             "checked" => "Checked",
             "last_write" => "Last segment write",
+            "last_heartbeat" => "Last heartbeat",
+            "backend_paused" => "Running, tracking paused",
+            "backend_idle" => "Running, you're idle",
+            "last_used" => "Last used",
+            "auto_restart" => "Auto-restart when stopped",
+            "restart_after" => "Stopped for more than",
+            "restart_attempts" => "Restart attempts",
+            "stopped_not_running" => "The backend is not running.",
+            "stopped_binary_missing" => "limetrace-backend was not found next to the viewer.",
+            "stopped_unresponsive" => "The backend is running but has stopped responding.",
+            "stopped_database_locked" => "Another process has locked the database.",
+            "stopped_crashed" => "The backend crashed last time; a crash report was saved.",
+            "open_install_folder" => "Open install folder",
+            "restart_backend" => "Restart backend",
+            "check_again" => "Check again",
+            "start_backend" => "Start backend now",
+            "previous_period" => "Previous period",
+            "new_in_period" => "new this period",
             "backend" => "LimeTrace Backend",
             "running" => "Running",
             "stopped" => "Stopped",
@@ -4017,6 +12601,275 @@ fn tr(language: UiLanguage, key: &'static str) -> &'static str {
             "help.reset" => "- Double-click: Reset View",
             _ => key,
         },
+        UiLanguage::ArSa => bidi::display_static(tr_ar(key)),
+    }
+}
+
+fn tr_ar(key: &'static str) -> &'static str {
+    match key {
+        "lang" => "\u{627}\u{644}\u{644}\u{63A}\u{629}",
+        "range" => "\u{627}\u{644}\u{646}\u{637}\u{627}\u{642}",
+        "date" => "\u{627}\u{644}\u{62A}\u{627}\u{631}\u{64A}\u{62E}",
+        "jump_to_date" => "\u{627}\u{646}\u{62A}\u{642}\u{627}\u{644} \u{625}\u{644}\u{649} \u{62A}\u{627}\u{631}\u{64A}\u{62E}",
+        "jump_to_date_hint" => "\u{64A}\u{642}\u{628}\u{644} 2024-5-3 \u{648}5/3 \u{648}today \u{648}yesterday \u{648}last monday",
+        "unrecognized_date" => "\u{62A}\u{627}\u{631}\u{64A}\u{62E} \u{63A}\u{64A}\u{631} \u{645}\u{639}\u{631}\u{648}\u{641}",
+        "from" => "\u{645}\u{646}",
+        "to" => "\u{625}\u{644}\u{649}",
+        "items" => "\u{627}\u{644}\u{639}\u{646}\u{627}\u{635}\u{631}",
+        "sort" => "\u{627}\u{644}\u{62A}\u{631}\u{62A}\u{64A}\u{628}",
+        "sessions" => "\u{627}\u{644}\u{62C}\u{644}\u{633}\u{627}\u{62A}",
+        "title_split" => "\u{62A}\u{642}\u{633}\u{64A}\u{645} \u{62D}\u{633}\u{628} \u{627}\u{644}\u{639}\u{646}\u{648}\u{627}\u{646}",
+        "avg_session" => "\u{645}\u{62A}\u{648}\u{633}\u{637} \u{627}\u{644}\u{62C}\u{644}\u{633}\u{629}",
+        "duration" => "\u{627}\u{644}\u{645}\u{62F}\u{629}",
+        "refresh" => "\u{62A}\u{62D}\u{62F}\u{64A}\u{62B}",
+        "theme" => "\u{627}\u{644}\u{645}\u{638}\u{647}\u{631}",
+        "category" => "\u{627}\u{644}\u{641}\u{626}\u{629}",
+        "remote_storage" => "\u{627}\u{644}\u{62A}\u{62E}\u{632}\u{64A}\u{646} \u{627}\u{644}\u{628}\u{639}\u{64A}\u{62F}",
+        "remote_storage_hint" => "\u{62A}\u{64F}\u{634}\u{641}\u{64E}\u{651}\u{631} \u{62F}\u{641}\u{639}\u{627}\u{62A} \u{627}\u{644}\u{645}\u{632}\u{627}\u{645}\u{646}\u{629} \u{648}\u{627}\u{644}\u{644}\u{642}\u{637}\u{627}\u{62A} \u{639}\u{644}\u{649} \u{647}\u{630}\u{627} \u{627}\u{644}\u{62C}\u{647}\u{627}\u{632} \u{642}\u{628}\u{644} \u{627}\u{644}\u{631}\u{641}\u{639}. \u{644}\u{627} \u{64A}\u{64F}\u{62D}\u{641}\u{638} \u{627}\u{644}\u{645}\u{641}\u{62A}\u{627}\u{62D} \u{627}\u{644}\u{633}\u{631}\u{64A} \u{648}\u{644}\u{627} \u{639}\u{628}\u{627}\u{631}\u{629} \u{627}\u{644}\u{645}\u{631}\u{648}\u{631} (LIMETRACE_REMOTE_SECRET\u{60C} LIMETRACE_REMOTE_PASSPHRASE).",
+        "remote_off" => "\u{625}\u{64A}\u{642}\u{627}\u{641}",
+        "updates" => "\u{627}\u{644}\u{62A}\u{62D}\u{62F}\u{64A}\u{62B}\u{627}\u{62A}",
+        "update_stable" => "\u{645}\u{633}\u{62A}\u{642}\u{631}",
+        "update_prerelease" => "\u{625}\u{635}\u{62F}\u{627}\u{631} \u{62A}\u{62C}\u{631}\u{64A}\u{628}\u{64A}",
+        "update_check_now" => "\u{627}\u{644}\u{62A}\u{62D}\u{642}\u{642} \u{627}\u{644}\u{622}\u{646}",
+        "update_up_to_date" => "\u{645}\u{62D}\u{62F}\u{64E}\u{651}\u{62B}",
+        "update_checking" => "\u{62C}\u{627}\u{631}\u{64D} \u{627}\u{644}\u{62A}\u{62D}\u{642}\u{642}\u{2026}",
+        "update_staged" => "\u{62A}\u{645} \u{62A}\u{646}\u{632}\u{64A}\u{644} LimeTrace {version}: \u{64A}\u{646}\u{62A}\u{642}\u{644} \u{627}\u{644}\u{62A}\u{62A}\u{628}\u{639} \u{625}\u{644}\u{64A}\u{647} \u{62E}\u{644}\u{627}\u{644} \u{62F}\u{642}\u{64A}\u{642}\u{629}\u{60C} \u{648}\u{627}\u{644}\u{648}\u{627}\u{62C}\u{647}\u{629} \u{639}\u{646}\u{62F} \u{62A}\u{634}\u{63A}\u{64A}\u{644}\u{647}\u{627} \u{627}\u{644}\u{62A}\u{627}\u{644}\u{64A}.",
+        "remote_endpoint" => "\u{639}\u{646}\u{648}\u{627}\u{646} \u{646}\u{642}\u{637}\u{629} \u{627}\u{644}\u{646}\u{647}\u{627}\u{64A}\u{629}",
+        "remote_region" => "\u{627}\u{644}\u{645}\u{646}\u{637}\u{642}\u{629}",
+        "remote_user" => "\u{627}\u{644}\u{645}\u{633}\u{62A}\u{62E}\u{62F}\u{645} / \u{645}\u{641}\u{62A}\u{627}\u{62D} \u{627}\u{644}\u{648}\u{635}\u{648}\u{644}",
+        "remote_secret" => "\u{643}\u{644}\u{645}\u{629} \u{627}\u{644}\u{645}\u{631}\u{648}\u{631} / \u{627}\u{644}\u{645}\u{641}\u{62A}\u{627}\u{62D} \u{627}\u{644}\u{633}\u{631}\u{64A}",
+        "remote_passphrase" => "\u{639}\u{628}\u{627}\u{631}\u{629} \u{645}\u{631}\u{648}\u{631} \u{627}\u{644}\u{62A}\u{634}\u{641}\u{64A}\u{631}",
+        "remote_snapshots" => "\u{631}\u{641}\u{639} \u{627}\u{644}\u{644}\u{642}\u{637}\u{627}\u{62A} \u{627}\u{644}\u{645}\u{62C}\u{62F}\u{648}\u{644}\u{629} \u{623}\u{64A}\u{636}\u{64B}\u{627}",
+        "remote_uploaded" => "\u{62A}\u{645} \u{627}\u{644}\u{631}\u{641}\u{639}",
+        "remote_downloaded" => "\u{62A}\u{645} \u{627}\u{644}\u{62A}\u{646}\u{632}\u{64A}\u{644}",
+        "folder_sync" => "\u{627}\u{644}\u{645}\u{632}\u{627}\u{645}\u{646}\u{629} \u{628}\u{64A}\u{646} \u{627}\u{644}\u{623}\u{62C}\u{647}\u{632}\u{629}",
+        "folder_sync_hint" => "\u{64A}\u{643}\u{62A}\u{628} \u{643}\u{644} \u{62C}\u{647}\u{627}\u{632} \u{645}\u{642}\u{627}\u{637}\u{639}\u{647} \u{641}\u{64A} \u{627}\u{644}\u{645}\u{62C}\u{644}\u{62F} \u{627}\u{644}\u{645}\u{634}\u{62A}\u{631}\u{643} \u{648}\u{64A}\u{633}\u{62A}\u{648}\u{631}\u{62F} \u{645}\u{642}\u{627}\u{637}\u{639} \u{627}\u{644}\u{623}\u{62C}\u{647}\u{632}\u{629} \u{627}\u{644}\u{623}\u{62E}\u{631}\u{649}. \u{627}\u{633}\u{62A}\u{62E}\u{62F}\u{645} \u{627}\u{644}\u{645}\u{641}\u{62A}\u{627}\u{62D} \u{627}\u{644}\u{633}\u{631}\u{64A} \u{646}\u{641}\u{633}\u{647} \u{639}\u{644}\u{649} \u{643}\u{644} \u{627}\u{644}\u{623}\u{62C}\u{647}\u{632}\u{629} (LIMETRACE_SYNC_SECRET)\u{61B} \u{644}\u{627} \u{64A}\u{64F}\u{62D}\u{641}\u{638}.",
+        "folder_sync_path" => "\u{627}\u{644}\u{645}\u{62C}\u{644}\u{62F} \u{627}\u{644}\u{645}\u{634}\u{62A}\u{631}\u{643}",
+        "folder_sync_secret" => "\u{627}\u{644}\u{645}\u{641}\u{62A}\u{627}\u{62D} \u{627}\u{644}\u{633}\u{631}\u{64A} \u{627}\u{644}\u{645}\u{634}\u{62A}\u{631}\u{643}",
+        "folder_sync_now" => "\u{645}\u{632}\u{627}\u{645}\u{646}\u{629} \u{627}\u{644}\u{622}\u{646}",
+        "folder_sync_exported" => "\u{62A}\u{645} \u{627}\u{644}\u{62A}\u{635}\u{62F}\u{64A}\u{631}",
+        "folder_sync_imported" => "\u{62A}\u{645} \u{627}\u{644}\u{627}\u{633}\u{62A}\u{64A}\u{631}\u{627}\u{62F}",
+        "folder_sync_duplicates" => "\u{645}\u{643}\u{631}\u{631}\u{627}\u{62A}",
+        "folder_sync_rejected" => "\u{62F}\u{641}\u{639}\u{627}\u{62A} \u{645}\u{631}\u{641}\u{648}\u{636}\u{629}",
+        "team_export" => "\u{62A}\u{635}\u{62F}\u{64A}\u{631} \u{644}\u{648}\u{62D}\u{629} \u{627}\u{644}\u{641}\u{631}\u{64A}\u{642}",
+        "team_export_days" => "\u{627}\u{644}\u{623}\u{64A}\u{627}\u{645}",
+        "team_export_path" => "\u{645}\u{644}\u{641} \u{627}\u{644}\u{625}\u{62E}\u{631}\u{627}\u{62C} (.json \u{623}\u{648} .db)",
+        "team_export_now" => "\u{62A}\u{635}\u{62F}\u{64A}\u{631} \u{627}\u{644}\u{622}\u{646}",
+        "team_export_hint" => "\u{645}\u{62C}\u{627}\u{645}\u{64A}\u{639} \u{627}\u{644}\u{641}\u{626}\u{627}\u{62A} \u{644}\u{643}\u{644} \u{633}\u{627}\u{639}\u{629} \u{648}\u{64A}\u{648}\u{645} \u{641}\u{642}\u{637}\u{61B} \u{628}\u{644}\u{627} \u{639}\u{646}\u{627}\u{648}\u{64A}\u{646} \u{623}\u{648} \u{623}\u{633}\u{645}\u{627}\u{621} \u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A}",
+        "trash" => "\u{627}\u{644}\u{645}\u{647}\u{645}\u{644}\u{627}\u{62A}",
+        "trash_restore" => "\u{627}\u{633}\u{62A}\u{639}\u{627}\u{62F}\u{629}",
+        "trash_restore_all" => "\u{627}\u{633}\u{62A}\u{639}\u{627}\u{62F}\u{629} \u{627}\u{644}\u{643}\u{644}",
+        "trash_purge" => "\u{62D}\u{630}\u{641} \u{646}\u{647}\u{627}\u{626}\u{64A}",
+        "trash_empty" => "\u{625}\u{641}\u{631}\u{627}\u{63A} \u{627}\u{644}\u{645}\u{647}\u{645}\u{644}\u{627}\u{62A}",
+        "trash_none" => "\u{627}\u{644}\u{645}\u{647}\u{645}\u{644}\u{627}\u{62A} \u{641}\u{627}\u{631}\u{63A}\u{629}",
+        "trash_deleted_at" => "\u{62A}\u{627}\u{631}\u{64A}\u{62E} \u{627}\u{644}\u{62D}\u{630}\u{641}",
+        "bulk_edit" => "\u{62A}\u{639}\u{62F}\u{64A}\u{644} \u{62C}\u{645}\u{627}\u{639}\u{64A}",
+        "bulk_app_hint" => "\u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642} (\u{641}\u{627}\u{631}\u{63A} = \u{623}\u{64A} \u{62A}\u{637}\u{628}\u{64A}\u{642})",
+        "bulk_title_hint" => "\u{62A}\u{639}\u{628}\u{64A}\u{631} \u{646}\u{645}\u{637}\u{64A} \u{644}\u{644}\u{639}\u{646}\u{648}\u{627}\u{646} (\u{627}\u{62E}\u{62A}\u{64A}\u{627}\u{631}\u{64A})",
+        "bulk_preview" => "\u{62A}\u{634}\u{63A}\u{64A}\u{644} \u{62A}\u{62C}\u{631}\u{64A}\u{628}\u{64A}",
+        "map_columns" => "\u{644}\u{645} \u{64A}\u{62A}\u{645} \u{627}\u{644}\u{62A}\u{639}\u{631}\u{641} \u{639}\u{644}\u{649} \u{627}\u{644}\u{639}\u{646}\u{627}\u{648}\u{64A}\u{646}\u{61B} \u{627}\u{62E}\u{62A}\u{631} \u{627}\u{644}\u{639}\u{645}\u{648}\u{62F} \u{644}\u{643}\u{644} \u{62D}\u{642}\u{644}",
+        "csv_preview" => "\u{627}\u{644}\u{635}\u{641}\u{648}\u{641} \u{627}\u{644}\u{623}\u{648}\u{644}\u{649}",
+        "time_zone" => "\u{627}\u{644}\u{645}\u{646}\u{637}\u{642}\u{629} \u{627}\u{644}\u{632}\u{645}\u{646}\u{64A}\u{629}",
+        "time_zone_hint" => "\u{645}\u{62D}\u{644}\u{64A} \u{623}\u{648} UTC \u{623}\u{648} \u{645}\u{62B}\u{644}\u{627}\u{64B} Asia/Riyadh",
+        "time_zone_offsets" => "\u{627}\u{644}\u{623}\u{648}\u{642}\u{627}\u{62A} \u{627}\u{644}\u{62A}\u{64A} \u{62A}\u{62A}\u{636}\u{645}\u{646} \u{625}\u{632}\u{627}\u{62D}\u{629} (\u{645}\u{62B}\u{644} 2024-03-04T09:00:00+03:00) \u{644}\u{627} \u{62A}\u{62A}\u{623}\u{62B}\u{631} \u{628}\u{647}\u{630}\u{627} \u{627}\u{644}\u{625}\u{639}\u{62F}\u{627}\u{62F}",
+        "bulk_apply" => "\u{62A}\u{637}\u{628}\u{64A}\u{642}",
+        "bulk_matches" => "{count} \u{645}\u{642}\u{637}\u{639}\u{60C} \u{628}\u{625}\u{62C}\u{645}\u{627}\u{644}\u{64A} {time}",
+        "bulk_tag" => "\u{625}\u{636}\u{627}\u{641}\u{629} \u{648}\u{633}\u{645}",
+        "bulk_category_hint" => "\u{62A}\u{64F}\u{637}\u{628}\u{64E}\u{651}\u{642} \u{627}\u{644}\u{641}\u{626}\u{627}\u{62A} \u{644}\u{643}\u{644} \u{62A}\u{637}\u{628}\u{64A}\u{642}: \u{64A}\u{644}\u{632}\u{645} \u{62A}\u{637}\u{628}\u{64A}\u{642} \u{62F}\u{648}\u{646} \u{646}\u{645}\u{637} \u{639}\u{646}\u{648}\u{627}\u{646}\u{60C} \u{648}\u{64A}\u{64F}\u{62A}\u{62C}\u{627}\u{647}\u{644} \u{627}\u{644}\u{646}\u{637}\u{627}\u{642}",
+        "bulk_done" => "\u{62A}\u{645} \u{62A}\u{62D}\u{62F}\u{64A}\u{62B} {count} \u{645}\u{642}\u{637}\u{639}",
+        "bulk_stale" => "\u{62A}\u{63A}\u{64A}\u{651}\u{631}\u{62A} \u{627}\u{644}\u{645}\u{639}\u{627}\u{64A}\u{64A}\u{631}\u{61B} \u{623}\u{639}\u{62F} \u{627}\u{644}\u{62A}\u{634}\u{63A}\u{64A}\u{644} \u{627}\u{644}\u{62A}\u{62C}\u{631}\u{64A}\u{628}\u{64A}",
+        "bulk_delete" => "\u{62D}\u{630}\u{641} \u{627}\u{644}\u{645}\u{642}\u{627}\u{637}\u{639}",
+        "segment_tags" => "\u{648}\u{633}\u{648}\u{645} \u{627}\u{644}\u{645}\u{642}\u{627}\u{637}\u{639}",
+        "segment_tags_hint" => "\u{627}\u{633}\u{62D}\u{628} \u{639}\u{644}\u{649} \u{627}\u{644}\u{62E}\u{637} \u{627}\u{644}\u{632}\u{645}\u{646}\u{64A} \u{645}\u{639} Shift \u{644}\u{62A}\u{62D}\u{62F}\u{64A}\u{62F} \u{641}\u{62A}\u{631}\u{629}\u{61B} \u{627}\u{646}\u{642}\u{631} \u{628}\u{632}\u{631} \u{627}\u{644}\u{641}\u{623}\u{631}\u{629} \u{627}\u{644}\u{623}\u{64A}\u{645}\u{646} \u{639}\u{644}\u{649} \u{643}\u{62A}\u{644}\u{629} \u{623}\u{648} \u{62A}\u{62D}\u{62F}\u{64A}\u{62F} \u{644}\u{648}\u{633}\u{645}\u{647}",
+        "segment_tags_empty" => "\u{644}\u{627} \u{62A}\u{648}\u{62C}\u{62F} \u{648}\u{633}\u{648}\u{645} \u{645}\u{642}\u{627}\u{637}\u{639} \u{641}\u{64A} \u{647}\u{630}\u{627} \u{627}\u{644}\u{646}\u{637}\u{627}\u{642}",
+        "tag_filter" => "\u{62A}\u{635}\u{641}\u{64A}\u{629} \u{628}\u{627}\u{644}\u{648}\u{633}\u{645}",
+        "tag_name" => "\u{627}\u{644}\u{648}\u{633}\u{645}",
+        "tag_range" => "\u{648}\u{633}\u{645} \u{627}\u{644}\u{641}\u{62A}\u{631}\u{629} \u{627}\u{644}\u{632}\u{645}\u{646}\u{64A}\u{629}",
+        "day_note" => "\u{645}\u{644}\u{627}\u{62D}\u{638}\u{629} \u{627}\u{644}\u{64A}\u{648}\u{645}",
+        "day_note_hint" => "\u{633}\u{64A}\u{627}\u{642} \u{647}\u{630}\u{627} \u{627}\u{644}\u{64A}\u{648}\u{645}\u{60C} \u{645}\u{62B}\u{644} \u{64A}\u{648}\u{645} \u{645}\u{631}\u{636}\u{64A} \u{623}\u{648} \u{64A}\u{648}\u{645} \u{625}\u{635}\u{62F}\u{627}\u{631}",
+        "days_off" => "\u{627}\u{644}\u{639}\u{637}\u{644}\u{627}\u{62A} \u{648}\u{627}\u{644}\u{625}\u{62C}\u{627}\u{632}\u{627}\u{62A}",
+        "day_off_holiday" => "\u{639}\u{637}\u{644}\u{629} \u{631}\u{633}\u{645}\u{64A}\u{629}",
+        "day_off_vacation" => "\u{625}\u{62C}\u{627}\u{632}\u{629}",
+        "day_off_label" => "\u{645}\u{644}\u{627}\u{62D}\u{638}\u{629}",
+        "day_off_preset" => "\u{627}\u{644}\u{639}\u{637}\u{644}\u{627}\u{62A} \u{627}\u{644}\u{648}\u{637}\u{646}\u{64A}\u{629}",
+        "day_off_mark_vacation" => "\u{62A}\u{639}\u{64A}\u{64A}\u{646} \u{643}\u{625}\u{62C}\u{627}\u{632}\u{629}",
+        "day_off_mark_holiday" => "\u{62A}\u{639}\u{64A}\u{64A}\u{646} \u{643}\u{639}\u{637}\u{644}\u{629} \u{631}\u{633}\u{645}\u{64A}\u{629}",
+        "day_off_clear" => "\u{625}\u{644}\u{63A}\u{627}\u{621} \u{64A}\u{648}\u{645} \u{627}\u{644}\u{639}\u{637}\u{644}\u{629}",
+        "overtime" => "\u{627}\u{644}\u{639}\u{645}\u{644} \u{627}\u{644}\u{625}\u{636}\u{627}\u{641}\u{64A}",
+        "overtime_contract_hours" => "\u{633}\u{627}\u{639}\u{627}\u{62A} \u{627}\u{644}\u{639}\u{642}\u{62F} \u{644}\u{643}\u{644} \u{64A}\u{648}\u{645} \u{639}\u{645}\u{644}",
+        "overtime_exported" => "\u{62A}\u{645} \u{62A}\u{635}\u{62F}\u{64A}\u{631} \u{62A}\u{642}\u{631}\u{64A}\u{631} \u{627}\u{644}\u{639}\u{645}\u{644} \u{627}\u{644}\u{625}\u{636}\u{627}\u{641}\u{64A}",
+        "overtime_month_balance" => "\u{631}\u{635}\u{64A}\u{62F} \u{627}\u{644}\u{634}\u{647}\u{631}",
+        "overtime_worked" => "\u{633}\u{627}\u{639}\u{627}\u{62A} \u{627}\u{644}\u{639}\u{645}\u{644}",
+        "overtime_target" => "\u{627}\u{644}\u{645}\u{633}\u{62A}\u{647}\u{62F}\u{641}",
+        "overtime_delta" => "\u{627}\u{644}\u{641}\u{631}\u{642}",
+        "overtime_balance" => "\u{627}\u{644}\u{631}\u{635}\u{64A}\u{62F}",
+        "workday" => "\u{64A}\u{648}\u{645} \u{627}\u{644}\u{639}\u{645}\u{644}",
+        "workday_avg_start" => "\u{645}\u{62A}\u{648}\u{633}\u{637} \u{627}\u{644}\u{628}\u{62F}\u{627}\u{64A}\u{629}",
+        "workday_avg_end" => "\u{645}\u{62A}\u{648}\u{633}\u{637} \u{627}\u{644}\u{646}\u{647}\u{627}\u{64A}\u{629}",
+        "workday_start" => "\u{627}\u{644}\u{628}\u{62F}\u{627}\u{64A}\u{629}",
+        "workday_end" => "\u{627}\u{644}\u{646}\u{647}\u{627}\u{64A}\u{629}",
+        "workday_span" => "\u{627}\u{644}\u{645}\u{62F}\u{649}",
+        "afk_confirm" => "\u{627}\u{644}\u{633}\u{624}\u{627}\u{644} \u{628}\u{639}\u{62F} \u{62E}\u{645}\u{648}\u{644} \u{637}\u{648}\u{64A}\u{644}",
+        "afk_welcome_back" => "\u{645}\u{631}\u{62D}\u{628}\u{64B}\u{627} \u{628}\u{639}\u{648}\u{62F}\u{62A}\u{643}",
+        "afk_away_for" => "\u{643}\u{646}\u{62A} \u{628}\u{639}\u{64A}\u{62F}\u{64B}\u{627} \u{644}\u{645}\u{62F}\u{629}",
+        "afk_last_activity" => "\u{622}\u{62E}\u{631} \u{646}\u{634}\u{627}\u{637} \u{642}\u{628}\u{644} \u{630}\u{644}\u{643}",
+        "afk_reclassify_minutes" => "\u{627}\u{644}\u{62F}\u{642}\u{627}\u{626}\u{642} \u{642}\u{628}\u{644} \u{627}\u{644}\u{645}\u{63A}\u{627}\u{62F}\u{631}\u{629} \u{627}\u{644}\u{62A}\u{64A} \u{62A}\u{64F}\u{639}\u{64E}\u{62F} \u{62E}\u{645}\u{648}\u{644}\u{64B}\u{627}",
+        "afk_keep" => "\u{625}\u{628}\u{642}\u{627}\u{621}",
+        "afk_mark_idle" => "\u{62A}\u{639}\u{64A}\u{64A}\u{646} \u{643}\u{62E}\u{645}\u{648}\u{644}",
+        "noise_threshold" => "\u{62A}\u{62C}\u{627}\u{647}\u{644} \u{627}\u{644}\u{641}\u{62A}\u{631}\u{627}\u{62A} \u{627}\u{644}\u{623}\u{642}\u{635}\u{631} \u{645}\u{646}",
+        "noise_threshold_hint" => "\u{62A}\u{64F}\u{633}\u{62A}\u{628}\u{639}\u{62F} \u{627}\u{644}\u{645}\u{642}\u{627}\u{637}\u{639} \u{627}\u{644}\u{646}\u{634}\u{637}\u{629} \u{627}\u{644}\u{623}\u{642}\u{635}\u{631} \u{645}\u{646} \u{647}\u{630}\u{627} \u{645}\u{646} \u{645}\u{62C}\u{627}\u{645}\u{64A}\u{639} \u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A} \u{648}\u{627}\u{644}\u{62A}\u{635}\u{62F}\u{64A}\u{631}",
+        "fleeting_switches" => "\u{62A}\u{628}\u{62F}\u{64A}\u{644}\u{627}\u{62A} \u{639}\u{627}\u{628}\u{631}\u{629}",
+        "away" => "\u{628}\u{639}\u{64A}\u{62F}\u{64B}\u{627}",
+        "away_hint" => "\u{648}\u{642}\u{62A} \u{627}\u{644}\u{62E}\u{645}\u{648}\u{644} \u{648}\u{627}\u{644}\u{642}\u{641}\u{644} \u{62D}\u{648}\u{644} \u{642}\u{641}\u{644} \u{627}\u{644}\u{62C}\u{644}\u{633}\u{629} \u{645}\u{639}\u{631}\u{648}\u{636}\u{64B}\u{627} \u{643}\u{641}\u{62A}\u{631}\u{629} \u{648}\u{627}\u{62D}\u{62F}\u{629}\u{61B} \u{644}\u{627} \u{64A}\u{64F}\u{62D}\u{62A}\u{633}\u{628} \u{644}\u{623}\u{64A} \u{62A}\u{637}\u{628}\u{64A}\u{642}",
+        "in_calls" => "\u{641}\u{64A} \u{627}\u{644}\u{645}\u{643}\u{627}\u{644}\u{645}\u{627}\u{62A}",
+        "in_calls_hint" => "\u{627}\u{644}\u{648}\u{642}\u{62A} \u{627}\u{644}\u{630}\u{64A} \u{641}\u{62A}\u{62D} \u{641}\u{64A}\u{647} \u{62A}\u{637}\u{628}\u{64A}\u{642} \u{627}\u{62A}\u{635}\u{627}\u{644} \u{627}\u{644}\u{645}\u{64A}\u{643}\u{631}\u{648}\u{641}\u{648}\u{646}",
+        "category_donut" => "\u{645}\u{62E}\u{637}\u{637} \u{627}\u{644}\u{641}\u{626}\u{627}\u{62A} \u{627}\u{644}\u{62F}\u{627}\u{626}\u{631}\u{64A}",
+        "category_donut_hint" => "\u{627}\u{646}\u{642}\u{631} \u{639}\u{644}\u{649} \u{634}\u{631}\u{64A}\u{62D}\u{629} \u{644}\u{644}\u{62A}\u{635}\u{641}\u{64A}\u{629} \u{62D}\u{633}\u{628} \u{627}\u{644}\u{641}\u{626}\u{629}",
+        "layout" => "\u{627}\u{644}\u{62A}\u{62E}\u{637}\u{64A}\u{637}",
+        "layout_reset" => "\u{625}\u{639}\u{627}\u{62F}\u{629} \u{627}\u{644}\u{62A}\u{639}\u{64A}\u{64A}\u{646} \u{625}\u{644}\u{649} \u{627}\u{644}\u{627}\u{641}\u{62A}\u{631}\u{627}\u{636}\u{64A}",
+        "layout_drag_hint" => "\u{627}\u{633}\u{62D}\u{628} \u{644}\u{625}\u{639}\u{627}\u{62F}\u{629} \u{627}\u{644}\u{62A}\u{631}\u{62A}\u{64A}\u{628}",
+        "hourly_pattern" => "\u{627}\u{644}\u{646}\u{645}\u{637} \u{627}\u{644}\u{633}\u{627}\u{639}\u{64A}",
+        "category_summary" => "\u{627}\u{644}\u{641}\u{626}\u{627}\u{62A}",
+        "forecast_on_pace" => "\u{641}\u{64A} \u{627}\u{644}\u{637}\u{631}\u{64A}\u{642} \u{625}\u{644}\u{649} {duration} \u{645}\u{646} \u{627}\u{644}\u{646}\u{634}\u{627}\u{637}",
+        "anomaly_above_normal" => "{category} \u{623}\u{639}\u{644}\u{649} \u{645}\u{646} \u{627}\u{644}\u{645}\u{639}\u{62A}\u{627}\u{62F} \u{D7}{ratio}",
+        "anomaly_missing" => "\u{644}\u{645} \u{64A}\u{64F}\u{633}\u{62C}\u{64E}\u{651}\u{644} \u{648}\u{642}\u{62A} {category} \u{62D}\u{62A}\u{649} {time}",
+        "events" => "\u{627}\u{644}\u{625}\u{634}\u{639}\u{627}\u{631}\u{627}\u{62A}",
+        "no_events" => "\u{644}\u{627} \u{62A}\u{648}\u{62C}\u{62F} \u{625}\u{634}\u{639}\u{627}\u{631}\u{627}\u{62A}",
+        "new_app_seen" => "\u{62A}\u{637}\u{628}\u{64A}\u{642} \u{62C}\u{62F}\u{64A}\u{62F} \u{642}\u{64A}\u{62F} \u{627}\u{644}\u{627}\u{633}\u{62A}\u{62E}\u{62F}\u{627}\u{645}",
+        "alias" => "\u{627}\u{633}\u{645} \u{645}\u{633}\u{62A}\u{639}\u{627}\u{631}",
+        "set_alias" => "\u{62A}\u{639}\u{64A}\u{64A}\u{646} \u{627}\u{633}\u{645} \u{645}\u{633}\u{62A}\u{639}\u{627}\u{631}",
+        "exclude" => "\u{627}\u{633}\u{62A}\u{628}\u{639}\u{627}\u{62F}",
+        "excluded_apps" => "\u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A} \u{627}\u{644}\u{645}\u{633}\u{62A}\u{628}\u{639}\u{62F}\u{629}",
+        "restore" => "\u{627}\u{633}\u{62A}\u{639}\u{627}\u{62F}\u{629}",
+        "clear_all" => "\u{645}\u{633}\u{62D} \u{627}\u{644}\u{643}\u{644}",
+        "git_title" => "\u{646}\u{634}\u{627}\u{637} Git",
+        "git_hint" => "\u{62A}\u{623}\u{62A}\u{64A} \u{627}\u{644}\u{625}\u{64A}\u{62F}\u{627}\u{639}\u{627}\u{62A} \u{645}\u{646} \u{633}\u{62C}\u{644} HEAD \u{644}\u{643}\u{644} \u{645}\u{633}\u{62A}\u{648}\u{62F}\u{639}. \u{64A}\u{64F}\u{62D}\u{62A}\u{633}\u{628} \u{648}\u{642}\u{62A} \u{627}\u{644}\u{628}\u{631}\u{645}\u{62C}\u{629} \u{645}\u{646} \u{646}\u{648}\u{627}\u{641}\u{630} \u{627}\u{644}\u{645}\u{62D}\u{631}\u{631} \u{627}\u{644}\u{62A}\u{64A} \u{64A}\u{630}\u{643}\u{631} \u{639}\u{646}\u{648}\u{627}\u{646}\u{647}\u{627} \u{627}\u{633}\u{645} \u{645}\u{62C}\u{644}\u{62F} \u{627}\u{644}\u{645}\u{633}\u{62A}\u{648}\u{62F}\u{639}.",
+        "git_show_markers" => "\u{625}\u{638}\u{647}\u{627}\u{631} \u{627}\u{644}\u{625}\u{64A}\u{62F}\u{627}\u{639}\u{627}\u{62A} \u{639}\u{644}\u{649} \u{627}\u{644}\u{62E}\u{637} \u{627}\u{644}\u{632}\u{645}\u{646}\u{64A}",
+        "git_repo_path" => "\u{645}\u{62C}\u{644}\u{62F} \u{627}\u{644}\u{645}\u{633}\u{62A}\u{648}\u{62F}\u{639}",
+        "git_repo_missing" => "\u{644}\u{645} \u{64A}\u{64F}\u{639}\u{62B}\u{631} \u{639}\u{644}\u{649} \u{633}\u{62C}\u{644} HEAD",
+        "git_repository" => "\u{627}\u{644}\u{645}\u{633}\u{62A}\u{648}\u{62F}\u{639}",
+        "git_coding_time" => "\u{648}\u{642}\u{62A} \u{627}\u{644}\u{628}\u{631}\u{645}\u{62C}\u{629}",
+        "git_commits" => "\u{627}\u{644}\u{625}\u{64A}\u{62F}\u{627}\u{639}\u{627}\u{62A}",
+        "title_tags" => "\u{648}\u{633}\u{648}\u{645} \u{627}\u{644}\u{639}\u{646}\u{627}\u{648}\u{64A}\u{646}",
+        "title_tags_menu" => "\u{648}\u{633}\u{648}\u{645} \u{627}\u{644}\u{639}\u{646}\u{627}\u{648}\u{64A}\u{646}\u{2026}",
+        "title_tags_hint" => "\u{62A}\u{633}\u{62A}\u{62E}\u{631}\u{62C} \u{627}\u{644}\u{642}\u{648}\u{627}\u{639}\u{62F} \u{627}\u{644}\u{648}\u{633}\u{648}\u{645} \u{645}\u{646} \u{639}\u{646}\u{627}\u{648}\u{64A}\u{646} \u{627}\u{644}\u{646}\u{648}\u{627}\u{641}\u{630} \u{628}\u{62A}\u{639}\u{628}\u{64A}\u{631} \u{646}\u{645}\u{637}\u{64A} (\u{645}\u{62C}\u{645}\u{648}\u{639}\u{629} \u{627}\u{644}\u{627}\u{644}\u{62A}\u{642}\u{627}\u{637} \u{627}\u{644}\u{623}\u{648}\u{644}\u{649} \u{625}\u{646} \u{648}\u{64F}\u{62C}\u{62F}\u{62A}) \u{648}\u{62A}\u{62C}\u{645}\u{639} \u{627}\u{644}\u{648}\u{642}\u{62A} \u{644}\u{643}\u{644} \u{648}\u{633}\u{645}.",
+        "tag_rule_name" => "\u{627}\u{644}\u{627}\u{633}\u{645}",
+        "tag_rule_pattern" => "\u{627}\u{644}\u{62A}\u{639}\u{628}\u{64A}\u{631} \u{627}\u{644}\u{646}\u{645}\u{637}\u{64A}",
+        "tag_rule_empty" => "\u{644}\u{627} \u{64A}\u{645}\u{643}\u{646} \u{623}\u{646} \u{64A}\u{643}\u{648}\u{646} \u{627}\u{633}\u{645} \u{627}\u{644}\u{642}\u{627}\u{639}\u{62F}\u{629} \u{623}\u{648} \u{646}\u{645}\u{637}\u{647}\u{627} \u{641}\u{627}\u{631}\u{63A}\u{64B}\u{627}",
+        "title_tags_empty" => "\u{644}\u{627} \u{64A}\u{648}\u{62C}\u{62F} \u{648}\u{642}\u{62A} \u{645}\u{648}\u{633}\u{648}\u{645} \u{641}\u{64A} \u{647}\u{630}\u{627} \u{627}\u{644}\u{646}\u{637}\u{627}\u{642}",
+        "jira" => "\u{633}\u{62C}\u{644}\u{627}\u{62A} \u{639}\u{645}\u{644} Jira",
+        "jira_hint" => "\u{64A}\u{64F}\u{62C}\u{645}\u{64E}\u{651}\u{639} \u{627}\u{644}\u{648}\u{642}\u{62A} \u{62D}\u{633}\u{628} \u{645}\u{641}\u{62A}\u{627}\u{62D} \u{627}\u{644}\u{645}\u{647}\u{645}\u{629} (\u{645}\u{62B}\u{644} PROJ-123 \u{641}\u{64A} \u{639}\u{646}\u{648}\u{627}\u{646} \u{627}\u{644}\u{646}\u{627}\u{641}\u{630}\u{629}) \u{648}\u{627}\u{644}\u{64A}\u{648}\u{645}. \u{631}\u{627}\u{62C}\u{639}\u{647} \u{642}\u{628}\u{644} \u{627}\u{644}\u{625}\u{631}\u{633}\u{627}\u{644}.",
+        "jira_base_url" => "\u{639}\u{646}\u{648}\u{627}\u{646} Jira",
+        "jira_email" => "\u{627}\u{644}\u{628}\u{631}\u{64A}\u{62F} \u{627}\u{644}\u{625}\u{644}\u{643}\u{62A}\u{631}\u{648}\u{646}\u{64A}",
+        "jira_token" => "\u{631}\u{645}\u{632} API",
+        "jira_scan" => "\u{627}\u{644}\u{628}\u{62D}\u{62B} \u{639}\u{646} \u{645}\u{641}\u{627}\u{62A}\u{64A}\u{62D} \u{627}\u{644}\u{645}\u{647}\u{627}\u{645}",
+        "jira_submit" => "\u{625}\u{631}\u{633}\u{627}\u{644} \u{627}\u{644}\u{645}\u{62D}\u{62F}\u{62F}",
+        "jira_submitting" => "\u{62C}\u{627}\u{631}\u{64D} \u{627}\u{644}\u{625}\u{631}\u{633}\u{627}\u{644}\u{2026}",
+        "jira_no_keys" => "\u{644}\u{645} \u{64A}\u{64F}\u{639}\u{62B}\u{631} \u{639}\u{644}\u{649} \u{645}\u{641}\u{627}\u{62A}\u{64A}\u{62D} \u{645}\u{647}\u{627}\u{645} \u{641}\u{64A} \u{639}\u{646}\u{627}\u{648}\u{64A}\u{646} \u{627}\u{644}\u{646}\u{648}\u{627}\u{641}\u{630} \u{644}\u{647}\u{630}\u{627} \u{627}\u{644}\u{646}\u{637}\u{627}\u{642}",
+        "jira_submitted" => "\u{62A}\u{645} \u{625}\u{631}\u{633}\u{627}\u{644} \u{633}\u{62C}\u{644}\u{627}\u{62A} \u{627}\u{644}\u{639}\u{645}\u{644}",
+        "jira_missing_credentials" => "\u{623}\u{62F}\u{62E}\u{644} \u{639}\u{646}\u{648}\u{627}\u{646} Jira \u{648}\u{627}\u{644}\u{628}\u{631}\u{64A}\u{62F} \u{627}\u{644}\u{625}\u{644}\u{643}\u{62A}\u{631}\u{648}\u{646}\u{64A} \u{648}\u{631}\u{645}\u{632} API",
+        "pin_app" => "\u{62A}\u{62B}\u{628}\u{64A}\u{62A} \u{641}\u{64A} \u{627}\u{644}\u{623}\u{639}\u{644}\u{649}",
+        "unpin_app" => "\u{625}\u{644}\u{63A}\u{627}\u{621} \u{627}\u{644}\u{62A}\u{62B}\u{628}\u{64A}\u{62A}",
+        "summary_filter_hint" => "\u{62A}\u{635}\u{641}\u{64A}\u{629}: *chrome*  !idle",
+        "title_rules" => "\u{642}\u{648}\u{627}\u{639}\u{62F} \u{627}\u{644}\u{639}\u{646}\u{627}\u{648}\u{64A}\u{646}",
+        "title_rules_menu" => "\u{642}\u{648}\u{627}\u{639}\u{62F} \u{627}\u{644}\u{639}\u{646}\u{627}\u{648}\u{64A}\u{646}\u{2026}",
+        "title_rules_hint" => "\u{627}\u{644}\u{639}\u{646}\u{627}\u{648}\u{64A}\u{646} \u{627}\u{644}\u{62A}\u{64A} \u{62A}\u{62D}\u{62A}\u{648}\u{64A} \u{639}\u{644}\u{649} \u{627}\u{644}\u{646}\u{635} \u{62A}\u{62A}\u{62C}\u{627}\u{648}\u{632} \u{641}\u{626}\u{629} \u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642}\u{61B} \u{623}\u{648}\u{644} \u{62A}\u{637}\u{627}\u{628}\u{642} \u{647}\u{648} \u{627}\u{644}\u{645}\u{639}\u{62A}\u{645}\u{62F}",
+        "title_contains" => "\u{627}\u{644}\u{639}\u{646}\u{648}\u{627}\u{646} \u{64A}\u{62D}\u{62A}\u{648}\u{64A} \u{639}\u{644}\u{649}",
+        "rule_pattern_empty" => "\u{644}\u{627} \u{64A}\u{645}\u{643}\u{646} \u{623}\u{646} \u{64A}\u{643}\u{648}\u{646} \u{646}\u{635} \u{627}\u{644}\u{642}\u{627}\u{639}\u{62F}\u{629} \u{641}\u{627}\u{631}\u{63A}\u{64B}\u{627}",
+        "productivity" => "\u{627}\u{644}\u{625}\u{646}\u{62A}\u{627}\u{62C}\u{64A}\u{629}",
+        "add" => "\u{625}\u{636}\u{627}\u{641}\u{629}",
+        "delete" => "\u{62D}\u{630}\u{641}",
+        "uncategorized" => "\u{63A}\u{64A}\u{631} \u{645}\u{635}\u{646}\u{641}",
+        "focus" => "\u{627}\u{644}\u{62A}\u{631}\u{643}\u{64A}\u{632}",
+        "start_focus" => "\u{628}\u{62F}\u{621} \u{62C}\u{644}\u{633}\u{629} \u{62A}\u{631}\u{643}\u{64A}\u{632}",
+        "stop_focus" => "\u{625}\u{64A}\u{642}\u{627}\u{641} \u{62C}\u{644}\u{633}\u{629} \u{627}\u{644}\u{62A}\u{631}\u{643}\u{64A}\u{632}",
+        "focus_minutes" => "\u{627}\u{644}\u{645}\u{62F}\u{629} (\u{628}\u{627}\u{644}\u{62F}\u{642}\u{627}\u{626}\u{642})",
+        "focus_minimize" => "\u{62A}\u{635}\u{63A}\u{64A}\u{631} \u{627}\u{644}\u{646}\u{648}\u{627}\u{641}\u{630} \u{627}\u{644}\u{645}\u{634}\u{62A}\u{62A}\u{629}",
+        "focus_no_distracting" => "\u{627}\u{646}\u{642}\u{631} \u{628}\u{632}\u{631} \u{627}\u{644}\u{641}\u{623}\u{631}\u{629} \u{627}\u{644}\u{623}\u{64A}\u{645}\u{646} \u{639}\u{644}\u{649} \u{62A}\u{637}\u{628}\u{64A}\u{642} \u{641}\u{64A} \u{627}\u{644}\u{642}\u{627}\u{626}\u{645}\u{629} \u{644}\u{62A}\u{639}\u{64A}\u{64A}\u{646}\u{647} \u{643}\u{645}\u{634}\u{62A}\u{62A}",
+        "last_focus" => "\u{627}\u{644}\u{62C}\u{644}\u{633}\u{629} \u{627}\u{644}\u{623}\u{62E}\u{64A}\u{631}\u{629}",
+        "focus_breaks" => "\u{627}\u{646}\u{642}\u{637}\u{627}\u{639}\u{627}\u{62A} \u{627}\u{644}\u{62A}\u{631}\u{643}\u{64A}\u{632}",
+        "focus_clean" => "\u{644}\u{627} \u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A} \u{645}\u{634}\u{62A}\u{62A}\u{629} \u{623}\u{62B}\u{646}\u{627}\u{621} \u{627}\u{644}\u{62C}\u{644}\u{633}\u{629}",
+        "rollover_title" => "\u{645}\u{644}\u{62E}\u{635} \u{627}\u{644}\u{641}\u{62A}\u{631}\u{629}",
+        "top_app" => "\u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642} \u{627}\u{644}\u{623}\u{648}\u{644}",
+        "view" => "\u{639}\u{631}\u{636}",
+        "dismiss" => "\u{62A}\u{62C}\u{627}\u{647}\u{644}",
+        "display" => "\u{627}\u{644}\u{639}\u{631}\u{636}",
+        "clock" => "\u{627}\u{644}\u{633}\u{627}\u{639}\u{629}",
+        "font" => "\u{627}\u{644}\u{62E}\u{637}",
+        "font_hint" => "\u{645}\u{644}\u{641} .ttf \u{623}\u{648} .otf \u{623}\u{648} .ttc\u{61B} \u{641}\u{627}\u{631}\u{63A} \u{644}\u{644}\u{62E}\u{637} \u{627}\u{644}\u{627}\u{641}\u{62A}\u{631}\u{627}\u{636}\u{64A}",
+        "font_apply" => "\u{62A}\u{637}\u{628}\u{64A}\u{642}",
+        "duration_format" => "\u{62A}\u{646}\u{633}\u{64A}\u{642} \u{627}\u{644}\u{645}\u{62F}\u{629}",
+        "compare" => "\u{645}\u{642}\u{627}\u{631}\u{646}\u{629}",
+        "compare_hint" => "\u{641}\u{62A}\u{62D} \u{646}\u{637}\u{627}\u{642} \u{62A}\u{627}\u{631}\u{64A}\u{62E} \u{622}\u{62E}\u{631} \u{641}\u{64A} \u{646}\u{627}\u{641}\u{630}\u{629} \u{645}\u{646}\u{641}\u{635}\u{644}\u{629}",
+        "today" => "\u{627}\u{644}\u{64A}\u{648}\u{645}",
+        "single_day" => "\u{64A}\u{648}\u{645} \u{648}\u{627}\u{62D}\u{62F}",
+        "custom" => "\u{645}\u{62E}\u{635}\u{635}",
+        "rolling" => "\u{645}\u{62A}\u{62D}\u{631}\u{643}",
+        "calendar" => "\u{627}\u{644}\u{62A}\u{642}\u{648}\u{64A}\u{645}",
+        "timeline" => "\u{627}\u{644}\u{62E}\u{637} \u{627}\u{644}\u{632}\u{645}\u{646}\u{64A}",
+        "follow_now" => "\u{645}\u{62A}\u{627}\u{628}\u{639}\u{629} \u{627}\u{644}\u{648}\u{642}\u{62A} \u{627}\u{644}\u{62D}\u{627}\u{644}\u{64A}",
+        "follow_now_hint" => "\u{625}\u{628}\u{642}\u{627}\u{621} \u{627}\u{644}\u{62E}\u{637} \u{627}\u{644}\u{632}\u{645}\u{646}\u{64A} \u{627}\u{644}\u{645}\u{643}\u{628}\u{64E}\u{651}\u{631} \u{64A}\u{62A}\u{62D}\u{631}\u{643} \u{645}\u{639} \u{627}\u{644}\u{648}\u{642}\u{62A} \u{627}\u{644}\u{62D}\u{627}\u{644}\u{64A}",
+        "top_apps" => "\u{623}\u{643}\u{62B}\u{631} \u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A} \u{627}\u{633}\u{62A}\u{62E}\u{62F}\u{627}\u{645}\u{64B}\u{627}",
+        "total" => "\u{627}\u{644}\u{625}\u{62C}\u{645}\u{627}\u{644}\u{64A}",
+        "import" => "\u{627}\u{633}\u{62A}\u{64A}\u{631}\u{627}\u{62F}",
+        "export" => "\u{62A}\u{635}\u{62F}\u{64A}\u{631}",
+        "backup" => "\u{646}\u{633}\u{62E} \u{627}\u{62D}\u{62A}\u{64A}\u{627}\u{637}\u{64A}",
+        "format" => "\u{627}\u{644}\u{62A}\u{646}\u{633}\u{64A}\u{642}",
+        "relocate_database" => "\u{646}\u{642}\u{644} \u{642}\u{627}\u{639}\u{62F}\u{629} \u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A}\u{2026}",
+        "open_crash_report" => "\u{641}\u{62A}\u{62D} \u{627}\u{644}\u{62A}\u{642}\u{631}\u{64A}\u{631}",
+        "whats_new" => "\u{645}\u{627} \u{627}\u{644}\u{62C}\u{62F}\u{64A}\u{62F}",
+        "migration_notes" => "\u{62E}\u{637}\u{648}\u{627}\u{62A} \u{627}\u{644}\u{62A}\u{631}\u{62D}\u{64A}\u{644}",
+        "relocate_to" => "\u{627}\u{644}\u{646}\u{642}\u{644} \u{625}\u{644}\u{649}",
+        "relocate" => "\u{646}\u{642}\u{644}",
+        "scheduled_snapshots" => "\u{627}\u{644}\u{644}\u{642}\u{637}\u{627}\u{62A} \u{627}\u{644}\u{645}\u{62C}\u{62F}\u{648}\u{644}\u{629}",
+        "snapshot_dir" => "\u{645}\u{62C}\u{644}\u{62F} \u{627}\u{644}\u{644}\u{642}\u{637}\u{627}\u{62A}",
+        "interval_hours" => "\u{643}\u{644} (\u{633}\u{627}\u{639}\u{627}\u{62A})",
+        "enabled" => "\u{645}\u{641}\u{639}\u{651}\u{644}",
+        "filename_template" => "\u{627}\u{633}\u{645} \u{627}\u{644}\u{645}\u{644}\u{641}",
+        "placeholders" => "\u{627}\u{644}\u{639}\u{646}\u{627}\u{635}\u{631} \u{627}\u{644}\u{646}\u{627}\u{626}\u{628}\u{629}",
+        "existing_file" => "\u{625}\u{630}\u{627} \u{643}\u{627}\u{646} \u{627}\u{644}\u{645}\u{644}\u{641} \u{645}\u{648}\u{62C}\u{648}\u{62F}\u{64B}\u{627}",
+        "auto_increment" => "\u{62A}\u{631}\u{642}\u{64A}\u{645} \u{62A}\u{644}\u{642}\u{627}\u{626}\u{64A}",
+        "overwrite" => "\u{627}\u{633}\u{62A}\u{628}\u{62F}\u{627}\u{644}",
+        "export_exclude_idle" => "\u{627}\u{633}\u{62A}\u{628}\u{639}\u{627}\u{62F} \u{627}\u{644}\u{62E}\u{645}\u{648}\u{644}",
+        "export_gzip" => "\u{636}\u{63A}\u{637} gzip",
+        "export_split_by_month" => "\u{645}\u{644}\u{641} \u{644}\u{643}\u{644} \u{634}\u{647}\u{631}",
+        "export_group_by" => "\u{627}\u{644}\u{62A}\u{62C}\u{645}\u{64A}\u{639} \u{62D}\u{633}\u{628}",
+        "project" => "\u{627}\u{644}\u{645}\u{634}\u{631}\u{648}\u{639}",
+        "export_only_categories" => "\u{627}\u{644}\u{641}\u{626}\u{627}\u{62A} \u{641}\u{642}\u{637}",
+        "export_exclude_system" => "\u{627}\u{633}\u{62A}\u{628}\u{639}\u{627}\u{62F} \u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A} \u{627}\u{644}\u{646}\u{638}\u{627}\u{645}",
+        "export_only_selected" => "\u{627}\u{644}\u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A} \u{627}\u{644}\u{645}\u{62D}\u{62F}\u{62F}\u{629} \u{641}\u{642}\u{637}",
+        "path" => "\u{627}\u{644}\u{645}\u{633}\u{627}\u{631}",
+        "no_data" => "\u{644}\u{627} \u{62A}\u{648}\u{62C}\u{62F} \u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A} \u{62A}\u{637}\u{628}\u{64A}\u{642}\u{627}\u{62A} \u{644}\u{644}\u{646}\u{637}\u{627}\u{642} \u{627}\u{644}\u{645}\u{62D}\u{62F}\u{62F}.",
+        "status" => "\u{627}\u{644}\u{62D}\u{627}\u{644}\u{629}",
+        "checked" => "\u{622}\u{62E}\u{631} \u{641}\u{62D}\u{635}",
+        "last_write" => "\u{622}\u{62E}\u{631} \u{643}\u{62A}\u{627}\u{628}\u{629} \u{644}\u{645}\u{642}\u{637}\u{639}",
+        "last_heartbeat" => "\u{622}\u{62E}\u{631} \u{646}\u{628}\u{636}\u{629}",
+        "backend_paused" => "\u{64A}\u{639}\u{645}\u{644}\u{60C} \u{627}\u{644}\u{62A}\u{62A}\u{628}\u{639} \u{645}\u{62A}\u{648}\u{642}\u{641}",
+        "backend_idle" => "\u{64A}\u{639}\u{645}\u{644}\u{60C} \u{623}\u{646}\u{62A} \u{62E}\u{627}\u{645}\u{644}",
+        "last_used" => "\u{622}\u{62E}\u{631} \u{627}\u{633}\u{62A}\u{62E}\u{62F}\u{627}\u{645}",
+        "auto_restart" => "\u{625}\u{639}\u{627}\u{62F}\u{629} \u{627}\u{644}\u{62A}\u{634}\u{63A}\u{64A}\u{644} \u{62A}\u{644}\u{642}\u{627}\u{626}\u{64A}\u{64B}\u{627} \u{639}\u{646}\u{62F} \u{627}\u{644}\u{62A}\u{648}\u{642}\u{641}",
+        "restart_after" => "\u{645}\u{62A}\u{648}\u{642}\u{641} \u{644}\u{623}\u{643}\u{62B}\u{631} \u{645}\u{646}",
+        "restart_attempts" => "\u{645}\u{62D}\u{627}\u{648}\u{644}\u{627}\u{62A} \u{625}\u{639}\u{627}\u{62F}\u{629} \u{627}\u{644}\u{62A}\u{634}\u{63A}\u{64A}\u{644}",
+        "stopped_not_running" => "\u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{644}\u{627} \u{62A}\u{639}\u{645}\u{644}.",
+        "stopped_binary_missing" => "\u{644}\u{645} \u{64A}\u{62A}\u{645} \u{627}\u{644}\u{639}\u{62B}\u{648}\u{631} \u{639}\u{644}\u{649} \u{628}\u{631}\u{646}\u{627}\u{645}\u{62C} \u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{628}\u{62C}\u{627}\u{646}\u{628} \u{627}\u{644}\u{639}\u{627}\u{631}\u{636}.",
+        "stopped_unresponsive" => "\u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{62A}\u{639}\u{645}\u{644} \u{644}\u{643}\u{646}\u{647}\u{627} \u{62A}\u{648}\u{642}\u{641}\u{62A} \u{639}\u{646} \u{627}\u{644}\u{627}\u{633}\u{62A}\u{62C}\u{627}\u{628}\u{629}.",
+        "stopped_database_locked" => "\u{642}\u{627}\u{639}\u{62F}\u{629} \u{627}\u{644}\u{628}\u{64A}\u{627}\u{646}\u{627}\u{62A} \u{645}\u{642}\u{641}\u{644}\u{629} \u{645}\u{646} \u{639}\u{645}\u{644}\u{64A}\u{629} \u{623}\u{62E}\u{631}\u{649}.",
+        "stopped_crashed" => "\u{62A}\u{639}\u{637}\u{644}\u{62A} \u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{641}\u{64A} \u{627}\u{644}\u{645}\u{631}\u{629} \u{627}\u{644}\u{633}\u{627}\u{628}\u{642}\u{629} \u{648}\u{62D}\u{64F}\u{641}\u{638} \u{62A}\u{642}\u{631}\u{64A}\u{631} \u{639}\u{637}\u{644}.",
+        "open_install_folder" => "\u{627}\u{641}\u{62A}\u{62D} \u{645}\u{62C}\u{644}\u{62F} \u{627}\u{644}\u{62A}\u{62B}\u{628}\u{64A}\u{62A}",
+        "restart_backend" => "\u{623}\u{639}\u{62F} \u{62A}\u{634}\u{63A}\u{64A}\u{644} \u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629}",
+        "check_again" => "\u{62A}\u{62D}\u{642}\u{642} \u{645}\u{62C}\u{62F}\u{62F}\u{64B}\u{627}",
+        "start_backend" => "\u{62A}\u{634}\u{63A}\u{64A}\u{644} \u{627}\u{644}\u{62E}\u{62F}\u{645}\u{629} \u{627}\u{644}\u{622}\u{646}",
+        "previous_period" => "\u{627}\u{644}\u{641}\u{62A}\u{631}\u{629} \u{627}\u{644}\u{633}\u{627}\u{628}\u{642}\u{629}",
+        "new_in_period" => "\u{62C}\u{62F}\u{64A}\u{62F} \u{641}\u{64A} \u{647}\u{630}\u{647} \u{627}\u{644}\u{641}\u{62A}\u{631}\u{629}",
+        "backend" => "\u{62E}\u{62F}\u{645}\u{629} LimeTrace",
+        "running" => "\u{642}\u{64A}\u{62F} \u{627}\u{644}\u{62A}\u{634}\u{63A}\u{64A}\u{644}",
+        "stopped" => "\u{645}\u{62A}\u{648}\u{642}\u{641}\u{629}",
+        "unknown" => "\u{63A}\u{64A}\u{631} \u{645}\u{639}\u{631}\u{648}\u{641}",
+        "help.timeline" => "\u{627}\u{644}\u{62E}\u{637} \u{627}\u{644}\u{632}\u{645}\u{646}\u{64A}",
+        "help.zoom" => "- \u{639}\u{62C}\u{644}\u{629} \u{627}\u{644}\u{641}\u{623}\u{631}\u{629}: \u{62A}\u{643}\u{628}\u{64A}\u{631} \u{648}\u{62A}\u{635}\u{63A}\u{64A}\u{631}",
+        "help.pan" => "- \u{627}\u{644}\u{633}\u{62D}\u{628}: \u{62A}\u{62D}\u{631}\u{64A}\u{643}",
+        "help.reset" => "- \u{646}\u{642}\u{631} \u{645}\u{632}\u{62F}\u{648}\u{62C}: \u{625}\u{639}\u{627}\u{62F}\u{629} \u{62A}\u{639}\u{64A}\u{64A}\u{646} \u{627}\u{644}\u{639}\u{631}\u{636}",
+        _ => tr(UiLanguage::EnUs, key),
     }
 }
 
@@ -4048,37 +12901,58 @@ fn color_for_app(is_idle: bool, app_name: &str) -> Color32 {
     PALETTE[(hash as usize) % PALETTE.len()]
 }
 
-fn configure_chinese_fonts(ctx: &egui::Context) {
-    let mut fonts = egui::FontDefinitions::default();
+#[cfg(bundled_fallback_font)]
+const BUNDLED_FALLBACK_FONT: Option<&[u8]> = Some(include_bytes!("../assets/fonts/NotoSansSC-Subset.otf"));
+#[cfg(not(bundled_fallback_font))]
+const BUNDLED_FALLBACK_FONT: Option<&[u8]> = None;
+
+// The custom font comes first, then a system CJK font, with the bundled subset filling
+// in when there is none; egui's defaults stay behind them for anything still missing.
+fn configure_fonts(ctx: &egui::Context, custom_font: Option<&Path>) -> Result<()> {
+    let mut chain = Vec::new();
+    if let Some(path) = custom_font {
+        chain.push(("custom", load_font_file(path)?));
+    }
     if let Some(font_bytes) = load_noto_sans_font_bytes() {
+        chain.push(("noto_sans", font_bytes));
+    } else if let Some(font_bytes) = load_chinese_font_bytes() {
+        chain.push(("zh_cn", font_bytes));
+    } else if let Some(font_bytes) = BUNDLED_FALLBACK_FONT {
+        chain.push(("bundled_noto_sans_sc", font_bytes.to_vec()));
+    }
+
+    let mut fonts = egui::FontDefinitions::default();
+    for (index, (name, font_bytes)) in chain.into_iter().enumerate() {
         fonts.font_data.insert(
-            "noto_sans".to_owned(),
+            name.to_owned(),
             egui::FontData::from_owned(font_bytes).into(),
         );
-        if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
-            family.insert(0, "noto_sans".to_owned());
-        }
-        if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Monospace) {
-            family.insert(0, "noto_sans".to_owned());
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            if let Some(family) = fonts.families.get_mut(&family) {
+                family.insert(index, name.to_owned());
+            }
         }
-        ctx.set_fonts(fonts);
-        return;
-    }
-
-    let Some(font_bytes) = load_chinese_font_bytes() else {
-        return;
-    };
-    fonts.font_data.insert(
-        "zh_cn".to_owned(),
-        egui::FontData::from_owned(font_bytes).into(),
-    );
-    if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Proportional) {
-        family.insert(0, "zh_cn".to_owned());
     }
-    if let Some(family) = fonts.families.get_mut(&egui::FontFamily::Monospace) {
-        family.insert(0, "zh_cn".to_owned());
+    // Last in line, so it only covers the Arabic script the fonts above lack.
+    if let Some(font_bytes) = load_arabic_font_bytes() {
+        fonts.font_data.insert("arabic".to_owned(), egui::FontData::from_owned(font_bytes).into());
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            if let Some(family) = fonts.families.get_mut(&family) {
+                family.push("arabic".to_owned());
+            }
+        }
     }
     ctx.set_fonts(fonts);
+    Ok(())
+}
+
+// egui only parses fonts when it first lays out text and panics on a bad one, so
+// files are checked here instead.
+fn load_font_file(path: &Path) -> Result<Vec<u8>> {
+    let font_bytes = fs::read(path).with_context(|| format!("failed to read font: {}", path.display()))?;
+    ab_glyph::FontRef::try_from_slice(&font_bytes)
+        .map_err(|_| anyhow!("not a TrueType or OpenType font: {}", path.display()))?;
+    Ok(font_bytes)
 }
 
 fn configure_interaction_style(ctx: &egui::Context) {
@@ -4143,6 +13017,30 @@ fn load_chinese_font_bytes() -> Option<Vec<u8>> {
     None
 }
 
+// Needs the Arabic presentation forms, which is what `bidi` shapes text into.
+fn load_arabic_font_bytes() -> Option<Vec<u8>> {
+    let mut candidates = Vec::new();
+
+    if let Some(windir) = env::var_os("WINDIR") {
+        let font_dir = PathBuf::from(windir).join("Fonts");
+        candidates.push(font_dir.join("segoeui.ttf"));
+        candidates.push(font_dir.join("tahoma.ttf"));
+        candidates.push(font_dir.join("arial.ttf"));
+    }
+
+    candidates.push(PathBuf::from(r"C:\Windows\Fonts\segoeui.ttf"));
+    candidates.push(PathBuf::from(r"C:\Windows\Fonts\tahoma.ttf"));
+    candidates.push(PathBuf::from("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf"));
+    candidates.push(PathBuf::from("/usr/share/fonts/TTF/DejaVuSans.ttf"));
+
+    for path in candidates {
+        if let Ok(bytes) = std::fs::read(&path) {
+            return Some(bytes);
+        }
+    }
+    None
+}
+
 #[cfg(target_os = "windows")]
 fn load_app_file_description(process_path: &str) -> Option<String> {
     if process_path.trim().is_empty() {
@@ -4245,8 +13143,8 @@ fn query_version_string_value(version_data: &[u8], query: &str) -> Option<String
 }
 
 #[cfg(not(target_os = "windows"))]
-fn load_app_file_description(_process_path: &str) -> Option<String> {
-    None
+fn load_app_file_description(process_path: &str) -> Option<String> {
+    app_visuals::load_display_name(process_path)
 }
 
 #[cfg(target_os = "windows")]
@@ -4301,8 +13199,8 @@ fn load_app_icon_image(process_path: &str) -> Option<egui::ColorImage> {
 }
 
 #[cfg(not(target_os = "windows"))]
-fn load_app_icon_image(_process_path: &str) -> Option<egui::ColorImage> {
-    None
+fn load_app_icon_image(process_path: &str) -> Option<egui::ColorImage> {
+    app_visuals::load_icon(process_path)
 }
 
 #[cfg(target_os = "windows")]
@@ -4414,15 +13312,50 @@ fn cleanup_icon_info(icon_info: &ICONINFO) {
     }
 }
 
-fn parse_db_path_from_args() -> Result<PathBuf> {
-    let mut db_path = default_db_path();
+fn parse_launch_options_from_args() -> Result<LaunchOptions> {
+    let mut db_path = relocated_db_path();
+    let mut initial_view = None;
+    let mut safe_mode = false;
+    let mut query_mode = false;
+    let mut generate_demo = false;
+    let mut demo_days = 30;
+    let mut demo_seed = 1;
     let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
         match arg.as_str() {
+            "query" => query_mode = true,
+            "generate-demo" => generate_demo = true,
+            "--days" => {
+                let value = args.next().context("missing value for --days")?;
+                demo_days = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|days| (1..=3660).contains(days))
+                    .with_context(|| format!("invalid value for --days (1-3660): {value}"))?;
+            }
+            "--seed" => {
+                let value = args.next().context("missing value for --seed")?;
+                demo_seed = value
+                    .parse::<u64>()
+                    .with_context(|| format!("invalid value for --seed: {value}"))?;
+            }
             "--db" => {
                 let value = args.next().context("missing value for --db")?;
                 db_path = PathBuf::from(value);
             }
+            "--date" => {
+                let value = args.next().context("missing value for --date")?;
+                let date = parse_cli_date(&value)
+                    .with_context(|| format!("invalid value for --date: {value}"))?;
+                initial_view = Some(InitialView::Date(date));
+            }
+            "--range" => {
+                let value = args.next().context("missing value for --range")?;
+                let view = parse_cli_range(&value)
+                    .with_context(|| format!("invalid value for --range: {value}"))?;
+                initial_view = Some(view);
+            }
+            "--safe-mode" => safe_mode = true,
             "--help" | "-h" => {
                 print_help();
                 std::process::exit(0);
@@ -4430,7 +13363,395 @@ fn parse_db_path_from_args() -> Result<PathBuf> {
             _ => return Err(anyhow!("unknown argument: {arg}")),
         }
     }
-    Ok(db_path)
+    Ok(LaunchOptions {
+        db_path,
+        initial_view,
+        safe_mode,
+        query_mode,
+        generate_demo,
+        demo_days,
+        demo_seed,
+    })
+}
+
+fn parse_cli_date(value: &str) -> Result<NaiveDate> {
+    parse_date_input(value, Local::now().date_naive())
+        .context("expected YYYY-MM-DD, M/D, today, yesterday or last <weekday>")
+}
+
+fn parse_date_input(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let text = input.trim().to_lowercase();
+    if text.is_empty() {
+        return None;
+    }
+    match text.as_str() {
+        "today" | "\u{4ECA}\u{5929}" => return Some(today),
+        "yesterday" | "\u{6628}\u{5929}" => return today.checked_sub_days(Days::new(1)),
+        "\u{524D}\u{5929}" => return today.checked_sub_days(Days::new(2)),
+        _ => {}
+    }
+
+    if let Some(days) = text
+        .strip_suffix("days ago")
+        .or_else(|| text.strip_suffix("day ago"))
+        .and_then(|rest| rest.trim().parse::<u64>().ok())
+    {
+        return today.checked_sub_days(Days::new(days));
+    }
+
+    let (strictly_before, weekday_text) = match text.strip_prefix("last ") {
+        Some(rest) => (true, rest.trim()),
+        None => (false, text.as_str()),
+    };
+    if let Some(weekday) = parse_weekday_name(weekday_text) {
+        let today_index = i64::from(today.weekday().num_days_from_monday());
+        let target_index = i64::from(weekday.num_days_from_monday());
+        let mut back = (today_index - target_index).rem_euclid(7);
+        if back == 0 && strictly_before {
+            back = 7;
+        }
+        return today.checked_sub_days(Days::new(back as u64));
+    }
+    if strictly_before {
+        return None;
+    }
+
+    let parts: Vec<&str> = text.split(['-', '/', '.']).map(str::trim).collect();
+    if parts.iter().any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    let number = |index: usize| parts[index].parse::<u32>().ok();
+    match parts.len() {
+        2 => NaiveDate::from_ymd_opt(today.year(), number(0)?, number(1)?),
+        3 if parts[0].len() == 4 => {
+            NaiveDate::from_ymd_opt(parts[0].parse().ok()?, number(1)?, number(2)?)
+        }
+        3 if parts[2].len() == 4 => {
+            NaiveDate::from_ymd_opt(parts[2].parse().ok()?, number(0)?, number(1)?)
+        }
+        _ => None,
+    }
+}
+
+fn parse_weekday_name(text: &str) -> Option<Weekday> {
+    let weekday = match text {
+        "monday" | "mon" => Weekday::Mon,
+        "tuesday" | "tue" | "tues" => Weekday::Tue,
+        "wednesday" | "wed" => Weekday::Wed,
+        "thursday" | "thu" | "thur" | "thurs" => Weekday::Thu,
+        "friday" | "fri" => Weekday::Fri,
+        "saturday" | "sat" => Weekday::Sat,
+        "sunday" | "sun" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(weekday)
+}
+
+fn parse_cli_range(value: &str) -> Result<InitialView> {
+    if let Some((start, end)) = value.split_once("..") {
+        let start = parse_cli_date(start)?;
+        let end = parse_cli_date(end)?;
+        if end < start {
+            bail!("range end is before range start");
+        }
+        return Ok(InitialView::Custom(start, end));
+    }
+    if let Some(preset) = RangePreset::from_code(value) {
+        return Ok(InitialView::Preset(preset));
+    }
+    let codes: Vec<&str> = RangePreset::ALL.iter().map(|preset| preset.code()).collect();
+    bail!("expected <start>..<end> or one of: {}", codes.join(", "))
+}
+
+fn backup_database_to(db_path: &Path, output_path: &Path) -> Result<()> {
+    let source = connection::open_read_only(db_path)?;
+    let mut destination = Connection::open(output_path)
+        .with_context(|| format!("failed to create backup file: {}", output_path.display()))?;
+
+    let backup = Backup::new(&source, &mut destination).context("failed to initialize SQLite backup")?;
+    backup
+        .run_to_completion(128, Duration::from_millis(20), None)
+        .context("failed to complete SQLite backup")?;
+    Ok(())
+}
+
+fn write_database_snapshot(db_path: &Path, snapshot_dir: &Path) -> Result<PathBuf> {
+    fs::create_dir_all(snapshot_dir)
+        .with_context(|| format!("failed to create snapshot directory: {}", snapshot_dir.display()))?;
+    let filename = format!("tracker_snapshot_{}.db", Local::now().format("%Y%m%d_%H%M%S"));
+    // Write under a temporary name so a sync client never uploads a half-written file.
+    let partial_path = snapshot_dir.join(format!("{filename}.partial"));
+    let output_path = snapshot_dir.join(filename);
+    backup_database_to(db_path, &partial_path)?;
+    fs::rename(&partial_path, &output_path)
+        .with_context(|| format!("failed to finalize snapshot: {}", output_path.display()))?;
+
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(snapshot_dir)
+        .with_context(|| format!("failed to list snapshot directory: {}", snapshot_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("tracker_snapshot_") && name.ends_with(".db"))
+        })
+        .collect();
+    snapshots.sort();
+    let excess = snapshots.len().saturating_sub(SNAPSHOT_RETAIN_COUNT);
+    for old in snapshots.into_iter().take(excess) {
+        let _ = fs::remove_file(old);
+    }
+    Ok(output_path)
+}
+
+/// Daily category totals only: no app names, titles, paths or timestamps finer than
+/// a day leave the machine. `.db`/`.sqlite` paths get a SQLite file, anything else JSON.
+fn write_team_export(db_path: &Path, output_path: &Path, categories: &CategorySnapshot, days: u32) -> Result<()> {
+    let conn = connection::open_read_only(db_path)?;
+    let today = Local::now().date_naive();
+    let mut rows = Vec::new();
+    for offset in (0..u64::from(days)).rev() {
+        let Some(date) = today.checked_sub_days(Days::new(offset)) else {
+            continue;
+        };
+        let Some((day_start, day_end)) = date
+            .checked_add_days(Days::new(1))
+            .and_then(|next| date_range_bounds(date, next))
+        else {
+            continue;
+        };
+        let mut totals = HashMap::new();
+        for_each_segment_in_range(&conn, day_start, day_end, |seg| {
+            if !is_system_level_app(&seg.app_name, seg.process_path.as_deref()) {
+                add_category_duration(&mut totals, categories, &seg, day_start, day_end);
+            }
+        })?;
+        rows.push((date, sorted_category_totals(totals)));
+    }
+
+    if let Some(parent) = output_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory: {}", parent.display()))?;
+    }
+    let file_name = output_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .context("team export path has no file name")?;
+    // Written under a temporary name so a sync client never picks up a partial file.
+    let partial_path = output_path.with_file_name(format!("{file_name}.partial"));
+    let _ = fs::remove_file(&partial_path);
+    let category_code = |category: Option<AppCategory>| category.map_or("uncategorized", AppCategory::code);
+    let is_sqlite = output_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("db") || ext.eq_ignore_ascii_case("sqlite"));
+    if is_sqlite {
+        let mut out = Connection::open(&partial_path)
+            .with_context(|| format!("failed to create team export: {}", partial_path.display()))?;
+        let tx = out.transaction().context("failed to start team export transaction")?;
+        tx.execute_batch(
+            "\
+            CREATE TABLE daily_category_totals (
+              date TEXT NOT NULL,
+              category TEXT NOT NULL,
+              secs INTEGER NOT NULL,
+              PRIMARY KEY(date, category)
+            );
+            CREATE TABLE export_meta (generated_ts INTEGER NOT NULL);",
+        )
+        .context("failed to create team export tables")?;
+        tx.execute("INSERT INTO export_meta (generated_ts) VALUES (?1)", params![unix_seconds_now()])
+            .context("failed to write team export metadata")?;
+        {
+            let mut insert = tx
+                .prepare("INSERT INTO daily_category_totals (date, category, secs) VALUES (?1, ?2, ?3)")
+                .context("failed to prepare team export insert")?;
+            for (date, totals) in &rows {
+                for (category, secs) in totals {
+                    insert
+                        .execute(params![date.format("%Y-%m-%d").to_string(), category_code(*category), secs])
+                        .context("failed to write team export row")?;
+                }
+            }
+        }
+        tx.commit().context("failed to commit team export")?;
+    } else {
+        let days: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|(date, totals)| {
+                let categories: serde_json::Map<String, serde_json::Value> = totals
+                    .iter()
+                    .map(|(category, secs)| (category_code(*category).to_owned(), json!(secs)))
+                    .collect();
+                json!({
+                    "date": date.format("%Y-%m-%d").to_string(),
+                    "active_secs": totals.iter().map(|(_, secs)| secs).sum::<i64>(),
+                    "categories": categories,
+                })
+            })
+            .collect();
+        let file = File::create(&partial_path)
+            .with_context(|| format!("failed to create team export: {}", partial_path.display()))?;
+        serde_json::to_writer_pretty(
+            BufWriter::new(file),
+            &json!({ "generated_ts": unix_seconds_now(), "days": days }),
+        )
+        .context("failed to write team export")?;
+    }
+    fs::rename(&partial_path, output_path)
+        .with_context(|| format!("failed to finalize team export: {}", output_path.display()))
+}
+
+fn detect_sync_folder(path: &Path) -> Option<&'static str> {
+    let normalized = path.to_string_lossy().replace('/', "\\").to_ascii_lowercase();
+    for var in ["OneDrive", "OneDriveConsumer", "OneDriveCommercial"] {
+        if let Some(root) = env::var_os(var) {
+            let root = root.to_string_lossy().replace('/', "\\").to_ascii_lowercase();
+            if !root.is_empty() && normalized.starts_with(&root) {
+                return Some("OneDrive");
+            }
+        }
+    }
+    for component in normalized.split('\\') {
+        let provider = if component.starts_with("onedrive") {
+            "OneDrive"
+        } else if component.starts_with("dropbox") {
+            "Dropbox"
+        } else if component == "google drive" || component == "googledrive" || component == "my drive" {
+            "Google Drive"
+        } else if component == "icloud drive" || component == "iclouddrive" {
+            "iCloud Drive"
+        } else if component == "box" || component == "box sync" {
+            "Box"
+        } else {
+            continue;
+        };
+        return Some(provider);
+    }
+    None
+}
+
+const JSON_RPC_PARSE_ERROR: i64 = -32700;
+const JSON_RPC_INVALID_REQUEST: i64 = -32600;
+const JSON_RPC_METHOD_NOT_FOUND: i64 = -32601;
+const JSON_RPC_INVALID_PARAMS: i64 = -32602;
+const JSON_RPC_INTERNAL_ERROR: i64 = -32603;
+
+/// `limetrace query`: line-delimited JSON-RPC 2.0 over stdin/stdout for scripts
+/// and launcher plugins. Runs until stdin closes.
+fn run_query_server(db_path: &Path) -> Result<()> {
+    let conn = connection::open_read_only(db_path)?;
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
+    for line in std::io::BufRead::lines(stdin.lock()) {
+        let line = line.context("failed to read query request")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = handle_query_request(&conn, db_path, &line);
+        serde_json::to_writer(&mut stdout, &response).context("failed to encode query response")?;
+        stdout
+            .write_all(b"\n")
+            .and_then(|_| stdout.flush())
+            .context("failed to write query response")?;
+    }
+    Ok(())
+}
+
+fn handle_query_request(conn: &Connection, db_path: &Path, line: &str) -> serde_json::Value {
+    let request: serde_json::Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => return json_rpc_error(serde_json::Value::Null, JSON_RPC_PARSE_ERROR, &err.to_string()),
+    };
+    let id = request.get("id").cloned().unwrap_or(serde_json::Value::Null);
+    let Some(method) = request.get("method").and_then(serde_json::Value::as_str) else {
+        return json_rpc_error(id, JSON_RPC_INVALID_REQUEST, "missing method");
+    };
+    let params = request.get("params").cloned().unwrap_or(serde_json::Value::Null);
+    let result = match method {
+        "today_totals" => {
+            let date = match params.get("date").and_then(serde_json::Value::as_str) {
+                Some(value) => match parse_cli_date(value) {
+                    Ok(date) => date,
+                    Err(err) => return json_rpc_error(id, JSON_RPC_INVALID_PARAMS, &format!("{err:#}")),
+                },
+                None => Local::now().date_naive(),
+            };
+            query_day_totals(conn, date)
+        }
+        "current_activity" => query_current_activity(db_path),
+        _ => return json_rpc_error(id, JSON_RPC_METHOD_NOT_FOUND, &format!("unknown method: {method}")),
+    };
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => json_rpc_error(id, JSON_RPC_INTERNAL_ERROR, &format!("{err:#}")),
+    }
+}
+
+fn json_rpc_error(id: serde_json::Value, code: i64, message: &str) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn query_day_totals(conn: &Connection, date: NaiveDate) -> Result<serde_json::Value> {
+    let (range_start, range_end) = date
+        .checked_add_days(Days::new(1))
+        .and_then(|end| date_range_bounds(date, end))
+        .context("failed to resolve day range")?;
+    let mut summary = SummaryAccumulator::new(range_start, range_end, 0, load_app_file_description);
+    for_each_segment_in_range(conn, range_start, range_end, |seg| summary.push(&seg))
+        .context("failed to query segments")?;
+    let rows = summary.finish();
+    let idle_secs: i64 = rows.iter().filter(|row| row.is_idle).map(|row| row.duration_secs).sum();
+    let apps: Vec<serde_json::Value> = rows
+        .iter()
+        .filter(|row| !row.is_idle)
+        .map(|row| {
+            json!({
+                "app": row.display_name,
+                "process_path": row.process_path,
+                "secs": row.duration_secs,
+                "sessions": row.session_count,
+                "last_used_ts": row.last_used_ts,
+            })
+        })
+        .collect();
+    let active_secs: i64 = rows.iter().filter(|row| !row.is_idle).map(|row| row.duration_secs).sum();
+    Ok(json!({
+        "date": date.format("%Y-%m-%d").to_string(),
+        "start_ts": range_start,
+        "end_ts": range_end,
+        "active_secs": active_secs,
+        "idle_secs": idle_secs,
+        "apps": apps,
+    }))
+}
+
+fn query_current_activity(db_path: &Path) -> Result<serde_json::Value> {
+    let status = probe_backend_status(&ConnectionPool::new(db_path))?;
+    let live = status.live_segment.as_ref();
+    Ok(json!({
+        "tracking": status.health == BackendHealth::Running,
+        "last_write_ts": status.last_write_ts,
+        "checked_ts": status.checked_ts,
+        "app": live.map(|seg| seg.app_name.as_str()),
+        "process_path": live.and_then(|seg| seg.process_path.as_deref()),
+        "title": live.and_then(|seg| seg.title.as_deref()),
+        "is_idle": live.map(|seg| seg.is_idle),
+        "since_ts": live.map(|seg| seg.start_ts),
+    }))
+}
+
+// Where the database was moved to through the backup window, if it was.
+fn relocated_db_path() -> PathBuf {
+    let default = default_db_path();
+    load_settings(&settings_path_for_db(&default))
+        .get("db_path")
+        .and_then(serde_json::Value::as_str)
+        .map(PathBuf::from)
+        .unwrap_or(default)
 }
 
 fn default_db_path() -> PathBuf {
@@ -4439,6 +13760,21 @@ fn default_db_path() -> PathBuf {
             .join("LimeTrace")
             .join("tracker.db");
     }
+    // Matches the backend's defaults.
+    #[cfg(target_os = "macos")]
+    if let Some(home) = env::var_os("HOME") {
+        return PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+            .join("LimeTrace")
+            .join("tracker.db");
+    }
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")));
+    if let Some(data_home) = data_home {
+        return data_home.join("LimeTrace").join("tracker.db");
+    }
     PathBuf::from("data").join("tracker.db")
 }
 
@@ -4448,19 +13784,116 @@ fn print_help() {
 LimeTrace
 
 Usage:
-  limetrace [--db <path>]
+  limetrace [--db <path>] [--date <date> | --range <range>] [--safe-mode]
+  limetrace query [--db <path>]
+  limetrace generate-demo --db <path> [--days <n>] [--seed <n>]
 
 Options:
-  --db         SQLite file path (default: %LOCALAPPDATA%\\LimeTrace\\tracker.db)
-  -h, --help   Print this help"
+  --db         SQLite file path (default: %LOCALAPPDATA%\\LimeTrace\\tracker.db,
+               $XDG_DATA_HOME/LimeTrace/tracker.db on Linux,
+               ~/Library/Application Support/LimeTrace/tracker.db on macOS)
+  --date       Open at a specific day: YYYY-MM-DD, M/D, today, yesterday,
+               last <weekday>
+  --range      Open at a range: <start>..<end> (dates as for --date) or
+               all, 7d, 30d, this-week, this-month, this-quarter, ytd;
+               a viewer that is already open switches to it instead
+  --safe-mode  Open with default settings, no icon cache and no custom fonts,
+               to recover from a corrupt settings file or cache; chosen
+               automatically after two startups in a row that never finished
+  -h, --help   Print this help
+
+Query mode reads one JSON-RPC 2.0 request per line from stdin and writes one
+response per line to stdout. Methods:
+  today_totals       active/idle seconds and per-app totals; optional
+                     params {{\"date\": <date>}} (as for --date)
+  current_activity   backend state and the app currently being tracked
+
+generate-demo fills a new or empty database with <n> days (default 30) of
+synthetic activity ending now, for demos, screenshots and performance runs.
+The same seed always gives the same data."
     );
 }
 
 fn main() -> Result<()> {
-    let db_path = parse_db_path_from_args()?;
+    let LaunchOptions {
+        db_path,
+        initial_view,
+        safe_mode,
+        query_mode,
+        generate_demo,
+        demo_days,
+        demo_seed,
+    } = parse_launch_options_from_args()?;
+    crash::install(crash::logs_dir(&db_path), "limetrace", env!("CARGO_PKG_VERSION"));
+    if query_mode {
+        return run_query_server(&db_path);
+    }
+    if generate_demo {
+        if let Some(parent) = db_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let mut conn = Connection::open(&db_path)
+            .with_context(|| format!("failed to open database: {}", db_path.display()))?;
+        let stats = demo::generate(&mut conn, demo_days, demo_seed)?;
+        println!(
+            "wrote {} segments covering {} days to {}",
+            stats.segments,
+            stats.days,
+            db_path.display()
+        );
+        return Ok(());
+    }
+    let instance_guard = match acquire_viewer_instance_guard() {
+        Ok(Some(guard)) => Some(guard),
+        Ok(None) => {
+            let message = match initial_view {
+                Some(view) => json!({ "view": view.cli_value() }),
+                None => json!({}),
+            };
+            if let Err(err) = hand_off_to_running_viewer(&message) {
+                eprintln!("LimeTrace is already running but could not be brought forward: {err:#}");
+            }
+            return Ok(());
+        }
+        Err(err) => {
+            // Failing to create the mutex should never keep the viewer from opening.
+            eprintln!("single-instance guard error: {err:#}");
+            None
+        }
+    };
+    if let (Ok(current_exe), Some(public_key)) = (env::current_exe(), update::UPDATE_PUBLIC_KEY) {
+        update::remove_replaced(&current_exe);
+        let staging_dir = update::staging_dir(&db_path);
+        match update::apply_staged(&staging_dir, &current_exe, env!("CARGO_PKG_VERSION"), public_key) {
+            Ok(Some(version)) => {
+                eprintln!("updated to {version}; restarting");
+                // Releases the single-instance mutex on Windows so the new viewer can take it.
+                #[allow(clippy::drop_non_drop)]
+                drop(instance_guard);
+                return update::relaunch(&current_exe);
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!("update failed, keeping the current version: {err:#}"),
+        }
+    }
+    let viewer_handoff_rx = instance_guard.is_some().then(spawn_viewer_handoff_listener);
+    let startup_marker = crash::logs_dir(&db_path).join(STARTUP_MARKER_FILE);
+    let safe_mode = if begin_startup_attempt(&startup_marker) {
+        SafeMode::AfterStartupCrashes
+    } else if safe_mode {
+        SafeMode::Requested
+    } else {
+        SafeMode::Off
+    };
+    let window_size = match safe_mode {
+        SafeMode::Off => load_window_size(&load_settings(&settings_path_for_db(&db_path))),
+        _ => None,
+    }
+    .unwrap_or(DEFAULT_WINDOW_SIZE);
     let mut viewport = egui::ViewportBuilder::default()
-        .with_inner_size([1280.0, 720.0])
-        .with_min_inner_size([980.0, 640.0]);
+        .with_inner_size(window_size)
+        .with_min_inner_size(MIN_WINDOW_SIZE);
     if let Ok(icon) = eframe::icon_data::from_png_bytes(APP_ICON_PNG) {
         viewport = viewport.with_icon(icon);
     }
@@ -4469,13 +13902,17 @@ fn main() -> Result<()> {
         ..Default::default()
     };
     eframe::run_native(
-        "LimeTrace",
+        VIEWER_WINDOW_TITLE,
         native_options,
         Box::new(move |cc| {
-            configure_chinese_fonts(&cc.egui_ctx);
             configure_interaction_style(&cc.egui_ctx);
-            cc.egui_ctx.set_theme(egui::ThemePreference::Light);
-            Ok(Box::new(TimelineApp::new(db_path.clone())))
+            let mut app = TimelineApp::new(db_path.clone(), initial_view, safe_mode, startup_marker);
+            app.viewer_handoff_rx = viewer_handoff_rx;
+            if safe_mode == SafeMode::Off {
+                app.apply_font(&cc.egui_ctx);
+            }
+            cc.egui_ctx.set_theme(app.theme_choice.preference());
+            Ok(Box::new(app))
         }),
     )
     .map_err(|err| anyhow!("failed to start LimeTrace: {err}"))
@@ -0,0 +1,434 @@
+//! Optional remote storage (S3-compatible buckets, WebDAV collections) for the
+//! folder-sync batches and scheduled snapshots.
+//!
+//! Every object is encrypted before it leaves the machine, under a passphrase that
+//! never goes to the server: each file gets its own key, derived with Argon2id from
+//! the passphrase and a random salt stored in the file, and is sealed with
+//! ChaCha20-Poly1305 under a random nonce, which also authenticates it. Transfers
+//! go through curl, which also signs the S3 requests (`--aws-sigv4`).
+
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const ENCRYPTED_MAGIC: &[u8; 4] = b"LTE2";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+// Keeps the objects out of the way when the bucket is shared with other data.
+const S3_KEY_PREFIX: &str = "limetrace/";
+
+static UPLOAD_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteKind {
+    S3,
+    WebDav,
+}
+
+impl RemoteKind {
+    pub fn code(self) -> &'static str {
+        match self {
+            RemoteKind::S3 => "s3",
+            RemoteKind::WebDav => "webdav",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "s3" => Some(RemoteKind::S3),
+            "webdav" => Some(RemoteKind::WebDav),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RemoteKind::S3 => "S3",
+            RemoteKind::WebDav => "WebDAV",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteConfig {
+    pub kind: RemoteKind,
+    /// Bucket URL for S3 (`https://bucket.s3.region.amazonaws.com` or path-style),
+    /// collection URL for WebDAV.
+    pub endpoint: String,
+    pub region: String,
+    pub user: String,
+    pub secret: String,
+    pub passphrase: String,
+}
+
+/// A flat namespace of objects; names are plain file names without slashes.
+pub trait RemoteStore {
+    fn list(&self) -> Result<Vec<String>>;
+    fn get(&self, name: &str) -> Result<Vec<u8>>;
+    fn put(&self, name: &str, data: &[u8]) -> Result<()>;
+}
+
+pub fn open(config: &RemoteConfig) -> Result<Box<dyn RemoteStore>> {
+    if config.passphrase.is_empty() {
+        bail!("remote storage needs an encryption passphrase");
+    }
+    let endpoint = config.endpoint.trim().trim_end_matches('/').to_owned();
+    if !endpoint.starts_with("https://") && !endpoint.starts_with("http://") {
+        bail!("remote endpoint must be an http(s) URL");
+    }
+    Ok(match config.kind {
+        RemoteKind::S3 => Box::new(S3Store {
+            endpoint,
+            region: config.region.trim().to_owned(),
+            user: config.user.clone(),
+            secret: config.secret.clone(),
+        }),
+        RemoteKind::WebDav => Box::new(WebDavStore {
+            endpoint,
+            user: config.user.clone(),
+            secret: config.secret.clone(),
+        }),
+    })
+}
+
+/// Downloads batches other machines pushed into the local exchange folder.
+pub fn pull_batches(
+    store: &dyn RemoteStore,
+    cipher: &Cipher,
+    remote_names: &[String],
+    folder: &Path,
+    machine_id: &str,
+) -> Result<usize> {
+    let mut pulled = 0;
+    for name in remote_names {
+        let Some((machine, seq)) = parse_batch_name(name) else {
+            continue;
+        };
+        if machine == machine_id {
+            continue;
+        }
+        let local_path = folder.join(machine).join(format!("{seq:08}.batch"));
+        if local_path.exists() {
+            continue;
+        }
+        let data = cipher
+            .decrypt(&store.get(name)?)
+            .with_context(|| format!("failed to decrypt {name}"))?;
+        let machine_dir = folder.join(machine);
+        fs::create_dir_all(&machine_dir)
+            .with_context(|| format!("failed to create sync directory: {}", machine_dir.display()))?;
+        let partial_path = machine_dir.join(format!("{seq:08}.batch.partial"));
+        fs::write(&partial_path, data)
+            .with_context(|| format!("failed to write sync batch: {}", partial_path.display()))?;
+        fs::rename(&partial_path, &local_path)
+            .with_context(|| format!("failed to finalize sync batch: {}", local_path.display()))?;
+        pulled += 1;
+    }
+    Ok(pulled)
+}
+
+/// Uploads this machine's batches the remote doesn't have yet.
+pub fn push_batches(
+    store: &dyn RemoteStore,
+    cipher: &Cipher,
+    remote_names: &[String],
+    folder: &Path,
+    machine_id: &str,
+) -> Result<usize> {
+    let machine_dir = folder.join(machine_id);
+    if !machine_dir.is_dir() {
+        return Ok(0);
+    }
+    let remote_names: HashSet<&str> = remote_names.iter().map(String::as_str).collect();
+    let mut pushed = 0;
+    let entries = fs::read_dir(&machine_dir)
+        .with_context(|| format!("failed to list sync directory: {}", machine_dir.display()))?;
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("batch") {
+            continue;
+        }
+        let Some(seq) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<i64>().ok())
+        else {
+            continue;
+        };
+        let name = batch_name(machine_id, seq);
+        if remote_names.contains(name.as_str()) {
+            continue;
+        }
+        let data = fs::read(&path).with_context(|| format!("failed to read sync batch: {}", path.display()))?;
+        store.put(&name, &cipher.encrypt(&data)?)?;
+        pushed += 1;
+    }
+    Ok(pushed)
+}
+
+pub fn push_snapshot(config: &RemoteConfig, snapshot_path: &Path) -> Result<String> {
+    let store = open(config)?;
+    let file_name = snapshot_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .context("snapshot has no file name")?;
+    let name = format!("snapshot.{file_name}.enc");
+    let data = fs::read(snapshot_path)
+        .with_context(|| format!("failed to read snapshot: {}", snapshot_path.display()))?;
+    store.put(&name, &Cipher::new(&config.passphrase).encrypt(&data)?)?;
+    Ok(name)
+}
+
+fn batch_name(machine_id: &str, seq: i64) -> String {
+    format!("batch.{machine_id}.{seq:08}.enc")
+}
+
+fn parse_batch_name(name: &str) -> Option<(&str, i64)> {
+    let rest = name.strip_prefix("batch.")?.strip_suffix(".enc")?;
+    let (machine, seq) = rest.split_once('.')?;
+    if machine.is_empty() || machine.contains(['/', '\\']) {
+        return None;
+    }
+    Some((machine, seq.parse().ok()?))
+}
+
+struct S3Store {
+    endpoint: String,
+    region: String,
+    user: String,
+    secret: String,
+}
+
+impl S3Store {
+    fn sigv4(&self) -> String {
+        let region = if self.region.is_empty() { "us-east-1" } else { &self.region };
+        format!("aws:amz:{region}:s3")
+    }
+}
+
+impl RemoteStore for S3Store {
+    fn list(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let mut continuation: Option<String> = None;
+        loop {
+            let mut url = format!("{}?list-type=2&prefix={S3_KEY_PREFIX}", self.endpoint);
+            if let Some(token) = &continuation {
+                url.push_str("&continuation-token=");
+                url.push_str(&percent_encode(token));
+            }
+            let body = run_curl(&self.user, &self.secret, &["--aws-sigv4", &self.sigv4(), &url], None)?;
+            let body = String::from_utf8_lossy(&body);
+            names.extend(
+                xml_values(&body, "Key")?
+                    .into_iter()
+                    .filter_map(|key| key.strip_prefix(S3_KEY_PREFIX).map(str::to_owned)),
+            );
+            continuation = xml_values(&body, "NextContinuationToken")?.into_iter().next();
+            if continuation.is_none() {
+                return Ok(names);
+            }
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/{S3_KEY_PREFIX}{name}", self.endpoint);
+        run_curl(&self.user, &self.secret, &["--aws-sigv4", &self.sigv4(), &url], None)
+    }
+
+    fn put(&self, name: &str, data: &[u8]) -> Result<()> {
+        let url = format!("{}/{S3_KEY_PREFIX}{name}", self.endpoint);
+        run_curl(&self.user, &self.secret, &["--aws-sigv4", &self.sigv4(), &url], Some(data)).map(|_| ())
+    }
+}
+
+struct WebDavStore {
+    endpoint: String,
+    user: String,
+    secret: String,
+}
+
+impl RemoteStore for WebDavStore {
+    fn list(&self) -> Result<Vec<String>> {
+        let url = format!("{}/", self.endpoint);
+        let body = run_curl(
+            &self.user,
+            &self.secret,
+            &["--request", "PROPFIND", "--header", "Depth: 1", &url],
+            None,
+        )?;
+        let body = String::from_utf8_lossy(&body);
+        Ok(xml_values(&body, "href")?
+            .into_iter()
+            .filter(|href| !href.ends_with('/'))
+            .filter_map(|href| href.rsplit('/').next().map(str::to_owned))
+            .collect())
+    }
+
+    fn get(&self, name: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/{name}", self.endpoint);
+        run_curl(&self.user, &self.secret, &[&url], None)
+    }
+
+    fn put(&self, name: &str, data: &[u8]) -> Result<()> {
+        let url = format!("{}/{name}", self.endpoint);
+        run_curl(&self.user, &self.secret, &[&url], Some(data)).map(|_| ())
+    }
+}
+
+// Credentials go to curl through a stdin config file so they stay out of the process
+// list; uploads are staged in a temp file because stdin is taken.
+fn run_curl(user: &str, secret: &str, args: &[&str], upload: Option<&[u8]>) -> Result<Vec<u8>> {
+    let staged = match upload {
+        Some(data) => {
+            let path = std::env::temp_dir().join(format!(
+                "limetrace-upload-{}-{}.tmp",
+                std::process::id(),
+                UPLOAD_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::write(&path, data).with_context(|| format!("failed to stage upload: {}", path.display()))?;
+            Some(path)
+        }
+        None => None,
+    };
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--fail", "--max-time", "120", "--config", "-"])
+        .args(args);
+    if let Some(path) = &staged {
+        command.arg("--upload-file").arg(path);
+    }
+    let result = (|| {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("failed to run curl")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            if !user.is_empty() || !secret.is_empty() {
+                let credentials = format!("{user}:{secret}").replace('\\', "\\\\").replace('"', "\\\"");
+                writeln!(stdin, "user = \"{credentials}\"").context("failed to pass credentials to curl")?;
+            }
+        }
+        let output = child.wait_with_output().context("failed to wait for curl")?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(output.stdout)
+    })();
+    if let Some(path) = staged {
+        let _ = fs::remove_file(path);
+    }
+    result
+}
+
+// Element text by local name, whatever the namespace prefix; enough for the S3 and
+// WebDAV listings.
+fn xml_values(xml: &str, local_name: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(xml);
+    let mut values = Vec::new();
+    let mut current: Option<String> = None;
+    loop {
+        match reader.read_event().context("invalid XML listing")? {
+            Event::Start(start) if start.local_name().as_ref() == local_name.as_bytes() => {
+                current = Some(String::new());
+            }
+            Event::Text(text) => {
+                if let Some(value) = &mut current {
+                    value.push_str(&text.decode()?);
+                }
+            }
+            Event::GeneralRef(reference) => {
+                if let Some(value) = &mut current {
+                    match reference.resolve_char_ref()? {
+                        Some(ch) => value.push(ch),
+                        None => {
+                            let name = reference.decode()?;
+                            value.push_str(
+                                resolve_predefined_entity(&name)
+                                    .with_context(|| format!("unknown XML entity &{name};"))?,
+                            );
+                        }
+                    }
+                }
+            }
+            Event::End(end) if end.local_name().as_ref() == local_name.as_bytes() => {
+                if let Some(value) = current.take() {
+                    values.push(value.trim().to_owned());
+                }
+            }
+            Event::Eof => return Ok(values),
+            _ => {}
+        }
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+pub struct Cipher {
+    passphrase: String,
+}
+
+impl Cipher {
+    pub fn new(passphrase: &str) -> Self {
+        Self {
+            passphrase: passphrase.to_owned(),
+        }
+    }
+
+    fn file_key(&self, salt: &[u8]) -> Result<Key> {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut key)
+            .map_err(|err| anyhow!("failed to derive file key: {err}"))?;
+        Ok(key)
+    }
+
+    /// `LTE2`, salt and nonce, then the ciphertext and its tag; the header is
+    /// authenticated along with the body.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::getrandom(&mut salt)
+            .and_then(|()| getrandom::getrandom(&mut nonce))
+            .map_err(|err| anyhow!("failed to generate salt and nonce: {err}"))?;
+        let mut output = ENCRYPTED_MAGIC.to_vec();
+        output.extend_from_slice(&salt);
+        output.extend_from_slice(&nonce);
+        let ciphertext = ChaCha20Poly1305::new(&self.file_key(&salt)?)
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &output })
+            .map_err(|_| anyhow!("failed to encrypt"))?;
+        output.extend_from_slice(&ciphertext);
+        Ok(output)
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let header_len = ENCRYPTED_MAGIC.len() + SALT_LEN + NONCE_LEN;
+        if data.len() < header_len + TAG_LEN || !data.starts_with(ENCRYPTED_MAGIC) {
+            bail!("not a LimeTrace encrypted file");
+        }
+        let (header, ciphertext) = data.split_at(header_len);
+        let (salt, nonce) = header[ENCRYPTED_MAGIC.len()..].split_at(SALT_LEN);
+        ChaCha20Poly1305::new(&self.file_key(salt)?)
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: header })
+            .map_err(|_| anyhow!("wrong passphrase or corrupted file"))
+    }
+}
@@ -0,0 +1,102 @@
+//! The optional auto-update: looks for a newer GitHub release, downloads both
+//! binaries for this platform through curl and hands them to
+//! `limetrace_core::update`, which checks the signed checksums and stages them.
+
+use anyhow::{bail, Context, Result};
+use limetrace_core::update;
+use std::env::consts::{ARCH, EXE_SUFFIX, OS};
+use std::path::Path;
+use std::process::Command;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/Jethuit/LimeTrace/releases?per_page=20";
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+const SIGNATURE_ASSET: &str = "SHA256SUMS.sig";
+const BINARIES: [&str; 2] = ["limetrace", "limetrace-backend"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateChannel {
+    Stable,
+    Prerelease,
+}
+
+impl UpdateChannel {
+    pub const ALL: [Self; 2] = [Self::Stable, Self::Prerelease];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Prerelease => "prerelease",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|channel| channel.code() == code)
+    }
+}
+
+/// Stages the newest release on `channel` if it is newer than this build and returns
+/// its version; `None` when already up to date.
+pub fn check_and_stage(channel: UpdateChannel, staging_dir: &Path) -> Result<Option<String>> {
+    let current = env!("CARGO_PKG_VERSION");
+    let releases: serde_json::Value =
+        serde_json::from_slice(&download(RELEASES_URL)?).context("invalid release list")?;
+    let newest = releases
+        .as_array()
+        .context("invalid release list")?
+        .iter()
+        .filter(|release| !release["draft"].as_bool().unwrap_or(true))
+        .filter(|release| channel == UpdateChannel::Prerelease || !release["prerelease"].as_bool().unwrap_or(true))
+        .filter_map(|release| Some((release["tag_name"].as_str()?.trim_start_matches('v'), release)))
+        .filter(|(version, _)| update::is_newer(version, current))
+        .reduce(|newest, candidate| if update::is_newer(candidate.0, newest.0) { candidate } else { newest });
+    let Some((version, release)) = newest else {
+        return Ok(None);
+    };
+    if update::staged(staging_dir).is_some_and(|(staged, _)| staged == version) {
+        return Ok(Some(version.to_owned()));
+    }
+    let Some(public_key) = update::UPDATE_PUBLIC_KEY else {
+        bail!("LimeTrace {version} is available, but this build can't verify updates; install it manually");
+    };
+
+    let asset = |name: &str| -> Result<Vec<u8>> {
+        let url = release["assets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|asset| asset["name"].as_str() == Some(name))
+            .and_then(|asset| asset["browser_download_url"].as_str())
+            .with_context(|| format!("release {version} has no {name}"))?;
+        download(url)
+    };
+    let checksums = String::from_utf8(asset(CHECKSUMS_ASSET)?).context("SHA256SUMS is not text")?;
+    let signature = String::from_utf8(asset(SIGNATURE_ASSET)?).context("SHA256SUMS.sig is not text")?;
+    let downloads = BINARIES
+        .into_iter()
+        .map(|binary| {
+            // Assets carry the platform; they are installed under the plain file name.
+            let asset_name = format!("{binary}-{OS}-{ARCH}{EXE_SUFFIX}");
+            Ok(update::Download {
+                bytes: asset(&asset_name)?,
+                asset: asset_name,
+                install_as: format!("{binary}{EXE_SUFFIX}"),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    update::stage(staging_dir, version, &downloads, &checksums, &signature, public_key)?;
+    Ok(Some(version.to_owned()))
+}
+
+fn download(url: &str) -> Result<Vec<u8>> {
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--max-time", "300"])
+        .args(["--header", "Accept: application/vnd.github+json"])
+        .args(["--user-agent", concat!("LimeTrace/", env!("CARGO_PKG_VERSION"))])
+        .arg(url)
+        .output()
+        .context("failed to run curl")?;
+    if !output.status.success() {
+        bail!("download failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(output.stdout)
+}
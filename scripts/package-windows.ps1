@@ -2,7 +2,10 @@ param(
     [Parameter(Mandatory = $false)]
     [string]$BuildDir = "target/release",
     [Parameter(Mandatory = $false)]
-    [string]$OutDir = "dist/windows"
+    [string]$OutDir = "dist/windows",
+    # Ed25519 private key (PEM) for the auto-update signature; needs OpenSSL 3.
+    [Parameter(Mandatory = $false)]
+    [string]$SigningKey = ""
 )
 
 $ErrorActionPreference = "Stop"
@@ -60,6 +63,42 @@ if (Test-Path $zipPath) {
 }
 Compress-Archive -Path (Join-Path $packageDir "*") -DestinationPath $zipPath -Force
 
+# Release assets for the auto-update: platform-qualified binaries and their SHA256SUMS.
+# The signature covers "limetrace <version>" on a first line followed by SHA256SUMS,
+# made with the key whose public half the release build embeds
+# (LIMETRACE_UPDATE_PUBLIC_KEY).
+$updateDir = Join-Path $outRoot "update"
+if (Test-Path $updateDir) {
+    Remove-Item -Path $updateDir -Recurse -Force
+}
+New-Item -ItemType Directory -Path $updateDir -Force | Out-Null
+$checksums = foreach ($binary in @("limetrace", "limetrace-backend")) {
+    $asset = "$binary-windows-x86_64.exe"
+    $assetPath = Join-Path $updateDir $asset
+    Copy-Item (Join-Path $buildRoot "$binary.exe") $assetPath -Force
+    "$((Get-FileHash $assetPath -Algorithm SHA256).Hash.ToLower())  $asset"
+}
+$checksumsPath = Join-Path $updateDir "SHA256SUMS"
+[System.IO.File]::WriteAllText($checksumsPath, (($checksums -join "`n") + "`n"))
+if ($SigningKey) {
+    $manifest = Get-Content -Path "crates/limetrace/Cargo.toml" -Raw
+    if ($manifest -notmatch '(?m)^version = "([^"]+)"') {
+        throw "No version in crates/limetrace/Cargo.toml"
+    }
+    $messagePath = Join-Path $updateDir "SHA256SUMS.msg"
+    [System.IO.File]::WriteAllText($messagePath, "limetrace $($Matches[1])`n" + [System.IO.File]::ReadAllText($checksumsPath))
+    $signaturePath = Join-Path $updateDir "SHA256SUMS.bin"
+    & openssl pkeyutl -sign -inkey $SigningKey -rawin -in $messagePath -out $signaturePath
+    if ($LASTEXITCODE -ne 0) {
+        throw "openssl failed to sign $checksumsPath"
+    }
+    Remove-Item $messagePath -Force
+    $hex = -join ([System.IO.File]::ReadAllBytes($signaturePath) | ForEach-Object { $_.ToString("x2") })
+    Set-Content -Path (Join-Path $updateDir "SHA256SUMS.sig") -Value $hex -Encoding ASCII -NoNewline
+    Remove-Item $signaturePath -Force
+}
+
 Write-Host "Package created:"
 Write-Host "  Folder: $packageDir"
 Write-Host "  Zip:    $zipPath"
+Write-Host "  Update: $updateDir"
@@ -0,0 +1,44 @@
+param(
+    [Parameter(Mandatory = $false)]
+    [string]$SourceFont = "",
+    [Parameter(Mandatory = $false)]
+    [string]$OutDir = "crates/limetrace/assets/fonts"
+)
+
+# Builds the CJK fallback font the viewer embeds. Needs Python with fonttools
+# (`pip install fonttools`).
+
+$ErrorActionPreference = "Stop"
+
+$notoBase = "https://github.com/notofonts/noto-cjk/raw/main/Sans"
+$workDir = Join-Path ([System.IO.Path]::GetTempPath()) "limetrace-font-subset"
+New-Item -ItemType Directory -Path $workDir -Force | Out-Null
+New-Item -ItemType Directory -Path $OutDir -Force | Out-Null
+
+if (-not $SourceFont) {
+    $SourceFont = Join-Path $workDir "NotoSansSC-Regular.otf"
+    Invoke-WebRequest -Uri "$notoBase/SubsetOTF/SC/NotoSansSC-Regular.otf" -OutFile $SourceFont
+}
+Invoke-WebRequest -Uri "https://github.com/notofonts/noto-cjk/raw/main/LICENSE" -OutFile (Join-Path $OutDir "OFL.txt")
+
+$textFile = Join-Path $workDir "subset.txt"
+$uiSource = Resolve-Path "crates/limetrace/src/main.rs"
+@"
+import re, sys
+chars = {chr(code) for code in range(0x20, 0x7F)}
+chars |= {chr(code) for code in range(0x3000, 0x3040)}
+chars |= {chr(code) for code in range(0xFF00, 0xFFF0)}
+for high in range(0xB0, 0xD8):
+    for low in range(0xA1, 0xFF):
+        try:
+            chars.add(bytes([high, low]).decode("gb2312"))
+        except UnicodeDecodeError:
+            pass
+source = open(sys.argv[1], encoding="utf-8-sig").read()
+chars |= {chr(int(code, 16)) for code in re.findall(r"\\u\{([0-9A-Fa-f]+)\}", source)}
+open(sys.argv[2], "w", encoding="utf-8").write("".join(sorted(chars)))
+"@ | python - $uiSource $textFile
+
+$outFont = Join-Path $OutDir "NotoSansSC-Subset.otf"
+python -m fontTools.subset $SourceFont "--text-file=$textFile" "--output-file=$outFont" --layout-features="*" --no-hinting
+Write-Host "Wrote $outFont ($([math]::Round((Get-Item $outFont).Length / 1KB)) KB)"